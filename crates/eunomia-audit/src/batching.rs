@@ -0,0 +1,597 @@
+//! Batching backend for high-throughput event logging.
+//!
+//! [`BatchingBackend`] wraps another [`LoggerBackend`] and only queues
+//! events on [`log`](LoggerBackend::log), so logging an authorization
+//! decision per request does not wait on the inner backend. A background
+//! thread writes queued events to the inner backend once
+//! [`BatchingConfig::batch_size`] of them are pending, or every
+//! [`BatchingConfig::flush_interval`] otherwise.
+//!
+//! The queue holds at most [`BatchingConfig::capacity`] events; what
+//! happens to events logged while it is full is set by its
+//! [`OverflowPolicy`]. Call [`flush`](LoggerBackend::flush) or
+//! [`BatchingBackend::shutdown`] before the process exits so queued events
+//! are not lost.
+
+use crate::logger::{EventInfo, LoggerBackend, LoggerError};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{error, warn};
+
+/// What [`BatchingBackend::log`](LoggerBackend::log) and
+/// [`AuditLogger::log_async`](crate::AuditLogger::log_async) do with an
+/// event while their queue is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Wait until the background thread has made room. No events are lost,
+    /// but logging slows down to the pace of the inner backend.
+    #[default]
+    Block,
+    /// Drop the oldest queued event to make room.
+    DropOldest,
+    /// Reject the event with [`LoggerError::BufferFull`].
+    Error,
+}
+
+/// Settings of a [`BatchingBackend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchingConfig {
+    /// Number of pending events that triggers a write to the inner backend.
+    pub batch_size: usize,
+    /// Longest time an event stays queued before it is written.
+    pub flush_interval: Duration,
+    /// Maximum number of queued events.
+    pub capacity: usize,
+    /// What to do with events logged while the queue is full.
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for BatchingConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            flush_interval: Duration::from_secs(1),
+            capacity: 10_000,
+            overflow: OverflowPolicy::Block,
+        }
+    }
+}
+
+impl BatchingConfig {
+    /// Sets the number of pending events that triggers a write (at least
+    /// one).
+    #[must_use]
+    pub const fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = if batch_size == 0 { 1 } else { batch_size };
+        self
+    }
+
+    /// Sets the longest time an event stays queued.
+    #[must_use]
+    pub const fn with_flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Sets the maximum number of queued events (at least one).
+    #[must_use]
+    pub const fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = if capacity == 0 { 1 } else { capacity };
+        self
+    }
+
+    /// Sets what to do with events logged while the queue is full.
+    #[must_use]
+    pub const fn with_overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// A queued event; events logged without metadata are passed on with
+/// [`LoggerBackend::log`].
+type Queued = (Option<EventInfo>, String);
+
+/// Queue shared with the background writer.
+#[derive(Debug)]
+pub struct Queue<T = Queued> {
+    pub events: VecDeque<T>,
+    /// Events ever queued.
+    pub queued: u64,
+    /// Events written to the inner backend or dropped.
+    pub done: u64,
+    /// Whether a flush is waiting for the queue to be written.
+    flush_requested: bool,
+    pub shutdown: bool,
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self {
+            events: VecDeque::new(),
+            queued: 0,
+            done: 0,
+            flush_requested: false,
+            shutdown: false,
+        }
+    }
+}
+
+impl<T> Queue<T> {
+    /// Applies `overflow` while the queue holds `capacity` events,
+    /// returning `false` if the caller has to wait for room.
+    pub fn make_room(
+        &mut self,
+        capacity: usize,
+        overflow: OverflowPolicy,
+        dropped: &AtomicU64,
+    ) -> Result<bool, LoggerError> {
+        while self.events.len() >= capacity {
+            match overflow {
+                OverflowPolicy::Block => return Ok(false),
+                OverflowPolicy::DropOldest => {
+                    self.events.pop_front();
+                    self.done += 1;
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                OverflowPolicy::Error => {
+                    dropped.fetch_add(1, Ordering::Relaxed);
+                    return Err(LoggerError::BufferFull { capacity });
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Appends an event.
+    pub fn push(&mut self, event: T) {
+        self.events.push_back(event);
+        self.queued += 1;
+    }
+
+    /// Returns whether queued events have not been written yet.
+    pub const fn has_pending(&self) -> bool {
+        self.done < self.queued
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    config: BatchingConfig,
+    inner: Arc<dyn LoggerBackend>,
+    queue: Mutex<Queue>,
+    /// Signals the background thread that a batch is ready.
+    ready: Condvar,
+    /// Signals loggers blocked on a full queue and flushes that events
+    /// were written.
+    written: Condvar,
+    dropped: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Writes queued events to the inner backend until shut down.
+    fn run(&self) {
+        let mut queue = self.lock();
+        loop {
+            let deadline = Instant::now() + self.config.flush_interval;
+            while queue.events.len() < self.config.batch_size
+                && !queue.flush_requested
+                && !queue.shutdown
+            {
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                queue = self
+                    .ready
+                    .wait_timeout(queue, deadline - now)
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .0;
+            }
+
+            queue.flush_requested = false;
+            while !queue.events.is_empty() {
+                let count = queue.events.len().min(self.config.batch_size);
+                let batch: Vec<Queued> = queue.events.drain(..count).collect();
+                self.written.notify_all();
+                drop(queue);
+
+                self.write(&batch);
+
+                queue = self.lock();
+                queue.done += batch.len() as u64;
+                self.written.notify_all();
+            }
+
+            if queue.shutdown {
+                return;
+            }
+        }
+    }
+
+    /// Writes a batch to the inner backend.
+    fn write(&self, batch: &[Queued]) {
+        for (info, event_json) in batch {
+            let result = info.as_ref().map_or_else(
+                || self.inner.log(event_json),
+                |info| self.inner.log_event(info, event_json),
+            );
+            if let Err(e) = result {
+                self.failed.fetch_add(1, Ordering::Relaxed);
+                error!(backend = self.inner.name(), error = %e, "Failed to write audit event");
+            }
+        }
+        if let Err(e) = self.inner.flush() {
+            error!(backend = self.inner.name(), error = %e, "Failed to flush audit backend");
+        }
+    }
+
+    fn enqueue(&self, event: Queued) -> Result<(), LoggerError> {
+        let mut queue = self.lock();
+        while !queue.shutdown
+            && !queue.make_room(self.config.capacity, self.config.overflow, &self.dropped)?
+        {
+            queue = self
+                .written
+                .wait(queue)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        if queue.shutdown {
+            return Err(LoggerError::Backend(
+                "batching backend is shut down".to_string(),
+            ));
+        }
+
+        queue.push(event);
+        let batch_ready = queue.events.len() == self.config.batch_size;
+        drop(queue);
+        if batch_ready {
+            self.ready.notify_one();
+        }
+        Ok(())
+    }
+
+    /// Waits until every event queued so far has been written or dropped.
+    fn wait_written(&self) {
+        let mut queue = self.lock();
+        let target = queue.queued;
+        if queue.has_pending() {
+            queue.flush_requested = true;
+            self.ready.notify_one();
+        }
+        while queue.done < target {
+            queue = self
+                .written
+                .wait(queue)
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+        }
+        drop(queue);
+    }
+}
+
+/// Backend that queues events and writes them to an inner backend in
+/// batches from a background thread.
+///
+/// # Example
+///
+/// ```rust
+/// use eunomia_audit::{AuditLogger, BatchingBackend, InMemoryBackend, LoggerBackend, PolicyEvent};
+/// use std::sync::Arc;
+///
+/// let inner = Arc::new(InMemoryBackend::new());
+/// let batching = Arc::new(BatchingBackend::new(inner.clone()));
+/// let logger = AuditLogger::builder()
+///     .with_backend(batching.clone())
+///     .build();
+///
+/// logger
+///     .log(&PolicyEvent::created("users-service", "1.0.0", "user@example.com"))
+///     .unwrap();
+///
+/// batching.shutdown().unwrap();
+/// assert_eq!(inner.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct BatchingBackend {
+    shared: Arc<Shared>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl BatchingBackend {
+    /// Creates a batching backend with the default [`BatchingConfig`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the background thread cannot be spawned.
+    #[must_use]
+    pub fn new(inner: Arc<dyn LoggerBackend>) -> Self {
+        Self::with_config(inner, BatchingConfig::default())
+    }
+
+    /// Creates a batching backend with the given settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the background thread cannot be spawned.
+    #[must_use]
+    pub fn with_config(inner: Arc<dyn LoggerBackend>, config: BatchingConfig) -> Self {
+        let config = config
+            .with_batch_size(config.batch_size)
+            .with_capacity(config.capacity);
+        let shared = Arc::new(Shared {
+            config,
+            inner,
+            queue: Mutex::new(Queue::default()),
+            ready: Condvar::new(),
+            written: Condvar::new(),
+            dropped: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+        });
+        let worker = {
+            let shared = Arc::clone(&shared);
+            std::thread::Builder::new()
+                .name("eunomia-audit-batch".to_string())
+                .spawn(move || shared.run())
+                .expect("failed to spawn audit batching thread")
+        };
+        Self {
+            shared,
+            worker: Mutex::new(Some(worker)),
+        }
+    }
+
+    /// Returns the settings of this backend.
+    #[must_use]
+    pub fn config(&self) -> &BatchingConfig {
+        &self.shared.config
+    }
+
+    /// Returns the number of events waiting to be written.
+    #[must_use]
+    pub fn pending(&self) -> usize {
+        self.shared.lock().events.len()
+    }
+
+    /// Returns the number of events dropped or rejected because the queue
+    /// was full.
+    #[must_use]
+    pub fn dropped_events(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of events the inner backend failed to write.
+    #[must_use]
+    pub fn failed_events(&self) -> u64 {
+        self.shared.failed.load(Ordering::Relaxed)
+    }
+
+    /// Writes all queued events, stops the background thread and flushes
+    /// the inner backend.
+    ///
+    /// Events logged afterwards are rejected. Calling this more than once
+    /// is harmless.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the inner backend fails to flush.
+    pub fn shutdown(&self) -> Result<(), LoggerError> {
+        self.shared.lock().shutdown = true;
+        self.shared.ready.notify_one();
+        self.shared.written.notify_all();
+        let worker = self
+            .worker
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        if let Some(worker) = worker {
+            if worker.join().is_err() {
+                warn!("Audit batching thread panicked");
+            }
+        }
+        self.shared.inner.flush()
+    }
+}
+
+impl Drop for BatchingBackend {
+    fn drop(&mut self) {
+        if let Err(e) = self.shutdown() {
+            error!(error = %e, "Failed to flush audit events on drop");
+        }
+    }
+}
+
+impl LoggerBackend for BatchingBackend {
+    fn log(&self, event_json: &str) -> Result<(), LoggerError> {
+        self.shared.enqueue((None, event_json.to_string()))
+    }
+
+    fn log_event(&self, info: &EventInfo, event_json: &str) -> Result<(), LoggerError> {
+        self.shared
+            .enqueue((Some(info.clone()), event_json.to_string()))
+    }
+
+    /// Waits until every event logged so far has been written, then flushes
+    /// the inner backend.
+    fn flush(&self) -> Result<(), LoggerError> {
+        self.shared.wait_written();
+        self.shared.inner.flush()
+    }
+
+    fn name(&self) -> &'static str {
+        "batching"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AuthorizationEvent;
+    use crate::logger::InMemoryBackend;
+
+    /// Backend that takes a while to write each event.
+    #[derive(Debug, Default)]
+    struct SlowBackend {
+        inner: InMemoryBackend,
+    }
+
+    impl LoggerBackend for SlowBackend {
+        fn log(&self, event_json: &str) -> Result<(), LoggerError> {
+            std::thread::sleep(Duration::from_millis(1));
+            self.inner.log(event_json)
+        }
+
+        fn flush(&self) -> Result<(), LoggerError> {
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+    }
+
+    fn event(n: usize) -> (EventInfo, String) {
+        let event = AuthorizationEvent::allowed("users-service", &format!("op-{n}"), "user");
+        (
+            EventInfo::of(&event),
+            serde_json::to_string(&event).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_flush_writes_pending_events() {
+        let inner = Arc::new(InMemoryBackend::new());
+        let config = BatchingConfig::default()
+            .with_batch_size(10)
+            .with_flush_interval(Duration::from_secs(30));
+        let backend = BatchingBackend::with_config(inner.clone(), config);
+
+        for n in 0..25 {
+            let (info, json) = event(n);
+            backend.log_event(&info, &json).unwrap();
+        }
+        backend.flush().unwrap();
+
+        assert_eq!(inner.len(), 25);
+        assert_eq!(backend.pending(), 0);
+        let first = &inner.records()[0];
+        assert_eq!(first.info.event_type, "authorization.allowed");
+        assert_eq!(first.event["operation_id"], "op-0");
+    }
+
+    #[test]
+    fn test_flush_interval_writes_partial_batch() {
+        let inner = Arc::new(InMemoryBackend::new());
+        let config = BatchingConfig::default().with_flush_interval(Duration::from_millis(20));
+        let backend = BatchingBackend::with_config(inner.clone(), config);
+
+        let (_, json) = event(0);
+        backend.log(&json).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while inner.is_empty() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_eq!(inner.len(), 1);
+    }
+
+    #[test]
+    fn test_shutdown_writes_pending_events() {
+        let inner = Arc::new(InMemoryBackend::new());
+        let backend = BatchingBackend::new(inner.clone());
+
+        for n in 0..3 {
+            let (info, json) = event(n);
+            backend.log_event(&info, &json).unwrap();
+        }
+        backend.shutdown().unwrap();
+        backend.shutdown().unwrap();
+
+        assert_eq!(inner.len(), 3);
+        let (info, json) = event(3);
+        assert!(backend.log_event(&info, &json).is_err());
+    }
+
+    #[test]
+    fn test_overflow_policies() {
+        let slow = || Arc::new(SlowBackend::default());
+        let config = BatchingConfig::default()
+            .with_batch_size(1)
+            .with_capacity(2);
+
+        let inner = slow();
+        let backend = BatchingBackend::with_config(
+            inner.clone(),
+            config.with_overflow(OverflowPolicy::Error),
+        );
+        let results: Vec<_> = (0..20).map(|n| backend.log(&event(n).1)).collect();
+        assert!(results
+            .iter()
+            .any(|r| matches!(r, Err(LoggerError::BufferFull { capacity: 2 }))));
+        let rejected = results.iter().filter(|r| r.is_err()).count() as u64;
+        assert_eq!(backend.dropped_events(), rejected);
+        backend.flush().unwrap();
+        assert_eq!(inner.inner.len() as u64, 20 - rejected);
+
+        let inner = slow();
+        let backend = BatchingBackend::with_config(
+            inner.clone(),
+            config.with_overflow(OverflowPolicy::DropOldest),
+        );
+        for n in 0..20 {
+            backend.log(&event(n).1).unwrap();
+        }
+        backend.flush().unwrap();
+        assert!(backend.dropped_events() > 0);
+        assert_eq!(inner.inner.len() as u64, 20 - backend.dropped_events());
+        let last = inner.inner.records().pop().unwrap();
+        assert_eq!(last.event["operation_id"], "op-19");
+
+        let inner = slow();
+        let backend = BatchingBackend::with_config(inner.clone(), config);
+        for n in 0..20 {
+            backend.log(&event(n).1).unwrap();
+        }
+        backend.flush().unwrap();
+        assert_eq!(backend.dropped_events(), 0);
+        assert_eq!(inner.inner.len(), 20);
+    }
+
+    #[test]
+    fn test_log_does_not_wait_for_slow_backend() {
+        const EVENTS: usize = 1_000;
+
+        let inner = Arc::new(SlowBackend::default());
+        let config = BatchingConfig::default().with_batch_size(500);
+        let backend = BatchingBackend::with_config(inner.clone(), config);
+        let events: Vec<_> = (0..EVENTS).map(event).collect();
+
+        let mut latencies: Vec<Duration> = events
+            .iter()
+            .map(|(info, json)| {
+                let start = Instant::now();
+                backend.log_event(info, json).unwrap();
+                start.elapsed()
+            })
+            .collect();
+        latencies.sort();
+
+        // The inner backend takes a millisecond per event; logging only
+        // queues them.
+        let median = latencies[EVENTS / 2];
+        assert!(
+            median < Duration::from_micros(1),
+            "median log() took {median:?}"
+        );
+        assert!(inner.inner.len() < EVENTS);
+    }
+}
@@ -0,0 +1,230 @@
+//! Channel for [`AuditLogger::log_async`](crate::AuditLogger::log_async).
+//!
+//! Events are serialized by the caller and queued in a bounded [`Queue`],
+//! the one [`BatchingBackend`](crate::BatchingBackend) uses; a writer task
+//! spawned when the logger is built hands them to the logger's backends on
+//! the blocking thread pool, so callers never wait for a backend write.
+
+use std::pin::pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use tokio::runtime::Handle;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+use crate::batching::{OverflowPolicy, Queue};
+use crate::logger::{dispatch, EventInfo, LoggerBackend, LoggerError};
+
+/// Default number of events the channel holds.
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 10_000;
+
+/// Share of the capacity above which the channel counts as nearly full.
+const NEARLY_FULL_PERCENT: usize = 80;
+
+/// Minimum time between two warnings about a nearly full channel.
+const WARNING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What [`AuditLogger::log_async`](crate::AuditLogger::log_async) does with
+/// an event when the channel is full.
+///
+/// Events dropped or rejected are counted in
+/// [`AuditLogger::dropped_events`](crate::AuditLogger::dropped_events).
+pub type ChannelFullPolicy = OverflowPolicy;
+
+/// A queued event.
+type Event = (EventInfo, String);
+
+/// State shared with the writer task.
+#[derive(Debug)]
+struct Shared {
+    capacity: usize,
+    on_full: ChannelFullPolicy,
+    queue: Mutex<Queue<Event>>,
+    /// Wakes the writer once events are queued or the channel is closed.
+    ready: Notify,
+    /// Wakes senders waiting for room and flushes once events were taken.
+    written: Notify,
+    dropped: AtomicU64,
+    last_warning: Mutex<Option<Instant>>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Queue<Event>> {
+        self.queue
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Writes queued events to `backends` until the channel is closed and
+    /// drained.
+    async fn run(self: Arc<Self>, backends: Arc<[Arc<dyn LoggerBackend>]>) {
+        loop {
+            let (batch, closed) = {
+                let mut queue = self.lock();
+                (queue.events.drain(..).collect::<Vec<_>>(), queue.shutdown)
+            };
+            if batch.is_empty() {
+                if closed {
+                    return;
+                }
+                self.ready.notified().await;
+                continue;
+            }
+            self.written.notify_waiters();
+
+            let count = batch.len() as u64;
+            let backends = Arc::clone(&backends);
+            let written = tokio::task::spawn_blocking(move || {
+                for (info, json) in &batch {
+                    dispatch(&backends, info, json);
+                }
+            })
+            .await;
+            if let Err(e) = written {
+                error!(error = %e, "Audit channel writer failed");
+            }
+
+            self.lock().done += count;
+            self.written.notify_waiters();
+        }
+    }
+}
+
+/// Sending side of the channel to a writer task.
+#[derive(Debug)]
+pub struct EventChannel {
+    shared: Arc<Shared>,
+    writer: JoinHandle<()>,
+}
+
+impl EventChannel {
+    /// Spawns a writer task dispatching events to `backends` on the current
+    /// Tokio runtime, or returns `None` outside of one.
+    ///
+    /// The task exits once the channel is dropped and drained.
+    pub fn start(
+        backends: &[Arc<dyn LoggerBackend>],
+        capacity: usize,
+        on_full: ChannelFullPolicy,
+    ) -> Option<Self> {
+        let runtime = Handle::try_current().ok()?;
+        let shared = Arc::new(Shared {
+            capacity: capacity.max(1),
+            on_full,
+            queue: Mutex::new(Queue::default()),
+            ready: Notify::new(),
+            written: Notify::new(),
+            dropped: AtomicU64::new(0),
+            last_warning: Mutex::new(None),
+        });
+        let writer = runtime.spawn(Arc::clone(&shared).run(backends.into()));
+        Some(Self { shared, writer })
+    }
+
+    /// Queues an event, applying the full-channel policy.
+    pub async fn send(&self, info: EventInfo, json: String) -> Result<(), LoggerError> {
+        let mut event = (info, json);
+        loop {
+            let mut written = pin!(self.shared.written.notified());
+            written.as_mut().enable();
+            match self.try_send(event)? {
+                Some(rejected) => event = rejected,
+                None => break,
+            }
+            written.await;
+        }
+        self.shared.ready.notify_one();
+
+        self.warn_if_nearly_full();
+        Ok(())
+    }
+
+    /// Queues an event if there is room for it, returning it if it has to
+    /// wait for room instead.
+    fn try_send(&self, event: Event) -> Result<Option<Event>, LoggerError> {
+        let shared = &self.shared;
+        let mut queue = shared.lock();
+        if queue.shutdown || self.writer.is_finished() {
+            return Err(stopped());
+        }
+        if !queue.make_room(shared.capacity, shared.on_full, &shared.dropped)? {
+            return Ok(Some(event));
+        }
+        queue.push(event);
+        drop(queue);
+        Ok(None)
+    }
+
+    /// Queues an event behind the events still being written, regardless
+    /// of the capacity, or returns it if there are none.
+    pub fn defer(&self, info: EventInfo, json: String) -> Option<Event> {
+        let mut queue = self.shared.lock();
+        if !queue.has_pending() {
+            return Some((info, json));
+        }
+        queue.push((info, json));
+        drop(queue);
+        self.shared.ready.notify_one();
+        None
+    }
+
+    /// Waits until every event queued so far was handed to the backends.
+    pub async fn flush(&self) -> Result<(), LoggerError> {
+        let shared = &self.shared;
+        let target = shared.lock().queued;
+        loop {
+            let mut written = pin!(shared.written.notified());
+            written.as_mut().enable();
+            if shared.lock().done >= target {
+                return Ok(());
+            }
+            if self.writer.is_finished() {
+                return Err(stopped());
+            }
+            written.await;
+        }
+    }
+
+    /// Returns the number of events dropped or rejected because the channel
+    /// was full.
+    pub fn dropped_events(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Warns, at most once per [`WARNING_INTERVAL`], while the channel is
+    /// more than [`NEARLY_FULL_PERCENT`] full.
+    fn warn_if_nearly_full(&self) {
+        let capacity = self.shared.capacity;
+        let queued = self.shared.lock().events.len();
+        if queued * 100 <= capacity * NEARLY_FULL_PERCENT {
+            return;
+        }
+
+        let mut last_warning = self
+            .shared
+            .last_warning
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if last_warning.is_some_and(|at| at.elapsed() < WARNING_INTERVAL) {
+            return;
+        }
+        *last_warning = Some(Instant::now());
+        drop(last_warning);
+        warn!(queued, capacity, "Audit event channel nearly full");
+    }
+}
+
+impl Drop for EventChannel {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.ready.notify_one();
+    }
+}
+
+/// Error for a writer task that is no longer running.
+fn stopped() -> LoggerError {
+    LoggerError::Backend("audit channel writer stopped".to_string())
+}
@@ -452,6 +452,10 @@ pub struct DistributionEvent {
 
     /// Correlation ID for tracing
     pub correlation_id: Option<String>,
+
+    /// IDs of the keys whose signatures on the bundle were verified
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signed_by: Vec<String>,
 }
 
 /// Types of distribution events.
@@ -493,6 +497,7 @@ impl DistributionEvent {
             outcome: EventOutcome::InProgress,
             details: None,
             correlation_id: None,
+            signed_by: Vec::new(),
         }
     }
 
@@ -522,6 +527,26 @@ impl DistributionEvent {
             outcome,
             details: Some(format!("{successful} successful, {failed} failed")),
             correlation_id: None,
+            signed_by: Vec::new(),
+        }
+    }
+
+    /// Creates a new deployment failed event.
+    #[must_use]
+    pub fn deployment_failed(service: &str, version: &str, error: &str) -> Self {
+        Self {
+            id: new_event_id(),
+            timestamp: Utc::now(),
+            event_type: DistributionEventType::DeploymentFailed,
+            service: service.to_string(),
+            version: version.to_string(),
+            instance: None,
+            instance_count: None,
+            strategy: None,
+            outcome: EventOutcome::Failure,
+            details: Some(error.to_string()),
+            correlation_id: None,
+            signed_by: Vec::new(),
         }
     }
 
@@ -540,6 +565,7 @@ impl DistributionEvent {
             outcome: EventOutcome::InProgress,
             details: Some(format!("from={from_version}")),
             correlation_id: None,
+            signed_by: Vec::new(),
         }
     }
 
@@ -562,6 +588,7 @@ impl DistributionEvent {
             },
             details: None,
             correlation_id: None,
+            signed_by: Vec::new(),
         }
     }
 
@@ -571,6 +598,13 @@ impl DistributionEvent {
         self.correlation_id = Some(id.to_string());
         self
     }
+
+    /// Records the key IDs whose signatures on the bundle were verified.
+    #[must_use]
+    pub fn with_signed_by(mut self, key_ids: &[String]) -> Self {
+        self.signed_by = key_ids.to_vec();
+        self
+    }
 }
 
 impl AuditEvent for DistributionEvent {
@@ -637,6 +671,10 @@ pub struct AuthorizationEvent {
 
     /// Correlation ID for tracing
     pub correlation_id: Option<String>,
+
+    /// Policy input the decision was made on, kept for decision log replay
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<serde_json::Value>,
 }
 
 impl AuthorizationEvent {
@@ -655,6 +693,7 @@ impl AuthorizationEvent {
             policy_version: None,
             evaluation_time_ns: None,
             correlation_id: None,
+            input: None,
         }
     }
 
@@ -673,6 +712,7 @@ impl AuthorizationEvent {
             policy_version: None,
             evaluation_time_ns: None,
             correlation_id: None,
+            input: None,
         }
     }
 
@@ -703,6 +743,13 @@ impl AuthorizationEvent {
         self.correlation_id = Some(id.to_string());
         self
     }
+
+    /// Records the policy input the decision was made on.
+    #[must_use]
+    pub fn with_input(mut self, input: serde_json::Value) -> Self {
+        self.input = Some(input);
+        self
+    }
 }
 
 impl AuditEvent for AuthorizationEvent {
@@ -781,6 +828,25 @@ mod tests {
         assert_eq!(event.event_type, DistributionEventType::DeploymentStarted);
         assert_eq!(event.instance_count, Some(3));
         assert_eq!(event.strategy, Some("immediate".to_string()));
+
+        let event = DistributionEvent::deployment_failed("users-service", "1.0.0", "hook failed");
+        assert_eq!(event.event_type, DistributionEventType::DeploymentFailed);
+        assert_eq!(event.outcome, EventOutcome::Failure);
+        assert_eq!(event.details, Some("hook failed".to_string()));
+    }
+
+    #[test]
+    fn test_distribution_event_signed_by() {
+        let event = DistributionEvent::deployment_completed("users-service", "1.0.0", 2, 0);
+        let json = serde_json::to_value(&event).unwrap();
+        assert!(json.get("signed_by").is_none());
+
+        let event = event.with_signed_by(&["release-key".to_string()]);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["signed_by"], serde_json::json!(["release-key"]));
+        assert!(crate::schema::distribution_event_schema()
+            .validate(&json)
+            .is_ok());
     }
 
     #[test]
@@ -809,6 +875,18 @@ mod tests {
         assert_eq!(event.reason, Some("insufficient permissions".to_string()));
     }
 
+    #[test]
+    fn test_authorization_event_input_round_trip() {
+        let input = serde_json::json!({"caller": {"type": "anonymous"}});
+        let event = AuthorizationEvent::allowed("users-service", "getUser", "anonymous")
+            .with_input(input.clone());
+
+        let json = serde_json::to_string(&event).unwrap();
+        let parsed: AuthorizationEvent = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.input, Some(input));
+    }
+
     #[test]
     fn test_event_serialization() {
         let event = PolicyEvent::created("users-service", "1.0.0", "user@example.com");
@@ -10,8 +10,11 @@
 //!
 //! - Structured audit events with consistent schema
 //! - Multiple output backends (stdout, file, custom)
+//! - Batched, asynchronous writes for high-throughput decision logging
+//! - Non-blocking logging from async hot paths with [`AuditLogger::log_async`]
 //! - Correlation IDs for request tracing
 //! - Tamper-evident event signing (optional)
+//! - OpenTelemetry trace and span IDs on events (`eunomia-otel` feature)
 //!
 //! # Example
 //!
@@ -29,17 +32,30 @@
 //! logger.log(&event).unwrap();
 //! ```
 
+mod batching;
+mod channel;
 mod event;
 mod logger;
+mod migration;
+#[cfg(feature = "eunomia-otel")]
+mod otel;
 mod schema;
 
+pub use batching::{BatchingBackend, BatchingConfig, OverflowPolicy};
+pub use channel::ChannelFullPolicy;
 pub use event::{
     AuditEvent, AuthorizationEvent, BundleEvent, DistributionEvent, EventOutcome, EventSeverity,
     PolicyEvent,
 };
-pub use logger::{AuditLogger, InMemoryBackend, LoggerBackend, LoggerError, TracingBackend};
+pub use logger::{
+    AuditLogger, AuditRecord, EventInfo, InMemoryBackend, LoggerBackend, LoggerError,
+    TracingBackend,
+};
+pub use migration::{parse_event, upgrade_event, ParsedEvent};
+#[cfg(feature = "eunomia-otel")]
+pub use otel::AUDIT_SPAN;
 pub use schema::{
-    authorization_event_schema, bundle_event_schema, distribution_event_schema,
+    authorization_event_schema, builtin_schemas, bundle_event_schema, distribution_event_schema,
     policy_event_schema, AuditMetadata, EventSchema, FieldDefinition, FieldType,
     CURRENT_SCHEMA_VERSION,
 };
@@ -1,6 +1,13 @@
 //! Audit logger implementation.
 
-use crate::event::AuditEvent;
+use crate::channel::{ChannelFullPolicy, EventChannel, DEFAULT_CHANNEL_CAPACITY};
+use crate::event::{AuditEvent, EventSeverity};
+#[cfg(feature = "eunomia-otel")]
+use crate::otel::{self, OtelTracer, TraceIds};
+use crate::schema::{builtin_schemas, EventSchema, CURRENT_SCHEMA_VERSION};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
@@ -14,6 +21,20 @@ pub trait LoggerBackend: Send + Sync + Debug {
     /// Returns an error if the event cannot be logged.
     fn log(&self, event_json: &str) -> Result<(), LoggerError>;
 
+    /// Logs an audit event together with its metadata.
+    ///
+    /// [`AuditLogger`] calls this instead of [`log`](Self::log). The default
+    /// implementation ignores the metadata; backends that filter events
+    /// override it to avoid re-parsing the JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be logged.
+    fn log_event(&self, info: &EventInfo, event_json: &str) -> Result<(), LoggerError> {
+        let _ = info;
+        self.log(event_json)
+    }
+
     /// Flushes any buffered events.
     ///
     /// # Errors
@@ -39,6 +60,93 @@ pub enum LoggerError {
     /// Backend-specific error
     #[error("Backend error: {0}")]
     Backend(String),
+
+    /// Event rejected because a batching backend's queue is full
+    #[error("Audit event buffer full ({capacity} events)")]
+    BufferFull {
+        /// Maximum number of queued events.
+        capacity: usize,
+    },
+
+    /// Persisted event has a schema version that cannot be upgraded
+    #[error("Unsupported schema version: {0}")]
+    UnsupportedSchemaVersion(String),
+
+    /// Event does not conform to its schema
+    #[error("Schema violation in field '{field}': {reason}")]
+    SchemaViolation {
+        /// Offending field, empty if the event as a whole is invalid.
+        field: String,
+        /// Why the field does not conform.
+        reason: String,
+    },
+}
+
+/// Metadata of a logged audit event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventInfo {
+    /// Event type identifier (e.g. `policy.created`).
+    pub event_type: String,
+    /// Event severity.
+    pub severity: EventSeverity,
+    /// Event timestamp.
+    pub timestamp: DateTime<Utc>,
+    /// Correlation ID for request tracing.
+    pub correlation_id: Option<String>,
+}
+
+impl EventInfo {
+    /// Returns the metadata of an event.
+    #[must_use]
+    pub fn of<E: AuditEvent>(event: &E) -> Self {
+        Self {
+            event_type: event.event_type().to_string(),
+            severity: event.severity(),
+            timestamp: event.timestamp(),
+            correlation_id: event.correlation_id().map(str::to_string),
+        }
+    }
+
+    /// Reads what metadata a serialized event carries.
+    ///
+    /// Events only serialize their type's variant (e.g. `created`) and not
+    /// their severity, so this is a best effort for events logged without
+    /// [`EventInfo`]: the severity defaults to info and the timestamp to now.
+    fn from_json(value: &serde_json::Value) -> Self {
+        let field = |name: &str| value.get(name).and_then(serde_json::Value::as_str);
+        Self {
+            event_type: field("event_type").unwrap_or("unknown").to_string(),
+            severity: EventSeverity::Info,
+            timestamp: field("timestamp")
+                .and_then(|ts| ts.parse().ok())
+                .unwrap_or_else(Utc::now),
+            correlation_id: field("correlation_id").map(str::to_string),
+        }
+    }
+}
+
+/// A logged audit event with its metadata, as returned by
+/// [`InMemoryBackend`] queries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Event metadata.
+    #[serde(flatten)]
+    pub info: EventInfo,
+    /// The event as logged.
+    pub event: serde_json::Value,
+}
+
+/// Serialized form of a logged event: its fields, stamped with the schema
+/// version they were written with.
+#[derive(Serialize)]
+struct Envelope<'a, E> {
+    schema_version: &'static str,
+    #[serde(flatten)]
+    event: &'a E,
+    /// Trace context the event was logged in.
+    #[cfg(feature = "eunomia-otel")]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    trace: Option<TraceIds>,
 }
 
 /// Audit logger that sends events to configured backends.
@@ -52,6 +160,26 @@ pub struct AuditLogger {
 
     /// Log level threshold (events below this level are filtered)
     min_severity: crate::event::EventSeverity,
+
+    /// Whether events are validated against their schema before logging
+    strict_schemas: bool,
+
+    /// Schemas events are validated against, later ones taking precedence
+    schemas: Vec<EventSchema>,
+
+    /// Tracer events outside a trace context scope are recorded with
+    #[cfg(feature = "eunomia-otel")]
+    tracer: Option<OtelTracer>,
+
+    /// Number of events `log_async` queues before applying `on_full`
+    channel_capacity: usize,
+
+    /// What `log_async` does with events while the channel is full
+    on_full: ChannelFullPolicy,
+
+    /// Channel to the writer task of `log_async` events, started with the
+    /// logger if it was built in a Tokio runtime
+    channel: Option<EventChannel>,
 }
 
 impl Default for AuditLogger {
@@ -68,6 +196,13 @@ impl AuditLogger {
             backends: Vec::new(),
             enabled: true,
             min_severity: crate::event::EventSeverity::Info,
+            strict_schemas: false,
+            schemas: builtin_schemas(),
+            #[cfg(feature = "eunomia-otel")]
+            tracer: None,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            on_full: ChannelFullPolicy::Block,
+            channel: EventChannel::start(&[], DEFAULT_CHANNEL_CAPACITY, ChannelFullPolicy::Block),
         }
     }
 
@@ -80,6 +215,8 @@ impl AuditLogger {
     /// Adds a backend to the logger.
     pub fn add_backend(&mut self, backend: Arc<dyn LoggerBackend>) {
         self.backends.push(backend);
+        // The writer of the old channel drains it to the old backends
+        self.channel = EventChannel::start(&self.backends, self.channel_capacity, self.on_full);
     }
 
     /// Enables or disables the logger.
@@ -94,14 +231,99 @@ impl AuditLogger {
 
     /// Logs an audit event to all configured backends.
     ///
+    /// The event is serialized with a `schema_version` field holding
+    /// [`CURRENT_SCHEMA_VERSION`].
+    ///
+    /// In strict schema mode the event is first validated against the
+    /// registered schema for its event type, and not logged if it does not
+    /// conform.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the event cannot be serialized.
+    /// Returns an error if the event cannot be serialized, or in strict
+    /// schema mode if it violates its schema or no schema is registered for
+    /// its event type.
     /// Backend errors are logged but do not cause this method to fail.
+    ///
+    /// While events queued by [`log_async`](Self::log_async) are still being
+    /// written, the event is queued behind them instead of being written
+    /// directly, so backends receive events in the order they were logged.
     pub fn log<E: AuditEvent>(&self, event: &E) -> Result<(), LoggerError> {
+        let Some((info, json)) = self.serialize(event)? else {
+            return Ok(());
+        };
+        let event = match &self.channel {
+            Some(channel) => channel.defer(info, json),
+            None => Some((info, json)),
+        };
+        if let Some((info, json)) = event {
+            dispatch(&self.backends, &info, &json);
+        }
+        Ok(())
+    }
+
+    /// Logs an audit event without waiting for the backends.
+    ///
+    /// The event is filtered and serialized like [`log`](Self::log), then
+    /// queued in a bounded channel that a writer task, spawned on the Tokio
+    /// runtime the logger was built in, drains into the backends. While the
+    /// channel is full the event is handled as configured with the
+    /// builder's `on_full`; a warning is logged at most once per
+    /// second while the channel is more than 80% full. Use
+    /// [`flush_async`](Self::flush_async) to wait for queued events.
+    ///
+    /// A logger built outside a Tokio runtime has no writer task and writes
+    /// the event directly, like [`log`](Self::log).
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`log`](Self::log),
+    /// [`LoggerError::BufferFull`] if the channel is full under
+    /// [`ChannelFullPolicy::Error`], and an error if the runtime running the
+    /// writer task shut down.
+    pub async fn log_async<E: AuditEvent + Sync>(&self, event: &E) -> Result<(), LoggerError> {
+        let Some((info, json)) = self.serialize(event)? else {
+            return Ok(());
+        };
+        if let Some(channel) = &self.channel {
+            return channel.send(info, json).await;
+        }
+        dispatch(&self.backends, &info, &json);
+        Ok(())
+    }
+
+    /// Waits until every event queued by [`log_async`](Self::log_async) was
+    /// handed to the backends, then flushes them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the writer task stopped or any backend fails to
+    /// flush.
+    pub async fn flush_async(&self) -> Result<(), LoggerError> {
+        if let Some(channel) = &self.channel {
+            channel.flush().await?;
+        }
+        self.flush()
+    }
+
+    /// Returns the number of events [`log_async`](Self::log_async) dropped
+    /// or rejected because its channel was full.
+    #[must_use]
+    pub fn dropped_events(&self) -> u64 {
+        self.channel
+            .as_ref()
+            .map_or(0, EventChannel::dropped_events)
+    }
+
+    /// Returns the metadata and serialized form of an event, or `None` if
+    /// the logger filters it out.
+    fn serialize<E: AuditEvent>(
+        &self,
+        event: &E,
+    ) -> Result<Option<(EventInfo, String)>, LoggerError> {
         if !self.enabled {
             debug!("Audit logging disabled, skipping event");
-            return Ok(());
+            return Ok(None);
         }
 
         if !self.should_log_severity(event.severity()) {
@@ -110,18 +332,35 @@ impl AuditLogger {
                 event.severity(),
                 self.min_severity
             );
-            return Ok(());
+            return Ok(None);
         }
 
-        let json = serde_json::to_string(event)?;
-
-        for backend in &self.backends {
-            if let Err(e) = backend.log(&json) {
-                error!("Failed to log event to backend {}: {}", backend.name(), e);
-            }
+        let info = EventInfo::of(event);
+        if self.strict_schemas {
+            self.validate(&info.event_type, &serde_json::to_value(event)?)?;
         }
+        let json = serde_json::to_string(&Envelope {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            event,
+            #[cfg(feature = "eunomia-otel")]
+            trace: otel::current(self, self.tracer.as_ref(), &info.event_type),
+        })?;
+
+        Ok(Some((info, json)))
+    }
 
-        Ok(())
+    /// Runs `f`, stamping the events this logger logs on the current thread
+    /// meanwhile with the `trace_id` and `span_id` of `span`.
+    ///
+    /// Scopes nest; the innermost one applies. A span with an invalid
+    /// context, such as one from a no-op tracer, stamps nothing.
+    #[cfg(feature = "eunomia-otel")]
+    pub fn with_trace_context<R>(
+        &self,
+        span: &dyn opentelemetry::global::ObjectSafeSpan,
+        f: impl FnOnce() -> R,
+    ) -> R {
+        otel::scoped(self, span, f)
     }
 
     /// Flushes all backends.
@@ -136,6 +375,22 @@ impl AuditLogger {
         Ok(())
     }
 
+    /// Validates a serialized event against the schema for its type.
+    fn validate(&self, event_type: &str, event: &serde_json::Value) -> Result<(), LoggerError> {
+        let schema = self
+            .schemas
+            .iter()
+            .rev()
+            .find(|schema| schema.matches(event_type))
+            .ok_or_else(|| LoggerError::SchemaViolation {
+                field: "event_type".to_string(),
+                reason: format!("no schema registered for event type {event_type}"),
+            })?;
+        schema.validate(event).inspect_err(|e| {
+            warn!(event_type, schema = %schema.name, "Rejected audit event: {e}");
+        })
+    }
+
     /// Returns the number of configured backends.
     #[must_use]
     pub fn backend_count(&self) -> usize {
@@ -169,6 +424,12 @@ pub struct AuditLoggerBuilder {
     backends: Vec<Arc<dyn LoggerBackend>>,
     enabled: bool,
     min_severity: crate::event::EventSeverity,
+    strict_schemas: bool,
+    schemas: Vec<EventSchema>,
+    #[cfg(feature = "eunomia-otel")]
+    tracer: Option<OtelTracer>,
+    channel_capacity: usize,
+    on_full: ChannelFullPolicy,
 }
 
 impl AuditLoggerBuilder {
@@ -179,6 +440,12 @@ impl AuditLoggerBuilder {
             backends: Vec::new(),
             enabled: true,
             min_severity: crate::event::EventSeverity::Info,
+            strict_schemas: false,
+            schemas: builtin_schemas(),
+            #[cfg(feature = "eunomia-otel")]
+            tracer: None,
+            channel_capacity: DEFAULT_CHANNEL_CAPACITY,
+            on_full: ChannelFullPolicy::Block,
         }
     }
 
@@ -203,13 +470,82 @@ impl AuditLoggerBuilder {
         self
     }
 
+    /// Enables strict schema mode, validating every event against its
+    /// schema before it is dispatched to backends.
+    #[must_use]
+    pub const fn strict_schemas(mut self, strict: bool) -> Self {
+        self.strict_schemas = strict;
+        self
+    }
+
+    /// Registers a schema, taking precedence over the built-in schemas and
+    /// earlier registrations for the event types it matches.
+    #[must_use]
+    pub fn with_schema(mut self, schema: EventSchema) -> Self {
+        self.schemas.push(schema);
+        self
+    }
+
+    /// Records every event logged outside
+    /// [`AuditLogger::with_trace_context`] as an
+    /// [`AUDIT_SPAN`](crate::AUDIT_SPAN) span under the current OpenTelemetry
+    /// context, stamping the event with that span's IDs.
+    ///
+    /// Any [`Tracer`](opentelemetry::trace::Tracer) can be passed, such as
+    /// `Arc::new(opentelemetry::global::tracer("eunomia-audit"))`.
+    #[cfg(feature = "eunomia-otel")]
+    #[must_use]
+    pub fn with_otel_tracer(
+        mut self,
+        tracer: Arc<dyn opentelemetry::global::ObjectSafeTracer + Send + Sync>,
+    ) -> Self {
+        self.tracer = Some(OtelTracer(tracer));
+        self
+    }
+
+    /// Sets how many events [`AuditLogger::log_async`] queues (at least one,
+    /// 10 000 by default).
+    #[must_use]
+    pub const fn channel_capacity(mut self, capacity: usize) -> Self {
+        self.channel_capacity = capacity;
+        self
+    }
+
+    /// Sets what [`AuditLogger::log_async`] does with events while its
+    /// channel is full ([`ChannelFullPolicy::Block`] by default).
+    #[must_use]
+    pub const fn on_full(mut self, policy: ChannelFullPolicy) -> Self {
+        self.on_full = policy;
+        self
+    }
+
     /// Builds the audit logger.
+    ///
+    /// Inside a Tokio runtime this spawns the writer task of
+    /// [`AuditLogger::log_async`] on it.
     #[must_use]
     pub fn build(self) -> AuditLogger {
+        let channel = EventChannel::start(&self.backends, self.channel_capacity, self.on_full);
         AuditLogger {
             backends: self.backends,
             enabled: self.enabled,
             min_severity: self.min_severity,
+            strict_schemas: self.strict_schemas,
+            schemas: self.schemas,
+            #[cfg(feature = "eunomia-otel")]
+            tracer: self.tracer,
+            channel_capacity: self.channel_capacity,
+            on_full: self.on_full,
+            channel,
+        }
+    }
+}
+
+/// Hands a serialized event to every backend, logging backend errors.
+pub fn dispatch(backends: &[Arc<dyn LoggerBackend>], info: &EventInfo, json: &str) {
+    for backend in backends {
+        if let Err(e) = backend.log_event(info, json) {
+            error!("Failed to log event to backend {}: {}", backend.name(), e);
         }
     }
 }
@@ -254,10 +590,15 @@ impl LoggerBackend for TracingBackend {
     }
 }
 
-/// In-memory backend for testing.
+/// In-memory backend for tests and embedded use.
+///
+/// Events are kept with their [`EventInfo`] so they can be queried by type,
+/// time and correlation ID. With a capacity set, the oldest events are
+/// dropped once it is reached. The backend is safe to share across threads.
 #[derive(Debug)]
 pub struct InMemoryBackend {
-    events: std::sync::Mutex<Vec<String>>,
+    events: std::sync::Mutex<VecDeque<(EventInfo, String)>>,
+    capacity: Option<usize>,
 }
 
 impl Default for InMemoryBackend {
@@ -267,14 +608,32 @@ impl Default for InMemoryBackend {
 }
 
 impl InMemoryBackend {
-    /// Creates a new in-memory backend.
+    /// Creates a new in-memory backend without a capacity limit.
     #[must_use]
     pub const fn new() -> Self {
         Self {
-            events: std::sync::Mutex::new(Vec::new()),
+            events: std::sync::Mutex::new(VecDeque::new()),
+            capacity: None,
+        }
+    }
+
+    /// Creates an in-memory backend keeping at most `capacity` events (at
+    /// least one), dropping the oldest first.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            events: std::sync::Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: Some(capacity),
         }
     }
 
+    /// Returns the maximum number of events kept, if limited.
+    #[must_use]
+    pub const fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
     /// Returns all logged events.
     ///
     /// # Panics
@@ -282,7 +641,72 @@ impl InMemoryBackend {
     /// Panics if the mutex is poisoned.
     #[must_use]
     pub fn events(&self) -> Vec<String> {
-        self.events.lock().unwrap().clone()
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, json)| json.clone())
+            .collect()
+    }
+
+    /// Returns all logged events with their metadata, oldest first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn records(&self) -> Vec<AuditRecord> {
+        self.query(|_| true)
+    }
+
+    /// Returns the events of a type (e.g. `policy.created`), oldest first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn events_by_type(&self, event_type: &str) -> Vec<AuditRecord> {
+        self.query(|info| info.event_type == event_type)
+    }
+
+    /// Returns the events with a timestamp at or after `since`, oldest first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn events_since(&self, since: DateTime<Utc>) -> Vec<AuditRecord> {
+        self.query(|info| info.timestamp >= since)
+    }
+
+    /// Returns the events with a correlation ID, oldest first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn events_with_correlation_id(&self, correlation_id: &str) -> Vec<AuditRecord> {
+        self.query(|info| info.correlation_id.as_deref() == Some(correlation_id))
+    }
+
+    /// Returns the number of events kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.lock().unwrap().len()
+    }
+
+    /// Returns true if no events are kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is poisoned.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 
     /// Clears all logged events.
@@ -293,11 +717,43 @@ impl InMemoryBackend {
     pub fn clear(&self) {
         self.events.lock().unwrap().clear();
     }
+
+    /// Returns the events whose metadata matches `filter`, deserializing
+    /// only those.
+    fn query(&self, filter: impl Fn(&EventInfo) -> bool) -> Vec<AuditRecord> {
+        self.events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(info, _)| filter(info))
+            .map(|(info, json)| AuditRecord {
+                info: info.clone(),
+                event: serde_json::from_str(json)
+                    .unwrap_or_else(|_| serde_json::Value::String(json.clone())),
+            })
+            .collect()
+    }
+
+    fn push(&self, info: EventInfo, event_json: &str) {
+        let mut events = self.events.lock().unwrap();
+        if let Some(capacity) = self.capacity {
+            while events.len() >= capacity {
+                events.pop_front();
+            }
+        }
+        events.push_back((info, event_json.to_string()));
+    }
 }
 
 impl LoggerBackend for InMemoryBackend {
     fn log(&self, event_json: &str) -> Result<(), LoggerError> {
-        self.events.lock().unwrap().push(event_json.to_string());
+        let value: serde_json::Value = serde_json::from_str(event_json)?;
+        self.push(EventInfo::from_json(&value), event_json);
+        Ok(())
+    }
+
+    fn log_event(&self, info: &EventInfo, event_json: &str) -> Result<(), LoggerError> {
+        self.push(info.clone(), event_json);
         Ok(())
     }
 
@@ -313,7 +769,54 @@ impl LoggerBackend for InMemoryBackend {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::event::PolicyEvent;
+    use crate::event::{BundleEvent, PolicyEvent};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_strict_schemas_reject_nonconforming_events() {
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = AuditLogger::builder()
+            .with_backend(backend.clone())
+            .strict_schemas(true)
+            .build();
+
+        // Built-in events conform to the built-in schemas
+        logger
+            .log(&PolicyEvent::created(
+                "users-service",
+                "1.0.0",
+                "user@example.com",
+            ))
+            .unwrap();
+        logger
+            .log(&BundleEvent::compiled("users-service", "1.0.0"))
+            .unwrap();
+        assert_eq!(backend.len(), 2);
+
+        let schema = crate::schema::policy_event_schema().required(
+            "ticket",
+            crate::schema::FieldType::String,
+            "Change ticket",
+        );
+        let logger = AuditLogger::builder()
+            .with_backend(backend.clone())
+            .strict_schemas(true)
+            .with_schema(schema)
+            .build();
+
+        let err = logger
+            .log(&PolicyEvent::created(
+                "users-service",
+                "1.1.0",
+                "user@example.com",
+            ))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            LoggerError::SchemaViolation { ref field, .. } if field == "ticket"
+        ));
+        assert_eq!(backend.len(), 2);
+    }
 
     #[test]
     fn test_logger_with_in_memory_backend() {
@@ -326,6 +829,7 @@ mod tests {
         let events = backend.events();
         assert_eq!(events.len(), 1);
         assert!(events[0].contains("users-service"));
+        assert!(events[0].contains(&format!("\"schema_version\":\"{CURRENT_SCHEMA_VERSION}\"")));
     }
 
     #[test]
@@ -414,4 +918,219 @@ mod tests {
         backend.clear();
         assert!(backend.events().is_empty());
     }
+
+    #[test]
+    fn test_in_memory_backend_capacity_evicts_oldest() {
+        let backend = Arc::new(InMemoryBackend::with_capacity(2));
+        let logger = AuditLogger::builder().with_backend(backend.clone()).build();
+
+        for version in ["1.0.0", "1.1.0", "1.2.0"] {
+            logger
+                .log(&PolicyEvent::created(
+                    "users-service",
+                    version,
+                    "user@example.com",
+                ))
+                .unwrap();
+        }
+
+        assert_eq!(backend.capacity(), Some(2));
+        assert_eq!(backend.len(), 2);
+        let versions: Vec<_> = backend
+            .records()
+            .iter()
+            .map(|r| r.event["version"].clone())
+            .collect();
+        assert_eq!(versions, ["1.1.0", "1.2.0"]);
+    }
+
+    #[test]
+    fn test_in_memory_backend_queries() {
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = AuditLogger::builder().with_backend(backend.clone()).build();
+
+        let before = Utc::now() - chrono::Duration::seconds(1);
+        logger
+            .log(
+                &PolicyEvent::created("users-service", "1.0.0", "user@example.com")
+                    .with_correlation_id("req-1"),
+            )
+            .unwrap();
+        logger
+            .log(&PolicyEvent::updated(
+                "users-service",
+                "1.1.0",
+                "user@example.com",
+            ))
+            .unwrap();
+        logger
+            .log(&BundleEvent::compiled("users-service", "1.1.0").with_correlation_id("req-1"))
+            .unwrap();
+
+        let created = backend.events_by_type("policy.created");
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].info.severity, EventSeverity::Info);
+        assert_eq!(created[0].event["version"], "1.0.0");
+
+        let correlated = backend.events_with_correlation_id("req-1");
+        let types: Vec<_> = correlated
+            .iter()
+            .map(|r| r.info.event_type.as_str())
+            .collect();
+        assert_eq!(types, ["policy.created", "bundle.compiled"]);
+
+        assert_eq!(backend.events_since(before).len(), 3);
+        assert!(backend
+            .events_since(Utc::now() + chrono::Duration::seconds(60))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_in_memory_backend_concurrent_logging() {
+        let backend = Arc::new(InMemoryBackend::with_capacity(50));
+        let logger = Arc::new(AuditLogger::builder().with_backend(backend.clone()).build());
+
+        let handles: Vec<_> = (0..4)
+            .map(|thread| {
+                let logger = Arc::clone(&logger);
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        let event = PolicyEvent::created("svc", &format!("{thread}.{i}"), "ci");
+                        logger.log(&event).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(backend.len(), 50);
+        assert_eq!(backend.events_by_type("policy.created").len(), 50);
+    }
+    /// Backend that holds writes while its gate is closed.
+    #[derive(Debug, Default)]
+    struct GatedBackend {
+        closed: AtomicBool,
+        inner: InMemoryBackend,
+    }
+
+    impl GatedBackend {
+        fn closed() -> Arc<Self> {
+            let backend = Self::default();
+            backend.closed.store(true, Ordering::SeqCst);
+            Arc::new(backend)
+        }
+
+        fn open(&self) {
+            self.closed.store(false, Ordering::SeqCst);
+        }
+    }
+
+    impl LoggerBackend for GatedBackend {
+        fn log(&self, event_json: &str) -> Result<(), LoggerError> {
+            while self.closed.load(Ordering::SeqCst) {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+            self.inner.log(event_json)
+        }
+
+        fn flush(&self) -> Result<(), LoggerError> {
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "gated"
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_log_async_delivers_every_event() {
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = AuditLogger::builder().with_backend(backend.clone()).build();
+
+        for i in 0..50_000 {
+            let event = PolicyEvent::created("svc", &format!("1.0.{i}"), "ci");
+            logger.log_async(&event).await.unwrap();
+        }
+        logger.flush_async().await.unwrap();
+
+        assert_eq!(backend.len(), 50_000);
+        assert_eq!(logger.dropped_events(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_log_async_drop_policy_counts_dropped_events() {
+        let backend = GatedBackend::closed();
+        let logger = AuditLogger::builder()
+            .with_backend(backend.clone())
+            .channel_capacity(10)
+            .on_full(ChannelFullPolicy::DropOldest)
+            .build();
+
+        for i in 0..100 {
+            let event = PolicyEvent::created("svc", &format!("1.0.{i}"), "ci");
+            logger.log_async(&event).await.unwrap();
+        }
+        backend.open();
+        logger.flush_async().await.unwrap();
+
+        let dropped = logger.dropped_events();
+        assert!(dropped > 0);
+        assert_eq!(backend.inner.len() as u64 + dropped, 100);
+        let last = backend.inner.records().pop().unwrap();
+        assert_eq!(last.event["version"], "1.0.99");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_log_async_error_policy_rejects_when_full() {
+        let backend = GatedBackend::closed();
+        let logger = AuditLogger::builder()
+            .with_backend(backend.clone())
+            .channel_capacity(10)
+            .on_full(ChannelFullPolicy::Error)
+            .build();
+
+        let mut sent = 0;
+        let err = loop {
+            let event = PolicyEvent::created("svc", &format!("1.0.{sent}"), "ci");
+            match logger.log_async(&event).await {
+                Ok(()) => sent += 1,
+                Err(err) => break err,
+            }
+        };
+        assert!(matches!(err, LoggerError::BufferFull { capacity: 10 }));
+
+        backend.open();
+        logger.flush_async().await.unwrap();
+        assert_eq!(backend.inner.len(), sent);
+        assert_eq!(logger.dropped_events(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_log_keeps_order_with_log_async() {
+        let backend = GatedBackend::closed();
+        let logger = AuditLogger::builder().with_backend(backend.clone()).build();
+
+        for i in 0..3 {
+            let event = PolicyEvent::created("svc", &format!("1.0.{i}"), "ci");
+            logger.log_async(&event).await.unwrap();
+        }
+        logger
+            .log(&PolicyEvent::created("svc", "1.0.3", "ci"))
+            .unwrap();
+        backend.open();
+        logger.flush_async().await.unwrap();
+        logger
+            .log(&PolicyEvent::created("svc", "1.0.4", "ci"))
+            .unwrap();
+
+        let versions: Vec<_> = backend
+            .inner
+            .records()
+            .into_iter()
+            .map(|record| record.event["version"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(versions, ["1.0.0", "1.0.1", "1.0.2", "1.0.3", "1.0.4"]);
+    }
 }
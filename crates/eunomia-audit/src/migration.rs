@@ -0,0 +1,180 @@
+//! Migration of persisted audit events to the current schema version.
+//!
+//! [`AuditLogger`](crate::AuditLogger) stamps every event it serializes with
+//! the [`CURRENT_SCHEMA_VERSION`] it was written with. Events read back from
+//! older audit logs are brought up to date with [`upgrade_event`] and turned
+//! into typed events with [`parse_event`].
+//!
+//! # Schema versions
+//!
+//! - `1.0.0`: events carry no `schema_version` field.
+//! - `1.1.0`: events are stamped with `schema_version`.
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::event::{
+    AuditEvent, AuthorizationEvent, BundleEvent, BundleEventType, DistributionEvent,
+    DistributionEventType, PolicyEvent, PolicyEventType,
+};
+use crate::logger::LoggerError;
+use crate::schema::CURRENT_SCHEMA_VERSION;
+
+/// Schema version of events written without a `schema_version` field.
+const UNVERSIONED_SCHEMA_VERSION: &str = "1.0.0";
+
+/// A step migrating an event from one schema version to the next.
+struct Migration {
+    from: &'static str,
+    to: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// Migrations in version order, ending at [`CURRENT_SCHEMA_VERSION`].
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: "1.0.0",
+    to: "1.1.0",
+    // 1.1.0 only adds the `schema_version` stamp, set by `upgrade_event`
+    apply: |_| {},
+}];
+
+/// Migrates a serialized event to [`CURRENT_SCHEMA_VERSION`].
+///
+/// Events without a `schema_version` field are taken to be version `1.0.0`.
+/// The returned event is stamped with the current version.
+///
+/// # Errors
+///
+/// Returns [`LoggerError::UnsupportedSchemaVersion`] if the event's version
+/// is unknown or newer than the current one, and
+/// [`LoggerError::SchemaViolation`] if it is not a JSON object.
+pub fn upgrade_event(value: Value) -> Result<Value, LoggerError> {
+    let Value::Object(mut event) = value else {
+        return Err(LoggerError::SchemaViolation {
+            field: String::new(),
+            reason: "event is not a JSON object".to_string(),
+        });
+    };
+
+    let mut version = match event.get("schema_version") {
+        None => UNVERSIONED_SCHEMA_VERSION.to_string(),
+        Some(Value::String(version)) => version.clone(),
+        Some(other) => return Err(LoggerError::UnsupportedSchemaVersion(other.to_string())),
+    };
+
+    while version != CURRENT_SCHEMA_VERSION {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|migration| migration.from == version)
+            .ok_or_else(|| LoggerError::UnsupportedSchemaVersion(version.clone()))?;
+        (migration.apply)(&mut event);
+        version = migration.to.to_string();
+    }
+
+    event.insert("schema_version".to_string(), Value::String(version));
+    Ok(Value::Object(event))
+}
+
+/// A persisted audit event of any of the built-in event types.
+#[derive(Debug, Clone)]
+pub enum ParsedEvent {
+    /// Policy lifecycle event
+    Policy(PolicyEvent),
+    /// Bundle operation event
+    Bundle(BundleEvent),
+    /// Distribution event
+    Distribution(DistributionEvent),
+    /// Authorization decision event
+    Authorization(AuthorizationEvent),
+}
+
+impl ParsedEvent {
+    /// Returns the event type identifier (e.g. `policy.created`).
+    #[must_use]
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::Policy(event) => event.event_type(),
+            Self::Bundle(event) => event.event_type(),
+            Self::Distribution(event) => event.event_type(),
+            Self::Authorization(event) => event.event_type(),
+        }
+    }
+}
+
+/// Upgrades a serialized event and parses it into its event type.
+///
+/// The event struct is chosen by the `event_type` field; authorization
+/// events are the only ones without it.
+///
+/// # Errors
+///
+/// Returns an error if the event cannot be upgraded, its `event_type` is
+/// unknown, or it does not deserialize into its event struct.
+pub fn parse_event(value: Value) -> Result<ParsedEvent, LoggerError> {
+    let value = upgrade_event(value)?;
+
+    let Some(event_type) = value.get("event_type") else {
+        return Ok(ParsedEvent::Authorization(serde_json::from_value(value)?));
+    };
+
+    if PolicyEventType::deserialize(event_type).is_ok() {
+        Ok(ParsedEvent::Policy(serde_json::from_value(value)?))
+    } else if BundleEventType::deserialize(event_type).is_ok() {
+        Ok(ParsedEvent::Bundle(serde_json::from_value(value)?))
+    } else if DistributionEventType::deserialize(event_type).is_ok() {
+        Ok(ParsedEvent::Distribution(serde_json::from_value(value)?))
+    } else {
+        Err(LoggerError::SchemaViolation {
+            field: "event_type".to_string(),
+            reason: format!("unknown event type {event_type}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_upgrade_unversioned_event() {
+        let event = json!({"event_type": "created", "service": "users-service"});
+
+        let upgraded = upgrade_event(event).unwrap();
+        assert_eq!(upgraded["schema_version"], CURRENT_SCHEMA_VERSION);
+        assert_eq!(upgraded["service"], "users-service");
+
+        // Current events are left as they are
+        assert_eq!(upgrade_event(upgraded.clone()).unwrap(), upgraded);
+    }
+
+    #[test]
+    fn test_upgrade_rejects_unknown_versions() {
+        for version in [json!("9.0.0"), json!(1)] {
+            let event = json!({"schema_version": version});
+            assert!(matches!(
+                upgrade_event(event),
+                Err(LoggerError::UnsupportedSchemaVersion(_))
+            ));
+        }
+        assert!(upgrade_event(json!([])).is_err());
+    }
+
+    #[test]
+    fn test_parse_event_dispatches_on_event_type() {
+        let policy = serde_json::to_value(PolicyEvent::created("svc", "1.0.0", "alice")).unwrap();
+        let bundle = serde_json::to_value(BundleEvent::compiled("svc", "1.0.0")).unwrap();
+        let auth = serde_json::to_value(AuthorizationEvent::allowed("svc", "op", "user")).unwrap();
+
+        assert!(matches!(parse_event(policy), Ok(ParsedEvent::Policy(_))));
+        assert!(matches!(parse_event(bundle), Ok(ParsedEvent::Bundle(_))));
+        let auth = parse_event(auth).unwrap();
+        assert_eq!(auth.event_type(), "authorization.allowed");
+
+        let unknown = json!({"event_type": "exploded"});
+        assert!(matches!(
+            parse_event(unknown),
+            Err(LoggerError::SchemaViolation { field, .. }) if field == "event_type"
+        ));
+    }
+}
@@ -0,0 +1,173 @@
+//! OpenTelemetry trace context for audit events.
+//!
+//! Events logged inside [`AuditLogger::with_trace_context`] carry the
+//! `trace_id` and `span_id` of the given span. Outside such a scope, a
+//! logger built with
+//! [`AuditLoggerBuilder::with_otel_tracer`](crate::logger::AuditLoggerBuilder::with_otel_tracer)
+//! records each event as an [`AUDIT_SPAN`] span under the current
+//! OpenTelemetry context and stamps it with that span's IDs.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use opentelemetry::global::{ObjectSafeSpan, ObjectSafeTracer};
+use opentelemetry::trace::{SpanBuilder, SpanContext};
+use opentelemetry::{Context, KeyValue};
+use serde::Serialize;
+
+use crate::logger::AuditLogger;
+
+/// Name of the span an event is recorded as by a logger with a tracer.
+pub const AUDIT_SPAN: &str = "eunomia.audit";
+
+thread_local! {
+    /// Trace contexts of the `with_trace_context` calls running on this
+    /// thread, innermost last, keyed by the address of their logger.
+    static SCOPES: RefCell<Vec<(usize, TraceIds)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Trace and span IDs stamped on an event, as lowercase hex.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceIds {
+    trace_id: String,
+    span_id: String,
+}
+
+impl TraceIds {
+    /// Returns the IDs of a span context, or `None` if it is invalid.
+    fn of(context: &SpanContext) -> Option<Self> {
+        context.is_valid().then(|| Self {
+            trace_id: context.trace_id().to_string(),
+            span_id: context.span_id().to_string(),
+        })
+    }
+}
+
+/// Tracer an [`AuditLogger`] records events with.
+#[derive(Clone)]
+pub struct OtelTracer(pub Arc<dyn ObjectSafeTracer + Send + Sync>);
+
+impl std::fmt::Debug for OtelTracer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OtelTracer").finish_non_exhaustive()
+    }
+}
+
+impl OtelTracer {
+    /// Records an event of `event_type` as a finished span, returning its
+    /// IDs.
+    fn record(&self, event_type: &str) -> Option<TraceIds> {
+        let builder = SpanBuilder::from_name(AUDIT_SPAN).with_attributes(vec![KeyValue::new(
+            "eunomia.audit.event_type",
+            event_type.to_string(),
+        )]);
+        let mut span = self
+            .0
+            .build_with_context_boxed(builder, &Context::current());
+        let ids = TraceIds::of(span.span_context());
+        span.end();
+        ids
+    }
+}
+
+/// Runs `f` with the IDs of `span` as the trace context of `logger`'s
+/// events.
+pub fn scoped<R>(logger: &AuditLogger, span: &dyn ObjectSafeSpan, f: impl FnOnce() -> R) -> R {
+    /// Pops the scope when `f` returns or panics.
+    struct Scope;
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            SCOPES.with(|scopes| scopes.borrow_mut().pop());
+        }
+    }
+
+    let Some(ids) = TraceIds::of(span.span_context()) else {
+        return f();
+    };
+    SCOPES.with(|scopes| scopes.borrow_mut().push((key(logger), ids)));
+    let _scope = Scope;
+    f()
+}
+
+/// Returns the trace context to stamp on an event `logger` is logging.
+pub fn current(
+    logger: &AuditLogger,
+    tracer: Option<&OtelTracer>,
+    event_type: &str,
+) -> Option<TraceIds> {
+    let key = key(logger);
+    SCOPES
+        .with(|scopes| {
+            scopes
+                .borrow()
+                .iter()
+                .rev()
+                .find(|(logger, _)| *logger == key)
+                .map(|(_, ids)| ids.clone())
+        })
+        .or_else(|| tracer.and_then(|tracer| tracer.record(event_type)))
+}
+
+/// Identifies a logger by its address.
+fn key(logger: &AuditLogger) -> usize {
+    std::ptr::from_ref(logger) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InMemoryBackend, PolicyEvent};
+    use opentelemetry::trace::{Span, Tracer, TracerProvider as _};
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+    use opentelemetry_sdk::trace::TracerProvider;
+
+    fn provider() -> (TracerProvider, InMemorySpanExporter) {
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        (provider, exporter)
+    }
+
+    fn event() -> PolicyEvent {
+        PolicyEvent::created("users-service", "1.0.0", "user@example.com")
+    }
+
+    #[test]
+    fn test_with_trace_context_stamps_events() {
+        let (provider, _) = provider();
+        let span = provider.tracer("test").start("request");
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = AuditLogger::builder().with_backend(backend.clone()).build();
+
+        logger.with_trace_context(&span, || logger.log(&event()).unwrap());
+        logger.log(&event()).unwrap();
+
+        let records = backend.records();
+        let context = Span::span_context(&span);
+        assert_eq!(records[0].event["trace_id"], context.trace_id().to_string());
+        assert_eq!(records[0].event["span_id"], context.span_id().to_string());
+        assert!(records[1].event.get("trace_id").is_none());
+    }
+
+    #[test]
+    fn test_otel_tracer_records_audit_spans() {
+        let (provider, exporter) = provider();
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = AuditLogger::builder()
+            .with_backend(backend.clone())
+            .with_otel_tracer(Arc::new(provider.tracer("eunomia-audit")))
+            .build();
+
+        logger.log(&event()).unwrap();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].name, AUDIT_SPAN);
+        assert_eq!(
+            backend.records()[0].event["span_id"],
+            spans[0].span_context.span_id().to_string()
+        );
+    }
+}
@@ -1,7 +1,13 @@
 //! Audit event schema and metadata definitions.
 
+use std::fmt;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::logger::LoggerError;
 
 /// Metadata attached to all audit events.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,7 +91,9 @@ impl AuditMetadata {
 }
 
 /// Current schema version.
-pub const CURRENT_SCHEMA_VERSION: &str = "1.0.0";
+///
+/// See [`upgrade_event`](crate::upgrade_event) for the version history.
+pub const CURRENT_SCHEMA_VERSION: &str = "1.1.0";
 
 /// Event schema definition for documentation and validation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -108,6 +116,11 @@ pub struct EventSchema {
     /// Optional fields
     pub optional_fields: Vec<FieldDefinition>,
 
+    /// Whether [`validate`](Self::validate) rejects fields the schema does
+    /// not declare
+    #[serde(default)]
+    pub deny_unknown_fields: bool,
+
     /// Schema creation timestamp
     pub created_at: DateTime<Utc>,
 }
@@ -161,6 +174,7 @@ impl EventSchema {
             description: description.to_string(),
             required_fields: Vec::new(),
             optional_fields: Vec::new(),
+            deny_unknown_fields: false,
             created_at: Utc::now(),
         }
     }
@@ -188,6 +202,70 @@ impl EventSchema {
         });
         self
     }
+
+    /// Sets whether fields the schema does not declare are rejected.
+    #[must_use]
+    pub const fn deny_unknown_fields(mut self, deny: bool) -> Self {
+        self.deny_unknown_fields = deny;
+        self
+    }
+
+    /// Returns true if this schema applies to an event type.
+    ///
+    /// A pattern ending in `*` matches every event type with that prefix;
+    /// any other pattern must match exactly.
+    #[must_use]
+    pub fn matches(&self, event_type: &str) -> bool {
+        self.event_type_pattern
+            .strip_suffix('*')
+            .map_or(event_type == self.event_type_pattern, |prefix| {
+                event_type.starts_with(prefix)
+            })
+    }
+
+    /// Validates a serialized event against this schema.
+    ///
+    /// Required fields must be present and not null, optional fields may be
+    /// missing or null, and every present field must have its declared type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoggerError::SchemaViolation`] for the first field that
+    /// does not conform.
+    pub fn validate(&self, event: &Value) -> Result<(), LoggerError> {
+        let Some(fields) = event.as_object() else {
+            return Err(violation("", "event is not a JSON object"));
+        };
+
+        for field in &self.required_fields {
+            match fields.get(&field.name) {
+                None | Some(Value::Null) => {
+                    return Err(violation(&field.name, "required field is missing"));
+                }
+                Some(value) => field.check(value)?,
+            }
+        }
+
+        for field in &self.optional_fields {
+            if let Some(value) = fields.get(&field.name).filter(|value| !value.is_null()) {
+                field.check(value)?;
+            }
+        }
+
+        if self.deny_unknown_fields {
+            let declared = |name: &String| {
+                self.required_fields
+                    .iter()
+                    .chain(&self.optional_fields)
+                    .any(|field| field.name == *name)
+            };
+            if let Some(name) = fields.keys().find(|name| !declared(name)) {
+                return Err(violation(name, "field is not declared by the schema"));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl FieldDefinition {
@@ -208,6 +286,80 @@ impl FieldDefinition {
         self.example = Some(example.to_string());
         self
     }
+
+    /// Checks that a field value has this field's type.
+    fn check(&self, value: &Value) -> Result<(), LoggerError> {
+        if self.field_type.accepts(value) {
+            return Ok(());
+        }
+        let found = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        Err(violation(
+            &self.name,
+            &format!("expected {}, found {found}", self.field_type),
+        ))
+    }
+}
+
+impl FieldType {
+    /// Returns true if a JSON value has this type.
+    ///
+    /// Timestamps must be RFC 3339 strings and UUIDs hyphenated strings.
+    #[must_use]
+    pub fn accepts(self, value: &Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Integer => value.is_i64() || value.is_u64(),
+            Self::Float => value.is_number(),
+            Self::Boolean => value.is_boolean(),
+            Self::Timestamp => value
+                .as_str()
+                .is_some_and(|ts| ts.parse::<DateTime<Utc>>().is_ok()),
+            Self::Uuid => value.as_str().is_some_and(|id| Uuid::parse_str(id).is_ok()),
+            Self::Array => value.is_array(),
+            Self::Object => value.is_object(),
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::String => "string",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::Boolean => "boolean",
+            Self::Timestamp => "timestamp",
+            Self::Uuid => "uuid",
+            Self::Array => "array",
+            Self::Object => "object",
+        };
+        f.write_str(name)
+    }
+}
+
+fn violation(field: &str, reason: &str) -> LoggerError {
+    LoggerError::SchemaViolation {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Returns the schemas of the built-in event types.
+#[must_use]
+pub fn builtin_schemas() -> Vec<EventSchema> {
+    vec![
+        policy_event_schema(),
+        bundle_event_schema(),
+        distribution_event_schema(),
+        authorization_event_schema(),
+    ]
 }
 
 /// Returns the schema for policy events.
@@ -229,6 +381,11 @@ pub fn policy_event_schema() -> EventSchema {
     .optional("git_commit", FieldType::String, "Git commit SHA")
     .optional("details", FieldType::String, "Additional details")
     .optional("correlation_id", FieldType::String, "Correlation ID")
+    .optional(
+        "schema_version",
+        FieldType::String,
+        "Schema version the event was written with",
+    )
 }
 
 /// Returns the schema for bundle events.
@@ -251,6 +408,11 @@ pub fn bundle_event_schema() -> EventSchema {
     .optional("size_bytes", FieldType::Integer, "Bundle size in bytes")
     .optional("details", FieldType::String, "Additional details")
     .optional("correlation_id", FieldType::String, "Correlation ID")
+    .optional(
+        "schema_version",
+        FieldType::String,
+        "Schema version the event was written with",
+    )
 }
 
 /// Returns the schema for distribution events.
@@ -277,6 +439,16 @@ pub fn distribution_event_schema() -> EventSchema {
     .optional("strategy", FieldType::String, "Deployment strategy")
     .optional("details", FieldType::String, "Additional details")
     .optional("correlation_id", FieldType::String, "Correlation ID")
+    .optional(
+        "signed_by",
+        FieldType::Array,
+        "Key IDs of the verified bundle signatures",
+    )
+    .optional(
+        "schema_version",
+        FieldType::String,
+        "Schema version the event was written with",
+    )
 }
 
 /// Returns the schema for authorization events.
@@ -303,6 +475,12 @@ pub fn authorization_event_schema() -> EventSchema {
     .optional("policy_version", FieldType::String, "Policy version used")
     .optional("evaluation_time_ns", FieldType::Integer, "Evaluation time")
     .optional("correlation_id", FieldType::String, "Correlation ID")
+    .optional("input", FieldType::Object, "Policy input of the decision")
+    .optional(
+        "schema_version",
+        FieldType::String,
+        "Schema version the event was written with",
+    )
 }
 
 #[cfg(test)]
@@ -390,6 +568,59 @@ mod tests {
         assert!(schema.required_fields.iter().any(|f| f.name == "allowed"));
     }
 
+    #[test]
+    fn test_schema_matches_event_types() {
+        let schema = policy_event_schema();
+        assert!(schema.matches("policy.created"));
+        assert!(!schema.matches("bundle.compiled"));
+
+        let exact = EventSchema::new("Custom", "1.0.0", "custom.event", "Custom");
+        assert!(exact.matches("custom.event"));
+        assert!(!exact.matches("custom.event.other"));
+    }
+
+    #[test]
+    fn test_schema_validate() {
+        let schema = EventSchema::new("TestEvent", "1.0.0", "test.*", "Test events")
+            .required("id", FieldType::Uuid, "Event ID")
+            .required("count", FieldType::Integer, "Count")
+            .optional("details", FieldType::String, "Details");
+        let id = Uuid::nil().to_string();
+
+        assert!(schema
+            .validate(&serde_json::json!({"id": id, "count": 3, "details": null}))
+            .is_ok());
+
+        let violation = |event: Value| match schema.validate(&event) {
+            Err(LoggerError::SchemaViolation { field, reason }) => (field, reason),
+            other => panic!("expected a schema violation, got {other:?}"),
+        };
+        assert_eq!(
+            violation(serde_json::json!({"id": id})),
+            ("count".to_string(), "required field is missing".to_string())
+        );
+        assert_eq!(
+            violation(serde_json::json!({"id": "not-a-uuid", "count": 3})),
+            ("id".to_string(), "expected uuid, found string".to_string())
+        );
+        assert_eq!(
+            violation(serde_json::json!({"id": id, "count": 3, "details": 7})),
+            (
+                "details".to_string(),
+                "expected string, found number".to_string()
+            )
+        );
+
+        // Unknown fields are only rejected when configured
+        let extra = serde_json::json!({"id": id, "count": 3, "extra": true});
+        assert!(schema.validate(&extra).is_ok());
+        let strict = schema.deny_unknown_fields(true);
+        assert!(matches!(
+            strict.validate(&extra),
+            Err(LoggerError::SchemaViolation { field, .. }) if field == "extra"
+        ));
+    }
+
     #[test]
     fn test_metadata_serialization() {
         let metadata = AuditMetadata::with_source("test").environment("dev");
@@ -0,0 +1,67 @@
+//! Round-trips persisted events of older schema versions through migration.
+//!
+//! Each file under `tests/fixtures/v<N>/` is an event as written by that
+//! schema version, named `<category>_<event>.json` after its event type.
+
+use std::path::Path;
+
+use eunomia_audit::{
+    builtin_schemas, parse_event, upgrade_event, ParsedEvent, CURRENT_SCHEMA_VERSION,
+};
+use serde_json::Value;
+
+fn fixtures(version: &str) -> Vec<(String, Value)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(version);
+    let mut fixtures: Vec<(String, Value)> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_stem().unwrap().to_string_lossy().to_string();
+            let value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            (name, value)
+        })
+        .collect();
+    fixtures.sort_by(|a, b| a.0.cmp(&b.0));
+    fixtures
+}
+
+fn to_value(event: &ParsedEvent) -> Value {
+    match event {
+        ParsedEvent::Policy(event) => serde_json::to_value(event),
+        ParsedEvent::Bundle(event) => serde_json::to_value(event),
+        ParsedEvent::Distribution(event) => serde_json::to_value(event),
+        ParsedEvent::Authorization(event) => serde_json::to_value(event),
+    }
+    .unwrap()
+}
+
+#[test]
+fn test_v1_events_round_trip() {
+    let fixtures = fixtures("v1");
+    assert_eq!(fixtures.len(), 4);
+
+    for (name, original) in fixtures {
+        assert!(original.get("schema_version").is_none(), "{name}");
+
+        let upgraded = upgrade_event(original.clone()).unwrap();
+        assert_eq!(upgraded["schema_version"], CURRENT_SCHEMA_VERSION, "{name}");
+
+        let event = parse_event(original).unwrap();
+        assert_eq!(event.event_type(), name.replacen('_', ".", 1));
+
+        // No field is lost or altered by parsing
+        let mut reserialized = to_value(&event);
+        reserialized["schema_version"] = CURRENT_SCHEMA_VERSION.into();
+        assert_eq!(reserialized, upgraded, "{name}");
+
+        // Upgraded events conform to the current schemas
+        let schema = builtin_schemas()
+            .into_iter()
+            .find(|schema| schema.matches(event.event_type()))
+            .unwrap()
+            .deny_unknown_fields(true);
+        schema.validate(&upgraded).unwrap();
+    }
+}
@@ -1,32 +1,41 @@
 //! Build command implementation.
 
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use clap::Args;
+use serde::Deserialize;
 use tracing::info;
 
-use eunomia_compiler::Bundler;
+use eunomia_compiler::bundler::CACHE_FILE_NAME;
+use eunomia_compiler::{Bundler, CompilationCache};
+use eunomia_core::{Bundle, BundleLimits, BundleSizes};
+use eunomia_registry::{BundleCache, CacheConfig, RegistryAuth, RegistryClient, RegistryConfig};
 
 /// Arguments for the build command.
 #[derive(Args)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct BuildArgs {
     /// Path to policies directory
     #[arg(default_value = "policies")]
     pub path: PathBuf,
 
-    /// Bundle name (defaults to directory name)
+    /// Bundle name (defaults to the manifest's bundle name, then the
+    /// directory name)
     #[arg(short, long)]
     pub name: Option<String>,
 
-    /// Bundle version (required)
+    /// Bundle version (required unless the manifest declares one)
     #[arg(short, long)]
-    pub version: String,
+    pub version: Option<String>,
 
     /// Output path for the bundle file (defaults to dist/<name>-v<version>.bundle.tar.gz)
-    #[arg(short, long)]
-    pub output: Option<PathBuf>,
+    #[arg(short, long = "output")]
+    pub output_file: Option<PathBuf>,
 
     /// Git commit SHA to include in metadata
     #[arg(long)]
@@ -39,50 +48,307 @@ pub struct BuildArgs {
     /// Skip validation
     #[arg(long)]
     pub no_validate: bool,
+
+    /// Data root provided outside the bundle (e.g. `data.rate_limits`);
+    /// references under it are not checked (repeatable)
+    #[arg(long = "external-root", value_name = "ROOT")]
+    pub external_roots: Vec<String>,
+
+    /// Allow policies whose package falls under none of the bundle roots,
+    /// while migrating an existing bundle
+    #[arg(long)]
+    pub allow_out_of_root: bool,
+
+    /// Reuse parse results of unchanged files from the `.eunomia-cache`
+    /// file in the current directory, and update it
+    #[arg(long)]
+    pub incremental: bool,
+
+    /// Produce a byte-identical bundle on every build by fixing the
+    /// creation timestamp to `SOURCE_DATE_EPOCH` (or the Unix epoch)
+    #[arg(long)]
+    pub reproducible: bool,
+
+    /// Build manifest declaring bundle defaults and dependencies (defaults
+    /// to `eunomia.toml` in the policies directory)
+    #[arg(long)]
+    pub manifest: Option<PathBuf>,
+
+    /// Registry URL to fetch dependencies from (overrides the manifest)
+    #[arg(long, env = "EUNOMIA_REGISTRY_URL")]
+    pub registry: Option<String>,
+
+    /// Bearer token for the dependency registry
+    #[arg(long, env = "EUNOMIA_REGISTRY_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Resolve dependencies from the local bundle cache only
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Bundle cache directory (defaults to the user cache directory)
+    #[arg(long, env = "EUNOMIA_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+}
+
+/// Default name of the build manifest.
+pub const BUILD_MANIFEST_FILE: &str = "eunomia.toml";
+
+/// Build manifest describing a policy bundle and the bundles it depends on.
+///
+/// ```toml
+/// [bundle]
+/// name = "users-service"
+/// version = "0.1.0"
+/// roots = ["users_service"]
+///
+/// [registry]
+/// url = "https://registry.example.com"
+/// namespace = "policies"
+///
+/// [dependencies]
+/// common-policies = "^1.2"
+///
+/// [limits]
+/// max_data_file_size = 16_777_216
+/// ```
+///
+/// Limits left out of `[limits]` keep their [`BundleLimits`] defaults.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BuildManifest {
+    /// Bundle defaults.
+    #[serde(default)]
+    bundle: ManifestBundle,
+    /// Registry the dependencies are fetched from.
+    #[serde(default)]
+    registry: ManifestRegistry,
+    /// Version constraints by repository name.
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+    /// Size and complexity limits of the bundle.
+    #[serde(default)]
+    limits: BundleLimits,
+}
+
+/// `[bundle]` section of the build manifest.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestBundle {
+    name: Option<String>,
+    version: Option<String>,
+    description: Option<String>,
+    /// Data roots owned by the bundle.
+    #[serde(default)]
+    roots: Vec<String>,
+}
+
+/// `[registry]` section of the build manifest.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManifestRegistry {
+    url: Option<String>,
+    namespace: Option<String>,
+}
+
+impl BuildManifest {
+    /// Loads the manifest, returning an empty one if an implicit manifest
+    /// path does not exist.
+    fn load(path: &Path, explicit: bool) -> Result<Self> {
+        if !explicit && !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read build manifest {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Invalid build manifest {}", path.display()))
+    }
+
+    /// Returns the bundle name and version, preferring the command-line
+    /// arguments over the manifest.
+    ///
+    /// The name falls back to the policy directory name; a version is
+    /// required.
+    fn name_and_version(&self, args: &BuildArgs) -> Result<(String, String)> {
+        let name = args
+            .name
+            .clone()
+            .or_else(|| self.bundle.name.clone())
+            .unwrap_or_else(|| {
+                args.path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("bundle")
+                    .to_string()
+            });
+        let version = args
+            .version
+            .clone()
+            .or_else(|| self.bundle.version.clone())
+            .context("No bundle version given: pass --version or set it in the manifest")?;
+        Ok((name, version))
+    }
+}
+
+/// Resolves and fetches the dependencies declared in `manifest`, returning
+/// each bundle with the digest of the manifest it was fetched by.
+///
+/// Fetched bundles are cached, so later `--offline` builds resolve the same
+/// constraints from the cache.
+fn fetch_dependencies(args: &BuildArgs, manifest: &BuildManifest) -> Result<Vec<(Bundle, String)>> {
+    let url = args
+        .registry
+        .as_deref()
+        .or(manifest.registry.url.as_deref())
+        .context("Dependencies require a registry: set [registry] url or pass --registry")?;
+
+    let mut config = RegistryConfig::new(url);
+    if let Some(namespace) = &manifest.registry.namespace {
+        config = config.with_namespace(namespace);
+    }
+    if let Some(token) = &args.token {
+        config = config.with_auth(RegistryAuth::Bearer {
+            token: token.clone(),
+        });
+    }
+
+    let cache_config = args
+        .cache_dir
+        .as_ref()
+        .map_or_else(CacheConfig::default, CacheConfig::new);
+    let cache = BundleCache::new(cache_config).context("Failed to open bundle cache")?;
+    let client = RegistryClient::new(config)
+        .context("Failed to create registry client")?
+        .with_cache(cache)
+        .with_offline(args.offline);
+
+    // `run` may be called from within the CLI's runtime, so block on a
+    // dedicated runtime in its own thread rather than nesting runtimes.
+    let fetch_all = || -> Result<Vec<(Bundle, String)>> {
+        let rt = tokio::runtime::Runtime::new().context("Failed to create async runtime")?;
+        rt.block_on(async {
+            let mut bundles = Vec::with_capacity(manifest.dependencies.len());
+            for (name, constraint) in &manifest.dependencies {
+                let version = client
+                    .resolve_version(name, constraint)
+                    .await
+                    .with_context(|| format!("Failed to resolve dependency {name} {constraint}"))?;
+                let fetched = client
+                    .fetch_with_digest(name, &version)
+                    .await
+                    .with_context(|| format!("Failed to fetch dependency {name}:{version}"))?;
+                println!("  {name} {constraint} → {version} ({})", fetched.1);
+                bundles.push(fetched);
+            }
+            Ok(bundles)
+        })
+    };
+    std::thread::scope(|scope| scope.spawn(fetch_all).join())
+        .map_err(|_| anyhow::anyhow!("Dependency resolution panicked"))?
+}
+
+/// Environment variable holding the build timestamp for reproducible builds.
+const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+/// Returns the creation timestamp to embed in the bundle.
+///
+/// `SOURCE_DATE_EPOCH` (seconds since the Unix epoch) is honored whenever
+/// it is set; otherwise `--reproducible` builds use the Unix epoch and other
+/// builds the current time (`None`).
+fn build_timestamp(
+    source_date_epoch: Option<&str>,
+    reproducible: bool,
+) -> Result<Option<DateTime<Utc>>> {
+    match source_date_epoch {
+        Some(value) => {
+            let seconds: i64 = value
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid {SOURCE_DATE_EPOCH}: {value}"))?;
+            DateTime::from_timestamp(seconds, 0)
+                .map(Some)
+                .with_context(|| format!("{SOURCE_DATE_EPOCH} out of range: {value}"))
+        }
+        None if reproducible => Ok(Some(DateTime::UNIX_EPOCH)),
+        None => Ok(None),
+    }
 }
 
 /// Runs the build command.
-pub fn run(args: BuildArgs) -> Result<()> {
-    info!(path = ?args.path, version = %args.version, "Building policy bundle");
+pub fn run(args: &BuildArgs) -> Result<()> {
+    info!(path = ?args.path, version = ?args.version, "Building policy bundle");
 
     // Ensure policy path exists
     if !args.path.exists() {
         anyhow::bail!("Policy path does not exist: {}", args.path.display());
     }
 
-    // Determine bundle name from path or argument
-    let bundle_name = args.name.unwrap_or_else(|| {
-        args.path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("bundle")
-            .to_string()
-    });
+    let manifest_path = args
+        .manifest
+        .clone()
+        .unwrap_or_else(|| args.path.join(BUILD_MANIFEST_FILE));
+    let manifest = BuildManifest::load(&manifest_path, args.manifest.is_some())?;
+
+    let (bundle_name, version) = manifest.name_and_version(args)?;
 
     // Determine output path
-    let output_path = args.output.unwrap_or_else(|| {
+    let output_path = args.output_file.clone().unwrap_or_else(|| {
         let output_dir = PathBuf::from("dist");
-        output_dir.join(format!("{}-v{}.bundle.tar.gz", bundle_name, args.version))
+        output_dir.join(format!("{bundle_name}-v{version}.bundle.tar.gz"))
     });
 
     println!("Eunomia Bundle Builder");
     println!("======================");
     println!("Path:    {}", args.path.display());
     println!("Name:    {bundle_name}");
-    println!("Version: {}", args.version);
+    println!("Version: {version}");
+    if let Some(description) = &manifest.bundle.description {
+        println!("About:   {description}");
+    }
     println!("Output:  {}", output_path.display());
     println!();
 
     // Build the bundler
     let mut bundler = Bundler::new(&bundle_name)
-        .version(&args.version)
+        .version(&version)
         .with_optimization(args.optimize)
-        .with_validation(!args.no_validate);
+        .with_validation(!args.no_validate)
+        .allow_out_of_root(args.allow_out_of_root)
+        .with_limits(manifest.limits);
 
     if let Some(commit) = &args.git_commit {
         bundler = bundler.git_commit(commit);
     }
 
+    for root in &manifest.bundle.roots {
+        bundler = bundler.with_root(root);
+    }
+
+    for root in &args.external_roots {
+        bundler = bundler.with_external_root(root);
+    }
+
+    let source_date_epoch = std::env::var(SOURCE_DATE_EPOCH).ok();
+    if let Some(created_at) = build_timestamp(source_date_epoch.as_deref(), args.reproducible)? {
+        bundler = bundler.created_at(created_at);
+    }
+
+    let cache = if args.incremental {
+        let cache =
+            CompilationCache::load(CACHE_FILE_NAME).context("Failed to load compilation cache")?;
+        bundler = bundler.with_cache(cache.clone());
+        Some(cache)
+    } else {
+        None
+    };
+
+    if !manifest.dependencies.is_empty() {
+        println!("Resolving dependencies...");
+        for (dependency, digest) in fetch_dependencies(args, &manifest)? {
+            bundler = bundler.add_dependency(dependency, digest);
+        }
+    }
+
     // Load policies from directory
     print!("Loading policies from {}... ", args.path.display());
     bundler = bundler
@@ -111,6 +377,12 @@ pub fn run(args: BuildArgs) -> Result<()> {
         .context("Failed to compile bundle")?;
     println!("✓");
 
+    if let Some(cache) = cache {
+        if let Err(e) = cache.save(CACHE_FILE_NAME) {
+            tracing::warn!("Failed to save compilation cache: {e}");
+        }
+    }
+
     println!();
     println!("Bundle built successfully!");
     println!("  Name:     {}", bundle.name);
@@ -119,5 +391,259 @@ pub fn run(args: BuildArgs) -> Result<()> {
     println!("  Checksum: {}", bundle.compute_checksum());
     println!("  Output:   {}", output_path.display());
 
+    // Sizes are printed on every build so growth shows up before a limit
+    // is reached
+    let sizes = BundleSizes::measure(&bundle).context("Failed to measure bundle")?;
+    println!();
+    print!("{}", format_sizes(&sizes, &manifest.limits));
+
     Ok(())
 }
+
+/// Formats the measured sizes of a bundle next to their limits.
+fn format_sizes(sizes: &BundleSizes, limits: &BundleLimits) -> String {
+    let mut out = format!(
+        "Bundle size:\n  \
+         Compressed:        {} (limit {})\n  \
+         Uncompressed:      {} (limit {})\n  \
+         Policies:          {} (limit {})\n",
+        format_bytes(sizes.compressed),
+        format_bytes(limits.max_compressed_size),
+        format_bytes(sizes.uncompressed),
+        format_bytes(limits.max_uncompressed_size),
+        sizes.policies(),
+        limits.max_policies,
+    );
+    if let Some((package, rules)) = sizes.largest_policy() {
+        let _ = writeln!(
+            out,
+            "  Most rules:        {rules} in {package} (limit {})",
+            limits.max_rules_per_policy
+        );
+    }
+    if let Some((path, size)) = sizes.largest_data_file() {
+        let _ = writeln!(
+            out,
+            "  Largest data file: {} {path} (limit {})",
+            format_bytes(size),
+            format_bytes(limits.max_data_file_size)
+        );
+    }
+    out
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 MiB`.
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "package users_service.authz\n\ndefault allow := false\n";
+
+    fn build_args(policies: &Path, output: &Path) -> BuildArgs {
+        BuildArgs {
+            path: policies.to_path_buf(),
+            name: Some("users-service".to_string()),
+            version: Some("1.0.0".to_string()),
+            output_file: Some(output.to_path_buf()),
+            git_commit: None,
+            optimize: false,
+            no_validate: true,
+            external_roots: Vec::new(),
+            allow_out_of_root: false,
+            incremental: false,
+            reproducible: true,
+            manifest: None,
+            registry: None,
+            token: None,
+            offline: false,
+            cache_dir: None,
+        }
+    }
+
+    fn build_to(policies: &Path, output: PathBuf) -> Vec<u8> {
+        run(&build_args(policies, &output)).unwrap();
+        fs::read(output).unwrap()
+    }
+
+    #[test]
+    fn test_reproducible_builds_are_identical() {
+        let dir = tempfile::tempdir().unwrap();
+        let policies = dir.path().join("policies");
+        fs::create_dir(&policies).unwrap();
+        fs::write(policies.join("authz.rego"), POLICY).unwrap();
+        fs::write(policies.join("data.json"), r#"{"admins":["alice"]}"#).unwrap();
+
+        let first = build_to(&policies, dir.path().join("first.bundle.tar.gz"));
+        let second = build_to(&policies, dir.path().join("second.bundle.tar.gz"));
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_build_enforces_manifest_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let policies = dir.path().join("policies");
+        fs::create_dir(&policies).unwrap();
+        fs::write(policies.join("authz.rego"), POLICY).unwrap();
+        fs::write(policies.join("data.json"), r#"{"admins":["alice"]}"#).unwrap();
+        let output = dir.path().join("users.bundle.tar.gz");
+        let args = build_args(&policies, &output);
+        run(&args).unwrap();
+
+        fs::write(
+            policies.join(BUILD_MANIFEST_FILE),
+            "[limits]\nmax_data_file_size = 10\n",
+        )
+        .unwrap();
+        let err = format!("{:#}", run(&args).unwrap_err());
+        assert!(err.contains("Data file 'data.json' is 20 bytes"), "{err}");
+        assert!(err.contains("max_data_file_size limit of 10"), "{err}");
+
+        fs::write(
+            policies.join(BUILD_MANIFEST_FILE),
+            "[limits]\nmax_data_size = 10\n",
+        )
+        .unwrap();
+        assert!(run(&args).is_err());
+    }
+
+    #[test]
+    fn test_format_sizes() {
+        let sizes = BundleSizes {
+            compressed: 1_536,
+            uncompressed: 10_240,
+            data_files: BTreeMap::from([("users.json".to_string(), 700)]),
+            policy_rules: BTreeMap::from([("users_service.authz".to_string(), 4)]),
+        };
+
+        assert_eq!(
+            format_sizes(&sizes, &BundleLimits::default()),
+            "Bundle size:\n  \
+             Compressed:        1.5 KiB (limit 10.0 MiB)\n  \
+             Uncompressed:      10.0 KiB (limit 64.0 MiB)\n  \
+             Policies:          1 (limit 1000)\n  \
+             Most rules:        4 in users_service.authz (limit 1000)\n  \
+             Largest data file: 700 B users.json (limit 8.0 MiB)\n"
+        );
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_build_uses_manifest_bundle_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let policies = dir.path().join("policies");
+        fs::create_dir(&policies).unwrap();
+        fs::write(policies.join("authz.rego"), POLICY).unwrap();
+        fs::write(
+            policies.join(BUILD_MANIFEST_FILE),
+            "[bundle]\nname = \"users-service\"\nversion = \"0.3.0\"\n\
+             roots = [\"users_service\", \"users_admin\"]\n",
+        )
+        .unwrap();
+
+        let output = dir.path().join("users.bundle.tar.gz");
+        let mut args = build_args(&policies, &output);
+        args.name = None;
+        args.version = None;
+        run(&args).unwrap();
+
+        let bundle = Bundle::from_file(&output).unwrap();
+        assert_eq!(bundle.name, "users-service");
+        assert_eq!(bundle.version, "0.3.0");
+        assert_eq!(bundle.manifest.roots, vec!["users_service", "users_admin"]);
+
+        fs::remove_file(policies.join(BUILD_MANIFEST_FILE)).unwrap();
+        assert!(run(&args).is_err());
+    }
+
+    #[test]
+    fn test_offline_build_merges_cached_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let policies = dir.path().join("policies");
+        fs::create_dir(&policies).unwrap();
+        fs::write(policies.join("authz.rego"), POLICY).unwrap();
+        fs::write(
+            policies.join(BUILD_MANIFEST_FILE),
+            "[registry]\nurl = \"http://127.0.0.1:9\"\n\n\
+             [dependencies]\ncommon-policies = \"^1.2\"\n",
+        )
+        .unwrap();
+
+        let common = Bundle::builder("common-policies")
+            .version("1.2.3")
+            .add_policy("common.roles", "package common.roles\n\nis_admin := true\n")
+            .add_root("common")
+            .build();
+        let cache_dir = dir.path().join("cache");
+        let cache = BundleCache::new(CacheConfig::new(&cache_dir)).unwrap();
+        let digest = "sha256:4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+        cache
+            .put_with_digest("common-policies", "v1.2.3", &common, Some(digest))
+            .unwrap();
+
+        let output = dir.path().join("users.bundle.tar.gz");
+        let mut args = build_args(&policies, &output);
+        args.offline = true;
+        args.cache_dir = Some(cache_dir);
+        run(&args).unwrap();
+
+        let bundle = Bundle::from_file(&output).unwrap();
+        assert!(bundle.has_policy("common.roles"));
+        assert_eq!(bundle.manifest.dependencies.len(), 1);
+        assert_eq!(bundle.manifest.dependencies[0].version, "1.2.3");
+        assert_eq!(bundle.manifest.dependencies[0].digest, digest);
+    }
+
+    #[test]
+    fn test_build_rejects_packages_outside_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let policies = dir.path().join("policies");
+        fs::create_dir(&policies).unwrap();
+        fs::write(policies.join("authz.rego"), POLICY).unwrap();
+        fs::write(
+            policies.join("orders.rego"),
+            "package orders_service.authz\n\ndefault allow := false\n",
+        )
+        .unwrap();
+
+        let output = dir.path().join("users.bundle.tar.gz");
+        let mut args = build_args(&policies, &output);
+        let err = run(&args).unwrap_err();
+        assert!(format!("{err:#}").contains("orders_service.authz"));
+
+        args.allow_out_of_root = true;
+        run(&args).unwrap();
+        let bundle = Bundle::from_file(&output).unwrap();
+        assert!(bundle.has_policy("orders_service.authz"));
+    }
+
+    #[test]
+    fn test_build_timestamp_sources() {
+        assert_eq!(build_timestamp(None, false).unwrap(), None);
+        assert_eq!(
+            build_timestamp(None, true).unwrap(),
+            Some(DateTime::UNIX_EPOCH)
+        );
+        assert_eq!(
+            build_timestamp(Some("1700000000"), false).unwrap(),
+            DateTime::from_timestamp(1_700_000_000, 0)
+        );
+        assert!(build_timestamp(Some("yesterday"), true).is_err());
+    }
+}
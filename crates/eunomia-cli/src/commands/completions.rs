@@ -0,0 +1,115 @@
+//! Completions command implementation.
+//!
+//! Prints a shell completion script to stdout. The default script is
+//! generated ahead of time from the command definitions. With `--dynamic`,
+//! the script instead calls back into `eunomia` for each completion, which
+//! also completes service names from the registry named by
+//! `EUNOMIA_REGISTRY_URL` and `EUNOMIA_REGISTRY_NAMESPACE` (or
+//! `registry.url` and `registry.namespace` in `.eunomia.toml`).
+
+use std::ffi::OsStr;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Args, CommandFactory};
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+use clap_complete::Shell;
+
+use super::diff::registry_client;
+use super::Cli;
+
+/// Name of the binary the scripts complete.
+const BIN_NAME: &str = "eunomia";
+
+/// Environment variable through which dynamic scripts request completions.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// How long a dynamic completion waits for the registry.
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Arguments for the completions command.
+#[derive(Args)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+
+    /// Complete by calling back into eunomia, including service names from
+    /// the registry
+    #[arg(long)]
+    pub dynamic: bool,
+}
+
+/// Runs the completions command.
+pub fn run(args: &CompletionsArgs) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    if args.dynamic {
+        let shell = args.shell.to_string();
+        Shells::builtins()
+            .completer(&shell)
+            .with_context(|| format!("Dynamic completions are not supported for {shell}"))?
+            .write_registration(COMPLETE_VAR, BIN_NAME, BIN_NAME, BIN_NAME, &mut stdout)
+            .context("Failed to write completion script")?;
+    } else {
+        clap_complete::generate(args.shell, &mut Cli::command(), BIN_NAME, &mut stdout);
+    }
+    Ok(())
+}
+
+/// Completes service names with the services in the registry.
+///
+/// Best effort: without a configured registry, or if the registry cannot be
+/// listed within a short timeout, nothing is suggested.
+pub fn complete_services(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    let config = Cli::load_config(None).ok().map(|config| config.registry);
+    let registry = std::env::var("EUNOMIA_REGISTRY_URL")
+        .ok()
+        .or_else(|| config.as_ref().and_then(|config| config.url.clone()));
+    let namespace = std::env::var("EUNOMIA_REGISTRY_NAMESPACE")
+        .ok()
+        .or_else(|| config.and_then(|config| config.namespace));
+    let token = std::env::var("EUNOMIA_REGISTRY_TOKEN").ok();
+
+    let Ok(client) = registry_client(registry.as_deref(), namespace.as_deref(), token.as_deref())
+    else {
+        return Vec::new();
+    };
+    let Ok(runtime) = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    else {
+        return Vec::new();
+    };
+    let services = runtime
+        .block_on(async { tokio::time::timeout(REGISTRY_TIMEOUT, client.list_services()).await })
+        .ok()
+        .and_then(Result::ok)
+        .unwrap_or_default();
+
+    services
+        .into_iter()
+        .filter(|service| service.starts_with(&*current))
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::Commands;
+    use clap::Parser;
+
+    #[test]
+    fn test_parse_completions() {
+        let cli = Cli::try_parse_from(["eunomia", "completions", "zsh", "--dynamic"]).unwrap();
+        let Commands::Completions(args) = cli.command else {
+            unreachable!()
+        };
+        assert_eq!(args.shell, Shell::Zsh);
+        assert!(args.dynamic);
+
+        assert!(Cli::try_parse_from(["eunomia", "completions", "tcsh"]).is_err());
+    }
+}
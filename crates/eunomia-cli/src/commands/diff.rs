@@ -0,0 +1,392 @@
+//! Diff command implementation.
+//!
+//! Compares two bundle versions by policy, rule, data file and manifest
+//! field. Each side is a local bundle file or a `registry://service:version`
+//! reference fetched from the registry.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Serialize;
+use tracing::info;
+
+use eunomia_compiler::{bundle_diff, BundleRuleDiff, SourceDiff};
+use eunomia_core::Bundle;
+use eunomia_registry::{RegistryAuth, RegistryClient, RegistryConfig};
+
+use crate::config::MISSING_REGISTRY;
+use crate::output::OutputFormat;
+
+/// Prefix of bundle references that are fetched from the registry.
+const REGISTRY_SCHEME: &str = "registry://";
+
+/// Arguments for the diff command.
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Old bundle: a bundle file or `registry://service:version`
+    pub old: String,
+
+    /// New bundle: a bundle file or `registry://service:version`
+    pub new: String,
+
+    /// Show the old and new source of modified policies
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+    pub format: DiffFormat,
+
+    /// Registry URL for `registry://` references
+    #[arg(short, long, env = "EUNOMIA_REGISTRY_URL")]
+    pub registry: Option<String>,
+
+    /// Namespace prefix for the repository
+    #[arg(short, long, env = "EUNOMIA_REGISTRY_NAMESPACE")]
+    pub namespace: Option<String>,
+
+    /// Bearer token for authentication
+    #[arg(long, env = "EUNOMIA_REGISTRY_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+}
+
+/// Output format of the diff command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DiffFormat {
+    /// Human-readable change list.
+    #[default]
+    Text,
+    /// JSON document, for automation.
+    Json,
+}
+
+/// A bundle to compare.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleRef {
+    /// A bundle file on disk.
+    File(PathBuf),
+    /// A bundle in the registry; the version may be any version query.
+    Registry { service: String, version: String },
+}
+
+impl BundleRef {
+    /// Parses a command-line bundle reference. A registry reference without
+    /// a version refers to `latest`.
+    pub(crate) fn parse(reference: &str) -> Result<Self> {
+        let Some(rest) = reference.strip_prefix(REGISTRY_SCHEME) else {
+            return Ok(Self::File(PathBuf::from(reference)));
+        };
+
+        let (service, version) = rest.split_once(':').unwrap_or((rest, "latest"));
+        if service.is_empty() || version.is_empty() {
+            anyhow::bail!(
+                "Invalid registry reference '{reference}': \
+                 expected {REGISTRY_SCHEME}service:version"
+            );
+        }
+        Ok(Self::Registry {
+            service: service.to_string(),
+            version: version.to_string(),
+        })
+    }
+
+    pub(crate) const fn is_registry(&self) -> bool {
+        matches!(self, Self::Registry { .. })
+    }
+}
+
+/// JSON output of the diff command.
+#[derive(Serialize)]
+struct DiffReport<'a> {
+    old: BundleInfo<'a>,
+    new: BundleInfo<'a>,
+    #[serde(flatten)]
+    diff: &'a BundleRuleDiff,
+    cosmetic_changes: Vec<&'a str>,
+    summary: String,
+}
+
+#[derive(Serialize)]
+struct BundleInfo<'a> {
+    name: &'a str,
+    version: &'a str,
+}
+
+impl<'a> From<&'a Bundle> for BundleInfo<'a> {
+    fn from(bundle: &'a Bundle) -> Self {
+        Self {
+            name: &bundle.name,
+            version: &bundle.version,
+        }
+    }
+}
+
+/// Runs the diff command.
+///
+/// With `--result-format json` or `--result-format yaml`, the JSON report is
+/// written in that format instead.
+pub async fn run(args: &DiffArgs, output: OutputFormat) -> Result<()> {
+    info!(old = %args.old, new = %args.new, "Comparing bundles");
+
+    let old_ref = BundleRef::parse(&args.old)?;
+    let new_ref = BundleRef::parse(&args.new)?;
+    let client = if old_ref.is_registry() || new_ref.is_registry() {
+        Some(registry_client(
+            args.registry.as_deref(),
+            args.namespace.as_deref(),
+            args.token.as_deref(),
+        )?)
+    } else {
+        None
+    };
+
+    let old = load_bundle(&old_ref, client.as_ref()).await?;
+    let new = load_bundle(&new_ref, client.as_ref()).await?;
+    let diff = bundle_diff(&old, &new).context("Failed to compare bundles")?;
+
+    let report = || DiffReport {
+        old: BundleInfo::from(&old),
+        new: BundleInfo::from(&new),
+        diff: &diff,
+        cosmetic_changes: diff.cosmetic_changes(),
+        summary: diff.bundle.summary(),
+    };
+    if output.is_structured() {
+        return output.print(&report());
+    }
+    match args.format {
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&report())?),
+        DiffFormat::Text => {
+            println!("Eunomia Bundle Diff");
+            println!("===================");
+            println!("Old: {} v{}", old.name, old.version);
+            println!("New: {} v{}", new.name, new.version);
+            println!();
+            print_diff(&diff, args.verbose);
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates the registry client for `registry://` references.
+pub fn registry_client(
+    registry: Option<&str>,
+    namespace: Option<&str>,
+    token: Option<&str>,
+) -> Result<RegistryClient> {
+    let registry = registry.context(MISSING_REGISTRY)?;
+
+    let mut config = RegistryConfig::new(registry);
+    if let Some(ns) = namespace {
+        config = config.with_namespace(ns);
+    }
+    if let Some(token) = token {
+        config = config.with_auth(RegistryAuth::Bearer {
+            token: token.to_string(),
+        });
+    }
+
+    RegistryClient::new(config).context("Failed to create registry client")
+}
+
+/// Loads a bundle from disk or the registry.
+pub async fn load_bundle(reference: &BundleRef, client: Option<&RegistryClient>) -> Result<Bundle> {
+    match reference {
+        BundleRef::File(path) => Bundle::from_file(path)
+            .with_context(|| format!("Failed to load bundle: {}", path.display())),
+        BundleRef::Registry { service, version } => {
+            let client = client.context("No registry client for registry:// reference")?;
+            let resolved = client
+                .resolve_version(service, version)
+                .await
+                .with_context(|| format!("Failed to resolve version {service}:{version}"))?;
+            // Boxed, as fetching with every registry backend enabled makes a
+            // future too large to keep inline in the diff and replay commands
+            Box::pin(client.fetch(service, &resolved))
+                .await
+                .with_context(|| format!("Failed to fetch bundle: {service}:{resolved}"))
+        }
+    }
+}
+
+/// Prints a bundle diff in text form.
+fn print_diff(diff: &BundleRuleDiff, verbose: bool) {
+    let changes = &diff.bundle;
+    if changes.is_empty() {
+        println!("No changes");
+        return;
+    }
+
+    for package in &changes.added {
+        println!("+ {package}");
+        print_rules(diff, package);
+    }
+    for package in &changes.removed {
+        println!("- {package}");
+        print_rules(diff, package);
+    }
+    for policy in &changes.modified {
+        if diff.rules.contains_key(&policy.package) {
+            println!(
+                "~ {} ({} lines changed)",
+                policy.package, policy.line_changes
+            );
+            print_rules(diff, &policy.package);
+        } else {
+            println!("~ {} (comments and whitespace only)", policy.package);
+        }
+        if verbose {
+            for line in policy.old_source.lines() {
+                println!("    - {line}");
+            }
+            for line in policy.new_source.lines() {
+                println!("    + {line}");
+            }
+        }
+    }
+
+    for path in &changes.added_data_files {
+        println!("+ {path} (data)");
+    }
+    for path in &changes.removed_data_files {
+        println!("- {path} (data)");
+    }
+    for path in &changes.modified_data_files {
+        println!("~ {path} (data)");
+    }
+
+    for change in &changes.manifest_changes {
+        println!(
+            "~ manifest {}: {} -> {}",
+            change.field,
+            change.old.as_deref().unwrap_or("(unset)"),
+            change.new.as_deref().unwrap_or("(unset)")
+        );
+    }
+
+    println!();
+    println!("Summary: {}", changes.summary());
+}
+
+/// Prints the rule and import changes of a policy, if any.
+fn print_rules(diff: &BundleRuleDiff, package: &str) {
+    if let Some(rules) = diff.rules.get(package) {
+        print_source_diff(rules);
+    }
+}
+
+/// Prints the rule and import changes of a policy.
+fn print_source_diff(diff: &SourceDiff) {
+    for rule in &diff.added_rules {
+        println!("    + rule {rule}");
+    }
+    for rule in &diff.removed_rules {
+        println!("    - rule {rule}");
+    }
+    for rule in &diff.modified_rules {
+        println!("    ~ rule {rule}");
+    }
+    for import in &diff.changed_imports {
+        let (sign, path) = import.split_at(1);
+        println!("    {sign} import {path}");
+    }
+    if diff.policy_complexity_delta != 0 {
+        println!("    rules: {:+}", diff.policy_complexity_delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(old: &str, new: &str) -> DiffArgs {
+        DiffArgs {
+            old: old.to_string(),
+            new: new.to_string(),
+            verbose: false,
+            format: DiffFormat::Json,
+            registry: None,
+            namespace: None,
+            token: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_bundle_ref() {
+        assert_eq!(
+            BundleRef::parse("dist/users-service-v1.3.0.bundle.tar.gz").unwrap(),
+            BundleRef::File(PathBuf::from("dist/users-service-v1.3.0.bundle.tar.gz"))
+        );
+        assert_eq!(
+            BundleRef::parse("registry://users-service:1.4.0").unwrap(),
+            BundleRef::Registry {
+                service: "users-service".to_string(),
+                version: "1.4.0".to_string(),
+            }
+        );
+        assert_eq!(
+            BundleRef::parse("registry://users-service").unwrap(),
+            BundleRef::Registry {
+                service: "users-service".to_string(),
+                version: "latest".to_string(),
+            }
+        );
+        assert!(BundleRef::parse("registry://:1.4.0").is_err());
+        assert!(BundleRef::parse("registry://users-service:").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_diff_bundle_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let old_path = dir.path().join("old.bundle.tar.gz");
+        let new_path = dir.path().join("new.bundle.tar.gz");
+
+        Bundle::builder("users-service")
+            .version("1.3.0")
+            .add_policy(
+                "users_service.authz",
+                "package users_service.authz\n\nx := 1\n",
+            )
+            .build()
+            .write_to_file(&old_path)
+            .unwrap();
+        Bundle::builder("users-service")
+            .version("1.4.0")
+            .add_policy(
+                "users_service.authz",
+                "package users_service.authz\n\nx := 2\n",
+            )
+            .build()
+            .write_to_file(&new_path)
+            .unwrap();
+
+        let args = args(old_path.to_str().unwrap(), new_path.to_str().unwrap());
+        run(&args, OutputFormat::Text).await.unwrap();
+        run(&args, OutputFormat::Yaml).await.unwrap();
+        run(
+            &DiffArgs {
+                format: DiffFormat::Text,
+                ..args
+            },
+            OutputFormat::Text,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_registry_ref_requires_registry() {
+        let err = run(
+            &args(
+                "registry://users-service:1.3.0",
+                "registry://users-service:1.4.0",
+            ),
+            OutputFormat::Text,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("--registry"));
+    }
+}
@@ -0,0 +1,526 @@
+//! Doctor command implementation.
+//!
+//! Checks the environment the other commands rely on and reports each
+//! requirement as passed (✓) or failed (✗), with a suggested fix for every
+//! failure. The run fails if any critical check fails; a failed check of a
+//! requirement that is not configured only warns.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use clap::Args;
+use semver::{Version, VersionReq};
+use serde::Serialize;
+use tonic::codegen::http::uri::PathAndQuery;
+use tracing::info;
+
+use super::sign::parse_key_pair;
+use crate::config::{EunomiaConfig, CONFIG_FILE};
+use crate::output::OutputFormat;
+
+/// OPA versions the generated bundles are known to work with.
+const DEFAULT_OPA_REQUIREMENT: &str = ">=0.60.0";
+
+/// Arguments for the doctor command.
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// Policies directory to check
+    #[arg(long, default_value = "policies")]
+    pub policies: PathBuf,
+
+    /// Registry URL to check (defaults to the configured registry)
+    #[arg(long, env = "EUNOMIA_REGISTRY_URL")]
+    pub registry: Option<String>,
+
+    /// Signing key file to check (defaults to the configured key file)
+    #[arg(long)]
+    pub key_file: Option<PathBuf>,
+
+    /// Archimedes instance endpoints to check (comma-separated host:port;
+    /// defaults to the configured endpoints)
+    #[arg(long, value_delimiter = ',')]
+    pub endpoints: Vec<String>,
+
+    /// OPA versions to accept if OPA is on the PATH
+    #[arg(long, default_value = DEFAULT_OPA_REQUIREMENT)]
+    pub opa_version: VersionReq,
+
+    /// Timeout of each network check in seconds
+    #[arg(long, default_value_t = 5)]
+    pub timeout: u64,
+}
+
+/// Result of a single check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct Check {
+    /// What was checked.
+    name: &'static str,
+    /// Whether the requirement is met.
+    passed: bool,
+    /// Whether a failure fails the run.
+    critical: bool,
+    /// What was found.
+    detail: String,
+    /// How to fix a failure.
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: true,
+            critical: false,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            passed: false,
+            critical: true,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    /// Marks a failure as a warning that does not fail the run.
+    const fn optional(mut self) -> Self {
+        self.critical = false;
+        self
+    }
+}
+
+/// Probes of the environment outside the process, replaced in tests.
+#[async_trait]
+trait Probes: Send + Sync {
+    /// Sends an HTTP `HEAD` request and returns the response status.
+    async fn http_head(&self, url: &str) -> Result<u16>;
+
+    /// Runs a gRPC health check against an endpoint and returns whether it
+    /// reports serving.
+    async fn grpc_health(&self, endpoint: &str) -> Result<bool>;
+
+    /// Returns the output of `opa version`, or `None` if OPA is not on the
+    /// PATH.
+    fn opa_version(&self) -> Option<Result<String>>;
+}
+
+/// Runs the doctor command.
+///
+/// `config_file` is the file given with `--config`, if any.
+pub async fn run(
+    args: &DoctorArgs,
+    config_file: Option<&Path>,
+    output: OutputFormat,
+) -> Result<()> {
+    info!("Checking environment");
+
+    let probes = SystemProbes::new(Duration::from_secs(args.timeout))?;
+    let checks = run_checks(args, config_file, &probes).await;
+    if output.is_structured() {
+        output.print(&checks)?;
+    } else {
+        print!("{}", render(&checks));
+    }
+
+    let failed = checks.iter().filter(|c| c.critical && !c.passed).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} critical check(s) failed");
+    }
+    Ok(())
+}
+
+/// Runs every check in order.
+async fn run_checks(
+    args: &DoctorArgs,
+    config_file: Option<&Path>,
+    probes: &impl Probes,
+) -> Vec<Check> {
+    let mut checks = vec![
+        check_config(config_file),
+        check_registry(args.registry.as_deref(), probes).await,
+        check_signing_key(args.key_file.as_deref()),
+    ];
+    checks.extend(check_instances(&args.endpoints, probes).await);
+    checks.push(check_opa(&args.opa_version, probes));
+    checks.push(check_policies(&args.policies));
+    checks
+}
+
+fn check_config(config_file: Option<&Path>) -> Check {
+    const NAME: &str = "Config file";
+    let path = config_file.map(Path::to_path_buf).or_else(|| {
+        std::env::current_dir()
+            .ok()
+            .and_then(|dir| EunomiaConfig::find(&dir))
+    });
+
+    match path {
+        Some(path) if path.is_file() => Check::pass(NAME, path.display().to_string()),
+        Some(path) => Check::fail(
+            NAME,
+            format!("{} does not exist", path.display()),
+            "Fix the path given with --config",
+        ),
+        None => Check::fail(
+            NAME,
+            format!("no {CONFIG_FILE} in this directory or its parents"),
+            format!("Create {CONFIG_FILE} at the project root to set command defaults"),
+        )
+        .optional(),
+    }
+}
+
+async fn check_registry(registry: Option<&str>, probes: &impl Probes) -> Check {
+    const NAME: &str = "Registry";
+    let Some(registry) = registry else {
+        return Check::fail(
+            NAME,
+            "no registry configured",
+            "Set registry.url in .eunomia.toml or EUNOMIA_REGISTRY_URL",
+        )
+        .optional();
+    };
+
+    // The OCI distribution API root answers every client, authenticated or
+    // not, so any status means the registry is reachable
+    let url = format!("{}/v2/", registry.trim_end_matches('/'));
+    match probes.http_head(&url).await {
+        Ok(status) => Check::pass(NAME, format!("{registry} reachable (HTTP {status})")),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{registry} unreachable: {e:#}"),
+            "Check the registry URL and your network or proxy settings",
+        ),
+    }
+}
+
+fn check_signing_key(key_file: Option<&Path>) -> Check {
+    const NAME: &str = "Signing key";
+    let Some(key_file) = key_file else {
+        return Check::fail(
+            NAME,
+            "no signing key configured",
+            "Set signer.key_file in .eunomia.toml; generate a key with \
+             `eunomia sign --generate-key`",
+        )
+        .optional();
+    };
+
+    let parsed = std::fs::read_to_string(key_file)
+        .with_context(|| format!("cannot read {}", key_file.display()))
+        .and_then(|material| parse_key_pair(&material));
+    match parsed {
+        Ok(key_pair) => Check::pass(
+            NAME,
+            format!("{} ({})", key_file.display(), key_pair.algorithm()),
+        ),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{e:#}"),
+            "Point signer.key_file at a base64 or PKCS#8 PEM private key",
+        ),
+    }
+}
+
+async fn check_instances(endpoints: &[String], probes: &impl Probes) -> Vec<Check> {
+    const NAME: &str = "Archimedes instance";
+    if endpoints.is_empty() {
+        return vec![Check::fail(
+            NAME,
+            "no instances configured",
+            "Set distributor.endpoints in .eunomia.toml",
+        )
+        .optional()];
+    }
+
+    let mut checks = Vec::with_capacity(endpoints.len());
+    for endpoint in endpoints {
+        let fix = "Check that the instance is running and its gRPC port is reachable";
+        checks.push(match probes.grpc_health(endpoint).await {
+            Ok(true) => Check::pass(NAME, format!("{endpoint} serving")),
+            Ok(false) => Check::fail(NAME, format!("{endpoint} not serving"), fix),
+            Err(e) => Check::fail(NAME, format!("{endpoint} unreachable: {e:#}"), fix),
+        });
+    }
+    checks
+}
+
+fn check_opa(requirement: &VersionReq, probes: &impl Probes) -> Check {
+    const NAME: &str = "OPA";
+    let fix = format!("Install an OPA release matching {requirement}");
+    match probes.opa_version() {
+        None => Check::pass(NAME, "not on PATH, skipped"),
+        Some(Err(e)) => Check::fail(NAME, format!("`opa version` failed: {e:#}"), fix),
+        Some(Ok(output)) => match parse_opa_version(&output) {
+            Some(version) if requirement.matches(&version) => {
+                Check::pass(NAME, format!("{version} matches {requirement}"))
+            }
+            Some(version) => {
+                Check::fail(NAME, format!("{version} does not match {requirement}"), fix)
+            }
+            None => Check::fail(NAME, "unrecognized `opa version` output", fix),
+        },
+    }
+}
+
+/// Extracts the version from the `Version: x.y.z` line of `opa version`.
+fn parse_opa_version(output: &str) -> Option<Version> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Version:"))
+        .and_then(|version| Version::parse(version.trim()).ok())
+}
+
+fn check_policies(dir: &Path) -> Check {
+    const NAME: &str = "Policy directory";
+    let fix = "Run `eunomia init` or pass the policies directory with --policies";
+    if !dir.is_dir() {
+        return Check::fail(NAME, format!("{} does not exist", dir.display()), fix);
+    }
+
+    let count = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rego"))
+        .count();
+    if count == 0 {
+        return Check::fail(NAME, format!("no .rego files in {}", dir.display()), fix);
+    }
+    Check::pass(NAME, format!("{count} .rego file(s) in {}", dir.display()))
+}
+
+/// Formats the checks, one line each with the fix of failures below it,
+/// followed by a summary.
+fn render(checks: &[Check]) -> String {
+    let mut out = String::new();
+    for check in checks {
+        let mark = if check.passed { '✓' } else { '✗' };
+        let _ = writeln!(out, "{mark} {}: {}", check.name, check.detail);
+        if let Some(fix) = check.fix.as_ref().filter(|_| !check.passed) {
+            let _ = writeln!(out, "    → {fix}");
+        }
+    }
+
+    let passed = checks.iter().filter(|c| c.passed).count();
+    let critical = checks.iter().filter(|c| c.critical && !c.passed).count();
+    let _ = write!(
+        out,
+        "\n{passed} of {} checks passed, {critical} critical failure(s)\n",
+        checks.len()
+    );
+    out
+}
+
+/// Probes that reach the network and run `opa`.
+struct SystemProbes {
+    http: reqwest::Client,
+    timeout: Duration,
+}
+
+impl SystemProbes {
+    fn new(timeout: Duration) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .context("Failed to create HTTP client")?;
+        Ok(Self { http, timeout })
+    }
+}
+
+#[async_trait]
+impl Probes for SystemProbes {
+    async fn http_head(&self, url: &str) -> Result<u16> {
+        let response = self.http.head(url).send().await?;
+        Ok(response.status().as_u16())
+    }
+
+    async fn grpc_health(&self, endpoint: &str) -> Result<bool> {
+        let uri = if endpoint.contains("://") {
+            endpoint.to_string()
+        } else {
+            format!("http://{endpoint}")
+        };
+        let endpoint = tonic::transport::Endpoint::from_shared(uri)?
+            .connect_timeout(self.timeout)
+            .timeout(self.timeout);
+        let mut client = tonic::client::Grpc::new(endpoint.connect().await?);
+        client.ready().await?;
+
+        let request = tonic::Request::new(HealthCheckRequest::default());
+        let path = PathAndQuery::from_static("/grpc.health.v1.Health/Check");
+        let response: tonic::Response<HealthCheckResponse> = client
+            .unary(request, path, tonic::codec::ProstCodec::default())
+            .await?;
+        drop(client);
+        Ok(response.into_inner().status == SERVING)
+    }
+
+    fn opa_version(&self) -> Option<Result<String>> {
+        match std::process::Command::new("opa").arg("version").output() {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => Some(Err(e.into())),
+            Ok(output) => Some(Ok(String::from_utf8_lossy(&output.stdout).into_owned())),
+        }
+    }
+}
+
+/// `grpc.health.v1.HealthCheckRequest`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct HealthCheckRequest {
+    /// Service to check; empty for the server as a whole.
+    #[prost(string, tag = "1")]
+    service: String,
+}
+
+/// `grpc.health.v1.HealthCheckResponse`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct HealthCheckResponse {
+    /// Serving status.
+    #[prost(int32, tag = "1")]
+    status: i32,
+}
+
+/// `HealthCheckResponse.ServingStatus.SERVING`.
+const SERVING: i32 = 1;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use eunomia_core::signing::SigningKeyPair;
+
+    /// Probes with canned answers.
+    struct MockProbes {
+        registry_up: bool,
+        serving: Vec<&'static str>,
+        opa: Option<&'static str>,
+    }
+
+    #[async_trait]
+    impl Probes for MockProbes {
+        async fn http_head(&self, url: &str) -> Result<u16> {
+            assert!(url.ends_with("/v2/"));
+            if self.registry_up {
+                Ok(401)
+            } else {
+                anyhow::bail!("connection refused")
+            }
+        }
+
+        async fn grpc_health(&self, endpoint: &str) -> Result<bool> {
+            if endpoint.starts_with("down") {
+                anyhow::bail!("connection refused");
+            }
+            Ok(self.serving.contains(&endpoint))
+        }
+
+        fn opa_version(&self) -> Option<Result<String>> {
+            self.opa.map(|output| Ok(output.to_string()))
+        }
+    }
+
+    fn args(dir: &Path) -> DoctorArgs {
+        DoctorArgs {
+            policies: dir.join("policies"),
+            registry: Some("https://registry.example.com".to_string()),
+            key_file: Some(dir.join("signing.key")),
+            endpoints: vec!["10.0.0.1:8080".to_string()],
+            opa_version: DEFAULT_OPA_REQUIREMENT.parse().unwrap(),
+            timeout: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_doctor_reports_each_check() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join(CONFIG_FILE);
+        std::fs::write(&config, "").unwrap();
+        std::fs::create_dir(dir.path().join("policies")).unwrap();
+        std::fs::write(dir.path().join("policies/authz.rego"), "package authz\n").unwrap();
+        let key = SigningKeyPair::generate();
+        std::fs::write(dir.path().join("signing.key"), key.to_base64().unwrap()).unwrap();
+
+        let probes = MockProbes {
+            registry_up: true,
+            serving: vec!["10.0.0.1:8080"],
+            opa: Some("Version: 0.61.0\nBuild Commit: abc\n"),
+        };
+        let checks = run_checks(&args(dir.path()), Some(&config), &probes).await;
+        let policies = dir.path().join("policies");
+        assert_eq!(
+            render(&checks),
+            format!(
+                "✓ Config file: {}\n\
+                 ✓ Registry: https://registry.example.com reachable (HTTP 401)\n\
+                 ✓ Signing key: {} (ed25519)\n\
+                 ✓ Archimedes instance: 10.0.0.1:8080 serving\n\
+                 ✓ OPA: 0.61.0 matches >=0.60.0\n\
+                 ✓ Policy directory: 1 .rego file(s) in {}\n\
+                 \n6 of 6 checks passed, 0 critical failure(s)\n",
+                config.display(),
+                dir.path().join("signing.key").display(),
+                policies.display()
+            )
+        );
+
+        std::fs::remove_file(policies.join("authz.rego")).unwrap();
+        let probes = MockProbes {
+            registry_up: false,
+            serving: vec![],
+            opa: Some("Version: 0.50.0\n"),
+        };
+        let mut args = args(dir.path());
+        args.endpoints.push("down:8080".to_string());
+        let checks = run_checks(&args, Some(&config), &probes).await;
+        let report = render(&checks);
+        assert!(report.contains(
+            "✗ Registry: https://registry.example.com unreachable: connection refused\n    → "
+        ));
+        assert!(report.contains("✗ Archimedes instance: 10.0.0.1:8080 not serving\n"));
+        assert!(report.contains("✗ Archimedes instance: down:8080 unreachable"));
+        assert!(report.contains("✗ OPA: 0.50.0 does not match >=0.60.0\n"));
+        assert!(report.contains("✗ Policy directory: no .rego files in"));
+        assert!(report.ends_with("2 of 7 checks passed, 5 critical failure(s)\n"));
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_requirements_only_warn() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("policies")).unwrap();
+        std::fs::write(dir.path().join("policies/authz.rego"), "package authz\n").unwrap();
+
+        let args = DoctorArgs {
+            registry: None,
+            key_file: None,
+            endpoints: Vec::new(),
+            ..args(dir.path())
+        };
+        let probes = MockProbes {
+            registry_up: false,
+            serving: vec![],
+            opa: None,
+        };
+        let checks = run_checks(&args, None, &probes).await;
+
+        assert!(checks.iter().all(|c| c.passed || !c.critical));
+        let report = render(&checks);
+        assert!(report.contains("✗ Signing key: no signing key configured\n    → "));
+        assert!(report.contains("✓ OPA: not on PATH, skipped\n"));
+    }
+
+    #[test]
+    fn test_parse_opa_version() {
+        assert_eq!(
+            parse_opa_version("Version: 0.68.0\nBuild Commit: 1a2b\nGo Version: go1.22\n"),
+            Some(Version::new(0, 68, 0))
+        );
+        assert_eq!(parse_opa_version("opa: unknown command"), None);
+    }
+}
@@ -0,0 +1,306 @@
+//! Eval command implementation.
+//!
+//! Evaluates a query against policy files, or the policies and data files
+//! in a directory, optionally explaining the result with an evaluation
+//! trace. The input document is given inline, read from a file, or piped
+//! through stdin:
+//!
+//! ```text
+//! echo '{"caller":{"type":"user"}}' | eunomia eval data.authz.allow policy.rego
+//! ```
+//!
+//! With `--output-format bool`, the command exits with 0 if the result is
+//! truthy and 1 otherwise; errors that stop evaluation exit with code 2.
+
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use tracing::info;
+
+use eunomia_compiler::{EvalResult, RegoEngine};
+use eunomia_test::TestDiscovery;
+
+use crate::output::OutputFormat;
+
+/// Arguments for the eval command.
+#[derive(Args)]
+pub struct EvalArgs {
+    /// Query to evaluate (e.g. `data.authz.allow`)
+    pub query: String,
+
+    /// Policy files, or directories of policies and data files
+    #[arg(default_value = "policies")]
+    pub policy_files: Vec<PathBuf>,
+
+    /// Input document as inline JSON (read from stdin if neither this nor
+    /// `--input-file` is given)
+    #[arg(long, conflicts_with = "input_file")]
+    pub input: Option<String>,
+
+    /// JSON file holding the input document
+    #[arg(long)]
+    pub input_file: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = EvalFormat::Json)]
+    pub output_format: EvalFormat,
+
+    /// Print the rules entered during evaluation and the expressions they
+    /// stopped at
+    #[arg(long)]
+    pub explain: bool,
+}
+
+/// Output format of the eval command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum EvalFormat {
+    /// The result as pretty-printed JSON.
+    #[default]
+    Json,
+    /// `true` or `false`, also reported through the exit code.
+    Bool,
+    /// Strings without quotes, other results as compact JSON.
+    Raw,
+}
+
+/// Outcome of an evaluation that completed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvalOutcome {
+    /// The result was printed and, for `--output-format bool`, is truthy.
+    Success,
+    /// The result is falsy and `--output-format bool` was requested.
+    Falsy,
+}
+
+impl EvalOutcome {
+    /// Returns the process exit code of the outcome.
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            Self::Success => ExitCode::SUCCESS,
+            Self::Falsy => ExitCode::from(1),
+        }
+    }
+}
+
+/// Exit code of an evaluation that failed before completing.
+pub const ERROR_EXIT_CODE: u8 = 2;
+
+/// Runs the eval command.
+///
+/// # Errors
+///
+/// Returns an error if the policies or input cannot be loaded, or the query
+/// cannot be evaluated.
+pub fn run(args: &EvalArgs, output: OutputFormat) -> Result<EvalOutcome> {
+    info!(query = %args.query, policies = ?args.policy_files, "Evaluating query");
+
+    let mut engine = RegoEngine::new();
+    for path in &args.policy_files {
+        if !path.exists() {
+            anyhow::bail!("Policy path does not exist: {}", path.display());
+        }
+        if path.is_dir() {
+            load_dir(&mut engine, path)?;
+        } else {
+            load_file(&mut engine, path)?;
+        }
+    }
+
+    if let Some(input) = read_input(args)? {
+        engine.set_input(input).context("Failed to set input")?;
+    }
+
+    let result = if args.explain {
+        let trace = engine
+            .eval_with_trace(&args.query)
+            .context("Failed to evaluate query")?;
+        print!("{trace}");
+        trace.result
+    } else {
+        let result = engine
+            .eval(&args.query)
+            .context("Failed to evaluate query")?;
+        if output.is_structured() {
+            output.print(&result.to_json())?;
+        } else {
+            print_result(&result, args.output_format)?;
+        }
+        result
+    };
+
+    Ok(
+        if args.output_format == EvalFormat::Bool && !result.is_truthy() {
+            EvalOutcome::Falsy
+        } else {
+            EvalOutcome::Success
+        },
+    )
+}
+
+/// Reads the input document from `--input`, `--input-file` or, if stdin is
+/// not a terminal, stdin.
+///
+/// Returns `None` if no input was given or stdin is empty.
+fn read_input(args: &EvalArgs) -> Result<Option<serde_json::Value>> {
+    let (content, source) = if let Some(input) = &args.input {
+        (input.clone(), "--input".to_string())
+    } else if let Some(path) = &args.input_file {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read input {}", path.display()))?;
+        (content, path.display().to_string())
+    } else if std::io::stdin().is_terminal() {
+        return Ok(None);
+    } else {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read input from stdin")?;
+        if content.trim().is_empty() {
+            return Ok(None);
+        }
+        (content, "stdin".to_string())
+    };
+
+    let input = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid JSON in input {source}"))?;
+    Ok(Some(input))
+}
+
+/// Prints the result in the given format.
+fn print_result(result: &EvalResult, format: EvalFormat) -> Result<()> {
+    match (format, result.to_json()) {
+        (EvalFormat::Json, value) => println!("{}", serde_json::to_string_pretty(&value)?),
+        (EvalFormat::Bool, _) => println!("{}", result.is_truthy()),
+        (EvalFormat::Raw, serde_json::Value::String(value)) => println!("{value}"),
+        (EvalFormat::Raw, value) => println!("{value}"),
+    }
+    Ok(())
+}
+
+/// Loads a single policy (`.rego`) or data (`.json`) file into `engine`.
+fn load_file(engine: &mut RegoEngine, path: &Path) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    if path.extension().is_some_and(|ext| ext == "json") {
+        let data = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid JSON in data file {}", path.display()))?;
+        engine
+            .add_data(data)
+            .with_context(|| format!("Failed to load data from {}", path.display()))?;
+    } else {
+        engine
+            .add_policy(&path.to_string_lossy(), &content)
+            .with_context(|| format!("Failed to load policy {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Loads the policy and data files under `path` into `engine`.
+fn load_dir(engine: &mut RegoEngine, path: &Path) -> Result<()> {
+    let suite = TestDiscovery::new()
+        .discover(path)
+        .with_context(|| format!("Failed to load policies from {}", path.display()))?;
+
+    let mut policies: Vec<_> = suite.policy_files().iter().collect();
+    policies.sort_by_key(|(file, _)| *file);
+    for (file, source) in policies {
+        engine
+            .add_policy(&file.to_string_lossy(), source)
+            .with_context(|| format!("Failed to load policy {}", file.display()))?;
+    }
+
+    let mut data_files: Vec<_> = suite.data_files().iter().collect();
+    data_files.sort_by_key(|(file, _)| *file);
+    for (file, data) in data_files {
+        engine
+            .add_data(data.clone())
+            .with_context(|| format!("Failed to load data from {}", file.display()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "package authz\n\nimport future.keywords.if\n\n\
+                          default allow := false\n\nallow if input.user.role == \"admin\"\n";
+
+    fn eval_args(policies: &Path, input_file: Option<PathBuf>, explain: bool) -> EvalArgs {
+        EvalArgs {
+            query: "data.authz.allow".to_string(),
+            policy_files: vec![policies.to_path_buf()],
+            input: None,
+            input_file,
+            output_format: EvalFormat::Json,
+            explain,
+        }
+    }
+
+    #[test]
+    fn test_eval_with_input_and_explain() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("authz.rego"), POLICY).unwrap();
+        let input = dir.path().join("input.json");
+        std::fs::write(&input, r#"{"user": {"role": "guest"}}"#).unwrap();
+
+        run(
+            &eval_args(dir.path(), Some(input.clone()), false),
+            OutputFormat::Text,
+        )
+        .unwrap();
+        run(
+            &eval_args(dir.path(), Some(input.clone()), false),
+            OutputFormat::Yaml,
+        )
+        .unwrap();
+        run(
+            &eval_args(dir.path(), Some(input), true),
+            OutputFormat::Text,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_eval_bool_output_of_policy_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = dir.path().join("authz.rego");
+        std::fs::write(&policy, POLICY).unwrap();
+
+        let mut args = eval_args(&policy, None, false);
+        args.input = Some(r#"{"user": {"role": "guest"}}"#.to_string());
+        assert_eq!(
+            run(&args, OutputFormat::Text).unwrap(),
+            EvalOutcome::Success
+        );
+
+        args.output_format = EvalFormat::Bool;
+        assert_eq!(run(&args, OutputFormat::Text).unwrap(), EvalOutcome::Falsy);
+
+        args.input = Some("{not json".to_string());
+        let err = run(&args, OutputFormat::Text).unwrap_err();
+        assert!(err.to_string().contains("Invalid JSON in input --input"));
+    }
+
+    #[test]
+    fn test_eval_rejects_invalid_input() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("authz.rego"), POLICY).unwrap();
+        let input = dir.path().join("input.json");
+        std::fs::write(&input, "{not json").unwrap();
+
+        let err = run(
+            &eval_args(dir.path(), Some(input), false),
+            OutputFormat::Text,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid JSON in input"));
+
+        let missing = dir.path().join("missing");
+        assert!(run(&eval_args(&missing, None, false), OutputFormat::Text).is_err());
+    }
+}
@@ -1,33 +1,51 @@
 //! Fetch command implementation.
 //!
-//! Fetches policy bundles from an OCI-compatible registry.
+//! Fetches policy bundles from an OCI-compatible registry, or from object
+//! storage with `--store`.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
 use tracing::info;
 
-use eunomia_registry::{RegistryAuth, RegistryClient, RegistryConfig};
+use eunomia_core::{BundleVerifier, SignedBundle};
+use eunomia_registry::{
+    BundleCache, BundleStore, CacheConfig, RegistryAuth, RegistryClient, RegistryConfig,
+    VersionQuery, VersionResolver,
+};
+
+use super::completions::complete_services;
+use crate::config::MISSING_REGISTRY;
+use crate::store::StoreArgs;
 
 /// Arguments for the fetch command.
 #[derive(Args)]
+// One bool per independent `--flag`, as clap expects
+#[allow(clippy::struct_excessive_bools)]
 pub struct FetchArgs {
     /// Registry URL (e.g., `<https://registry.example.com>`)
     #[arg(short, long, env = "EUNOMIA_REGISTRY_URL")]
-    pub registry: String,
+    pub registry: Option<String>,
 
     /// Service name (repository name in the registry)
-    #[arg(short, long)]
+    #[arg(short, long, add = ArgValueCompleter::new(complete_services))]
     pub service: String,
 
-    /// Version to fetch (tag, "latest", or digest)
+    /// Version to fetch: "latest", an exact version, a range (`v1`, `^1.2`,
+    /// `~1.4.0`, `>=1.0.0 <2.0.0`), a channel tag such as "stable", or a
+    /// digest
     #[arg(short, long, default_value = "latest")]
     pub version: String,
 
+    /// Let "latest" and version ranges resolve to prerelease versions
+    #[arg(long)]
+    pub include_prereleases: bool,
+
     /// Output path for the bundle
-    #[arg(short, long)]
-    pub output: Option<PathBuf>,
+    #[arg(short, long = "output")]
+    pub output_file: Option<PathBuf>,
 
     /// Namespace prefix for the repository
     #[arg(short, long, env = "EUNOMIA_REGISTRY_NAMESPACE")]
@@ -52,6 +70,26 @@ pub struct FetchArgs {
     /// Print bundle information without downloading
     #[arg(long)]
     pub info_only: bool,
+
+    /// Serve the bundle from the local cache without contacting the registry
+    #[arg(long, conflicts_with_all = ["info_only", "store"])]
+    pub offline: bool,
+
+    /// Bundle cache directory (defaults to the user cache directory)
+    #[arg(long, env = "EUNOMIA_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Trusted public key as `[KEY_ID=]PATH_OR_BASE64` (repeatable); the
+    /// bundle must carry a valid signature from one of them
+    #[arg(long = "verify-key", value_name = "[KEY_ID=]KEY")]
+    pub verify_key: Vec<String>,
+
+    /// Refuse bundles that carry no signature
+    #[arg(long)]
+    pub require_signature: bool,
+
+    #[command(flatten)]
+    pub store: StoreArgs,
 }
 
 /// Runs the fetch command.
@@ -62,15 +100,14 @@ pub struct FetchArgs {
 /// - Registry authentication fails
 /// - The bundle cannot be fetched
 /// - The output file cannot be written
-pub fn run(args: &FetchArgs) -> Result<()> {
-    // Use tokio runtime for async operations
-    let rt = tokio::runtime::Runtime::new().context("Failed to create async runtime")?;
-    rt.block_on(run_async(args))
-}
+pub async fn run(args: &FetchArgs) -> Result<()> {
+    if let Some(store) = args.store.open().await? {
+        return fetch_from_store(args, store.as_ref()).await;
+    }
 
-async fn run_async(args: &FetchArgs) -> Result<()> {
+    let registry = args.registry.as_deref().context(MISSING_REGISTRY)?;
     info!(
-        registry = %args.registry,
+        registry = %registry,
         service = %args.service,
         version = %args.version,
         "Fetching bundle"
@@ -81,8 +118,8 @@ async fn run_async(args: &FetchArgs) -> Result<()> {
     println!();
 
     // Build registry configuration
-    let mut config = RegistryConfig::new(&args.registry)
-        .with_timeout(std::time::Duration::from_secs(args.timeout));
+    let mut config =
+        RegistryConfig::new(registry).with_timeout(std::time::Duration::from_secs(args.timeout));
 
     if let Some(ref ns) = args.namespace {
         config = config.with_namespace(ns);
@@ -92,26 +129,55 @@ async fn run_async(args: &FetchArgs) -> Result<()> {
     let auth = determine_auth(args)?;
     config = config.with_auth(auth);
 
+    let cache_config = args
+        .cache_dir
+        .as_ref()
+        .map_or_else(CacheConfig::default, CacheConfig::new);
+    let cache = BundleCache::new(cache_config).context("Failed to open bundle cache")?;
+
     // Create client
-    print!("Connecting to registry... ");
-    let client = RegistryClient::new(config).context("Failed to create registry client")?;
-    println!("✓");
+    if !args.offline {
+        print!("Connecting to registry... ");
+    }
+    let client = RegistryClient::new(config)
+        .context("Failed to create registry client")?
+        .with_cache(cache)
+        .with_offline(args.offline)
+        .with_prereleases(args.include_prereleases);
+    if !args.offline {
+        println!("✓");
+    }
 
-    // Resolve version
-    print!("Resolving version '{}'... ", args.version);
-    let resolved_version = client
-        .resolve_version(&args.service, &args.version)
-        .await
-        .context("Failed to resolve version")?;
-    println!("{resolved_version}");
+    let resolved_version = if args.offline {
+        // Exact references are served as-is; queries resolve against the
+        // versions in the cache.
+        println!("Offline mode: using cached bundles only");
+        match VersionQuery::parse(&args.version)? {
+            VersionQuery::Exact(version) | VersionQuery::Digest(version) => version,
+            _ => client
+                .resolve_version(&args.service, &args.version)
+                .await
+                .context("Failed to resolve version from cache")?,
+        }
+    } else {
+        // Resolve version
+        print!("Resolving version '{}'... ", args.version);
+        let resolved_version = client
+            .resolve_version(&args.service, &args.version)
+            .await
+            .context("Failed to resolve version")?;
+        println!("{resolved_version}");
+
+        // Check if bundle exists
+        print!("Checking bundle existence... ");
+        if !client.exists(&args.service, &resolved_version).await? {
+            println!("✗");
+            anyhow::bail!("Bundle not found: {}:{}", args.service, resolved_version);
+        }
+        println!("✓");
 
-    // Check if bundle exists
-    print!("Checking bundle existence... ");
-    if !client.exists(&args.service, &resolved_version).await? {
-        println!("✗");
-        anyhow::bail!("Bundle not found: {}:{}", args.service, resolved_version);
-    }
-    println!("✓");
+        resolved_version
+    };
 
     if args.info_only {
         // Just list available tags
@@ -130,12 +196,91 @@ async fn run_async(args: &FetchArgs) -> Result<()> {
 
     // Fetch bundle
     print!("Downloading bundle... ");
-    let bundle = client
-        .fetch(&args.service, &resolved_version)
+    let signed = client
+        .fetch_signed(&args.service, &resolved_version)
         .await
         .context("Failed to fetch bundle")?;
     println!("✓");
 
+    save_bundle(args, &signed, &resolved_version)
+}
+
+/// Fetches the bundle from the object storage given with `--store`.
+async fn fetch_from_store(args: &FetchArgs, store: &dyn BundleStore) -> Result<()> {
+    let url = args.store.store.as_deref().unwrap_or_default();
+    info!(
+        store = %url,
+        service = %args.service,
+        version = %args.version,
+        "Fetching bundle"
+    );
+
+    println!("Eunomia Bundle Fetcher");
+    println!("======================");
+    println!();
+
+    // Object storage has no channel tags, so queries resolve against the
+    // stored versions alone
+    print!("Resolving version '{}'... ", args.version);
+    let versions = store
+        .list_versions(&args.service)
+        .await
+        .context("Failed to list stored versions")?;
+    let resolved_version = VersionResolver::new()
+        .with_prereleases(args.include_prereleases)
+        .resolve(
+            &VersionQuery::parse(&args.version)?,
+            &versions,
+            &args.service,
+        )
+        .context("Failed to resolve version")?;
+    println!("{resolved_version}");
+
+    if args.info_only {
+        println!();
+        println!("Available versions:");
+        for version in &versions {
+            if version == &resolved_version {
+                println!("  * {version} (selected)");
+            } else {
+                println!("    {version}");
+            }
+        }
+        return Ok(());
+    }
+
+    print!("Downloading bundle... ");
+    let signed = store
+        .get(&args.service, &resolved_version)
+        .await
+        .context("Failed to fetch bundle")?;
+    println!("✓");
+
+    save_bundle(args, &signed, &resolved_version)
+}
+
+/// Checks the signatures of a fetched bundle as requested and writes it to
+/// the output path.
+fn save_bundle(args: &FetchArgs, signed: &SignedBundle, resolved_version: &str) -> Result<()> {
+    if args.require_signature && !signed.is_signed() {
+        anyhow::bail!(
+            "Bundle {}:{} is not signed and --require-signature was given",
+            args.service,
+            resolved_version
+        );
+    }
+
+    if !args.verify_key.is_empty() {
+        print!("Verifying signature... ");
+        verify_bundle(signed, &args.verify_key)?;
+        println!("✓");
+    }
+
+    let bundle = &signed.bundle;
+    bundle
+        .validate()
+        .context("Fetched bundle failed validation")?;
+
     println!();
     println!("Bundle Information:");
     println!("  Name:    {}", bundle.name);
@@ -143,7 +288,7 @@ async fn run_async(args: &FetchArgs) -> Result<()> {
     println!("  Policies: {}", bundle.policies.len());
 
     // Determine output path
-    let output_path = args.output.clone().unwrap_or_else(|| {
+    let output_path = args.output_file.clone().unwrap_or_else(|| {
         PathBuf::from(format!(
             "{}-{}.bundle",
             args.service,
@@ -153,7 +298,7 @@ async fn run_async(args: &FetchArgs) -> Result<()> {
 
     // Save bundle
     print!("Saving to {}... ", output_path.display());
-    bundle
+    signed
         .write_to_file(&output_path)
         .context("Failed to save bundle")?;
     println!("✓");
@@ -165,6 +310,68 @@ async fn run_async(args: &FetchArgs) -> Result<()> {
     Ok(())
 }
 
+/// Verifies `signed` against the `--verify-key` arguments.
+fn verify_bundle(signed: &SignedBundle, key_specs: &[String]) -> Result<()> {
+    let verifier = load_verifier(key_specs)?;
+    let key_ids: Vec<&str> = key_specs
+        .iter()
+        .map(|spec| split_key_spec(spec).0)
+        .collect();
+
+    verifier.verify(signed).map_err(|e| {
+        let signed_by: Vec<&str> = signed
+            .signatures
+            .signatures
+            .iter()
+            .map(|s| s.key_id.as_str())
+            .collect();
+        anyhow::anyhow!(
+            "Signature verification failed: {e}\n  Keys tried: {}\n  Signed by:  {}",
+            key_ids.join(", "),
+            if signed_by.is_empty() {
+                "(unsigned)".to_string()
+            } else {
+                signed_by.join(", ")
+            }
+        )
+    })
+}
+
+/// Builds a verifier trusting the keys of `--verify-key` arguments.
+pub fn load_verifier(key_specs: &[String]) -> Result<BundleVerifier> {
+    let mut verifier = BundleVerifier::new();
+    for spec in key_specs {
+        let (key_id, key) = split_key_spec(spec);
+        let material = if Path::new(key).is_file() {
+            std::fs::read_to_string(key)
+                .with_context(|| format!("Failed to read verification key: {key}"))?
+        } else {
+            key.to_string()
+        };
+
+        if material.trim_start().starts_with("-----BEGIN") {
+            verifier.add_public_key_pem(key_id, &material)
+        } else {
+            verifier.add_public_key_base64(key_id, &material)
+        }
+        .with_context(|| format!("Invalid verification key '{key_id}'"))?;
+    }
+    Ok(verifier)
+}
+
+/// Splits a `--verify-key` value into key ID and key, defaulting the ID to
+/// `default`.
+///
+/// Base64 padding is not mistaken for the separator.
+fn split_key_spec(spec: &str) -> (&str, &str) {
+    match spec.split_once('=') {
+        Some((key_id, key)) if !key_id.is_empty() && !key.trim_matches('=').is_empty() => {
+            (key_id, key)
+        }
+        _ => ("default", spec),
+    }
+}
+
 /// Determines the authentication method from CLI arguments.
 fn determine_auth(args: &FetchArgs) -> Result<RegistryAuth> {
     if let Some(ref token) = args.token {
@@ -195,16 +402,22 @@ mod tests {
     #[test]
     fn test_determine_auth_none() {
         let args = FetchArgs {
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: "test-service".to_string(),
             version: "latest".to_string(),
-            output: None,
+            include_prereleases: false,
+            output_file: None,
             namespace: None,
             token: None,
             username: None,
             password: None,
             timeout: 60,
             info_only: false,
+            offline: false,
+            cache_dir: None,
+            verify_key: vec![],
+            require_signature: false,
+            store: StoreArgs::default(),
         };
 
         let auth = determine_auth(&args).unwrap();
@@ -214,16 +427,22 @@ mod tests {
     #[test]
     fn test_determine_auth_bearer() {
         let args = FetchArgs {
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: "test-service".to_string(),
             version: "latest".to_string(),
-            output: None,
+            include_prereleases: false,
+            output_file: None,
             namespace: None,
             token: Some("test-token".to_string()),
             username: None,
             password: None,
             timeout: 60,
             info_only: false,
+            offline: false,
+            cache_dir: None,
+            verify_key: vec![],
+            require_signature: false,
+            store: StoreArgs::default(),
         };
 
         let auth = determine_auth(&args).unwrap();
@@ -236,16 +455,22 @@ mod tests {
     #[test]
     fn test_determine_auth_basic() {
         let args = FetchArgs {
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: "test-service".to_string(),
             version: "latest".to_string(),
-            output: None,
+            include_prereleases: false,
+            output_file: None,
             namespace: None,
             token: None,
             username: Some("user".to_string()),
             password: Some("pass".to_string()),
             timeout: 60,
             info_only: false,
+            offline: false,
+            cache_dir: None,
+            verify_key: vec![],
+            require_signature: false,
+            store: StoreArgs::default(),
         };
 
         let auth = determine_auth(&args).unwrap();
@@ -261,19 +486,65 @@ mod tests {
     #[test]
     fn test_determine_auth_incomplete_basic() {
         let args = FetchArgs {
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: "test-service".to_string(),
             version: "latest".to_string(),
-            output: None,
+            include_prereleases: false,
+            output_file: None,
             namespace: None,
             token: None,
             username: Some("user".to_string()),
             password: None,
             timeout: 60,
             info_only: false,
+            offline: false,
+            cache_dir: None,
+            verify_key: vec![],
+            require_signature: false,
+            store: StoreArgs::default(),
         };
 
         let result = determine_auth(&args);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_split_key_spec() {
+        assert_eq!(
+            split_key_spec("release=keys/release.pub"),
+            ("release", "keys/release.pub")
+        );
+        assert_eq!(
+            split_key_spec("keys/release.pub"),
+            ("default", "keys/release.pub")
+        );
+        assert_eq!(split_key_spec("AAAA+/BBBB="), ("default", "AAAA+/BBBB="));
+        assert_eq!(split_key_spec("AAAA+/BB=="), ("default", "AAAA+/BB=="));
+        assert_eq!(split_key_spec("ci=AAAA+/BB=="), ("ci", "AAAA+/BB=="));
+    }
+
+    #[test]
+    fn test_verify_bundle_reports_key_ids() {
+        use eunomia_core::{Bundle, BundleSigner, SigningKeyPair};
+
+        let trusted = SigningKeyPair::generate();
+        let other = SigningKeyPair::generate();
+        let bundle = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("users_service.authz", "package users_service.authz\n")
+            .build();
+        let signed = BundleSigner::from_key_pair(&trusted, "release".to_string()).sign(&bundle);
+
+        verify_bundle(
+            &signed,
+            &[format!("release={}", trusted.public_key_base64())],
+        )
+        .unwrap();
+
+        let err =
+            verify_bundle(&signed, &[format!("ci={}", other.public_key_base64())]).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Keys tried: ci"));
+        assert!(message.contains("Signed by:  release"));
+    }
 }
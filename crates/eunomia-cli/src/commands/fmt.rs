@@ -0,0 +1,147 @@
+//! Fmt command implementation.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use tracing::info;
+
+use eunomia_compiler::format_rego;
+
+/// Arguments for the fmt command.
+#[derive(Args)]
+pub struct FmtArgs {
+    /// Policy files or directories to format
+    #[arg(default_value = "policies")]
+    pub paths: Vec<PathBuf>,
+
+    /// Report files that would change without writing them, and fail if any
+    #[arg(long)]
+    pub check: bool,
+}
+
+/// Runs the fmt command.
+pub fn run(args: &FmtArgs) -> Result<()> {
+    let mut files = Vec::new();
+    for path in &args.paths {
+        if !path.exists() {
+            anyhow::bail!("Path does not exist: {}", path.display());
+        }
+        collect_rego_files(path, &mut files)?;
+    }
+    info!(
+        files = files.len(),
+        check = args.check,
+        "Formatting policies"
+    );
+
+    let mut changed = 0;
+    for file in &files {
+        let source = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let formatted =
+            format_rego(&source).with_context(|| format!("Failed to format {}", file.display()))?;
+
+        if formatted == source {
+            continue;
+        }
+        changed += 1;
+
+        if args.check {
+            println!("Would reformat: {}", file.display());
+        } else {
+            std::fs::write(file, formatted)
+                .with_context(|| format!("Failed to write {}", file.display()))?;
+            println!("Formatted: {}", file.display());
+        }
+    }
+
+    if args.check && changed > 0 {
+        anyhow::bail!("{changed} of {} file(s) need formatting", files.len());
+    }
+
+    println!(
+        "{} file(s) checked, {changed} {}",
+        files.len(),
+        if args.check {
+            "need formatting"
+        } else {
+            "reformatted"
+        }
+    );
+    Ok(())
+}
+
+/// Collects `.rego` files under `path` in sorted order.
+pub fn collect_rego_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(path)
+        .with_context(|| format!("Failed to read directory {}", path.display()))?
+        .filter_map(std::result::Result::ok)
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            collect_rego_files(&entry, files)?;
+        } else if entry.extension().is_some_and(|e| e == "rego") {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSY: &str =
+        "package authz\nimport rego.v1\nimport data.common\ndefault allow = false\n";
+
+    #[test]
+    fn test_fmt_check_then_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("nested");
+        std::fs::create_dir(&nested).unwrap();
+        let file = nested.join("authz.rego");
+        std::fs::write(&file, MESSY).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not rego").unwrap();
+
+        let mut args = FmtArgs {
+            paths: vec![dir.path().to_path_buf()],
+            check: true,
+        };
+        let err = run(&args).unwrap_err();
+        assert!(err.to_string().contains("1 of 1 file(s) need formatting"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), MESSY);
+
+        args.check = false;
+        run(&args).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file).unwrap(),
+            "package authz\n\nimport data.common\nimport rego.v1\n\ndefault allow := false\n"
+        );
+
+        args.check = true;
+        assert!(run(&args).is_ok());
+    }
+
+    #[test]
+    fn test_fmt_reports_unformattable_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("broken.rego");
+        std::fs::write(&file, "allow := true\n").unwrap();
+
+        let args = FmtArgs {
+            paths: vec![file],
+            check: false,
+        };
+        let err = run(&args).unwrap_err();
+        assert!(format!("{err:#}").contains("missing package declaration"));
+    }
+}
@@ -0,0 +1,421 @@
+//! Init command implementation.
+//!
+//! Scaffolds a policy project for a new service:
+//!
+//! ```text
+//! <project>/
+//! ├── .eunomia.toml
+//! ├── Makefile                  # test, build and publish targets
+//! └── policies/<service>/
+//!     ├── authz.rego            # default deny, plus the template's rules
+//!     ├── authz_test.rego
+//!     ├── eunomia.toml          # build manifest
+//!     └── data.json             # with --data
+//! ```
+//!
+//! Policies, the Makefile and the TOML files are rendered from templates
+//! embedded in the binary; the tests are generated from the mock identities
+//! of `eunomia_test`.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use serde_json::{json, Value};
+use tracing::info;
+
+use eunomia_test::{InputBuilder, MockApiKey, MockSpiffe, MockUser};
+
+use super::build::BUILD_MANIFEST_FILE;
+
+/// Operation every authenticated user may call in the generated policy.
+const EXAMPLE_OPERATION: &str = "getHealth";
+
+/// Name of the project configuration file.
+const PROJECT_CONFIG_FILE: &str = ".eunomia.toml";
+
+const MINIMAL_POLICY: &str = include_str!("../../templates/init/minimal.rego");
+const STANDARD_POLICY: &str = include_str!("../../templates/init/standard.rego");
+const ADVANCED_POLICY: &str = include_str!("../../templates/init/advanced.rego");
+const MAKEFILE: &str = include_str!("../../templates/init/Makefile");
+const BUILD_MANIFEST: &str = include_str!("../../templates/init/eunomia.toml");
+const PROJECT_CONFIG: &str = include_str!("../../templates/init/project.toml");
+
+/// Arguments for the init command.
+#[derive(Args)]
+pub struct InitArgs {
+    /// Project directory to create
+    pub project_name: String,
+
+    /// Name of the service (e.g. `users-service`)
+    pub service_name: String,
+
+    /// Policy template to start from
+    #[arg(long, value_enum, default_value_t = InitTemplate::Minimal)]
+    pub template: InitTemplate,
+
+    /// Also generate an example `data.json`
+    #[arg(long)]
+    pub data: bool,
+
+    /// Scaffold into an existing project directory, overwriting the files
+    /// it generates
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Policy template of a new project.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum InitTemplate {
+    /// Denies every request.
+    #[default]
+    Minimal,
+    /// Allows users with the `admin` role, and any user to check health.
+    #[value(alias = "rbac")]
+    Standard,
+    /// Adds trusted SPIFFE services and API keys with the
+    /// `write:<service>` scope to the standard rules.
+    #[value(alias = "scope-based")]
+    Advanced,
+}
+
+impl InitTemplate {
+    /// Returns the embedded policy template.
+    const fn policy(self) -> &'static str {
+        match self {
+            Self::Minimal => MINIMAL_POLICY,
+            Self::Standard => STANDARD_POLICY,
+            Self::Advanced => ADVANCED_POLICY,
+        }
+    }
+}
+
+/// Names substituted into the templates.
+struct Names<'a> {
+    project: &'a str,
+    service: &'a str,
+    /// Data root of the service, also the first segment of its packages.
+    root: String,
+}
+
+impl Names<'_> {
+    /// Replaces the `{{...}}` placeholders of `template`.
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{project}}", self.project)
+            .replace("{{service}}", self.service)
+            .replace("{{root}}", &self.root)
+            .replace("{{package}}", &format!("{}.authz", self.root))
+    }
+}
+
+/// Runs the init command.
+///
+/// # Errors
+///
+/// Returns an error if the service name is invalid, the project directory
+/// already exists and `--force` is not set, or a file cannot be written.
+pub fn run(args: &InitArgs) -> Result<()> {
+    info!(
+        project = %args.project_name,
+        service = %args.service_name,
+        template = ?args.template,
+        "Initializing policy project"
+    );
+
+    let project = PathBuf::from(&args.project_name);
+    let names = Names {
+        project: project
+            .file_name()
+            .and_then(|name| name.to_str())
+            .context("Project name must end in a directory name")?,
+        service: &args.service_name,
+        root: data_root(&args.service_name)?,
+    };
+
+    if project.exists() && !args.force {
+        anyhow::bail!(
+            "Project directory {} already exists (use --force to scaffold into it)",
+            project.display()
+        );
+    }
+
+    let policies = project.join("policies").join(&args.service_name);
+    let mut files = vec![
+        (
+            project.join(PROJECT_CONFIG_FILE),
+            names.render(PROJECT_CONFIG),
+        ),
+        (project.join("Makefile"), names.render(MAKEFILE)),
+        (
+            policies.join("authz.rego"),
+            names.render(args.template.policy()),
+        ),
+        (
+            policies.join("authz_test.rego"),
+            policy_test(&names, args.template),
+        ),
+        (
+            policies.join(BUILD_MANIFEST_FILE),
+            names.render(BUILD_MANIFEST),
+        ),
+    ];
+    if args.data {
+        files.push((policies.join("data.json"), data(&names.root)?));
+    }
+
+    fs::create_dir_all(&policies)
+        .with_context(|| format!("Failed to create directory {}", policies.display()))?;
+    for (path, content) in &files {
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    println!("Initialized policy project {}:", project.display());
+    for (path, _) in &files {
+        println!(
+            "  {}",
+            path.strip_prefix(&project).unwrap_or(path).display()
+        );
+    }
+    println!("\nNext steps:");
+    println!("  cd {}", project.display());
+    println!("  make test");
+
+    Ok(())
+}
+
+/// Derives the data root of a service, which is also the first segment of
+/// its policy packages (`users-service` becomes `users_service`).
+fn data_root(service: &str) -> Result<String> {
+    let valid = service.starts_with(|c: char| c.is_ascii_alphabetic())
+        && service
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        anyhow::bail!(
+            "Invalid service name '{service}': use letters, digits, '-' and '_', \
+             starting with a letter"
+        );
+    }
+    Ok(service.replace('-', "_"))
+}
+
+/// Generates `authz_test.rego` with passing tests of the template's rules.
+fn policy_test(names: &Names<'_>, template: InitTemplate) -> String {
+    let service = names.service;
+    let input = |operation: &str, method: &str, path: &str| {
+        InputBuilder::new()
+            .operation(operation)
+            .method(method)
+            .path(path)
+            .service(service)
+    };
+    let update = || input("updateResource", "PUT", "/resources/1");
+    let health = || input(EXAMPLE_OPERATION, "GET", "/health");
+
+    let mut cases = vec![("test_anonymous_denied", false, health().build())];
+    match template {
+        InitTemplate::Minimal => {
+            cases.push((
+                "test_admin_denied",
+                false,
+                update().caller(MockUser::admin()).build(),
+            ));
+        }
+        InitTemplate::Standard | InitTemplate::Advanced => {
+            cases.extend([
+                (
+                    "test_admin_allowed",
+                    true,
+                    update().caller(MockUser::admin()).build(),
+                ),
+                (
+                    "test_viewer_denied",
+                    false,
+                    update().caller(MockUser::viewer()).build(),
+                ),
+                (
+                    "test_user_can_check_health",
+                    true,
+                    health().caller(MockUser::guest()).build(),
+                ),
+            ]);
+        }
+    }
+    if template == InitTemplate::Advanced {
+        let write_key = MockApiKey::new("mock-write-key")
+            .with_scope(format!("write:{service}"))
+            .build();
+        cases.extend([
+            (
+                "test_trusted_service_allowed",
+                true,
+                update().caller(MockSpiffe::gateway()).build(),
+            ),
+            (
+                "test_untrusted_service_denied",
+                false,
+                update().caller(MockSpiffe::new("unknown").build()).build(),
+            ),
+            (
+                "test_write_key_allowed",
+                true,
+                update().caller(write_key).build(),
+            ),
+            (
+                "test_read_only_key_denied",
+                false,
+                update().caller(MockApiKey::read_only()).build(),
+            ),
+        ]);
+    }
+
+    let mut source = format!(
+        "# METADATA\n\
+         # title: {service} Authorization Policy Tests\n\
+         # scope: test\n\
+         package {root}.authz_test\n\n\
+         import future.keywords.if\n\
+         import data.{root}.authz\n",
+        root = names.root,
+    );
+    for (name, allow, input) in &cases {
+        push_test(&mut source, name, *allow, input);
+    }
+    source
+}
+
+/// Appends a test asserting that `input` is allowed or denied.
+fn push_test(source: &mut String, name: &str, allow: bool, input: &Value) {
+    // Indent like hand-written tests: four spaces, nested in the test body
+    let mut json = Vec::new();
+    let mut serializer =
+        serde_json::Serializer::with_formatter(&mut json, PrettyFormatter::with_indent(b"    "));
+    let _ = input.serialize(&mut serializer);
+    let input = String::from_utf8_lossy(&json).replace('\n', "\n    ");
+    let assertion = if allow {
+        "authz.allow"
+    } else {
+        "not authz.allow"
+    };
+    let _ = write!(
+        source,
+        "\n{name} if {{\n    {assertion} with input as {input}\n}}\n"
+    );
+}
+
+/// Generates an example `data.json` under the service's data root.
+fn data(root: &str) -> Result<String> {
+    let data = json!({ root: { "public_operations": [EXAMPLE_OPERATION] } });
+    Ok(serde_json::to_string_pretty(&data)? + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(project: &std::path::Path, template: InitTemplate) -> InitArgs {
+        InitArgs {
+            project_name: project.to_string_lossy().to_string(),
+            service_name: "users-service".to_string(),
+            template,
+            data: false,
+            force: false,
+        }
+    }
+
+    #[test]
+    fn test_init_scaffolds_minimal_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("acme-policies");
+        run(&args(&project, InitTemplate::Minimal)).unwrap();
+
+        let policies = project.join("policies/users-service");
+        for file in [
+            project.join(PROJECT_CONFIG_FILE),
+            project.join("Makefile"),
+            policies.join("authz.rego"),
+            policies.join("authz_test.rego"),
+            policies.join(BUILD_MANIFEST_FILE),
+        ] {
+            assert!(file.is_file(), "{} was not generated", file.display());
+        }
+        assert!(!policies.join("data.json").exists());
+
+        let policy = fs::read_to_string(policies.join("authz.rego")).unwrap();
+        assert!(policy.contains("package users_service.authz\n"));
+        assert!(policy.contains("default allow := false\n"));
+        assert!(!policy.contains("allow if"));
+
+        let tests = fs::read_to_string(policies.join("authz_test.rego")).unwrap();
+        assert!(tests.contains("package users_service.authz_test\n"));
+        assert!(tests.contains("import data.users_service.authz\n"));
+        assert!(tests.contains("test_admin_denied if {\n    not authz.allow with input as {"));
+
+        let makefile = fs::read_to_string(project.join("Makefile")).unwrap();
+        assert!(makefile.contains("SERVICE := users-service\n"));
+        assert!(makefile.contains("\ntest:\n\teunomia test $(POLICIES)\n"));
+        assert!(makefile.contains("\npublish: build\n"));
+
+        let config = fs::read_to_string(project.join(PROJECT_CONFIG_FILE)).unwrap();
+        assert!(config.contains("namespace = \"acme-policies\"\n"));
+        let manifest = fs::read_to_string(policies.join(BUILD_MANIFEST_FILE)).unwrap();
+        assert!(manifest.contains("roots = [\"users_service\"]\n"));
+    }
+
+    #[test]
+    fn test_init_refuses_existing_project_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("acme-policies");
+        fs::create_dir(&project).unwrap();
+        fs::write(project.join("Makefile"), "custom:\n").unwrap();
+
+        let mut args = args(&project, InitTemplate::Standard);
+        let err = run(&args).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        assert_eq!(
+            fs::read_to_string(project.join("Makefile")).unwrap(),
+            "custom:\n"
+        );
+
+        args.force = true;
+        args.data = true;
+        run(&args).unwrap();
+        assert!(fs::read_to_string(project.join("Makefile"))
+            .unwrap()
+            .contains("SERVICE := users-service\n"));
+        let data: Value = serde_json::from_str(
+            &fs::read_to_string(project.join("policies/users-service/data.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(data["users_service"]["public_operations"][0], "getHealth");
+    }
+
+    #[test]
+    fn test_init_templates() {
+        let names = Names {
+            project: "acme",
+            service: "orders",
+            root: data_root("orders").unwrap(),
+        };
+
+        let standard = names.render(InitTemplate::Standard.policy());
+        assert!(standard.contains("\"admin\" in input.caller.roles"));
+        assert!(standard.contains("input.operation_id == \"getHealth\""));
+        assert!(!standard.contains("{{"));
+
+        let advanced = names.render(InitTemplate::Advanced.policy());
+        assert!(advanced.contains("input.caller.service_name in trusted_services"));
+        assert!(advanced.contains("\"write:orders\" in input.caller.scopes"));
+        let tests = policy_test(&names, InitTemplate::Advanced);
+        assert!(tests.contains("test_trusted_service_allowed if {"));
+        assert!(tests.contains("test_read_only_key_denied if {"));
+
+        assert!(data_root("1-service").is_err());
+        assert!(data_root("users service").is_err());
+    }
+}
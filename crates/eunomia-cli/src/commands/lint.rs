@@ -0,0 +1,245 @@
+//! Lint command implementation.
+//!
+//! Runs the [`Linter`] and, unless disabled, the [`SemanticValidator`] over
+//! every policy under the given paths. Violations fail the command with
+//! exit code 1; errors that stop linting, such as unreadable files, exit
+//! with code 2.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use tracing::info;
+
+use eunomia_compiler::{
+    IssueCategory, IssueSeverity, Linter, SarifLog, SemanticValidator, ValidationIssue,
+    ValidationReport,
+};
+
+use super::fmt::collect_rego_files;
+use super::validate::format_issue;
+use crate::output::OutputFormat;
+
+/// Arguments for the lint command.
+#[derive(Args)]
+pub struct LintArgs {
+    /// Policy files or directories to lint
+    #[arg(default_value = "policies")]
+    pub path: Vec<PathBuf>,
+
+    /// Fail on warnings as well as errors
+    #[arg(
+        long,
+        num_args = 0,
+        default_missing_value = "true",
+        overrides_with = "no_strict"
+    )]
+    pub strict: Option<bool>,
+
+    /// Do not fail on warnings, also when .eunomia.toml sets `strict`
+    #[arg(long, overrides_with = "strict")]
+    pub no_strict: bool,
+
+    /// Skip semantic validation
+    #[arg(long)]
+    pub no_semantic: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = LintFormat::Text)]
+    pub output_format: LintFormat,
+
+    /// Fail on hints as well as warnings and errors
+    #[arg(
+        long,
+        num_args = 0,
+        default_missing_value = "true",
+        overrides_with = "no_fail_on_hint"
+    )]
+    pub fail_on_hint: Option<bool>,
+
+    /// Do not fail on hints, also when .eunomia.toml sets `fail_on_hint`
+    #[arg(long, overrides_with = "fail_on_hint")]
+    pub no_fail_on_hint: bool,
+}
+
+/// Output format of the lint command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LintFormat {
+    /// Human-readable violations per policy.
+    #[default]
+    Text,
+    /// The validation report of each policy, as a JSON array.
+    Json,
+    /// SARIF 2.1.0 log with the violations of all policies.
+    Sarif,
+}
+
+/// Outcome of a lint run that completed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintOutcome {
+    /// No violation at or above the failing severity.
+    Clean,
+    /// At least one violation at or above the failing severity.
+    Violations,
+}
+
+impl LintOutcome {
+    /// Returns the process exit code of the outcome.
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            Self::Clean => ExitCode::SUCCESS,
+            Self::Violations => ExitCode::from(1),
+        }
+    }
+}
+
+/// Exit code of a lint run that failed before completing.
+pub const ERROR_EXIT_CODE: u8 = 2;
+
+impl LintArgs {
+    /// Returns the least severe issue that fails the run.
+    const fn failing_severity(&self) -> IssueSeverity {
+        if matches!(self.fail_on_hint, Some(true)) {
+            IssueSeverity::Hint
+        } else if matches!(self.strict, Some(true)) {
+            IssueSeverity::Warning
+        } else {
+            IssueSeverity::Error
+        }
+    }
+}
+
+/// Runs the lint command.
+///
+/// # Errors
+///
+/// Returns an error if a path does not exist, or a policy or lint config
+/// cannot be read.
+pub fn run(args: &LintArgs, output: OutputFormat) -> Result<LintOutcome> {
+    info!(paths = ?args.path, "Linting policies");
+
+    let mut reports = Vec::new();
+    for path in &args.path {
+        if !path.exists() {
+            anyhow::bail!("Path does not exist: {}", path.display());
+        }
+        let linter = Linter::discover(path)
+            .with_context(|| format!("Failed to load lint config for {}", path.display()))?;
+
+        let mut files = Vec::new();
+        collect_rego_files(path, &mut files)?;
+        for file in files {
+            reports.push(lint_file(&file, &linter, !args.no_semantic)?);
+        }
+    }
+
+    match args.output_format {
+        _ if output.is_structured() => output.print(&reports)?,
+        LintFormat::Text => print_text(&reports),
+        LintFormat::Json => println!("{}", serde_json::to_string_pretty(&reports)?),
+        LintFormat::Sarif => println!("{}", SarifLog::from_reports(&reports).to_json()?),
+    }
+
+    let failing = args.failing_severity();
+    let violations = reports
+        .iter()
+        .flat_map(|report| &report.issues)
+        .any(|issue| issue.severity >= failing);
+    Ok(if violations {
+        LintOutcome::Violations
+    } else {
+        LintOutcome::Clean
+    })
+}
+
+/// Lints a single policy, collecting its lint and semantic issues.
+fn lint_file(path: &PathBuf, linter: &Linter, semantic: bool) -> Result<ValidationReport> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read policy {}", path.display()))?;
+    let file_name = path.to_string_lossy();
+
+    let mut report = ValidationReport::new();
+    report.file = Some(file_name.to_string());
+
+    for violation in linter.lint(&source, &file_name) {
+        report.add_issue(ValidationIssue {
+            severity: violation.severity.into(),
+            category: IssueCategory::Lint,
+            message: violation.message,
+            line: violation.line,
+            file: Some(file_name.to_string()),
+            rule_id: Some(violation.rule_id.to_string()),
+            suggestion: violation.suggestion,
+        });
+    }
+
+    if semantic {
+        for issue in SemanticValidator::new().validate_source(&source, &file_name) {
+            report.add_issue(issue.into());
+        }
+    }
+
+    Ok(report)
+}
+
+/// Prints the issues of each policy, followed by a summary.
+fn print_text(reports: &[ValidationReport]) {
+    for report in reports {
+        let file = report.file.as_deref().unwrap_or_default();
+        if report.issues.is_empty() {
+            println!("✓ {file}");
+            continue;
+        }
+
+        println!("{file}");
+        for issue in &report.issues {
+            println!("  {}", format_issue(issue));
+        }
+    }
+
+    let issues: usize = reports.iter().map(|report| report.issues.len()).sum();
+    println!("\nLinted {} policies: {issues} issue(s)", reports.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(path: PathBuf) -> LintArgs {
+        LintArgs {
+            path: vec![path],
+            strict: None,
+            no_strict: false,
+            no_semantic: false,
+            output_format: LintFormat::Text,
+            fail_on_hint: None,
+            no_fail_on_hint: false,
+        }
+    }
+
+    #[test]
+    fn test_lint_failing_severity() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("authz.rego");
+        // Only a warning: `if` is used without importing it
+        std::fs::write(
+            &file,
+            "package authz\n\ndefault allow := false\n\n\
+             allow if {\n    input.method == \"GET\"\n}\n",
+        )
+        .unwrap();
+
+        let mut args = args(file);
+        assert_eq!(run(&args, OutputFormat::Text).unwrap(), LintOutcome::Clean);
+
+        args.strict = Some(true);
+        assert_eq!(
+            run(&args, OutputFormat::Text).unwrap(),
+            LintOutcome::Violations
+        );
+
+        args.path = vec![dir.path().join("missing")];
+        assert!(run(&args, OutputFormat::Text).is_err());
+    }
+}
@@ -1,17 +1,31 @@
 //! CLI commands and argument parsing.
 
 pub mod build;
+pub mod completions;
+pub mod diff;
+pub mod doctor;
+pub mod eval;
 pub mod fetch;
+pub mod fmt;
+pub mod init;
+pub mod lint;
 pub mod publish;
 pub mod push;
+pub mod replay;
 pub mod rollback;
 pub mod sign;
 pub mod status;
 pub mod test;
 pub mod validate;
 
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 
+use crate::config::EunomiaConfig;
+use crate::output::OutputFormat;
+
 /// Eunomia - Authorization Policy Platform for Themis
 #[derive(Parser)]
 #[command(name = "eunomia")]
@@ -19,17 +33,55 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Configuration file (defaults to the nearest `.eunomia.toml`)
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Format of the command result; with json or yaml, the result is
+    /// written to stdout in that format and logging is turned off
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub result_format: OutputFormat,
+}
+
+impl Cli {
+    /// Loads the configuration file at `path` or, without one, the
+    /// `.eunomia.toml` in the current directory or its nearest parent.
+    ///
+    /// Without either, every setting is left to the command line.
+    pub fn load_config(path: Option<PathBuf>) -> Result<EunomiaConfig> {
+        if let Some(path) = path {
+            return EunomiaConfig::from_file(&path);
+        }
+        let dir = std::env::current_dir().context("Failed to get current directory")?;
+        EunomiaConfig::find(&dir).map_or_else(
+            || Ok(EunomiaConfig::default()),
+            |path| EunomiaConfig::from_file(&path),
+        )
+    }
 }
 
 /// Available commands.
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Scaffold the policy project of a new service
+    Init(init::InitArgs),
+
     /// Run policy tests
     Test(test::TestArgs),
 
     /// Build a policy bundle
     Build(build::BuildArgs),
 
+    /// Evaluate a query against policies
+    Eval(eval::EvalArgs),
+
+    /// Show differences between two bundles
+    Diff(diff::DiffArgs),
+
+    /// Replay recorded decisions against a bundle
+    Replay(replay::ReplayArgs),
+
     /// Sign a policy bundle
     Sign(sign::SignArgs),
 
@@ -42,6 +94,12 @@ pub enum Commands {
     /// Validate policies
     Validate(validate::ValidateArgs),
 
+    /// Format policy source files
+    Fmt(fmt::FmtArgs),
+
+    /// Lint policies
+    Lint(lint::LintArgs),
+
     /// Push a policy to Archimedes instances
     Push(push::PushArgs),
 
@@ -51,6 +109,134 @@ pub enum Commands {
     /// Check deployment status
     Status(status::StatusArgs),
 
+    /// Check the environment the other commands depend on
+    Doctor(doctor::DoctorArgs),
+
+    /// Generate shell completions
+    Completions(completions::CompletionsArgs),
+
     /// Print version information
     Version,
 }
+
+impl Commands {
+    /// Returns true if the command was asked for JSON output, in which case
+    /// its errors are reported as JSON too.
+    pub fn json_output(&self) -> bool {
+        match self {
+            Self::Test(args) => args.format == test::ReportFormat::Json,
+            Self::Diff(args) => args.format == diff::DiffFormat::Json,
+            Self::Replay(args) => args.format == replay::ReplayFormat::Json,
+            Self::Publish(args) => args.dry_run.format == publish::PublishFormat::Json,
+            Self::Lint(args) => args.output_format == lint::LintFormat::Json,
+            _ => false,
+        }
+    }
+
+    /// Returns the format to write the result in: the command's own format
+    /// flag if it was given, or else the global `--result-format`.
+    pub fn output_format(&self, global: OutputFormat) -> OutputFormat {
+        match self {
+            Self::Push(args) => args.output,
+            Self::Rollback(args) => args.output,
+            Self::Status(args) => args.format,
+            _ => None,
+        }
+        .unwrap_or(global)
+    }
+
+    /// Returns true if the command has a result to write as JSON or YAML.
+    pub const fn has_structured_output(&self) -> bool {
+        match self {
+            Self::Publish(args) => args.dry_run.enabled,
+            Self::Eval(args) => !args.explain,
+            Self::Test(_)
+            | Self::Diff(_)
+            | Self::Replay(_)
+            | Self::Validate(_)
+            | Self::Lint(_)
+            | Self::Push(_)
+            | Self::Rollback(_)
+            | Self::Status(_)
+            | Self::Doctor(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Fills the arguments of the command that were not given on the command
+    /// line from the configuration.
+    pub fn apply_config(&mut self, config: &EunomiaConfig) {
+        match self {
+            Self::Diff(args) => config.merge_with_args(args),
+            Self::Replay(args) => config.merge_with_args(args),
+            Self::Sign(args) => config.merge_with_args(args),
+            Self::Publish(args) => config.merge_with_args(args),
+            Self::Fetch(args) => config.merge_with_args(args),
+            Self::Lint(args) => config.merge_with_args(args),
+            Self::Push(args) => config.merge_with_args(args),
+            Self::Rollback(args) => config.merge_with_args(args),
+            Self::Doctor(args) => config.merge_with_args(args),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("eunomia").chain(args.iter().copied())).unwrap()
+    }
+
+    #[test]
+    fn test_output_format() {
+        let cli = parse(&["rollback", "users-service", "-o", "json"]);
+        assert_eq!(
+            cli.command.output_format(cli.result_format),
+            OutputFormat::Json
+        );
+
+        let cli = parse(&["--result-format", "yaml", "rollback", "users-service"]);
+        assert_eq!(
+            cli.command.output_format(cli.result_format),
+            OutputFormat::Yaml
+        );
+
+        let cli = parse(&[
+            "push",
+            "-s",
+            "users-service",
+            "-v",
+            "1.0.0",
+            "--result-format",
+            "yaml",
+        ]);
+        assert_eq!(
+            cli.command.output_format(cli.result_format),
+            OutputFormat::Yaml
+        );
+        assert!(cli.command.has_structured_output());
+
+        // File path flags keep their `--output` name
+        let cli = parse(&[
+            "build",
+            "--output",
+            "bundle.tar.gz",
+            "--result-format",
+            "json",
+        ]);
+        let Commands::Build(args) = &cli.command else {
+            panic!("expected build");
+        };
+        assert_eq!(args.output_file, Some(PathBuf::from("bundle.tar.gz")));
+        assert!(!cli.command.has_structured_output());
+
+        assert!(parse(&["eval", "data.authz.allow"])
+            .command
+            .has_structured_output());
+        assert!(!parse(&["eval", "data.authz.allow", "--explain"])
+            .command
+            .has_structured_output());
+    }
+}
@@ -1,17 +1,27 @@
 //! Publish command implementation.
 //!
-//! Publishes policy bundles to an OCI-compatible registry.
+//! Publishes policy bundles to an OCI-compatible registry, or to object
+//! storage with `--store`. With `--dry-run` the bundle digest and manifest
+//! that would be pushed are printed instead, without contacting the
+//! registry.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
+use serde::Serialize;
 use tracing::info;
 
 use eunomia_audit::{AuditLogger, BundleEvent, TracingBackend};
-use eunomia_core::Bundle;
-use eunomia_registry::{RegistryAuth, RegistryClient, RegistryConfig};
+use eunomia_core::{Bundle, BundleSigner, SignedBundle};
+use eunomia_registry::{BundleStore, Manifest, RegistryAuth, RegistryClient, RegistryConfig};
+
+use super::completions::complete_services;
+use crate::config::MISSING_REGISTRY;
+use crate::output::OutputFormat;
+use crate::store::StoreArgs;
 
 /// Arguments for the publish command.
 #[derive(Args)]
@@ -22,10 +32,10 @@ pub struct PublishArgs {
 
     /// Registry URL (e.g., `<https://registry.example.com>`)
     #[arg(short, long, env = "EUNOMIA_REGISTRY_URL")]
-    pub registry: String,
+    pub registry: Option<String>,
 
     /// Service name (repository name in the registry)
-    #[arg(short, long)]
+    #[arg(short, long, add = ArgValueCompleter::new(complete_services))]
     pub service: Option<String>,
 
     /// Version tag for the bundle (defaults to bundle version)
@@ -55,6 +65,67 @@ pub struct PublishArgs {
     /// Request timeout in seconds
     #[arg(long, default_value = "60")]
     pub timeout: u64,
+
+    /// Sign the bundle with this private key (base64 or PKCS#8 PEM) before
+    /// uploading
+    #[arg(long)]
+    pub signing_key: Option<PathBuf>,
+
+    /// Key ID to include in the signature
+    #[arg(long, default_value = "default", requires = "signing_key")]
+    pub key_id: String,
+
+    /// Fail if the version tag already exists in the registry
+    #[arg(long)]
+    pub no_overwrite: bool,
+
+    #[command(flatten)]
+    pub dry_run: DryRunArgs,
+
+    #[command(flatten)]
+    pub store: StoreArgs,
+}
+
+/// Arguments of a publish dry run.
+#[derive(Args, Debug, Clone, Default)]
+pub struct DryRunArgs {
+    /// Print the bundle digest and manifest that would be pushed, without
+    /// contacting the registry
+    #[arg(id = "dry_run", long = "dry-run", conflicts_with = "store")]
+    pub enabled: bool,
+
+    /// Output format for --dry-run
+    #[arg(long, value_enum, default_value_t = PublishFormat::Text, requires = "dry_run")]
+    pub format: PublishFormat,
+
+    /// Let --dry-run check the registry for an existing version tag; the
+    /// only request a dry run makes
+    #[arg(long, requires = "dry_run")]
+    pub check_remote: bool,
+}
+
+/// Output format of a dry run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum PublishFormat {
+    /// Human-readable summary followed by the manifest.
+    #[default]
+    Text,
+    /// JSON object with the digests and the manifest.
+    Json,
+}
+
+/// What a dry run would publish.
+#[derive(Debug, Serialize)]
+struct DryRunReport<'a> {
+    registry: &'a str,
+    service: String,
+    version: String,
+    repository: String,
+    bundle_digest: &'a str,
+    manifest_digest: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag_exists: Option<bool>,
+    manifest: &'a Manifest,
 }
 
 /// Runs the publish command.
@@ -65,46 +136,70 @@ pub struct PublishArgs {
 /// - The bundle cannot be loaded
 /// - Registry authentication fails
 /// - The upload fails
-pub fn run(args: &PublishArgs) -> Result<()> {
-    // Use tokio runtime for async operations
-    let rt = tokio::runtime::Runtime::new().context("Failed to create async runtime")?;
-    rt.block_on(run_async(args))
-}
-
-async fn run_async(args: &PublishArgs) -> Result<()> {
-    info!(bundle = ?args.bundle, registry = %args.registry, "Publishing bundle");
+pub async fn run(args: &PublishArgs, output: OutputFormat) -> Result<()> {
+    let destination = match args.store.store {
+        Some(ref url) => url.as_str(),
+        None => args.registry.as_deref().context(MISSING_REGISTRY)?,
+    };
+    info!(bundle = ?args.bundle, destination = %destination, "Publishing bundle");
 
     // Ensure bundle exists
     if !args.bundle.exists() {
         anyhow::bail!("Bundle file does not exist: {}", args.bundle.display());
     }
 
+    if args.dry_run.enabled {
+        return dry_run(args, output).await;
+    }
+
     println!("Eunomia Bundle Publisher");
     println!("========================");
     println!();
 
     // Load the bundle
     print!("Loading bundle... ");
-    let bundle = Bundle::from_file(&args.bundle).context("Failed to load bundle")?;
+    let bundle = load_bundle(&args.bundle)?;
     println!("✓");
 
-    let service = args.service.clone().unwrap_or_else(|| bundle.name.clone());
+    let signed = match args.signing_key {
+        Some(ref key_path) => {
+            print!("Signing bundle... ");
+            let signed = sign_bundle(bundle, key_path, &args.key_id)?;
+            println!("✓");
+            signed
+        }
+        None => SignedBundle::from_embedded(bundle),
+    };
+    let bundle = &signed.bundle;
 
-    let version = args
-        .version
-        .clone()
-        .unwrap_or_else(|| format!("v{}", bundle.version));
+    let (service, version) = target(args, bundle);
 
     println!();
     println!("Bundle Information:");
     println!("  Name:    {}", bundle.name);
     println!("  Version: {}", bundle.version);
+    let key_ids: Vec<&str> = signed
+        .signatures
+        .signatures
+        .iter()
+        .map(|s| s.key_id.as_str())
+        .collect();
+    if key_ids.is_empty() {
+        println!("  Signed:  no");
+    } else {
+        println!("  Signed:  {}", key_ids.join(", "));
+    }
     println!();
-    println!("Registry Information:");
-    println!("  URL:       {}", args.registry);
+    let kind = if args.store.store.is_some() {
+        "Store"
+    } else {
+        "Registry"
+    };
+    println!("{kind} Information:");
+    println!("  URL:       {destination}");
     println!("  Service:   {service}");
     println!("  Tag:       {version}");
-    if let Some(ref ns) = args.namespace {
+    if let (None, Some(ns)) = (&args.store.store, &args.namespace) {
         println!("  Namespace: {ns}");
     }
     println!();
@@ -123,44 +218,37 @@ async fn run_async(args: &PublishArgs) -> Result<()> {
         }
     }
 
-    // Build registry configuration
-    let mut config = RegistryConfig::new(&args.registry)
-        .with_timeout(std::time::Duration::from_secs(args.timeout));
-
-    if let Some(ref ns) = args.namespace {
-        config = config.with_namespace(ns);
-    }
-
-    // Set authentication
-    let auth = determine_auth(args)?;
-    config = config.with_auth(auth);
-
-    // Create client
-    print!("Connecting to registry... ");
-    let client = RegistryClient::new(config).context("Failed to create registry client")?;
-    println!("✓");
-
-    // Publish bundle
-    print!("Uploading bundle... ");
-    let digest = client
-        .publish(&service, &version, &bundle)
-        .await
-        .context("Failed to publish bundle")?;
-    println!("✓");
+    let (digest, fetch_from) = if let Some(store) = args.store.open().await? {
+        let digest = upload_to_store(args, store.as_ref(), &service, &version, &signed).await?;
+        (digest, format!("--store {destination}"))
+    } else {
+        let digest = upload_to_registry(args, &service, &version, &signed).await?;
+        (digest, format!("--registry {destination}"))
+    };
 
     println!();
     println!("Bundle published successfully!");
     println!();
     println!("  Digest: {digest}");
-    println!("  Pull:   {}/{service}:{version}", args.registry);
+    if args.store.store.is_none() {
+        println!("  Pull:   {destination}/{service}:{version}");
+    }
     println!();
     println!("To fetch this bundle:");
-    println!(
-        "  eunomia fetch --registry {} --service {service} --version {version}",
-        args.registry
-    );
+    println!("  eunomia fetch {fetch_from} --service {service} --version {version}");
+
+    emit_audit_event(args, &service, &version, destination, &digest);
+    Ok(())
+}
 
-    // Emit audit event for bundle publication
+/// Emits the audit event of a successful publication.
+fn emit_audit_event(
+    args: &PublishArgs,
+    service: &str,
+    version: &str,
+    destination: &str,
+    digest: &str,
+) {
     let audit_logger = AuditLogger::builder()
         .with_backend(Arc::new(TracingBackend::new()))
         .build();
@@ -169,18 +257,222 @@ async fn run_async(args: &PublishArgs) -> Result<()> {
         .map(|m| m.len())
         .unwrap_or(0);
 
-    let audit_event = BundleEvent::published(&service, &version, &args.registry)
-        .with_checksum(&digest)
+    let audit_event = BundleEvent::published(service, version, destination)
+        .with_checksum(digest)
         .with_size(bundle_size)
         .with_correlation_id(&format!("publish-{service}-{version}"));
 
     if let Err(e) = audit_logger.log(&audit_event) {
         tracing::warn!("Failed to emit audit event for bundle publish: {e}");
     }
+}
+
+/// Uploads the bundle to the registry, returning its digest.
+async fn upload_to_registry(
+    args: &PublishArgs,
+    service: &str,
+    version: &str,
+    signed: &SignedBundle,
+) -> Result<String> {
+    print!("Connecting to registry... ");
+    let client =
+        RegistryClient::new(registry_config(args)?).context("Failed to create registry client")?;
+    println!("✓");
+
+    if args.no_overwrite
+        && client
+            .exists(service, version)
+            .await
+            .context("Failed to check registry for an existing tag")?
+    {
+        anyhow::bail!("Version {version} of {service} already exists in the registry");
+    }
+
+    print!("Uploading bundle... ");
+    let digest = client
+        .publish_signed_with_progress(service, version, signed, print_progress)
+        .await
+        .context("Failed to publish bundle")?;
+    println!(" ✓");
+    Ok(digest)
+}
+
+/// Uploads the bundle to the object storage given with `--store`,
+/// returning its digest.
+async fn upload_to_store(
+    args: &PublishArgs,
+    store: &dyn BundleStore,
+    service: &str,
+    version: &str,
+    signed: &SignedBundle,
+) -> Result<String> {
+    if args.no_overwrite
+        && store
+            .exists(service, version)
+            .await
+            .context("Failed to check the store for an existing version")?
+    {
+        anyhow::bail!("Version {version} of {service} already exists in the store");
+    }
+
+    print!("Uploading bundle... ");
+    let digest = store
+        .put(service, version, signed)
+        .await
+        .context("Failed to publish bundle")?;
+    println!("✓");
+    Ok(digest)
+}
+
+/// Loads the bundle to publish and checks its structural integrity.
+fn load_bundle(path: &Path) -> Result<Bundle> {
+    let bundle = Bundle::from_file(path).context("Failed to load bundle")?;
+    bundle.validate().context("Bundle failed validation")?;
+    Ok(bundle)
+}
+
+/// Prints what publishing the bundle would push, without uploading it.
+///
+/// The registry is only contacted with `--check-remote`, to look up the
+/// version tag.
+async fn dry_run(args: &PublishArgs, output: OutputFormat) -> Result<()> {
+    if args.no_overwrite && !args.dry_run.check_remote {
+        anyhow::bail!(
+            "--no-overwrite needs the registry; pass --check-remote to check the tag in a dry run"
+        );
+    }
+
+    let bundle = load_bundle(&args.bundle)?;
+    let signed = match args.signing_key {
+        Some(ref key_path) => sign_bundle(bundle, key_path, &args.key_id)?,
+        None => SignedBundle::from_embedded(bundle),
+    };
+    let (service, version) = target(args, &signed.bundle);
 
+    let config = registry_config(args)?;
+    let registry = config.url.clone();
+    let repository = config.repository_name(&service);
+    let client = RegistryClient::new(config).context("Failed to create registry client")?;
+    let signatures = signed.is_signed().then_some(&signed.signatures);
+    let prepared = client
+        .prepare_publish(&service, &version, &signed.bundle, signatures)
+        .context("Failed to prepare bundle")?;
+
+    let tag_exists = if args.dry_run.check_remote {
+        Some(
+            client
+                .exists(&service, &version)
+                .await
+                .context("Failed to check registry for an existing tag")?,
+        )
+    } else {
+        None
+    };
+
+    let report = DryRunReport {
+        registry: &registry,
+        service,
+        version,
+        repository,
+        bundle_digest: prepared.bundle_digest(),
+        manifest_digest: prepared.manifest_digest()?,
+        tag_exists,
+        manifest: &prepared.manifest,
+    };
+    match args.dry_run.format {
+        _ if output.is_structured() => output.print(&report)?,
+        PublishFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        PublishFormat::Text => print_dry_run(&report)?,
+    }
+
+    if args.no_overwrite && tag_exists == Some(true) {
+        anyhow::bail!(
+            "Version {} of {} already exists in the registry",
+            report.version,
+            report.service
+        );
+    }
+    Ok(())
+}
+
+fn print_dry_run(report: &DryRunReport<'_>) -> Result<()> {
+    println!("Dry run: nothing will be pushed");
+    println!();
+    println!("  Registry:        {}", report.registry);
+    println!("  Repository:      {}", report.repository);
+    println!("  Tag:             {}", report.version);
+    println!("  Bundle digest:   {}", report.bundle_digest);
+    println!("  Manifest digest: {}", report.manifest_digest);
+    match report.tag_exists {
+        Some(true) => println!("  Tag exists:      yes (would be overwritten)"),
+        Some(false) => println!("  Tag exists:      no"),
+        None => {}
+    }
+    println!();
+    println!("Manifest:");
+    println!("{}", serde_json::to_string_pretty(report.manifest)?);
     Ok(())
 }
 
+/// Returns the service and version tag to publish a bundle as.
+fn target(args: &PublishArgs, bundle: &Bundle) -> (String, String) {
+    let service = args.service.clone().unwrap_or_else(|| bundle.name.clone());
+    let version = args
+        .version
+        .clone()
+        .unwrap_or_else(|| format!("v{}", bundle.version));
+    (service, version)
+}
+
+/// Builds the registry configuration from CLI arguments.
+fn registry_config(args: &PublishArgs) -> Result<RegistryConfig> {
+    let registry = args.registry.as_deref().context(MISSING_REGISTRY)?;
+    let mut config =
+        RegistryConfig::new(registry).with_timeout(std::time::Duration::from_secs(args.timeout));
+
+    if let Some(ref ns) = args.namespace {
+        config = config.with_namespace(ns);
+    }
+
+    Ok(config.with_auth(determine_auth(args)?))
+}
+
+/// Signs a bundle with the key at `key_path`.
+///
+/// Existing signatures are kept, so several parties can sign the same
+/// bundle in turn before it is published.
+fn sign_bundle(bundle: Bundle, key_path: &Path, key_id: &str) -> Result<SignedBundle> {
+    let key_material = std::fs::read_to_string(key_path)
+        .with_context(|| format!("Failed to read signing key: {}", key_path.display()))?;
+    let key_pair = super::sign::parse_key_pair(&key_material)?;
+    let signer = BundleSigner::from_key_pair(&key_pair, key_id.to_string());
+
+    let mut embedded = SignedBundle::from_embedded(bundle);
+    if embedded.is_signed() {
+        let signature = signer.sign_checksum(&embedded.bundle.compute_checksum());
+        embedded.signatures.add_signature(signature);
+        Ok(embedded)
+    } else {
+        Ok(signer.sign(&embedded.bundle))
+    }
+}
+
+/// Redraws the upload progress line.
+fn print_progress(sent: u64, total: u64) {
+    use std::io::Write;
+
+    const BAR_WIDTH: u64 = 30;
+    let filled = (sent * BAR_WIDTH).checked_div(total).unwrap_or(BAR_WIDTH);
+    let percent = (sent * 100).checked_div(total).unwrap_or(100);
+
+    print!(
+        "\rUploading bundle... [{}{}] {percent:>3}% ({sent}/{total} bytes)",
+        "#".repeat(usize::try_from(filled).unwrap_or(0)),
+        " ".repeat(usize::try_from(BAR_WIDTH.saturating_sub(filled)).unwrap_or(0)),
+    );
+    let _ = std::io::stdout().flush();
+}
+
 /// Determines the authentication method from CLI arguments.
 fn determine_auth(args: &PublishArgs) -> Result<RegistryAuth> {
     if let Some(ref token) = args.token {
@@ -212,7 +504,7 @@ mod tests {
     fn test_determine_auth_none() {
         let args = PublishArgs {
             bundle: PathBuf::from("test.bundle"),
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: None,
             version: None,
             namespace: None,
@@ -221,6 +513,11 @@ mod tests {
             password: None,
             yes: false,
             timeout: 60,
+            signing_key: None,
+            key_id: "default".to_string(),
+            no_overwrite: false,
+            dry_run: DryRunArgs::default(),
+            store: StoreArgs::default(),
         };
 
         let auth = determine_auth(&args).unwrap();
@@ -231,7 +528,7 @@ mod tests {
     fn test_determine_auth_bearer() {
         let args = PublishArgs {
             bundle: PathBuf::from("test.bundle"),
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: None,
             version: None,
             namespace: None,
@@ -240,6 +537,11 @@ mod tests {
             password: None,
             yes: false,
             timeout: 60,
+            signing_key: None,
+            key_id: "default".to_string(),
+            no_overwrite: false,
+            dry_run: DryRunArgs::default(),
+            store: StoreArgs::default(),
         };
 
         let auth = determine_auth(&args).unwrap();
@@ -253,7 +555,7 @@ mod tests {
     fn test_determine_auth_basic() {
         let args = PublishArgs {
             bundle: PathBuf::from("test.bundle"),
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: None,
             version: None,
             namespace: None,
@@ -262,6 +564,11 @@ mod tests {
             password: Some("pass".to_string()),
             yes: false,
             timeout: 60,
+            signing_key: None,
+            key_id: "default".to_string(),
+            no_overwrite: false,
+            dry_run: DryRunArgs::default(),
+            store: StoreArgs::default(),
         };
 
         let auth = determine_auth(&args).unwrap();
@@ -278,7 +585,7 @@ mod tests {
     fn test_determine_auth_incomplete_basic() {
         let args = PublishArgs {
             bundle: PathBuf::from("test.bundle"),
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: None,
             version: None,
             namespace: None,
@@ -287,6 +594,11 @@ mod tests {
             password: None,
             yes: false,
             timeout: 60,
+            signing_key: None,
+            key_id: "default".to_string(),
+            no_overwrite: false,
+            dry_run: DryRunArgs::default(),
+            store: StoreArgs::default(),
         };
 
         let result = determine_auth(&args);
@@ -297,7 +609,7 @@ mod tests {
     fn test_determine_auth_bearer_takes_precedence() {
         let args = PublishArgs {
             bundle: PathBuf::from("test.bundle"),
-            registry: "https://registry.example.com".to_string(),
+            registry: Some("https://registry.example.com".to_string()),
             service: None,
             version: None,
             namespace: None,
@@ -306,9 +618,123 @@ mod tests {
             password: Some("pass".to_string()),
             yes: false,
             timeout: 60,
+            signing_key: None,
+            key_id: "default".to_string(),
+            no_overwrite: false,
+            dry_run: DryRunArgs::default(),
+            store: StoreArgs::default(),
         };
 
         let auth = determine_auth(&args).unwrap();
         assert!(matches!(auth, RegistryAuth::Bearer { .. }));
     }
+
+    #[tokio::test]
+    async fn test_dry_run_makes_no_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("users.bundle");
+        Bundle::builder("users-service")
+            .version("1.2.0")
+            .add_policy("users_service.authz", "package users_service.authz\n")
+            .build()
+            .write_to_file(&path)
+            .unwrap();
+
+        let mut args = PublishArgs {
+            bundle: path,
+            // Nothing listens here, so any request would fail the run
+            registry: Some("http://127.0.0.1:9".to_string()),
+            service: None,
+            version: None,
+            namespace: Some("acme".to_string()),
+            token: None,
+            username: None,
+            password: None,
+            yes: false,
+            timeout: 1,
+            signing_key: None,
+            key_id: "default".to_string(),
+            no_overwrite: false,
+            dry_run: DryRunArgs {
+                enabled: true,
+                format: PublishFormat::Json,
+                check_remote: false,
+            },
+            store: StoreArgs::default(),
+        };
+        run(&args, OutputFormat::Text).await.unwrap();
+
+        args.dry_run.format = PublishFormat::Text;
+        run(&args, OutputFormat::Text).await.unwrap();
+
+        // Checking the tag needs the registry
+        args.no_overwrite = true;
+        let err = run(&args, OutputFormat::Text).await.unwrap_err();
+        assert!(err.to_string().contains("--check-remote"));
+        args.dry_run.check_remote = true;
+        assert!(run(&args, OutputFormat::Text).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_publish_rejects_invalid_bundle() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("users.bundle");
+        Bundle::builder("users-service")
+            .version("latest")
+            .add_policy("users_service.authz", "package users_service.authz\n")
+            .build()
+            .write_to_file(&path)
+            .unwrap();
+
+        let args = PublishArgs {
+            bundle: path,
+            registry: Some("http://127.0.0.1:9".to_string()),
+            service: None,
+            version: None,
+            namespace: None,
+            token: None,
+            username: None,
+            password: None,
+            yes: false,
+            timeout: 1,
+            signing_key: None,
+            key_id: "default".to_string(),
+            no_overwrite: false,
+            dry_run: DryRunArgs {
+                enabled: true,
+                format: PublishFormat::Text,
+                check_remote: false,
+            },
+            store: StoreArgs::default(),
+        };
+        let err = run(&args, OutputFormat::Text).await.unwrap_err();
+        assert!(err.to_string().contains("failed validation"));
+    }
+
+    #[test]
+    fn test_sign_bundle_keeps_existing_signatures() {
+        use eunomia_core::{BundleVerifier, SigningKeyPair};
+
+        let dir = tempfile::tempdir().unwrap();
+        let first = SigningKeyPair::generate();
+        let second = SigningKeyPair::generate_ecdsa_p256();
+        let second_path = dir.path().join("second.pem");
+        std::fs::write(&second_path, second.to_pem()).unwrap();
+
+        let bundle = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("users_service.authz", "package users_service.authz\n")
+            .build();
+        let bundle = BundleSigner::from_key_pair(&first, "first".to_string())
+            .sign(&bundle)
+            .bundle;
+
+        let signed = sign_bundle(bundle, &second_path, "second").unwrap();
+        assert_eq!(signed.signatures.signatures.len(), 2);
+
+        let mut verifier = BundleVerifier::new().with_threshold(2);
+        verifier.add_public_key("first", first.verifying_key());
+        verifier.add_public_key("second", second.verifying_key());
+        verifier.verify(&signed).unwrap();
+    }
 }
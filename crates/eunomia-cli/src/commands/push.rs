@@ -3,27 +3,42 @@
 //! This command uses the distributor to push policy bundles to target instances
 //! using various deployment strategies.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
 use eunomia_audit::{AuditLogger, DistributionEvent, TracingBackend};
 use eunomia_distributor::{
     config::{DiscoveryConfig, DistributorConfig},
     discovery::DiscoverySource,
+    grpc::types::{GrpcDeploymentStrategy, GrpcStrategyType, ScheduleDeploymentRequest},
+    grpc::ControlPlaneClient,
     strategy::DeploymentStrategy,
-    Distributor,
+    Distributor, ScheduleConstraints, SignatureConfig, TimeWindow,
 };
+use eunomia_registry::{
+    BundleCache, CacheConfig, RegistryAuth, RegistryClient, RegistryConfig, VersionQuery,
+};
+
+use super::completions::complete_services;
+use super::fetch::load_verifier;
+use crate::output::OutputFormat;
 
 /// Arguments for the push command.
 #[derive(Args)]
+// One bool per independent `--flag`, as clap expects
+#[allow(clippy::struct_excessive_bools)]
 pub struct PushArgs {
     /// Target service name
-    #[arg(short, long)]
+    #[arg(short, long, add = ArgValueCompleter::new(complete_services))]
     pub service: String,
 
-    /// Policy version to deploy
+    /// Policy version to deploy: an exact version, or with --registry a
+    /// range such as `^1.2` or a channel tag such as "stable"
     #[arg(short, long)]
     pub version: String,
 
@@ -56,36 +71,124 @@ pub struct PushArgs {
     pub max_concurrent: usize,
 
     /// Enable auto-rollback on failure
-    #[arg(long)]
-    pub auto_rollback: bool,
+    #[arg(
+        long,
+        num_args = 0,
+        default_missing_value = "true",
+        overrides_with = "no_auto_rollback"
+    )]
+    pub auto_rollback: Option<bool>,
+
+    /// Disable auto-rollback, also when .eunomia.toml enables it
+    #[arg(long, overrides_with = "auto_rollback")]
+    pub no_auto_rollback: bool,
 
     /// Maximum failures before stopping
     #[arg(long, default_value = "1")]
     pub max_failures: u32,
 
-    /// Dry run - don't actually push
+    /// Push to instances that already run this version
+    #[arg(long)]
+    pub force: bool,
+
+    /// Also push to instances the health monitor reports as unhealthy
+    #[arg(long)]
+    pub include_unhealthy: bool,
+
+    /// Dry run - health check the instances and report the planned
+    /// action for each, without pushing
     #[arg(long)]
     pub dry_run: bool,
 
-    /// Output format: text, json
-    #[arg(long, default_value = "text")]
-    pub output: String,
+    /// Schedule the deployment on the control plane instead of pushing:
+    /// it starts at an RFC 3339 time, or inside a weekly UTC window such
+    /// as "Mon-Fri 09:00-16:00" (repeatable)
+    #[arg(
+        long,
+        value_name = "WHEN",
+        conflicts_with_all = ["dry_run", "force", "include_unhealthy"]
+    )]
+    pub schedule: Vec<String>,
+
+    /// Control plane gRPC endpoint that --schedule queues the deployment on
+    #[arg(
+        long,
+        env = "EUNOMIA_CONTROL_PLANE",
+        default_value = "http://localhost:50052"
+    )]
+    pub control_plane: String,
+
+    /// Output format (defaults to --result-format)
+    #[arg(long, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    #[command(flatten)]
+    pub registry: PushRegistryArgs,
+}
+
+/// Registry the push command resolves version queries in and fetches the
+/// bundle from, to check it against the push limits and its signatures.
+#[derive(Args, Debug, Clone, Default)]
+pub struct PushRegistryArgs {
+    /// Registry URL (e.g., `<https://registry.example.com>`)
+    #[arg(
+        id = "registry",
+        short,
+        long = "registry",
+        env = "EUNOMIA_REGISTRY_URL"
+    )]
+    pub url: Option<String>,
+
+    /// Namespace prefix for the repository
+    #[arg(long, env = "EUNOMIA_REGISTRY_NAMESPACE")]
+    pub namespace: Option<String>,
+
+    /// Bearer token for authentication
+    #[arg(long, env = "EUNOMIA_REGISTRY_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Resolve the version and fetch the bundle from the local cache
+    /// without contacting the registry
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Bundle cache directory (defaults to the user cache directory)
+    #[arg(long, env = "EUNOMIA_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Trusted public key as `[KEY_ID=]PATH_OR_BASE64` (repeatable); the
+    /// bundle must carry a valid signature from one of them
+    #[arg(long = "verify-key", value_name = "[KEY_ID=]KEY")]
+    pub verify_key: Vec<String>,
 }
 
 /// Execute the push command.
-pub async fn execute(args: PushArgs) -> Result<()> {
-    if args.endpoints.is_empty() {
+pub async fn execute(args: PushArgs, output: OutputFormat) -> Result<()> {
+    if args.endpoints.is_empty() && args.schedule.is_empty() {
         anyhow::bail!("At least one endpoint must be specified with --endpoints");
     }
 
-    print_deployment_header(&args);
-
     // Parse strategy
     let strategy = parse_strategy(&args)?;
-    println!("   Strategy config: {:?}", strategy.strategy_type());
+    if !output.is_structured() {
+        print_deployment_header(&args);
+        println!("   Strategy config: {:?}", strategy.strategy_type());
+    }
+
+    let constraints = parse_schedule(&args.schedule)?;
+
+    let registry = registry_client(&args.registry)?;
+    let version = resolve_version(registry.as_deref(), &args.service, &args.version).await?;
+    if version != args.version && !output.is_structured() {
+        println!("   Resolved version: {version}");
+    }
+
+    if !args.schedule.is_empty() {
+        return schedule_deployment(&args, &version, &constraints, output).await;
+    }
 
     // Create distributor config
-    let config = DistributorConfig {
+    let mut config = DistributorConfig {
         discovery: DiscoveryConfig {
             source: DiscoverySource::Static {
                 endpoints: args.endpoints.clone(),
@@ -94,16 +197,27 @@ pub async fn execute(args: PushArgs) -> Result<()> {
         },
         ..Default::default()
     };
-
-    if args.dry_run {
-        print_dry_run(&args.endpoints);
-        return Ok(());
+    if !args.registry.verify_key.is_empty() {
+        let verifier = load_verifier(&args.registry.verify_key)?;
+        config.signatures = Some(SignatureConfig::new(verifier));
     }
 
     // Create distributor and deploy
-    let distributor = Distributor::new(config)
+    let mut distributor = Distributor::new(config)
         .await
         .context("Failed to create distributor")?;
+    if let Some(registry) = registry {
+        distributor = distributor.with_registry(registry);
+    }
+
+    if args.dry_run {
+        let result = distributor
+            .deploy(&args.service, &version, strategy)
+            .await
+            .context("Dry run failed")?;
+        print_result(&result, output)?;
+        return Ok(());
+    }
 
     // Initialize audit logger
     let audit_logger = AuditLogger::builder()
@@ -113,7 +227,7 @@ pub async fn execute(args: PushArgs) -> Result<()> {
     // Emit deployment started event
     let started_event = DistributionEvent::deployment_started(
         &args.service,
-        &args.version,
+        &version,
         args.endpoints.len(),
         &args.strategy,
     );
@@ -122,14 +236,14 @@ pub async fn execute(args: PushArgs) -> Result<()> {
     }
 
     let result = distributor
-        .deploy(&args.service, &args.version, strategy)
+        .deploy(&args.service, &version, strategy)
         .await
         .context("Deployment failed")?;
 
     // Emit deployment completed event
     let completed_event = DistributionEvent::deployment_completed(
         &args.service,
-        &args.version,
+        &version,
         result.successful,
         result.failed,
     );
@@ -138,13 +252,57 @@ pub async fn execute(args: PushArgs) -> Result<()> {
     }
 
     // Output results
-    if args.output == "json" {
-        print_json_output(&result)?;
-    } else {
-        print_text_output(&result);
+    print_result(&result, output)
+}
+
+/// Creates the registry client of `--registry`, if given.
+fn registry_client(args: &PushRegistryArgs) -> Result<Option<Arc<RegistryClient>>> {
+    let Some(url) = &args.url else {
+        return Ok(None);
+    };
+
+    let mut config = RegistryConfig::new(url);
+    if let Some(namespace) = &args.namespace {
+        config = config.with_namespace(namespace);
+    }
+    if let Some(token) = &args.token {
+        config = config.with_auth(RegistryAuth::Bearer {
+            token: token.clone(),
+        });
     }
+    let cache_config = args
+        .cache_dir
+        .as_ref()
+        .map_or_else(CacheConfig::default, CacheConfig::new);
+    let cache = BundleCache::new(cache_config).context("Failed to open bundle cache")?;
+
+    let client = RegistryClient::new(config)
+        .context("Failed to create registry client")?
+        .with_cache(cache)
+        .with_offline(args.offline);
+    Ok(Some(Arc::new(client)))
+}
 
-    Ok(())
+/// Resolves the `--version` query in the registry, or in the cached
+/// versions with `--offline`.
+///
+/// Without a registry exact versions and digests are deployed as given,
+/// and ranges, channel tags and "latest" are refused.
+async fn resolve_version(
+    registry: Option<&RegistryClient>,
+    service: &str,
+    version: &str,
+) -> Result<String> {
+    let Some(registry) = registry else {
+        return match VersionQuery::parse(version)? {
+            VersionQuery::Exact(_) | VersionQuery::Digest(_) => Ok(version.to_string()),
+            _ => anyhow::bail!("Version '{version}' is a query: pass --registry to resolve it"),
+        };
+    };
+    registry
+        .resolve_version(service, version)
+        .await
+        .with_context(|| format!("Failed to resolve version '{version}' of {service}"))
 }
 
 fn print_deployment_header(args: &PushArgs) {
@@ -161,20 +319,23 @@ fn print_deployment_header(args: &PushArgs) {
     }
 }
 
-fn print_dry_run(endpoints: &[String]) {
-    println!("\n📋 Would deploy to:");
-    for (i, endpoint) in endpoints.iter().enumerate() {
-        println!("   {}. {}", i + 1, endpoint);
+fn print_result(
+    result: &eunomia_distributor::DeploymentResult,
+    output: OutputFormat,
+) -> Result<()> {
+    if !output.is_structured() {
+        print_text_output(result);
+        return Ok(());
     }
-    println!("\n✅ Dry run complete");
-}
 
-fn print_json_output(result: &eunomia_distributor::DeploymentResult) -> Result<()> {
-    let json = serde_json::to_string_pretty(&ResultOutput {
+    output.print(&ResultOutput {
         deployment_id: result.deployment_id.clone(),
         successful: result.successful,
         failed: result.failed,
         skipped: result.skipped,
+        dry_run: result.dry_run,
+        health_degraded: result.health_degraded,
+        rollback_triggered: result.rollback_triggered,
         instance_results: result
             .instance_results
             .iter()
@@ -189,6 +350,9 @@ fn print_json_output(result: &eunomia_distributor::DeploymentResult) -> Result<(
                     eunomia_distributor::InstanceResultStatus::Skipped => {
                         ("Skipped".to_string(), None)
                     }
+                    eunomia_distributor::InstanceResultStatus::DryRunSuccess => {
+                        ("DryRunSuccess".to_string(), None)
+                    }
                 };
                 InstanceResultOutput {
                     instance_id: r.instance_id.clone(),
@@ -197,9 +361,7 @@ fn print_json_output(result: &eunomia_distributor::DeploymentResult) -> Result<(
                 }
             })
             .collect(),
-    })?;
-    println!("{json}");
-    Ok(())
+    })
 }
 
 fn print_text_output(result: &eunomia_distributor::DeploymentResult) {
@@ -223,19 +385,116 @@ fn print_text_output(result: &eunomia_distributor::DeploymentResult) {
             let (status_icon, message) = match &r.status {
                 eunomia_distributor::InstanceResultStatus::Success => ("✅", "OK".to_string()),
                 eunomia_distributor::InstanceResultStatus::Failed(msg) => ("❌", msg.clone()),
-                eunomia_distributor::InstanceResultStatus::Skipped => ("⏭️", "Skipped".to_string()),
+                eunomia_distributor::InstanceResultStatus::Skipped => {
+                    ("⏭️", "Already at target version".to_string())
+                }
+                eunomia_distributor::InstanceResultStatus::DryRunSuccess => {
+                    ("✅", "Would deploy".to_string())
+                }
             };
             println!("   {} {} - {}", status_icon, r.instance_id, message);
         }
     }
 
-    if result.is_fully_successful() {
+    if result.dry_run {
+        if result.is_fully_successful() {
+            println!("\n✅ Dry run complete, no changes made");
+        } else {
+            println!("\n⚠️  Dry run found issues, no changes made");
+        }
+    } else if result.rollback_triggered {
+        println!("\n↩️  Health degraded, rolled back to the previous version");
+    } else if result.health_degraded {
+        println!("\n❌ Health degraded, deployment halted with nothing to roll back to");
+    } else if result.is_fully_successful() {
         println!("\n🎉 Deployment completed successfully!");
     } else {
         println!("\n⚠️  Deployment completed with issues");
     }
 }
 
+/// Parses `--schedule` values into deployment constraints.
+///
+/// RFC 3339 times set the earliest start; anything else is a time window.
+fn parse_schedule(schedule: &[String]) -> Result<ScheduleConstraints> {
+    let mut constraints = ScheduleConstraints::new();
+    for when in schedule {
+        if let Ok(at) = DateTime::parse_from_rfc3339(when) {
+            let at = at.with_timezone(&Utc);
+            constraints.not_before = Some(constraints.not_before.map_or(at, |t| t.max(at)));
+        } else {
+            let window: TimeWindow = when.parse().with_context(|| {
+                format!("Invalid --schedule '{when}': expected an RFC 3339 time or a window")
+            })?;
+            constraints = constraints.with_window(window);
+        }
+    }
+    Ok(constraints)
+}
+
+/// Queues the deployment on the control plane, which starts it once the
+/// schedule allows.
+async fn schedule_deployment(
+    args: &PushArgs,
+    version: &str,
+    constraints: &ScheduleConstraints,
+    output: OutputFormat,
+) -> Result<()> {
+    let request = ScheduleDeploymentRequest {
+        service: args.service.clone(),
+        version: version.to_string(),
+        strategy: Some(grpc_strategy(args)?),
+        not_before: constraints.not_before,
+        windows: constraints
+            .windows
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        freezes: Vec::new(),
+    };
+
+    let scheduled = ControlPlaneClient::connect(args.control_plane.clone())
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to connect to the control plane at {}",
+                args.control_plane
+            )
+        })?
+        .schedule_deployment(request)
+        .await
+        .map_err(|status| anyhow::anyhow!("ScheduleDeployment failed: {}", status.message()))?
+        .into_inner();
+
+    if output.is_structured() {
+        return output.print(&scheduled);
+    }
+    println!("\n🗓️  Deployment scheduled on {}", args.control_plane);
+    println!("   Scheduled ID: {}", scheduled.scheduled_id);
+    if let Some(next_run_at) = scheduled.next_run_at {
+        println!("   Starts at: {}", next_run_at.to_rfc3339());
+    }
+    Ok(())
+}
+
+/// Converts the strategy arguments for the control plane.
+fn grpc_strategy(args: &PushArgs) -> Result<GrpcDeploymentStrategy> {
+    let (strategy_type, delay) = match args.strategy.to_lowercase().as_str() {
+        "immediate" => (GrpcStrategyType::Immediate, 0),
+        "canary" => (GrpcStrategyType::Canary, args.canary_duration),
+        "rolling" => (GrpcStrategyType::Rolling, args.batch_delay),
+        other => anyhow::bail!("Unknown strategy '{other}'. Use: immediate, canary, or rolling"),
+    };
+    Ok(GrpcDeploymentStrategy {
+        strategy_type,
+        canary_percentage: i32::try_from(args.canary_percentage)?,
+        rolling_batch_size: i32::try_from(args.batch_size)?,
+        batch_delay_seconds: i64::try_from(delay)?,
+        auto_rollback: args.auto_rollback == Some(true),
+        max_failures: i32::try_from(args.max_failures)?,
+    })
+}
+
 fn parse_strategy(args: &PushArgs) -> Result<DeploymentStrategy> {
     let mut strategy = match args.strategy.to_lowercase().as_str() {
         "immediate" => DeploymentStrategy::immediate(),
@@ -249,7 +508,7 @@ fn parse_strategy(args: &PushArgs) -> Result<DeploymentStrategy> {
         other => anyhow::bail!("Unknown strategy '{other}'. Use: immediate, canary, or rolling"),
     };
 
-    if args.auto_rollback {
+    if args.auto_rollback == Some(true) {
         strategy = strategy.with_auto_rollback(true);
     }
 
@@ -257,6 +516,18 @@ fn parse_strategy(args: &PushArgs) -> Result<DeploymentStrategy> {
         strategy = strategy.with_max_failures(args.max_failures);
     }
 
+    if args.force {
+        strategy = strategy.with_force(true);
+    }
+
+    if args.include_unhealthy {
+        strategy = strategy.with_include_unhealthy(true);
+    }
+
+    if args.dry_run {
+        strategy = strategy.with_dry_run();
+    }
+
     Ok(strategy)
 }
 
@@ -266,6 +537,9 @@ struct ResultOutput {
     successful: usize,
     failed: usize,
     skipped: usize,
+    dry_run: bool,
+    health_degraded: bool,
+    rollback_triggered: bool,
     instance_results: Vec<InstanceResultOutput>,
 }
 
@@ -292,10 +566,16 @@ mod tests {
             batch_size: 1,
             batch_delay: 30,
             max_concurrent: 10,
-            auto_rollback: false,
+            auto_rollback: None,
+            no_auto_rollback: false,
             max_failures: 1,
+            force: false,
+            include_unhealthy: false,
             dry_run: false,
-            output: "text".to_string(),
+            schedule: vec![],
+            control_plane: "http://localhost:50052".to_string(),
+            output: None,
+            registry: PushRegistryArgs::default(),
         };
 
         let strategy = parse_strategy(&args).unwrap();
@@ -303,6 +583,27 @@ mod tests {
             strategy.strategy_type(),
             eunomia_distributor::strategy::StrategyType::Immediate
         );
+        assert!(!strategy.force());
+
+        let args = PushArgs {
+            force: true,
+            ..args
+        };
+        assert!(parse_strategy(&args).unwrap().force());
+        assert!(!parse_strategy(&args).unwrap().dry_run());
+
+        let args = PushArgs {
+            dry_run: true,
+            ..args
+        };
+        assert!(parse_strategy(&args).unwrap().dry_run());
+        assert!(!parse_strategy(&args).unwrap().include_unhealthy());
+
+        let args = PushArgs {
+            include_unhealthy: true,
+            ..args
+        };
+        assert!(parse_strategy(&args).unwrap().include_unhealthy());
     }
 
     #[test]
@@ -317,10 +618,16 @@ mod tests {
             batch_size: 1,
             batch_delay: 30,
             max_concurrent: 10,
-            auto_rollback: true,
+            auto_rollback: Some(true),
+            no_auto_rollback: false,
             max_failures: 2,
+            force: false,
+            include_unhealthy: false,
             dry_run: false,
-            output: "text".to_string(),
+            schedule: vec![],
+            control_plane: "http://localhost:50052".to_string(),
+            output: None,
+            registry: PushRegistryArgs::default(),
         };
 
         let strategy = parse_strategy(&args).unwrap();
@@ -328,6 +635,13 @@ mod tests {
             strategy.strategy_type(),
             eunomia_distributor::strategy::StrategyType::Canary
         );
+
+        let strategy = grpc_strategy(&args).unwrap();
+        assert_eq!(strategy.strategy_type, GrpcStrategyType::Canary);
+        assert_eq!(strategy.canary_percentage, 20);
+        assert_eq!(strategy.batch_delay_seconds, 600);
+        assert!(strategy.auto_rollback);
+        assert_eq!(strategy.max_failures, 2);
     }
 
     #[test]
@@ -342,10 +656,16 @@ mod tests {
             batch_size: 5,
             batch_delay: 60,
             max_concurrent: 10,
-            auto_rollback: false,
+            auto_rollback: None,
+            no_auto_rollback: false,
             max_failures: 1,
+            force: false,
+            include_unhealthy: false,
             dry_run: false,
-            output: "text".to_string(),
+            schedule: vec![],
+            control_plane: "http://localhost:50052".to_string(),
+            output: None,
+            registry: PushRegistryArgs::default(),
         };
 
         let strategy = parse_strategy(&args).unwrap();
@@ -367,13 +687,81 @@ mod tests {
             batch_size: 1,
             batch_delay: 30,
             max_concurrent: 10,
-            auto_rollback: false,
+            auto_rollback: None,
+            no_auto_rollback: false,
             max_failures: 1,
+            force: false,
+            include_unhealthy: false,
             dry_run: false,
-            output: "text".to_string(),
+            schedule: vec![],
+            control_plane: "http://localhost:50052".to_string(),
+            output: None,
+            registry: PushRegistryArgs::default(),
         };
 
         let result = parse_strategy(&args);
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_resolve_version_without_registry() {
+        let version = resolve_version(None, "users-service", "1.0.0").await;
+        assert_eq!(version.unwrap(), "1.0.0");
+
+        for query in ["^1.2", "stable", "latest"] {
+            let err = resolve_version(None, "users-service", query)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("pass --registry"), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_version_offline() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let bundle = eunomia_core::Bundle::builder("users-service")
+            .version("1.4.0")
+            .build();
+        for version in ["v1.2.0", "v1.4.0", "v2.0.0"] {
+            cache.put("users-service", version, &bundle).unwrap();
+        }
+        let args = PushRegistryArgs {
+            url: Some("http://127.0.0.1:9".to_string()),
+            offline: true,
+            cache_dir: Some(dir.path().to_path_buf()),
+            ..PushRegistryArgs::default()
+        };
+        let registry = registry_client(&args).unwrap().unwrap();
+
+        let version = resolve_version(Some(&registry), "users-service", "^1.2").await;
+        assert_eq!(version.unwrap(), "v1.4.0");
+
+        // Queries nothing cached satisfies fail instead of deploying as given
+        let err = resolve_version(Some(&registry), "users-service", "^3")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to resolve version '^3'"));
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        assert_eq!(parse_schedule(&[]).unwrap(), ScheduleConstraints::new());
+
+        let constraints = parse_schedule(&[
+            "2026-03-06T12:00:00+01:00".to_string(),
+            "Mon-Fri 09:00-16:00".to_string(),
+            "2026-03-06T10:00:00Z".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            constraints.not_before,
+            Some("2026-03-06T11:00:00Z".parse().unwrap())
+        );
+        assert_eq!(constraints.windows.len(), 1);
+        assert_eq!(constraints.windows[0].days.len(), 5);
+
+        let err = parse_schedule(&["tomorrow".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Invalid --schedule 'tomorrow'"));
+    }
 }
@@ -0,0 +1,265 @@
+//! Replay command implementation.
+//!
+//! Re-evaluates a decision log against a candidate bundle and reports the
+//! decisions that would flip. The log is streamed line by line, so it may be
+//! arbitrarily large. When `--max-changes` is given, more flipped decisions
+//! than that fail the command with exit code 1; errors that stop the replay,
+//! such as an unreadable bundle, exit with code 2.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anyhow::{Context, Result};
+use clap::Args;
+use tracing::info;
+
+use eunomia_test::{ChangedDecision, DecisionReplayer, ReplayReport, DEFAULT_SAMPLE_SIZE};
+
+use super::diff::{load_bundle, registry_client, BundleRef};
+use crate::output::OutputFormat;
+
+/// Arguments for the replay command.
+#[derive(Args)]
+pub struct ReplayArgs {
+    /// Decision log of authorization events or policy inputs, one JSON
+    /// document per line (`-` reads stdin)
+    pub log: PathBuf,
+
+    /// Bundle to replay against: a bundle file or `registry://service:version`
+    pub bundle: String,
+
+    /// Bundle that decides inputs with no recorded decision
+    #[arg(long)]
+    pub baseline: Option<String>,
+
+    /// Query that makes the decision (defaults to `data.<root>.authz.allow`)
+    #[arg(short, long)]
+    pub query: Option<String>,
+
+    /// Fail if more than this many decisions flip
+    #[arg(long)]
+    pub max_changes: Option<usize>,
+
+    /// Number of affected inputs to show per kind of change
+    #[arg(long, default_value_t = DEFAULT_SAMPLE_SIZE)]
+    pub samples: usize,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ReplayFormat::Text)]
+    pub format: ReplayFormat,
+
+    /// Registry URL for `registry://` references
+    #[arg(short, long, env = "EUNOMIA_REGISTRY_URL")]
+    pub registry: Option<String>,
+
+    /// Namespace prefix for the repository
+    #[arg(short, long, env = "EUNOMIA_REGISTRY_NAMESPACE")]
+    pub namespace: Option<String>,
+
+    /// Bearer token for authentication
+    #[arg(long, env = "EUNOMIA_REGISTRY_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+}
+
+/// Output format of the replay command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReplayFormat {
+    /// Human-readable report.
+    #[default]
+    Text,
+    /// The replay report as JSON, for automation.
+    Json,
+}
+
+/// Outcome of a replay that completed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayOutcome {
+    /// No more flipped decisions than `--max-changes`.
+    WithinLimit,
+    /// More flipped decisions than `--max-changes`.
+    LimitExceeded,
+}
+
+impl ReplayOutcome {
+    /// Returns the process exit code of the outcome.
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            Self::WithinLimit => ExitCode::SUCCESS,
+            Self::LimitExceeded => ExitCode::from(1),
+        }
+    }
+}
+
+/// Exit code of a replay that could not complete.
+pub const ERROR_EXIT_CODE: u8 = 2;
+
+/// Runs the replay command.
+pub async fn run(args: &ReplayArgs, output: OutputFormat) -> Result<ReplayOutcome> {
+    info!(log = %args.log.display(), bundle = %args.bundle, "Replaying decisions");
+
+    let bundle_ref = BundleRef::parse(&args.bundle)?;
+    let baseline_ref = args.baseline.as_deref().map(BundleRef::parse).transpose()?;
+    let client =
+        if bundle_ref.is_registry() || baseline_ref.as_ref().is_some_and(BundleRef::is_registry) {
+            Some(registry_client(
+                args.registry.as_deref(),
+                args.namespace.as_deref(),
+                args.token.as_deref(),
+            )?)
+        } else {
+            None
+        };
+
+    let bundle = load_bundle(&bundle_ref, client.as_ref()).await?;
+    let mut replayer = DecisionReplayer::new(&bundle)
+        .context("Failed to load bundle policies")?
+        .with_sample_size(args.samples);
+    if let Some(query) = &args.query {
+        replayer = replayer.with_query(query);
+    }
+    if let Some(baseline_ref) = &baseline_ref {
+        let baseline = load_bundle(baseline_ref, client.as_ref()).await?;
+        replayer = replayer
+            .with_baseline(&baseline)
+            .context("Failed to load baseline policies")?;
+    }
+
+    let report = replayer
+        .replay(open_log(&args.log)?)
+        .with_context(|| format!("Failed to replay {}", args.log.display()))?;
+
+    match args.format {
+        _ if output.is_structured() => output.print(&report)?,
+        ReplayFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+        ReplayFormat::Text => print!("{}", format_report(&report, replayer.query())),
+    }
+
+    Ok(match args.max_changes {
+        Some(max) if report.changes() > max => ReplayOutcome::LimitExceeded,
+        _ => ReplayOutcome::WithinLimit,
+    })
+}
+
+/// Opens the decision log, or stdin for `-`.
+fn open_log(path: &Path) -> Result<Box<dyn BufRead>> {
+    if path.as_os_str() == "-" {
+        return Ok(Box::new(std::io::stdin().lock()));
+    }
+    let file = File::open(path)
+        .with_context(|| format!("Failed to open decision log: {}", path.display()))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Formats a replay report in text form.
+fn format_report(report: &ReplayReport, query: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Eunomia Decision Replay");
+    let _ = writeln!(out, "=======================");
+    let _ = writeln!(out, "Query: {query}");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Decisions:     {}", report.total);
+    let _ = writeln!(out, "Unchanged:     {}", report.unchanged);
+    let _ = writeln!(out, "Allow -> deny: {}", report.allow_to_deny.count);
+    let _ = writeln!(out, "Deny -> allow: {}", report.deny_to_allow.count);
+    if report.unrecorded > 0 {
+        let _ = writeln!(out, "Unrecorded:    {}", report.unrecorded);
+    }
+    if report.unreplayable > 0 {
+        let _ = writeln!(out, "Unreplayable:  {}", report.unreplayable);
+    }
+    let _ = writeln!(out, "Errors:        {}", report.errors.count);
+
+    format_samples(&mut out, "Allow -> deny", &report.allow_to_deny.samples);
+    format_samples(&mut out, "Deny -> allow", &report.deny_to_allow.samples);
+    if !report.errors.samples.is_empty() {
+        let _ = writeln!(out);
+        let _ = writeln!(out, "Errors:");
+        for failure in &report.errors.samples {
+            let _ = writeln!(out, "  line {}: {}", failure.line, failure.message);
+        }
+    }
+
+    out
+}
+
+/// Formats the sampled inputs of one kind of change.
+fn format_samples(out: &mut String, title: &str, samples: &[ChangedDecision]) {
+    if samples.is_empty() {
+        return;
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{title}:");
+    for sample in samples {
+        let _ = writeln!(out, "  line {}: {}", sample.line, sample.input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eunomia_test::{ReplayFailure, Sampled};
+    use serde_json::json;
+
+    #[test]
+    fn test_format_report() {
+        let report = ReplayReport {
+            total: 3,
+            unchanged: 1,
+            allow_to_deny: Sampled {
+                count: 1,
+                samples: vec![ChangedDecision {
+                    line: 2,
+                    input: json!({"caller": {"type": "anonymous"}}),
+                }],
+            },
+            errors: Sampled {
+                count: 1,
+                samples: vec![ReplayFailure {
+                    line: 3,
+                    message: "Parse error: bad line".to_string(),
+                }],
+            },
+            ..ReplayReport::default()
+        };
+
+        let text = format_report(&report, "data.authz.allow");
+
+        assert!(text.contains("Query: data.authz.allow\n"));
+        assert!(text.contains("Allow -> deny: 1\n"));
+        assert!(text.contains("Deny -> allow: 0\n"));
+        assert!(!text.contains("Unrecorded"));
+        assert!(text.contains("Allow -> deny:\n  line 2: {\"caller\":{\"type\":\"anonymous\"}}\n"));
+        assert!(text.contains("Errors:\n  line 3: Parse error: bad line\n"));
+    }
+
+    #[tokio::test]
+    async fn test_replay_missing_log_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let bundle_path = dir.path().join("bundle.tar.gz");
+        eunomia_core::Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("users_service.authz", "package users_service.authz\n")
+            .build()
+            .write_to_file(&bundle_path)
+            .unwrap();
+
+        let args = ReplayArgs {
+            log: dir.path().join("missing.jsonl"),
+            bundle: bundle_path.to_string_lossy().to_string(),
+            baseline: None,
+            query: None,
+            max_changes: None,
+            samples: DEFAULT_SAMPLE_SIZE,
+            format: ReplayFormat::Text,
+            registry: None,
+            namespace: None,
+            token: None,
+        };
+
+        let err = run(&args, OutputFormat::Text).await.unwrap_err();
+        assert!(format!("{err:#}").contains("Failed to open decision log"));
+    }
+}
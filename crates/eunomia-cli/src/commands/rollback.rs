@@ -27,6 +27,7 @@ use std::time::Instant;
 
 use anyhow::{anyhow, Context, Result};
 use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
 use eunomia_audit::{AuditLogger, DistributionEvent, TracingBackend};
 use eunomia_distributor::{
     config::{DiscoveryConfig, DistributorConfig},
@@ -36,11 +37,17 @@ use eunomia_distributor::{
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use super::completions::complete_services;
+use crate::output::OutputFormat;
+
 /// Arguments for the rollback command.
 #[derive(Debug, Args)]
 pub struct RollbackArgs {
     /// Service name to rollback
-    #[arg(help = "Name of the service to rollback")]
+    #[arg(
+        help = "Name of the service to rollback",
+        add = ArgValueCompleter::new(complete_services)
+    )]
     pub service: String,
 
     /// Target version to rollback to (defaults to previous version)
@@ -85,14 +92,9 @@ pub struct RollbackArgs {
     #[arg(long, value_delimiter = ',')]
     pub endpoints: Vec<String>,
 
-    /// Output format
-    #[arg(
-        short,
-        long,
-        default_value = "text",
-        help = "Output format: text or json"
-    )]
-    pub output: String,
+    /// Output format (defaults to --result-format)
+    #[arg(short, long, value_enum)]
+    pub output: Option<OutputFormat>,
 
     /// Path to deployment state file
     #[arg(long, help = "Path to deployment state file")]
@@ -210,13 +212,13 @@ pub struct VersionHistoryEntry {
 /// - Service not found
 /// - No previous version available
 /// - Rollback fails
-pub fn run(args: &RollbackArgs) -> Result<()> {
+pub fn run(args: &RollbackArgs, output: OutputFormat) -> Result<()> {
     let strategy = parse_strategy(&args.strategy)?;
 
     if args.dry_run {
-        run_dry_run(args, strategy)
+        run_dry_run(args, strategy, output)
     } else {
-        run_rollback(args, strategy);
+        run_rollback(args, strategy, output);
         Ok(())
     }
 }
@@ -232,7 +234,11 @@ fn parse_strategy(s: &str) -> Result<RollbackStrategy> {
     }
 }
 
-fn run_dry_run(args: &RollbackArgs, strategy: RollbackStrategy) -> Result<()> {
+fn run_dry_run(
+    args: &RollbackArgs,
+    strategy: RollbackStrategy,
+    output: OutputFormat,
+) -> Result<()> {
     // Note: In production, this would connect to the control plane gRPC service
     // to fetch actual deployment state. For CLI dry-run, we show simulated data.
 
@@ -254,8 +260,8 @@ fn run_dry_run(args: &RollbackArgs, strategy: RollbackStrategy) -> Result<()> {
         warnings: vec![],
     };
 
-    if args.output == "json" {
-        println!("{}", serde_json::to_string_pretty(&plan)?);
+    if output.is_structured() {
+        output.print(&plan)?;
     } else {
         println!("Rollback Plan (Dry Run)");
         println!("========================");
@@ -280,11 +286,11 @@ fn run_dry_run(args: &RollbackArgs, strategy: RollbackStrategy) -> Result<()> {
     Ok(())
 }
 
-fn run_rollback(args: &RollbackArgs, strategy: RollbackStrategy) {
+fn run_rollback(args: &RollbackArgs, strategy: RollbackStrategy, output: OutputFormat) {
     // Execute rollback via Distributor
     let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
 
-    if let Err(e) = rt.block_on(execute_rollback(args, strategy)) {
+    if let Err(e) = rt.block_on(execute_rollback(args, strategy, output)) {
         eprintln!("Rollback failed: {e:#}");
         std::process::exit(1);
     }
@@ -292,7 +298,11 @@ fn run_rollback(args: &RollbackArgs, strategy: RollbackStrategy) {
 
 /// Execute the rollback operation asynchronously.
 #[allow(clippy::too_many_lines)]
-async fn execute_rollback(args: &RollbackArgs, strategy: RollbackStrategy) -> Result<()> {
+async fn execute_rollback(
+    args: &RollbackArgs,
+    strategy: RollbackStrategy,
+    output: OutputFormat,
+) -> Result<()> {
     let start = Instant::now();
 
     // Build discovery configuration from endpoints
@@ -340,15 +350,17 @@ async fn execute_rollback(args: &RollbackArgs, strategy: RollbackStrategy) -> Re
         tracing::warn!("Failed to emit rollback started audit event: {e}");
     }
 
-    println!("Starting rollback...");
-    println!("  Service:        {}", args.service);
-    println!("  Target Version: {target_version}");
-    println!("  Strategy:       {strategy}");
-    println!("  Endpoints:      {:?}", args.endpoints);
-    if let Some(reason) = &args.reason {
-        println!("  Reason:         {reason}");
+    if !output.is_structured() {
+        println!("Starting rollback...");
+        println!("  Service:        {}", args.service);
+        println!("  Target Version: {target_version}");
+        println!("  Strategy:       {strategy}");
+        println!("  Endpoints:      {:?}", args.endpoints);
+        if let Some(reason) = &args.reason {
+            println!("  Reason:         {reason}");
+        }
+        println!();
     }
-    println!();
 
     // Execute rollback
     let result = distributor
@@ -381,9 +393,9 @@ async fn execute_rollback(args: &RollbackArgs, strategy: RollbackStrategy) -> Re
     let total_instances = result.successful + result.failed + result.skipped;
 
     // Output results
-    if args.output == "json" {
+    if output.is_structured() {
         #[allow(clippy::cast_possible_truncation)]
-        let output = RollbackResult {
+        let rollback = RollbackResult {
             service: args.service.clone(),
             from_version: "unknown".to_string(), // Would come from state tracking
             to_version: target_version.to_string(),
@@ -394,7 +406,7 @@ async fn execute_rollback(args: &RollbackArgs, strategy: RollbackStrategy) -> Re
             dry_run: false,
             reason: args.reason.clone(),
         };
-        println!("{}", serde_json::to_string_pretty(&output)?);
+        output.print(&rollback)?;
     } else {
         println!("Rollback Complete");
         println!("=================");
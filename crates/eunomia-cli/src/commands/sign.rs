@@ -7,7 +7,7 @@ use anyhow::{Context, Result};
 use clap::Args;
 use tracing::info;
 
-use eunomia_core::signing::{BundleSigner, SigningKeyPair};
+use eunomia_core::signing::{BundleSigner, SigningAlgorithm, SigningKeyPair};
 use eunomia_core::Bundle;
 
 /// Arguments for the sign command.
@@ -17,7 +17,8 @@ pub struct SignArgs {
     #[arg(required_unless_present = "generate_key")]
     pub bundle: Option<PathBuf>,
 
-    /// Path to the private key file (base64-encoded Ed25519 key)
+    /// Path to the private key file (base64-encoded Ed25519 key, or PKCS#8 PEM
+    /// Ed25519 or ECDSA P-256 key)
     #[arg(short, long)]
     pub key_file: Option<PathBuf>,
 
@@ -30,19 +31,23 @@ pub struct SignArgs {
     pub key_id: String,
 
     /// Output path for signed bundle (defaults to overwriting input)
-    #[arg(short, long)]
-    pub output: Option<PathBuf>,
+    #[arg(short, long = "output")]
+    pub output_file: Option<PathBuf>,
 
     /// Generate a new signing key pair and print it
     #[arg(long)]
     pub generate_key: bool,
+
+    /// Algorithm for --generate-key (ed25519 or ecdsa-p256)
+    #[arg(long, default_value = "ed25519", requires = "generate_key")]
+    pub algorithm: SigningAlgorithm,
 }
 
 /// Runs the sign command.
 pub fn run(args: &SignArgs) -> Result<()> {
     // Handle key generation mode
     if args.generate_key {
-        generate_key();
+        generate_key(args.algorithm);
         return Ok(());
     }
 
@@ -55,7 +60,7 @@ pub fn run(args: &SignArgs) -> Result<()> {
     info!(bundle = ?bundle_path, key_id = %args.key_id, "Signing bundle");
 
     // Load the private key
-    let key_base64 = if let Some(key_file) = &args.key_file {
+    let key_material = if let Some(key_file) = &args.key_file {
         fs::read_to_string(key_file)
             .with_context(|| format!("Failed to read key file: {}", key_file.display()))?
     } else if let Some(key) = &args.key {
@@ -67,8 +72,8 @@ pub fn run(args: &SignArgs) -> Result<()> {
     };
 
     // Create the bundle signer
-    let bundle_signer = BundleSigner::from_base64(&key_base64, args.key_id.clone())
-        .context("Invalid signing key format")?;
+    let key_pair = parse_key_pair(&key_material)?;
+    let bundle_signer = BundleSigner::from_key_pair(&key_pair, args.key_id.clone());
 
     // Ensure bundle exists
     if !bundle_path.exists() {
@@ -96,28 +101,18 @@ pub fn run(args: &SignArgs) -> Result<()> {
     println!("✓");
 
     // Write the signed bundle
-    let output_path = args.output.as_ref().unwrap_or(bundle_path);
+    let output_path = args.output_file.as_ref().unwrap_or(bundle_path);
     print!("Writing signed bundle to {}... ", output_path.display());
 
-    // For now, write the signature to a separate file
-    let sig_file = output_path.with_extension("sig");
-    let sig_json = signed_bundle
-        .signatures
-        .to_json()
-        .context("Failed to serialize signature")?;
-
-    fs::write(&sig_file, &sig_json).context("Failed to write signature file")?;
+    // The signature is embedded as .signatures/.manifest.sig in the archive
+    signed_bundle
+        .write_to_file(output_path)
+        .context("Failed to write signed bundle")?;
     println!("✓");
 
-    // Also update the bundle with signature info if it's a new output
-    if args.output.is_some() {
-        // Copy the original bundle to the new location
-        fs::copy(bundle_path, output_path).context("Failed to copy bundle")?;
-    }
-
     println!();
     println!("Bundle signed successfully!");
-    println!("  Signature: {}", sig_file.display());
+    println!("  Output:    {}", output_path.display());
     println!(
         "  Algorithm: {}",
         signed_bundle
@@ -131,18 +126,44 @@ pub fn run(args: &SignArgs) -> Result<()> {
     Ok(())
 }
 
+/// Parses a private key given as PKCS#8 PEM or base64.
+pub fn parse_key_pair(key_material: &str) -> Result<SigningKeyPair> {
+    if key_material.trim_start().starts_with("-----BEGIN") {
+        SigningKeyPair::from_pem(key_material)
+    } else {
+        SigningKeyPair::from_base64(key_material)
+    }
+    .context("Invalid signing key format")
+}
+
 /// Generates a new signing key pair and prints it.
-fn generate_key() {
-    println!("Generating new Ed25519 signing key pair...");
+///
+/// Ed25519 keys are printed as base64; ECDSA P-256 keys as PEM, since a raw
+/// 32-byte secret would be indistinguishable from an Ed25519 seed.
+fn generate_key(algorithm: SigningAlgorithm) {
+    let key_pair = match algorithm {
+        SigningAlgorithm::Ed25519 => {
+            println!("Generating new Ed25519 signing key pair...");
+            SigningKeyPair::generate()
+        }
+        SigningAlgorithm::EcdsaP256 => {
+            println!("Generating new ECDSA P-256 signing key pair...");
+            SigningKeyPair::generate_ecdsa_p256()
+        }
+    };
     println!();
 
-    let key_pair = SigningKeyPair::generate();
-
     println!("Private Key (keep this secret!):");
-    println!("{}", key_pair.to_base64());
+    match key_pair.to_base64() {
+        Some(encoded) => println!("{encoded}"),
+        None => print!("{}", key_pair.to_pem()),
+    }
     println!();
     println!("Public Key (distribute to verifiers):");
-    println!("{}", key_pair.public_key_base64());
+    match algorithm {
+        SigningAlgorithm::Ed25519 => println!("{}", key_pair.public_key_base64()),
+        SigningAlgorithm::EcdsaP256 => print!("{}", key_pair.public_key_pem()),
+    }
     println!();
     println!("Usage:");
     println!("  1. Save the private key to a file (e.g., signing.key)");
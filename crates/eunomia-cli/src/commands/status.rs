@@ -1,43 +1,114 @@
 //! Status command implementation.
-
-use std::path::{Path, PathBuf};
-
-use anyhow::Result;
+//!
+//! Shows the latest deployment of each service, as recorded by the control
+//! plane at `--endpoint`, with the policy version and health of its
+//! instances. With `--history` the past deployments are shown instead,
+//! fetched with the `ListDeployments` call.
+//!
+//! With `--watch` the deployments are polled with `GetDeployment` and
+//! redrawn as a table of instances, highlighting what changed since the
+//! previous poll, until every deployment shown reaches a terminal state or
+//! `--timeout` passes. The command then fails if any of them failed, was
+//! rolled back, cancelled or interrupted.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
 use clap::Args;
+use clap_complete::engine::ArgValueCompleter;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
+use eunomia_distributor::grpc::types::{
+    DeploymentRecord, GetDeploymentRequest, GetPolicyStatusRequest, GrpcDeploymentState,
+    GrpcHealthState, ListDeploymentsRequest,
+};
+use eunomia_distributor::grpc::ControlPlaneClient;
+
+use super::completions::complete_services;
+use crate::output::OutputFormat;
+use crate::watch;
+
+/// Terminal width assumed when it cannot be determined.
+const DEFAULT_WIDTH: usize = 80;
+
+/// Narrowest the instance column of the watch table gets.
+const MIN_INSTANCE_WIDTH: usize = 12;
+
+/// Most recent deployments searched for the latest one of each service.
+const STATUS_SCAN_LIMIT: usize = 100;
+
 /// Arguments for the status command.
 #[derive(Args)]
 pub struct StatusArgs {
+    /// Service name to check status for (same as --service)
+    #[arg(
+        value_name = "SERVICE",
+        conflicts_with = "service",
+        add = ArgValueCompleter::new(complete_services)
+    )]
+    pub service_name: Option<String>,
+
     /// Service name to check status for
-    #[arg(short, long)]
+    #[arg(short, long, add = ArgValueCompleter::new(complete_services))]
     pub service: Option<String>,
 
-    /// Path to the state directory
-    #[arg(long, default_value = ".eunomia")]
-    pub state_dir: PathBuf,
+    /// Control plane gRPC endpoint
+    #[arg(
+        long,
+        visible_alias = "control-plane",
+        env = "EUNOMIA_CONTROL_PLANE",
+        default_value = "http://localhost:50052"
+    )]
+    pub endpoint: String,
 
-    /// Output format (text, json)
-    #[arg(short, long, default_value = "text")]
-    pub format: OutputFormat,
+    /// Output format (defaults to --result-format)
+    #[arg(short, long, value_enum)]
+    pub format: Option<OutputFormat>,
 
     /// Show verbose output
     #[arg(short, long)]
     pub verbose: bool,
+
+    /// Show past deployments instead of the current status
+    #[arg(long)]
+    pub history: bool,
+
+    /// Maximum number of deployments to show with --history
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Poll and redraw the instances until the deployment finishes, exiting
+    /// with an error if it failed
+    #[arg(short, long, conflicts_with = "history")]
+    pub watch: bool,
+
+    /// Seconds between polls with --watch
+    #[arg(long, default_value_t = 2, requires = "watch")]
+    pub interval: u64,
+
+    /// Seconds to wait with --watch for the deployment to finish before
+    /// giving up
+    #[arg(long, default_value_t = 1800, requires = "watch")]
+    pub timeout: u64,
 }
 
-/// Output format for status command.
-#[derive(Clone, Debug, Default, clap::ValueEnum)]
-pub enum OutputFormat {
-    #[default]
-    Text,
-    Json,
+impl StatusArgs {
+    /// Returns the service to show, given positionally or with --service.
+    pub fn service(&self) -> Option<&str> {
+        self.service_name.as_deref().or(self.service.as_deref())
+    }
 }
 
 /// Deployment status information.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeploymentStatus {
+    pub deployment_id: String,
     pub service: String,
     pub version: String,
     pub status: String,
@@ -48,14 +119,15 @@ pub struct DeploymentStatus {
 /// Instance status information.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InstanceStatus {
-    pub endpoint: String,
+    pub instance_id: String,
     pub status: String,
     pub version: Option<String>,
-    pub last_health_check: Option<String>,
+    pub last_updated: Option<String>,
+    pub last_seen: Option<String>,
 }
 
 /// Overall status summary.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StatusSummary {
     pub deployments: Vec<DeploymentStatus>,
     pub total_instances: usize,
@@ -64,57 +136,459 @@ pub struct StatusSummary {
 }
 
 /// Runs the status command.
-pub fn run(args: &StatusArgs) -> Result<()> {
-    info!(service = ?args.service, "Checking deployment status");
+pub async fn run(args: &StatusArgs, output: OutputFormat) -> Result<()> {
+    info!(service = ?args.service(), "Checking deployment status");
+    let format = args.format.unwrap_or(output);
 
-    // Read deployment state from state directory
-    let summary = read_deployment_state(&args.state_dir, args.service.as_deref())?;
+    if args.watch && format.is_structured() {
+        anyhow::bail!("--watch is not supported with JSON or YAML output");
+    }
+    let mut client = connect(&args.endpoint).await?;
 
-    match args.format {
-        OutputFormat::Text => print_text_status(&summary, args.verbose),
-        OutputFormat::Json => print_json_status(&summary)?,
+    if args.watch {
+        return watch_status(args, &mut client, &watch::stop_on_ctrl_c()).await;
+    }
+
+    if args.history {
+        let deployments = fetch_deployment_history(&mut client, args.service(), args.limit).await?;
+        if format.is_structured() {
+            format.print(&deployments)?;
+        } else {
+            print_text_history(&deployments, args.verbose);
+        }
+        return Ok(());
+    }
+
+    let deployments = latest_deployments(&mut client, args.service()).await?;
+    let summary = summarize(&mut client, deployments).await?;
+    drop(client);
+
+    if format.is_structured() {
+        format.print(&summary)?;
+    } else {
+        print_text_status(&summary, args.verbose);
     }
 
     Ok(())
 }
 
-fn read_deployment_state(state_dir: &Path, service_filter: Option<&str>) -> Result<StatusSummary> {
-    let state_file = state_dir.join("deployments.json");
+/// Connects to the control plane at `endpoint`.
+async fn connect(endpoint: &str) -> Result<ControlPlaneClient> {
+    ControlPlaneClient::connect(endpoint.to_string())
+        .await
+        .with_context(|| format!("Failed to connect to the control plane at {endpoint}"))
+}
 
-    // If no state file exists, return empty status
-    if !state_file.exists() {
-        return Ok(StatusSummary {
-            deployments: Vec::new(),
-            total_instances: 0,
-            healthy_instances: 0,
-            unhealthy_instances: 0,
+/// Fetches up to `limit` deployments from the control plane, newest first,
+/// optionally for one service.
+async fn fetch_deployment_history(
+    client: &mut ControlPlaneClient,
+    service_filter: Option<&str>,
+    limit: usize,
+) -> Result<Vec<DeploymentRecord>> {
+    let mut deployments = Vec::new();
+    let mut page_token = String::new();
+    while deployments.len() < limit {
+        let page = client
+            .list_deployments(ListDeploymentsRequest {
+                service_filter: service_filter.unwrap_or_default().to_string(),
+                page_size: i32::try_from(limit - deployments.len()).unwrap_or(i32::MAX),
+                page_token,
+                ..Default::default()
+            })
+            .await
+            .map_err(|status| anyhow::anyhow!("ListDeployments failed: {}", status.message()))?
+            .into_inner();
+
+        deployments.extend(page.deployments);
+        if page.next_page_token.is_empty() {
+            break;
+        }
+        page_token = page.next_page_token;
+    }
+    Ok(deployments)
+}
+
+/// Fetches the latest deployment of each service, or of one service, from
+/// the control plane.
+async fn latest_deployments(
+    client: &mut ControlPlaneClient,
+    service_filter: Option<&str>,
+) -> Result<Vec<DeploymentRecord>> {
+    let limit = if service_filter.is_some() {
+        1
+    } else {
+        STATUS_SCAN_LIMIT
+    };
+    let mut seen = HashSet::new();
+    let mut deployments = fetch_deployment_history(client, service_filter, limit).await?;
+    deployments.retain(|d| seen.insert(d.service.clone()));
+    Ok(deployments)
+}
+
+/// Fetches the deployments with `ids` from the control plane.
+async fn fetch_deployments(
+    client: &mut ControlPlaneClient,
+    ids: &[String],
+) -> Result<Vec<DeploymentRecord>> {
+    let mut deployments = Vec::with_capacity(ids.len());
+    for deployment_id in ids {
+        let deployment = client
+            .get_deployment(GetDeploymentRequest {
+                deployment_id: deployment_id.clone(),
+            })
+            .await
+            .map_err(|status| anyhow::anyhow!("GetDeployment failed: {}", status.message()))?
+            .into_inner();
+        deployments.push(deployment);
+    }
+    Ok(deployments)
+}
+
+/// Summarizes `deployments` with the policy version and health of the
+/// instances of each service, fetched from the control plane.
+async fn summarize(
+    client: &mut ControlPlaneClient,
+    deployments: Vec<DeploymentRecord>,
+) -> Result<StatusSummary> {
+    let mut summary = StatusSummary::default();
+    for deployment in deployments {
+        let status = client
+            .get_policy_status(GetPolicyStatusRequest {
+                service: deployment.service.clone(),
+            })
+            .await
+            .map_err(|status| anyhow::anyhow!("GetPolicyStatus failed: {}", status.message()))?
+            .into_inner();
+
+        let instances: Vec<InstanceStatus> = status
+            .instance_statuses
+            .into_iter()
+            .map(|instance| InstanceStatus {
+                instance_id: instance.instance_id,
+                status: health_label(instance.health).to_string(),
+                version: Some(instance.version).filter(|v| !v.is_empty()),
+                last_updated: instance.last_updated.map(timestamp),
+                last_seen: instance.last_seen.map(timestamp),
+            })
+            .collect();
+        summary.total_instances += instances.len();
+        summary.healthy_instances += instances.iter().filter(|i| i.status == "healthy").count();
+
+        summary.deployments.push(DeploymentStatus {
+            last_updated: deployment
+                .completed_at
+                .or(deployment.started_at)
+                .map_or_else(|| "-".to_string(), timestamp),
+            status: state_label(deployment.state).to_string(),
+            deployment_id: deployment.deployment_id,
+            service: deployment.service,
+            version: deployment.version,
+            instances,
         });
     }
+    summary.unhealthy_instances = summary.total_instances - summary.healthy_instances;
+    Ok(summary)
+}
 
-    // Read and parse state file
-    let content = std::fs::read_to_string(&state_file)?;
-    let mut deployments: Vec<DeploymentStatus> = serde_json::from_str(&content)?;
+/// Formats a time as shown in the status.
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
 
-    // Filter by service if specified
-    if let Some(service) = service_filter {
-        deployments.retain(|d| d.service == service);
+/// Returns the status shown for a deployment state.
+const fn state_label(state: GrpcDeploymentState) -> &'static str {
+    match state {
+        GrpcDeploymentState::Completed => "deployed",
+        GrpcDeploymentState::InProgress => "deploying",
+        GrpcDeploymentState::Pending => "pending",
+        GrpcDeploymentState::Paused => "paused",
+        GrpcDeploymentState::Failed => "failed",
+        GrpcDeploymentState::RolledBack => "rolled_back",
+        GrpcDeploymentState::Cancelled => "cancelled",
+        GrpcDeploymentState::Interrupted => "interrupted",
+        GrpcDeploymentState::Unknown => "unknown",
     }
+}
 
-    // Calculate summary statistics
-    let total_instances: usize = deployments.iter().map(|d| d.instances.len()).sum();
-    let healthy_instances: usize = deployments
+/// Returns the status shown for an instance health state.
+const fn health_label(health: GrpcHealthState) -> &'static str {
+    match health {
+        GrpcHealthState::Healthy => "healthy",
+        GrpcHealthState::Degraded => "degraded",
+        GrpcHealthState::Unhealthy => "unhealthy",
+        GrpcHealthState::Unknown => "unknown",
+    }
+}
+
+/// Redraws the status every `args.interval` seconds until the deployments
+/// finish, `args.timeout` passes or `stop` is set.
+///
+/// The latest deployments are looked up until one is found, and from then
+/// on the same deployments are polled.
+async fn watch_status(
+    args: &StatusArgs,
+    client: &mut ControlPlaneClient,
+    stop: &AtomicBool,
+) -> Result<()> {
+    let colors = std::io::stdout().is_terminal();
+    let interval = Duration::from_secs(args.interval.max(1));
+    let deadline = Instant::now() + Duration::from_secs(args.timeout);
+    let mut watched: Vec<String> = Vec::new();
+    let mut previous: Option<StatusSummary> = None;
+
+    loop {
+        let deployments = if watched.is_empty() {
+            latest_deployments(client, args.service()).await?
+        } else {
+            fetch_deployments(client, &watched).await?
+        };
+        watched = deployments
+            .iter()
+            .map(|d| d.deployment_id.clone())
+            .collect();
+        let summary = summarize(client, deployments).await?;
+
+        if colors {
+            // Clear the screen and move the cursor home
+            print!("\x1b[2J\x1b[H");
+        }
+        print!(
+            "{}",
+            render_watch(&summary, previous.as_ref(), terminal_width(), colors)
+        );
+
+        match outcome(&summary) {
+            Some(Ok(())) => return Ok(()),
+            Some(Err(failed)) => anyhow::bail!("Deployment failed: {}", failed.join(", ")),
+            None => {}
+        }
+        if Instant::now() >= deadline {
+            anyhow::bail!(
+                "Gave up waiting for the deployment to finish after {}s",
+                args.timeout
+            );
+        }
+        previous = Some(summary);
+
+        let next_poll = (Instant::now() + interval).min(deadline);
+        while Instant::now() < next_poll {
+            if stop.load(Ordering::Relaxed) {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+/// Returns the outcome once every deployment reached a terminal state:
+/// `Ok` if all were deployed, otherwise the services whose deployment
+/// failed, was rolled back, cancelled or interrupted. Returns `None` while
+/// any is still running or none is recorded yet.
+fn outcome(summary: &StatusSummary) -> Option<Result<(), Vec<String>>> {
+    if summary.deployments.is_empty() {
+        return None;
+    }
+
+    let mut failed = Vec::new();
+    for deployment in &summary.deployments {
+        match deployment.status.as_str() {
+            "deployed" => {}
+            "failed" | "rolled_back" | "cancelled" | "interrupted" => {
+                failed.push(deployment.service.clone());
+            }
+            _ => return None,
+        }
+    }
+    Some(if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(failed)
+    })
+}
+
+/// Returns the width of the terminal stdout is attached to.
+fn terminal_width() -> usize {
+    terminal_size::terminal_size().map_or(DEFAULT_WIDTH, |(terminal_size::Width(width), _)| {
+        usize::from(width)
+    })
+}
+
+/// Renders the instance table of a watch poll, fitted to `width` columns.
+///
+/// Cells that changed since `previous` are highlighted, or with `colors`
+/// off, their rows are marked with `*`.
+fn render_watch(
+    summary: &StatusSummary,
+    previous: Option<&StatusSummary>,
+    width: usize,
+    colors: bool,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Eunomia Deployment Status (updated {})",
+        Utc::now().format("%H:%M:%S")
+    );
+    let _ = writeln!(out);
+
+    if summary.deployments.is_empty() {
+        let _ = writeln!(out, "Waiting for a deployment...");
+        return out;
+    }
+
+    let previous: HashMap<(&str, &str), &InstanceStatus> = previous
+        .into_iter()
+        .flat_map(|summary| &summary.deployments)
+        .flat_map(|d| {
+            d.instances
+                .iter()
+                .map(move |i| ((d.service.as_str(), i.instance_id.as_str()), i))
+        })
+        .collect();
+
+    let version_width = summary
+        .deployments
         .iter()
         .flat_map(|d| &d.instances)
-        .filter(|i| i.status == "healthy")
-        .count();
-    let unhealthy_instances = total_instances - healthy_instances;
-
-    Ok(StatusSummary {
-        deployments,
-        total_instances,
-        healthy_instances,
-        unhealthy_instances,
-    })
+        .filter_map(|i| i.version.as_deref())
+        .map(|v| v.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("VERSION".len());
+    let health_width = "unhealthy".len();
+    let updated_width = "2026-01-05T10:00:00Z".len();
+    // Row marker and the gaps between columns
+    let fixed = 2 + version_width + health_width + updated_width + 6;
+    let instance_width = width.saturating_sub(fixed).max(MIN_INSTANCE_WIDTH);
+
+    for deployment in &summary.deployments {
+        let _ = writeln!(
+            out,
+            "{} v{} [{}]",
+            deployment.service, deployment.version, deployment.status
+        );
+        let _ = writeln!(
+            out,
+            "  {:instance_width$}  {:version_width$}  {:health_width$}  LAST UPDATE",
+            "INSTANCE", "VERSION", "HEALTH"
+        );
+
+        for instance in &deployment.instances {
+            let before =
+                previous.get(&(deployment.service.as_str(), instance.instance_id.as_str()));
+            let updated = instance.last_updated.as_deref().unwrap_or("-");
+            let version = instance.version.as_deref().unwrap_or("-");
+
+            let cells = [
+                (
+                    truncate(&instance.instance_id, instance_width),
+                    instance_width,
+                    before.is_none(),
+                ),
+                (
+                    version.to_string(),
+                    version_width,
+                    before.is_some_and(|b| b.version != instance.version),
+                ),
+                (
+                    instance.status.clone(),
+                    health_width,
+                    before.is_some_and(|b| b.status != instance.status),
+                ),
+                (
+                    updated.to_string(),
+                    0,
+                    before.is_some_and(|b| b.last_updated != instance.last_updated),
+                ),
+            ];
+            let changed = !previous.is_empty() && cells.iter().any(|(_, _, changed)| *changed);
+
+            let _ = write!(out, "{}", if changed && !colors { "* " } else { "  " });
+            for (i, (text, width, cell_changed)) in cells.iter().enumerate() {
+                let padded = format!("{text:width$}");
+                if colors && changed && *cell_changed {
+                    let _ = write!(out, "\x1b[1;33m{padded}\x1b[0m");
+                } else {
+                    out.push_str(&padded);
+                }
+                if i + 1 < cells.len() {
+                    out.push_str("  ");
+                }
+            }
+            out.push('\n');
+        }
+        let _ = writeln!(out);
+    }
+    out
+}
+
+/// Shortens `text` to at most `width` characters, ending in `…` if cut.
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn print_text_history(deployments: &[DeploymentRecord], verbose: bool) {
+    println!("Eunomia Deployment History");
+    println!("==========================");
+    println!();
+
+    if deployments.is_empty() {
+        println!("No deployments found.");
+        return;
+    }
+
+    for deployment in deployments {
+        let (status_icon, state) = match deployment.state {
+            GrpcDeploymentState::Completed => ("✓", "completed"),
+            GrpcDeploymentState::InProgress => ("⟳", "in progress"),
+            GrpcDeploymentState::Paused => ("⏸", "paused"),
+            GrpcDeploymentState::Pending => ("…", "pending"),
+            GrpcDeploymentState::Failed => ("✗", "failed"),
+            GrpcDeploymentState::RolledBack => ("⟲", "rolled back"),
+            GrpcDeploymentState::Cancelled => ("-", "cancelled"),
+            GrpcDeploymentState::Interrupted => ("!", "interrupted"),
+            GrpcDeploymentState::Unknown => ("?", "unknown"),
+        };
+        println!(
+            "{} {} v{} [{}] {:?}",
+            status_icon, deployment.service, deployment.version, state, deployment.strategy
+        );
+        println!("  ID: {}", deployment.deployment_id);
+        if let Some(started) = deployment.started_at {
+            println!("  Started: {}", started.to_rfc3339());
+        }
+        if let Some(completed) = deployment.completed_at {
+            println!("  Finished: {}", completed.to_rfc3339());
+        }
+        if let Some(summary) = &deployment.summary {
+            println!(
+                "  Instances: {} succeeded, {} failed, {} skipped ({} ms)",
+                summary.successful, summary.failed, summary.skipped, summary.duration_ms
+            );
+        }
+        if !deployment.error_message.is_empty() {
+            println!("  Error: {}", deployment.error_message);
+        }
+
+        if verbose {
+            for result in &deployment.instance_results {
+                if result.skipped {
+                    println!("    - {} (already at version)", result.instance_id);
+                } else if result.success {
+                    println!("    ✓ {}", result.instance_id);
+                } else {
+                    println!("    ✗ {}: {}", result.instance_id, result.error_message);
+                }
+            }
+        }
+        println!();
+    }
 }
 
 fn print_text_status(summary: &StatusSummary, verbose: bool) {
@@ -140,8 +614,12 @@ fn print_text_status(summary: &StatusSummary, verbose: bool) {
         let status_icon = match deployment.status.as_str() {
             "deployed" => "✓",
             "deploying" => "⟳",
+            "pending" => "…",
+            "paused" => "⏸",
             "failed" => "✗",
-            "rolling_back" => "⟲",
+            "rolled_back" => "⟲",
+            "cancelled" => "-",
+            "interrupted" => "!",
             _ => "?",
         };
 
@@ -152,22 +630,27 @@ fn print_text_status(summary: &StatusSummary, verbose: bool) {
         println!("  Last updated: {}", deployment.last_updated);
 
         if verbose {
+            println!("  Deployment: {}", deployment.deployment_id);
             println!("  Instances:");
             for instance in &deployment.instances {
                 let instance_icon = match instance.status.as_str() {
                     "healthy" => "●",
                     "unhealthy" => "○",
+                    "degraded" => "◐",
                     _ => "?",
                 };
                 println!(
                     "    {} {} ({})",
-                    instance_icon, instance.endpoint, instance.status
+                    instance_icon, instance.instance_id, instance.status
                 );
                 if let Some(version) = &instance.version {
                     println!("      Version: {version}");
                 }
-                if let Some(last_check) = &instance.last_health_check {
-                    println!("      Last check: {last_check}");
+                if let Some(last_updated) = &instance.last_updated {
+                    println!("      Last check: {last_updated}");
+                }
+                if let Some(last_seen) = &instance.last_seen {
+                    println!("      Last seen: {last_seen}");
                 }
             }
         } else {
@@ -183,92 +666,242 @@ fn print_text_status(summary: &StatusSummary, verbose: bool) {
     }
 }
 
-fn print_json_status(summary: &StatusSummary) -> Result<()> {
-    let json = serde_json::to_string_pretty(summary)?;
-    println!("{json}");
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use eunomia_distributor::grpc::{GrpcServer, GrpcServerConfig, GrpcServerHandle};
+    use eunomia_distributor::{
+        DeploymentStrategy, Distributor, DistributorConfig, SimulatedTransport,
+    };
+
     use super::*;
-    use tempfile::TempDir;
 
-    #[test]
-    fn test_empty_state_returns_empty_summary() {
-        let temp_dir = TempDir::new().unwrap();
-        let summary = read_deployment_state(&temp_dir.path().to_path_buf(), None).unwrap();
-
-        assert!(summary.deployments.is_empty());
-        assert_eq!(summary.total_instances, 0);
-        assert_eq!(summary.healthy_instances, 0);
-        assert_eq!(summary.unhealthy_instances, 0);
+    /// Starts a control plane that has run `deployments`, returning its
+    /// endpoint.
+    async fn control_plane(deployments: &[(&str, &str)]) -> (String, GrpcServerHandle) {
+        let config = DistributorConfig::builder()
+            .static_endpoints(vec!["localhost:8080".to_string()])
+            .build();
+        let distributor = Arc::new(
+            Distributor::new(config)
+                .await
+                .unwrap()
+                .with_transport(Arc::new(SimulatedTransport::default())),
+        );
+        for (service, version) in deployments {
+            distributor
+                .deploy(service, version, DeploymentStrategy::immediate())
+                .await
+                .unwrap();
+        }
+
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let addr = format!("127.0.0.1:{port}").parse().unwrap();
+        let handle = GrpcServer::new(distributor, GrpcServerConfig::new(addr))
+            .run()
+            .await
+            .unwrap();
+        // Give the server time to bind
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        (format!("http://{addr}"), handle)
     }
 
-    #[test]
-    fn test_read_deployment_state() {
-        let temp_dir = TempDir::new().unwrap();
-        let state_file = temp_dir.path().join("deployments.json");
-
-        let deployments = vec![DeploymentStatus {
-            service: "users-service".to_string(),
-            version: "1.0.0".to_string(),
-            status: "deployed".to_string(),
-            instances: vec![
-                InstanceStatus {
-                    endpoint: "host1:8080".to_string(),
-                    status: "healthy".to_string(),
-                    version: Some("1.0.0".to_string()),
-                    last_health_check: Some("2026-01-05T10:00:00Z".to_string()),
-                },
-                InstanceStatus {
-                    endpoint: "host2:8080".to_string(),
-                    status: "unhealthy".to_string(),
-                    version: Some("1.0.0".to_string()),
-                    last_health_check: Some("2026-01-05T10:00:00Z".to_string()),
-                },
-            ],
-            last_updated: "2026-01-05T10:00:00Z".to_string(),
-        }];
-
-        std::fs::write(&state_file, serde_json::to_string(&deployments).unwrap()).unwrap();
-
-        let summary = read_deployment_state(&temp_dir.path().to_path_buf(), None).unwrap();
-
-        assert_eq!(summary.deployments.len(), 1);
+    fn status_args(args: &[&str]) -> StatusArgs {
+        use crate::commands::{Cli, Commands};
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["eunomia", "status"].iter().chain(args)).unwrap();
+        let Commands::Status(args) = cli.command else {
+            unreachable!()
+        };
+        args
+    }
+
+    #[tokio::test]
+    async fn test_fetch_deployment_history() {
+        let (endpoint, handle) = control_plane(&[
+            ("users-service", "1.0.0"),
+            ("orders-service", "1.0.0"),
+            ("users-service", "1.1.0"),
+        ])
+        .await;
+        let mut client = connect(&endpoint).await.unwrap();
+
+        let versions = |deployments: Vec<DeploymentRecord>| {
+            deployments
+                .into_iter()
+                .map(|d| format!("{}@{}", d.service, d.version))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            versions(
+                fetch_deployment_history(&mut client, None, 10)
+                    .await
+                    .unwrap()
+            ),
+            [
+                "users-service@1.1.0",
+                "orders-service@1.0.0",
+                "users-service@1.0.0"
+            ]
+        );
+        assert_eq!(
+            versions(
+                fetch_deployment_history(&mut client, Some("users-service"), 1)
+                    .await
+                    .unwrap()
+            ),
+            ["users-service@1.1.0"]
+        );
+
+        handle.shutdown_and_wait().await;
+        assert!(connect(&endpoint).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_status_shows_latest_deployment_of_each_service() {
+        let (endpoint, handle) = control_plane(&[
+            ("users-service", "1.0.0"),
+            ("orders-service", "1.0.0"),
+            ("users-service", "1.1.0"),
+        ])
+        .await;
+        let mut client = connect(&endpoint).await.unwrap();
+
+        let deployments = latest_deployments(&mut client, None).await.unwrap();
+        let summary = summarize(&mut client, deployments).await.unwrap();
+        let shown: Vec<_> = summary
+            .deployments
+            .iter()
+            .map(|d| format!("{}@{} {}", d.service, d.version, d.status))
+            .collect();
+        assert_eq!(
+            shown,
+            [
+                "users-service@1.1.0 deployed",
+                "orders-service@1.0.0 deployed"
+            ]
+        );
         assert_eq!(summary.total_instances, 2);
-        assert_eq!(summary.healthy_instances, 1);
-        assert_eq!(summary.unhealthy_instances, 1);
+        assert_eq!(summary.deployments[0].instances[0].instance_id, "static-0");
+
+        let deployments = latest_deployments(&mut client, Some("orders-service"))
+            .await
+            .unwrap();
+        assert_eq!(deployments.len(), 1);
+        assert_eq!(deployments[0].service, "orders-service");
+
+        drop(client);
+        handle.shutdown_and_wait().await;
     }
 
-    #[test]
-    fn test_filter_by_service() {
-        let temp_dir = TempDir::new().unwrap();
-        let state_file = temp_dir.path().join("deployments.json");
+    #[tokio::test]
+    async fn test_watch_status_polls_until_finished() {
+        let (endpoint, handle) = control_plane(&[("users-service", "1.0.0")]).await;
+        let mut client = connect(&endpoint).await.unwrap();
+        let stop = AtomicBool::new(false);
+
+        let args = status_args(&["users-service", "--watch", "--endpoint", &endpoint]);
+        watch_status(&args, &mut client, &stop).await.unwrap();
+
+        let args = status_args(&["missing-service", "--watch", "--timeout", "0"]);
+        let err = watch_status(&args, &mut client, &stop).await.unwrap_err();
+        assert!(err.to_string().contains("Gave up"), "{err}");
+
+        drop(client);
+        handle.shutdown_and_wait().await;
+    }
 
-        let deployments = vec![
-            DeploymentStatus {
+    fn summary(status: &str, health: &str) -> StatusSummary {
+        StatusSummary {
+            deployments: vec![DeploymentStatus {
+                deployment_id: "deploy-1".to_string(),
                 service: "users-service".to_string(),
-                version: "1.0.0".to_string(),
-                status: "deployed".to_string(),
-                instances: vec![],
+                version: "1.1.0".to_string(),
+                status: status.to_string(),
+                instances: vec![InstanceStatus {
+                    instance_id: "users-service-7d9f8b6c5d-x2x4p.prod.svc.cluster.local:8080"
+                        .to_string(),
+                    status: health.to_string(),
+                    version: Some("1.1.0".to_string()),
+                    last_updated: Some("2026-01-05T10:00:00Z".to_string()),
+                    last_seen: None,
+                }],
                 last_updated: "2026-01-05T10:00:00Z".to_string(),
-            },
-            DeploymentStatus {
-                service: "orders-service".to_string(),
-                version: "2.0.0".to_string(),
-                status: "deployed".to_string(),
-                instances: vec![],
-                last_updated: "2026-01-05T10:00:00Z".to_string(),
-            },
-        ];
+            }],
+            total_instances: 1,
+            healthy_instances: 1,
+            unhealthy_instances: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_positional_service() {
+        use crate::commands::Cli;
+        use clap::Parser;
+
+        let args = status_args(&["users-service", "--watch"]);
+        assert_eq!(args.service(), Some("users-service"));
+        assert!(args.watch);
+        assert_eq!(args.interval, 2);
+        assert_eq!(args.timeout, 1800);
+
+        assert!(Cli::try_parse_from(["eunomia", "status", "a", "--service", "b"]).is_err());
+        assert!(Cli::try_parse_from(["eunomia", "status", "--interval", "5"]).is_err());
+        assert!(Cli::try_parse_from(["eunomia", "status", "--timeout", "5"]).is_err());
+        assert!(Cli::try_parse_from(["eunomia", "status", "--watch", "--history"]).is_err());
+    }
+
+    #[test]
+    fn test_outcome() {
+        assert_eq!(outcome(&StatusSummary::default()), None);
+        assert_eq!(outcome(&summary("deploying", "healthy")), None);
+        assert_eq!(outcome(&summary("paused", "healthy")), None);
+        assert_eq!(outcome(&summary("deployed", "healthy")), Some(Ok(())));
+        assert_eq!(
+            outcome(&summary("rolled_back", "unhealthy")),
+            Some(Err(vec!["users-service".to_string()]))
+        );
+        assert_eq!(
+            outcome(&summary("interrupted", "healthy")),
+            Some(Err(vec!["users-service".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_render_watch_highlights_changes() {
+        let before = summary("deploying", "unknown");
+        let after = summary("deploying", "healthy");
+
+        let first = render_watch(&after, None, 80, false);
+        assert!(first.contains("  users-service-7d9f"));
+        assert!(!first.contains("* "));
 
-        std::fs::write(&state_file, serde_json::to_string(&deployments).unwrap()).unwrap();
+        let unchanged = render_watch(&after, Some(&after), 80, false);
+        assert!(!unchanged.contains("* "));
 
-        let summary =
-            read_deployment_state(&temp_dir.path().to_path_buf(), Some("users-service")).unwrap();
+        let changed = render_watch(&after, Some(&before), 80, false);
+        assert!(changed.contains("* users-service-7d9f"));
 
-        assert_eq!(summary.deployments.len(), 1);
-        assert_eq!(summary.deployments[0].service, "users-service");
+        let colored = render_watch(&after, Some(&before), 80, true);
+        assert!(colored.contains("\x1b[1;33mhealthy"));
+        assert!(!colored.contains("\x1b[1;33m1.1.0"));
+    }
+
+    #[test]
+    fn test_render_watch_truncates_instances() {
+        let output = render_watch(&summary("deployed", "healthy"), None, 60, false);
+
+        for line in output.lines() {
+            assert!(line.chars().count() <= 60, "{line}");
+        }
+        assert!(output.contains('…'));
+        assert_eq!(truncate("host1:8080", 12), "host1:8080");
+        assert_eq!(truncate("host1:8080", 6), "host1…");
     }
 }
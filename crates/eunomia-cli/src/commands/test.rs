@@ -1,12 +1,23 @@
 //! Test command implementation.
 
-use std::path::PathBuf;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use clap::Args;
 use tracing::info;
 
-use eunomia_test::{ConsoleReporter, Reporter, TestConfig, TestDiscovery, TestRunner};
+use eunomia_compiler::{RegoEngine, Severity};
+use eunomia_test::{
+    ConsoleReporter, CoverageReport, CoverageReporter, JsonReporter, JunitReporter, Reporter,
+    SnapshotStore, TestConfig, TestDiscovery, TestResults, TestRunner, TestSuite,
+    DEFAULT_FUZZ_RUNS,
+};
+
+use super::validate::{format_violation, load_linter};
+use crate::output::OutputFormat;
+use crate::watch::{self, FileWatcher};
 
 /// Arguments for the test command.
 #[derive(Args)]
@@ -20,33 +31,136 @@ pub struct TestArgs {
     #[arg(short, long)]
     pub fail_fast: bool,
 
-    /// Run tests in parallel
+    /// Run test packages in parallel
     #[arg(short, long)]
     pub parallel: bool,
 
-    /// Number of parallel workers
-    #[arg(short, long, default_value = "4")]
-    pub workers: usize,
+    /// Number of parallel workers (implies --parallel when greater than 1)
+    #[arg(short, long, visible_alias = "workers")]
+    pub jobs: Option<usize>,
+
+    /// Report format
+    #[arg(long, value_enum, default_value_t = ReportFormat::Console)]
+    pub format: ReportFormat,
 
-    /// Output format (console, json)
-    #[arg(short, long, default_value = "console")]
-    pub output: String,
+    /// Write the report to this file; the console summary is still printed
+    #[arg(short, long = "output")]
+    pub output_file: Option<PathBuf>,
 
     /// Disable colored output
     #[arg(long)]
     pub no_color: bool,
 
-    /// Filter tests by pattern (matches package or test name)
+    /// Only run tests whose qualified name (`data.<package>.<test>`)
+    /// matches this regex, or contains it if it is not a valid regex
     #[arg(long)]
     pub filter: Option<String>,
 
+    /// Only run tests in this package or its subpackages
+    #[arg(long)]
+    pub package: Option<String>,
+
+    /// Lint policy files before running tests, using `.eunomia-lint.toml`
+    /// if present
+    #[arg(long)]
+    pub lint: bool,
+
+    /// Collect line and rule coverage of policy files
+    #[arg(long)]
+    pub coverage: bool,
+
+    /// Format for the coverage file written with --coverage-out
+    #[arg(long, value_enum, default_value_t = CoverageFormat::Lcov)]
+    pub coverage_format: CoverageFormat,
+
+    /// Write the coverage report to this file (implies --coverage)
+    #[arg(long)]
+    pub coverage_out: Option<PathBuf>,
+
+    /// Write an HTML coverage report to this directory (implies --coverage)
+    #[arg(long, value_name = "DIR")]
+    pub coverage_html: Option<PathBuf>,
+
+    /// Fail if total coverage is below this percentage (implies --coverage)
+    #[arg(long)]
+    pub coverage_threshold: Option<f64>,
+
+    /// Re-run affected tests whenever policy, data or fixture files change,
+    /// keeping policies loaded between runs
+    #[arg(long)]
+    pub watch: bool,
+
+    /// Quiet period after the last change before --watch re-runs tests, in
+    /// milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 200, requires = "watch")]
+    pub debounce_ms: u64,
+
+    /// Fail a test that runs longer than this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout_per_test: Option<u64>,
+
+    /// Cancel the tests still running after this many seconds
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout_global: Option<u64>,
+
+    /// Check the decisions recorded in the `.toml` and `.json` snapshot
+    /// files in this directory against the current policies
+    #[arg(long, value_name = "DIR")]
+    pub snapshot_dir: Option<PathBuf>,
+
+    /// Record the current decisions in the snapshot files instead of
+    /// failing on changed ones
+    #[arg(long, requires = "snapshot_dir")]
+    pub update_snapshots: bool,
+
+    /// Fuzz the `allow` rule of every policy package with generated inputs,
+    /// failing if it is ever undefined or not a boolean
+    #[arg(long)]
+    pub fuzz: bool,
+
+    /// Number of inputs to generate per package with --fuzz
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_FUZZ_RUNS)]
+    pub fuzz_runs: u32,
+
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
 }
 
+/// Format of the test report.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Human-readable summary.
+    #[default]
+    Console,
+    /// `JUnit` XML, one test suite per Rego package.
+    Junit,
+    /// JSON serialization of all results, including durations.
+    Json,
+}
+
+/// File format for coverage reports.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum CoverageFormat {
+    /// LCOV tracefile, for CI coverage tooling.
+    #[default]
+    Lcov,
+    /// JSON serialization of the coverage report.
+    Json,
+}
+
+impl TestArgs {
+    /// Returns whether coverage needs to be collected.
+    const fn collect_coverage(&self) -> bool {
+        self.coverage
+            || self.coverage_out.is_some()
+            || self.coverage_html.is_some()
+            || self.coverage_threshold.is_some()
+    }
+}
+
 /// Runs the test command.
-pub fn run(args: &TestArgs) -> Result<()> {
+pub fn run(args: &TestArgs, output: OutputFormat) -> Result<()> {
     info!(path = ?args.path, "Running policy tests");
 
     let path = args
@@ -54,6 +168,10 @@ pub fn run(args: &TestArgs) -> Result<()> {
         .canonicalize()
         .unwrap_or_else(|_| args.path.clone());
 
+    if args.watch {
+        return watch_tests(args, &path);
+    }
+
     // Discover tests
     if args.verbose {
         println!("Discovering tests in: {}", path.display());
@@ -69,16 +187,27 @@ pub fn run(args: &TestArgs) -> Result<()> {
 
     // Filter tests if pattern provided
     let test_count = suite.test_count();
+    let fixture_count = suite.fixtures().len();
     let policy_count = suite.policy_files().len();
 
-    if test_count == 0 {
+    if test_count == 0 && fixture_count == 0 && args.snapshot_dir.is_none() && !args.fuzz {
+        if output.is_structured() {
+            return output.print(&TestResults::default());
+        }
         println!("No tests found in {}", path.display());
-        println!("\nLooking for files matching '*_test.rego' pattern.");
+        println!("\nLooking for files matching '*_test.rego' pattern or fixture files.");
         return Ok(());
     }
 
+    if args.lint {
+        lint_policies(&path, suite.policy_files())?;
+    }
+
     if args.verbose {
         println!("Found {test_count} tests in {policy_count} policy files");
+        if fixture_count > 0 {
+            println!("Found {fixture_count} fixture files");
+        }
         println!();
         for (pkg, tests) in suite.tests_by_package() {
             println!("Package: {pkg}");
@@ -90,26 +219,816 @@ pub fn run(args: &TestArgs) -> Result<()> {
     }
 
     // Configure and run tests
-    let config = TestConfig::new()
-        .with_fail_fast(args.fail_fast)
-        .with_parallel(args.parallel)
-        .with_workers(args.workers);
-
-    let runner = TestRunner::new(config);
-    let results = match runner.run_suite(&suite) {
+    let runner = TestRunner::new(test_config(args));
+    let mut results = match runner.run_all(&suite) {
         Ok(r) => r,
         Err(e) => {
             anyhow::bail!("Test execution failed: {e}");
         }
     };
 
-    // Report results
-    let reporter = ConsoleReporter::new().with_colors(!args.no_color);
-    reporter.report(&results)?;
+    if args.fuzz && (!args.fail_fast || results.all_passed()) {
+        let fuzzed = runner
+            .run_fuzz(&suite)
+            .map_err(|e| anyhow::anyhow!("Fuzzing failed: {e}"))?;
+        for result in fuzzed {
+            results.add(result);
+        }
+        results.sort();
+    }
+
+    report(args, output, &results)?;
+
+    if let Some(coverage) = &results.coverage {
+        check_coverage(args, coverage)?;
+    }
+
+    let changed = match &args.snapshot_dir {
+        Some(dir) => check_snapshots(&runner, &suite, dir)?,
+        None => 0,
+    };
+
+    if !results.all_passed() {
+        anyhow::bail!("{} test(s) failed", results.failed())
+    }
+    if changed > 0 {
+        anyhow::bail!(
+            "{changed} snapshot decision(s) changed; rerun with --update-snapshots to accept"
+        )
+    }
+    Ok(())
+}
+
+/// Builds the runner configuration from the command-line arguments.
+fn test_config(args: &TestArgs) -> TestConfig {
+    let mut config = TestConfig::new()
+        .with_fail_fast(args.fail_fast)
+        .with_parallel(args.parallel || args.jobs.is_some_and(|jobs| jobs > 1))
+        .with_coverage(args.collect_coverage());
+    if let Some(jobs) = args.jobs {
+        config = config.with_workers(jobs);
+    }
+    if let Some(filter) = &args.filter {
+        config = config.with_filter(filter);
+    }
+    if let Some(package) = &args.package {
+        config = config.with_package(package);
+    }
+    if let Some(seconds) = args.timeout_per_test {
+        config = config.with_test_timeout(Duration::from_secs(seconds));
+    }
+    if let Some(seconds) = args.timeout_global {
+        config = config.with_global_timeout(Duration::from_secs(seconds));
+    }
+    config
+        .with_update_snapshots(args.update_snapshots)
+        .with_fuzz_runs(args.fuzz_runs)
+}
+
+/// Runs all tests, then re-runs the affected ones after every change.
+fn watch_tests(args: &TestArgs, path: &Path) -> Result<()> {
+    // Start watching before the first run so edits made during it are seen.
+    let watcher = FileWatcher::new(
+        path,
+        Duration::from_millis(args.debounce_ms),
+        watch::WATCHED_EXTENSIONS,
+    )?;
 
-    if results.all_passed() {
+    let mut session = WatchSession::new(path, TestRunner::new(test_config(args)), !args.no_color);
+    if let Err(e) = session.start() {
+        println!("✗ {e:#}");
+    }
+    watcher.run(&watch::stop_on_ctrl_c(), |changed| session.rerun(changed));
+    Ok(())
+}
+
+/// Policies and data kept loaded in one engine between the runs of
+/// `--watch`.
+///
+/// New policy files are added to the engine. `regorus` cannot unload a
+/// single policy, so an edited or removed policy has the engine rebuilt
+/// from the sources already in memory, once per batch of changes. A
+/// changed data file reloads all data files.
+struct WatchSession {
+    root: PathBuf,
+    runner: TestRunner,
+    engine: RegoEngine,
+    /// Data files loaded into the engine.
+    data_files: BTreeSet<PathBuf>,
+    /// Policy files that failed to compile; tests do not run until they do.
+    broken: BTreeSet<PathBuf>,
+    colors: bool,
+}
+
+impl WatchSession {
+    fn new(root: &Path, runner: TestRunner, colors: bool) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            runner,
+            engine: RegoEngine::new(),
+            data_files: BTreeSet::new(),
+            broken: BTreeSet::new(),
+            colors,
+        }
+    }
+
+    /// Loads every policy and data file, then runs all tests.
+    fn start(&mut self) -> Result<()> {
+        let suite = self.discover()?;
+        for (path, source) in suite.policy_files() {
+            if let Err(e) = self.load_policy(path, source) {
+                println!("✗ {e:#}");
+            }
+        }
+        self.load_data(&suite)?;
+        self.run_tests(&suite)
+    }
+
+    /// Reloads the changed policy and data files, then runs the tests they
+    /// affect.
+    fn rerun(&mut self, changed: &[PathBuf]) -> Result<()> {
+        let files: Vec<String> = changed
+            .iter()
+            .map(|path| path.strip_prefix(&self.root).unwrap_or(path))
+            .map(|path| path.display().to_string())
+            .collect();
+        println!("▶ {} changed, re-running...", files.join(", "));
+
+        let suite = self.discover()?;
+        self.reload_policies(&suite, changed)?;
+        if changed
+            .iter()
+            .any(|path| self.data_files.contains(path) || suite.data_files().contains_key(path))
+        {
+            self.load_data(&suite)?;
+        }
+        let suite = affected_suite(&suite, changed).unwrap_or(suite);
+        self.run_tests(&suite)
+    }
+
+    /// Replaces the changed policies in the engine with their sources in
+    /// `suite`, dropping removed ones. Other policies stay loaded.
+    fn reload_policies(&mut self, suite: &TestSuite, changed: &[PathBuf]) -> Result<()> {
+        let policies: Vec<&PathBuf> = changed
+            .iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rego"))
+            .collect();
+        let names: Vec<String> = policies
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        self.engine
+            .remove_policies(&names.iter().map(String::as_str).collect::<Vec<_>>())?;
+
+        for path in policies {
+            self.broken.remove(path);
+            if let Some(source) = suite.policy_files().get(path) {
+                if let Err(e) = self.load_policy(path, source) {
+                    println!("✗ {e:#}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn load_policy(&mut self, path: &Path, source: &str) -> Result<()> {
+        if let Err(e) = self.engine.add_policy(&path.to_string_lossy(), source) {
+            self.broken.insert(path.to_path_buf());
+            return Err(e.into());
+        }
         Ok(())
+    }
+
+    /// Replaces the data in the engine with the data files of `suite`.
+    fn load_data(&mut self, suite: &TestSuite) -> Result<()> {
+        self.engine.clear_data();
+        self.data_files.clear();
+        for (path, data) in suite.data_files() {
+            self.engine
+                .add_data(data.clone())
+                .with_context(|| format!("Failed to load data from {}", path.display()))?;
+            self.data_files.insert(path.clone());
+        }
+        Ok(())
+    }
+
+    fn discover(&self) -> Result<TestSuite> {
+        TestDiscovery::new()
+            .discover(self.root.to_str().unwrap_or("."))
+            .map_err(|e| anyhow!("Failed to discover tests: {e}"))
+    }
+
+    /// Runs the tests and fixtures of `suite` on the session's engine and
+    /// prints a summary.
+    fn run_tests(&mut self, suite: &TestSuite) -> Result<()> {
+        if !self.broken.is_empty() {
+            anyhow::bail!(
+                "Tests not run: {} policy file(s) failed to compile",
+                self.broken.len()
+            );
+        }
+
+        let mut results = self
+            .runner
+            .run_suite_on(&mut self.engine, suite)
+            .map_err(|e| anyhow!("Test execution failed: {e}"))?;
+        if !suite.fixtures().is_empty() {
+            let fixtures = self
+                .runner
+                .run_discovered_fixtures(suite)
+                .map_err(|e| anyhow!("Test execution failed: {e}"))?;
+            for result in fixtures.results() {
+                results.add(result.clone());
+            }
+            results.sort();
+            results.total_duration += fixtures.total_duration;
+        }
+
+        print_watch_summary(&results, self.colors);
+        Ok(())
+    }
+}
+
+/// Builds a suite with only the tests affected by `changed` policy files:
+/// tests in a changed package, in its `_test` package, or in files that
+/// reference it.
+///
+/// Returns `None` if every test must re-run, because a file other than an
+/// existing policy (a data, fixture or config file, or a deleted policy)
+/// changed.
+fn affected_suite(suite: &TestSuite, changed: &[PathBuf]) -> Option<TestSuite> {
+    let mut packages = BTreeSet::new();
+    for path in changed {
+        let source = suite.policy_files().get(path)?;
+        packages.insert(package_name(source)?);
+    }
+
+    let mut affected = TestSuite::new(suite.root());
+    for (path, source) in suite.policy_files() {
+        affected.add_policy_file(path.clone(), source.clone());
+    }
+    for (path, data) in suite.data_files() {
+        affected.add_data_file(path.clone(), data.clone());
+    }
+
+    for test in suite.tests() {
+        let source = suite
+            .policy_files()
+            .get(&test.file)
+            .map_or("", String::as_str);
+        let is_affected = packages.iter().any(|package| {
+            test.package == *package
+                || test.package.strip_suffix("_test") == Some(package.as_str())
+                || references_package(source, package)
+        });
+        if is_affected {
+            affected.add_test(test.clone());
+        }
+    }
+
+    Some(affected)
+}
+
+/// Returns the package declared by a policy source.
+fn package_name(source: &str) -> Option<String> {
+    source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("package "))
+        .map(|package| package.trim().to_string())
+}
+
+/// Returns true if `source` refers to `data.<package>` or a rule in it.
+fn references_package(source: &str, package: &str) -> bool {
+    let reference = format!("data.{package}");
+    source.match_indices(&reference).any(|(i, _)| {
+        !source[i + reference.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// Prints a one-line summary of a watch cycle, followed by any failures.
+fn print_watch_summary(results: &TestResults, colors: bool) {
+    let (mark, color) = if results.all_passed() {
+        ("✓", "\x1b[32m")
     } else {
-        anyhow::bail!("{} test(s) failed", results.failed())
+        ("✗", "\x1b[31m")
+    };
+    let summary = format!(
+        "{mark} {} passed, {} failed in {}",
+        results.passed(),
+        results.failed(),
+        format_duration(results.total_duration)
+    );
+
+    if colors {
+        println!("{color}{summary}\x1b[0m");
+    } else {
+        println!("{summary}");
+    }
+    for failure in results.failures() {
+        let package = failure
+            .package
+            .as_deref()
+            .map_or(String::new(), |p| format!("{p}."));
+        let error = failure.error.as_deref().unwrap_or("failed");
+        println!("  ✗ {package}{}: {error}", failure.name);
+    }
+}
+
+/// Formats a duration as milliseconds, or seconds above one second.
+fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+/// Prints the console report and writes the requested report format.
+///
+/// With `--output`, the report is written to the file and the console
+/// summary is still printed. Without it, the report goes to stdout so `JUnit`
+/// or JSON output can be piped. With `--result-format json` or
+/// `--result-format yaml`, the results are written to stdout in that format
+/// in place of the console summary or report.
+fn report(args: &TestArgs, output: OutputFormat, results: &TestResults) -> Result<()> {
+    let Some(path) = &args.output_file else {
+        if output.is_structured() {
+            return output.print(results);
+        }
+        reporter(args.format, !args.no_color, args.verbose).report(results)?;
+        return Ok(());
+    };
+
+    let mut file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create report file: {}", path.display()))?;
+    reporter(args.format, false, args.verbose).write_report(results, &mut file)?;
+
+    if output.is_structured() {
+        return output.print(results);
+    }
+    reporter(ReportFormat::Console, !args.no_color, args.verbose).report(results)?;
+    println!("Report written to {}", path.display());
+    Ok(())
+}
+
+fn reporter(format: ReportFormat, colors: bool, verbose: bool) -> Box<dyn Reporter> {
+    match format {
+        ReportFormat::Console => Box::new(
+            ConsoleReporter::new()
+                .with_colors(colors)
+                .with_verbose(verbose),
+        ),
+        ReportFormat::Junit => Box::new(JunitReporter::new()),
+        ReportFormat::Json => Box::new(JsonReporter::new().with_pretty(true)),
+    }
+}
+
+/// Writes the coverage report if requested and enforces the threshold.
+fn check_coverage(args: &TestArgs, coverage: &CoverageReport) -> Result<()> {
+    if let Some(out) = &args.coverage_out {
+        let content = match args.coverage_format {
+            CoverageFormat::Lcov => coverage.to_lcov(),
+            CoverageFormat::Json => serde_json::to_string_pretty(coverage)?,
+        };
+        std::fs::write(out, content)
+            .with_context(|| format!("Failed to write coverage report: {}", out.display()))?;
+        println!("Coverage report written to {}", out.display());
+    }
+
+    if let Some(dir) = &args.coverage_html {
+        let index = CoverageReporter::new(dir)
+            .write(coverage)
+            .context("Failed to write HTML coverage report")?;
+        println!("HTML coverage report written to {}", index.display());
+    }
+
+    if let Some(threshold) = args.coverage_threshold {
+        if !coverage.meets_threshold(threshold) {
+            anyhow::bail!(
+                "Coverage {:.1}% is below the threshold of {threshold:.1}%",
+                coverage.total_coverage
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks, or updates, the snapshot files in `dir`.
+///
+/// Returns the number of changed decisions that were not recorded.
+fn check_snapshots(runner: &TestRunner, suite: &TestSuite, dir: &Path) -> Result<usize> {
+    let stores = SnapshotStore::load_dir(dir)
+        .with_context(|| format!("Failed to load snapshots from {}", dir.display()))?;
+
+    let mut changed = 0;
+    for mut store in stores {
+        let result = runner
+            .run_snapshot_tests(suite, &mut store)
+            .with_context(|| format!("Failed to check snapshot {}", store.path().display()))?;
+
+        if result.updated {
+            println!(
+                "Snapshot {}: recorded {} decision(s), {} changed",
+                result.file.display(),
+                result.checked,
+                result.mismatches.len()
+            );
+            continue;
+        }
+
+        println!(
+            "Snapshot {}: {} decision(s), {} changed",
+            result.file.display(),
+            result.checked,
+            result.mismatches.len()
+        );
+        for mismatch in &result.mismatches {
+            println!(
+                "  ✗ input {} {}: was {}, now {}",
+                mismatch.index,
+                mismatch.input,
+                decision(mismatch.expected),
+                decision(mismatch.actual)
+            );
+        }
+        changed += result.mismatches.len();
+    }
+    Ok(changed)
+}
+
+const fn decision(allowed: bool) -> &'static str {
+    if allowed {
+        "allow"
+    } else {
+        "deny"
+    }
+}
+
+/// Lints the discovered policy files, failing on error-level violations.
+fn lint_policies(path: &Path, policy_files: &HashMap<PathBuf, String>) -> Result<()> {
+    let linter = load_linter(path)?;
+
+    let mut files: Vec<_> = policy_files.iter().collect();
+    files.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut errors = 0;
+    for (file, source) in files {
+        let violations = linter.lint(source, &file.to_string_lossy());
+        if violations.is_empty() {
+            continue;
+        }
+
+        println!("{}", file.display());
+        for violation in &violations {
+            println!("  {}", format_violation(violation));
+        }
+        errors += violations
+            .iter()
+            .filter(|v| v.severity == Severity::Error)
+            .count();
+    }
+
+    if errors > 0 {
+        anyhow::bail!("{errors} lint error(s)");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(path: PathBuf) -> TestArgs {
+        TestArgs {
+            path,
+            fail_fast: false,
+            parallel: false,
+            jobs: None,
+            format: ReportFormat::Console,
+            output_file: None,
+            no_color: true,
+            filter: None,
+            package: None,
+            lint: false,
+            coverage: false,
+            coverage_format: CoverageFormat::Lcov,
+            coverage_out: None,
+            coverage_html: None,
+            coverage_threshold: None,
+            watch: false,
+            debounce_ms: 200,
+            timeout_per_test: None,
+            timeout_global: None,
+            snapshot_dir: None,
+            update_snapshots: false,
+            fuzz: false,
+            fuzz_runs: DEFAULT_FUZZ_RUNS,
+            verbose: false,
+        }
+    }
+
+    fn write_policies(dir: &Path) {
+        std::fs::write(
+            dir.join("authz.rego"),
+            r#"package authz
+
+import future.keywords.if
+
+default allow := false
+
+allow if {
+    input.caller.type == "admin"
+}
+
+deny if {
+    input.caller.type == "banned"
+}
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("authz_test.rego"),
+            r#"package authz_test
+
+import data.authz
+import future.keywords.if
+
+test_admin_allowed if {
+    authz.allow with input as {"caller": {"type": "admin"}}
+}
+"#,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_coverage_html_writes_report() {
+        let dir = tempfile::tempdir().unwrap();
+        write_policies(dir.path());
+        let out = dir.path().join("coverage-report");
+
+        let mut args = args(dir.path().to_path_buf());
+        args.coverage_html = Some(out.clone());
+        run(&args, OutputFormat::Text).unwrap();
+
+        let html = std::fs::read_to_string(out.join("index.html")).unwrap();
+        assert!(html.contains("authz.rego"));
+        assert!(html.contains("<li class=\"covered\">allow (line 5)</li>"));
+        assert!(html.contains("<li class=\"uncovered\">deny (line 11)</li>"));
+        assert!(!html.contains("authz_test.rego"));
+    }
+
+    #[test]
+    fn test_coverage_out_writes_lcov() {
+        let dir = tempfile::tempdir().unwrap();
+        write_policies(dir.path());
+        let out = dir.path().join("cov.info");
+
+        let mut args = args(dir.path().to_path_buf());
+        args.coverage_out = Some(out.clone());
+        run(&args, OutputFormat::Text).unwrap();
+
+        let lcov = std::fs::read_to_string(out).unwrap();
+        assert!(lcov.contains("SF:"));
+        assert!(lcov.contains("authz.rego"));
+        assert!(!lcov.contains("authz_test.rego"));
+        assert!(lcov.contains("end_of_record"));
+    }
+
+    #[test]
+    fn test_coverage_threshold_fails_run() {
+        let dir = tempfile::tempdir().unwrap();
+        write_policies(dir.path());
+
+        let mut args = args(dir.path().to_path_buf());
+        args.coverage_threshold = Some(100.0);
+        let err = run(&args, OutputFormat::Text).unwrap_err();
+        assert!(err.to_string().contains("below the threshold"));
+
+        args.coverage_threshold = Some(0.0);
+        assert!(run(&args, OutputFormat::Text).is_ok());
+    }
+
+    #[test]
+    fn test_junit_output_file() {
+        let dir = tempfile::tempdir().unwrap();
+        write_policies(dir.path());
+        let out = dir.path().join("report.xml");
+
+        let mut args = args(dir.path().to_path_buf());
+        args.format = ReportFormat::Junit;
+        args.output_file = Some(out.clone());
+        run(&args, OutputFormat::Text).unwrap();
+
+        let xml = std::fs::read_to_string(out).unwrap();
+        assert!(xml.contains(r#"<testsuite name="authz_test" tests="1" failures="0""#));
+        assert!(xml.contains(r#"<testcase name="test_admin_allowed""#));
+    }
+
+    #[test]
+    fn test_filter_and_package_select_tests() {
+        let dir = tempfile::tempdir().unwrap();
+        write_policies(dir.path());
+        let out = dir.path().join("report.json");
+
+        let mut args = args(dir.path().to_path_buf());
+        args.format = ReportFormat::Json;
+        args.output_file = Some(out.clone());
+        args.jobs = Some(2);
+
+        let total = |args: &TestArgs| {
+            run(args, OutputFormat::Text).unwrap();
+            let report: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+            report["results"].as_array().unwrap().len()
+        };
+
+        args.package = Some("authz_test".to_string());
+        assert_eq!(total(&args), 1);
+
+        args.filter = Some("test_guest_.*".to_string());
+        assert_eq!(total(&args), 0);
+
+        args.filter = None;
+        args.package = Some("other".to_string());
+        assert_eq!(total(&args), 0);
+    }
+
+    #[test]
+    fn test_affected_suite() {
+        let dir = tempfile::tempdir().unwrap();
+        write_policies(dir.path());
+        std::fs::write(
+            dir.path().join("audit_test.rego"),
+            "package audit_test\n\nimport future.keywords.if\n\n\
+             test_noop if { data.authz_admin.enabled }\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("data.json"), "{}").unwrap();
+        let suite = TestDiscovery::new().discover(dir.path()).unwrap();
+
+        let affected = affected_suite(&suite, &[dir.path().join("authz.rego")]).unwrap();
+        let tests: Vec<&str> = affected.tests().iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(tests, vec!["test_admin_allowed"]);
+        assert_eq!(affected.policy_files().len(), suite.policy_files().len());
+
+        // Data changes and deleted policies re-run everything
+        assert!(affected_suite(&suite, &[dir.path().join("data.json")]).is_none());
+        assert!(affected_suite(&suite, &[dir.path().join("gone.rego")]).is_none());
+    }
+
+    #[test]
+    fn test_references_package() {
+        let source = "import data.authz\nx := data.authz_admin.y\n";
+        assert!(references_package(source, "authz"));
+        assert!(references_package(source, "authz_admin"));
+        assert!(!references_package(source, "auth"));
+        assert!(references_package(
+            "allow if data.authz.roles.admin",
+            "authz.roles"
+        ));
+    }
+
+    #[test]
+    fn test_timeout_flags() {
+        let mut args = args(PathBuf::from("policies"));
+        assert_eq!(test_config(&args).test_timeout, None);
+
+        args.timeout_per_test = Some(5);
+        args.timeout_global = Some(60);
+        let config = test_config(&args);
+        assert_eq!(config.test_timeout, Some(Duration::from_secs(5)));
+        assert_eq!(config.global_timeout, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_snapshot_dir_detects_changed_decisions() {
+        let dir = tempfile::tempdir().unwrap();
+        write_policies(dir.path());
+        let snapshots = dir.path().join("snapshots");
+        let mut store = SnapshotStore::new(snapshots.join("authz.toml"), "data.authz.allow");
+        store.record(serde_json::json!({"caller": {"type": "admin"}}), false);
+        store.record(serde_json::json!({"caller": {"type": "guest"}}), false);
+        store.save().unwrap();
+
+        let mut args = args(dir.path().to_path_buf());
+        args.snapshot_dir = Some(snapshots.clone());
+        let err = run(&args, OutputFormat::Text).unwrap_err();
+        assert!(
+            err.to_string().contains("1 snapshot decision(s) changed"),
+            "{err}"
+        );
+
+        args.update_snapshots = true;
+        run(&args, OutputFormat::Text).unwrap();
+        let store = SnapshotStore::load(snapshots.join("authz.toml")).unwrap();
+        assert!(store.decisions()[0].1);
+
+        args.update_snapshots = false;
+        run(&args, OutputFormat::Text).unwrap();
+    }
+
+    #[test]
+    fn test_fuzz_catches_missing_default() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("open.rego"),
+            "package open\n\nimport future.keywords.if\n\nallow if input.method == \"GET\"\n",
+        )
+        .unwrap();
+
+        // Fuzzing runs even without tests
+        let mut args = args(dir.path().to_path_buf());
+        run(&args, OutputFormat::Text).unwrap();
+
+        args.fuzz = true;
+        args.fuzz_runs = 50;
+        let err = run(&args, OutputFormat::Text).unwrap_err();
+        assert_eq!(err.to_string(), "1 test(s) failed");
+    }
+
+    #[test]
+    fn test_watcher_fires_on_change() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::{mpsc, Arc};
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let policy = root.join("authz.rego");
+        std::fs::write(&policy, "package authz\n").unwrap();
+
+        let watcher =
+            FileWatcher::new(&root, Duration::from_millis(50), watch::WATCHED_EXTENSIONS).unwrap();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        let task = {
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || {
+                watcher.run(&stop, |changed| {
+                    tx.send(changed.to_vec()).ok();
+                    Ok(())
+                });
+            })
+        };
+
+        std::fs::write(&policy, "package authz\n\ndefault allow := false\n").unwrap();
+        let changed = rx
+            .recv_timeout(Duration::from_millis(500))
+            .expect("watcher did not fire within 500ms");
+        assert_eq!(changed, vec![policy]);
+
+        stop.store(true, Ordering::Relaxed);
+        task.join().unwrap();
+    }
+
+    #[test]
+    fn test_watch_session_reloads_changed_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let policy = root.join("authz.rego");
+        let name = policy.to_string_lossy().to_string();
+        std::fs::write(&policy, "package authz\n\ndefault allow := false\n").unwrap();
+
+        let mut session = WatchSession::new(&root, TestRunner::default(), false);
+        session.start().unwrap();
+        assert_eq!(
+            session.engine.get_policy_info(&name).unwrap().package,
+            "authz"
+        );
+
+        std::fs::write(&policy, "package authz.v2\n\ndefault allow := false\n").unwrap();
+        session.rerun(std::slice::from_ref(&policy)).unwrap();
+        assert_eq!(
+            session.engine.get_policy_info(&name).unwrap().package,
+            "authz.v2"
+        );
+
+        // A policy that fails to compile holds back the tests until fixed
+        std::fs::write(&policy, "default allow := false\n").unwrap();
+        assert!(session.rerun(std::slice::from_ref(&policy)).is_err());
+        assert!(session.engine.get_policy_info(&name).is_none());
+
+        std::fs::remove_file(&policy).unwrap();
+        session.rerun(&[policy]).unwrap();
+        assert!(session.broken.is_empty());
+        assert!(session.engine.policy_files().is_empty());
+    }
+
+    #[test]
+    fn test_watch_session_reloads_changed_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::write(root.join("authz.rego"), "package authz\n").unwrap();
+        let data = root.join("data.json");
+        std::fs::write(&data, r#"{"roles": ["admin"]}"#).unwrap();
+
+        let mut session = WatchSession::new(&root, TestRunner::default(), false);
+        session.start().unwrap();
+        assert_eq!(session.data_files, BTreeSet::from([data.clone()]));
+
+        std::fs::remove_file(&data).unwrap();
+        session.rerun(std::slice::from_ref(&data)).unwrap();
+        assert!(session.data_files.is_empty());
     }
 }
@@ -1,17 +1,28 @@
 //! Validate command implementation.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args;
 use tracing::info;
 
-use eunomia_compiler::{Analyzer, Parser};
+use crate::output::OutputFormat;
+use crate::watch;
+
+use eunomia_core::Bundle;
+
+use eunomia_compiler::{
+    IssueSeverity, LintConfig, LintViolation, Linter, PolicyValidator, RegoVersion, SarifLog,
+    ServiceContract, Severity, ValidationIssue, ValidationReport, ValidatorConfig,
+};
 
 /// Arguments for the validate command.
 #[derive(Args)]
+// One bool per independent `--flag`, as clap expects
+#[allow(clippy::struct_excessive_bools)]
 pub struct ValidateArgs {
-    /// Path to policies directory or specific policy file
+    /// Path to policies directory, specific policy file or bundle archive
+    /// (`.tar.gz`)
     #[arg(default_value = "policies")]
     pub path: PathBuf,
 
@@ -19,82 +30,348 @@ pub struct ValidateArgs {
     #[arg(long, default_value = "true")]
     pub require_default: bool,
 
+    /// Rego syntax version to enforce (v0, v1, or auto to detect from imports)
+    #[arg(long, default_value = "v0")]
+    pub rego_version: RegoVersion,
+
+    /// Skip lint checks
+    #[arg(long)]
+    pub no_lint: bool,
+
+    /// Count lint violations silenced by suppression comments
+    #[arg(long)]
+    pub show_suppressed: bool,
+
+    /// Re-validate changed policies whenever files change
+    #[arg(long)]
+    pub watch: bool,
+
+    /// OpenAPI spec (JSON or YAML) to check operation IDs and methods
+    /// against (repeatable)
+    #[arg(long = "contract", value_name = "OPENAPI")]
+    pub contracts: Vec<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ValidateFormat::Text)]
+    pub format: ValidateFormat,
+
+    /// File to write the report to (required for SARIF)
+    #[arg(short, long = "output", required_if_eq("format", "sarif"))]
+    pub output_file: Option<PathBuf>,
+
     /// Show detailed output
     #[arg(short, long)]
     pub verbose: bool,
 }
 
+/// Output format of the validate command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ValidateFormat {
+    /// Human-readable findings per policy.
+    #[default]
+    Text,
+    /// SARIF 2.1.0 log with the findings of all policies, for code scanning.
+    Sarif,
+}
+
 /// Runs the validate command.
-pub fn run(args: &ValidateArgs) -> Result<()> {
+///
+/// With `--result-format json` or `--result-format yaml`, the
+/// `ValidationReport` of a policy file, or the reports of every policy in a
+/// directory, are written to stdout instead.
+///
+/// A bundle archive is validated as a whole: each of its policies, the
+/// imports between them, and the bundle structure.
+pub fn run(args: &ValidateArgs, output: OutputFormat) -> Result<()> {
     info!(path = ?args.path, "Validating policies");
 
+    if is_bundle_archive(&args.path) {
+        if args.watch || args.format == ValidateFormat::Sarif {
+            anyhow::bail!("--watch and --format sarif are not supported for bundle archives");
+        }
+        return validate_bundle_archive(args, output);
+    }
+
+    if output.is_structured() {
+        if args.watch {
+            anyhow::bail!("--watch is not supported with JSON or YAML output");
+        }
+        if !args.path.exists() {
+            anyhow::bail!("Path does not exist: {}", args.path.display());
+        }
+        return print_reports(args, output);
+    }
+
     println!("Eunomia Policy Validator");
     println!("========================");
     println!("Path: {}", args.path.display());
     println!();
 
-    let parser = Parser::new();
-    let analyzer = Analyzer::new().with_require_default(args.require_default);
+    if !args.path.exists() {
+        anyhow::bail!("Path does not exist: {}", args.path.display());
+    }
+
+    if args.format == ValidateFormat::Sarif {
+        if args.watch {
+            anyhow::bail!("--watch is not supported with --format sarif");
+        }
+        return write_sarif(args);
+    }
+
+    if args.watch {
+        if let Err(e) = validate_path(args) {
+            println!("✗ {e:#}");
+        }
+        return watch::watch(&args.path, |changed| revalidate(args, changed));
+    }
+
+    validate_path(args)
+}
+
+/// Validates every policy under the target path.
+fn validate_path(args: &ValidateArgs) -> Result<()> {
+    let validator = build_validator(args)?;
 
     // Check if path is a file or directory
-    if args.path.is_file() {
-        validate_file(&args.path, &parser, &analyzer, args.verbose)?;
-    } else if args.path.is_dir() {
-        validate_directory(&args.path, &parser, &analyzer, args.verbose)?;
+    let suppressed = if args.path.is_file() {
+        validate_file(&args.path, &validator, args)?
     } else {
-        anyhow::bail!("Path does not exist: {}", args.path.display());
+        validate_directory(&args.path, &validator, args)?
+    };
+
+    if args.show_suppressed {
+        println!("\nSuppressed lint violations: {suppressed}");
     }
 
     println!("\n✓ All policies validated successfully");
     Ok(())
 }
 
-fn validate_file(
-    path: &PathBuf,
-    parser: &Parser,
-    analyzer: &Analyzer,
-    verbose: bool,
-) -> Result<()> {
-    let policy = parser.parse_file(path)?;
-
-    if verbose {
-        println!("Validating: {}", path.display());
-        println!("  Package: {}", policy.package_name);
+/// Re-validates after a change in watch mode.
+///
+/// Only changed policies are validated; any other change (such as the lint
+/// config) or a deleted policy re-validates the whole path.
+fn revalidate(args: &ValidateArgs, changed: &[PathBuf]) -> Result<()> {
+    let only_policies = changed
+        .iter()
+        .all(|path| path.is_file() && path.extension().is_some_and(|e| e == "rego"));
+    if !only_policies {
+        return validate_path(args);
     }
 
-    let result = analyzer.analyze(&policy)?;
+    let validator = build_validator(args)?;
+    let failed = changed
+        .iter()
+        .filter(|path| match validate_file(path, &validator, args) {
+            Ok(_) => false,
+            Err(e) => {
+                println!("✗ {}: {e}", path.display());
+                true
+            }
+        })
+        .count();
 
-    if verbose {
-        println!(
-            "  Has default: {}",
-            result.has_default_allow || result.has_default_deny
+    if failed > 0 {
+        anyhow::bail!(
+            "{failed} of {} changed policies failed validation",
+            changed.len()
         );
-        println!("  Imports: {}", result.imports.len());
-        println!("  Rules: {}", result.rules.len());
+    }
+    println!(
+        "\n✓ {} changed policies validated successfully",
+        changed.len()
+    );
+    Ok(())
+}
 
-        for warning in &result.warnings {
-            println!("  ⚠ Warning: {}", warning.message);
+/// Builds a linter from the nearest `.eunomia-lint.toml`, if any.
+pub fn load_linter(path: &Path) -> Result<Linter> {
+    match LintConfig::discover(path)? {
+        Some((config_path, config)) => {
+            println!("Lint config: {}", config_path.display());
+            Ok(Linter::from_config(&config)?)
         }
+        None => Ok(Linter::new()),
     }
+}
 
-    println!("✓ {}", path.display());
+/// Loads the OpenAPI contracts policies are checked against.
+fn load_contracts(paths: &[PathBuf]) -> Result<Vec<ServiceContract>> {
+    paths
+        .iter()
+        .map(|path| {
+            ServiceContract::from_openapi_file(path)
+                .with_context(|| format!("Failed to load contract: {}", path.display()))
+        })
+        .collect()
+}
+
+/// Validates every policy under the target path and writes all findings to
+/// the output file as a single SARIF run.
+fn write_sarif(args: &ValidateArgs) -> Result<()> {
+    let output = args
+        .output_file
+        .as_ref()
+        .context("--output is required with --format sarif")?;
+
+    let reports = validate_reports(args)?;
+
+    std::fs::write(output, SarifLog::from_reports(&reports).to_json()?)
+        .with_context(|| format!("Failed to write SARIF log: {}", output.display()))?;
+
+    let findings: usize = reports.iter().map(|r| r.issues.len()).sum();
+    println!("SARIF log: {} ({findings} findings)", output.display());
+
+    check_reports(&reports)
+}
+
+/// Validates every policy under the target path and writes the reports to
+/// stdout in `output`'s format: a single report for a file, and a list of
+/// reports for a directory.
+fn print_reports(args: &ValidateArgs, output: OutputFormat) -> Result<()> {
+    let reports = validate_reports(args)?;
+    match reports.as_slice() {
+        [report] if args.path.is_file() => output.print(report)?,
+        _ => output.print(&reports)?,
+    }
+    check_reports(&reports)
+}
+
+/// Fails if any of the reports has errors.
+fn check_reports(reports: &[ValidationReport]) -> Result<()> {
+    let failed = reports.iter().filter(|r| !r.is_valid()).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} policies failed validation", reports.len());
+    }
+    Ok(())
+}
+
+/// Returns whether `path` is a bundle archive rather than policy sources.
+fn is_bundle_archive(path: &Path) -> bool {
+    path.is_file() && path.to_string_lossy().ends_with(".tar.gz")
+}
+
+/// Validates a bundle archive with [`PolicyValidator::validate_bundle`].
+fn validate_bundle_archive(args: &ValidateArgs, output: OutputFormat) -> Result<()> {
+    let bundle = Bundle::from_file(&args.path)
+        .with_context(|| format!("Failed to load bundle: {}", args.path.display()))?;
+    let report = build_validator(args)?.validate_bundle(&bundle);
+
+    if output.is_structured() {
+        output.print(&report)?;
+    } else {
+        println!("Bundle: {} v{}", bundle.name, bundle.version);
+        for issue in &report.issues {
+            println!("  {}", format_issue(issue));
+        }
+    }
+
+    check_reports(std::slice::from_ref(&report))?;
+    if !output.is_structured() {
+        println!("\n✓ Bundle validated successfully");
+    }
     Ok(())
 }
 
+/// Builds a [`PolicyValidator`] configured from the command's arguments.
+fn build_validator(args: &ValidateArgs) -> Result<PolicyValidator> {
+    let mut config = ValidatorConfig {
+        require_default_deny: args.require_default,
+        rego_version: args.rego_version,
+        run_linting: !args.no_lint,
+        ..ValidatorConfig::default()
+    };
+    for contract in load_contracts(&args.contracts)? {
+        config = config.with_contract(contract);
+    }
+
+    let mut validator = PolicyValidator::with_config(config);
+    if !args.no_lint {
+        if let Some((_, lint_config)) = LintConfig::discover(&args.path)? {
+            validator = validator.with_linter(Linter::from_config(&lint_config)?);
+        }
+    }
+    Ok(validator)
+}
+
+/// Validates every policy under the target path with a [`PolicyValidator`],
+/// returning their reports in path order.
+fn validate_reports(args: &ValidateArgs) -> Result<Vec<ValidationReport>> {
+    let validator = build_validator(args)?;
+
+    let mut files = if args.path.is_file() {
+        vec![args.path.clone()]
+    } else {
+        walkdir(&args.path)?
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .map_or(true, |path| path.extension().is_some_and(|e| e == "rego"))
+            })
+            .collect::<Result<Vec<_>>>()?
+    };
+    files.sort();
+
+    let reports = files
+        .iter()
+        .map(|path| validator.validate_file(path))
+        .collect::<eunomia_compiler::Result<Vec<_>>>()?;
+    Ok(reports)
+}
+
+/// Validates a single file, returning the number of suppressed lint violations.
+fn validate_file(path: &Path, validator: &PolicyValidator, args: &ValidateArgs) -> Result<usize> {
+    let report = validator.validate_file(path)?;
+
+    if args.verbose {
+        println!("Validating: {}", path.display());
+        if let Some(package) = &report.package {
+            println!("  Package: {package}");
+        }
+        if let Some(analysis) = &report.analysis {
+            println!(
+                "  Has default: {}",
+                analysis.has_default_allow || analysis.has_default_deny
+            );
+            println!("  Imports: {}", analysis.imports.len());
+            println!("  Rules: {}", analysis.rules.len());
+        }
+    }
+
+    for issue in &report.issues {
+        println!("  {}", format_issue(issue));
+    }
+    let suppressed = report.suppressed_lint_violations;
+    if args.show_suppressed && suppressed > 0 {
+        println!("  ({suppressed} suppressed lint violations)");
+    }
+
+    let errors = report.errors();
+    if let Some(first) = errors.first() {
+        anyhow::bail!("{} error(s): {}", errors.len(), first.message);
+    }
+
+    println!("✓ {}", path.display());
+    Ok(suppressed)
+}
+
 fn validate_directory(
     path: &PathBuf,
-    parser: &Parser,
-    analyzer: &Analyzer,
-    verbose: bool,
-) -> Result<()> {
+    validator: &PolicyValidator,
+    args: &ValidateArgs,
+) -> Result<usize> {
     let mut count = 0;
+    let mut suppressed = 0;
     let mut errors = Vec::new();
 
     for entry in walkdir(path)? {
         let entry_path = entry?;
         if entry_path.extension().is_some_and(|e| e == "rego") {
-            match validate_file(&entry_path, parser, analyzer, verbose) {
-                Ok(()) => count += 1,
+            match validate_file(&entry_path, validator, args) {
+                Ok(n) => {
+                    count += 1;
+                    suppressed += n;
+                }
                 Err(e) => errors.push((entry_path, e)),
             }
         }
@@ -109,7 +386,45 @@ fn validate_directory(
     }
 
     println!("\nValidated {count} policies");
-    Ok(())
+    Ok(suppressed)
+}
+
+/// Formats a lint violation as a single line.
+pub fn format_violation(violation: &LintViolation) -> String {
+    let marker = match violation.severity {
+        Severity::Error => "✗",
+        Severity::Warning => "⚠",
+        Severity::Hint => "ℹ",
+    };
+    let location = violation
+        .line
+        .map(|line| format!(" (line {line})"))
+        .unwrap_or_default();
+
+    format!(
+        "{marker} [{}] {}{location}",
+        violation.rule_id, violation.message
+    )
+}
+
+/// Formats a validation issue as a single line, matching [`format_violation`].
+pub fn format_issue(issue: &ValidationIssue) -> String {
+    let marker = match issue.severity {
+        IssueSeverity::Error => "✗",
+        IssueSeverity::Warning => "⚠",
+        IssueSeverity::Hint => "ℹ",
+    };
+    let rule = issue
+        .rule_id
+        .as_ref()
+        .map(|id| format!("[{id}] "))
+        .unwrap_or_default();
+    let location = issue
+        .line
+        .map(|line| format!(" (line {line})"))
+        .unwrap_or_default();
+
+    format!("{marker} {rule}{}{location}", issue.message)
 }
 
 /// Simple directory walker (placeholder - would use walkdir crate in production).
@@ -121,3 +436,203 @@ fn walkdir(path: &PathBuf) -> Result<impl Iterator<Item = Result<PathBuf>>> {
 
     Ok(entries.into_iter())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r"package test.authz
+
+import future.keywords.if
+
+default allow := false
+
+allow := true
+";
+
+    fn args(path: PathBuf) -> ValidateArgs {
+        ValidateArgs {
+            path,
+            require_default: true,
+            rego_version: RegoVersion::V0,
+            no_lint: false,
+            show_suppressed: true,
+            watch: false,
+            contracts: Vec::new(),
+            format: ValidateFormat::Text,
+            output_file: None,
+            verbose: false,
+        }
+    }
+
+    #[test]
+    fn test_lint_config_severity_fails_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("authz.rego"), POLICY).unwrap();
+        std::fs::write(
+            dir.path().join(".eunomia-lint.toml"),
+            "[severity]\n\"security/no-wildcard-allow\" = \"error\"\n",
+        )
+        .unwrap();
+
+        assert!(run(&args(dir.path().to_path_buf()), OutputFormat::Text).is_err());
+
+        let mut no_lint = args(dir.path().to_path_buf());
+        no_lint.no_lint = true;
+        assert!(run(&no_lint, OutputFormat::Text).is_ok());
+    }
+
+    #[test]
+    fn test_suppression_comment_passes_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = POLICY.replace(
+            "allow := true",
+            "allow := true # eunomia-lint: disable=security/no-wildcard-allow",
+        );
+        std::fs::write(dir.path().join("authz.rego"), source).unwrap();
+        std::fs::write(
+            dir.path().join(".eunomia-lint.toml"),
+            "[severity]\n\"security/no-wildcard-allow\" = \"error\"\n",
+        )
+        .unwrap();
+
+        assert!(run(&args(dir.path().to_path_buf()), OutputFormat::Text).is_ok());
+    }
+
+    #[test]
+    fn test_rego_version_v1_rejects_v0_syntax() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("authz.rego");
+        std::fs::write(
+            &path,
+            "package test.authz\n\ndefault allow := false\n\nallow {\n    input.caller.admin\n}\n",
+        )
+        .unwrap();
+
+        let mut args = args(path);
+        args.no_lint = true;
+        assert!(run(&args, OutputFormat::Text).is_ok());
+
+        args.rego_version = RegoVersion::V1;
+        let err = run(&args, OutputFormat::Text).unwrap_err();
+        assert!(err.to_string().contains("Rego v1"));
+    }
+
+    #[test]
+    fn test_revalidate_changed_policies() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("authz.rego");
+        let bad = dir.path().join("broken.rego");
+        std::fs::write(&good, POLICY).unwrap();
+        std::fs::write(&bad, "package broken\n\nallow := true\n").unwrap();
+
+        let mut args = args(dir.path().to_path_buf());
+        args.no_lint = true;
+
+        assert!(revalidate(&args, std::slice::from_ref(&good)).is_ok());
+        let err = revalidate(&args, &[good, bad.clone()]).unwrap_err();
+        assert!(err.to_string().contains("1 of 2 changed policies"));
+
+        // A deleted policy re-validates the whole directory
+        std::fs::remove_file(&bad).unwrap();
+        assert!(revalidate(&args, &[bad]).is_ok());
+    }
+
+    #[test]
+    fn test_contract_checks_operations() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = dir.path().join("authz.rego");
+        let contract = dir.path().join("openapi.yaml");
+        let rule = r#"allow if {
+    input.operation_id == "getUser"
+    input.method == "DELETE"
+}"#;
+        std::fs::write(&policy, POLICY.replace("allow := true", rule)).unwrap();
+        std::fs::write(
+            &contract,
+            r"openapi: 3.0.3
+info:
+  title: users-service
+paths:
+  /users/{id}:
+    get:
+      operationId: getUser
+",
+        )
+        .unwrap();
+
+        let mut args = args(policy.clone());
+        args.no_lint = true;
+        args.contracts = vec![contract];
+        let report = build_validator(&args)
+            .unwrap()
+            .validate_file(&policy)
+            .unwrap();
+        assert!(report
+            .issues
+            .iter()
+            .any(|i| i.message.contains("not allowed for operation 'getUser'")));
+        assert!(run(&args, OutputFormat::Text).is_ok());
+
+        args.contracts = vec![dir.path().join("missing.yaml")];
+        let err = run(&args, OutputFormat::Text).unwrap_err();
+        assert!(format!("{err:#}").contains("missing.yaml"));
+    }
+
+    #[test]
+    fn test_sarif_output_aggregates_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("authz.rego"), POLICY).unwrap();
+        std::fs::write(
+            dir.path().join("users.rego"),
+            POLICY.replace("test.authz", "test.users"),
+        )
+        .unwrap();
+        let output = dir.path().join("findings.sarif");
+
+        let mut args = args(dir.path().to_path_buf());
+        args.format = ValidateFormat::Sarif;
+        args.output_file = Some(output.clone());
+        assert!(run(&args, OutputFormat::Text).is_ok());
+
+        let sarif: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif["runs"].as_array().unwrap().len(), 1);
+
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        let wildcard: Vec<_> = results
+            .iter()
+            .filter(|r| r["ruleId"] == "security/no-wildcard-allow")
+            .collect();
+        assert_eq!(wildcard.len(), 2);
+        let uri = wildcard[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap();
+        assert!(uri.ends_with("authz.rego"));
+    }
+
+    #[test]
+    fn test_bundle_archive_is_validated_as_a_whole() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test-1.0.0.tar.gz");
+
+        Bundle::builder("test")
+            .version("1.0.0")
+            .add_policy("test.authz", POLICY)
+            .build()
+            .write_to_file(&path)
+            .unwrap();
+        assert!(run(&args(path.clone()), OutputFormat::Json).is_ok());
+
+        Bundle::builder("test")
+            .version("1.0.0")
+            .add_policy("test.authz", POLICY)
+            .add_root("missing")
+            .build()
+            .write_to_file(&path)
+            .unwrap();
+        let err = run(&args(path), OutputFormat::Text).unwrap_err();
+        assert!(err.to_string().contains("failed validation"));
+    }
+}
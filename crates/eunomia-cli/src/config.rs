@@ -0,0 +1,406 @@
+//! Project configuration from `.eunomia.toml`.
+//!
+//! The configuration file is looked up in the current directory and each of
+//! its parents, so that commands run anywhere inside a project pick up the
+//! project's settings; `--config` names the file explicitly. Settings in the
+//! file are defaults only: flags and environment variables take precedence.
+//!
+//! ```toml
+//! [registry]
+//! url = "https://registry.example.com"
+//! namespace = "platform"
+//!
+//! [signer]
+//! key_file = "keys/signing.key"
+//!
+//! [distributor]
+//! endpoints = ["10.0.0.1:8080", "10.0.0.2:8080"]
+//!
+//! [lint]
+//! strict = true
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::commands::diff::DiffArgs;
+use crate::commands::doctor::DoctorArgs;
+use crate::commands::fetch::FetchArgs;
+use crate::commands::lint::LintArgs;
+use crate::commands::publish::PublishArgs;
+use crate::commands::push::PushArgs;
+use crate::commands::replay::ReplayArgs;
+use crate::commands::rollback::RollbackArgs;
+use crate::commands::sign::SignArgs;
+
+/// File name of the project configuration.
+pub const CONFIG_FILE: &str = ".eunomia.toml";
+
+/// Error message of registry commands run without a registry URL.
+pub const MISSING_REGISTRY: &str = "No registry given: pass --registry, set \
+                                    EUNOMIA_REGISTRY_URL or set registry.url in .eunomia.toml";
+
+/// Contents of a `.eunomia.toml` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EunomiaConfig {
+    /// Defaults of the registry commands.
+    pub registry: RegistryConfig,
+    /// Defaults of the commands that sign bundles.
+    pub signer: Option<SignerConfig>,
+    /// Defaults of the commands that deploy to instances.
+    pub distributor: Option<DistributorConfig>,
+    /// Defaults of the lint command.
+    pub lint: LintConfig,
+}
+
+/// The `[registry]` section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegistryConfig {
+    /// Registry URL.
+    pub url: Option<String>,
+    /// Namespace prefix for repositories.
+    pub namespace: Option<String>,
+    /// Username for basic authentication.
+    pub username: Option<String>,
+}
+
+/// The `[signer]` section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignerConfig {
+    /// Private key file, relative to the configuration file.
+    pub key_file: Option<PathBuf>,
+}
+
+/// The `[distributor]` section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DistributorConfig {
+    /// Instance endpoints (host:port) to push to and roll back.
+    pub endpoints: Vec<String>,
+    /// Roll back pushes that fail.
+    pub auto_rollback: bool,
+}
+
+/// The `[lint]` section.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// Fail on warnings as well as errors.
+    pub strict: bool,
+    /// Fail on hints as well as warnings and errors.
+    pub fail_on_hint: bool,
+}
+
+impl EunomiaConfig {
+    /// Reads a configuration file.
+    ///
+    /// Relative paths in the file are resolved against its directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid
+    /// configuration.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        let mut config: Self = toml::from_str(&content)
+            .with_context(|| format!("Invalid config file: {}", path.display()))?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        if let Some(key_file) = config.signer.as_mut().and_then(|s| s.key_file.as_mut()) {
+            if key_file.is_relative() {
+                *key_file = base.join(&*key_file);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Returns the `.eunomia.toml` in `dir` or the nearest of its parents.
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Fills the arguments of a command that were not given on the command
+    /// line with the configured defaults.
+    pub fn merge_with_args<A: ConfigDefaults>(&self, args: &mut A) {
+        args.apply_config(self);
+    }
+}
+
+/// Command arguments with defaults in the project configuration.
+pub trait ConfigDefaults {
+    /// Fills the arguments that were not given with the configured defaults.
+    fn apply_config(&mut self, config: &EunomiaConfig);
+}
+
+/// Sets an unset argument to its configured default.
+fn fill<T: Clone>(arg: &mut Option<T>, default: Option<&T>) {
+    if arg.is_none() {
+        *arg = default.cloned();
+    }
+}
+
+/// Sets a flag that was neither given nor turned off with its `--no-` flag
+/// to its configured default.
+fn fill_flag(flag: &mut Option<bool>, turned_off: bool, default: bool) {
+    if turned_off {
+        *flag = Some(false);
+    }
+    fill(flag, Some(&default));
+}
+
+impl ConfigDefaults for PublishArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        fill(&mut self.registry, config.registry.url.as_ref());
+        fill(&mut self.namespace, config.registry.namespace.as_ref());
+        fill(&mut self.username, config.registry.username.as_ref());
+        let key_file = config.signer.as_ref().and_then(|s| s.key_file.as_ref());
+        fill(&mut self.signing_key, key_file);
+    }
+}
+
+impl ConfigDefaults for FetchArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        fill(&mut self.registry, config.registry.url.as_ref());
+        fill(&mut self.namespace, config.registry.namespace.as_ref());
+        fill(&mut self.username, config.registry.username.as_ref());
+    }
+}
+
+impl ConfigDefaults for DiffArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        fill(&mut self.registry, config.registry.url.as_ref());
+        fill(&mut self.namespace, config.registry.namespace.as_ref());
+    }
+}
+
+impl ConfigDefaults for ReplayArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        fill(&mut self.registry, config.registry.url.as_ref());
+        fill(&mut self.namespace, config.registry.namespace.as_ref());
+    }
+}
+
+impl ConfigDefaults for SignArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        // A key given through EUNOMIA_SIGNING_KEY wins over the configured file
+        if self.key.is_none() {
+            let key_file = config.signer.as_ref().and_then(|s| s.key_file.as_ref());
+            fill(&mut self.key_file, key_file);
+        }
+    }
+}
+
+impl ConfigDefaults for PushArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        fill(&mut self.registry.url, config.registry.url.as_ref());
+        fill(
+            &mut self.registry.namespace,
+            config.registry.namespace.as_ref(),
+        );
+        if let Some(distributor) = &config.distributor {
+            if self.endpoints.is_empty() {
+                self.endpoints.clone_from(&distributor.endpoints);
+            }
+            fill_flag(
+                &mut self.auto_rollback,
+                self.no_auto_rollback,
+                distributor.auto_rollback,
+            );
+        }
+    }
+}
+
+impl ConfigDefaults for RollbackArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        if let Some(distributor) = &config.distributor {
+            if self.endpoints.is_empty() {
+                self.endpoints.clone_from(&distributor.endpoints);
+            }
+        }
+    }
+}
+
+impl ConfigDefaults for DoctorArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        fill(&mut self.registry, config.registry.url.as_ref());
+        let key_file = config.signer.as_ref().and_then(|s| s.key_file.as_ref());
+        fill(&mut self.key_file, key_file);
+        if let Some(distributor) = &config.distributor {
+            if self.endpoints.is_empty() {
+                self.endpoints.clone_from(&distributor.endpoints);
+            }
+        }
+    }
+}
+
+impl ConfigDefaults for LintArgs {
+    fn apply_config(&mut self, config: &EunomiaConfig) {
+        fill_flag(&mut self.strict, self.no_strict, config.lint.strict);
+        fill_flag(
+            &mut self.fail_on_hint,
+            self.no_fail_on_hint,
+            config.lint.fail_on_hint,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{Cli, Commands};
+    use clap::Parser;
+
+    const CONFIG: &str = "[registry]\n\
+                          url = \"https://registry.internal.example\"\n\
+                          namespace = \"platform\"\n\n\
+                          [signer]\n\
+                          key_file = \"keys/signing.key\"\n\n\
+                          [distributor]\n\
+                          endpoints = [\"10.0.0.1:8080\"]\n\n\
+                          [lint]\n\
+                          strict = true\n";
+
+    fn parse(args: &[&str]) -> Commands {
+        Cli::try_parse_from(args).unwrap().command
+    }
+
+    #[test]
+    fn test_find_searches_parent_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("policies/users-service");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(EunomiaConfig::find(&nested), None);
+
+        std::fs::write(dir.path().join(CONFIG_FILE), CONFIG).unwrap();
+        assert_eq!(
+            EunomiaConfig::find(&nested),
+            Some(dir.path().join(CONFIG_FILE))
+        );
+    }
+
+    #[test]
+    fn test_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        std::fs::write(&path, CONFIG).unwrap();
+
+        let config = EunomiaConfig::from_file(&path).unwrap();
+
+        assert_eq!(
+            config.registry.url.as_deref(),
+            Some("https://registry.internal.example")
+        );
+        assert_eq!(
+            config.signer.unwrap().key_file,
+            Some(dir.path().join("keys/signing.key"))
+        );
+        assert_eq!(config.distributor.unwrap().endpoints, ["10.0.0.1:8080"]);
+        assert!(config.lint.strict);
+        assert!(!config.lint.fail_on_hint);
+
+        std::fs::write(&path, "[registry]\nurl = 1\n").unwrap();
+        assert!(EunomiaConfig::from_file(&path).is_err());
+        std::fs::write(&path, "[unknown]\n").unwrap();
+        assert!(EunomiaConfig::from_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_flags_override_config() {
+        let config: EunomiaConfig = toml::from_str(CONFIG).unwrap();
+
+        let Commands::Fetch(mut args) = parse(&["eunomia", "fetch", "--service", "users"]) else {
+            unreachable!()
+        };
+        config.merge_with_args(&mut args);
+        assert_eq!(
+            args.registry.as_deref(),
+            Some("https://registry.internal.example")
+        );
+        assert_eq!(args.namespace.as_deref(), Some("platform"));
+
+        let Commands::Fetch(mut args) = parse(&[
+            "eunomia",
+            "fetch",
+            "--service",
+            "users",
+            "--registry",
+            "https://other.example",
+        ]) else {
+            unreachable!()
+        };
+        config.merge_with_args(&mut args);
+        assert_eq!(args.registry.as_deref(), Some("https://other.example"));
+
+        let Commands::Push(mut args) = parse(&[
+            "eunomia",
+            "push",
+            "--service",
+            "users",
+            "--version",
+            "1.0.0",
+            "--endpoints",
+            "127.0.0.1:8080",
+        ]) else {
+            unreachable!()
+        };
+        config.merge_with_args(&mut args);
+        assert_eq!(args.endpoints, ["127.0.0.1:8080"]);
+
+        let Commands::Lint(mut args) = parse(&["eunomia", "lint"]) else {
+            unreachable!()
+        };
+        config.merge_with_args(&mut args);
+        assert_eq!(args.strict, Some(true));
+        assert_eq!(args.fail_on_hint, Some(false));
+
+        // Flags turn configured defaults off, and the last one given wins
+        let Commands::Lint(mut args) = parse(&["eunomia", "lint", "--no-strict"]) else {
+            unreachable!()
+        };
+        config.merge_with_args(&mut args);
+        assert_eq!(args.strict, Some(false));
+
+        let Commands::Lint(mut args) = parse(&[
+            "eunomia",
+            "lint",
+            "--no-strict",
+            "--strict",
+            "--fail-on-hint",
+        ]) else {
+            unreachable!()
+        };
+        config.merge_with_args(&mut args);
+        assert_eq!(args.strict, Some(true));
+        assert_eq!(args.fail_on_hint, Some(true));
+
+        let config: EunomiaConfig =
+            toml::from_str("[distributor]\nauto_rollback = true\n").unwrap();
+        let push = |flags: &[&str]| {
+            let mut command_line = vec![
+                "eunomia",
+                "push",
+                "--service",
+                "users",
+                "--version",
+                "1.0.0",
+            ];
+            command_line.extend_from_slice(flags);
+            let Commands::Push(mut args) = parse(&command_line) else {
+                unreachable!()
+            };
+            config.merge_with_args(&mut args);
+            args.auto_rollback
+        };
+        assert_eq!(push(&[]), Some(true));
+        assert_eq!(push(&["--no-auto-rollback"]), Some(false));
+        assert_eq!(push(&["--no-auto-rollback", "--auto-rollback"]), Some(true));
+    }
+}
@@ -0,0 +1,129 @@
+//! Reporting of command errors.
+//!
+//! Errors caused by a [`DistributorError`] or a [`RegistryError`] are
+//! printed with the error's stable code, as `error[EUN-D001]: ...`, so that
+//! scripts can act on them without matching messages. Commands run with
+//! `--format json` print errors to stdout as a JSON object instead:
+//!
+//! ```json
+//! {
+//!   "code": "EUN-R003",
+//!   "message": "Failed to fetch bundle",
+//!   "details": ["Bundle not found: users-service:v1.2.0"]
+//! }
+//! ```
+
+use std::fmt;
+
+use anyhow::Error;
+use serde::Serialize;
+
+use eunomia_distributor::DistributorError;
+use eunomia_registry::RegistryError;
+
+/// A command error, as printed to the user.
+#[derive(Debug, Serialize)]
+pub struct ErrorReport {
+    /// Code of the outermost coded error in the chain, if any.
+    pub code: Option<&'static str>,
+    /// The error message.
+    pub message: String,
+    /// Messages of the errors that caused it, outermost first.
+    pub details: Vec<String>,
+}
+
+impl ErrorReport {
+    /// Creates the report of an error.
+    pub fn new(error: &Error) -> Self {
+        Self {
+            code: error_code(error),
+            message: error.to_string(),
+            details: error.chain().skip(1).map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(code) => write!(f, "error[{code}]: {}", self.message)?,
+            None => write!(f, "error: {}", self.message)?,
+        }
+        for detail in &self.details {
+            write!(f, "\n  caused by: {detail}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the code of the outermost distributor or registry error in
+/// `error`'s chain.
+pub fn error_code(error: &Error) -> Option<&'static str> {
+    error.chain().find_map(|cause| {
+        cause
+            .downcast_ref::<DistributorError>()
+            .map(DistributorError::code)
+            .or_else(|| {
+                cause
+                    .downcast_ref::<RegistryError>()
+                    .map(RegistryError::code)
+            })
+    })
+}
+
+/// Prints an error, as JSON to stdout if `json` is set and to stderr
+/// otherwise.
+pub fn report(error: &Error, json: bool) {
+    let report = ErrorReport::new(error);
+    if json {
+        match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{json}"),
+            Err(_) => eprintln!("{report}"),
+        }
+    } else {
+        eprintln!("{report}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_report_includes_code_of_cause() {
+        let result: anyhow::Result<()> = Err(RegistryError::NotFound {
+            service: "users-service".to_string(),
+            version: "v1.2.0".to_string(),
+        })
+        .context("Failed to fetch bundle");
+        let report = ErrorReport::new(&result.unwrap_err());
+
+        assert_eq!(report.code, Some("EUN-R003"));
+        assert_eq!(
+            report.to_string(),
+            "error[EUN-R003]: Failed to fetch bundle\n  \
+             caused by: Bundle not found: users-service:v1.2.0"
+        );
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "code": "EUN-R003",
+                "message": "Failed to fetch bundle",
+                "details": ["Bundle not found: users-service:v1.2.0"],
+            })
+        );
+
+        let error = anyhow::Error::new(DistributorError::NoInstancesFound {
+            service: "users-service".to_string(),
+        });
+        assert_eq!(
+            ErrorReport::new(&error).to_string(),
+            "error[EUN-D001]: no instances found for service 'users-service'"
+        );
+
+        let report = ErrorReport::new(&anyhow::anyhow!("Policy path does not exist"));
+        assert_eq!(report.code, None);
+        assert_eq!(report.to_string(), "error: Policy path does not exist");
+    }
+}
@@ -1,39 +1,112 @@
 //! Eunomia CLI - Command-line interface for the Eunomia authorization platform.
 
-use anyhow::Result;
-use clap::Parser;
+use std::process::ExitCode;
+
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod commands;
+mod config;
+mod error;
+mod output;
+mod store;
+mod watch;
 
 use commands::{Cli, Commands};
 
+fn main() -> ExitCode {
+    // Answers completion requests from `eunomia completions --dynamic`
+    // scripts; completers start their own runtime, so this runs outside one
+    CompleteEnv::with_factory(Cli::command)
+        .var(commands::completions::COMPLETE_VAR)
+        .complete();
+
+    run()
+}
+
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn run() -> ExitCode {
+    let mut cli = Cli::parse();
+    let output = cli.command.output_format(cli.result_format);
+    // Errors are reported as JSON with YAML output too, as JSON is valid YAML
+    let json = output.is_structured() || cli.command.json_output();
+
     // Initialize tracing
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "eunomia=info".into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        // Logs go to stderr so they never mix with JSON or SARIF output, and
+        // are turned off when the result is written as JSON or YAML
+        .with(
+            (!output.is_structured())
+                .then(|| tracing_subscriber::fmt::layer().with_writer(std::io::stderr)),
+        )
         .init();
 
-    let cli = Cli::parse();
+    match Cli::load_config(cli.config.clone()) {
+        Ok(config) => cli.command.apply_config(&config),
+        Err(e) => {
+            error::report(&e, json);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if output.is_structured() && !cli.command.has_structured_output() {
+        let e = anyhow::anyhow!("This command has no result to write with --result-format");
+        error::report(&e, json);
+        return ExitCode::FAILURE;
+    }
 
-    match cli.command {
-        Commands::Test(args) => commands::test::run(&args),
-        Commands::Build(args) => commands::build::run(args),
+    let result = match cli.command {
+        Commands::Init(args) => commands::init::run(&args),
+        Commands::Test(args) => commands::test::run(&args, output),
+        Commands::Build(args) => commands::build::run(&args),
+        Commands::Eval(args) => match commands::eval::run(&args, output) {
+            Ok(outcome) => return outcome.exit_code(),
+            Err(e) => {
+                error::report(&e, json);
+                return ExitCode::from(commands::eval::ERROR_EXIT_CODE);
+            }
+        },
+        Commands::Diff(args) => commands::diff::run(&args, output).await,
+        Commands::Replay(args) => match commands::replay::run(&args, output).await {
+            Ok(outcome) => return outcome.exit_code(),
+            Err(e) => {
+                error::report(&e, json);
+                return ExitCode::from(commands::replay::ERROR_EXIT_CODE);
+            }
+        },
         Commands::Sign(args) => commands::sign::run(&args),
-        Commands::Publish(args) => commands::publish::run(&args),
-        Commands::Fetch(args) => commands::fetch::run(&args),
-        Commands::Validate(args) => commands::validate::run(&args),
-        Commands::Push(args) => commands::push::execute(args).await,
-        Commands::Rollback(args) => commands::rollback::run(&args),
-        Commands::Status(args) => commands::status::run(&args),
+        Commands::Publish(args) => commands::publish::run(&args, output).await,
+        Commands::Fetch(args) => commands::fetch::run(&args).await,
+        Commands::Validate(args) => commands::validate::run(&args, output),
+        Commands::Fmt(args) => commands::fmt::run(&args),
+        Commands::Lint(args) => match commands::lint::run(&args, output) {
+            Ok(outcome) => return outcome.exit_code(),
+            Err(e) => {
+                error::report(&e, json);
+                return ExitCode::from(commands::lint::ERROR_EXIT_CODE);
+            }
+        },
+        Commands::Push(args) => commands::push::execute(args, output).await,
+        Commands::Rollback(args) => commands::rollback::run(&args, output),
+        Commands::Status(args) => commands::status::run(&args, output).await,
+        Commands::Doctor(args) => commands::doctor::run(&args, cli.config.as_deref(), output).await,
+        Commands::Completions(args) => commands::completions::run(&args),
         Commands::Version => {
             println!("eunomia {}", env!("CARGO_PKG_VERSION"));
             Ok(())
         }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            error::report(&e, json);
+            ExitCode::FAILURE
+        }
     }
 }
@@ -0,0 +1,77 @@
+//! Structured output of command results.
+//!
+//! With the global `--result-format json` or `--result-format yaml`,
+//! commands write their result (such as a `ValidationReport` or
+//! `DeploymentResult`) to stdout in that format instead of printing
+//! human-readable text, and logging is turned off so nothing else reaches
+//! the terminal. Commands without a result to write reject the flag.
+
+use anyhow::Result;
+use serde::Serialize;
+
+/// Format of command output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// Pretty-printed JSON.
+    Json,
+    /// YAML.
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Returns true for JSON and YAML.
+    pub const fn is_structured(self) -> bool {
+        !matches!(self, Self::Text)
+    }
+
+    /// Serializes `value` in this format.
+    ///
+    /// Text has no serialization of its own and is rendered as JSON;
+    /// commands print their text output themselves.
+    pub fn render<T: Serialize + ?Sized>(self, value: &T) -> Result<String> {
+        Ok(match self {
+            Self::Text | Self::Json => serde_json::to_string_pretty(value)?,
+            Self::Yaml => serde_yaml::to_string(value)?,
+        })
+    }
+
+    /// Writes `value` to stdout in this format.
+    pub fn print<T: Serialize + ?Sized>(self, value: &T) -> Result<()> {
+        let output = self.render(value)?;
+        println!("{}", output.trim_end());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Summary {
+        service: &'static str,
+        passed: bool,
+    }
+
+    #[test]
+    fn test_serializes_in_format() {
+        let result = Summary {
+            service: "users-service",
+            passed: true,
+        };
+
+        assert_eq!(
+            OutputFormat::Json.render(&result).unwrap(),
+            "{\n  \"service\": \"users-service\",\n  \"passed\": true\n}"
+        );
+        assert_eq!(
+            OutputFormat::Yaml.render(&result).unwrap(),
+            "service: users-service\npassed: true\n"
+        );
+        assert!(!OutputFormat::Text.is_structured());
+        assert!(OutputFormat::Yaml.is_structured());
+    }
+}
@@ -0,0 +1,124 @@
+//! Object storage for `publish` and `fetch`, used instead of a registry
+//! when `--store` is given.
+
+use anyhow::Result;
+use clap::Args;
+
+use eunomia_registry::{BundleStore, S3Config, S3Credentials};
+
+/// Object storage arguments shared by `publish` and `fetch`.
+#[derive(Args, Debug, Clone, Default)]
+pub struct StoreArgs {
+    /// Object storage to use instead of a registry: `s3://bucket/prefix`,
+    /// or `gs://bucket/prefix` for Google Cloud Storage
+    #[arg(long, env = "EUNOMIA_STORE", value_name = "URL")]
+    pub store: Option<String>,
+
+    /// Endpoint of an S3-compatible service other than AWS
+    #[arg(long, env = "EUNOMIA_S3_ENDPOINT", value_name = "URL")]
+    pub s3_endpoint: Option<String>,
+
+    /// S3 region (defaults to the AWS configuration)
+    #[arg(long, env = "EUNOMIA_S3_REGION")]
+    pub s3_region: Option<String>,
+
+    /// Address the bucket in the URL path instead of the host name
+    #[arg(long)]
+    pub s3_path_style: bool,
+
+    /// Access key ID (defaults to the AWS credential chain)
+    #[arg(
+        long,
+        env = "EUNOMIA_S3_ACCESS_KEY_ID",
+        requires = "s3_secret_access_key"
+    )]
+    pub s3_access_key_id: Option<String>,
+
+    /// Secret access key
+    #[arg(
+        long,
+        env = "EUNOMIA_S3_SECRET_ACCESS_KEY",
+        hide_env_values = true,
+        requires = "s3_access_key_id"
+    )]
+    pub s3_secret_access_key: Option<String>,
+}
+
+impl StoreArgs {
+    /// Returns the storage configuration, or `None` without `--store`.
+    pub fn config(&self) -> Result<Option<S3Config>> {
+        let Some(ref url) = self.store else {
+            return Ok(None);
+        };
+
+        let mut config = S3Config::from_url(url)?;
+        if let Some(ref endpoint) = self.s3_endpoint {
+            config = config.with_endpoint(endpoint);
+        }
+        if let Some(ref region) = self.s3_region {
+            config = config.with_region(region);
+        }
+        if self.s3_path_style {
+            config = config.with_path_style(true);
+        }
+        if let (Some(id), Some(secret)) = (&self.s3_access_key_id, &self.s3_secret_access_key) {
+            config = config.with_credentials(S3Credentials::access_key(id, secret));
+        }
+        Ok(Some(config))
+    }
+
+    /// Opens the store given with `--store`, or returns `None` to use the
+    /// registry.
+    pub async fn open(&self) -> Result<Option<Box<dyn BundleStore>>> {
+        let Some(config) = self.config()? else {
+            return Ok(None);
+        };
+        open_s3(config).await.map(Some)
+    }
+}
+
+#[cfg(feature = "s3")]
+async fn open_s3(config: S3Config) -> Result<Box<dyn BundleStore>> {
+    Ok(Box::new(eunomia_registry::S3Store::new(config).await))
+}
+
+#[cfg(not(feature = "s3"))]
+#[allow(clippy::unused_async)]
+async fn open_s3(_config: S3Config) -> Result<Box<dyn BundleStore>> {
+    anyhow::bail!("--store needs S3 support; rebuild eunomia with the `s3` feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_applies_overrides() {
+        let args = StoreArgs::default();
+        assert!(args.config().unwrap().is_none());
+
+        let args = StoreArgs {
+            store: Some("s3://policies/prod".to_string()),
+            s3_endpoint: Some("http://minio:9000".to_string()),
+            s3_path_style: true,
+            s3_access_key_id: Some("minio".to_string()),
+            s3_secret_access_key: Some("minio-secret".to_string()),
+            ..StoreArgs::default()
+        };
+        let config = args.config().unwrap().unwrap();
+        assert_eq!(config.bucket, "policies");
+        assert_eq!(config.prefix, "prod");
+        assert_eq!(config.endpoint.as_deref(), Some("http://minio:9000"));
+        assert!(config.path_style);
+        assert_eq!(
+            config.credentials,
+            S3Credentials::access_key("minio", "minio-secret")
+        );
+
+        let args = StoreArgs {
+            store: Some("ftp://policies".to_string()),
+            ..StoreArgs::default()
+        };
+        assert!(args.config().is_err());
+    }
+}
@@ -0,0 +1,233 @@
+//! File watching for the `--watch` mode of `test` and `validate`.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use tracing::warn;
+
+/// Quiet period after the last change before a new cycle starts.
+pub const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// How often the watcher checks for Ctrl-C while waiting for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Extensions of files whose changes start a new cycle.
+pub const WATCHED_EXTENSIONS: &[&str] = &["rego", "json", "yaml", "yml", "toml"];
+
+/// Watches `path` recursively and calls `on_change` with the changed files
+/// after each burst of changes, until Ctrl-C is pressed.
+///
+/// Created, modified, removed and renamed files are all reported; removed
+/// files no longer exist when `on_change` runs. Errors returned by
+/// `on_change` are printed and watching continues, so a policy saved
+/// mid-edit does not end the session.
+///
+/// # Errors
+///
+/// Returns an error if the watcher cannot be started.
+pub fn watch(path: &Path, mut on_change: impl FnMut(&[PathBuf]) -> Result<()>) -> Result<()> {
+    let watcher = FileWatcher::new(path, DEBOUNCE, WATCHED_EXTENSIONS)?;
+    watcher.run(&stop_on_ctrl_c(), |changed| {
+        for file in changed {
+            println!("Changed: {}", file.display());
+        }
+        on_change(changed)
+    });
+    Ok(())
+}
+
+/// A recursive watch on a directory, reporting changes in batches.
+///
+/// Changes are recorded from the moment the watcher is created, so none
+/// are missed between creating it and calling [`run`](Self::run).
+pub struct FileWatcher {
+    path: PathBuf,
+    debounce: Duration,
+    extensions: &'static [&'static str],
+    rx: Receiver<notify::Result<Event>>,
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl FileWatcher {
+    /// Starts watching `path` for changes to files with one of
+    /// `extensions`, batching changes until none has arrived for
+    /// `debounce`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the watcher cannot be started.
+    pub fn new(
+        path: &Path,
+        debounce: Duration,
+        extensions: &'static [&'static str],
+    ) -> Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            debounce,
+            extensions,
+            rx,
+            _watcher: watcher,
+        })
+    }
+
+    /// Calls `on_change` with the changed files after each burst of
+    /// changes, until `stop` is set.
+    ///
+    /// Errors returned by `on_change` are printed and watching continues.
+    pub fn run(self, stop: &AtomicBool, mut on_change: impl FnMut(&[PathBuf]) -> Result<()>) {
+        let path = self.path.display();
+        println!("\nWatching {path} for changes (Ctrl-C to stop)");
+        while let Some(changed) = next_batch(&self.rx, self.debounce, self.extensions, stop) {
+            println!();
+            if let Err(e) = on_change(&changed) {
+                println!("✗ {e:#}");
+            }
+            println!("\nWatching {path} for changes (Ctrl-C to stop)");
+        }
+        println!("\nStopped watching");
+    }
+}
+
+/// Returns a flag that is set when Ctrl-C is pressed.
+///
+/// Without a Tokio runtime, Ctrl-C keeps its default behavior of ending the
+/// process.
+pub fn stop_on_ctrl_c() -> Arc<AtomicBool> {
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+        let stop = Arc::clone(&stop);
+        runtime.spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                stop.store(true, Ordering::Relaxed);
+            }
+        });
+    }
+    stop
+}
+
+/// Waits for changes to files with one of `extensions` and returns them
+/// once no further change has arrived for `debounce`.
+///
+/// Returns `None` when `stop` is set or the watcher has shut down.
+fn next_batch(
+    rx: &Receiver<notify::Result<Event>>,
+    debounce: Duration,
+    extensions: &[&str],
+    stop: &AtomicBool,
+) -> Option<Vec<PathBuf>> {
+    let mut changed = BTreeSet::new();
+    let mut last_change: Option<Instant> = None;
+
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let timeout = last_change.map_or(POLL_INTERVAL, |at| {
+            debounce.saturating_sub(at.elapsed()).min(POLL_INTERVAL)
+        });
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(event)) => {
+                if matches!(event.kind, EventKind::Access(_)) {
+                    continue;
+                }
+                let before = changed.len();
+                changed.extend(
+                    event
+                        .paths
+                        .into_iter()
+                        .filter(|p| is_watched(p, extensions)),
+                );
+                if changed.len() > before || last_change.is_some() {
+                    last_change = Some(Instant::now());
+                }
+            }
+            Ok(Err(e)) => warn!(error = %e, "File watch error"),
+            Err(RecvTimeoutError::Timeout) => {
+                if last_change.is_some_and(|at| at.elapsed() >= debounce) {
+                    return Some(changed.into_iter().collect());
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+/// Returns true if changes to `path` should start a new cycle.
+fn is_watched(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.contains(&ext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use notify::event::{CreateKind, ModifyKind, RemoveKind};
+
+    fn event(kind: EventKind, path: &str) -> Event {
+        Event::new(kind).add_path(PathBuf::from(path))
+    }
+
+    #[test]
+    fn test_is_watched() {
+        let watched = |path| is_watched(Path::new(path), WATCHED_EXTENSIONS);
+        assert!(watched("policies/authz.rego"));
+        assert!(watched("policies/.eunomia-lint.toml"));
+        assert!(watched("fixtures/admin.yaml"));
+        assert!(!watched("policies/.authz.rego.swp"));
+        assert!(!watched("policies/authz.rego~"));
+        assert!(!is_watched(Path::new("fixtures/admin.yaml"), &["rego"]));
+    }
+
+    #[test]
+    fn test_next_batch_debounces_changes() {
+        let (tx, rx) = mpsc::channel();
+        for (kind, path) in [
+            (EventKind::Create(CreateKind::File), "p/new.rego"),
+            (EventKind::Modify(ModifyKind::Any), "p/authz.rego"),
+            (EventKind::Modify(ModifyKind::Any), "p/authz.rego"),
+            (EventKind::Remove(RemoveKind::File), "p/old.rego"),
+            (EventKind::Modify(ModifyKind::Any), "p/notes.txt"),
+        ] {
+            tx.send(Ok(event(kind, path))).unwrap();
+        }
+
+        let stop = AtomicBool::new(false);
+        let batch = next_batch(&rx, Duration::from_millis(10), WATCHED_EXTENSIONS, &stop).unwrap();
+        assert_eq!(
+            batch,
+            vec![
+                PathBuf::from("p/authz.rego"),
+                PathBuf::from("p/new.rego"),
+                PathBuf::from("p/old.rego"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_batch_stops() {
+        let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+
+        let stop = AtomicBool::new(true);
+        assert!(next_batch(&rx, DEBOUNCE, WATCHED_EXTENSIONS, &stop).is_none());
+
+        drop(tx);
+        let stop = AtomicBool::new(false);
+        assert!(next_batch(&rx, DEBOUNCE, WATCHED_EXTENSIONS, &stop).is_none());
+    }
+}
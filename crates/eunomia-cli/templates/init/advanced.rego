@@ -0,0 +1,38 @@
+# METADATA
+# title: {{service}} Authorization Policy
+# description: Authorization rules for the {{service}}
+# scope: service
+package {{package}}
+
+import future.keywords.if
+import future.keywords.in
+
+# Default deny - all requests are denied unless explicitly allowed
+default allow := false
+
+# Services allowed to call this service, by SPIFFE service name
+trusted_services := {"gateway"}
+
+# Admins can call every operation
+allow if {
+    input.caller.type == "user"
+    "admin" in input.caller.roles
+}
+
+# Any authenticated user can check the health of the service
+allow if {
+    input.caller.type == "user"
+    input.operation_id == "getHealth"
+}
+
+# Trusted services can call every operation
+allow if {
+    input.caller.type == "spiffe"
+    input.caller.service_name in trusted_services
+}
+
+# API keys need the write scope of the service
+allow if {
+    input.caller.type == "api_key"
+    "write:{{service}}" in input.caller.scopes
+}
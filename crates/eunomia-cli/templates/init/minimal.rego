@@ -0,0 +1,8 @@
+# METADATA
+# title: {{service}} Authorization Policy
+# description: Authorization rules for the {{service}}
+# scope: service
+package {{package}}
+
+# Default deny - all requests are denied unless explicitly allowed
+default allow := false
@@ -0,0 +1,23 @@
+# METADATA
+# title: {{service}} Authorization Policy
+# description: Authorization rules for the {{service}}
+# scope: service
+package {{package}}
+
+import future.keywords.if
+import future.keywords.in
+
+# Default deny - all requests are denied unless explicitly allowed
+default allow := false
+
+# Admins can call every operation
+allow if {
+    input.caller.type == "user"
+    "admin" in input.caller.roles
+}
+
+# Any authenticated user can check the health of the service
+allow if {
+    input.caller.type == "user"
+    input.operation_id == "getHealth"
+}
@@ -0,0 +1,83 @@
+//! Generates shell completion scripts with `eunomia completions`.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::process::{Command, Output};
+
+const SHELLS: [&str; 4] = ["bash", "zsh", "fish", "powershell"];
+
+fn eunomia(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_eunomia"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn script(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_completions_for_each_shell() {
+    for shell in SHELLS {
+        let script = script(&eunomia(&["completions", shell]));
+        assert!(!script.is_empty(), "{shell}");
+        assert!(script.contains("eunomia"), "{shell}");
+        // Subcommands are completed
+        assert!(script.contains("publish"), "{shell}");
+    }
+}
+
+#[test]
+fn test_dynamic_completions_for_each_shell() {
+    for shell in SHELLS {
+        let script = script(&eunomia(&["completions", shell, "--dynamic"]));
+        assert!(script.contains("eunomia"), "{shell}");
+        assert!(script.contains("COMPLETE"), "{shell}");
+    }
+}
+
+/// Serves a registry catalog on a local port and returns the registry URL.
+fn serve_catalog(body: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+        }
+    });
+    url
+}
+
+#[test]
+fn test_dynamic_service_completion_lists_registry_services() {
+    let registry = serve_catalog(r#"{"repositories":["orders","users-service","users-admin"]}"#);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_eunomia"))
+        .env("COMPLETE", "fish")
+        .env("EUNOMIA_REGISTRY_URL", &registry)
+        .env_remove("EUNOMIA_REGISTRY_NAMESPACE")
+        .args(["--", "eunomia", "fetch", "--service", "users"])
+        .output()
+        .unwrap();
+
+    let candidates = script(&output);
+    let candidates: Vec<_> = candidates.lines().collect();
+    assert_eq!(candidates, ["users-service", "users-admin"]);
+}
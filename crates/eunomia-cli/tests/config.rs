@@ -0,0 +1,76 @@
+//! Runs commands inside a project with a `.eunomia.toml`.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use eunomia_core::Bundle;
+
+const CONFIG: &str = "[registry]\n\
+                      url = \"https://registry.internal.example\"\n\
+                      namespace = \"platform\"\n";
+
+/// Runs `eunomia publish --dry-run` on a bundle in `dir`, with the
+/// registry environment variables cleared.
+fn publish_dry_run(dir: &Path, args: &[&str]) -> Output {
+    let bundle = dir.join("users-service.bundle.tar.gz");
+    Bundle::builder("users-service")
+        .version("1.0.0")
+        .add_policy("users_service.authz", "package users_service.authz\n")
+        .build()
+        .write_to_file(&bundle)
+        .unwrap();
+
+    Command::new(env!("CARGO_BIN_EXE_eunomia"))
+        .current_dir(dir)
+        .env_remove("EUNOMIA_REGISTRY_URL")
+        .env_remove("EUNOMIA_REGISTRY_NAMESPACE")
+        .args(["publish", "--dry-run", "--format", "json"])
+        .arg(&bundle)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn dry_run_report(output: &Output) -> serde_json::Value {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_registry_from_parent_config() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join(".eunomia.toml"), CONFIG).unwrap();
+    let nested = dir.path().join("policies/users-service");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let report = dry_run_report(&publish_dry_run(&nested, &[]));
+    assert_eq!(report["registry"], "https://registry.internal.example");
+    assert_eq!(report["repository"], "platform/users-service");
+
+    // Flags take precedence over the configuration
+    let output = publish_dry_run(&nested, &["--registry", "https://other.example"]);
+    assert_eq!(dry_run_report(&output)["registry"], "https://other.example");
+}
+
+#[test]
+fn test_explicit_config_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("ci.toml");
+    std::fs::write(&config, CONFIG).unwrap();
+
+    let output = publish_dry_run(dir.path(), &["--config", &config.to_string_lossy()]);
+    assert_eq!(
+        dry_run_report(&output)["registry"],
+        "https://registry.internal.example"
+    );
+
+    // Without a registry anywhere, publishing fails; JSON output reports the
+    // error on stdout
+    let output = publish_dry_run(dir.path(), &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("registry.url in .eunomia.toml"));
+}
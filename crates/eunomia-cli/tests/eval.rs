@@ -0,0 +1,105 @@
+//! Runs `eunomia eval` against policy files in a temporary directory.
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+const POLICY: &str = "package authz\n\nimport future.keywords.if\n\n\
+                      default allow := false\n\nallow if input.caller.type == \"user\"\n";
+
+/// Evaluates `data.authz.allow` with `input` piped through stdin.
+fn eval(args: &[&str], policy: &Path, input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_eunomia"))
+        .arg("eval")
+        .arg("data.authz.allow")
+        .arg(policy)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn write_policy(dir: &Path) -> std::path::PathBuf {
+    let policy = dir.join("authz.rego");
+    std::fs::write(&policy, POLICY).unwrap();
+    policy
+}
+
+#[test]
+fn test_eval_truthy_policy() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(dir.path());
+
+    let output = eval(
+        &["--output-format", "bool"],
+        &policy,
+        r#"{"caller":{"type":"user"}}"#,
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "true\n");
+
+    let output = eval(&[], &policy, r#"{"caller":{"type":"user"}}"#);
+    assert_eq!(output.status.code(), Some(0));
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(result, serde_json::Value::Bool(true));
+}
+
+#[test]
+fn test_eval_falsy_policy() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(dir.path());
+
+    let output = eval(
+        &["--output-format", "bool"],
+        &policy,
+        r#"{"caller":{"type":"spiffe"}}"#,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "false\n");
+
+    // Other formats only print the result
+    let output = eval(
+        &["--output-format", "raw"],
+        &policy,
+        r#"{"caller":{"type":"spiffe"}}"#,
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "false\n");
+}
+
+#[test]
+fn test_eval_input_sources_and_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(dir.path());
+    let input_file = dir.path().join("input.json");
+    std::fs::write(&input_file, r#"{"caller":{"type":"spiffe"}}"#).unwrap();
+
+    // Inline and file inputs take precedence over stdin
+    let user = r#"{"caller":{"type":"user"}}"#;
+    let args = ["--output-format", "bool", "--input-file"];
+    let output = eval(
+        &[&args[..], &[input_file.to_str().unwrap()]].concat(),
+        &policy,
+        user,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let output = eval(&["--output-format", "bool", "--input", user], &policy, "");
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = eval(&["--output-format", "bool"], &policy, "{not json");
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Invalid JSON in input stdin"), "{stderr}");
+
+    let missing = dir.path().join("missing.rego");
+    assert_eq!(eval(&[], &missing, "").status.code(), Some(2));
+}
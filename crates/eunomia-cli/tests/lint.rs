@@ -0,0 +1,52 @@
+//! Runs `eunomia lint` against policies in a temporary directory.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn lint(args: &[&str], path: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_eunomia"))
+        .arg("lint")
+        .args(args)
+        .arg(path)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_lint_reports_violations() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("good.rego"),
+        "package authz.good\n\ndefault allow := false\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("bad.rego"),
+        "package authz.bad\n\ndefault allow := true\n",
+    )
+    .unwrap();
+
+    let output = lint(&[], dir.path());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(1), "{stdout}");
+    assert!(
+        stdout.contains("[security/default-deny] Default allow is set to true - this is insecure"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Linted 2 policies"), "{stdout}");
+
+    let output = lint(&["--output-format", "sarif"], dir.path());
+    assert_eq!(output.status.code(), Some(1));
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(
+        sarif["runs"][0]["results"][0]["ruleId"],
+        "security/default-deny"
+    );
+
+    std::fs::remove_file(dir.path().join("bad.rego")).unwrap();
+    assert_eq!(lint(&[], dir.path()).status.code(), Some(0));
+
+    let missing = dir.path().join("missing");
+    assert_eq!(lint(&[], &missing).status.code(), Some(2));
+}
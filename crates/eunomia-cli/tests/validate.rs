@@ -0,0 +1,59 @@
+//! Runs `eunomia validate` with structured output against policies in a
+//! temporary directory.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use eunomia_compiler::ValidationReport;
+
+const POLICY: &str = "package authz.test\n\nimport future.keywords.if\n\n\
+                      default allow := false\n\nallow if input.caller.type == \"user\"\n";
+
+fn validate(args: &[&str], dir: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_eunomia"))
+        .current_dir(dir)
+        .arg("validate")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn write_policies(dir: &Path) {
+    std::fs::create_dir(dir.join("policies")).unwrap();
+    std::fs::write(dir.join("policies/test.rego"), POLICY).unwrap();
+}
+
+#[test]
+fn test_validate_json_output() {
+    let dir = tempfile::tempdir().unwrap();
+    write_policies(dir.path());
+
+    let output = validate(
+        &["--result-format", "json", "policies/test.rego"],
+        dir.path(),
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert!(output.stderr.is_empty());
+
+    let report: ValidationReport = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report.file.as_deref(), Some("policies/test.rego"));
+    assert_eq!(report.package.as_deref(), Some("authz.test"));
+    assert!(report.is_valid());
+}
+
+#[test]
+fn test_validate_yaml_output_of_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    write_policies(dir.path());
+
+    let output = validate(&["--result-format", "yaml", "policies"], dir.path());
+    assert!(output.status.success());
+
+    let reports: Vec<ValidationReport> = serde_yaml::from_slice(&output.stdout).unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].package.as_deref(), Some("authz.test"));
+}
@@ -2,11 +2,27 @@
 //!
 //! This module provides validation and analysis of Rego policies.
 
-use eunomia_core::Policy;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use eunomia_core::{Bundle, BundleDiff, Policy};
+use serde::Serialize;
+use thiserror::Error;
 use tracing::warn;
 
+use crate::engine::{extract_package_from_source, extract_rule_name, extract_rules_from_source};
 use crate::error::{CompilerError, Result};
 
+/// Errors found by cross-policy analysis.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AnalysisError {
+    /// Packages import each other in a cycle.
+    #[error("Circular import: {}", format_cycle(cycle))]
+    CircularDependency {
+        /// Packages in the cycle, in import order.
+        cycle: Vec<String>,
+    },
+}
+
 /// Static analyzer for Rego policies.
 ///
 /// The analyzer validates policies and checks for common issues.
@@ -157,6 +173,91 @@ impl Analyzer {
         Ok(result)
     }
 
+    /// Checks that no policies import each other in a cycle.
+    ///
+    /// Builds a directed graph of `data.*` imports between the packages in
+    /// `policies` (keyed by package name) and topologically sorts it with
+    /// Kahn's algorithm. An import resolves to the longest package name that
+    /// prefixes its path, so `import data.common.roles.is_admin` depends on
+    /// package `common.roles`. Imports of packages outside `policies`, and
+    /// imports resolving to the importing package itself, are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`AnalysisError::CircularDependency`] with the packages of one
+    /// cycle, starting from the alphabetically first, if any cycle exists.
+    pub fn check_circular_imports(
+        policies: &HashMap<String, Policy>,
+    ) -> std::result::Result<(), AnalysisError> {
+        // Package -> packages it imports, ordered for deterministic output
+        let mut graph: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for (package, policy) in policies {
+            let targets = graph.entry(package.as_str()).or_default();
+            for import in imported_paths(&policy.source) {
+                let target = policies
+                    .keys()
+                    .filter(|p| import == **p || import.starts_with(&format!("{p}.")))
+                    .max_by_key(|p| p.len());
+                // A package's own paths are its rules or sub-packages
+                // missing from `policies`, never an import of itself
+                if let Some(target) = target.filter(|target| *target != package) {
+                    targets.insert(target.as_str());
+                }
+            }
+        }
+
+        // Kahn's algorithm: repeatedly remove packages nothing imports
+        let mut in_degree: BTreeMap<&str, usize> = graph.keys().map(|p| (*p, 0)).collect();
+        for targets in graph.values() {
+            for target in targets {
+                *in_degree.entry(*target).or_default() += 1;
+            }
+        }
+
+        let mut ready: Vec<&str> = in_degree
+            .iter()
+            .filter(|(_, degree)| **degree == 0)
+            .map(|(package, _)| *package)
+            .collect();
+        while let Some(package) = ready.pop() {
+            in_degree.remove(package);
+            for target in &graph[package] {
+                if let Some(degree) = in_degree.get_mut(target) {
+                    *degree -= 1;
+                    if *degree == 0 {
+                        ready.push(*target);
+                    }
+                }
+            }
+        }
+
+        // Every remaining package lies on or after a cycle, and all of its
+        // imports remain too, so following them must revisit a package.
+        let Some(start) = in_degree.keys().next().copied() else {
+            return Ok(());
+        };
+        let mut path = vec![start];
+        loop {
+            let current = path[path.len() - 1];
+            let next = graph[current]
+                .iter()
+                .find(|target| in_degree.contains_key(*target))
+                .copied()
+                .unwrap_or(start);
+            if let Some(pos) = path.iter().position(|p| *p == next) {
+                let mut cycle: Vec<String> = path[pos..].iter().map(ToString::to_string).collect();
+                let first = cycle
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, p)| p.as_str())
+                    .map_or(0, |(i, _)| i);
+                cycle.rotate_left(first);
+                return Err(AnalysisError::CircularDependency { cycle });
+            }
+            path.push(next);
+        }
+    }
+
     /// Validates that a policy meets basic requirements.
     ///
     /// # Errors
@@ -169,8 +270,255 @@ impl Analyzer {
     }
 }
 
+/// Semantic difference between two versions of a policy source.
+///
+/// Rules are compared by name: a renamed rule shows up as one removed and
+/// one added rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SourceDiff {
+    /// Rules present only in the new source.
+    pub added_rules: Vec<String>,
+    /// Rules present only in the old source.
+    pub removed_rules: Vec<String>,
+    /// Rules present in both sources whose definitions changed.
+    pub modified_rules: Vec<String>,
+    /// Imports added (`+data.x`) or removed (`-data.x`).
+    pub changed_imports: Vec<String>,
+    /// Change in the number of rules (new minus old).
+    pub policy_complexity_delta: i32,
+}
+
+impl SourceDiff {
+    /// Returns true if no rules or imports changed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added_rules.is_empty()
+            && self.removed_rules.is_empty()
+            && self.modified_rules.is_empty()
+            && self.changed_imports.is_empty()
+    }
+}
+
+/// Compares two versions of a policy by rule and import rather than by line.
+///
+/// Comment and whitespace changes inside a rule, including changes to the
+/// spacing within a line, do not mark it modified.
+///
+/// # Errors
+///
+/// Returns [`CompilerError::MissingPackage`] if either source has no
+/// package declaration.
+pub fn source_diff(old: &str, new: &str, file_name: &str) -> Result<SourceDiff> {
+    extract_package_from_source(old, file_name)?;
+    extract_package_from_source(new, file_name)?;
+
+    let old_rules = extract_rules_from_source(old);
+    let new_rules = extract_rules_from_source(new);
+    let old_bodies = rule_definitions(old);
+    let new_bodies = rule_definitions(new);
+
+    let mut diff = SourceDiff {
+        added_rules: new_rules
+            .iter()
+            .filter(|rule| !old_rules.contains(rule))
+            .cloned()
+            .collect(),
+        removed_rules: old_rules
+            .iter()
+            .filter(|rule| !new_rules.contains(rule))
+            .cloned()
+            .collect(),
+        modified_rules: new_rules
+            .iter()
+            .filter(|rule| {
+                old_rules.contains(rule) && old_bodies.get(*rule) != new_bodies.get(*rule)
+            })
+            .cloned()
+            .collect(),
+        changed_imports: Vec::new(),
+        policy_complexity_delta: rule_count(&new_rules) - rule_count(&old_rules),
+    };
+
+    let old_imports: BTreeSet<&str> = imports(old).collect();
+    let new_imports: BTreeSet<&str> = imports(new).collect();
+    diff.changed_imports.extend(
+        new_imports
+            .difference(&old_imports)
+            .map(|import| format!("+{import}")),
+    );
+    diff.changed_imports.extend(
+        old_imports
+            .difference(&new_imports)
+            .map(|import| format!("-{import}")),
+    );
+
+    Ok(diff)
+}
+
+/// Rule-level differences between two versions of a bundle.
+///
+/// Produced by [`bundle_diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BundleRuleDiff {
+    /// Policy, data file and manifest changes.
+    pub bundle: BundleDiff,
+    /// Rule and import changes by package, for added, removed and modified
+    /// policies. Policies whose only changes are comments or whitespace have
+    /// no entry.
+    pub rules: BTreeMap<String, SourceDiff>,
+}
+
+impl BundleRuleDiff {
+    /// Returns true if nothing but comments and whitespace changed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+            && self.bundle.added.is_empty()
+            && self.bundle.removed.is_empty()
+            && self.bundle.added_data_files.is_empty()
+            && self.bundle.removed_data_files.is_empty()
+            && self.bundle.modified_data_files.is_empty()
+            && self.bundle.manifest_changes.is_empty()
+    }
+
+    /// Returns the modified policies whose only changes are comments or
+    /// whitespace.
+    #[must_use]
+    pub fn cosmetic_changes(&self) -> Vec<&str> {
+        self.bundle
+            .modified
+            .iter()
+            .map(|policy| policy.package.as_str())
+            .filter(|package| !self.rules.contains_key(*package))
+            .collect()
+    }
+}
+
+/// Compares two bundles by policy, rule, data file and manifest field.
+///
+/// Every rule of an added or removed policy is reported as added or removed.
+///
+/// # Errors
+///
+/// Returns [`CompilerError::MissingPackage`] if a modified policy has no
+/// package declaration.
+pub fn bundle_diff(old: &Bundle, new: &Bundle) -> Result<BundleRuleDiff> {
+    let bundle = Bundle::diff(old, new);
+    let mut rules = BTreeMap::new();
+
+    for package in &bundle.added {
+        if let Some(source) = new.policies.get(package) {
+            let added = extract_rules_from_source(source);
+            rules.insert(
+                package.clone(),
+                SourceDiff {
+                    policy_complexity_delta: rule_count(&added),
+                    added_rules: added,
+                    changed_imports: imports(source).map(|i| format!("+{i}")).collect(),
+                    ..SourceDiff::default()
+                },
+            );
+        }
+    }
+    for package in &bundle.removed {
+        if let Some(source) = old.policies.get(package) {
+            let removed = extract_rules_from_source(source);
+            rules.insert(
+                package.clone(),
+                SourceDiff {
+                    policy_complexity_delta: -rule_count(&removed),
+                    removed_rules: removed,
+                    changed_imports: imports(source).map(|i| format!("-{i}")).collect(),
+                    ..SourceDiff::default()
+                },
+            );
+        }
+    }
+    for policy in &bundle.modified {
+        let diff = source_diff(&policy.old_source, &policy.new_source, &policy.package)?;
+        rules.insert(policy.package.clone(), diff);
+    }
+    rules.retain(|_, diff| !diff.is_empty());
+
+    Ok(BundleRuleDiff { bundle, rules })
+}
+
+fn rule_count(rules: &[String]) -> i32 {
+    i32::try_from(rules.len()).unwrap_or(i32::MAX)
+}
+
+/// Returns the normalized definition text of each rule, keyed by name.
+///
+/// A rule starts at an unindented line with a rule head and runs until the
+/// next one; definitions of the same rule are concatenated.
+fn rule_definitions(source: &str) -> BTreeMap<String, String> {
+    let mut definitions: BTreeMap<String, String> = BTreeMap::new();
+    let mut current: Option<String> = None;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if !line.starts_with(char::is_whitespace) {
+            if trimmed.starts_with("package ") || trimmed.starts_with("import ") {
+                current = None;
+                continue;
+            }
+            if let Some(name) = extract_rule_name(trimmed) {
+                current = Some(name);
+            }
+        }
+
+        if let Some(ref name) = current {
+            let definition = definitions.entry(name.clone()).or_default();
+            let code = trimmed.split(" #").next().unwrap_or(trimmed);
+            definition.push_str(&code.split_whitespace().collect::<Vec<_>>().join(" "));
+            definition.push('\n');
+        }
+    }
+
+    definitions
+}
+
+/// Returns the import paths of a policy, including any `as` alias.
+fn imports(source: &str) -> impl Iterator<Item = &str> {
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("import "))
+        .map(|import| {
+            let import = import.split('#').next().unwrap_or(import);
+            import.trim().trim_end_matches(';').trim()
+        })
+}
+
+/// Formats a cycle as `a -> b -> a`.
+fn format_cycle(cycle: &[String]) -> String {
+    let mut packages: Vec<&str> = cycle.iter().map(String::as_str).collect();
+    packages.extend(cycle.first().map(String::as_str));
+    packages.join(" -> ")
+}
+
+/// Returns the `data.*` import paths of a policy without the `data.` prefix
+/// or any `as` alias.
+fn imported_paths(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("import "))
+        .map(|import| {
+            let import = import.split('#').next().unwrap_or(import);
+            let import = import.split(" as ").next().unwrap_or(import);
+            import.trim().trim_end_matches(';').trim()
+        })
+        .filter_map(|import| import.strip_prefix("data."))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use std::fmt::Write as _;
+
     use super::*;
 
     #[test]
@@ -275,4 +623,238 @@ default allow := false
         assert!(result.imports.contains(&"future.keywords.if".to_string()));
         assert!(result.imports.contains(&"data.common.roles".to_string()));
     }
+
+    fn import_graph(edges: &[(&str, &[&str])]) -> HashMap<String, Policy> {
+        edges
+            .iter()
+            .map(|(package, imports)| {
+                let mut source = format!("package {package}\n\n");
+                for import in *imports {
+                    let _ = writeln!(source, "import data.{import}");
+                }
+                source.push_str("\ndefault allow := false\n");
+                ((*package).to_string(), Policy::new(*package, source))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_circular_imports_two_nodes() {
+        let policies = import_graph(&[("a", &["b"]), ("b", &["a.allow"])]);
+
+        let err = Analyzer::check_circular_imports(&policies).unwrap_err();
+        assert_eq!(
+            err,
+            AnalysisError::CircularDependency {
+                cycle: vec!["a".to_string(), "b".to_string()]
+            }
+        );
+        assert_eq!(err.to_string(), "Circular import: a -> b -> a");
+    }
+
+    #[test]
+    fn test_circular_imports_three_nodes() {
+        let policies = import_graph(&[
+            ("svc.a", &["svc.b"]),
+            ("svc.b", &["svc.c.rule"]),
+            ("svc.c", &["svc.a"]),
+            // Depends on the cycle but is not part of it
+            ("svc.entry", &["svc.a"]),
+        ]);
+
+        let err = Analyzer::check_circular_imports(&policies).unwrap_err();
+        let AnalysisError::CircularDependency { cycle } = err;
+        assert_eq!(cycle, vec!["svc.a", "svc.b", "svc.c"]);
+    }
+
+    #[test]
+    fn test_circular_imports_acyclic() {
+        let policies = import_graph(&[("a", &["b", "c"]), ("b", &["c"]), ("c", &["external.lib"])]);
+
+        assert!(Analyzer::check_circular_imports(&policies).is_ok());
+    }
+
+    #[test]
+    fn test_circular_imports_sub_package_import() {
+        // `a.helpers` is not in the bundle, so the import resolves to `a`
+        let policies = import_graph(&[("a", &["a.helpers"])]);
+        assert!(Analyzer::check_circular_imports(&policies).is_ok());
+
+        let policies = import_graph(&[("a", &["a.helpers"]), ("a.helpers", &[])]);
+        assert!(Analyzer::check_circular_imports(&policies).is_ok());
+
+        let policies = import_graph(&[("a", &["a.helpers"]), ("a.helpers", &["a.allow"])]);
+        let err = Analyzer::check_circular_imports(&policies).unwrap_err();
+        assert_eq!(err.to_string(), "Circular import: a -> a.helpers -> a");
+    }
+
+    const DIFF_BASE: &str = r#"package authz
+
+import data.common.roles
+
+default allow := false
+
+allow if {
+    roles.is_admin
+}
+
+is_owner if {
+    input.resource.owner == input.caller.id
+}
+"#;
+
+    #[test]
+    fn test_source_diff_added_helper_rule() {
+        let new = format!("{DIFF_BASE}\nis_reader if {{\n    input.method == \"GET\"\n}}\n");
+        let diff = source_diff(DIFF_BASE, &new, "authz.rego").unwrap();
+
+        assert_eq!(diff.added_rules, vec!["is_reader"]);
+        assert!(diff.removed_rules.is_empty());
+        assert!(diff.modified_rules.is_empty());
+        assert_eq!(diff.policy_complexity_delta, 1);
+    }
+
+    #[test]
+    fn test_source_diff_removed_entry_point() {
+        let new = DIFF_BASE.replace("allow if {\n    roles.is_admin\n}\n", "");
+        let new = new.replace("default allow := false\n", "");
+        let diff = source_diff(DIFF_BASE, &new, "authz.rego").unwrap();
+
+        assert_eq!(diff.removed_rules, vec!["allow"]);
+        assert!(diff.added_rules.is_empty());
+        assert_eq!(diff.policy_complexity_delta, -1);
+    }
+
+    #[test]
+    fn test_source_diff_renamed_rule() {
+        let new = DIFF_BASE.replace("is_owner", "is_resource_owner");
+        let diff = source_diff(DIFF_BASE, &new, "authz.rego").unwrap();
+
+        assert_eq!(diff.added_rules, vec!["is_resource_owner"]);
+        assert_eq!(diff.removed_rules, vec!["is_owner"]);
+        assert!(diff.modified_rules.is_empty());
+        assert_eq!(diff.policy_complexity_delta, 0);
+    }
+
+    #[test]
+    fn test_source_diff_modified_rule_and_imports() {
+        let new = DIFF_BASE
+            .replace(
+                "roles.is_admin",
+                "roles.is_admin\n    # admins only\n    input.mfa",
+            )
+            .replace(
+                "import data.common.roles",
+                "import data.common.roles\nimport rego.v1",
+            );
+        let diff = source_diff(DIFF_BASE, &new, "authz.rego").unwrap();
+
+        assert_eq!(diff.modified_rules, vec!["allow"]);
+        assert_eq!(diff.changed_imports, vec!["+rego.v1"]);
+
+        let comment_only = DIFF_BASE.replace("roles.is_admin", "roles.is_admin # admins");
+        assert!(source_diff(DIFF_BASE, &comment_only, "authz.rego")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_source_diff_ignores_spacing() {
+        let respaced = DIFF_BASE.replace(
+            "input.resource.owner == input.caller.id",
+            "input.resource.owner  ==  input.caller.id   ",
+        );
+        let diff = source_diff(DIFF_BASE, &respaced, "authz.rego").unwrap();
+
+        assert!(diff.modified_rules.is_empty());
+    }
+
+    #[test]
+    fn test_bundle_diff() {
+        let bundle = |version: &str| Bundle::builder("users-service").version(version);
+        let old = bundle("1.3.0")
+            .add_policy("users_service.authz", DIFF_BASE)
+            .add_policy(
+                "users_service.legacy",
+                "package users_service.legacy\n\nx := 1\n",
+            )
+            .add_policy(
+                "users_service.notes",
+                "package users_service.notes\n\ny := 1\n",
+            )
+            .build();
+        let new = bundle("1.4.0")
+            .add_policy(
+                "users_service.authz",
+                DIFF_BASE.replace("roles.is_admin", "roles.is_admin\n    input.mfa"),
+            )
+            .add_policy(
+                "users_service.notes",
+                "# Reviewed\npackage users_service.notes\n\ny := 1 # one\n",
+            )
+            .add_policy(
+                "users_service.reports",
+                "package users_service.reports\n\nimport rego.v1\n\nreport := {}\n",
+            )
+            .build();
+
+        let diff = bundle_diff(&old, &new).unwrap();
+        assert!(!diff.is_empty());
+        assert_eq!(diff.bundle.added, vec!["users_service.reports"]);
+        assert_eq!(diff.bundle.removed, vec!["users_service.legacy"]);
+        assert_eq!(
+            diff.rules.keys().collect::<Vec<_>>(),
+            vec![
+                "users_service.authz",
+                "users_service.legacy",
+                "users_service.reports"
+            ]
+        );
+        assert_eq!(
+            diff.rules["users_service.authz"].modified_rules,
+            vec!["allow"]
+        );
+        assert_eq!(diff.rules["users_service.legacy"].removed_rules, vec!["x"]);
+        assert_eq!(
+            diff.rules["users_service.reports"].added_rules,
+            vec!["report"]
+        );
+        assert_eq!(
+            diff.rules["users_service.reports"].changed_imports,
+            vec!["+rego.v1"]
+        );
+        assert_eq!(diff.cosmetic_changes(), vec!["users_service.notes"]);
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["bundle"]["added"][0], "users_service.reports");
+        assert_eq!(
+            json["rules"]["users_service.authz"]["modified_rules"][0],
+            "allow"
+        );
+    }
+
+    #[test]
+    fn test_bundle_diff_cosmetic_only() {
+        let old = Bundle::builder("users-service")
+            .version("1.3.0")
+            .add_policy("users_service.authz", DIFF_BASE)
+            .build();
+        let new = Bundle::builder("users-service")
+            .version("1.4.0")
+            .add_policy(
+                "users_service.authz",
+                format!("# Authorization rules\n{DIFF_BASE}\n\n"),
+            )
+            .build();
+
+        let diff = bundle_diff(&old, &new).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.cosmetic_changes(), vec!["users_service.authz"]);
+    }
+
+    #[test]
+    fn test_source_diff_missing_package() {
+        let result = source_diff(DIFF_BASE, "allow := true", "authz.rego");
+        assert!(matches!(result, Err(CompilerError::MissingPackage { .. })));
+    }
 }
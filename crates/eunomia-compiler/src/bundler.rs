@@ -2,17 +2,243 @@
 //!
 //! This module provides functionality for compiling policies into distributable bundles.
 
-use std::path::Path;
-use std::time::Instant;
-
-use eunomia_core::{Bundle, Policy};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Instant, SystemTime};
+
+use chrono::{DateTime, Utc};
+use eunomia_core::{
+    package_under_root, Bundle, BundleDependency, BundleLimitError, BundleLimits, BundleSizes,
+    Policy,
+};
 use eunomia_metrics::MetricsRegistry;
-use tracing::info;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
 
 use crate::analyzer::Analyzer;
 use crate::error::{CompilerError, Result};
 use crate::optimizer::Optimizer;
 use crate::parser::Parser;
+use crate::references::ReferenceValidator;
+use crate::semantic::SemanticIssue;
+
+/// Conventional name of the persisted compilation cache in a project root.
+pub const CACHE_FILE_NAME: &str = ".eunomia-cache";
+
+/// A parsed policy file and the file metadata it was parsed from.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    /// Modification time of the file when it was parsed.
+    pub mtime: SystemTime,
+    /// Size of the file in bytes when it was parsed.
+    pub size: u64,
+    /// The parsed policy.
+    pub parse_result: Arc<Policy>,
+}
+
+/// Parse results of policy files, keyed by path.
+///
+/// A file is re-parsed only when its modification time or size differs from
+/// the cached entry. Clones share the same entries, so a cache passed to
+/// [`Bundler::with_cache`] keeps its results after the bundler is consumed by
+/// [`Bundler::compile`] and can be reused for the next build.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use eunomia_compiler::bundler::CACHE_FILE_NAME;
+/// use eunomia_compiler::{Bundler, CompilationCache};
+///
+/// let cache = CompilationCache::load(CACHE_FILE_NAME).unwrap();
+///
+/// Bundler::new("users-service")
+///     .version("1.0.0")
+///     .with_cache(cache.clone())
+///     .add_policy_dir("policies/users-service")
+///     .unwrap()
+///     .compile()
+///     .unwrap();
+///
+/// cache.save(CACHE_FILE_NAME).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CompilationCache {
+    entries: Arc<Mutex<HashMap<PathBuf, CacheEntry>>>,
+}
+
+/// On-disk form of a [`CompilationCache`].
+#[derive(Serialize, Deserialize)]
+struct PersistedCache {
+    /// `eunomia-compiler` version that wrote the cache.
+    version: String,
+    entries: Vec<PersistedEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedEntry {
+    path: PathBuf,
+    mtime: SystemTime,
+    size: u64,
+    policy: Policy,
+}
+
+impl CompilationCache {
+    /// Creates an empty cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a cache persisted with [`save`](Self::save).
+    ///
+    /// A missing file, a corrupt file, or a cache written by a different
+    /// `eunomia-compiler` version yields an empty cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::new());
+        }
+
+        let content = std::fs::read_to_string(path).map_err(|e| CompilerError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        let persisted: PersistedCache = match serde_json::from_str(&content) {
+            Ok(persisted) => persisted,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Ignoring corrupt compilation cache");
+                return Ok(Self::new());
+            }
+        };
+
+        if persisted.version != env!("CARGO_PKG_VERSION") {
+            debug!(
+                cached = %persisted.version,
+                current = env!("CARGO_PKG_VERSION"),
+                "Discarding compilation cache from another compiler version"
+            );
+            return Ok(Self::new());
+        }
+
+        let entries = persisted
+            .entries
+            .into_iter()
+            .map(|entry| {
+                (
+                    entry.path,
+                    CacheEntry {
+                        mtime: entry.mtime,
+                        size: entry.size,
+                        parse_result: Arc::new(entry.policy),
+                    },
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            entries: Arc::new(Mutex::new(entries)),
+        })
+    }
+
+    /// Writes the cache to `path` as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be serialized or written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let persisted = PersistedCache {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: self
+                .lock()
+                .iter()
+                .map(|(path, entry)| PersistedEntry {
+                    path: path.clone(),
+                    mtime: entry.mtime,
+                    size: entry.size,
+                    policy: Policy::clone(&entry.parse_result),
+                })
+                .collect(),
+        };
+
+        let json = serde_json::to_string(&persisted).map_err(|e| CompilerError::CacheError {
+            message: e.to_string(),
+        })?;
+        std::fs::write(path, json).map_err(|e| CompilerError::Io {
+            path: path.to_path_buf(),
+            source: e,
+        })
+    }
+
+    /// Returns the cached policy for `path`, whether or not it is current.
+    #[must_use]
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<Arc<Policy>> {
+        self.lock()
+            .get(path.as_ref())
+            .map(|entry| Arc::clone(&entry.parse_result))
+    }
+
+    /// Returns the number of cached files.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if no files are cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Parses `path`, reusing the cached result if the file's modification
+    /// time and size are unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn parse_file(&self, parser: &Parser, path: &Path) -> Result<Arc<Policy>> {
+        let metadata = std::fs::metadata(path).map_err(|e| CompilerError::FileReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        let mtime = metadata
+            .modified()
+            .map_err(|e| CompilerError::FileReadError {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+        let size = metadata.len();
+
+        if let Some(entry) = self.lock().get(path) {
+            if entry.mtime == mtime && entry.size == size {
+                debug!(?path, "Using cached parse result");
+                return Ok(Arc::clone(&entry.parse_result));
+            }
+        }
+
+        let policy = Arc::new(parser.parse_file(path)?);
+        self.lock().insert(
+            path.to_path_buf(),
+            CacheEntry {
+                mtime,
+                size,
+                parse_result: Arc::clone(&policy),
+            },
+        );
+
+        Ok(policy)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, CacheEntry>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
 
 /// Compiles Rego policies into distributable bundles.
 ///
@@ -44,14 +270,29 @@ pub struct Bundler {
     version: Option<String>,
     /// Git commit SHA.
     git_commit: Option<String>,
+    /// Fixed creation timestamp for reproducible builds.
+    created_at: Option<DateTime<Utc>>,
     /// Policies to include.
     policies: Vec<Policy>,
     /// Data files to include.
     data_files: Vec<(String, String)>,
+    /// Bundles merged into the output, with the manifest digests they were
+    /// fetched by.
+    dependencies: Vec<(Bundle, String)>,
     /// Whether to optimize policies.
     optimize: bool,
     /// Whether to validate policies.
     validate: bool,
+    /// Data roots provided outside the bundle.
+    external_roots: Vec<String>,
+    /// Data roots owned by the bundle besides the one derived from its name.
+    roots: Vec<String>,
+    /// Whether policies may fall outside the bundle's roots.
+    allow_out_of_root: bool,
+    /// Parse results reused across builds.
+    cache: CompilationCache,
+    /// Size and complexity limits of the output.
+    limits: BundleLimits,
 }
 
 impl Bundler {
@@ -66,10 +307,17 @@ impl Bundler {
             name: name.into(),
             version: None,
             git_commit: None,
+            created_at: None,
             policies: Vec::new(),
             data_files: Vec::new(),
+            dependencies: Vec::new(),
             optimize: false,
             validate: true,
+            external_roots: Vec::new(),
+            roots: Vec::new(),
+            allow_out_of_root: false,
+            cache: CompilationCache::new(),
+            limits: BundleLimits::default(),
         }
     }
 
@@ -87,6 +335,16 @@ impl Bundler {
         self
     }
 
+    /// Sets the bundle creation timestamp instead of using the build time.
+    ///
+    /// Compiling the same policies with the same timestamp produces a
+    /// byte-identical archive.
+    #[must_use]
+    pub const fn created_at(mut self, created_at: DateTime<Utc>) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
     /// Adds a policy to the bundle.
     #[must_use]
     pub fn add_policy(mut self, policy: Policy) -> Self {
@@ -94,6 +352,44 @@ impl Bundler {
         self
     }
 
+    /// Merges a dependency bundle, such as shared `common` policies fetched
+    /// from a registry, into the output bundle.
+    ///
+    /// The dependency's policies, data files and roots are added to the
+    /// output, and its name, version and `digest` (that of the OCI manifest
+    /// it was fetched by) are recorded in the manifest. A package or data
+    /// file provided by two sources makes [`compile`](Self::compile) fail
+    /// with [`CompilerError::DependencyConflict`].
+    #[must_use]
+    pub fn add_dependency(mut self, bundle: Bundle, digest: impl Into<String>) -> Self {
+        self.dependencies.push((bundle, digest.into()));
+        self
+    }
+
+    /// Uses `cache` for parse results of policy files.
+    ///
+    /// Set the cache before adding policy files; files whose modification
+    /// time and size match a cache entry are not parsed again.
+    #[must_use]
+    pub fn with_cache(mut self, cache: CompilationCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Sets the size and complexity limits the compiled bundle must stay
+    /// within, instead of the [`BundleLimits`] defaults.
+    #[must_use]
+    pub const fn with_limits(mut self, limits: BundleLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the compilation cache.
+    #[must_use]
+    pub const fn cache(&self) -> &CompilationCache {
+        &self.cache
+    }
+
     /// Adds a policy file to the bundle.
     ///
     /// # Errors
@@ -101,8 +397,8 @@ impl Bundler {
     /// Returns an error if the file cannot be read or parsed.
     pub fn add_policy_file(mut self, path: impl AsRef<Path>) -> Result<Self> {
         let parser = Parser::new();
-        let policy = parser.parse_file(path)?;
-        self.policies.push(policy);
+        let policy = self.cache.parse_file(&parser, path.as_ref())?;
+        self.policies.push(Policy::clone(&policy));
         Ok(self)
     }
 
@@ -161,8 +457,8 @@ impl Bundler {
                             continue;
                         }
                     }
-                    let policy = parser.parse_file(&path)?;
-                    self.policies.push(policy);
+                    let policy = self.cache.parse_file(&parser, &path)?;
+                    self.policies.push(Policy::clone(&policy));
                 }
             }
         }
@@ -240,6 +536,137 @@ impl Bundler {
         self
     }
 
+    /// Declares a data root provided outside the bundle (e.g. by another
+    /// bundle or pushed at runtime).
+    ///
+    /// References under external roots are not checked by
+    /// [`validate`](Self::validate).
+    #[must_use]
+    pub fn with_external_root(mut self, root: impl Into<String>) -> Self {
+        self.external_roots.push(root.into());
+        self
+    }
+
+    /// Declares a data root owned by the bundle.
+    ///
+    /// The root derived from the bundle name is always included; declaring
+    /// it again has no effect.
+    #[must_use]
+    pub fn with_root(mut self, root: impl Into<String>) -> Self {
+        self.roots.push(root.into());
+        self
+    }
+
+    /// Allows policies whose package falls under none of the bundle's
+    /// roots, which [`compile`](Self::compile) rejects otherwise.
+    ///
+    /// Meant for migrating bundles built before roots were enforced.
+    #[must_use]
+    pub const fn allow_out_of_root(mut self, allow: bool) -> Self {
+        self.allow_out_of_root = allow;
+        self
+    }
+
+    /// Returns the roots owned by the bundle: the one derived from its
+    /// name, the declared ones, then those of its dependencies.
+    fn bundle_roots(&self) -> Vec<String> {
+        let mut roots = vec![self.name.replace('-', "_")];
+        let dependency_roots = self
+            .dependencies
+            .iter()
+            .flat_map(|(dependency, _)| &dependency.manifest.roots);
+        for root in self.roots.iter().chain(dependency_roots) {
+            if !roots.contains(root) {
+                roots.push(root.clone());
+            }
+        }
+        roots
+    }
+
+    /// Fails if a policy's package falls under none of `roots`, unless
+    /// [`allow_out_of_root`](Self::allow_out_of_root) is set.
+    ///
+    /// Another bundle loaded next to this one would otherwise be able to
+    /// collide with the package.
+    fn check_roots(&self, roots: &[String]) -> Result<()> {
+        let mut packages: Vec<String> = self
+            .policies
+            .iter()
+            .map(|policy| policy.package_name.clone())
+            .filter(|package| !roots.iter().any(|root| package_under_root(package, root)))
+            .collect();
+        if packages.is_empty() || self.allow_out_of_root {
+            return Ok(());
+        }
+
+        packages.sort();
+        packages.dedup();
+        Err(CompilerError::PackagesOutsideRoots {
+            packages,
+            roots: roots.to_vec(),
+        })
+    }
+
+    /// Checks `data.*` imports and references across all added policies.
+    ///
+    /// Returns one diagnostic per reference that resolves to no package,
+    /// rule or data file in the bundle and is not under an external root.
+    /// [`compile`](Self::compile) runs this check when validation is enabled.
+    /// Policies and data files of dependencies count as provided.
+    #[must_use]
+    pub fn validate(&self) -> Vec<SemanticIssue> {
+        let validator = self.external_roots.iter().fold(
+            ReferenceValidator::new(),
+            ReferenceValidator::with_external_root,
+        );
+        if self.dependencies.is_empty() {
+            return validator.validate(&self.policies, &self.data_files);
+        }
+
+        let mut policies = self.policies.clone();
+        let mut data_files = self.data_files.clone();
+        for (dependency, _) in &self.dependencies {
+            policies.extend(
+                dependency
+                    .policies
+                    .iter()
+                    .map(|(package, source)| Policy::new(package, source)),
+            );
+            data_files.extend(dependency.data_files.clone());
+        }
+        validator.validate(&policies, &data_files)
+    }
+
+    /// Fails if a package or data file is provided by more than one of the
+    /// bundle itself and its dependencies.
+    fn check_dependency_conflicts(&self) -> Result<()> {
+        let mut owners: HashMap<&str, &str> = self
+            .policies
+            .iter()
+            .map(|p| p.package_name.as_str())
+            .chain(self.data_files.iter().map(|(path, _)| path.as_str()))
+            .map(|entry| (entry, self.name.as_str()))
+            .collect();
+
+        for (dependency, _) in &self.dependencies {
+            let entries = dependency
+                .policies
+                .keys()
+                .chain(dependency.data_files.keys());
+            for entry in entries {
+                if let Some(first) = owners.insert(entry, &dependency.name) {
+                    return Err(CompilerError::DependencyConflict {
+                        entry: entry.clone(),
+                        first: first.to_string(),
+                        second: dependency.name.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Compiles the policies into a bundle.
     ///
     /// # Errors
@@ -248,6 +675,11 @@ impl Bundler {
     /// - No version is set
     /// - No policies are added
     /// - Policy validation fails
+    /// - A policy references something the bundle does not provide
+    ///   ([`CompilerError::UnresolvedReferences`])
+    /// - The bundle fails [`Bundle::validate`]
+    ///   ([`CompilerError::InvalidBundle`])
+    /// - The bundle exceeds its limits ([`CompilerError::LimitExceeded`])
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     pub fn compile(self) -> Result<Bundle> {
         let start = Instant::now();
@@ -277,9 +709,12 @@ impl Bundler {
     }
 
     fn compile_internal(self) -> Result<Bundle> {
-        let version = self.version.ok_or_else(|| CompilerError::BundleError {
-            message: "Bundle version is required".to_string(),
-        })?;
+        let version = self
+            .version
+            .clone()
+            .ok_or_else(|| CompilerError::BundleError {
+                message: "Bundle version is required".to_string(),
+            })?;
 
         if self.policies.is_empty() {
             return Err(CompilerError::BundleError {
@@ -287,6 +722,10 @@ impl Bundler {
             });
         }
 
+        self.check_dependency_conflicts()?;
+        let roots = self.bundle_roots();
+        self.check_roots(&roots)?;
+
         // Capture policy count before potentially moving self.policies
         let policy_count = self.policies.len() as u64;
         let bundle_name = self.name.clone();
@@ -307,6 +746,11 @@ impl Bundler {
                     analyzer.validate(policy)?;
                 }
             }
+
+            let issues = self.validate();
+            if !issues.is_empty() {
+                return Err(CompilerError::UnresolvedReferences { issues });
+            }
         }
 
         // Optionally optimize policies
@@ -335,6 +779,10 @@ impl Bundler {
             builder = builder.git_commit(commit);
         }
 
+        if let Some(created_at) = self.created_at {
+            builder = builder.created_at(created_at);
+        }
+
         // Add policies
         for policy in &policies {
             builder = builder.add_policy(&policy.package_name, &policy.source);
@@ -345,10 +793,24 @@ impl Bundler {
             builder = builder.add_data_file(path, content);
         }
 
-        // Add roots based on service name
-        builder = builder.add_root(self.name.replace('-', "_"));
+        // Add roots, then merge dependencies under their own roots
+        for root in &roots {
+            builder = builder.add_root(root);
+        }
+        for (dependency, digest) in &self.dependencies {
+            for (package, source) in &dependency.policies {
+                builder = builder.add_policy(package, source);
+            }
+            for (path, content) in &dependency.data_files {
+                builder = builder.add_data_file(path, content);
+            }
+            builder = builder.add_dependency(BundleDependency::of(dependency, digest));
+        }
 
         let bundle = builder.build();
+        if self.validate {
+            bundle.validate()?;
+        }
 
         // Record bundle size metric
         let bundle_bytes = bundle.to_bytes()?;
@@ -357,6 +819,19 @@ impl Bundler {
             .compiler()
             .record_bundle_size(&bundle_name, bundle_size as u64);
 
+        // Enforce size and complexity limits
+        let sizes = BundleSizes::from_archive(&bundle, &bundle_bytes)?;
+        sizes.check(&self.limits).map_err(|error| {
+            let file = match &error {
+                BundleLimitError::TooManyRules { package, .. } => policies
+                    .iter()
+                    .find(|policy| &policy.package_name == package)
+                    .and_then(|policy| policy.file_path.clone()),
+                _ => None,
+            };
+            CompilerError::LimitExceeded { error, file }
+        })?;
+
         Ok(bundle)
     }
 
@@ -424,6 +899,68 @@ mod tests {
         assert!(bundle.has_policy("users_service.authz"));
     }
 
+    #[test]
+    fn test_bundler_enforces_limits() {
+        let bundler = || {
+            Bundler::new("users-service")
+                .version("1.0.0")
+                .add_policy(Policy::with_file_path(
+                    "users_service.authz",
+                    "package users_service.authz\ndefault allow := false\nallow if input.admin",
+                    "policies/authz.rego",
+                ))
+                .add_data_file(
+                    "users_service/roles.json",
+                    r#"{"admin": ["read", "write"]}"#,
+                )
+        };
+
+        let err = bundler()
+            .with_limits(BundleLimits::default().with_max_data_file_size(16))
+            .compile()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Data file 'users_service/roles.json' is 28 bytes, \
+             over the max_data_file_size limit of 16"
+        );
+
+        let err = bundler()
+            .with_limits(BundleLimits::default().with_max_rules_per_policy(1))
+            .compile()
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("policies/authz.rego: Policy 'users_service.authz'"),
+            "{err}"
+        );
+
+        assert!(bundler()
+            .with_limits(BundleLimits::default().with_max_compressed_size(64))
+            .compile()
+            .is_err());
+        assert!(bundler().compile().is_ok());
+    }
+
+    #[test]
+    fn test_bundler_with_declared_roots() {
+        let policy = Policy::new(
+            "users_service.authz",
+            "package users_service.authz\ndefault allow := false",
+        );
+
+        let bundle = Bundler::new("users-service")
+            .version("1.0.0")
+            .add_policy(policy)
+            .with_root("users_service")
+            .with_root("users_admin")
+            .add_data_file("users_admin/admins.json", r#"["alice"]"#)
+            .compile()
+            .unwrap();
+
+        assert_eq!(bundle.manifest.roots, vec!["users_service", "users_admin"]);
+    }
+
     #[test]
     fn test_bundler_with_git_commit() {
         let policy = Policy::new("test.authz", "package test.authz\ndefault allow := false");
@@ -506,7 +1043,10 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let output_path = dir.path().join("test.bundle.tar.gz");
 
-        let policy = Policy::new("test.authz", "package test.authz\ndefault allow := false");
+        let policy = Policy::new(
+            "test_service.authz",
+            "package test_service.authz\ndefault allow := false",
+        );
 
         let bundle = Bundler::new("test-service")
             .version("1.0.0")
@@ -584,8 +1124,8 @@ mod tests {
     #[test]
     fn test_bundler_records_metrics_on_success() {
         let policy = Policy::new(
-            "metrics_test.authz",
-            "package metrics_test.authz\ndefault allow := false",
+            "metrics_test_service.authz",
+            "package metrics_test_service.authz\ndefault allow := false",
         );
 
         // Compile the bundle
@@ -626,4 +1166,238 @@ mod tests {
             .get_compilation_count("failure-test-service", false);
         assert!(failure_count >= 1.0, "Should record failed compilation");
     }
+
+    #[test]
+    fn test_bundler_rejects_unresolved_references() {
+        let policy = Policy::new(
+            "test.authz",
+            "package test.authz\n\nimport data.common.roles\n\ndefault allow := false\n",
+        );
+
+        let err = Bundler::new("test")
+            .version("1.0.0")
+            .add_policy(policy)
+            .compile()
+            .unwrap_err();
+
+        match err {
+            CompilerError::UnresolvedReferences { issues } => {
+                assert_eq!(issues.len(), 1);
+                assert_eq!(issues[0].file.as_deref(), Some("test.authz.rego"));
+                assert_eq!(issues[0].line, Some(3));
+            }
+            other => panic!("Expected UnresolvedReferences, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_bundler_resolves_references_across_policies() {
+        let roles = Policy::new(
+            "common.roles",
+            "package common.roles\n\ndefault allow := false\n\nis_admin if input.caller.type == \"admin\"\n",
+        );
+        let authz = Policy::new(
+            "test.authz",
+            r"package test.authz
+
+import data.common.roles
+
+default allow := false
+
+allow if {
+    data.common.roles.is_admin
+    data.rate_limits.enabled
+}
+",
+        );
+
+        let bundler = Bundler::new("test")
+            .version("1.0.0")
+            .add_policy(roles)
+            .add_policy(authz)
+            .with_root("common")
+            .with_external_root("rate_limits");
+
+        assert!(bundler.validate().is_empty());
+        assert!(bundler.compile().is_ok());
+    }
+
+    #[test]
+    fn test_compilation_cache_reuses_unchanged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let unchanged_path = dir.path().join("common.rego");
+        let changed_path = dir.path().join("authz.rego");
+        std::fs::write(
+            &unchanged_path,
+            "package test.common\ndefault allow := false",
+        )
+        .unwrap();
+        std::fs::write(&changed_path, "package test.authz\ndefault allow := false").unwrap();
+
+        let cache = CompilationCache::new();
+        let compile = |cache: &CompilationCache| {
+            Bundler::new("test")
+                .version("1.0.0")
+                .with_cache(cache.clone())
+                .add_policy_dir(dir.path())
+                .unwrap()
+                .compile()
+                .unwrap()
+        };
+
+        compile(&cache);
+        assert_eq!(cache.len(), 2);
+        let unchanged_before = cache.get(&unchanged_path).unwrap();
+        let changed_before = cache.get(&changed_path).unwrap();
+
+        std::fs::write(
+            &changed_path,
+            "package test.authz\ndefault allow := false\n\nallow if input.caller.type == \"user\"",
+        )
+        .unwrap();
+        let bundle = compile(&cache);
+
+        assert!(Arc::ptr_eq(
+            &unchanged_before,
+            &cache.get(&unchanged_path).unwrap()
+        ));
+        let changed_after = cache.get(&changed_path).unwrap();
+        assert!(!Arc::ptr_eq(&changed_before, &changed_after));
+        assert!(changed_after.source.contains("allow if"));
+        assert!(bundle.policies["test.authz"].contains("allow if"));
+    }
+
+    #[test]
+    fn test_compilation_cache_persistence() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy_path = dir.path().join("authz.rego");
+        let cache_path = dir.path().join(CACHE_FILE_NAME);
+        std::fs::write(&policy_path, "package test.authz\ndefault allow := false").unwrap();
+
+        let cache = CompilationCache::new();
+        cache.parse_file(&Parser::new(), &policy_path).unwrap();
+        cache.save(&cache_path).unwrap();
+
+        let loaded = CompilationCache::load(&cache_path).unwrap();
+        assert_eq!(loaded.get(&policy_path).unwrap().package_name, "test.authz");
+
+        // A cache from another compiler version is discarded
+        let content = std::fs::read_to_string(&cache_path).unwrap().replace(
+            &format!("\"version\":\"{}\"", env!("CARGO_PKG_VERSION")),
+            "\"version\":\"0.0.0-old\"",
+        );
+        std::fs::write(&cache_path, content).unwrap();
+        assert!(CompilationCache::load(&cache_path).unwrap().is_empty());
+
+        // Missing and corrupt files yield an empty cache
+        assert!(CompilationCache::load(dir.path().join("missing"))
+            .unwrap()
+            .is_empty());
+        std::fs::write(&cache_path, "not json").unwrap();
+        assert!(CompilationCache::load(&cache_path).unwrap().is_empty());
+    }
+
+    /// Manifest digest the common bundle was fetched by.
+    const COMMON_DIGEST: &str =
+        "sha256:4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+
+    fn common_bundle() -> Bundle {
+        Bundle::builder("common-policies")
+            .version("1.2.3")
+            .add_policy(
+                "common.roles",
+                "package common.roles\n\nis_admin if input.caller.type == \"admin\"\n",
+            )
+            .add_data_file("common/data.json", r#"{"admins":["alice"]}"#)
+            .add_root("common")
+            .build()
+    }
+
+    #[test]
+    fn test_bundler_merges_dependencies() {
+        let common = common_bundle();
+        let authz = Policy::new(
+            "users_service.authz",
+            "package users_service.authz\n\nimport data.common.roles\n\n\
+             default allow := false\n\nallow if roles.is_admin\n",
+        );
+
+        let bundle = Bundler::new("users-service")
+            .version("1.0.0")
+            .add_policy(authz)
+            .add_dependency(common.clone(), COMMON_DIGEST)
+            .compile()
+            .unwrap();
+
+        assert!(bundle.has_policy("users_service.authz"));
+        assert!(bundle.has_policy("common.roles"));
+        assert!(bundle.data_files.contains_key("common/data.json"));
+        assert_eq!(bundle.manifest.roots, vec!["users_service", "common"]);
+        assert_eq!(
+            bundle.manifest.dependencies,
+            vec![BundleDependency::of(&common, COMMON_DIGEST)]
+        );
+    }
+
+    #[test]
+    fn test_bundler_rejects_dependency_conflicts() {
+        let shadowing = Policy::new("common.roles", "package common.roles\n\nis_admin := true\n");
+
+        let err = Bundler::new("users-service")
+            .version("1.0.0")
+            .add_policy(shadowing)
+            .add_dependency(common_bundle(), COMMON_DIGEST)
+            .compile()
+            .unwrap_err();
+
+        match err {
+            CompilerError::DependencyConflict {
+                entry,
+                first,
+                second,
+            } => {
+                assert_eq!(entry, "common.roles");
+                assert_eq!(first, "users-service");
+                assert_eq!(second, "common-policies");
+            }
+            other => panic!("Expected DependencyConflict, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_bundler_enforces_roots() {
+        let bundler = || {
+            Bundler::new("users-service")
+                .version("1.0.0")
+                .add_policy(Policy::new(
+                    "users_service.authz",
+                    "package users_service.authz\ndefault allow := false",
+                ))
+                .add_policy(Policy::new(
+                    "orders_service.authz",
+                    "package orders_service.authz\ndefault allow := false",
+                ))
+        };
+
+        let err = bundler().compile().unwrap_err();
+        match err {
+            CompilerError::PackagesOutsideRoots { packages, roots } => {
+                assert_eq!(packages, vec!["orders_service.authz"]);
+                assert_eq!(roots, vec!["users_service"]);
+            }
+            other => panic!("Expected PackagesOutsideRoots, got {other:?}"),
+        }
+
+        let bundle = bundler().allow_out_of_root(true).compile().unwrap();
+        assert_eq!(
+            bundle.packages_outside_roots(),
+            vec!["orders_service.authz"]
+        );
+
+        let bundle = bundler().with_root("orders_service").compile().unwrap();
+        assert_eq!(
+            bundle.manifest.roots,
+            vec!["users_service", "orders_service"]
+        );
+    }
 }
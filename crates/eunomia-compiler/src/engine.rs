@@ -10,6 +10,7 @@
 //! - Data injection for policy evaluation
 //! - Query evaluation and result handling
 //! - Rule enumeration for test discovery
+//! - Line and rule coverage of evaluations
 //!
 //! # Examples
 //!
@@ -30,20 +31,24 @@
 //! let allowed = engine.eval_bool("data.authz.allow")?;
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::path::Path;
 
 use serde_json::Value;
 use tracing::{debug, instrument, warn};
 
+pub(crate) use eunomia_core::policy::rule_name as extract_rule_name;
+
 use crate::error::{CompilerError, Result};
+use crate::trace::EvalTrace;
 
 /// A Rego policy engine based on `regorus`.
 ///
 /// The engine maintains a collection of policies and data, and can evaluate
-/// Rego queries against them.
-#[derive(Debug)]
+/// Rego queries against them. Cloning an engine copies its loaded policies
+/// and data, which is cheaper than reparsing them.
+#[derive(Debug, Clone)]
 pub struct RegoEngine {
     /// The underlying regorus engine.
     inner: regorus::Engine,
@@ -51,6 +56,16 @@ pub struct RegoEngine {
     policies: HashMap<String, PolicyInfo>,
     /// Whether strict mode is enabled for additional validation.
     strict_mode: bool,
+    /// Whether line and rule coverage are being collected.
+    coverage_enabled: bool,
+    /// Source lines of each rule (`<package>.<rule>`), per policy file.
+    rule_lines: HashMap<String, Vec<(String, Vec<usize>)>>,
+    /// Number of queries that returned a defined value, per rule.
+    rule_hits: HashMap<String, u64>,
+    /// Source code of each loaded policy, for rebuilding the engine.
+    sources: HashMap<String, String>,
+    /// Data documents added so far, for rebuilding the engine.
+    data: Vec<Value>,
 }
 
 /// Information about a loaded policy.
@@ -66,6 +81,56 @@ pub struct PolicyInfo {
     pub is_test: bool,
 }
 
+/// Line coverage of a loaded policy, as collected during evaluation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyCoverage {
+    /// Name the policy was loaded under.
+    pub file: String,
+    /// Policy source code.
+    pub source: String,
+    /// Executable lines (1-based) evaluated at least once.
+    pub covered: BTreeSet<usize>,
+    /// Executable lines (1-based) never evaluated.
+    pub not_covered: BTreeSet<usize>,
+}
+
+/// Rule coverage collected by a [`RegoEngine`] with coverage enabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Coverage of each rule, keyed by `<package>.<rule_name>`.
+    pub rules: HashMap<String, RuleCoverage>,
+}
+
+impl CoverageReport {
+    /// Returns the rules that were never hit, sorted by name.
+    #[must_use]
+    pub fn uncovered_rules(&self) -> Vec<String> {
+        let mut rules: Vec<String> = self
+            .rules
+            .iter()
+            .filter(|(_, coverage)| coverage.hit_count == 0)
+            .map(|(name, _)| name.clone())
+            .collect();
+        rules.sort();
+        rules
+    }
+}
+
+/// Coverage of a single rule.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleCoverage {
+    /// Number of times the rule was hit.
+    ///
+    /// Each query that returned a defined value for the rule counts once.
+    /// A rule that was never queried directly but whose lines were
+    /// evaluated (for example, a helper called by another rule) counts as
+    /// hit once.
+    pub hit_count: u64,
+    /// Lines (1-based) of the rule's definitions, excluding blank lines and
+    /// comments.
+    pub source_lines: Vec<usize>,
+}
+
 /// Result of evaluating a Rego query.
 #[derive(Debug, Clone)]
 pub enum EvalResult {
@@ -132,9 +197,25 @@ impl RegoEngine {
             inner: regorus::Engine::new(),
             policies: HashMap::new(),
             strict_mode: false,
+            coverage_enabled: false,
+            rule_lines: HashMap::new(),
+            rule_hits: HashMap::new(),
+            sources: HashMap::new(),
+            data: Vec::new(),
         }
     }
 
+    /// Creates a new engine with coverage collection enabled.
+    ///
+    /// See [`coverage`](Self::coverage) and
+    /// [`coverage_report`](Self::coverage_report).
+    #[must_use]
+    pub fn with_coverage() -> Self {
+        let mut engine = Self::new();
+        engine.set_enable_coverage(true);
+        engine
+    }
+
     /// Creates a new engine with strict mode enabled.
     ///
     /// Strict mode enables additional validation checks.
@@ -199,6 +280,12 @@ impl RegoEngine {
         let rules = extract_rules_from_source(source);
         let is_test = name.ends_with("_test.rego") || package.ends_with("_test");
 
+        let rule_lines = rule_line_spans(source)
+            .into_iter()
+            .map(|(rule, lines)| (format!("{package}.{rule}"), lines))
+            .collect();
+        self.rule_lines.insert(name.to_string(), rule_lines);
+
         let info = PolicyInfo {
             package,
             file_path: Some(name.to_string()),
@@ -207,10 +294,75 @@ impl RegoEngine {
         };
 
         self.policies.insert(name.to_string(), info.clone());
+        self.sources.insert(name.to_string(), source.to_string());
 
         Ok(info)
     }
 
+    /// Removes a loaded policy, returning its information.
+    ///
+    /// See [`remove_policies`](Self::remove_policies).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a remaining policy or data document can no
+    /// longer be loaded.
+    pub fn remove_policy(&mut self, name: &str) -> Result<Option<PolicyInfo>> {
+        Ok(self.remove_policies(&[name])?.pop())
+    }
+
+    /// Removes loaded policies, returning the information of those that
+    /// were loaded.
+    ///
+    /// `regorus` cannot unload a single policy, so unless none of `names`
+    /// is loaded, the remaining policies and data are parsed again into a
+    /// fresh engine, once for the whole batch; their extracted metadata is
+    /// kept. The input is reset and collected coverage starts over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a remaining policy or data document can no
+    /// longer be loaded.
+    #[instrument(skip(self))]
+    pub fn remove_policies(&mut self, names: &[&str]) -> Result<Vec<PolicyInfo>> {
+        let removed: Vec<PolicyInfo> = names
+            .iter()
+            .filter_map(|name| {
+                let info = self.policies.remove(*name)?;
+                self.sources.remove(*name);
+                self.rule_lines.remove(*name);
+                Some(info)
+            })
+            .collect();
+        if removed.is_empty() {
+            return Ok(removed);
+        }
+        debug!(count = removed.len(), "Removing policies");
+        self.rule_hits.clear();
+
+        let mut inner = regorus::Engine::new();
+        inner.set_enable_coverage(self.coverage_enabled);
+        for (file, source) in &self.sources {
+            inner
+                .add_policy(file.clone(), source.clone())
+                .map_err(|e| CompilerError::ParseError {
+                    file: file.clone(),
+                    line: extract_line_from_error(&e.to_string()).unwrap_or(1),
+                    message: e.to_string(),
+                })?;
+        }
+        for data in &self.data {
+            inner
+                .add_data(data.clone().into())
+                .map_err(|e| CompilerError::ValidationError {
+                    message: format!("Failed to add data: {e}"),
+                })?;
+        }
+        self.inner = inner;
+
+        Ok(removed)
+    }
+
     /// Sets the input data for evaluation.
     ///
     /// # Arguments
@@ -249,17 +401,26 @@ impl RegoEngine {
     ///
     /// Returns an error if the data cannot be added.
     pub fn add_data(&mut self, data: Value) -> Result<()> {
-        let regorus_value: regorus::Value = data.into();
+        let regorus_value: regorus::Value = data.clone().into();
         self.inner
             .add_data(regorus_value)
             .map_err(|e| CompilerError::ValidationError {
                 message: format!("Failed to add data: {e}"),
-            })
+            })?;
+        self.data.push(data);
+        Ok(())
+    }
+
+    /// Removes all data documents, keeping the loaded policies.
+    pub fn clear_data(&mut self) {
+        self.inner.clear_data();
+        self.data.clear();
     }
 
     /// Clears the input data.
     pub fn clear_input(&mut self) {
         self.inner.clear_data();
+        self.data.clear();
     }
 
     /// Evaluates a Rego query and returns the result.
@@ -278,9 +439,7 @@ impl RegoEngine {
         let results = self
             .inner
             .eval_query(query.to_string(), false)
-            .map_err(|e| CompilerError::ValidationError {
-                message: format!("Query evaluation failed: {e}"),
-            })?;
+            .map_err(|e| self.evaluation_error(query, &e.to_string()))?;
 
         // Convert regorus result to our EvalResult
         if results.result.is_empty() {
@@ -294,7 +453,68 @@ impl RegoEngine {
         }
 
         let value = &first_result.expressions[0].value;
-        Ok(convert_value(value))
+        let result = convert_value(value);
+
+        if self.coverage_enabled && !matches!(result, EvalResult::Undefined) {
+            if let Some(rule) = query.trim().strip_prefix("data.") {
+                if self
+                    .rule_lines
+                    .values()
+                    .flatten()
+                    .any(|(name, _)| name == rule)
+                {
+                    *self.rule_hits.entry(rule.to_string()).or_default() += 1;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Builds an [`CompilerError::EvaluationError`], locating the error in
+    /// a loaded policy file when the message names one.
+    fn evaluation_error(&self, query: &str, message: &str) -> CompilerError {
+        let location = self.policies.keys().find_map(|file| {
+            let (_, rest) = message.split_once(&format!("{file}:"))?;
+            let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+            Some((file.clone(), digits.parse().ok()))
+        });
+        let (file, line) = match location {
+            Some((file, line)) => (Some(file), line),
+            None => (None, None),
+        };
+
+        CompilerError::EvaluationError {
+            query: query.to_string(),
+            file,
+            line,
+            message: message.trim().to_string(),
+        }
+    }
+
+    /// Evaluates a query and explains the result with a trace of the rule
+    /// definitions entered and the expressions they stopped at.
+    ///
+    /// The query runs against the current input on a copy of the engine
+    /// with coverage enabled, so coverage collected by this engine is left
+    /// untouched. See [`crate::trace`] for how the trace is derived.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query cannot be evaluated.
+    pub fn eval_with_trace(&self, query: &str) -> Result<EvalTrace> {
+        let mut engine = self.clone();
+        engine.set_enable_coverage(true);
+        engine.clear_coverage();
+
+        let result = engine.eval(query)?;
+        let coverage = engine.coverage()?;
+        Ok(EvalTrace::from_coverage(
+            query,
+            result,
+            &coverage,
+            &self.rule_lines,
+        ))
     }
 
     /// Evaluates a query and returns a boolean result.
@@ -309,6 +529,39 @@ impl RegoEngine {
         Ok(result.is_truthy())
     }
 
+    /// Evaluates a query against the given input.
+    ///
+    /// The input only applies to this evaluation: it is reset to `null`
+    /// afterwards, whether or not evaluation succeeds. Prefer this over
+    /// interpolating the input into the query with `with input as`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input cannot be set or the query cannot be
+    /// evaluated.
+    pub fn eval_with_input(&mut self, query: &str, input: Value) -> Result<EvalResult> {
+        self.set_input(input)?;
+        let result = self.eval(query);
+        self.set_input(Value::Null)?;
+        result
+    }
+
+    /// Evaluates a query against the given input and returns a boolean
+    /// result.
+    ///
+    /// Returns `false` if the result is undefined. Like
+    /// [`eval_with_input`](Self::eval_with_input), the input is reset
+    /// afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input cannot be set or the query cannot be
+    /// evaluated.
+    pub fn eval_bool_with_input(&mut self, query: &str, input: Value) -> Result<bool> {
+        let result = self.eval_with_input(query, input)?;
+        Ok(result.is_truthy())
+    }
+
     /// Gets the list of loaded policy files.
     #[must_use]
     pub fn policy_files(&self) -> Vec<&str> {
@@ -349,6 +602,88 @@ impl RegoEngine {
         tests
     }
 
+    /// Enables or disables line and rule coverage collection.
+    ///
+    /// Coverage accumulates across evaluations until
+    /// [`clear_coverage`](Self::clear_coverage) is called.
+    pub fn set_enable_coverage(&mut self, enable: bool) {
+        self.inner.set_enable_coverage(enable);
+        self.coverage_enabled = enable;
+    }
+
+    /// Discards collected coverage data.
+    pub fn clear_coverage(&mut self) {
+        self.inner.clear_coverage_data();
+        self.rule_hits.clear();
+    }
+
+    /// Returns rule coverage for every loaded policy.
+    ///
+    /// Combines the rules queried directly (see [`RuleCoverage::hit_count`])
+    /// with line coverage, so helper rules evaluated on the way to a result
+    /// count as hit too. Without coverage enabled, every rule is reported
+    /// as uncovered.
+    #[must_use]
+    pub fn coverage_report(&self) -> CoverageReport {
+        let covered: HashMap<String, BTreeSet<usize>> = if self.coverage_enabled {
+            self.coverage()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|policy| (policy.file, policy.covered))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let mut report = CoverageReport::default();
+        for (file, rules) in &self.rule_lines {
+            let covered = covered.get(file);
+            for (rule, lines) in rules {
+                let coverage = report.rules.entry(rule.clone()).or_default();
+                coverage.source_lines.extend(lines);
+                if coverage.hit_count == 0
+                    && covered.is_some_and(|covered| lines.iter().any(|l| covered.contains(l)))
+                {
+                    coverage.hit_count = 1;
+                }
+            }
+        }
+
+        for (rule, coverage) in &mut report.rules {
+            if let Some(hits) = self.rule_hits.get(rule) {
+                coverage.hit_count = *hits;
+            }
+            coverage.source_lines.sort_unstable();
+        }
+
+        report
+    }
+
+    /// Returns line coverage for every loaded policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the coverage report cannot be produced.
+    pub fn coverage(&self) -> Result<Vec<PolicyCoverage>> {
+        let report =
+            self.inner
+                .get_coverage_report()
+                .map_err(|e| CompilerError::ValidationError {
+                    message: format!("Failed to collect coverage: {e}"),
+                })?;
+
+        Ok(report
+            .files
+            .into_iter()
+            .map(|file| PolicyCoverage {
+                file: file.path,
+                source: file.code,
+                covered: file.covered.into_iter().map(|l| l as usize).collect(),
+                not_covered: file.not_covered.into_iter().map(|l| l as usize).collect(),
+            })
+            .collect())
+    }
+
     /// Returns whether strict mode is enabled.
     #[must_use]
     pub const fn is_strict_mode(&self) -> bool {
@@ -370,7 +705,7 @@ pub struct TestRule {
 }
 
 /// Extracts the package name from Rego source.
-fn extract_package_from_source(source: &str, file: &str) -> Result<String> {
+pub(crate) fn extract_package_from_source(source: &str, file: &str) -> Result<String> {
     for (line_num, line) in source.lines().enumerate() {
         let trimmed = line.trim();
 
@@ -404,7 +739,7 @@ fn extract_package_from_source(source: &str, file: &str) -> Result<String> {
 }
 
 /// Extracts rule names from Rego source.
-fn extract_rules_from_source(source: &str) -> Vec<String> {
+pub(crate) fn extract_rules_from_source(source: &str) -> Vec<String> {
     let mut rules = Vec::new();
 
     for line in source.lines() {
@@ -427,56 +762,41 @@ fn extract_rules_from_source(source: &str) -> Vec<String> {
     rules
 }
 
-/// Extracts a rule name from a line.
-fn extract_rule_name(line: &str) -> Option<String> {
-    // Skip imports and package
-    if line.starts_with("import") || line.starts_with("package") {
-        return None;
-    }
-
-    // Skip `default` keyword but extract the name after it
-    let line = line.strip_prefix("default").map_or(line, str::trim);
-
-    // Find rule name (before := , = , if, {, [, or ()
-    let name_end = line
-        .find(":=")
-        .or_else(|| line.find(" = "))
-        .or_else(|| line.find(" if"))
-        .or_else(|| line.find('{'))
-        .or_else(|| line.find('['))
-        .or_else(|| line.find('('))?;
-
-    let name = line[..name_end].trim();
+/// Returns the lines (1-based) of each rule's definitions, excluding blank
+/// lines and comments.
+///
+/// A definition starts at an unindented rule head and runs until the next
+/// one, or until a `package` or `import` statement.
+fn rule_line_spans(source: &str) -> Vec<(String, Vec<usize>)> {
+    let mut spans: Vec<(String, Vec<usize>)> = Vec::new();
+    let mut current: Option<usize> = None;
 
-    // Validate it looks like an identifier
-    if name.is_empty() || !name.chars().next()?.is_alphabetic() {
-        return None;
-    }
+    for (idx, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
 
-    // Handle array/object rules like `arr[x]` -> `arr`
-    let name = name.split('[').next()?.trim();
+        if !line.starts_with(char::is_whitespace) {
+            if trimmed.starts_with("package") || trimmed.starts_with("import") {
+                current = None;
+                continue;
+            }
+            if let Some(name) = extract_rule_name(trimmed) {
+                let pos = spans.iter().position(|(n, _)| *n == name);
+                current = Some(pos.unwrap_or_else(|| {
+                    spans.push((name, Vec::new()));
+                    spans.len() - 1
+                }));
+            }
+        }
 
-    if is_valid_identifier(name) {
-        Some(name.to_string())
-    } else {
-        None
+        if let Some(pos) = current {
+            spans[pos].1.push(idx + 1);
+        }
     }
-}
 
-/// Checks if a string is a valid Rego identifier.
-fn is_valid_identifier(s: &str) -> bool {
-    if s.is_empty() {
-        return false;
-    }
-    let mut chars = s.chars();
-    // SAFETY: Safe because we already checked `!s.is_empty()` above
-    let Some(first) = chars.next() else {
-        return false;
-    };
-    if !first.is_alphabetic() && first != '_' {
-        return false;
-    }
-    chars.all(|c| c.is_alphanumeric() || c == '_')
+    spans
 }
 
 /// Extracts line number from error message.
@@ -663,6 +983,111 @@ test_guest_denied if {
         assert!(!result);
     }
 
+    #[test]
+    fn test_eval_with_input_resets_input() {
+        let mut engine = RegoEngine::new();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+
+        let result = engine
+            .eval_with_input("data.authz.allow", json!({"user": {"role": "admin"}}))
+            .unwrap();
+        assert!(matches!(result, EvalResult::Bool(true)));
+
+        // The admin input must not leak into the next evaluation
+        assert!(!engine.eval_bool("data.authz.allow").unwrap());
+        assert!(matches!(
+            engine.eval("input").unwrap(),
+            EvalResult::Undefined
+        ));
+    }
+
+    #[test]
+    fn test_eval_bool_with_input() {
+        let mut engine = RegoEngine::new();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+
+        assert!(engine
+            .eval_bool_with_input("data.authz.allow", json!({"user": {"role": "admin"}}))
+            .unwrap());
+        assert!(!engine
+            .eval_bool_with_input("data.authz.allow", json!({"user": {"role": "guest"}}))
+            .unwrap());
+
+        // Input is reset even when evaluation fails
+        engine
+            .eval_bool_with_input("data.authz.allow[", json!({"user": {"role": "admin"}}))
+            .unwrap_err();
+        assert!(!engine.eval_bool("data.authz.allow").unwrap());
+    }
+
+    #[test]
+    fn test_coverage_report_uncovered_rules() {
+        let tests = r#"
+package authz_test
+
+import data.authz
+
+test_admin_allowed if {
+    authz.allow with input as {"user": {"role": "admin"}}
+}
+
+test_guest_denied if {
+    not authz.allow with input as {"user": {"role": "guest"}}
+}
+
+test_anonymous_denied if {
+    not authz.allow with input as {}
+}
+"#;
+        let mut engine = RegoEngine::with_coverage();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+        engine.add_policy("authz_test.rego", tests).unwrap();
+
+        assert!(engine
+            .eval_bool("data.authz_test.test_admin_allowed")
+            .unwrap());
+        assert!(engine
+            .eval_bool("data.authz_test.test_guest_denied")
+            .unwrap());
+        assert!(engine
+            .eval_bool("data.authz_test.test_admin_allowed")
+            .unwrap());
+
+        let report = engine.coverage_report();
+        let uncovered = report.uncovered_rules();
+        assert!(uncovered.contains(&"authz_test.test_anonymous_denied".to_string()));
+        assert!(!uncovered.contains(&"authz_test.test_admin_allowed".to_string()));
+        assert!(!uncovered.contains(&"authz_test.test_guest_denied".to_string()));
+
+        let admin = &report.rules["authz_test.test_admin_allowed"];
+        assert_eq!(admin.hit_count, 2);
+        assert_eq!(admin.source_lines, vec![6, 7, 8]);
+
+        // `authz.allow` was only evaluated through the tests
+        assert!(report.rules["authz.allow"].hit_count > 0);
+
+        engine.clear_coverage();
+        assert_eq!(
+            engine.coverage_report().uncovered_rules().len(),
+            report.rules.len()
+        );
+    }
+
+    #[test]
+    fn test_coverage_report_without_coverage() {
+        let mut engine = RegoEngine::new();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+        engine
+            .set_input(json!({"user": {"role": "admin"}}))
+            .unwrap();
+        assert!(engine.eval_bool("data.authz.allow").unwrap());
+
+        assert_eq!(
+            engine.coverage_report().uncovered_rules(),
+            vec!["authz.allow"]
+        );
+    }
+
     #[test]
     fn test_get_test_rules() {
         let mut engine = RegoEngine::new();
@@ -740,4 +1165,111 @@ test_guest_denied if {
         let info = engine.get_policy_info("authz.rego").unwrap();
         assert_eq!(info.package, "authz");
     }
+
+    #[test]
+    fn test_remove_policy() {
+        let mut engine = RegoEngine::new();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+        engine.add_policy("authz_test.rego", TEST_POLICY).unwrap();
+
+        let removed = engine.remove_policy("authz_test.rego").unwrap().unwrap();
+        assert_eq!(removed.package, "authz_test");
+        assert_eq!(engine.policy_files(), vec!["authz.rego"]);
+        assert!(engine.get_test_rules().is_empty());
+        assert!(engine.remove_policy("authz_test.rego").unwrap().is_none());
+
+        // The remaining policy is still loaded, and the removed one can be
+        // added again under the same name.
+        assert!(engine
+            .eval_bool_with_input("data.authz.allow", json!({"user": {"role": "admin"}}))
+            .unwrap());
+        engine.add_policy("authz_test.rego", TEST_POLICY).unwrap();
+        assert_eq!(engine.get_test_rules().len(), 2);
+
+        let removed = engine
+            .remove_policies(&["authz.rego", "missing.rego", "authz_test.rego"])
+            .unwrap();
+        assert_eq!(removed.len(), 2);
+        assert!(engine.policy_files().is_empty());
+    }
+
+    #[test]
+    fn test_clear_data() {
+        let mut engine = RegoEngine::new();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+        engine.add_data(json!({"roles": ["admin"]})).unwrap();
+        engine.clear_data();
+        assert!(engine.data.is_empty());
+
+        // Data cleared before a rebuild is not loaded again
+        engine.add_policy("other.rego", "package other\n").unwrap();
+        engine.remove_policy("other.rego").unwrap();
+        assert!(engine.data.is_empty());
+        assert_eq!(engine.policy_files(), vec!["authz.rego"]);
+    }
+
+    #[test]
+    fn test_coverage_collection() {
+        let mut engine = RegoEngine::new();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+        engine.set_enable_coverage(true);
+        engine
+            .set_input(json!({"user": {"role": "admin"}}))
+            .unwrap();
+
+        assert!(engine.eval_bool("data.authz.allow").unwrap());
+
+        let coverage = engine.coverage().unwrap();
+        let authz = coverage.iter().find(|c| c.file == "authz.rego").unwrap();
+        assert!(authz.covered.contains(&7));
+        assert_eq!(authz.source, SIMPLE_POLICY);
+
+        engine.clear_coverage();
+        let coverage = engine.coverage().unwrap();
+        assert!(coverage.iter().all(|c| c.covered.is_empty()));
+    }
+
+    #[test]
+    fn test_eval_with_trace() {
+        let mut engine = RegoEngine::with_coverage();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+        engine
+            .set_input(json!({"user": {"role": "guest"}}))
+            .unwrap();
+
+        let trace = engine.eval_with_trace("data.authz.allow").unwrap();
+        assert!(matches!(trace.result, EvalResult::Bool(false)));
+
+        let allow = trace.rules.iter().find(|r| r.line == 6).unwrap();
+        assert_eq!(allow.rule, "authz.allow");
+        assert_eq!(allow.failed_at.as_ref().map(|l| l.line), Some(7));
+
+        // The engine's own coverage is unaffected
+        assert!(engine
+            .coverage()
+            .unwrap()
+            .iter()
+            .all(|c| c.covered.is_empty()));
+    }
+
+    #[test]
+    fn test_eval_error_is_structured() {
+        let mut engine = RegoEngine::new();
+        engine.add_policy("authz.rego", SIMPLE_POLICY).unwrap();
+
+        let err = engine.eval("data.authz.allow[").unwrap_err();
+        assert!(matches!(
+            err,
+            CompilerError::EvaluationError { ref query, .. } if query == "data.authz.allow["
+        ));
+
+        let err = engine.evaluation_error("data.authz.allow", "authz.rego:7:5: error: boom");
+        match err {
+            CompilerError::EvaluationError { file, line, .. } => {
+                assert_eq!(file.as_deref(), Some("authz.rego"));
+                assert_eq!(line, Some(7));
+            }
+            other => panic!("Expected EvaluationError, got {other:?}"),
+        }
+    }
 }
@@ -1,8 +1,13 @@
 //! Error types for the Eunomia compiler.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use eunomia_core::{BundleLimitError, BundleValidationError};
 use thiserror::Error;
 
+use crate::analyzer::AnalysisError;
+use crate::semantic::SemanticIssue;
+
 /// Result type alias for compiler operations.
 pub type Result<T> = std::result::Result<T, CompilerError>;
 
@@ -37,6 +42,19 @@ pub enum CompilerError {
         message: String,
     },
 
+    /// Evaluating a query failed.
+    #[error("Evaluation of {query} failed: {message}")]
+    EvaluationError {
+        /// The evaluated query.
+        query: String,
+        /// Policy file the error was reported in, if known.
+        file: Option<String>,
+        /// Line (1-based) the error was reported at, if known.
+        line: Option<usize>,
+        /// Error message.
+        message: String,
+    },
+
     /// Missing required package declaration.
     #[error("Missing package declaration in {file}")]
     MissingPackage {
@@ -44,6 +62,35 @@ pub enum CompilerError {
         file: String,
     },
 
+    /// Policies reference packages, rules or data that the bundle does not
+    /// provide.
+    #[error("Unresolved references in bundle: {}", format_unresolved(issues))]
+    UnresolvedReferences {
+        /// One diagnostic per unresolved reference.
+        issues: Vec<SemanticIssue>,
+    },
+
+    /// A lint configuration is invalid.
+    #[error("Invalid lint configuration: {message}")]
+    LintConfigError {
+        /// Error message.
+        message: String,
+    },
+
+    /// The compilation cache could not be read or written.
+    #[error("Compilation cache error: {message}")]
+    CacheError {
+        /// Error message.
+        message: String,
+    },
+
+    /// A service contract could not be loaded.
+    #[error("Invalid service contract: {message}")]
+    ContractError {
+        /// Error message.
+        message: String,
+    },
+
     /// Bundle creation failed.
     #[error("Bundle creation error: {message}")]
     BundleError {
@@ -51,6 +98,43 @@ pub enum CompilerError {
         message: String,
     },
 
+    /// A package or data file is provided by more than one source bundle.
+    #[error("{entry} is defined by both {first} and {second}")]
+    DependencyConflict {
+        /// Package name or data file path.
+        entry: String,
+        /// Bundle that provided the entry first.
+        first: String,
+        /// Bundle that provided it again.
+        second: String,
+    },
+
+    /// Policy packages fall under none of the bundle's roots.
+    #[error(
+        "Packages outside the bundle roots ({}): {}",
+        roots.join(", "),
+        packages.join(", ")
+    )]
+    PackagesOutsideRoots {
+        /// Offending packages, sorted.
+        packages: Vec<String>,
+        /// Roots owned by the bundle.
+        roots: Vec<String>,
+    },
+
+    /// The bundle exceeds one of its size or complexity limits.
+    #[error("{}{error}", file_prefix(file.as_deref()))]
+    LimitExceeded {
+        /// The exceeded limit.
+        error: BundleLimitError,
+        /// Source file of the offending policy, if known.
+        file: Option<PathBuf>,
+    },
+
+    /// The compiled bundle fails its structural integrity check.
+    #[error("Invalid bundle: {0}")]
+    InvalidBundle(#[from] BundleValidationError),
+
     /// I/O error during directory operations.
     #[error("I/O error at {path}: {source}")]
     Io {
@@ -61,11 +145,37 @@ pub enum CompilerError {
         source: std::io::Error,
     },
 
+    /// Cross-policy analysis error.
+    #[error(transparent)]
+    Analysis(#[from] AnalysisError),
+
     /// Core library error.
     #[error(transparent)]
     CoreError(#[from] eunomia_core::Error),
 }
 
+/// Formats `file` as a message prefix, or nothing without one.
+fn file_prefix(file: Option<&Path>) -> String {
+    file.map(|file| format!("{}: ", file.display()))
+        .unwrap_or_default()
+}
+
+/// Formats unresolved reference diagnostics as `file:line: message` lines.
+fn format_unresolved(issues: &[SemanticIssue]) -> String {
+    use std::fmt::Write as _;
+
+    issues.iter().fold(String::new(), |mut out, issue| {
+        let _ = write!(
+            out,
+            "\n  {}:{}: {}",
+            issue.file.as_deref().unwrap_or("<unknown>"),
+            issue.line.unwrap_or(0),
+            issue.message
+        );
+        out
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,6 +193,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_packages_outside_roots_display() {
+        let err = CompilerError::PackagesOutsideRoots {
+            packages: vec!["orders_service.authz".to_string()],
+            roots: vec!["users_service".to_string(), "common".to_string()],
+        };
+        assert_eq!(
+            err.to_string(),
+            "Packages outside the bundle roots (users_service, common): orders_service.authz"
+        );
+    }
+
+    #[test]
+    fn test_limit_exceeded_display() {
+        let error = BundleLimitError::TooManyRules {
+            package: "users_service.authz".to_string(),
+            rules: 12,
+            limit: 10,
+        };
+        let err = CompilerError::LimitExceeded {
+            error: error.clone(),
+            file: Some(PathBuf::from("policies/authz.rego")),
+        };
+        assert_eq!(
+            err.to_string(),
+            "policies/authz.rego: Policy 'users_service.authz' has 12 rules, \
+             over the max_rules_per_policy limit of 10"
+        );
+
+        let err = CompilerError::LimitExceeded { error, file: None };
+        assert!(err.to_string().starts_with("Policy 'users_service.authz'"));
+    }
+
     #[test]
     fn test_missing_package_display() {
         let err = CompilerError::MissingPackage {
@@ -90,4 +233,25 @@ mod tests {
         };
         assert_eq!(err.to_string(), "Missing package declaration in test.rego");
     }
+
+    #[test]
+    fn test_unresolved_references_display() {
+        use crate::semantic::{SemanticCategory, SemanticSeverity};
+
+        let err = CompilerError::UnresolvedReferences {
+            issues: vec![SemanticIssue {
+                severity: SemanticSeverity::Error,
+                category: SemanticCategory::DataReference,
+                message: "Import 'data.common.roles' does not resolve to anything in the bundle"
+                    .to_string(),
+                file: Some("authz.rego".to_string()),
+                line: Some(3),
+                snippet: None,
+                suggestion: None,
+            }],
+        };
+        assert!(err
+            .to_string()
+            .starts_with("Unresolved references in bundle: \n  authz.rego:3: Import"));
+    }
 }
@@ -0,0 +1,448 @@
+//! Rego source formatter.
+//!
+//! A line-based formatter that produces a consistent layout without parsing
+//! the full Rego grammar:
+//!
+//! - Leading comments, then the `package` declaration, come first
+//! - `import` statements follow, sorted alphabetically and deduplicated
+//! - Top-level statements are separated by exactly one blank line
+//! - Lines are indented by 4 spaces per open bracket
+//! - `default <rule> = <value>` becomes `default <rule> := <value>`
+//!
+//! Comments directly above an import or rule stay attached to it. Raw
+//! (backtick) strings are preserved verbatim.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use eunomia_compiler::format_rego;
+//!
+//! let source = "package authz\ndefault allow = false\nallow if {\n  input.admin\n}\n";
+//!
+//! assert_eq!(
+//!     format_rego(source).unwrap(),
+//!     "package authz\n\ndefault allow := false\n\nallow if {\n    input.admin\n}\n"
+//! );
+//! ```
+
+use thiserror::Error;
+
+/// Indentation used for each bracket level.
+const INDENT: &str = "    ";
+
+/// Errors that prevent a source from being formatted.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FormatterError {
+    /// The source is not well-formed enough to format.
+    #[error("Cannot format policy: line {line}: {message}")]
+    ParseFailed {
+        /// Line number (1-based) where the problem was found.
+        line: usize,
+        /// Description of the problem.
+        message: String,
+    },
+}
+
+impl FormatterError {
+    fn parse_failed(line: usize, message: impl Into<String>) -> Self {
+        Self::ParseFailed {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+/// Formats Rego source.
+///
+/// Formatting is idempotent: formatting already formatted source returns it
+/// unchanged.
+///
+/// # Errors
+///
+/// Returns [`FormatterError::ParseFailed`] if the source has no `package`
+/// declaration, more than one, unbalanced brackets, or an unterminated
+/// string.
+pub fn format_rego(source: &str) -> Result<String, FormatterError> {
+    let mut layout = Layout::default();
+    let mut scanner = Scanner::default();
+    let mut block: Option<Vec<String>> = None;
+    let mut comments: Vec<String> = Vec::new();
+
+    for (idx, raw) in source.lines().enumerate() {
+        let line_no = idx + 1;
+
+        // Lines inside a multi-line raw string are kept as they are
+        if scanner.in_raw_string {
+            scanner.scan(raw, line_no)?;
+            block.get_or_insert_with(Vec::new).push(raw.to_string());
+            if scanner.depth == 0 && !scanner.in_raw_string {
+                layout.blocks.extend(block.take());
+            }
+            continue;
+        }
+
+        let text = raw.trim();
+        let depth = scanner.indent_depth(text);
+        scanner.scan(text, line_no)?;
+
+        // Continuation of a multi-line statement
+        if let Some(lines) = block.as_mut() {
+            if depth > 0 || scanner.depth > 0 || text.starts_with(['}', ']', ')']) {
+                if !text.is_empty() || lines.last().is_some_and(|l| !l.is_empty()) {
+                    lines.push(indent(depth, text));
+                }
+                if scanner.depth == 0 && !scanner.in_raw_string {
+                    layout.blocks.extend(block.take());
+                }
+                continue;
+            }
+            layout.blocks.extend(block.take());
+        }
+
+        if text.is_empty() {
+            // A blank line detaches comments from what follows
+            if !comments.is_empty() {
+                layout.blocks.push(std::mem::take(&mut comments));
+            }
+        } else if text.starts_with('#') {
+            comments.push(text.to_string());
+        } else if is_keyword(text, "package") {
+            if layout.package.is_some() {
+                return Err(FormatterError::parse_failed(
+                    line_no,
+                    "multiple package declarations",
+                ));
+            }
+            layout.header.append(&mut comments);
+            layout.package = Some(collapse_spaces(text));
+        } else if is_keyword(text, "import") {
+            layout
+                .imports
+                .push((collapse_spaces(text), std::mem::take(&mut comments)));
+        } else {
+            let mut lines = std::mem::take(&mut comments);
+            lines.push(normalize_default(text));
+            if scanner.depth > 0 || scanner.in_raw_string {
+                block = Some(lines);
+            } else {
+                layout.blocks.push(lines);
+            }
+        }
+    }
+
+    if scanner.in_raw_string {
+        return Err(FormatterError::parse_failed(
+            source.lines().count(),
+            "unterminated raw string",
+        ));
+    }
+    if scanner.depth > 0 {
+        return Err(FormatterError::parse_failed(
+            source.lines().count(),
+            "unclosed bracket",
+        ));
+    }
+    if !comments.is_empty() {
+        layout.blocks.push(comments);
+    }
+
+    layout.render()
+}
+
+/// The top-level structure of a policy.
+#[derive(Debug, Default)]
+struct Layout {
+    /// Comments before the package declaration.
+    header: Vec<String>,
+    package: Option<String>,
+    /// Imports with the comments directly above them.
+    imports: Vec<(String, Vec<String>)>,
+    /// Top-level statements and standalone comment blocks.
+    blocks: Vec<Vec<String>>,
+}
+
+impl Layout {
+    fn render(mut self) -> Result<String, FormatterError> {
+        let package = self
+            .package
+            .ok_or_else(|| FormatterError::parse_failed(1, "missing package declaration"))?;
+
+        self.imports.sort_by(|a, b| a.0.cmp(&b.0));
+        self.imports.dedup_by(|a, b| a.0 == b.0);
+
+        let mut sections: Vec<Vec<String>> = Vec::new();
+        let mut head = self.header;
+        head.push(package);
+        sections.push(head);
+
+        if !self.imports.is_empty() {
+            sections.push(
+                self.imports
+                    .into_iter()
+                    .flat_map(|(import, comments)| comments.into_iter().chain([import]))
+                    .collect(),
+            );
+        }
+        sections.extend(self.blocks.into_iter().map(|mut block| {
+            while block.last().is_some_and(String::is_empty) {
+                block.pop();
+            }
+            block
+        }));
+
+        let mut out = sections
+            .iter()
+            .map(|section| section.join("\n"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        out.push('\n');
+        Ok(out)
+    }
+}
+
+/// Tracks bracket depth and string state across lines.
+#[derive(Debug, Default)]
+struct Scanner {
+    depth: usize,
+    in_raw_string: bool,
+}
+
+impl Scanner {
+    /// Returns the indentation level of a trimmed line: the current depth,
+    /// less any closing brackets the line starts with.
+    fn indent_depth(&self, text: &str) -> usize {
+        let closers = text
+            .chars()
+            .take_while(|c| matches!(c, '}' | ']' | ')'))
+            .count();
+        self.depth.saturating_sub(closers)
+    }
+
+    /// Updates the bracket depth with the brackets on a line, ignoring
+    /// strings and comments.
+    fn scan(&mut self, text: &str, line_no: usize) -> Result<(), FormatterError> {
+        let mut chars = text.chars();
+        let mut in_string = false;
+
+        while let Some(c) = chars.next() {
+            if self.in_raw_string {
+                self.in_raw_string = c != '`';
+                continue;
+            }
+            if in_string {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => in_string = true,
+                '`' => self.in_raw_string = true,
+                '#' => break,
+                '{' | '[' | '(' => self.depth += 1,
+                '}' | ']' | ')' => {
+                    self.depth = self.depth.checked_sub(1).ok_or_else(|| {
+                        FormatterError::parse_failed(line_no, format!("unmatched '{c}'"))
+                    })?;
+                }
+                _ => {}
+            }
+        }
+
+        if in_string {
+            return Err(FormatterError::parse_failed(line_no, "unterminated string"));
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if `text` starts with `keyword` followed by whitespace.
+fn is_keyword(text: &str, keyword: &str) -> bool {
+    text.strip_prefix(keyword)
+        .is_some_and(|rest| rest.starts_with(char::is_whitespace))
+}
+
+/// Indents a trimmed line to the given depth.
+fn indent(depth: usize, text: &str) -> String {
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("{}{text}", INDENT.repeat(depth))
+    }
+}
+
+/// Collapses runs of whitespace into single spaces.
+fn collapse_spaces(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Rewrites `default <rule> = <value>` as `default <rule> := <value>`.
+fn normalize_default(text: &str) -> String {
+    let Some(rest) = text
+        .strip_prefix("default")
+        .filter(|r| r.starts_with(char::is_whitespace))
+    else {
+        return text.to_string();
+    };
+    if rest.contains(":=") {
+        return text.to_string();
+    }
+    match rest.split_once('=') {
+        Some((name, value)) if !value.starts_with('=') => {
+            format!("default {} := {}", name.trim(), value.trim())
+        }
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSY: &str = r#"# Authorization policy
+package   authz
+
+import future.keywords.in
+# keywords
+import future.keywords.if
+import data.common.roles
+import future.keywords.in
+default allow = false
+default reason="denied"
+allow if {
+  roles.is_admin
+	input.method in ["GET", "HEAD"]
+
+
+  # owners may write
+  input.caller.id == input.resource.owner
+}
+deny contains msg if {
+        some x in input.items
+        x.blocked
+        msg := sprintf("blocked: %v", [x.id])
+}
+
+
+
+# trailing note
+"#;
+
+    const FORMATTED: &str = r#"# Authorization policy
+package authz
+
+import data.common.roles
+# keywords
+import future.keywords.if
+import future.keywords.in
+
+default allow := false
+
+default reason := "denied"
+
+allow if {
+    roles.is_admin
+    input.method in ["GET", "HEAD"]
+
+    # owners may write
+    input.caller.id == input.resource.owner
+}
+
+deny contains msg if {
+    some x in input.items
+    x.blocked
+    msg := sprintf("blocked: %v", [x.id])
+}
+
+# trailing note
+"#;
+
+    #[test]
+    fn test_format_rego() {
+        assert_eq!(format_rego(MESSY).unwrap(), FORMATTED);
+    }
+
+    #[test]
+    fn test_format_rego_is_idempotent() {
+        assert_eq!(format_rego(FORMATTED).unwrap(), FORMATTED);
+
+        let once = format_rego(MESSY).unwrap();
+        assert_eq!(format_rego(&once).unwrap(), once);
+    }
+
+    #[test]
+    fn test_imports_sorted() {
+        let source = "package x\n\nimport rego.v1\nimport data.b\nimport data.a as alias\n";
+        assert_eq!(
+            format_rego(source).unwrap(),
+            "package x\n\nimport data.a as alias\nimport data.b\nimport rego.v1\n"
+        );
+    }
+
+    #[test]
+    fn test_package_moved_first() {
+        let source = "import rego.v1\n\npackage x\n\nallow := true\n";
+        assert_eq!(
+            format_rego(source).unwrap(),
+            "package x\n\nimport rego.v1\n\nallow := true\n"
+        );
+    }
+
+    #[test]
+    fn test_multiline_values_and_strings() {
+        let source = "package x\n\nroles := {\n\"admin\": [\"read\",\n\"write\"],\n}\n\n\
+                      msg := \"{ not a bracket\"\n\ndoc := `\n  raw { text\n`\n";
+        let formatted = format_rego(source).unwrap();
+
+        assert_eq!(
+            formatted,
+            "package x\n\nroles := {\n    \"admin\": [\"read\",\n        \"write\"],\n}\n\n\
+             msg := \"{ not a bracket\"\n\ndoc := `\n  raw { text\n`\n"
+        );
+        assert_eq!(format_rego(&formatted).unwrap(), formatted);
+    }
+
+    #[test]
+    fn test_normalize_default() {
+        assert_eq!(
+            normalize_default("default allow = false"),
+            "default allow := false"
+        );
+        assert_eq!(
+            normalize_default("default allow := false"),
+            "default allow := false"
+        );
+        assert_eq!(
+            normalize_default("default_roles = []"),
+            "default_roles = []"
+        );
+    }
+
+    #[test]
+    fn test_parse_failed() {
+        assert!(matches!(
+            format_rego("allow := true\n"),
+            Err(FormatterError::ParseFailed { .. })
+        ));
+        assert_eq!(
+            format_rego("package x\n\nallow if {\n    true\n"),
+            Err(FormatterError::parse_failed(4, "unclosed bracket"))
+        );
+        assert_eq!(
+            format_rego("package x\n\nallow if }\n"),
+            Err(FormatterError::parse_failed(3, "unmatched '}'"))
+        );
+        assert_eq!(
+            format_rego("package x\npackage y\n"),
+            Err(FormatterError::parse_failed(
+                2,
+                "multiple package declarations"
+            ))
+        );
+        assert!(format_rego("package x\n\nmsg := \"open\n").is_err());
+    }
+}
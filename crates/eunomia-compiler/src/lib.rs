@@ -5,10 +5,13 @@
 //! This crate provides functionality for:
 //!
 //! - Parsing Rego policy files
-//! - Real OPA/Rego evaluation using `regorus`
+//! - Real OPA/Rego evaluation using `regorus`, with evaluation traces
 //! - Static analysis and validation
 //! - Semantic validation with mock contracts
+//! - Cross-policy reference validation
 //! - Linting with configurable rules
+//! - SARIF output for validation findings
+//! - Source formatting
 //! - Bundle compilation
 //! - Bundle optimization
 //!
@@ -50,23 +53,38 @@ pub mod analyzer;
 pub mod bundler;
 pub mod engine;
 pub mod error;
+pub mod fmt;
 pub mod lint;
 pub mod optimizer;
 pub mod parser;
+pub mod references;
+pub mod sarif;
 pub mod semantic;
+pub mod trace;
 pub mod validator;
 
-pub use analyzer::Analyzer;
-pub use bundler::Bundler;
-pub use engine::{EvalResult, PolicyInfo, RegoEngine, TestRule};
+pub use analyzer::{bundle_diff, source_diff, AnalysisError, Analyzer, BundleRuleDiff, SourceDiff};
+pub use bundler::{Bundler, CompilationCache};
+pub use engine::{
+    CoverageReport, EvalResult, PolicyCoverage, PolicyInfo, RegoEngine, RuleCoverage, TestRule,
+};
 pub use error::{CompilerError, Result};
-pub use lint::{LintRule, LintViolation, Linter, RuleCategory, Severity};
+pub use fmt::{format_rego, FormatterError};
+pub use lint::{
+    DefaultDenyRule, ExplicitImportsRule, LintConfig, LintReport, LintRule, LintThresholds,
+    LintViolation, Linter, NoHardcodedSecretsRule, NoHardcodedUserIdRule, NoWildcardAllowRule,
+    RuleCategory, RuleInfo, Severity,
+};
 pub use parser::Parser;
+pub use references::ReferenceValidator;
+pub use sarif::SarifLog;
 pub use semantic::{
     InputSchema, MockServiceContract, SemanticCategory, SemanticIssue, SemanticSeverity,
-    SemanticValidator,
+    SemanticValidator, ServiceContract,
 };
+pub use trace::{EvalTrace, RuleTrace, TraceLine};
 pub use validator::{
-    validate_file, validate_source, IssueCategory, IssueSeverity, PolicyValidator, ValidationIssue,
-    ValidationReport, ValidatorConfig,
+    validate_file, validate_source, CompatibilityIssue, IssueCategory, IssueSeverity,
+    PolicyValidator, RegoV1CompatibilityChecker, RegoVersion, ValidationIssue, ValidationReport,
+    ValidatorConfig,
 };
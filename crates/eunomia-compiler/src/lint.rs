@@ -2,17 +2,56 @@
 //!
 //! This module provides linting rules for static analysis of Rego policies.
 //! It detects common security issues, best practice violations, and potential bugs.
+//!
+//! # Suppressing violations
+//!
+//! A comment of the form `# eunomia-lint: disable=rule-id` suppresses the
+//! listed rules (comma-separated) on its own line and on the line that
+//! follows it. `# eunomia-lint: disable-file=rule-id` suppresses them for the
+//! whole file. Suppressed violations are still reported separately by
+//! [`Linter::lint_report`].
+//!
+//! # Configuration
+//!
+//! A [`LintConfig`] is usually read from a `.eunomia-lint.toml` file in the
+//! policy directory root:
+//!
+//! ```toml
+//! enabled = ["style/package-naming"]
+//! disabled = ["style/explicit-imports"]
+//!
+//! [severity]
+//! "security/no-wildcard-allow" = "error"
+//!
+//! [thresholds]
+//! rule_body_lines = 20
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::error::{CompilerError, Result};
+
+/// File name of the project-level lint configuration.
+pub const LINT_CONFIG_FILE: &str = ".eunomia-lint.toml";
+
+/// Marker that starts a suppression comment.
+const SUPPRESSION_MARKER: &str = "eunomia-lint:";
+
 /// Severity level for lint violations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Severity {
     /// Informational hint.
+    #[serde(alias = "hint")]
     Hint,
     /// Warning - may indicate a problem.
+    #[serde(alias = "warning")]
     Warning,
     /// Error - definitely a problem.
+    #[serde(alias = "error")]
     Error,
 }
 
@@ -28,9 +67,24 @@ impl Severity {
     }
 }
 
-/// A lint rule that can be applied to policy source code.
+/// A lint check that can be registered with a [`Linter`].
+///
+/// Built-in rules implement this trait, and custom rules can be added with
+/// [`Linter::register`].
+pub trait LintRule: Send + Sync {
+    /// Unique identifier, e.g. `security/default-deny`.
+    fn id(&self) -> &'static str;
+
+    /// Category of the rule.
+    fn category(&self) -> RuleCategory;
+
+    /// Checks a policy source and returns any violations.
+    fn check(&self, source: &str, file: &str) -> Vec<LintViolation>;
+}
+
+/// Descriptive metadata for a built-in lint rule.
 #[derive(Debug, Clone)]
-pub struct LintRule {
+pub struct RuleInfo {
     /// Unique identifier for the rule.
     pub id: &'static str,
     /// Human-readable name.
@@ -122,7 +176,7 @@ impl LintViolation {
 
 // Built-in lint rules
 /// Rule: Policies should default to deny.
-pub const RULE_DEFAULT_DENY: LintRule = LintRule {
+pub const RULE_DEFAULT_DENY: RuleInfo = RuleInfo {
     id: "security/default-deny",
     name: "Default Deny",
     description: "Policies should explicitly default to deny for security",
@@ -132,7 +186,7 @@ pub const RULE_DEFAULT_DENY: LintRule = LintRule {
 };
 
 /// Rule: Avoid hardcoded secrets.
-pub const RULE_NO_HARDCODED_SECRETS: LintRule = LintRule {
+pub const RULE_NO_HARDCODED_SECRETS: RuleInfo = RuleInfo {
     id: "security/no-hardcoded-secrets",
     name: "No Hardcoded Secrets",
     description: "Policy should not contain hardcoded secrets or credentials",
@@ -142,7 +196,7 @@ pub const RULE_NO_HARDCODED_SECRETS: LintRule = LintRule {
 };
 
 /// Rule: Use explicit imports.
-pub const RULE_EXPLICIT_IMPORTS: LintRule = LintRule {
+pub const RULE_EXPLICIT_IMPORTS: RuleInfo = RuleInfo {
     id: "style/explicit-imports",
     name: "Explicit Imports",
     description: "Use explicit imports for future.keywords",
@@ -152,7 +206,7 @@ pub const RULE_EXPLICIT_IMPORTS: LintRule = LintRule {
 };
 
 /// Rule: Avoid overly permissive rules.
-pub const RULE_NO_WILDCARD_ALLOW: LintRule = LintRule {
+pub const RULE_NO_WILDCARD_ALLOW: RuleInfo = RuleInfo {
     id: "security/no-wildcard-allow",
     name: "No Wildcard Allow",
     description: "Avoid allow rules that match everything without conditions",
@@ -162,7 +216,7 @@ pub const RULE_NO_WILDCARD_ALLOW: LintRule = LintRule {
 };
 
 /// Rule: Policy packages should follow naming conventions.
-pub const RULE_PACKAGE_NAMING: LintRule = LintRule {
+pub const RULE_PACKAGE_NAMING: RuleInfo = RuleInfo {
     id: "style/package-naming",
     name: "Package Naming",
     description: "Package names should follow the service.module convention",
@@ -172,7 +226,7 @@ pub const RULE_PACKAGE_NAMING: LintRule = LintRule {
 };
 
 /// Rule: Test files should test all rules.
-pub const RULE_TEST_COVERAGE: LintRule = LintRule {
+pub const RULE_TEST_COVERAGE: RuleInfo = RuleInfo {
     id: "best-practice/test-coverage",
     name: "Test Coverage",
     description: "All allow/deny rules should have corresponding tests",
@@ -182,7 +236,7 @@ pub const RULE_TEST_COVERAGE: LintRule = LintRule {
 };
 
 /// The default set of lint rules.
-pub static DEFAULT_RULES: &[&LintRule] = &[
+pub static DEFAULT_RULES: &[&RuleInfo] = &[
     &RULE_DEFAULT_DENY,
     &RULE_NO_HARDCODED_SECRETS,
     &RULE_EXPLICIT_IMPORTS,
@@ -191,13 +245,183 @@ pub static DEFAULT_RULES: &[&LintRule] = &[
     &RULE_TEST_COVERAGE,
 ];
 
-/// A linter for Rego policies.
+/// Tunable limits used by lint rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintThresholds {
+    /// Number of lines after a rule head searched for its body's conditions.
+    pub rule_body_lines: usize,
+}
+
+impl Default for LintThresholds {
+    fn default() -> Self {
+        Self {
+            rule_body_lines: 10,
+        }
+    }
+}
+
+/// Project-level lint configuration, usually read from [`LINT_CONFIG_FILE`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// Rule IDs to enable in addition to the defaults.
+    pub enabled: Vec<String>,
+    /// Rule IDs to disable.
+    pub disabled: Vec<String>,
+    /// Severity overrides by rule ID.
+    pub severity: HashMap<String, Severity>,
+    /// Rule thresholds.
+    pub thresholds: LintThresholds,
+}
+
+impl LintConfig {
+    /// Parses a configuration from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is invalid or has unknown keys.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content).map_err(|e| CompilerError::LintConfigError {
+            message: e.to_string(),
+        })
+    }
+
+    /// Loads a configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is invalid.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| CompilerError::FileReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::from_toml(&content).map_err(|e| CompilerError::LintConfigError {
+            message: format!("{}: {e}", path.display()),
+        })
+    }
+
+    /// Finds and loads the nearest [`LINT_CONFIG_FILE`] in `start` or one of
+    /// its ancestors. `start` may be a file, in which case the search begins
+    /// in its directory.
+    ///
+    /// Returns `None` if no configuration file exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configuration file is found but is invalid.
+    pub fn discover(start: impl AsRef<Path>) -> Result<Option<(PathBuf, Self)>> {
+        let start = start.as_ref();
+        let dir = if start.is_file() {
+            start.parent().unwrap_or(start)
+        } else {
+            start
+        };
+
+        for ancestor in dir.ancestors() {
+            let candidate = ancestor.join(LINT_CONFIG_FILE);
+            if candidate.is_file() {
+                let config = Self::load(&candidate)?;
+                return Ok(Some((candidate, config)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Violations found in a file, split by whether a suppression comment
+/// silenced them.
+#[derive(Debug, Clone, Default)]
+pub struct LintReport {
+    /// Violations to report.
+    pub violations: Vec<LintViolation>,
+    /// Violations silenced by `# eunomia-lint:` comments.
+    pub suppressed: Vec<LintViolation>,
+}
+
+/// Suppression comments found in a file.
 #[derive(Debug, Default)]
+struct Suppressions {
+    /// Rules disabled for the whole file.
+    file: HashSet<String>,
+    /// Rules disabled per line (1-based).
+    lines: HashMap<usize, HashSet<String>>,
+}
+
+impl Suppressions {
+    fn parse(source: &str) -> Self {
+        let mut suppressions = Self::default();
+
+        for (line_num, line) in source.lines().enumerate() {
+            let Some(directive) = line
+                .match_indices('#')
+                .map(|(idx, _)| line[idx + 1..].trim_start())
+                .find_map(|comment| comment.strip_prefix(SUPPRESSION_MARKER))
+            else {
+                continue;
+            };
+
+            let directive = directive.trim();
+            let rule_ids = |list: &str| {
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            };
+
+            if let Some(list) = directive.strip_prefix("disable-file=") {
+                suppressions.file.extend(rule_ids(list));
+            } else if let Some(list) = directive.strip_prefix("disable=") {
+                let ids = rule_ids(list);
+                for target in [line_num + 1, line_num + 2] {
+                    suppressions
+                        .lines
+                        .entry(target)
+                        .or_default()
+                        .extend(ids.iter().cloned());
+                }
+            }
+        }
+
+        suppressions
+    }
+
+    fn suppresses(&self, violation: &LintViolation) -> bool {
+        self.file.contains(violation.rule_id)
+            || violation
+                .line
+                .and_then(|line| self.lines.get(&line))
+                .is_some_and(|ids| ids.contains(violation.rule_id))
+    }
+}
+
+/// A linter for Rego policies.
+#[derive(Default)]
 pub struct Linter {
+    /// Registered rules, in the order they run.
+    rules: Vec<Box<dyn LintRule>>,
     /// Enabled rule IDs.
     enabled_rules: Vec<&'static str>,
     /// Disabled rule IDs.
     disabled_rules: Vec<&'static str>,
+    /// Severity overrides by rule ID.
+    severity_overrides: HashMap<&'static str, Severity>,
+}
+
+impl fmt::Debug for Linter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rules: Vec<_> = self.rules.iter().map(|rule| rule.id()).collect();
+        f.debug_struct("Linter")
+            .field("rules", &rules)
+            .field("enabled_rules", &self.enabled_rules)
+            .field("disabled_rules", &self.disabled_rules)
+            .field("severity_overrides", &self.severity_overrides)
+            .finish()
+    }
 }
 
 impl Linter {
@@ -211,11 +435,76 @@ impl Linter {
             .collect();
 
         Self {
+            rules: vec![
+                Box::new(DefaultDenyRule),
+                Box::new(NoHardcodedSecretsRule),
+                Box::new(ExplicitImportsRule),
+                Box::new(NoWildcardAllowRule::default()),
+            ],
             enabled_rules,
             disabled_rules: Vec::new(),
+            severity_overrides: HashMap::new(),
+        }
+    }
+
+    /// Creates a linter with the default rules adjusted by `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration names an unknown rule.
+    pub fn from_config(config: &LintConfig) -> Result<Self> {
+        let mut linter = Self::new();
+        linter.register(Box::new(NoWildcardAllowRule::new(
+            config.thresholds.rule_body_lines,
+        )));
+
+        for rule_id in &config.enabled {
+            linter.enable_rule(linter.known_rule_id(rule_id)?);
+        }
+        for rule_id in &config.disabled {
+            linter.disable_rule(linter.known_rule_id(rule_id)?);
+        }
+        for (rule_id, severity) in &config.severity {
+            let rule_id = linter.known_rule_id(rule_id)?;
+            linter.severity_overrides.insert(rule_id, *severity);
+        }
+
+        Ok(linter)
+    }
+
+    /// Creates a linter from the nearest [`LINT_CONFIG_FILE`] above `start`,
+    /// or with the default rules if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configuration file is found but is invalid.
+    pub fn discover(start: impl AsRef<Path>) -> Result<Self> {
+        match LintConfig::discover(start)? {
+            Some((_, config)) => Self::from_config(&config),
+            None => Ok(Self::new()),
         }
     }
 
+    /// Registers a rule and enables it.
+    ///
+    /// A previously registered rule with the same ID is replaced.
+    pub fn register(&mut self, rule: Box<dyn LintRule>) {
+        let rule_id = rule.id();
+        if let Some(existing) = self.rules.iter_mut().find(|r| r.id() == rule_id) {
+            *existing = rule;
+        } else {
+            self.rules.push(rule);
+        }
+        self.enable_rule(rule_id);
+    }
+
+    /// Removes a registered rule, returning it if it was present.
+    pub fn unregister(&mut self, rule_id: &'static str) -> Option<Box<dyn LintRule>> {
+        self.enabled_rules.retain(|&id| id != rule_id);
+        let index = self.rules.iter().position(|r| r.id() == rule_id)?;
+        Some(self.rules.remove(index))
+    }
+
     /// Enables a rule by ID.
     pub fn enable_rule(&mut self, rule_id: &'static str) {
         if !self.enabled_rules.contains(&rule_id) {
@@ -239,30 +528,70 @@ impl Linter {
     }
 
     /// Lints a policy source and returns violations.
+    ///
+    /// Violations silenced by suppression comments are omitted; use
+    /// [`lint_report`](Self::lint_report) to see them.
     #[must_use]
     pub fn lint(&self, source: &str, file_name: &str) -> Vec<LintViolation> {
-        let mut violations = Vec::new();
+        self.lint_report(source, file_name).violations
+    }
 
-        if self.is_rule_enabled("security/default-deny") {
-            violations.extend(Self::check_default_deny(source));
-        }
+    /// Lints a policy source, separating violations silenced by suppression
+    /// comments from the rest.
+    #[must_use]
+    pub fn lint_report(&self, source: &str, file_name: &str) -> LintReport {
+        let suppressions = Suppressions::parse(source);
+        let mut report = LintReport::default();
 
-        if self.is_rule_enabled("security/no-hardcoded-secrets") {
-            violations.extend(Self::check_no_hardcoded_secrets(source, file_name));
+        for mut violation in self.check_all(source, file_name) {
+            if let Some(severity) = self.severity_overrides.get(violation.rule_id) {
+                violation.severity = *severity;
+            }
+            if suppressions.suppresses(&violation) {
+                report.suppressed.push(violation);
+            } else {
+                report.violations.push(violation);
+            }
         }
 
-        if self.is_rule_enabled("style/explicit-imports") {
-            violations.extend(Self::check_explicit_imports(source));
-        }
+        report
+    }
 
-        if self.is_rule_enabled("security/no-wildcard-allow") {
-            violations.extend(Self::check_no_wildcard_allow(source));
-        }
+    fn check_all(&self, source: &str, file_name: &str) -> Vec<LintViolation> {
+        self.rules
+            .iter()
+            .filter(|rule| self.is_rule_enabled(rule.id()))
+            .flat_map(|rule| rule.check(source, file_name))
+            .collect()
+    }
 
-        violations
+    /// Returns the static ID of a registered or built-in rule equal to `rule_id`.
+    fn known_rule_id(&self, rule_id: &str) -> Result<&'static str> {
+        self.rules
+            .iter()
+            .map(|rule| rule.id())
+            .chain(DEFAULT_RULES.iter().map(|rule| rule.id))
+            .find(|id| *id == rule_id)
+            .ok_or_else(|| CompilerError::LintConfigError {
+                message: format!("unknown lint rule '{rule_id}'"),
+            })
     }
+}
+
+/// Checks that a policy defaults to deny.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultDenyRule;
 
-    fn check_default_deny(source: &str) -> Vec<LintViolation> {
+impl LintRule for DefaultDenyRule {
+    fn id(&self) -> &'static str {
+        RULE_DEFAULT_DENY.id
+    }
+
+    fn category(&self) -> RuleCategory {
+        RULE_DEFAULT_DENY.category
+    }
+
+    fn check(&self, source: &str, _file: &str) -> Vec<LintViolation> {
         let has_default = source.lines().any(|line| {
             let trimmed = line.trim();
             trimmed.starts_with("default allow")
@@ -305,8 +634,22 @@ impl Linter {
 
         violations
     }
+}
+
+/// Flags string assignments that look like secrets.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoHardcodedSecretsRule;
+
+impl LintRule for NoHardcodedSecretsRule {
+    fn id(&self) -> &'static str {
+        RULE_NO_HARDCODED_SECRETS.id
+    }
+
+    fn category(&self) -> RuleCategory {
+        RULE_NO_HARDCODED_SECRETS.category
+    }
 
-    fn check_no_hardcoded_secrets(source: &str, file_name: &str) -> Vec<LintViolation> {
+    fn check(&self, source: &str, file_name: &str) -> Vec<LintViolation> {
         // Patterns that might indicate hardcoded secrets
         const SECRET_PATTERNS: &[&str] = &[
             "password",
@@ -354,9 +697,23 @@ impl Linter {
 
         violations
     }
+}
+
+/// Requires `future.keywords` imports when future keywords are used.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExplicitImportsRule;
+
+impl LintRule for ExplicitImportsRule {
+    fn id(&self) -> &'static str {
+        RULE_EXPLICIT_IMPORTS.id
+    }
+
+    fn category(&self) -> RuleCategory {
+        RULE_EXPLICIT_IMPORTS.category
+    }
 
     #[allow(clippy::similar_names)]
-    fn check_explicit_imports(source: &str) -> Vec<LintViolation> {
+    fn check(&self, source: &str, _file: &str) -> Vec<LintViolation> {
         let mut violations = Vec::new();
 
         // Check for usage of future.keywords without explicit import
@@ -385,8 +742,40 @@ impl Linter {
 
         violations
     }
+}
+
+/// Flags allow rules that grant access without inspecting the input.
+#[derive(Debug, Clone, Copy)]
+pub struct NoWildcardAllowRule {
+    rule_body_lines: usize,
+}
+
+impl NoWildcardAllowRule {
+    /// Creates the rule, searching `rule_body_lines` lines after each rule
+    /// head for conditions.
+    #[must_use]
+    pub const fn new(rule_body_lines: usize) -> Self {
+        Self { rule_body_lines }
+    }
+}
+
+impl Default for NoWildcardAllowRule {
+    fn default() -> Self {
+        Self::new(LintThresholds::default().rule_body_lines)
+    }
+}
+
+impl LintRule for NoWildcardAllowRule {
+    fn id(&self) -> &'static str {
+        RULE_NO_WILDCARD_ALLOW.id
+    }
 
-    fn check_no_wildcard_allow(source: &str) -> Vec<LintViolation> {
+    fn category(&self) -> RuleCategory {
+        RULE_NO_WILDCARD_ALLOW.category
+    }
+
+    fn check(&self, source: &str, _file: &str) -> Vec<LintViolation> {
+        let rule_body_lines = self.rule_body_lines;
         let mut violations = Vec::new();
 
         for (line_num, line) in source.lines().enumerate() {
@@ -399,7 +788,7 @@ impl Linter {
                 let has_input_check = source
                     .lines()
                     .skip(line_num)
-                    .take(10) // Look at next 10 lines for the rule body
+                    .take(rule_body_lines) // Look at the following lines for the rule body
                     .any(|l| l.contains("input.") || l.contains("data."));
 
                 if !has_input_check
@@ -422,6 +811,97 @@ impl Linter {
     }
 }
 
+/// Example custom rule that flags comparisons against literal user IDs.
+///
+/// Hardcoding a user ID (`input.caller.user_id == "u-1234"`) grants access to
+/// one specific principal and usually means a role or data lookup is
+/// missing. The rule is not registered by default; add it with
+/// [`Linter::register`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoHardcodedUserIdRule;
+
+impl NoHardcodedUserIdRule {
+    /// Rule identifier.
+    pub const ID: &'static str = "security/no-hardcoded-user-id";
+
+    /// Field names that hold a user identifier.
+    const USER_ID_FIELDS: &'static [&'static str] = &[
+        "user_id",
+        "userid",
+        "uid",
+        "sub",
+        "subject_id",
+        "principal_id",
+        "caller_id",
+    ];
+
+    /// Parents whose `id` field is a user identifier.
+    const USER_PARENTS: &'static [&'static str] = &["user", "caller", "principal", "subject"];
+
+    fn is_user_id_reference(expr: &str) -> bool {
+        let mut segments = expr.rsplit('.').map(str::trim);
+        let Some(field) = segments.next() else {
+            return false;
+        };
+        let field = field.to_lowercase();
+
+        Self::USER_ID_FIELDS.contains(&field.as_str())
+            || (field == "id"
+                && segments
+                    .next()
+                    .is_some_and(|parent| Self::USER_PARENTS.contains(&parent)))
+    }
+
+    fn is_string_literal(expr: &str) -> bool {
+        expr.len() >= 2 && expr.starts_with('"') && expr.ends_with('"')
+    }
+}
+
+impl LintRule for NoHardcodedUserIdRule {
+    fn id(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    fn check(&self, source: &str, _file: &str) -> Vec<LintViolation> {
+        let mut violations = Vec::new();
+
+        for (line_num, line) in source.lines().enumerate() {
+            let code = line.split('#').next().unwrap_or_default();
+
+            for op in ["==", "!="] {
+                let Some((lhs, rhs)) = code.split_once(op) else {
+                    continue;
+                };
+                let (lhs, rhs) = (lhs.trim(), rhs.trim());
+
+                let literal = if Self::is_user_id_reference(lhs) && Self::is_string_literal(rhs) {
+                    rhs
+                } else if Self::is_user_id_reference(rhs) && Self::is_string_literal(lhs) {
+                    lhs
+                } else {
+                    continue;
+                };
+
+                violations.push(
+                    LintViolation::new(
+                        Self::ID,
+                        Severity::Warning,
+                        format!("Comparison against hardcoded user ID {literal}"),
+                    )
+                    .at_line(line_num + 1)
+                    .with_suggestion("Check a role or look the user up in data instead"),
+                );
+            }
+        }
+
+        violations
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -601,4 +1081,227 @@ allow := true
         assert!(Severity::Error > Severity::Warning);
         assert!(Severity::Warning > Severity::Hint);
     }
+
+    const WILDCARD_POLICY: &str = r#"
+package test.authz
+
+import future.keywords.if
+
+default allow := false
+
+allow := true
+"#;
+
+    #[test]
+    fn test_suppress_next_line() {
+        let source = WILDCARD_POLICY.replace(
+            "allow := true",
+            "# eunomia-lint: disable=security/no-wildcard-allow\nallow := true",
+        );
+
+        let report = Linter::new().lint_report(&source, "test.rego");
+
+        assert!(report.violations.is_empty());
+        assert_eq!(report.suppressed.len(), 1);
+        assert_eq!(report.suppressed[0].rule_id, "security/no-wildcard-allow");
+    }
+
+    #[test]
+    fn test_suppress_same_line() {
+        let source = WILDCARD_POLICY.replace(
+            "allow := true",
+            concat!(
+                "allow := true # eunomia-lint: ",
+                "disable=style/explicit-imports, security/no-wildcard-allow"
+            ),
+        );
+
+        assert!(Linter::new().lint(&source, "test.rego").is_empty());
+    }
+
+    #[test]
+    fn test_suppression_only_covers_listed_rule_and_line() {
+        let source = WILDCARD_POLICY.replace(
+            "allow := true",
+            "# eunomia-lint: disable=security/default-deny\n\nallow := true",
+        );
+
+        let report = Linter::new().lint_report(&source, "test.rego");
+
+        assert_eq!(report.violations.len(), 1);
+        assert!(report.suppressed.is_empty());
+    }
+
+    #[test]
+    fn test_suppress_whole_file() {
+        let source = r#"
+# eunomia-lint: disable-file=security/default-deny
+package test.authz
+
+allow if {
+    input.caller.role == "admin"
+}
+"#;
+
+        let report = Linter::new().lint_report(source, "test.rego");
+
+        assert!(!report
+            .violations
+            .iter()
+            .any(|v| v.rule_id == "security/default-deny"));
+        assert_eq!(report.suppressed.len(), 1);
+    }
+
+    #[test]
+    fn test_lint_config_from_toml() {
+        let config = LintConfig::from_toml(
+            r#"
+enabled = ["style/package-naming"]
+disabled = ["security/no-wildcard-allow"]
+
+[severity]
+"security/default-deny" = "warning"
+
+[thresholds]
+rule_body_lines = 3
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.severity["security/default-deny"], Severity::Warning);
+        assert_eq!(config.thresholds.rule_body_lines, 3);
+
+        let linter = Linter::from_config(&config).unwrap();
+        assert!(linter.is_rule_enabled("style/package-naming"));
+        assert!(!linter.is_rule_enabled("security/no-wildcard-allow"));
+
+        let violations = linter.lint("package test.authz\n", "test.rego");
+        let default_deny = violations
+            .iter()
+            .find(|v| v.rule_id == "security/default-deny")
+            .unwrap();
+        assert_eq!(default_deny.severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_lint_config_rejects_unknown_rules_and_keys() {
+        let config = LintConfig::from_toml(r#"disabled = ["no/such-rule"]"#).unwrap();
+        assert!(Linter::from_config(&config).is_err());
+
+        assert!(LintConfig::from_toml("unknown = 1").is_err());
+    }
+
+    #[test]
+    fn test_lint_config_discovery() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("policies").join("users");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert!(LintConfig::discover(&nested).unwrap().is_none());
+
+        std::fs::write(
+            dir.path().join(LINT_CONFIG_FILE),
+            r#"disabled = ["style/explicit-imports"]"#,
+        )
+        .unwrap();
+        let (path, config) = LintConfig::discover(&nested).unwrap().unwrap();
+        assert_eq!(path, dir.path().join(LINT_CONFIG_FILE));
+        assert_eq!(config.disabled, vec!["style/explicit-imports"]);
+
+        let linter = Linter::discover(&nested).unwrap();
+        assert!(!linter.is_rule_enabled("style/explicit-imports"));
+    }
+
+    #[derive(Debug)]
+    struct NoTodoRule;
+
+    impl LintRule for NoTodoRule {
+        fn id(&self) -> &'static str {
+            "style/no-todo"
+        }
+
+        fn category(&self) -> RuleCategory {
+            RuleCategory::Style
+        }
+
+        fn check(&self, source: &str, _file: &str) -> Vec<LintViolation> {
+            source
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| line.contains("TODO"))
+                .map(|(i, _)| {
+                    LintViolation::new(self.id(), Severity::Hint, "Unresolved TODO").at_line(i + 1)
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_register_custom_rule() {
+        let source = "package test.authz\n\ndefault allow := false\n# TODO: tighten\n";
+        let mut linter = Linter::new();
+        assert!(linter.lint(source, "test.rego").is_empty());
+
+        linter.register(Box::new(NoTodoRule));
+        assert!(linter.is_rule_enabled("style/no-todo"));
+
+        let violations = linter.lint(source, "test.rego");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_id, "style/no-todo");
+        assert_eq!(violations[0].line, Some(4));
+
+        assert!(linter.unregister("style/no-todo").is_some());
+        assert!(linter.lint(source, "test.rego").is_empty());
+        assert!(linter.unregister("style/no-todo").is_none());
+    }
+
+    #[test]
+    fn test_register_replaces_builtin_rule() {
+        let source =
+            "package test.authz\n\ndefault allow := false\n\nallow := true\n\n\n# input.x\n";
+        let mut linter = Linter::new();
+        assert!(linter.lint(source, "test.rego").is_empty());
+
+        linter.register(Box::new(NoWildcardAllowRule::new(2)));
+        let violations = linter.lint(source, "test.rego");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule_id, "security/no-wildcard-allow");
+    }
+
+    #[test]
+    fn test_no_hardcoded_user_id_rule() {
+        let source = r#"
+package test.authz
+
+import future.keywords.if
+
+default allow := false
+
+allow if {
+    input.caller.user_id == "u-1234"
+}
+
+allow if {
+    "u-5678" == input.caller.id
+}
+
+allow if {
+    input.caller.role == "admin"
+    input.resource.id == "doc-1"
+}
+"#;
+        let mut linter = Linter::new();
+        linter.register(Box::new(NoHardcodedUserIdRule));
+
+        let violations: Vec<_> = linter
+            .lint(source, "test.rego")
+            .into_iter()
+            .filter(|v| v.rule_id == NoHardcodedUserIdRule::ID)
+            .collect();
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].line, Some(9));
+        assert_eq!(violations[1].line, Some(13));
+        assert!(violations[0].message.contains("\"u-1234\""));
+    }
 }
@@ -0,0 +1,461 @@
+//! Cross-policy reference validation.
+//!
+//! [`SemanticValidator`](crate::SemanticValidator) looks at one file at a
+//! time, so it cannot tell whether `data.common.roles.is_admin` exists in
+//! another policy of the same bundle. This module checks every `data.*`
+//! import and reference across a whole set of policies and reports the ones
+//! that resolve to nothing: no package, rule, or data document in the bundle
+//! provides them and they are not under a declared external root.
+//!
+//! # Example
+//!
+//! ```rust
+//! use eunomia_compiler::ReferenceValidator;
+//! use eunomia_core::Policy;
+//!
+//! let roles = Policy::new(
+//!     "common.roles",
+//!     "package common.roles\n\nis_admin if { input.caller.roles[_] == \"admin\" }\n",
+//! );
+//! let authz = Policy::new(
+//!     "users_service.authz",
+//!     "package users_service.authz\n\nimport data.common.roles\n\nallow if { roles.is_admin }\n",
+//! );
+//!
+//! let issues = ReferenceValidator::new().validate(&[roles, authz], &[]);
+//! assert!(issues.is_empty());
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use eunomia_core::Policy;
+
+use crate::semantic::{SemanticCategory, SemanticIssue, SemanticSeverity};
+
+/// Validates `data.*` references across all policies of a bundle.
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceValidator {
+    /// Roots provided outside the bundle (e.g. `rate_limits`).
+    external_roots: Vec<Vec<String>>,
+}
+
+impl ReferenceValidator {
+    /// Creates a validator with no external roots.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            external_roots: Vec::new(),
+        }
+    }
+
+    /// Declares a data root provided outside the bundle.
+    ///
+    /// References under the root (with or without a leading `data.`) are
+    /// never reported.
+    #[must_use]
+    pub fn with_external_root(mut self, root: impl AsRef<str>) -> Self {
+        let segments = path_segments(root.as_ref().trim_start_matches("data."));
+        if !segments.is_empty() {
+            self.external_roots.push(segments);
+        }
+        self
+    }
+
+    /// Validates the references in `policies`.
+    ///
+    /// `data_files` are `(path, content)` pairs as added to the bundle; they
+    /// provide data documents at the path of their directory. Test policies
+    /// are skipped, as they are not part of the compiled bundle.
+    ///
+    /// Every unresolved reference is reported once per line as a
+    /// [`SemanticSeverity::Error`] with the policy's file and line.
+    #[must_use]
+    pub fn validate(
+        &self,
+        policies: &[Policy],
+        data_files: &[(String, String)],
+    ) -> Vec<SemanticIssue> {
+        let policies: Vec<&Policy> = policies.iter().filter(|p| !p.is_test()).collect();
+
+        let mut index = BundleIndex::default();
+        for policy in &policies {
+            index.add_policy(policy);
+        }
+        for (path, content) in data_files {
+            if !index.add_data_file(path, content) {
+                // A root document we cannot read may define anything.
+                return Vec::new();
+            }
+        }
+
+        let mut issues = Vec::new();
+        for policy in &policies {
+            let file = policy_file(policy);
+            for (line_num, line) in policy.source.lines().enumerate() {
+                let code = strip_strings_and_comments(line);
+                let mut seen = HashSet::new();
+
+                for reference in data_references(&code) {
+                    if !seen.insert(reference.clone()) {
+                        continue;
+                    }
+                    let segments = path_segments(&reference["data.".len()..]);
+                    if self.is_external(&segments) || index.resolves(&segments) {
+                        continue;
+                    }
+
+                    let kind = if code.trim_start().starts_with("import ") {
+                        "Import"
+                    } else {
+                        "Reference"
+                    };
+                    issues.push(SemanticIssue {
+                        severity: SemanticSeverity::Error,
+                        category: SemanticCategory::DataReference,
+                        message: format!(
+                            "{kind} '{reference}' does not resolve to anything in the bundle"
+                        ),
+                        file: Some(file.clone()),
+                        line: Some(line_num + 1),
+                        snippet: Some(line.trim().to_string()),
+                        suggestion: Some(
+                            "Define it in a bundled policy or data file, or declare its root \
+                             as external"
+                                .to_string(),
+                        ),
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    fn is_external(&self, segments: &[String]) -> bool {
+        self.external_roots
+            .iter()
+            .any(|root| segments.starts_with(root))
+    }
+}
+
+/// Packages, rules and data documents defined in a bundle.
+#[derive(Debug, Default)]
+struct BundleIndex {
+    /// Rules defined in each package.
+    packages: HashMap<Vec<String>, HashSet<String>>,
+    /// Paths of data documents.
+    data_roots: Vec<Vec<String>>,
+}
+
+impl BundleIndex {
+    fn add_policy(&mut self, policy: &Policy) {
+        let package = policy
+            .source
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("package "))
+            .map_or_else(|| policy.package_name.clone(), |p| p.trim().to_string());
+
+        let rules = self.packages.entry(path_segments(&package)).or_default();
+        rules.extend(policy.source.lines().filter_map(top_level_rule_name));
+    }
+
+    /// Registers a data file, returning `false` if it is a root document
+    /// whose keys cannot be determined.
+    fn add_data_file(&mut self, path: &str, content: &str) -> bool {
+        let path = Path::new(path);
+        let mut segments: Vec<String> = path
+            .parent()
+            .into_iter()
+            .flat_map(Path::components)
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .filter(|c| !c.is_empty() && c != ".")
+            .collect();
+
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string());
+        let is_data_document = matches!(file_name.as_deref(), Some("data.json" | "data.yaml"));
+        if !is_data_document {
+            if let Some(stem) = path.file_stem() {
+                segments.push(stem.to_string_lossy().to_string());
+            }
+        }
+
+        if !segments.is_empty() {
+            self.data_roots.push(segments);
+            return true;
+        }
+
+        // A root data.json contributes its top-level keys.
+        match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(serde_json::Value::Object(map)) => {
+                self.data_roots.extend(map.keys().map(|k| vec![k.clone()]));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if `data.<segments>` names something in the bundle.
+    fn resolves(&self, segments: &[String]) -> bool {
+        if segments.is_empty() {
+            return true;
+        }
+
+        if self
+            .data_roots
+            .iter()
+            .any(|root| segments.starts_with(root) || root.starts_with(segments))
+        {
+            return true;
+        }
+
+        self.packages.iter().any(|(package, rules)| {
+            if package.starts_with(segments) {
+                // The package itself or a namespace containing it
+                return true;
+            }
+            segments.starts_with(package) && rules.contains(&segments[package.len()])
+        })
+    }
+}
+
+/// Returns the name used to identify a policy in diagnostics.
+fn policy_file(policy: &Policy) -> String {
+    policy.file_path.as_ref().map_or_else(
+        || format!("{}.rego", policy.package_name),
+        |path| path.display().to_string(),
+    )
+}
+
+/// Splits a dotted path into segments.
+fn path_segments(path: &str) -> Vec<String> {
+    path.split('.')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Extracts the rule name from an unindented rule head.
+fn top_level_rule_name(line: &str) -> Option<String> {
+    if line.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let head = line.strip_prefix("default ").unwrap_or(line);
+    if head.starts_with("package ") || head.starts_with("import ") {
+        return None;
+    }
+
+    let name: String = head
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect();
+    let first = name.chars().next()?;
+    (first.is_alphabetic() || first == '_').then_some(name)
+}
+
+/// Blanks out string literals and drops trailing comments.
+fn strip_strings_and_comments(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in line.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+                result.push(c);
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                result.push(c);
+            }
+            '#' => break,
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Finds `data.<path>` references in a line of code.
+///
+/// The path stops at the first character that is not part of a dotted
+/// identifier, so `data.roles[role].permissions` yields `data.roles`.
+fn data_references(code: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    let mut offset = 0;
+
+    while let Some(idx) = code[offset..].find("data.") {
+        let start = offset + idx;
+        offset = start + "data.".len();
+
+        let preceded_by_ident = code[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.');
+        if preceded_by_ident {
+            continue;
+        }
+
+        let rest = &code[offset..];
+        let end = rest
+            .find(|c: char| !c.is_alphanumeric() && c != '_' && c != '.')
+            .unwrap_or(rest.len());
+        let path = rest[..end].trim_end_matches('.');
+        if !path.is_empty() {
+            references.push(format!("data.{path}"));
+        }
+    }
+
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roles_policy() -> Policy {
+        Policy::with_file_path(
+            "common.roles",
+            r#"package common.roles
+
+default is_admin := false
+
+is_admin if {
+    input.caller.roles[_] == "admin"
+}
+"#,
+            "policies/common/roles.rego",
+        )
+    }
+
+    fn authz_policy(body: &str) -> Policy {
+        Policy::with_file_path(
+            "users_service.authz",
+            format!("package users_service.authz\n\n{body}"),
+            "policies/users/authz.rego",
+        )
+    }
+
+    #[test]
+    fn test_resolves_cross_policy_references() {
+        let authz = authz_policy(
+            r"import data.common.roles
+
+default allow := false
+
+allow if {
+    data.common.roles.is_admin
+}
+",
+        );
+
+        let issues = ReferenceValidator::new().validate(&[roles_policy(), authz], &[]);
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn test_reports_missing_rule_with_file_and_line() {
+        let authz = authz_policy(
+            r"import data.common.permissions
+
+allow if {
+    data.common.roles.is_owner
+}
+",
+        );
+
+        let issues = ReferenceValidator::new().validate(&[roles_policy(), authz], &[]);
+        assert_eq!(issues.len(), 2);
+
+        assert_eq!(issues[0].file.as_deref(), Some("policies/users/authz.rego"));
+        assert_eq!(issues[0].line, Some(3));
+        assert!(issues[0]
+            .message
+            .contains("Import 'data.common.permissions'"));
+
+        assert_eq!(issues[1].line, Some(6));
+        assert!(issues[1].message.contains("data.common.roles.is_owner"));
+        assert!(issues.iter().all(|i| i.severity == SemanticSeverity::Error
+            && i.category == SemanticCategory::DataReference));
+    }
+
+    #[test]
+    fn test_external_roots_are_not_reported() {
+        let authz = authz_policy("deny if {\n    data.rate_limits[input.caller.id].exceeded\n}\n");
+
+        let issues = ReferenceValidator::new().validate(std::slice::from_ref(&authz), &[]);
+        assert_eq!(issues.len(), 1);
+
+        let issues = ReferenceValidator::new()
+            .with_external_root("data.rate_limits")
+            .validate(&[authz], &[]);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_data_files_provide_documents() {
+        let authz = authz_policy(
+            r"allow if {
+    some p in data.roles[input.caller.roles[_]]
+    data.tenants.acme.enabled
+}
+",
+        );
+        let data_files = vec![
+            ("data.json".to_string(), r#"{"roles": {}}"#.to_string()),
+            ("tenants/data.json".to_string(), "{}".to_string()),
+        ];
+
+        let issues = ReferenceValidator::new().validate(&[authz], &data_files);
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn test_ignores_strings_comments_and_test_policies() {
+        let authz = authz_policy("# uses data.missing.rule\nmsg := \"see data.missing.doc\"\n");
+        let test = Policy::with_file_path(
+            "users_service.authz_test",
+            "package users_service.authz_test\n\ntest_x if { data.nowhere.rule }\n",
+            "policies/users/authz_test.rego",
+        );
+
+        let issues = ReferenceValidator::new().validate(&[authz, test], &[]);
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn test_data_references() {
+        assert_eq!(
+            data_references("x := data.roles[r].permissions; y := mydata.z"),
+            vec!["data.roles".to_string()]
+        );
+        assert_eq!(
+            data_references("import data.common.authz as base"),
+            vec!["data.common.authz".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_top_level_rule_name() {
+        assert_eq!(top_level_rule_name("allow if {"), Some("allow".to_string()));
+        assert_eq!(
+            top_level_rule_name("default is_admin := false"),
+            Some("is_admin".to_string())
+        );
+        assert_eq!(
+            top_level_rule_name("violations contains msg if {"),
+            Some("violations".to_string())
+        );
+        assert_eq!(top_level_rule_name("    input.x == 1"), None);
+        assert_eq!(top_level_rule_name("}"), None);
+        assert_eq!(top_level_rule_name("import data.x"), None);
+    }
+}
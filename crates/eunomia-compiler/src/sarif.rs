@@ -0,0 +1,399 @@
+//! SARIF 2.1.0 output for validation reports.
+//!
+//! [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! is the format GitHub code scanning (and most CI annotation tooling)
+//! ingests. All reports passed to [`SarifLog::from_reports`] end up in a
+//! single run; each [`ValidationIssue`] becomes one result, located at its
+//! file and, when known, its line.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use eunomia_compiler::{PolicyValidator, SarifLog};
+//!
+//! let validator = PolicyValidator::new();
+//! let report = validator.validate_file("policies/authz.rego")?;
+//!
+//! let sarif = SarifLog::from_reports(&[report]);
+//! std::fs::write("findings.sarif", sarif.to_json()?)?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CompilerError, Result};
+use crate::lint::DEFAULT_RULES;
+use crate::validator::{IssueCategory, IssueSeverity, ValidationIssue, ValidationReport};
+
+/// SARIF specification version produced by this module.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+/// JSON schema of the SARIF version produced by this module.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Name of the tool reported in SARIF output.
+const TOOL_NAME: &str = "eunomia";
+
+/// Homepage of the tool reported in SARIF output.
+const TOOL_URI: &str = "https://github.com/A-Somniatore/eunomia";
+
+/// A SARIF log file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SarifLog {
+    /// URI of the JSON schema the log conforms to.
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    /// SARIF specification version.
+    pub version: String,
+    /// Analysis runs; reports are always aggregated into a single run.
+    pub runs: Vec<SarifRun>,
+}
+
+/// A single invocation of the analysis tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SarifRun {
+    /// The tool that produced the results.
+    pub tool: SarifTool,
+    /// Findings of the run.
+    pub results: Vec<SarifResult>,
+}
+
+/// The analysis tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SarifTool {
+    /// The tool's primary component.
+    pub driver: SarifDriver,
+}
+
+/// The tool component that produced the results.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifDriver {
+    /// Tool name.
+    pub name: String,
+    /// Tool version.
+    pub version: String,
+    /// Tool homepage.
+    pub information_uri: String,
+    /// Rules referenced by the results, in order of first use.
+    pub rules: Vec<SarifRule>,
+}
+
+/// A rule results can refer to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    /// Rule ID, e.g. `security/no-wildcard-allow`.
+    pub id: String,
+    /// Human-readable rule name, for built-in lint rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Short description, for built-in lint rules.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short_description: Option<SarifMessage>,
+}
+
+/// A single finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    /// ID of the rule that was violated.
+    pub rule_id: String,
+    /// Index of the rule in [`SarifDriver::rules`].
+    pub rule_index: usize,
+    /// Severity: `error`, `warning` or `note`.
+    pub level: String,
+    /// Description of the finding.
+    pub message: SarifMessage,
+    /// Where the finding was detected.
+    pub locations: Vec<SarifLocation>,
+}
+
+/// A plain-text message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SarifMessage {
+    /// Message text.
+    pub text: String,
+}
+
+/// The location of a finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    /// File and region of the finding.
+    pub physical_location: SarifPhysicalLocation,
+}
+
+/// A file and, optionally, a region within it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    /// The file.
+    pub artifact_location: SarifArtifactLocation,
+    /// The lines of the finding, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<SarifRegion>,
+}
+
+/// A file reference.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SarifArtifactLocation {
+    /// Path of the file, with `/` separators.
+    pub uri: String,
+}
+
+/// A range of lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    /// First line (1-based).
+    pub start_line: usize,
+}
+
+impl SarifLog {
+    /// Aggregates validation reports into a single-run SARIF log.
+    #[must_use]
+    pub fn from_reports(reports: &[ValidationReport]) -> Self {
+        let mut rules: Vec<SarifRule> = Vec::new();
+        let mut results = Vec::new();
+
+        for report in reports {
+            for issue in &report.issues {
+                let rule_id = rule_id(issue);
+                let rule_index = rules
+                    .iter()
+                    .position(|rule| rule.id == rule_id)
+                    .unwrap_or_else(|| {
+                        rules.push(sarif_rule(&rule_id));
+                        rules.len() - 1
+                    });
+
+                let file = issue.file.as_ref().or(report.file.as_ref());
+                results.push(SarifResult {
+                    rule_id,
+                    rule_index,
+                    level: level(issue.severity).to_string(),
+                    message: SarifMessage {
+                        text: issue.message.clone(),
+                    },
+                    locations: file
+                        .map(|file| SarifLocation {
+                            physical_location: SarifPhysicalLocation {
+                                artifact_location: SarifArtifactLocation {
+                                    uri: file.replace('\\', "/"),
+                                },
+                                region: issue.line.map(|start_line| SarifRegion { start_line }),
+                            },
+                        })
+                        .into_iter()
+                        .collect(),
+                });
+            }
+        }
+
+        Self {
+            schema: SARIF_SCHEMA.to_string(),
+            version: SARIF_VERSION.to_string(),
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: TOOL_NAME.to_string(),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                        information_uri: TOOL_URI.to_string(),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+    }
+
+    /// Serializes the log as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| CompilerError::ValidationError {
+            message: format!("Failed to serialize SARIF log: {e}"),
+        })
+    }
+}
+
+impl ValidationReport {
+    /// Converts this report into a single-run SARIF log.
+    #[must_use]
+    pub fn to_sarif(&self) -> SarifLog {
+        SarifLog::from_reports(std::slice::from_ref(self))
+    }
+}
+
+/// Returns the SARIF rule ID of an issue: its lint or semantic rule ID, or
+/// `eunomia/<category>` for issues without one.
+fn rule_id(issue: &ValidationIssue) -> String {
+    issue.rule_id.clone().unwrap_or_else(|| {
+        let category = match issue.category {
+            IssueCategory::Syntax => "syntax",
+            IssueCategory::Analysis => "analysis",
+            IssueCategory::Lint => "lint",
+            IssueCategory::Io => "io",
+            IssueCategory::Bundle => "bundle",
+            IssueCategory::Compatibility => "compatibility",
+            IssueCategory::Semantic => "semantic",
+        };
+        format!("eunomia/{category}")
+    })
+}
+
+/// Builds the rule entry for `id`, described if it is a built-in lint rule.
+fn sarif_rule(id: &str) -> SarifRule {
+    let info = DEFAULT_RULES.iter().find(|info| info.id == id);
+    SarifRule {
+        id: id.to_string(),
+        name: info.map(|info| info.name.to_string()),
+        short_description: info.map(|info| SarifMessage {
+            text: info.description.to_string(),
+        }),
+    }
+}
+
+/// Maps an issue severity to a SARIF result level.
+const fn level(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Hint => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(
+        severity: IssueSeverity,
+        category: IssueCategory,
+        line: Option<usize>,
+        rule_id: Option<&str>,
+    ) -> ValidationIssue {
+        ValidationIssue {
+            severity,
+            category,
+            message: format!("{category:?} issue"),
+            line,
+            file: None,
+            rule_id: rule_id.map(str::to_string),
+            suggestion: None,
+        }
+    }
+
+    fn sample_reports() -> Vec<ValidationReport> {
+        let mut authz = ValidationReport::for_file("policies/authz.rego");
+        authz.add_issue(issue(
+            IssueSeverity::Warning,
+            IssueCategory::Lint,
+            Some(7),
+            Some("security/no-wildcard-allow"),
+        ));
+        authz.add_issue(issue(
+            IssueSeverity::Error,
+            IssueCategory::Analysis,
+            None,
+            None,
+        ));
+
+        let mut users = ValidationReport::for_file("policies\\users.rego");
+        users.add_issue(issue(
+            IssueSeverity::Hint,
+            IssueCategory::Lint,
+            Some(1),
+            Some("security/no-wildcard-allow"),
+        ));
+
+        vec![authz, users]
+    }
+
+    #[test]
+    fn test_from_reports_aggregates_into_one_run() {
+        let log = SarifLog::from_reports(&sample_reports());
+
+        assert_eq!(log.version, "2.1.0");
+        assert_eq!(log.runs.len(), 1);
+        let run = &log.runs[0];
+        assert_eq!(run.tool.driver.name, "eunomia");
+        assert_eq!(run.results.len(), 3);
+
+        // Rules are shared between results and described when built in
+        let rules = &run.tool.driver.rules;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].id, "security/no-wildcard-allow");
+        assert!(rules[0].short_description.is_some());
+        assert_eq!(rules[1].id, "eunomia/analysis");
+        assert!(rules[1].short_description.is_none());
+        assert_eq!(run.results[2].rule_index, 0);
+
+        let levels: Vec<_> = run.results.iter().map(|r| r.level.as_str()).collect();
+        assert_eq!(levels, vec!["warning", "error", "note"]);
+    }
+
+    #[test]
+    fn test_locations() {
+        let log = SarifLog::from_reports(&sample_reports());
+        let results = &log.runs[0].results;
+
+        let located = &results[0].locations[0].physical_location;
+        assert_eq!(located.artifact_location.uri, "policies/authz.rego");
+        assert_eq!(located.region, Some(SarifRegion { start_line: 7 }));
+
+        // Issues without a line only carry the artifact location
+        let unlocated = &results[1].locations[0].physical_location;
+        assert_eq!(unlocated.artifact_location.uri, "policies/authz.rego");
+        assert!(unlocated.region.is_none());
+
+        assert_eq!(
+            results[2].locations[0]
+                .physical_location
+                .artifact_location
+                .uri,
+            "policies/users.rego"
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip_is_structurally_valid() {
+        let log = SarifLog::from_reports(&sample_reports());
+        let json = log.to_json().unwrap();
+
+        let parsed: SarifLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, log);
+
+        // Required properties of the SARIF 2.1.0 schema
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["$schema"], SARIF_SCHEMA);
+        assert_eq!(value["version"], "2.1.0");
+        let run = &value["runs"][0];
+        assert!(run["tool"]["driver"]["name"].is_string());
+        let rule_count = run["tool"]["driver"]["rules"].as_array().unwrap().len();
+        for rule in run["tool"]["driver"]["rules"].as_array().unwrap() {
+            assert!(rule["id"].is_string());
+        }
+        for result in run["results"].as_array().unwrap() {
+            assert!(result["message"]["text"].is_string());
+            assert!(["error", "warning", "note"].contains(&result["level"].as_str().unwrap()));
+            assert!(result["ruleIndex"].as_u64().unwrap() < rule_count as u64);
+            let location = &result["locations"][0]["physicalLocation"];
+            assert!(location["artifactLocation"]["uri"].is_string());
+            if let Some(region) = location.get("region") {
+                assert!(region["startLine"].as_u64().unwrap() >= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_report_to_sarif() {
+        let report = ValidationReport::for_file("empty.rego");
+        let log = report.to_sarif();
+
+        assert!(log.runs[0].results.is_empty());
+        assert!(log.runs[0].tool.driver.rules.is_empty());
+    }
+}
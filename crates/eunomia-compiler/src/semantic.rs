@@ -5,6 +5,8 @@
 //! - Rule reference validation
 //! - Input schema validation against expected structure
 //! - Operation ID validation against service contracts
+//! - HTTP method validation against the input schema and service contracts
+//! - Service contracts loaded from OpenAPI 3 specs
 //! - Data flow analysis for potential issues
 //!
 //! # Example
@@ -39,6 +41,9 @@ pub struct SemanticIssue {
     pub category: SemanticCategory,
     /// Human-readable message.
     pub message: String,
+    /// File the issue was found in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
     /// Line number (1-based, if applicable).
     pub line: Option<usize>,
     /// The problematic code snippet.
@@ -59,7 +64,7 @@ pub enum SemanticSeverity {
 }
 
 /// Category of semantic issues.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SemanticCategory {
     /// Unknown or invalid operation ID.
     UnknownOperation,
@@ -73,8 +78,32 @@ pub enum SemanticCategory {
     DataReference,
     /// Type mismatch or incompatibility.
     TypeMismatch,
+    /// HTTP method not allowed for the operation it is compared with.
+    MethodMismatch,
 }
 
+impl SemanticCategory {
+    /// Returns the kebab-case identifier of the category
+    /// (e.g. `unknown-operation`).
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::UnknownOperation => "unknown-operation",
+            Self::UndefinedRule => "undefined-rule",
+            Self::Unused => "unused",
+            Self::InputSchema => "input-schema",
+            Self::DataReference => "data-reference",
+            Self::TypeMismatch => "type-mismatch",
+            Self::MethodMismatch => "method-mismatch",
+        }
+    }
+}
+
+/// HTTP methods an OpenAPI path item can define operations for.
+const OPENAPI_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
 /// A mock service contract for testing without Themis.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MockServiceContract {
@@ -84,8 +113,19 @@ pub struct MockServiceContract {
     pub operation_ids: HashSet<String>,
     /// HTTP methods allowed per operation.
     pub operation_methods: HashMap<String, Vec<String>>,
+    /// Path of each operation, for contracts loaded from OpenAPI specs.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub operation_paths: HashMap<String, String>,
+    /// Problems found while loading the contract, such as operations
+    /// without an operation ID.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
+/// A service contract, either registered by hand or loaded from an OpenAPI
+/// spec with [`MockServiceContract::from_openapi`].
+pub type ServiceContract = MockServiceContract;
+
 impl MockServiceContract {
     /// Creates a new mock contract for a service.
     #[must_use]
@@ -94,9 +134,102 @@ impl MockServiceContract {
             service_name: service_name.into(),
             operation_ids: HashSet::new(),
             operation_methods: HashMap::new(),
+            operation_paths: HashMap::new(),
+            warnings: Vec::new(),
         }
     }
 
+    /// Builds a contract from an OpenAPI 3 document in JSON or YAML.
+    ///
+    /// The service is named after `info.title`. Every operation with an
+    /// `operationId` is added with its HTTP method and path; operations
+    /// without one are recorded in [`MockServiceContract::warnings`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be parsed or is not an
+    /// OpenAPI 3 document.
+    pub fn from_openapi(spec: &str) -> Result<Self> {
+        let document: serde_json::Value =
+            serde_yaml::from_str(spec).map_err(|e| CompilerError::ContractError {
+                message: format!("failed to parse OpenAPI document: {e}"),
+            })?;
+
+        let version = document
+            .get("openapi")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default();
+        if !version.starts_with("3.") {
+            return Err(CompilerError::ContractError {
+                message: format!(
+                    "expected an OpenAPI 3 document, found openapi version '{version}'"
+                ),
+            });
+        }
+
+        let title = document
+            .pointer("/info/title")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| CompilerError::ContractError {
+                message: "OpenAPI document has no info.title".to_string(),
+            })?;
+        let mut contract = Self::new(title);
+
+        let paths = document
+            .get("paths")
+            .and_then(serde_json::Value::as_object)
+            .into_iter()
+            .flatten();
+        for (path, item) in paths {
+            for method in OPENAPI_METHODS {
+                let Some(operation) = item.get(method) else {
+                    continue;
+                };
+                let method = method.to_uppercase();
+                let Some(op_id) = operation
+                    .get("operationId")
+                    .and_then(serde_json::Value::as_str)
+                else {
+                    let warning = format!("{method} {path} has no operationId and was skipped");
+                    tracing::warn!(service = %contract.service_name, "{warning}");
+                    contract.warnings.push(warning);
+                    continue;
+                };
+
+                contract.operation_ids.insert(op_id.to_string());
+                contract
+                    .operation_methods
+                    .entry(op_id.to_string())
+                    .or_default()
+                    .push(method);
+                contract
+                    .operation_paths
+                    .insert(op_id.to_string(), path.clone());
+            }
+        }
+
+        debug!(
+            service = %contract.service_name,
+            operations = contract.operation_ids.len(),
+            "Loaded service contract from OpenAPI document"
+        );
+        Ok(contract)
+    }
+
+    /// Builds a contract from an OpenAPI 3 file in JSON or YAML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn from_openapi_file(path: impl AsRef<Path>) -> Result<Self> {
+        let spec =
+            std::fs::read_to_string(path.as_ref()).map_err(|e| CompilerError::FileReadError {
+                path: path.as_ref().to_path_buf(),
+                source: e,
+            })?;
+        Self::from_openapi(&spec)
+    }
+
     /// Adds an operation ID to the contract.
     pub fn add_operation(&mut self, operation_id: impl Into<String>) -> &mut Self {
         self.operation_ids.insert(operation_id.into());
@@ -127,6 +260,12 @@ impl MockServiceContract {
     pub fn get_methods(&self, operation_id: &str) -> Option<&Vec<String>> {
         self.operation_methods.get(operation_id)
     }
+
+    /// Returns the path of an operation, if known.
+    #[must_use]
+    pub fn get_path(&self, operation_id: &str) -> Option<&str> {
+        self.operation_paths.get(operation_id).map(String::as_str)
+    }
 }
 
 /// Expected structure for authorization input.
@@ -340,12 +479,19 @@ impl SemanticValidator {
             self.check_input_schema_usage(source, &policy_info, &mut issues);
         }
 
+        // Check HTTP methods against the schema and operations
+        self.check_methods(source, &policy_info, &mut issues);
+
         // Check for undefined rule references
         self.check_rule_references(source, &policy_info, &mut issues);
 
         // Check for unused rules
         if self.check_unused {
-            self.check_unused_rules(&policy_info, &mut issues);
+            self.check_unused_rules(source, &policy_info, &mut issues);
+        }
+
+        for issue in &mut issues {
+            issue.file = Some(file_name.to_string());
         }
 
         issues
@@ -354,6 +500,8 @@ impl SemanticValidator {
     /// Analyzes a policy to extract structural information.
     fn analyze_policy(&self, source: &str) -> PolicyAnalysis {
         let mut analysis = PolicyAnalysis::default();
+        let mut rule = RuleComparisons::default();
+        let mut depth = 0usize;
 
         for (line_num, line) in source.lines().enumerate() {
             let trimmed = line.trim();
@@ -383,13 +531,30 @@ impl SemanticValidator {
             }
 
             // Extract rule references (simplified)
-            self.extract_rule_references(trimmed, &mut analysis.referenced_rules);
+            self.extract_rule_references(trimmed, line_num + 1, &mut analysis.referenced_rules);
 
             // Extract input field accesses
-            self.extract_input_accesses(trimmed, &mut analysis.input_accesses);
+            self.extract_input_accesses(trimmed, line_num + 1, &mut analysis.input_accesses);
 
             // Extract operation ID literals
-            self.extract_operation_ids(trimmed, &mut analysis.operation_ids);
+            self.extract_operation_ids(trimmed, line_num + 1, &mut analysis.operation_ids);
+
+            // Track operation ID and method comparisons per rule body
+            if let Some(op_id) = equality_literal(trimmed, "input.operation_id") {
+                rule.operation_ids.push((op_id, line_num + 1));
+            }
+            if let Some(method) = equality_literal(trimmed, "input.method") {
+                rule.methods.push((method, line_num + 1));
+            }
+            depth =
+                (depth + trimmed.matches('{').count()).saturating_sub(trimmed.matches('}').count());
+            if depth == 0 && !rule.is_empty() {
+                analysis.rule_comparisons.push(std::mem::take(&mut rule));
+            }
+        }
+
+        if !rule.is_empty() {
+            analysis.rule_comparisons.push(rule);
         }
 
         analysis
@@ -422,7 +587,12 @@ impl SemanticValidator {
     }
 
     #[allow(clippy::unused_self)]
-    fn extract_rule_references(&self, line: &str, refs: &mut HashSet<String>) {
+    fn extract_rule_references(
+        &self,
+        line: &str,
+        line_num: usize,
+        refs: &mut Vec<(String, usize)>,
+    ) {
         // Simple pattern matching for rule references
         let trimmed = line.trim();
 
@@ -450,7 +620,7 @@ impl SemanticValidator {
                 && potential_ref != "true"
                 && potential_ref != "false"
             {
-                refs.insert(potential_ref.to_string());
+                push_unique(refs, potential_ref.to_string(), line_num);
             }
         }
 
@@ -465,13 +635,13 @@ impl SemanticValidator {
                 {
                     let reference = after[..end].trim();
                     if !reference.is_empty() && !reference.starts_with("input.") {
-                        refs.insert(reference.to_string());
+                        push_unique(refs, reference.to_string(), line_num);
                     }
                 } else if !after.trim().is_empty() {
                     // Reference goes to end of segment
                     let reference = after.trim();
                     if !reference.starts_with("input.") {
-                        refs.insert(reference.to_string());
+                        push_unique(refs, reference.to_string(), line_num);
                     }
                 }
             }
@@ -488,7 +658,7 @@ impl SemanticValidator {
                     .unwrap_or(after.len());
                 let reference = &after[..end];
                 if !reference.is_empty() {
-                    refs.insert(format!("data.{reference}"));
+                    push_unique(refs, format!("data.{reference}"), line_num);
                 }
             }
             remaining = &remaining[idx + 5..];
@@ -496,7 +666,12 @@ impl SemanticValidator {
     }
 
     #[allow(clippy::unused_self)]
-    fn extract_input_accesses(&self, line: &str, accesses: &mut HashSet<String>) {
+    fn extract_input_accesses(
+        &self,
+        line: &str,
+        line_num: usize,
+        accesses: &mut Vec<(String, usize)>,
+    ) {
         // Find all input.* accesses
         let mut remaining = line;
         while let Some(idx) = remaining.find("input.") {
@@ -508,7 +683,7 @@ impl SemanticValidator {
                     .unwrap_or(after.len());
                 let access = after[..end].trim();
                 if !access.is_empty() {
-                    accesses.insert(format!("input.{access}"));
+                    push_unique(accesses, format!("input.{access}"), line_num);
                 }
             }
             remaining = &remaining[idx + 6..];
@@ -516,7 +691,7 @@ impl SemanticValidator {
     }
 
     #[allow(clippy::unused_self)]
-    fn extract_operation_ids(&self, line: &str, ops: &mut HashSet<String>) {
+    fn extract_operation_ids(&self, line: &str, line_num: usize, ops: &mut Vec<(String, usize)>) {
         // Look for operation_id comparisons
         if line.contains("operation_id") {
             // Extract string literals
@@ -527,7 +702,7 @@ impl SemanticValidator {
                 if c == '"' {
                     if in_string {
                         if !current.is_empty() {
-                            ops.insert(current.clone());
+                            push_unique(ops, current.clone(), line_num);
                         }
                         current.clear();
                     }
@@ -541,11 +716,11 @@ impl SemanticValidator {
 
     fn check_operation_ids(
         &self,
-        _source: &str,
+        source: &str,
         analysis: &PolicyAnalysis,
         issues: &mut Vec<SemanticIssue>,
     ) {
-        for op_id in &analysis.operation_ids {
+        for (op_id, line) in &analysis.operation_ids {
             let is_known = self.global_operations.contains(op_id)
                 || self.contracts.values().any(|c| c.has_operation(op_id));
 
@@ -554,8 +729,9 @@ impl SemanticValidator {
                     severity: SemanticSeverity::Warning,
                     category: SemanticCategory::UnknownOperation,
                     message: format!("Unknown operation ID: '{op_id}'"),
-                    line: None,
-                    snippet: Some(format!("operation_id == \"{op_id}\"")),
+                    file: None,
+                    line: Some(*line),
+                    snippet: line_snippet(source, *line),
                     suggestion: Some(
                         "Register this operation in a service contract or use register_operation()"
                             .to_string(),
@@ -567,7 +743,7 @@ impl SemanticValidator {
 
     fn check_input_schema_usage(
         &self,
-        _source: &str,
+        source: &str,
         analysis: &PolicyAnalysis,
         issues: &mut Vec<SemanticIssue>,
     ) {
@@ -580,15 +756,16 @@ impl SemanticValidator {
             ),
         ];
 
-        for access in &analysis.input_accesses {
+        for (access, line) in &analysis.input_accesses {
             for (pattern, suggestion) in &deprecated_patterns {
                 if access.starts_with(pattern) {
                     issues.push(SemanticIssue {
                         severity: SemanticSeverity::Warning,
                         category: SemanticCategory::InputSchema,
                         message: format!("Deprecated input field: '{access}'"),
-                        line: None,
-                        snippet: Some(access.clone()),
+                        file: None,
+                        line: Some(*line),
+                        snippet: line_snippet(source, *line),
                         suggestion: Some((*suggestion).to_string()),
                     });
                 }
@@ -613,7 +790,7 @@ impl SemanticValidator {
             "input.time",
         ];
 
-        for access in &analysis.input_accesses {
+        for (access, line) in &analysis.input_accesses {
             // Simple typo detection for top-level fields
             let parts: Vec<&str> = access.split('.').collect();
             if parts.len() >= 2 {
@@ -631,8 +808,9 @@ impl SemanticValidator {
                             severity: SemanticSeverity::Hint,
                             category: SemanticCategory::InputSchema,
                             message: format!("Unknown input field: '{access}'"),
-                            line: None,
-                            snippet: Some(access.clone()),
+                            file: None,
+                            line: Some(*line),
+                            snippet: line_snippet(source, *line),
                             suggestion: Some(format!("Did you mean: {}?", suggestions.join(", "))),
                         });
                     }
@@ -641,6 +819,77 @@ impl SemanticValidator {
         }
     }
 
+    /// Checks `input.method` comparisons: the method must be one the input
+    /// schema allows, and one the contract allows for every operation
+    /// compared in the same rule.
+    fn check_methods(
+        &self,
+        source: &str,
+        analysis: &PolicyAnalysis,
+        issues: &mut Vec<SemanticIssue>,
+    ) {
+        let valid_methods = self
+            .input_schema
+            .nested_requirements
+            .get("method")
+            .and_then(|r| r.allowed_values.as_ref())
+            .filter(|_| self.validate_input_schema);
+
+        for rule in &analysis.rule_comparisons {
+            for (method, line) in &rule.methods {
+                if let Some(valid) = valid_methods.filter(|v| !v.contains(method)) {
+                    let upper = method.to_uppercase();
+                    let suggestion = if valid.contains(&upper) {
+                        format!("HTTP methods are upper case: use \"{upper}\"")
+                    } else {
+                        format!("Valid methods: {}", valid.join(", "))
+                    };
+                    issues.push(SemanticIssue {
+                        severity: SemanticSeverity::Warning,
+                        category: SemanticCategory::InputSchema,
+                        message: format!("Invalid HTTP method: '{method}'"),
+                        file: None,
+                        line: Some(*line),
+                        snippet: line_snippet(source, *line),
+                        suggestion: Some(suggestion),
+                    });
+                    continue;
+                }
+
+                if !self.validate_operations {
+                    continue;
+                }
+                for (op_id, _) in &rule.operation_ids {
+                    let allowed: Vec<&str> = self
+                        .contracts
+                        .values()
+                        .filter_map(|c| c.get_methods(op_id))
+                        .flatten()
+                        .map(String::as_str)
+                        .collect();
+                    if allowed.is_empty() || allowed.contains(&method.as_str()) {
+                        continue;
+                    }
+
+                    issues.push(SemanticIssue {
+                        severity: SemanticSeverity::Warning,
+                        category: SemanticCategory::MethodMismatch,
+                        message: format!(
+                            "Method '{method}' is not allowed for operation '{op_id}'"
+                        ),
+                        file: None,
+                        line: Some(*line),
+                        snippet: line_snippet(source, *line),
+                        suggestion: Some(format!(
+                            "'{op_id}' allows: {}; this rule can never match",
+                            allowed.join(", ")
+                        )),
+                    });
+                }
+            }
+        }
+    }
+
     #[allow(clippy::unused_self)]
     fn find_similar_fields(&self, target: &str, known: &[&str]) -> Vec<String> {
         known
@@ -657,12 +906,12 @@ impl SemanticValidator {
     #[allow(clippy::unused_self)]
     fn check_rule_references(
         &self,
-        _source: &str,
+        source: &str,
         analysis: &PolicyAnalysis,
         issues: &mut Vec<SemanticIssue>,
     ) {
         // Check for references to undefined rules
-        for reference in &analysis.referenced_rules {
+        for (reference, line) in &analysis.referenced_rules {
             // Skip data.* references (external data)
             if reference.starts_with("data.") {
                 continue;
@@ -705,6 +954,16 @@ impl SemanticValidator {
                 continue;
             }
 
+            // Skip references through imports; they resolve in other
+            // policies, which the bundle-level ReferenceValidator checks
+            if analysis
+                .imports
+                .iter()
+                .any(|i| import_alias(i) == base_name)
+            {
+                continue;
+            }
+
             // Check if it's a defined rule
             if !analysis.defined_rules.contains(reference)
                 && !analysis.defined_rules.contains(base_name)
@@ -713,8 +972,9 @@ impl SemanticValidator {
                     severity: SemanticSeverity::Hint,
                     category: SemanticCategory::UndefinedRule,
                     message: format!("Reference to undefined rule: '{reference}'"),
-                    line: None,
-                    snippet: None,
+                    file: None,
+                    line: Some(*line),
+                    snippet: line_snippet(source, *line),
                     suggestion: Some(format!(
                         "Define '{reference}' or import it from another package"
                     )),
@@ -724,7 +984,12 @@ impl SemanticValidator {
     }
 
     #[allow(clippy::unused_self)]
-    fn check_unused_rules(&self, analysis: &PolicyAnalysis, issues: &mut Vec<SemanticIssue>) {
+    fn check_unused_rules(
+        &self,
+        source: &str,
+        analysis: &PolicyAnalysis,
+        issues: &mut Vec<SemanticIssue>,
+    ) {
         // Entry point rules that should not be flagged as unused
         let entry_points = ["allow", "deny", "violation", "warn", "final_allow"];
 
@@ -740,7 +1005,7 @@ impl SemanticValidator {
             }
 
             // Check if rule is referenced
-            let is_referenced = analysis.referenced_rules.iter().any(|r| {
+            let is_referenced = analysis.referenced_rules.iter().any(|(r, _)| {
                 r == rule || r.starts_with(&format!("{rule}.")) || r.ends_with(&format!(".{rule}"))
             });
 
@@ -750,8 +1015,9 @@ impl SemanticValidator {
                     severity: SemanticSeverity::Hint,
                     category: SemanticCategory::Unused,
                     message: format!("Rule '{rule}' appears to be unused"),
+                    file: None,
                     line,
-                    snippet: None,
+                    snippet: line.and_then(|line| line_snippet(source, line)),
                     suggestion: Some(format!(
                         "Consider removing '{rule}' or using it in another rule"
                     )),
@@ -761,6 +1027,63 @@ impl SemanticValidator {
     }
 }
 
+/// Returns the name an import binds (`data.a.b` → `b`, `data.a as x` → `x`).
+fn import_alias(import: &str) -> &str {
+    match import.split_once(" as ") {
+        Some((_, alias)) => alias.trim(),
+        None => import.rsplit('.').next().unwrap_or(import).trim(),
+    }
+}
+
+/// Returns the trimmed text of a 1-based source line.
+fn line_snippet(source: &str, line: usize) -> Option<String> {
+    source
+        .lines()
+        .nth(line.checked_sub(1)?)
+        .map(|text| text.trim().to_string())
+}
+
+/// Appends a value found on a line, unless the line already had it.
+fn push_unique(found: &mut Vec<(String, usize)>, value: String, line: usize) {
+    if !found.iter().any(|(v, l)| *l == line && *v == value) {
+        found.push((value, line));
+    }
+}
+
+/// Returns the string literal a field is compared with for equality, as in
+/// `input.method == "GET"` or `"GET" == input.method`.
+fn equality_literal(line: &str, field: &str) -> Option<String> {
+    let idx = line.find(field)?;
+    if let Some(rest) = line[idx + field.len()..].trim_start().strip_prefix("==") {
+        let rest = rest.trim_start().strip_prefix('"')?;
+        return rest.find('"').map(|end| rest[..end].to_string());
+    }
+
+    let before = line[..idx]
+        .trim_end()
+        .strip_suffix("==")?
+        .trim_end()
+        .strip_suffix('"')?;
+    before
+        .rfind('"')
+        .map(|start| before[start + 1..].to_string())
+}
+
+/// Equality comparisons in one rule body, with their line numbers.
+#[derive(Debug, Default)]
+struct RuleComparisons {
+    /// Operation IDs compared with `input.operation_id`.
+    operation_ids: Vec<(String, usize)>,
+    /// Methods compared with `input.method`.
+    methods: Vec<(String, usize)>,
+}
+
+impl RuleComparisons {
+    const fn is_empty(&self) -> bool {
+        self.operation_ids.is_empty() && self.methods.is_empty()
+    }
+}
+
 /// Internal analysis of a policy's structure.
 #[derive(Debug, Default)]
 struct PolicyAnalysis {
@@ -772,12 +1095,14 @@ struct PolicyAnalysis {
     defined_rules: HashSet<String>,
     /// Line numbers for defined rules.
     rule_lines: HashMap<String, usize>,
-    /// Referenced rule names.
-    referenced_rules: HashSet<String>,
-    /// Input field accesses.
-    input_accesses: HashSet<String>,
-    /// Operation IDs found in the policy.
-    operation_ids: HashSet<String>,
+    /// Referenced rule names, with the lines referencing them.
+    referenced_rules: Vec<(String, usize)>,
+    /// Input field accesses, with their lines.
+    input_accesses: Vec<(String, usize)>,
+    /// Operation IDs found in the policy, with their lines.
+    operation_ids: Vec<(String, usize)>,
+    /// Operation ID and method comparisons of each rule body.
+    rule_comparisons: Vec<RuleComparisons>,
 }
 
 /// Creates a mock contract for the users service.
@@ -835,6 +1160,89 @@ mod tests {
         assert!(methods.contains(&"PATCH".to_string()));
     }
 
+    const OPENAPI_SPEC: &str = r"
+openapi: 3.0.3
+info:
+  title: users-service
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      operationId: getUser
+    put:
+      operationId: updateUser
+    patch:
+      operationId: updateUser
+    delete:
+      summary: Delete a user
+  /users:
+    post:
+      operationId: createUser
+";
+
+    #[test]
+    fn test_contract_from_openapi() {
+        let contract = ServiceContract::from_openapi(OPENAPI_SPEC).unwrap();
+
+        assert_eq!(contract.service_name, "users-service");
+        assert_eq!(contract.operation_ids.len(), 3);
+        assert_eq!(contract.get_methods("getUser").unwrap(), &vec!["GET"]);
+        assert_eq!(
+            contract.get_methods("updateUser").unwrap(),
+            &vec!["PUT", "PATCH"]
+        );
+        assert_eq!(contract.get_path("createUser"), Some("/users"));
+        assert_eq!(
+            contract.warnings,
+            vec!["DELETE /users/{id} has no operationId and was skipped"]
+        );
+
+        // JSON documents parse as well
+        let json = r#"{"openapi": "3.1.0", "info": {"title": "orders-service"},
+            "paths": {"/orders": {"get": {"operationId": "listOrders"}}}}"#;
+        let contract = ServiceContract::from_openapi(json).unwrap();
+        assert_eq!(contract.service_name, "orders-service");
+        assert!(contract.has_operation("listOrders"));
+        assert!(contract.warnings.is_empty());
+
+        assert!(ServiceContract::from_openapi("swagger: '2.0'\ninfo:\n  title: x\n").is_err());
+        assert!(ServiceContract::from_openapi("openapi: [").is_err());
+    }
+
+    #[test]
+    fn test_openapi_contract_validation() {
+        let mut validator = SemanticValidator::new();
+        validator.register_contract(ServiceContract::from_openapi(OPENAPI_SPEC).unwrap());
+
+        let source = r#"
+package users_service.authz
+
+default allow := false
+
+allow if {
+    input.operation_id == "getUser"
+    input.method == "POST"
+}
+
+allow if {
+    input.operation_id == "deleteUser"
+}
+"#;
+
+        let issues = validator.validate_source(source, "authz.rego");
+
+        let mismatch = issues
+            .iter()
+            .find(|i| i.category == SemanticCategory::MethodMismatch)
+            .unwrap();
+        assert_eq!(mismatch.line, Some(8));
+        let unknown = issues
+            .iter()
+            .find(|i| i.category == SemanticCategory::UnknownOperation)
+            .unwrap();
+        assert!(unknown.message.contains("deleteUser"));
+    }
+
     #[test]
     fn test_semantic_validator_operation_check() {
         let mut validator = SemanticValidator::new();
@@ -866,6 +1274,11 @@ allow if {
 
         assert_eq!(unknown_ops.len(), 1);
         assert!(unknown_ops[0].message.contains("unknownOperation"));
+        assert_eq!(unknown_ops[0].line, Some(12));
+        assert_eq!(
+            unknown_ops[0].snippet.as_deref(),
+            Some("input.operation_id == \"unknownOperation\"")
+        );
     }
 
     #[test]
@@ -892,7 +1305,63 @@ allow if {
             .collect();
 
         assert!(!deprecated.is_empty());
-        assert!(deprecated.iter().any(|i| i.message.contains("action")));
+        let action = deprecated
+            .iter()
+            .find(|i| i.message.contains("action"))
+            .unwrap();
+        assert_eq!(action.line, Some(7));
+        assert_eq!(
+            action.snippet.as_deref(),
+            Some("input.action == \"read\"  # deprecated")
+        );
+    }
+
+    #[test]
+    fn test_issue_per_line() {
+        let validator = SemanticValidator::new();
+
+        let source = r#"
+package test.authz
+
+default allow := false
+
+allow if {
+    input.action == "read"
+    input.action != "delete"
+    input.action == lower(input.action)
+    undefined_helper
+}
+
+deny if {
+    not undefined_helper
+}
+"#;
+
+        let issues = validator.validate_source(source, "test.rego");
+
+        let lines = |filter: &dyn Fn(&SemanticIssue) -> bool| -> Vec<usize> {
+            let mut lines: Vec<_> = issues
+                .iter()
+                .filter(|i| filter(i))
+                .filter_map(|i| i.line)
+                .collect();
+            lines.sort_unstable();
+            lines
+        };
+
+        // A field accessed twice on line 9 yields one issue for that line
+        assert_eq!(
+            lines(&|i| i.message.contains("Deprecated input field")),
+            vec![7, 8, 9]
+        );
+        assert_eq!(
+            lines(&|i| i.category == SemanticCategory::UndefinedRule),
+            vec![10, 14]
+        );
+        assert!(issues
+            .iter()
+            .filter(|i| i.category == SemanticCategory::UndefinedRule)
+            .all(|i| i.snippet.as_deref().unwrap().ends_with("undefined_helper")));
     }
 
     #[test]
@@ -924,6 +1393,93 @@ allow if {
 
         assert_eq!(unknown_ops.len(), 1);
         assert!(unknown_ops[0].message.contains("invalidOperation"));
+        assert_eq!(unknown_ops[0].line, Some(11));
+    }
+
+    #[test]
+    fn test_method_not_allowed_for_operation() {
+        let mut validator = SemanticValidator::new();
+        validator.register_contract(users_service_contract());
+
+        let source = r#"
+package users_service.authz
+
+default allow := false
+
+allow if {
+    input.operation_id == "getUser"
+    input.method == "DELETE"
+}
+
+allow if {
+    input.operation_id == "updateUser"
+    "PATCH" == input.method
+}
+
+allow if {
+    input.operation_id == "deleteUser"
+}
+
+allow if {
+    input.method == "POST"
+}
+"#;
+
+        let issues = validator.validate_source(source, "authz.rego");
+
+        let mismatches: Vec<_> = issues
+            .iter()
+            .filter(|i| i.category == SemanticCategory::MethodMismatch)
+            .collect();
+        assert_eq!(mismatches.len(), 1);
+        assert!(mismatches[0].message.contains("'DELETE'"));
+        assert!(mismatches[0].message.contains("'getUser'"));
+        assert_eq!(mismatches[0].line, Some(8));
+        assert_eq!(
+            mismatches[0].snippet.as_deref(),
+            Some("input.method == \"DELETE\"")
+        );
+        assert_eq!(mismatches[0].severity, SemanticSeverity::Warning);
+    }
+
+    #[test]
+    fn test_invalid_http_method() {
+        let validator = SemanticValidator::new();
+
+        let source = r#"
+package test.authz
+
+default allow := false
+
+allow if {
+    input.method == "GETT"
+}
+
+allow if input.method == "post"
+
+allow if {
+    input.method == "HEAD"
+}
+"#;
+
+        let issues = validator.validate_source(source, "test.rego");
+
+        let invalid: Vec<_> = issues
+            .iter()
+            .filter(|i| i.message.starts_with("Invalid HTTP method"))
+            .collect();
+        assert_eq!(invalid.len(), 2);
+        assert!(invalid
+            .iter()
+            .all(|i| i.category == SemanticCategory::InputSchema));
+
+        let gett = invalid.iter().find(|i| i.message.contains("GETT")).unwrap();
+        assert_eq!(gett.line, Some(7));
+        assert!(gett.suggestion.as_ref().unwrap().contains("GET, POST"));
+
+        let post = invalid.iter().find(|i| i.message.contains("post")).unwrap();
+        assert_eq!(post.line, Some(10));
+        assert!(post.suggestion.as_ref().unwrap().contains("\"POST\""));
     }
 
     #[test]
@@ -956,11 +1512,43 @@ unused_helper if {
             .filter(|i| i.category == SemanticCategory::Unused)
             .collect();
 
-        assert!(unused.iter().any(|i| i.message.contains("unused_helper")));
+        let helper = unused
+            .iter()
+            .find(|i| i.message.contains("unused_helper"))
+            .unwrap();
+        assert_eq!(helper.line, Some(14));
+        assert_eq!(helper.snippet.as_deref(), Some("unused_helper if {"));
         // is_admin should NOT be flagged as unused
         assert!(!unused.iter().any(|i| i.message.contains("is_admin")));
     }
 
+    #[test]
+    fn test_imported_references_are_not_undefined() {
+        let validator = SemanticValidator::new();
+
+        let source = r#"
+package test.authz
+
+import data.common.roles
+import data.common.authz as base
+
+default allow := false
+
+allow if roles.is_admin
+
+allow if base.is_service
+"#;
+
+        let issues = validator.validate_source(source, "test.rego");
+
+        assert!(!issues
+            .iter()
+            .any(|i| i.category == SemanticCategory::UndefinedRule));
+        assert!(issues
+            .iter()
+            .all(|i| i.file.as_deref() == Some("test.rego")));
+    }
+
     #[test]
     fn test_input_schema_validation() {
         let schema = InputSchema::themis_standard();
@@ -0,0 +1,240 @@
+//! Evaluation traces explaining query results.
+//!
+//! `regorus` does not expose a step-by-step evaluation trace, so
+//! [`RegoEngine::eval_with_trace`](crate::RegoEngine::eval_with_trace)
+//! derives one from line coverage of a single evaluation:
+//!
+//! - A rule definition was **entered** if any of its lines was evaluated.
+//! - An entered definition **failed** if some of its expressions were never
+//!   evaluated; evaluation stopped at the last evaluated line before them,
+//!   which is reported as the failing expression.
+//!
+//! Definitions that were never entered are left out of the trace.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use eunomia_compiler::RegoEngine;
+//!
+//! let mut engine = RegoEngine::new();
+//! engine.add_policy_from_file("policies/authz.rego")?;
+//! engine.set_input(serde_json::json!({"caller": {"type": "user"}}))?;
+//!
+//! let trace = engine.eval_with_trace("data.authz.allow")?;
+//! println!("{trace}");
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::engine::{extract_rule_name, EvalResult, PolicyCoverage};
+
+/// Result of a query together with the rules evaluated to produce it.
+#[derive(Debug, Clone)]
+pub struct EvalTrace {
+    /// The evaluated query.
+    pub query: String,
+    /// The query result.
+    pub result: EvalResult,
+    /// Entered rule definitions, ordered by file and line.
+    pub rules: Vec<RuleTrace>,
+}
+
+/// A rule definition entered during evaluation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTrace {
+    /// Rule name (`<package>.<rule>`).
+    pub rule: String,
+    /// Policy file the definition is in.
+    pub file: String,
+    /// Line (1-based) of the definition's head.
+    pub line: usize,
+    /// Expression evaluation stopped at, if the definition failed.
+    pub failed_at: Option<TraceLine>,
+}
+
+impl RuleTrace {
+    /// Returns true if every expression of the definition was evaluated.
+    #[must_use]
+    pub const fn completed(&self) -> bool {
+        self.failed_at.is_none()
+    }
+}
+
+/// A source line referenced by a trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceLine {
+    /// Line number (1-based).
+    pub line: usize,
+    /// Source text of the line, trimmed.
+    pub source: String,
+}
+
+impl EvalTrace {
+    /// Builds a trace from the coverage of a single evaluation.
+    ///
+    /// `rule_lines` holds the lines of each rule's definitions, per policy
+    /// file, as tracked by the engine.
+    pub(crate) fn from_coverage(
+        query: &str,
+        result: EvalResult,
+        coverage: &[PolicyCoverage],
+        rule_lines: &HashMap<String, Vec<(String, Vec<usize>)>>,
+    ) -> Self {
+        let mut rules = Vec::new();
+
+        for policy in coverage {
+            let Some(file_rules) = rule_lines.get(&policy.file) else {
+                continue;
+            };
+            let source: Vec<&str> = policy.source.lines().collect();
+            let text = |line: usize| source.get(line - 1).copied().unwrap_or_default();
+
+            for (rule, lines) in file_rules {
+                for definition in split_definitions(lines, text) {
+                    let Some(&last) = definition
+                        .iter()
+                        .rev()
+                        .find(|line| policy.covered.contains(line))
+                    else {
+                        continue;
+                    };
+
+                    let stopped = definition
+                        .iter()
+                        .any(|line| *line > last && policy.not_covered.contains(line));
+                    rules.push(RuleTrace {
+                        rule: rule.clone(),
+                        file: policy.file.clone(),
+                        line: definition[0],
+                        failed_at: stopped.then(|| TraceLine {
+                            line: last,
+                            source: text(last).trim().to_string(),
+                        }),
+                    });
+                }
+            }
+        }
+
+        rules.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+        Self {
+            query: query.to_string(),
+            result,
+            rules,
+        }
+    }
+}
+
+/// Splits a rule's lines into its definitions, each starting at an
+/// unindented rule head.
+fn split_definitions<'a>(lines: &[usize], text: impl Fn(usize) -> &'a str) -> Vec<Vec<usize>> {
+    let mut definitions: Vec<Vec<usize>> = Vec::new();
+    for &line in lines {
+        let source = text(line);
+        let is_head =
+            !source.starts_with(char::is_whitespace) && extract_rule_name(source.trim()).is_some();
+        match definitions.last_mut() {
+            Some(definition) if !is_head => definition.push(line),
+            _ => definitions.push(vec![line]),
+        }
+    }
+    definitions
+}
+
+impl fmt::Display for EvalTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Query:  {}", self.query)?;
+        writeln!(f, "Result: {}", self.result.to_json())?;
+
+        if self.rules.is_empty() {
+            return writeln!(f, "No rules were entered");
+        }
+
+        writeln!(f, "Rules entered:")?;
+        for rule in &self.rules {
+            let mark = if rule.completed() { "✓" } else { "✗" };
+            writeln!(f, "  {mark} {} ({}:{})", rule.rule, rule.file, rule.line)?;
+            if let Some(failed) = &rule.failed_at {
+                writeln!(
+                    f,
+                    "      stopped at line {}: {}",
+                    failed.line, failed.source
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"package authz
+
+default allow := false
+
+allow if {
+    input.user.role == "admin"
+    input.user.active
+}
+
+allow if {
+    input.user.role == "owner"
+}
+"#;
+
+    fn trace(covered: &[usize], not_covered: &[usize]) -> EvalTrace {
+        let coverage = vec![PolicyCoverage {
+            file: "authz.rego".to_string(),
+            source: POLICY.to_string(),
+            covered: covered.iter().copied().collect(),
+            not_covered: not_covered.iter().copied().collect(),
+        }];
+        let rule_lines = HashMap::from([(
+            "authz.rego".to_string(),
+            vec![("authz.allow".to_string(), vec![3, 5, 6, 7, 8, 10, 11, 12])],
+        )]);
+        EvalTrace::from_coverage(
+            "data.authz.allow",
+            EvalResult::Bool(false),
+            &coverage,
+            &rule_lines,
+        )
+    }
+
+    #[test]
+    fn test_trace_reports_failed_expression() {
+        // `allow` at line 5 stopped at the role check; the definition at
+        // line 10 was never entered
+        let trace = trace(&[3, 5, 6], &[7, 10, 11]);
+
+        assert_eq!(trace.rules.len(), 2);
+        assert!(trace.rules[0].completed());
+        assert_eq!(trace.rules[0].line, 3);
+
+        let failed = &trace.rules[1];
+        assert_eq!(failed.line, 5);
+        assert_eq!(
+            failed.failed_at,
+            Some(TraceLine {
+                line: 6,
+                source: "input.user.role == \"admin\"".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_trace_display() {
+        let output = trace(&[3, 5, 6, 7, 10, 11], &[]).to_string();
+
+        assert!(output.starts_with("Query:  data.authz.allow\nResult: false\n"));
+        assert!(output.contains("✓ authz.allow (authz.rego:5)"));
+        assert!(output.contains("✓ authz.allow (authz.rego:10)"));
+        assert!(!output.contains("stopped at"));
+
+        let output = trace(&[], &[3, 6, 7, 11]).to_string();
+        assert!(output.ends_with("No rules were entered\n"));
+    }
+}
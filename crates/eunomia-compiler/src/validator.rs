@@ -2,8 +2,12 @@
 //!
 //! This module provides a unified validation interface that combines:
 //! - Rego syntax validation (via regorus)
+//! - Rego v1 compatibility (via [`RegoV1CompatibilityChecker`])
 //! - Static analysis (via the analyzer)
 //! - Linting rules (via the linter)
+//! - Semantic checks against service contracts (via [`SemanticValidator`],
+//!   when [`ValidatorConfig::run_semantic`] is set)
+//! - Bundle structural checks (via [`Bundle::validate`])
 //!
 //! # Example
 //!
@@ -22,16 +26,22 @@
 //! }
 //! ```
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 
 use crate::analyzer::{AnalysisResult, Analyzer};
 use crate::engine::RegoEngine;
 use crate::error::{CompilerError, Result};
 use crate::lint::{Linter, Severity};
 use crate::parser::Parser;
+use crate::semantic::{
+    InputSchema, SemanticCategory, SemanticIssue, SemanticSeverity, SemanticValidator,
+    ServiceContract,
+};
 
-use eunomia_core::Policy;
+use eunomia_core::{Bundle, Policy};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
@@ -51,6 +61,18 @@ pub struct ValidatorConfig {
     pub require_default_deny: bool,
     /// Linting rules to disable.
     pub disabled_lint_rules: Vec<String>,
+    /// Whether to run [`Bundle::validate`] when validating a bundle.
+    pub validate_bundle: bool,
+    /// Rego syntax version policies must follow.
+    pub rego_version: RegoVersion,
+    /// Whether to run semantic validation.
+    pub run_semantic: bool,
+    /// Service contracts operation IDs and HTTP methods are checked against.
+    pub contracts: Vec<ServiceContract>,
+    /// Schema `input` references are checked against.
+    pub input_schema: InputSchema,
+    /// Severity overrides for semantic issues, by category.
+    pub semantic_severities: HashMap<SemanticCategory, IssueSeverity>,
 }
 
 impl Default for ValidatorConfig {
@@ -62,6 +84,12 @@ impl Default for ValidatorConfig {
             fail_on_warnings: false,
             require_default_deny: true,
             disabled_lint_rules: Vec::new(),
+            validate_bundle: true,
+            rego_version: RegoVersion::default(),
+            run_semantic: false,
+            contracts: Vec::new(),
+            input_schema: InputSchema::default(),
+            semantic_severities: HashMap::new(),
         }
     }
 }
@@ -86,6 +114,208 @@ impl ValidatorConfig {
             ..Default::default()
         }
     }
+
+    /// Registers a service contract and enables semantic validation.
+    #[must_use]
+    pub fn with_contract(mut self, contract: ServiceContract) -> Self {
+        self.run_semantic = true;
+        self.contracts.push(contract);
+        self
+    }
+
+    /// Reports semantic issues of `category` with `severity`, e.g. to
+    /// promote [`SemanticCategory::UnknownOperation`] to an error.
+    #[must_use]
+    pub fn with_semantic_severity(
+        mut self,
+        category: SemanticCategory,
+        severity: IssueSeverity,
+    ) -> Self {
+        self.semantic_severities.insert(category, severity);
+        self
+    }
+}
+
+/// Rego syntax version a policy is validated against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegoVersion {
+    /// Legacy syntax; no compatibility checks.
+    #[default]
+    V0,
+    /// Rego v1 syntax (`import rego.v1`, required by OPA 1.0).
+    V1,
+    /// V1 for policies that import `rego.v1` or `future.keywords`, V0 otherwise.
+    Auto,
+}
+
+impl RegoVersion {
+    /// Returns whether `source` must follow Rego v1 syntax under this setting.
+    #[must_use]
+    pub fn requires_v1(self, source: &str) -> bool {
+        match self {
+            Self::V0 => false,
+            Self::V1 => true,
+            Self::Auto => source.lines().any(|line| {
+                let trimmed = line.trim();
+                trimmed == "import rego.v1" || trimmed.starts_with("import future.keywords")
+            }),
+        }
+    }
+}
+
+impl FromStr for RegoVersion {
+    type Err = CompilerError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "v0" | "0" => Ok(Self::V0),
+            "v1" | "1" => Ok(Self::V1),
+            "auto" => Ok(Self::Auto),
+            other => Err(CompilerError::ValidationError {
+                message: format!("unknown Rego version '{other}'; expected v0, v1 or auto"),
+            }),
+        }
+    }
+}
+
+/// A v0-only construct that Rego v1 rejects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatibilityIssue {
+    /// Line number (1-based).
+    pub line: usize,
+    /// Description of the construct.
+    pub construct: String,
+    /// How to rewrite it for Rego v1.
+    pub suggestion: String,
+}
+
+/// Scans Rego source for constructs that are only valid in Rego v0.
+///
+/// The checker is line-based and detects:
+/// - rule bodies without `if`, including partial rules like `p[x] { ... }`
+/// - `some x` declarations without `in`
+/// - rule heads assigned with `=` instead of `:=`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RegoV1CompatibilityChecker;
+
+impl RegoV1CompatibilityChecker {
+    /// Creates a new checker.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Returns the v0-only constructs found in `source`.
+    #[must_use]
+    pub fn check(&self, source: &str) -> Vec<CompatibilityIssue> {
+        let mut issues = Vec::new();
+
+        for (idx, line) in source.lines().enumerate() {
+            let code = strip_comment(line).trim_end();
+            let trimmed = code.trim_start();
+            let issue = |construct: &str, suggestion: &str| CompatibilityIssue {
+                line: idx + 1,
+                construct: construct.to_string(),
+                suggestion: suggestion.to_string(),
+            };
+
+            if trimmed.starts_with("some ") && !trimmed.contains(" in ") {
+                issues.push(issue(
+                    "`some` declaration without `in`",
+                    "Iterate with `some x in collection`",
+                ));
+            }
+
+            let Some((head, after_head)) = rule_head(code) else {
+                continue;
+            };
+
+            if code.ends_with('{') && !is_literal_assignment(code) && !has_if(code) {
+                if head.contains('[') {
+                    issues.push(issue(
+                        "partial rule without `if`",
+                        "Use `p contains x if { ... }` for partial sets or \
+                         `p[x] := value if { ... }` for partial objects",
+                    ));
+                } else {
+                    issues.push(issue(
+                        "rule body without `if`",
+                        "Add `if` before the rule body, e.g. `allow if { ... }`",
+                    ));
+                }
+            }
+
+            let after_head = after_head.trim_start();
+            if after_head.starts_with('=') && !after_head.starts_with("==") {
+                issues.push(issue(
+                    "rule head assigned with `=`",
+                    "Use `:=` in rule heads, e.g. `default allow := false`",
+                ));
+            }
+        }
+
+        issues
+    }
+}
+
+/// Removes a trailing `#` comment, ignoring `#` inside string literals.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (idx, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..idx],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+/// Splits a top-level rule definition line into its head (name plus any
+/// `[..]` or `(..)` suffix) and the text that follows it.
+fn rule_head(code: &str) -> Option<(&str, &str)> {
+    if code.is_empty() || code.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let code = code.strip_prefix("default ").unwrap_or(code).trim_start();
+    let first = code.split_whitespace().next()?;
+    if matches!(first, "package" | "import" | "else") || code.starts_with('}') {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    let mut end = 0;
+    for (idx, c) in code.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth = depth.saturating_sub(1),
+            c if depth == 0 && !(c.is_ascii_alphanumeric() || c == '_' || c == '.') => break,
+            _ => {}
+        }
+        end = idx + c.len_utf8();
+    }
+
+    let (head, rest) = code.split_at(end);
+    head.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        .then_some((head, rest))
+}
+
+/// Returns whether the `{` ending `code` opens an object or set literal.
+fn is_literal_assignment(code: &str) -> bool {
+    let before = code[..code.len() - 1].trim_end();
+    before.ends_with(":=") || (before.ends_with('=') && !before.ends_with("=="))
+}
+
+/// Returns whether a rule head line uses the `if` keyword.
+fn has_if(code: &str) -> bool {
+    code.split_whitespace().any(|token| token == "if")
 }
 
 /// A validation issue found during policy validation.
@@ -101,7 +331,8 @@ pub struct ValidationIssue {
     pub line: Option<usize>,
     /// Source file path.
     pub file: Option<String>,
-    /// Rule ID (if from linting).
+    /// Rule ID (if from linting, or `semantic/<category>` for semantic
+    /// issues).
     pub rule_id: Option<String>,
     /// Suggestion for fixing the issue.
     pub suggestion: Option<String>,
@@ -128,6 +359,30 @@ impl From<Severity> for IssueSeverity {
     }
 }
 
+impl From<SemanticSeverity> for IssueSeverity {
+    fn from(s: SemanticSeverity) -> Self {
+        match s {
+            SemanticSeverity::Hint => Self::Hint,
+            SemanticSeverity::Warning => Self::Warning,
+            SemanticSeverity::Error => Self::Error,
+        }
+    }
+}
+
+impl From<SemanticIssue> for ValidationIssue {
+    fn from(issue: SemanticIssue) -> Self {
+        Self {
+            severity: issue.severity.into(),
+            category: IssueCategory::Semantic,
+            message: issue.message,
+            line: issue.line,
+            file: issue.file,
+            rule_id: Some(format!("semantic/{}", issue.category.id())),
+            suggestion: issue.suggestion,
+        }
+    }
+}
+
 /// Category of validation issues.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum IssueCategory {
@@ -139,6 +394,12 @@ pub enum IssueCategory {
     Lint,
     /// I/O errors.
     Io,
+    /// Bundle structural errors.
+    Bundle,
+    /// Rego v1 compatibility issues.
+    Compatibility,
+    /// Semantic issues (operation IDs, input schema, rule references).
+    Semantic,
 }
 
 /// Result of validating a policy.
@@ -153,6 +414,9 @@ pub struct ValidationReport {
     /// Analysis result (if analysis was run).
     #[serde(skip)]
     pub analysis: Option<AnalysisResult>,
+    /// Number of lint violations silenced by suppression comments.
+    #[serde(default)]
+    pub suppressed_lint_violations: usize,
 }
 
 impl ValidationReport {
@@ -232,6 +496,7 @@ pub struct PolicyValidator {
     config: ValidatorConfig,
     linter: Linter,
     analyzer: Analyzer,
+    semantic: Option<SemanticValidator>,
 }
 
 impl Default for PolicyValidator {
@@ -262,11 +527,35 @@ impl PolicyValidator {
 
         let analyzer = Analyzer::new().with_require_default(config.require_default_deny);
 
+        let semantic = config.run_semantic.then(|| {
+            let mut semantic = SemanticValidator::new();
+            semantic.with_input_schema(config.input_schema.clone());
+            for contract in &config.contracts {
+                semantic.register_contract(contract.clone());
+            }
+            semantic
+        });
+
         Self {
             config,
             linter,
             analyzer,
+            semantic,
+        }
+    }
+
+    /// Replaces the linter, e.g. with one built from a
+    /// [`LintConfig`](crate::lint::LintConfig).
+    ///
+    /// Rules listed in [`ValidatorConfig::disabled_lint_rules`] stay disabled.
+    #[must_use]
+    pub fn with_linter(mut self, mut linter: Linter) -> Self {
+        for rule_id in &self.config.disabled_lint_rules {
+            let static_id: &'static str = Box::leak(rule_id.clone().into_boxed_str());
+            linter.disable_rule(static_id);
         }
+        self.linter = linter;
+        self
     }
 
     /// Creates a strict validator that fails on warnings.
@@ -333,6 +622,11 @@ impl PolicyValidator {
             Self::check_syntax(source, file_name, report);
         }
 
+        if self.config.rego_version.requires_v1(source) {
+            debug!("Running Rego v1 compatibility check");
+            Self::check_rego_v1(source, file_name, report);
+        }
+
         // Step 2: Parse with our parser for metadata
         let policy = match Parser::new().parse_source(source, file_name) {
             Ok(p) => {
@@ -361,6 +655,9 @@ impl PolicyValidator {
             if let Some(ref policy) = policy {
                 debug!("Running static analysis");
                 self.run_analysis(policy, file_name, report);
+
+                let policies = HashMap::from([(policy.package_name.clone(), policy.clone())]);
+                Self::check_circular_imports(&policies, file_name, report);
             }
         }
 
@@ -369,6 +666,26 @@ impl PolicyValidator {
             debug!("Running linting");
             self.run_linting(source, file_name, report);
         }
+
+        // Step 5: Semantic validation
+        if let (Some(semantic), Some(_)) = (&self.semantic, &policy) {
+            debug!("Running semantic validation");
+            self.run_semantic(semantic, source, file_name, report);
+        }
+    }
+
+    fn check_rego_v1(source: &str, file_name: &str, report: &mut ValidationReport) {
+        for issue in RegoV1CompatibilityChecker::new().check(source) {
+            report.add_issue(ValidationIssue {
+                severity: IssueSeverity::Error,
+                category: IssueCategory::Compatibility,
+                message: format!("Not valid in Rego v1: {}", issue.construct),
+                line: Some(issue.line),
+                file: Some(file_name.to_string()),
+                rule_id: None,
+                suggestion: Some(issue.suggestion),
+            });
+        }
     }
 
     fn check_syntax(source: &str, file_name: &str, report: &mut ValidationReport) {
@@ -423,10 +740,32 @@ impl PolicyValidator {
         }
     }
 
+    /// Reports import cycles between `policies` as an analysis error.
+    fn check_circular_imports(
+        policies: &HashMap<String, Policy>,
+        file_name: &str,
+        report: &mut ValidationReport,
+    ) {
+        if let Err(e) = Analyzer::check_circular_imports(policies) {
+            report.add_issue(ValidationIssue {
+                severity: IssueSeverity::Error,
+                category: IssueCategory::Analysis,
+                message: e.to_string(),
+                line: None,
+                file: Some(file_name.to_string()),
+                rule_id: None,
+                suggestion: Some(
+                    "Move the shared rules into a package that neither imports".to_string(),
+                ),
+            });
+        }
+    }
+
     fn run_linting(&self, source: &str, file_name: &str, report: &mut ValidationReport) {
-        let violations = self.linter.lint(source, file_name);
+        let lint_report = self.linter.lint_report(source, file_name);
+        report.suppressed_lint_violations += lint_report.suppressed.len();
 
-        for violation in violations {
+        for violation in lint_report.violations {
             // Skip lint errors for things already caught by analysis
             if report.issues.iter().any(|i| {
                 i.category == IssueCategory::Analysis
@@ -448,6 +787,76 @@ impl PolicyValidator {
         }
     }
 
+    fn run_semantic(
+        &self,
+        semantic: &SemanticValidator,
+        source: &str,
+        file_name: &str,
+        report: &mut ValidationReport,
+    ) {
+        for issue in semantic.validate_source(source, file_name) {
+            let severity = self
+                .config
+                .semantic_severities
+                .get(&issue.category)
+                .copied();
+            let mut issue = ValidationIssue::from(issue);
+            if let Some(severity) = severity {
+                issue.severity = severity;
+            }
+            issue.file = Some(file_name.to_string());
+            report.add_issue(issue);
+        }
+    }
+
+    /// Validates every policy in a bundle and, if enabled, the bundle structure.
+    ///
+    /// Each policy is validated as if it were a standalone file named after
+    /// its package. When [`ValidatorConfig::validate_bundle`] is set, the
+    /// result of [`Bundle::validate`] is reported as a
+    /// [`IssueCategory::Bundle`] error.
+    #[must_use]
+    pub fn validate_bundle(&self, bundle: &Bundle) -> ValidationReport {
+        let file_name = bundle.file_name();
+        info!(bundle = %file_name, "Validating bundle");
+
+        let mut report = ValidationReport::for_file(&file_name);
+
+        let mut packages: Vec<&String> = bundle.policies.keys().collect();
+        packages.sort();
+        for package in packages {
+            let policy_report = self.validate_source_str(&bundle.policies[package], package);
+            report.issues.extend(policy_report.issues);
+            report.suppressed_lint_violations += policy_report.suppressed_lint_violations;
+        }
+
+        if self.config.run_analysis {
+            let policies = bundle
+                .policies
+                .iter()
+                .map(|(package, source)| (package.clone(), Policy::new(package, source)))
+                .collect();
+            Self::check_circular_imports(&policies, &file_name, &mut report);
+        }
+
+        if self.config.validate_bundle {
+            debug!("Running bundle structural validation");
+            if let Err(e) = bundle.validate() {
+                report.add_issue(ValidationIssue {
+                    severity: IssueSeverity::Error,
+                    category: IssueCategory::Bundle,
+                    message: e.to_string(),
+                    line: None,
+                    file: Some(file_name),
+                    rule_id: None,
+                    suggestion: None,
+                });
+            }
+        }
+
+        report
+    }
+
     /// Validates and returns a Result indicating success or failure.
     ///
     /// # Errors
@@ -616,6 +1025,12 @@ allow if {
             fail_on_warnings: true,
             require_default_deny: false,
             disabled_lint_rules: vec!["style/explicit-imports".to_string()],
+            validate_bundle: true,
+            rego_version: RegoVersion::V0,
+            run_semantic: true,
+            contracts: Vec::new(),
+            input_schema: InputSchema::minimal(),
+            semantic_severities: HashMap::new(),
         };
 
         let validator = PolicyValidator::with_config(config);
@@ -623,4 +1038,268 @@ allow if {
         // Should work without crashing
         let _ = validator.validate_source_str("package test\ndefault allow := false", "test.rego");
     }
+
+    #[test]
+    fn test_validate_bundle_reports_root_mismatch() {
+        let bundle = Bundle::builder("test")
+            .version("1.0.0")
+            .add_policy("test.authz", VALID_POLICY)
+            .add_root("missing")
+            .build();
+
+        let report = PolicyValidator::new().validate_bundle(&bundle);
+
+        assert!(!report.is_valid());
+        let bundle_errors: Vec<_> = report
+            .errors()
+            .into_iter()
+            .filter(|e| e.category == IssueCategory::Bundle)
+            .collect();
+        assert_eq!(bundle_errors.len(), 1);
+        assert!(bundle_errors[0].message.contains("'missing'"));
+    }
+
+    #[test]
+    fn test_validate_bundle_can_be_disabled() {
+        let bundle = Bundle::builder("test")
+            .version("1.0.0")
+            .add_policy("test.authz", VALID_POLICY)
+            .add_root("missing")
+            .build();
+
+        let config = ValidatorConfig {
+            validate_bundle: false,
+            ..Default::default()
+        };
+        let report = PolicyValidator::with_config(config).validate_bundle(&bundle);
+
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn test_validate_bundle_reports_circular_imports() {
+        let bundle = Bundle::builder("test")
+            .version("1.0.0")
+            .add_policy(
+                "test.a",
+                "package test.a\n\nimport data.test.b\n\ndefault allow := false\n",
+            )
+            .add_policy(
+                "test.b",
+                "package test.b\n\nimport data.test.a\n\ndefault allow := false\n",
+            )
+            .add_root("test")
+            .build();
+
+        let report = PolicyValidator::new().validate_bundle(&bundle);
+
+        let cycles: Vec<_> = report
+            .errors()
+            .into_iter()
+            .filter(|e| e.message.starts_with("Circular import"))
+            .collect();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(
+            cycles[0].message,
+            "Circular import: test.a -> test.b -> test.a"
+        );
+    }
+
+    #[test]
+    fn test_suppressed_lint_violations_are_counted() {
+        let source = r#"
+package test.authz
+
+import future.keywords.if
+
+default allow := false
+
+# eunomia-lint: disable=security/no-wildcard-allow
+allow := true
+"#;
+
+        let report = PolicyValidator::new().validate_source_str(source, "test.rego");
+
+        assert!(!report
+            .issues
+            .iter()
+            .any(|i| i.rule_id.as_deref() == Some("security/no-wildcard-allow")));
+        assert_eq!(report.suppressed_lint_violations, 1);
+    }
+
+    #[test]
+    fn test_with_linter_keeps_disabled_rules() {
+        let config = ValidatorConfig {
+            disabled_lint_rules: vec!["security/no-wildcard-allow".to_string()],
+            ..Default::default()
+        };
+        let mut linter = Linter::new();
+        linter.enable_rule("style/package-naming");
+
+        let validator = PolicyValidator::with_config(config).with_linter(linter);
+
+        assert!(!validator
+            .linter
+            .is_rule_enabled("security/no-wildcard-allow"));
+        assert!(validator.linter.is_rule_enabled("style/package-naming"));
+    }
+
+    const V0_POLICY: &str = r#"package test.authz
+
+default allow = false
+
+allow {
+    input.caller.role == "admin"
+}
+
+admins[name] {
+    some i
+    name := data.users[i].name
+}
+
+roles := {"admin", "viewer"}
+
+allow if {
+    some role in input.caller.roles # some x without in is fine in comments
+    role == "editor"
+}
+"#;
+
+    #[test]
+    fn test_rego_v1_checker_finds_v0_constructs() {
+        let issues = RegoV1CompatibilityChecker::new().check(V0_POLICY);
+        let found: Vec<_> = issues
+            .iter()
+            .map(|i| (i.line, i.construct.as_str()))
+            .collect();
+
+        assert_eq!(
+            found,
+            vec![
+                (3, "rule head assigned with `=`"),
+                (5, "rule body without `if`"),
+                (9, "partial rule without `if`"),
+                (10, "`some` declaration without `in`"),
+            ]
+        );
+        assert!(issues[0].suggestion.contains(":="));
+    }
+
+    #[test]
+    fn test_rego_v1_checker_accepts_v1_policy() {
+        let source = r#"package test.authz
+
+import rego.v1
+
+default allow := false
+
+allow if {
+    some role in input.caller.roles
+    role == "admin"
+}
+
+admins contains name if {
+    some user in data.users
+    name := user.name
+}
+
+limits := {"max": 10}
+
+deny[msg] := "denied" if {
+    input.caller.blocked == true
+    msg := "blocked"
+}
+"#;
+
+        assert!(RegoV1CompatibilityChecker::new().check(source).is_empty());
+    }
+
+    #[test]
+    fn test_rego_version_detection() {
+        assert!(!RegoVersion::V0.requires_v1("package a\nimport rego.v1\n"));
+        assert!(RegoVersion::V1.requires_v1("package a\n"));
+        assert!(RegoVersion::Auto.requires_v1("package a\nimport rego.v1\n"));
+        assert!(RegoVersion::Auto.requires_v1("package a\nimport future.keywords.if\n"));
+        assert!(!RegoVersion::Auto.requires_v1("package a\n"));
+
+        assert_eq!("V1".parse::<RegoVersion>().unwrap(), RegoVersion::V1);
+        assert_eq!("auto".parse::<RegoVersion>().unwrap(), RegoVersion::Auto);
+        assert!("v2".parse::<RegoVersion>().is_err());
+    }
+
+    #[test]
+    fn test_validator_reports_rego_v1_issues() {
+        let config = ValidatorConfig {
+            rego_version: RegoVersion::V1,
+            run_linting: false,
+            ..Default::default()
+        };
+        let report = PolicyValidator::with_config(config).validate_source_str(V0_POLICY, "v0.rego");
+
+        let compat: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.category == IssueCategory::Compatibility)
+            .collect();
+        assert_eq!(compat.len(), 4);
+        assert!(compat.iter().all(|i| i.severity == IssueSeverity::Error));
+
+        let default_report = PolicyValidator::new().validate_source_str(V0_POLICY, "v0.rego");
+        assert!(!default_report
+            .issues
+            .iter()
+            .any(|i| i.category == IssueCategory::Compatibility));
+    }
+
+    const UNKNOWN_OPERATION_POLICY: &str = r#"
+package test.authz
+
+import future.keywords.if
+
+default allow := false
+
+allow if {
+    input.operation_id == "deleteEverything"
+}
+"#;
+
+    #[test]
+    fn test_semantic_stage_reports_unknown_operations() {
+        let report = PolicyValidator::new().validate_source_str(UNKNOWN_OPERATION_POLICY, "a.rego");
+        assert!(!report
+            .issues
+            .iter()
+            .any(|i| i.category == IssueCategory::Semantic));
+
+        let config =
+            ValidatorConfig::default().with_contract(crate::semantic::users_service_contract());
+        let report = PolicyValidator::with_config(config)
+            .validate_source_str(UNKNOWN_OPERATION_POLICY, "a.rego");
+
+        let issue = report
+            .issues
+            .iter()
+            .find(|i| i.rule_id.as_deref() == Some("semantic/unknown-operation"))
+            .unwrap();
+        assert_eq!(issue.category, IssueCategory::Semantic);
+        assert_eq!(issue.severity, IssueSeverity::Warning);
+        assert_eq!(issue.line, Some(9));
+        assert_eq!(issue.file.as_deref(), Some("a.rego"));
+        assert!(report.is_valid());
+        assert!(!report.is_valid_strict());
+    }
+
+    #[test]
+    fn test_semantic_severity_override() {
+        let config = ValidatorConfig::default()
+            .with_contract(crate::semantic::users_service_contract())
+            .with_semantic_severity(SemanticCategory::UnknownOperation, IssueSeverity::Error);
+        let report = PolicyValidator::with_config(config)
+            .validate_source_str(UNKNOWN_OPERATION_POLICY, "a.rego");
+
+        let (errors, _, _) = report.counts();
+        assert_eq!(errors, 1);
+        assert!(!report.is_valid());
+        assert_eq!(report.errors()[0].category, IssueCategory::Semantic);
+    }
 }
@@ -17,10 +17,13 @@
 //!     └── .manifest.sig
 //! ```
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
 use chrono::{DateTime, Utc};
 use flate2::read::GzDecoder;
 use flate2::write::GzEncoder;
@@ -29,6 +32,73 @@ use hex;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tar::{Archive, Builder};
+use thiserror::Error;
+
+use crate::signing::{BundleSignature, SignatureFile, SigningAlgorithm};
+
+/// Path of the OPA signature file inside a bundle archive.
+pub const SIGNATURE_FILE_PATH: &str = ".signatures/.manifest.sig";
+
+/// Length of the AES-GCM nonce prefixed to an [`EncryptedBundle`].
+const NONCE_LEN: usize = 12;
+
+/// Length of the AES-GCM authentication tag appended to an [`EncryptedBundle`].
+const TAG_LEN: usize = 16;
+
+/// Default cap on the total decompressed size of a bundle archive (64 MiB).
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Default cap on the number of entries in a bundle archive.
+pub const DEFAULT_MAX_ENTRIES: usize = 10_000;
+
+/// Limits applied when reading an untrusted bundle archive.
+///
+/// Bundles fetched from a registry are untrusted input, so reading them is
+/// bounded both in total decompressed size (to defuse gzip bombs) and in the
+/// number of archive entries.
+///
+/// # Examples
+///
+/// ```
+/// use eunomia_core::ArchiveLimits;
+///
+/// let limits = ArchiveLimits::default()
+///     .with_max_decompressed_size(1024 * 1024)
+///     .with_max_entries(100);
+/// assert_eq!(limits.max_entries, 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveLimits {
+    /// Maximum total size in bytes of all entry contents.
+    pub max_decompressed_size: u64,
+    /// Maximum number of entries, including directories.
+    pub max_entries: usize,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+impl ArchiveLimits {
+    /// Sets the maximum total decompressed size in bytes.
+    #[must_use]
+    pub const fn with_max_decompressed_size(mut self, bytes: u64) -> Self {
+        self.max_decompressed_size = bytes;
+        self
+    }
+
+    /// Sets the maximum number of archive entries.
+    #[must_use]
+    pub const fn with_max_entries(mut self, entries: usize) -> Self {
+        self.max_entries = entries;
+        self
+    }
+}
 
 /// A compiled policy bundle ready for distribution.
 ///
@@ -70,13 +140,17 @@ pub struct Bundle {
     /// Bundle manifest with additional metadata.
     pub manifest: BundleManifest,
 
-    /// Ed25519 signature of the bundle contents.
+    /// Signature of the bundle contents (base64).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signature: Option<String>,
 
     /// Public key ID used for signing.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub signing_key_id: Option<String>,
+
+    /// Algorithm of [`Bundle::signature`] (Ed25519 if unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_algorithm: Option<SigningAlgorithm>,
 }
 
 /// Metadata about a bundle.
@@ -96,6 +170,40 @@ pub struct BundleManifest {
     /// Custom metadata.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
+
+    /// Bundles merged into this one at build time.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<BundleDependency>,
+}
+
+/// A bundle whose policies were merged into another bundle at build time.
+///
+/// Recorded in the manifest under `metadata.eunomia.dependencies` for
+/// provenance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleDependency {
+    /// Name of the dependency bundle.
+    pub name: String,
+
+    /// Resolved version of the dependency bundle.
+    pub version: String,
+
+    /// Digest of the OCI manifest the dependency bundle was fetched by
+    /// (`sha256:<hex>`), which pins it in the registry.
+    pub digest: String,
+}
+
+impl BundleDependency {
+    /// Records `bundle`, fetched by the manifest with `digest`, as a
+    /// dependency.
+    #[must_use]
+    pub fn of(bundle: &Bundle, digest: impl Into<String>) -> Self {
+        Self {
+            name: bundle.name.clone(),
+            version: bundle.version.clone(),
+            digest: digest.into(),
+        }
+    }
 }
 
 impl Bundle {
@@ -145,6 +253,84 @@ impl Bundle {
         format!("{}-v{}.bundle.tar.gz", self.name, self.version)
     }
 
+    /// Computes the differences between two bundles.
+    ///
+    /// Policies are compared by package name and data files by path. Results
+    /// are sorted so the diff is deterministic regardless of map ordering.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eunomia_core::Bundle;
+    ///
+    /// let old = Bundle::builder("users-service")
+    ///     .version("1.0.0")
+    ///     .add_policy("users_service.authz", "package users_service.authz")
+    ///     .build();
+    /// let new = Bundle::builder("users-service")
+    ///     .version("1.1.0")
+    ///     .add_policy("users_service.authz", "package users_service.authz")
+    ///     .add_policy("users_service.roles", "package users_service.roles")
+    ///     .build();
+    ///
+    /// let diff = Bundle::diff(&old, &new);
+    /// assert_eq!(diff.added, vec!["users_service.roles"]);
+    /// ```
+    #[must_use]
+    pub fn diff(old: &Self, new: &Self) -> BundleDiff {
+        let mut diff = BundleDiff::default();
+
+        for (package, new_source) in &new.policies {
+            match old.policies.get(package) {
+                None => diff.added.push(package.clone()),
+                Some(old_source) if old_source != new_source => {
+                    diff.modified.push(PolicyDiff {
+                        package: package.clone(),
+                        old_source: old_source.clone(),
+                        new_source: new_source.clone(),
+                        line_changes: count_line_changes(old_source, new_source),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+
+        diff.removed = old
+            .policies
+            .keys()
+            .filter(|package| !new.policies.contains_key(*package))
+            .cloned()
+            .collect();
+
+        for (path, new_content) in &new.data_files {
+            match old.data_files.get(path) {
+                None => diff.added_data_files.push(path.clone()),
+                Some(old_content) if old_content != new_content => {
+                    diff.modified_data_files.push(path.clone());
+                }
+                Some(_) => {}
+            }
+        }
+
+        diff.removed_data_files = old
+            .data_files
+            .keys()
+            .filter(|path| !new.data_files.contains_key(*path))
+            .cloned()
+            .collect();
+
+        diff.manifest_changes = diff_manifests(old, new);
+
+        diff.added.sort();
+        diff.removed.sort();
+        diff.modified.sort_by(|a, b| a.package.cmp(&b.package));
+        diff.added_data_files.sort();
+        diff.removed_data_files.sort();
+        diff.modified_data_files.sort();
+
+        diff
+    }
+
     /// Converts a package name to a path within the bundle.
     ///
     /// Example: `"users_service.authz"` → `"users_service/authz.rego"`
@@ -160,6 +346,144 @@ impl Bundle {
         }
     }
 
+    /// Returns the manifest roots, or the top-level namespace of every
+    /// policy package if none are declared.
+    ///
+    /// OPA treats an empty roots list as owning all of `data`, so a bundle
+    /// without declared roots claims only the namespaces its policies use.
+    #[must_use]
+    pub fn effective_roots(&self) -> Vec<String> {
+        if !self.manifest.roots.is_empty() {
+            return self.manifest.roots.clone();
+        }
+        let roots: BTreeSet<&str> = self
+            .policies
+            .keys()
+            .map(|package| package.split('.').next().unwrap_or(package))
+            .collect();
+        roots.into_iter().map(ToString::to_string).collect()
+    }
+
+    /// Returns the policy packages that fall under none of the manifest
+    /// roots, sorted.
+    ///
+    /// Always empty if the manifest declares no roots.
+    #[must_use]
+    pub fn packages_outside_roots(&self) -> Vec<&str> {
+        if self.manifest.roots.is_empty() {
+            return Vec::new();
+        }
+        let mut packages: Vec<&str> = self
+            .policies
+            .keys()
+            .map(String::as_str)
+            .filter(|package| {
+                !self
+                    .manifest
+                    .roots
+                    .iter()
+                    .any(|root| package_under_root(package, root))
+            })
+            .collect();
+        packages.sort_unstable();
+        packages
+    }
+
+    /// Checks the structural integrity of the bundle.
+    ///
+    /// This is independent of signature verification: a correctly signed
+    /// bundle can still be malformed, and an unsigned bundle can be valid.
+    /// Checks run in order and the first failure is returned:
+    ///
+    /// 1. Every policy source parses as Rego
+    /// 2. Every manifest root is the namespace of at least one policy or
+    ///    holds at least one data file
+    /// 3. `version` is a valid semantic version
+    /// 4. `name` is non-empty and contains only `[a-z0-9-]`
+    /// 5. Every `.json` data file contains valid JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`BundleValidationError`] for the first failing check.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eunomia_core::Bundle;
+    ///
+    /// let bundle = Bundle::builder("users-service")
+    ///     .version("1.0.0")
+    ///     .add_policy("users_service.authz", "package users_service.authz\n\ndefault allow := false")
+    ///     .add_root("users_service")
+    ///     .build();
+    ///
+    /// assert!(bundle.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), BundleValidationError> {
+        let mut packages: Vec<&String> = self.policies.keys().collect();
+        packages.sort();
+
+        for package in &packages {
+            let source = &self.policies[*package];
+            let mut engine = regorus::Engine::new();
+            engine
+                .add_policy(Self::package_to_path(package), source.clone())
+                .map_err(|e| BundleValidationError::InvalidPolicy {
+                    package: (*package).clone(),
+                    message: format!("{e:#}"),
+                })?;
+        }
+
+        for root in &self.manifest.roots {
+            let prefix = format!("{}/", root.trim_matches('/'));
+            let covered = prefix == "/"
+                || packages
+                    .iter()
+                    .any(|package| package_under_root(package, root))
+                || self
+                    .data_files
+                    .keys()
+                    .any(|path| path.trim_start_matches('/').starts_with(&prefix));
+            if !covered {
+                return Err(BundleValidationError::RootMismatch { root: root.clone() });
+            }
+        }
+
+        if !is_valid_semver(&self.version) {
+            return Err(BundleValidationError::InvalidVersion {
+                version: self.version.clone(),
+            });
+        }
+
+        if self.name.is_empty()
+            || !self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return Err(BundleValidationError::InvalidName {
+                name: self.name.clone(),
+            });
+        }
+
+        let mut data_paths: Vec<&String> = self.data_files.keys().collect();
+        data_paths.sort();
+
+        for path in data_paths
+            .into_iter()
+            .filter(|p| p.to_ascii_lowercase().ends_with(".json"))
+        {
+            serde_json::from_str::<serde_json::Value>(&self.data_files[path]).map_err(|e| {
+                BundleValidationError::InvalidDataFile {
+                    path: path.clone(),
+                    message: e.to_string(),
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+
     /// Computes the SHA-256 checksum of the bundle contents.
     ///
     /// The checksum is computed over sorted file paths and contents
@@ -211,7 +535,9 @@ impl Bundle {
     /// Generates the OPA-compatible manifest JSON.
     ///
     /// The manifest follows OPA's bundle specification with Eunomia extensions
-    /// stored under `metadata.eunomia`.
+    /// stored under `metadata.eunomia`. Its roots are the
+    /// [`effective_roots`](Self::effective_roots), so it never claims all of
+    /// `data`.
     #[must_use]
     pub fn generate_manifest(&self) -> serde_json::Value {
         let revision = self
@@ -223,9 +549,9 @@ impl Bundle {
 
         let checksum = self.compute_checksum();
 
-        serde_json::json!({
+        let mut manifest = serde_json::json!({
             "revision": revision.to_string(),
-            "roots": self.manifest.roots,
+            "roots": self.effective_roots(),
             "metadata": {
                 "eunomia": {
                     "version": self.version,
@@ -238,7 +564,14 @@ impl Bundle {
                     "value": checksum
                 }
             }
-        })
+        });
+
+        if !self.manifest.dependencies.is_empty() {
+            manifest["metadata"]["eunomia"]["dependencies"] =
+                serde_json::json!(self.manifest.dependencies);
+        }
+
+        manifest
     }
 
     /// Writes the bundle to a tar.gz file.
@@ -282,6 +615,32 @@ impl Bundle {
     ///
     /// Returns an error if writing fails.
     pub fn write_to_writer<W: Write>(&self, writer: W) -> crate::Result<()> {
+        self.write_archive(writer, self.signature_file().as_ref())
+    }
+
+    /// Returns the signature file for this bundle's embedded signature, if any.
+    pub(crate) fn signature_file(&self) -> Option<SignatureFile> {
+        let (Some(value), Some(key_id)) = (&self.signature, &self.signing_key_id) else {
+            return None;
+        };
+
+        let mut file = SignatureFile::new();
+        file.add_signature(BundleSignature {
+            key_id: key_id.clone(),
+            algorithm: self.signing_algorithm.unwrap_or_default(),
+            value: value.clone(),
+        });
+        Some(file)
+    }
+
+    /// Writes the bundle archive with an optional signature file.
+    ///
+    /// When `signatures` is non-empty it is stored at [`SIGNATURE_FILE_PATH`].
+    pub(crate) fn write_archive<W: Write>(
+        &self,
+        writer: W,
+        signatures: Option<&SignatureFile>,
+    ) -> crate::Result<()> {
         let encoder = GzEncoder::new(writer, Compression::default());
         let mut archive = Builder::new(encoder);
 
@@ -293,17 +652,34 @@ impl Bundle {
             })?;
         Self::add_bytes_to_archive(&mut archive, ".manifest", &manifest_bytes)?;
 
-        // Add policies
-        for (package, source) in &self.policies {
-            let path = Self::package_to_path(package);
+        // Add policies and data files in path order so the archive layout
+        // does not depend on map iteration order
+        let mut policies: Vec<_> = self
+            .policies
+            .iter()
+            .map(|(package, source)| (Self::package_to_path(package), source))
+            .collect();
+        policies.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        for (path, source) in policies {
             Self::add_bytes_to_archive(&mut archive, &path, source.as_bytes())?;
         }
 
-        // Add data files
-        for (path, content) in &self.data_files {
+        let mut data_files: Vec<_> = self.data_files.iter().collect();
+        data_files.sort_unstable_by_key(|(path, _)| *path);
+        for (path, content) in data_files {
             Self::add_bytes_to_archive(&mut archive, path, content.as_bytes())?;
         }
 
+        // Add signatures
+        if let Some(signatures) = signatures.filter(|s| !s.is_empty()) {
+            let json = signatures
+                .to_json()
+                .map_err(|e| crate::Error::Serialization {
+                    message: format!("failed to serialize signatures: {e}"),
+                })?;
+            Self::add_bytes_to_archive(&mut archive, SIGNATURE_FILE_PATH, json.as_bytes())?;
+        }
+
         // Finish the archive
         let encoder = archive.into_inner().map_err(|e| crate::Error::Io {
             message: format!("failed to finish archive: {e}"),
@@ -373,14 +749,51 @@ impl Bundle {
 
     /// Reads a bundle from any reader containing tar.gz data.
     ///
+    /// If the archive contains a signature file, the first signature is
+    /// exposed through [`Bundle::signature`] and [`Bundle::signing_key_id`].
+    /// Use [`SignedBundle::from_reader`](crate::SignedBundle::from_reader) to
+    /// keep every signature.
+    ///
+    /// The archive is read with the default [`ArchiveLimits`].
+    ///
     /// # Errors
     ///
-    /// Returns an error if the data is not a valid bundle.
+    /// Returns an error if the data is not a valid bundle, or
+    /// [`Error::InvalidInput`](crate::Error::InvalidInput) if an entry has an
+    /// unsafe path, is not valid UTF-8, or the archive exceeds the limits.
     pub fn from_reader<R: Read>(reader: R) -> crate::Result<Self> {
+        Self::from_reader_with_limits(reader, ArchiveLimits::default())
+    }
+
+    /// Reads a bundle from any reader containing tar.gz data, enforcing the
+    /// given size and entry-count limits.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Bundle::from_reader`].
+    pub fn from_reader_with_limits<R: Read>(
+        reader: R,
+        limits: ArchiveLimits,
+    ) -> crate::Result<Self> {
+        Self::read_archive(reader, limits).map(|(bundle, _)| bundle)
+    }
+
+    /// Reads a bundle archive, returning the bundle and its signature file.
+    ///
+    /// Entries with absolute paths or `..` components are rejected rather
+    /// than skipped, since a bundle containing them was not produced by
+    /// [`Bundle::write_to_writer`].
+    pub(crate) fn read_archive<R: Read>(
+        reader: R,
+        limits: ArchiveLimits,
+    ) -> crate::Result<(Self, Option<SignatureFile>)> {
         let decoder = GzDecoder::new(reader);
         let mut archive = Archive::new(decoder);
+        let mut entry_count = 0usize;
+        let mut remaining = limits.max_decompressed_size;
 
         let mut manifest_data: Option<serde_json::Value> = None;
+        let mut signatures: Option<SignatureFile> = None;
         let mut policies = HashMap::new();
         let mut data_files = HashMap::new();
 
@@ -391,20 +804,50 @@ impl Bundle {
                 message: format!("failed to read archive entry: {e}"),
             })?;
 
-            let path = entry
-                .path()
-                .map_err(|e| crate::Error::Io {
-                    message: format!("failed to get entry path: {e}"),
-                })?
-                .to_string_lossy()
-                .to_string();
+            entry_count += 1;
+            if entry_count > limits.max_entries {
+                return Err(crate::Error::InvalidInput {
+                    reason: format!(
+                        "bundle archive has more than {} entries",
+                        limits.max_entries
+                    ),
+                });
+            }
+
+            let entry_path = entry.path().map_err(|e| crate::Error::Io {
+                message: format!("failed to get entry path: {e}"),
+            })?;
+            let path = entry_path.to_string_lossy().to_string();
+            Self::check_entry_path(&entry_path, &path)?;
 
-            let mut contents = String::new();
+            let size = entry.header().size().map_err(|e| crate::Error::Io {
+                message: format!("failed to read size of entry '{path}': {e}"),
+            })?;
+            if size > remaining {
+                return Err(crate::Error::InvalidInput {
+                    reason: format!(
+                        "bundle entry '{path}' exceeds the decompressed size limit of {} bytes",
+                        limits.max_decompressed_size
+                    ),
+                });
+            }
+            remaining -= size;
+
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+
+            let mut bytes = Vec::new();
             entry
-                .read_to_string(&mut contents)
+                .by_ref()
+                .take(size)
+                .read_to_end(&mut bytes)
                 .map_err(|e| crate::Error::Io {
-                    message: format!("failed to read entry contents: {e}"),
+                    message: format!("failed to read entry '{path}': {e}"),
                 })?;
+            let contents = String::from_utf8(bytes).map_err(|_| crate::Error::InvalidInput {
+                reason: format!("bundle entry '{path}' is not valid UTF-8"),
+            })?;
 
             let path_obj = Path::new(&path);
 
@@ -414,6 +857,12 @@ impl Bundle {
                         message: format!("failed to parse manifest: {e}"),
                     }
                 })?);
+            } else if path == SIGNATURE_FILE_PATH {
+                signatures = Some(SignatureFile::from_json(&contents).map_err(|e| {
+                    crate::Error::Serialization {
+                        message: format!("failed to parse signatures: {e}"),
+                    }
+                })?);
             } else if path_obj
                 .extension()
                 .is_some_and(|ext| ext.eq_ignore_ascii_case("rego"))
@@ -432,7 +881,35 @@ impl Bundle {
             reason: "bundle missing .manifest file".to_string(),
         })?;
 
-        Self::from_manifest(&manifest, policies, data_files)
+        let mut bundle = Self::from_manifest(&manifest, policies, data_files)?;
+
+        if let Some(first) = signatures.as_ref().and_then(|s| s.signatures.first()) {
+            bundle.signature = Some(first.value.clone());
+            bundle.signing_key_id = Some(first.key_id.clone());
+            bundle.signing_algorithm = Some(first.algorithm);
+        }
+
+        Ok((bundle, signatures))
+    }
+
+    /// Rejects archive entry paths that could escape the bundle root.
+    fn check_entry_path(entry_path: &Path, display: &str) -> crate::Result<()> {
+        for component in entry_path.components() {
+            match component {
+                Component::RootDir | Component::Prefix(_) => {
+                    return Err(crate::Error::InvalidInput {
+                        reason: format!("bundle entry '{display}' has an absolute path"),
+                    });
+                }
+                Component::ParentDir => {
+                    return Err(crate::Error::InvalidInput {
+                        reason: format!("bundle entry '{display}' contains a '..' component"),
+                    });
+                }
+                Component::CurDir | Component::Normal(_) => {}
+            }
+        }
+        Ok(())
     }
 
     /// Reads a bundle from raw bytes.
@@ -444,6 +921,44 @@ impl Bundle {
         Self::from_reader(std::io::Cursor::new(bytes))
     }
 
+    /// Encrypts the serialized bundle with AES-256-GCM.
+    ///
+    /// A fresh random nonce is generated for every call, so encrypting the
+    /// same bundle twice yields different ciphertexts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be serialized or encrypted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use eunomia_core::Bundle;
+    ///
+    /// let key = [7u8; 32];
+    /// let bundle = Bundle::builder("users-service").version("1.0.0").build();
+    ///
+    /// let encrypted = bundle.encrypt(&key).unwrap();
+    /// let decrypted = encrypted.decrypt(&key).unwrap();
+    /// assert_eq!(decrypted.name, "users-service");
+    /// ```
+    pub fn encrypt(&self, key: &[u8; 32]) -> Result<EncryptedBundle, CryptoError> {
+        let plaintext = self.to_bytes().map_err(|e| CryptoError::Serialization {
+            message: e.to_string(),
+        })?;
+
+        let cipher = Aes256Gcm::new(key.into());
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| CryptoError::EncryptionFailed)?;
+
+        let mut data = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        Ok(EncryptedBundle(data))
+    }
+
     /// Converts a path back to a package name.
     ///
     /// Example: `"users_service/authz.rego"` → `"users_service.authz"`
@@ -495,6 +1010,15 @@ impl Bundle {
             .and_then(|s| s.parse().ok())
             .unwrap_or(0);
 
+        let dependencies = eunomia
+            .and_then(|e| e.get("dependencies"))
+            .map(|v| serde_json::from_value(v.clone()))
+            .transpose()
+            .map_err(|e| crate::Error::Serialization {
+                message: format!("failed to parse manifest dependencies: {e}"),
+            })?
+            .unwrap_or_default();
+
         let roots: Vec<String> = manifest
             .get("roots")
             .and_then(|v| v.as_array())
@@ -517,89 +1041,703 @@ impl Bundle {
                 roots,
                 opa_version: None,
                 metadata: HashMap::new(),
+                dependencies,
             },
             signature: None,
             signing_key_id: None,
+            signing_algorithm: None,
         })
     }
 }
 
-/// Builder for constructing [`Bundle`] instances.
-#[derive(Debug)]
-pub struct BundleBuilder {
-    name: String,
-    version: Option<String>,
-    git_commit: Option<String>,
-    policies: HashMap<String, String>,
-    data_files: HashMap<String, String>,
-    manifest: BundleManifest,
-}
+/// Differences between two versions of a bundle.
+///
+/// Produced by [`Bundle::diff`]. Policy entries are identified by package
+/// name; data file entries by their path within the bundle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleDiff {
+    /// Package names present only in the new bundle.
+    pub added: Vec<String>,
 
-impl BundleBuilder {
-    /// Creates a new bundle builder.
-    fn new(name: impl Into<String>) -> Self {
-        Self {
-            name: name.into(),
-            version: None,
-            git_commit: None,
-            policies: HashMap::new(),
-            data_files: HashMap::new(),
-            manifest: BundleManifest::default(),
-        }
-    }
+    /// Package names present only in the old bundle.
+    pub removed: Vec<String>,
 
-    /// Sets the bundle version.
-    #[must_use]
-    pub fn version(mut self, version: impl Into<String>) -> Self {
-        self.version = Some(version.into());
-        self
-    }
+    /// Policies present in both bundles whose source changed.
+    pub modified: Vec<PolicyDiff>,
 
-    /// Sets the git commit SHA.
-    #[must_use]
-    pub fn git_commit(mut self, commit: impl Into<String>) -> Self {
-        self.git_commit = Some(commit.into());
-        self
-    }
+    /// Data file paths present only in the new bundle.
+    #[serde(default)]
+    pub added_data_files: Vec<String>,
 
-    /// Adds a policy to the bundle.
-    #[must_use]
-    pub fn add_policy(
-        mut self,
-        package_name: impl Into<String>,
-        source: impl Into<String>,
-    ) -> Self {
-        self.policies.insert(package_name.into(), source.into());
-        self
-    }
+    /// Data file paths present only in the old bundle.
+    #[serde(default)]
+    pub removed_data_files: Vec<String>,
 
-    /// Adds a data file to the bundle.
-    #[must_use]
-    pub fn add_data_file(mut self, path: impl Into<String>, content: impl Into<String>) -> Self {
-        self.data_files.insert(path.into(), content.into());
-        self
-    }
+    /// Data file paths present in both bundles whose content changed.
+    #[serde(default)]
+    pub modified_data_files: Vec<String>,
 
-    /// Sets the manifest revision.
-    #[must_use]
-    pub const fn revision(mut self, revision: u64) -> Self {
-        self.manifest.revision = revision;
-        self
-    }
+    /// Manifest fields whose value changed, sorted by field.
+    #[serde(default)]
+    pub manifest_changes: Vec<ManifestChange>,
+}
 
-    /// Adds a root document to the manifest.
+impl BundleDiff {
+    /// Returns true if the bundles have identical policies, data files and
+    /// manifests.
     #[must_use]
-    pub fn add_root(mut self, root: impl Into<String>) -> Self {
-        self.manifest.roots.push(root.into());
-        self
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.added_data_files.is_empty()
+            && self.removed_data_files.is_empty()
+            && self.modified_data_files.is_empty()
+            && self.manifest_changes.is_empty()
     }
 
-    /// Sets the OPA version compatibility.
+    /// Returns a human-readable one-line summary of the diff.
+    ///
+    /// Example: `"2 added, 1 removed, 1 modified (14 lines changed), 1 data file changed"`
+    ///
+    /// Manifest changes are appended as `", 1 manifest field changed"`.
     #[must_use]
-    pub fn opa_version(mut self, version: impl Into<String>) -> Self {
-        self.manifest.opa_version = Some(version.into());
-        self
-    }
+    pub fn summary(&self) -> String {
+        if self.is_empty() {
+            return "no changes".to_string();
+        }
+
+        let line_changes: usize = self.modified.iter().map(|p| p.line_changes).sum();
+        let mut summary = format!(
+            "{} added, {} removed, {} modified ({line_changes} lines changed)",
+            self.added.len(),
+            self.removed.len(),
+            self.modified.len(),
+        );
+
+        let data_changes = self.added_data_files.len()
+            + self.removed_data_files.len()
+            + self.modified_data_files.len();
+        if data_changes > 0 {
+            let noun = if data_changes == 1 { "file" } else { "files" };
+            let _ = write!(summary, ", {data_changes} data {noun} changed");
+        }
+
+        let manifest_changes = self.manifest_changes.len();
+        if manifest_changes > 0 {
+            let noun = if manifest_changes == 1 {
+                "field"
+            } else {
+                "fields"
+            };
+            let _ = write!(summary, ", {manifest_changes} manifest {noun} changed");
+        }
+
+        summary
+    }
+}
+
+/// A policy whose source differs between two bundles.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyDiff {
+    /// Package name of the policy.
+    pub package: String,
+
+    /// Source in the old bundle.
+    pub old_source: String,
+
+    /// Source in the new bundle.
+    pub new_source: String,
+
+    /// Approximate number of changed lines.
+    ///
+    /// Counts lines that differ at the same position plus the difference in
+    /// line count; this is not a minimal edit distance.
+    pub line_changes: usize,
+}
+
+/// A manifest field whose value differs between two bundles.
+///
+/// Custom metadata entries are reported as `metadata.<key>`. A value of
+/// `None` means the field is unset (or the metadata key absent) in that
+/// bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestChange {
+    /// Manifest field name, e.g. `revision`, `git_commit` or `metadata.team`.
+    pub field: String,
+
+    /// Value in the old bundle.
+    pub old: Option<String>,
+
+    /// Value in the new bundle.
+    pub new: Option<String>,
+}
+
+/// Structural problems detected by [`Bundle::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BundleValidationError {
+    /// A policy source is not valid Rego.
+    #[error("Policy '{package}' failed to parse: {message}")]
+    InvalidPolicy {
+        /// Package name of the offending policy.
+        package: String,
+        /// Parser error message.
+        message: String,
+    },
+
+    /// A manifest root holds no policy package and no data file.
+    #[error("Manifest root '{root}' does not match any policy package or data file")]
+    RootMismatch {
+        /// The unmatched root.
+        root: String,
+    },
+
+    /// The bundle version is not a valid semantic version.
+    #[error("Invalid bundle version '{version}': expected MAJOR.MINOR.PATCH")]
+    InvalidVersion {
+        /// The rejected version string.
+        version: String,
+    },
+
+    /// The bundle name is empty or contains disallowed characters.
+    #[error("Invalid bundle name '{name}': must be non-empty and contain only [a-z0-9-]")]
+    InvalidName {
+        /// The rejected name.
+        name: String,
+    },
+
+    /// A JSON data file does not contain valid JSON.
+    #[error("Data file '{path}' is not valid JSON: {message}")]
+    InvalidDataFile {
+        /// Path of the data file within the bundle.
+        path: String,
+        /// JSON parser error message.
+        message: String,
+    },
+}
+
+/// A bundle encrypted with AES-256-GCM by [`Bundle::encrypt`].
+///
+/// The layout is `nonce (12 bytes) || ciphertext || tag (16 bytes)`, where
+/// the plaintext is the bundle's tar.gz serialization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedBundle(Vec<u8>);
+
+impl EncryptedBundle {
+    /// Wraps raw encrypted bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CryptoError::InvalidCiphertext`] if `data` is too short to
+    /// hold a nonce and authentication tag.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, CryptoError> {
+        if data.len() < NONCE_LEN + TAG_LEN {
+            return Err(CryptoError::InvalidCiphertext {
+                len: data.len(),
+                min: NONCE_LEN + TAG_LEN,
+            });
+        }
+        Ok(Self(data.to_vec()))
+    }
+
+    /// Returns the encrypted bytes.
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    /// Returns the encrypted bytes without copying.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Decrypts and deserializes the bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CryptoError::DecryptionFailed`] if the key is wrong or the
+    /// data has been tampered with, [`CryptoError::InvalidCiphertext`] if
+    /// the data is truncated, and [`CryptoError::Serialization`] if the
+    /// decrypted bytes are not a valid bundle.
+    pub fn decrypt(&self, key: &[u8; 32]) -> Result<Bundle, CryptoError> {
+        if self.0.len() < NONCE_LEN + TAG_LEN {
+            return Err(CryptoError::InvalidCiphertext {
+                len: self.0.len(),
+                min: NONCE_LEN + TAG_LEN,
+            });
+        }
+
+        let (nonce, ciphertext) = self.0.split_at(NONCE_LEN);
+        let cipher = Aes256Gcm::new(key.into());
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+
+        Bundle::from_bytes(&plaintext).map_err(|e| CryptoError::Serialization {
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Errors from [`Bundle::encrypt`] and [`EncryptedBundle::decrypt`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CryptoError {
+    /// Decryption failed because the key is wrong or the data was modified.
+    #[error("Bundle decryption failed: wrong key or corrupted data")]
+    DecryptionFailed,
+
+    /// The encrypted data is too short to contain a nonce and tag.
+    #[error("Invalid ciphertext: {len} bytes, expected at least {min}")]
+    InvalidCiphertext {
+        /// Length of the data received.
+        len: usize,
+        /// Minimum valid length.
+        min: usize,
+    },
+
+    /// Encryption failed.
+    #[error("Bundle encryption failed")]
+    EncryptionFailed,
+
+    /// The bundle could not be serialized or deserialized.
+    #[error("Bundle serialization failed: {message}")]
+    Serialization {
+        /// Error message.
+        message: String,
+    },
+}
+
+/// Returns true if `version` is a semantic version (`MAJOR.MINOR.PATCH`
+/// with optional `-prerelease` and `+build` suffixes).
+fn is_valid_semver(version: &str) -> bool {
+    let (rest, build) = version
+        .split_once('+')
+        .map_or((version, None), |(v, b)| (v, Some(b)));
+    let (core, pre) = rest
+        .split_once('-')
+        .map_or((rest, None), |(v, p)| (v, Some(p)));
+
+    let is_ident =
+        |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    let suffix_ok = |s: Option<&str>| s.is_none_or(|s| s.split('.').all(is_ident));
+
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts.iter().all(|p| {
+            !p.is_empty()
+                && p.chars().all(|c| c.is_ascii_digit())
+                && (p.len() == 1 || !p.starts_with('0'))
+        })
+        && suffix_ok(pre)
+        && suffix_ok(build)
+}
+
+/// Returns the changed manifest fields between two bundles, sorted by field.
+///
+/// The bundle version is not compared; it is expected to differ.
+fn diff_manifests(old: &Bundle, new: &Bundle) -> Vec<ManifestChange> {
+    let (old_commit, new_commit) = (old.git_commit.clone(), new.git_commit.clone());
+    let (old, new) = (&old.manifest, &new.manifest);
+
+    let mut fields: Vec<(String, Option<String>, Option<String>)> = vec![
+        ("git_commit".to_string(), old_commit, new_commit),
+        (
+            "revision".to_string(),
+            Some(old.revision.to_string()),
+            Some(new.revision.to_string()),
+        ),
+        (
+            "roots".to_string(),
+            Some(old.roots.join(",")),
+            Some(new.roots.join(",")),
+        ),
+        (
+            "opa_version".to_string(),
+            old.opa_version.clone(),
+            new.opa_version.clone(),
+        ),
+    ];
+
+    let keys: BTreeSet<&String> = old.metadata.keys().chain(new.metadata.keys()).collect();
+    fields.extend(keys.into_iter().map(|key| {
+        (
+            format!("metadata.{key}"),
+            old.metadata.get(key).cloned(),
+            new.metadata.get(key).cloned(),
+        )
+    }));
+
+    let mut changes: Vec<ManifestChange> = fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| ManifestChange { field, old, new })
+        .collect();
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    changes
+}
+
+/// Counts changed lines between two sources without computing a full diff.
+fn count_line_changes(old: &str, new: &str) -> usize {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let differing = old_lines
+        .iter()
+        .zip(new_lines.iter())
+        .filter(|(a, b)| a != b)
+        .count();
+
+    differing + old_lines.len().abs_diff(new_lines.len())
+}
+
+/// Errors that can occur in [`BundleBuilder::from_directory`].
+#[derive(Debug, Error)]
+pub enum BundleBuilderError {
+    /// A file or directory could not be read.
+    #[error("I/O error at {path}: {source}")]
+    IoError {
+        /// Path that could not be read.
+        path: PathBuf,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The directory contains no `.rego` files.
+    #[error("No .rego files found in {0}")]
+    EmptyDirectory(PathBuf),
+
+    /// Two policy files declare the same package.
+    #[error("Package '{0}' is declared by more than one policy file")]
+    DuplicatePackage(String),
+
+    /// A `.rego` file has no `package` declaration.
+    #[error("Missing package declaration in {0}")]
+    MissingPackage(PathBuf),
+}
+
+/// Exclusion patterns loaded from a `.bundleignore` file.
+#[derive(Debug, Default)]
+struct BundleIgnore {
+    patterns: Vec<IgnorePattern>,
+}
+
+#[derive(Debug)]
+struct IgnorePattern {
+    glob: String,
+    dir_only: bool,
+    match_path: bool,
+}
+
+impl BundleIgnore {
+    /// File name of the ignore file at the bundle root.
+    const FILE_NAME: &'static str = ".bundleignore";
+
+    fn load(dir: &Path) -> Result<Self, BundleBuilderError> {
+        let path = dir.join(Self::FILE_NAME);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+
+        let content =
+            std::fs::read_to_string(&path).map_err(|source| BundleBuilderError::IoError {
+                path: path.clone(),
+                source,
+            })?;
+
+        Ok(Self::parse(&content))
+    }
+
+    fn parse(content: &str) -> Self {
+        let patterns = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let dir_only = line.ends_with('/');
+                let line = line.trim_end_matches('/');
+                let match_path = line.contains('/');
+                IgnorePattern {
+                    glob: line.trim_start_matches('/').to_string(),
+                    dir_only,
+                    match_path,
+                }
+            })
+            .collect();
+
+        Self { patterns }
+    }
+
+    /// Returns true if the entry at `relative` (using `/` separators) is excluded.
+    fn is_ignored(&self, relative: &str, is_dir: bool) -> bool {
+        let name = relative.rsplit('/').next().unwrap_or(relative);
+        self.patterns.iter().any(|p| {
+            if p.dir_only && !is_dir {
+                return false;
+            }
+            let target = if p.match_path { relative } else { name };
+            glob_match(p.glob.as_bytes(), target.as_bytes())
+        })
+    }
+}
+
+/// Matches `text` against a glob where `*` and `?` stay within a path
+/// segment and `**` spans segments.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((b'*', rest)) => rest.strip_prefix(b"*").map_or_else(
+            || {
+                (0..=text.len())
+                    .take_while(|&i| i == 0 || text[i - 1] != b'/')
+                    .any(|i| glob_match(rest, &text[i..]))
+            },
+            |rest| {
+                // `**/` also matches zero directories.
+                rest.strip_prefix(b"/")
+                    .is_some_and(|after| glob_match(after, text))
+                    || (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+            },
+        ),
+        Some((b'?', rest)) => {
+            text.first().is_some_and(|&c| c != b'/') && glob_match(rest, &text[1..])
+        }
+        Some((c, rest)) => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
+/// Recursively collects files under `dir` that are not ignored, in sorted
+/// order, as `(absolute path, relative path)` pairs.
+fn collect_files(
+    dir: &Path,
+    prefix: &str,
+    ignore: &BundleIgnore,
+    files: &mut Vec<(PathBuf, String)>,
+) -> Result<(), BundleBuilderError> {
+    let io_err = |source| BundleBuilderError::IoError {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    let mut entries = std::fs::read_dir(dir)
+        .map_err(io_err)?
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(io_err)?;
+    entries.sort_by_key(std::fs::DirEntry::file_name);
+
+    for entry in entries {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let relative = if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}/{name}")
+        };
+        let is_dir = path.is_dir();
+
+        if ignore.is_ignored(&relative, is_dir) {
+            continue;
+        }
+
+        if is_dir {
+            collect_files(&path, &relative, ignore, files)?;
+        } else {
+            files.push((path, relative));
+        }
+    }
+
+    Ok(())
+}
+
+/// Extracts the package name from the first `package` declaration in a Rego source.
+fn extract_package(source: &str) -> Option<String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .find_map(|line| line.strip_prefix("package "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .map(ToString::to_string)
+}
+
+/// Builder for constructing [`Bundle`] instances.
+#[derive(Debug)]
+pub struct BundleBuilder {
+    name: String,
+    version: Option<String>,
+    git_commit: Option<String>,
+    policies: HashMap<String, String>,
+    data_files: HashMap<String, String>,
+    manifest: BundleManifest,
+    created_at: Option<DateTime<Utc>>,
+    allow_out_of_root: bool,
+}
+
+impl BundleBuilder {
+    /// Creates a new bundle builder.
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            git_commit: None,
+            policies: HashMap::new(),
+            data_files: HashMap::new(),
+            manifest: BundleManifest::default(),
+            created_at: None,
+            allow_out_of_root: false,
+        }
+    }
+
+    /// Creates a builder populated from a directory of policies.
+    ///
+    /// The directory is walked recursively. Every `.rego` file is added as a
+    /// policy keyed by its `package` declaration, and every `data.json` file
+    /// is added as a data file under its path relative to `dir`.
+    ///
+    /// If `dir` contains a `.bundleignore` file, each non-empty line that is
+    /// not a `#` comment is treated as a glob pattern (`*`, `**`, `?`).
+    /// Patterns without a `/` match file or directory names at any depth,
+    /// patterns with a `/` match paths relative to `dir`, and a trailing `/`
+    /// restricts the pattern to directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read, contains no `.rego`
+    /// files, or two files declare the same package.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use eunomia_core::bundle::BundleBuilder;
+    ///
+    /// let bundle = BundleBuilder::from_directory("policies/users-service", "users-service", "1.0.0")
+    ///     .unwrap()
+    ///     .add_root("users_service")
+    ///     .build();
+    /// ```
+    pub fn from_directory(
+        dir: impl AsRef<Path>,
+        name: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Result<Self, BundleBuilderError> {
+        let dir = dir.as_ref();
+        let ignore = BundleIgnore::load(dir)?;
+
+        let mut files = Vec::new();
+        collect_files(dir, "", &ignore, &mut files)?;
+
+        let mut builder = Self::new(name).version(version);
+
+        for (path, relative) in files {
+            let io_err = |source| BundleBuilderError::IoError {
+                path: path.clone(),
+                source,
+            };
+
+            let is_rego = Path::new(&relative)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("rego"));
+            if is_rego {
+                let source = std::fs::read_to_string(&path).map_err(io_err)?;
+                let package = extract_package(&source)
+                    .ok_or_else(|| BundleBuilderError::MissingPackage(path.clone()))?;
+                if builder.policies.contains_key(&package) {
+                    return Err(BundleBuilderError::DuplicatePackage(package));
+                }
+                builder = builder.add_policy(package, source);
+            } else if relative == "data.json" || relative.ends_with("/data.json") {
+                let content = std::fs::read_to_string(&path).map_err(io_err)?;
+                builder = builder.add_data_file(relative, content);
+            }
+        }
+
+        if builder.policies.is_empty() {
+            return Err(BundleBuilderError::EmptyDirectory(dir.to_path_buf()));
+        }
+
+        Ok(builder)
+    }
+
+    /// Sets the bundle version.
+    #[must_use]
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the git commit SHA.
+    #[must_use]
+    pub fn git_commit(mut self, commit: impl Into<String>) -> Self {
+        self.git_commit = Some(commit.into());
+        self
+    }
+
+    /// Adds a policy to the bundle.
+    #[must_use]
+    pub fn add_policy(
+        mut self,
+        package_name: impl Into<String>,
+        source: impl Into<String>,
+    ) -> Self {
+        self.policies.insert(package_name.into(), source.into());
+        self
+    }
+
+    /// Adds a data file to the bundle.
+    #[must_use]
+    pub fn add_data_file(mut self, path: impl Into<String>, content: impl Into<String>) -> Self {
+        self.data_files.insert(path.into(), content.into());
+        self
+    }
+
+    /// Sets the creation timestamp (defaults to the time of `build`).
+    ///
+    /// The manifest revision is derived from this timestamp, so fixing it
+    /// makes two builds of the same policies produce byte-identical archives.
+    #[must_use]
+    pub const fn created_at(mut self, created_at: DateTime<Utc>) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    /// Sets the manifest revision.
+    #[must_use]
+    pub const fn revision(mut self, revision: u64) -> Self {
+        self.manifest.revision = revision;
+        self
+    }
+
+    /// Adds a root document to the manifest.
+    #[must_use]
+    pub fn add_root(mut self, root: impl Into<String>) -> Self {
+        self.manifest.roots.push(root.into());
+        self
+    }
+
+    /// Allows policies whose package falls under none of the declared
+    /// roots, which [`try_build`](Self::try_build) rejects otherwise.
+    ///
+    /// Meant for migrating bundles built before roots were enforced.
+    #[must_use]
+    pub const fn allow_out_of_root(mut self, allow: bool) -> Self {
+        self.allow_out_of_root = allow;
+        self
+    }
+
+    /// Sets the OPA version compatibility.
+    #[must_use]
+    pub fn opa_version(mut self, version: impl Into<String>) -> Self {
+        self.manifest.opa_version = Some(version.into());
+        self
+    }
+
+    /// Records a bundle merged into this one, for provenance.
+    #[must_use]
+    pub fn add_dependency(mut self, dependency: BundleDependency) -> Self {
+        self.manifest.dependencies.push(dependency);
+        self
+    }
 
     /// Adds custom metadata to the manifest.
     #[must_use]
@@ -619,42 +1757,77 @@ impl BundleBuilder {
             name: self.name,
             version: self.version.expect("version is required"),
             git_commit: self.git_commit,
-            created_at: Utc::now(),
+            created_at: self.created_at.unwrap_or_else(Utc::now),
             policies: self.policies,
             data_files: self.data_files,
             manifest: self.manifest,
             signature: None,
             signing_key_id: None,
+            signing_algorithm: None,
         }
     }
 
     /// Attempts to build the bundle, returning an error if required fields are missing.
     ///
+    /// When the manifest declares roots, every policy package must fall
+    /// under one of them, unless [`allow_out_of_root`](Self::allow_out_of_root)
+    /// is set.
+    ///
     /// # Errors
     ///
-    /// Returns an error if version is not set.
+    /// Returns an error if version is not set, or listing the packages
+    /// outside the declared roots.
     pub fn try_build(self) -> crate::Result<Bundle> {
         let version = self.version.ok_or_else(|| crate::Error::InvalidInput {
             reason: "version is required".to_string(),
         })?;
 
-        Ok(Bundle {
+        let bundle = Bundle {
             name: self.name,
             version,
             git_commit: self.git_commit,
-            created_at: Utc::now(),
+            created_at: self.created_at.unwrap_or_else(Utc::now),
             policies: self.policies,
             data_files: self.data_files,
             manifest: self.manifest,
             signature: None,
             signing_key_id: None,
-        })
+            signing_algorithm: None,
+        };
+
+        let outside = bundle.packages_outside_roots();
+        if !self.allow_out_of_root && !outside.is_empty() {
+            return Err(crate::Error::InvalidInput {
+                reason: format!(
+                    "packages outside the bundle roots ({}): {}",
+                    bundle.manifest.roots.join(", "),
+                    outside.join(", ")
+                ),
+            });
+        }
+
+        Ok(bundle)
     }
 }
 
+/// Returns true if `package` is the namespace of `root` or nested in it.
+///
+/// Roots are slash-separated data paths such as `users_service/authz`; an
+/// empty root covers every package.
+#[must_use]
+pub fn package_under_root(package: &str, root: &str) -> bool {
+    let namespace = root.trim_matches('/').replace('/', ".");
+    namespace.is_empty()
+        || package == namespace
+        || package
+            .strip_prefix(namespace.as_str())
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::sample_bundle;
 
     #[test]
     fn test_bundle_builder_basic() {
@@ -725,6 +1898,65 @@ mod tests {
         assert!(err.to_string().contains("version is required"));
     }
 
+    #[test]
+    fn test_bundle_try_build_enforces_roots() {
+        let builder = || {
+            Bundle::builder("users-service")
+                .version("1.0.0")
+                .add_policy("users_service.authz", "package users_service.authz")
+                .add_policy("users_service", "package users_service")
+                .add_policy("orders_service.authz", "package orders_service.authz")
+                .add_policy("users_service_extra", "package users_service_extra")
+                .add_root("users_service")
+        };
+
+        let err = builder().try_build().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: packages outside the bundle roots (users_service): \
+             orders_service.authz, users_service_extra"
+        );
+
+        let bundle = builder().allow_out_of_root(true).try_build().unwrap();
+        assert_eq!(
+            bundle.packages_outside_roots(),
+            vec!["orders_service.authz", "users_service_extra"]
+        );
+        assert!(builder()
+            .add_root("orders_service/authz")
+            .add_root("users_service_extra")
+            .try_build()
+            .is_ok());
+
+        // Without declared roots, nothing is enforced
+        let bundle = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("orders_service.authz", "package orders_service.authz")
+            .try_build()
+            .unwrap();
+        assert!(bundle.packages_outside_roots().is_empty());
+    }
+
+    #[test]
+    fn test_generate_manifest_derives_roots() {
+        let bundle = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("users_service.authz", "package users_service.authz")
+            .add_policy("users_service.roles", "package users_service.roles")
+            .add_policy("common", "package common")
+            .build();
+
+        assert_eq!(bundle.effective_roots(), vec!["common", "users_service"]);
+        let manifest = bundle.generate_manifest();
+        assert_eq!(
+            manifest["roots"],
+            serde_json::json!(["common", "users_service"])
+        );
+
+        let restored = Bundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.manifest.roots, vec!["common", "users_service"]);
+    }
+
     #[test]
     fn test_bundle_serialization() {
         let bundle = Bundle::builder("users-service")
@@ -827,6 +2059,65 @@ mod tests {
         assert!(checksum["value"].as_str().is_some());
     }
 
+    #[test]
+    fn test_bundle_with_fixed_created_at_is_reproducible() {
+        let created_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let packages: Vec<String> = (0..16).map(|i| format!("svc.p{i}")).collect();
+        let build = |order: &mut dyn Iterator<Item = &String>| {
+            order
+                .fold(
+                    Bundle::builder("svc")
+                        .version("1.0.0")
+                        .created_at(created_at),
+                    |builder, package| {
+                        builder
+                            .add_policy(package, format!("package {package}\n"))
+                            .add_data_file(format!("{package}/data.json"), "{}")
+                    },
+                )
+                .build()
+                .to_bytes()
+                .unwrap()
+        };
+
+        let first = build(&mut packages.iter());
+        let second = build(&mut packages.iter().rev());
+
+        assert_eq!(first, second);
+        let restored = Bundle::from_bytes(&first).unwrap();
+        assert_eq!(restored.created_at, created_at);
+    }
+
+    #[test]
+    fn test_manifest_records_dependencies() {
+        const COMMON_DIGEST: &str =
+            "sha256:4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+        let common = Bundle::builder("common-policies")
+            .version("v1.2.3")
+            .add_policy("common.roles", "package common.roles\n")
+            .build();
+        let bundle = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("users_service.authz", "package users_service.authz\n")
+            .add_dependency(BundleDependency::of(&common, COMMON_DIGEST))
+            .build();
+
+        let manifest = bundle.generate_manifest();
+        let recorded = &manifest["metadata"]["eunomia"]["dependencies"][0];
+        assert_eq!(recorded["name"], "common-policies");
+        assert_eq!(recorded["version"], "v1.2.3");
+        assert_eq!(recorded["digest"], COMMON_DIGEST);
+
+        let restored = Bundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            restored.manifest.dependencies,
+            vec![BundleDependency::of(&common, COMMON_DIGEST)]
+        );
+        assert!(sample_bundle().generate_manifest()["metadata"]["eunomia"]
+            .get("dependencies")
+            .is_none());
+    }
+
     #[test]
     fn test_bundle_roundtrip_bytes() {
         let original = Bundle::builder("test-service")
@@ -880,6 +2171,153 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn diff_base() -> BundleBuilder {
+        Bundle::builder("test-service")
+            .version("1.0.0")
+            .add_policy("test.authz", "package test.authz\ndefault allow := false")
+            .add_data_file("test/data.json", r#"{"roles": ["admin"]}"#)
+    }
+
+    #[test]
+    fn test_diff_identical_bundles_is_empty() {
+        let old = diff_base().build();
+        let new = diff_base().version("1.0.1").build();
+
+        let diff = Bundle::diff(&old, &new);
+        assert!(diff.is_empty());
+        assert_eq!(diff.summary(), "no changes");
+    }
+
+    #[test]
+    fn test_diff_added_policy() {
+        let old = diff_base().build();
+        let new = diff_base()
+            .add_policy("test.roles", "package test.roles")
+            .build();
+
+        let diff = Bundle::diff(&old, &new);
+        assert_eq!(diff.added, vec!["test.roles"]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.modified.is_empty());
+        assert!(diff.summary().starts_with("1 added, 0 removed"));
+    }
+
+    #[test]
+    fn test_diff_removed_policy() {
+        let old = diff_base()
+            .add_policy("test.roles", "package test.roles")
+            .build();
+        let new = diff_base().build();
+
+        let diff = Bundle::diff(&old, &new);
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.removed, vec!["test.roles"]);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn test_diff_modified_policy() {
+        let old = diff_base().build();
+        let new = diff_base()
+            .add_policy(
+                "test.authz",
+                "package test.authz\ndefault allow := true\nallow if { input.admin }",
+            )
+            .build();
+
+        let diff = Bundle::diff(&old, &new);
+        assert_eq!(diff.modified.len(), 1);
+
+        let policy = &diff.modified[0];
+        assert_eq!(policy.package, "test.authz");
+        assert!(policy.old_source.contains("allow := false"));
+        assert!(policy.new_source.contains("allow := true"));
+        // One changed line plus one added line
+        assert_eq!(policy.line_changes, 2);
+        assert!(diff.summary().contains("(2 lines changed)"));
+    }
+
+    #[test]
+    fn test_diff_changed_data_file() {
+        let old = diff_base().build();
+        let new = diff_base()
+            .add_data_file("test/data.json", r#"{"roles": ["admin", "viewer"]}"#)
+            .build();
+
+        let diff = Bundle::diff(&old, &new);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(diff.modified.is_empty());
+        assert_eq!(diff.modified_data_files, vec!["test/data.json"]);
+        assert!(diff.summary().ends_with("1 data file changed"));
+    }
+
+    #[test]
+    fn test_diff_manifest_changes() {
+        let old = diff_base()
+            .revision(1)
+            .git_commit("abc123")
+            .metadata("team", "identity")
+            .metadata("ticket", "SEC-1")
+            .build();
+        let new = diff_base()
+            .revision(2)
+            .git_commit("abc123")
+            .opa_version("0.60.0")
+            .metadata("team", "identity")
+            .metadata("owner", "alice")
+            .build();
+
+        let diff = Bundle::diff(&old, &new);
+        let change = |field: &str, old: Option<&str>, new: Option<&str>| ManifestChange {
+            field: field.to_string(),
+            old: old.map(String::from),
+            new: new.map(String::from),
+        };
+        assert_eq!(
+            diff.manifest_changes,
+            vec![
+                change("metadata.owner", None, Some("alice")),
+                change("metadata.ticket", Some("SEC-1"), None),
+                change("opa_version", None, Some("0.60.0")),
+                change("revision", Some("1"), Some("2")),
+            ]
+        );
+        assert!(!diff.is_empty());
+        assert!(diff.summary().ends_with("4 manifest fields changed"));
+    }
+
+    #[test]
+    fn test_bundle_roundtrip_preserves_signature() {
+        let mut bundle = Bundle::builder("test-service")
+            .version("1.0.0")
+            .add_policy("test.authz", "package test.authz")
+            .build();
+        bundle.signature = Some("c2lnbmF0dXJl".to_string());
+        bundle.signing_key_id = Some("prod-2026".to_string());
+
+        let restored = Bundle::from_bytes(&bundle.to_bytes().unwrap()).unwrap();
+
+        assert!(restored.is_signed());
+        assert_eq!(restored.signature, bundle.signature);
+        assert_eq!(restored.signing_key_id, bundle.signing_key_id);
+    }
+
+    #[test]
+    fn test_unsigned_bundle_has_no_signature_file() {
+        let bundle = Bundle::builder("test-service").version("1.0.0").build();
+
+        let bytes = bundle.to_bytes().unwrap();
+        let mut archive = Archive::new(GzDecoder::new(bytes.as_slice()));
+        let has_sig = archive
+            .entries()
+            .unwrap()
+            .any(|e| e.unwrap().path().unwrap().to_string_lossy() == SIGNATURE_FILE_PATH);
+
+        assert!(!has_sig);
+        assert!(!Bundle::from_bytes(&bytes).unwrap().is_signed());
+    }
+
     #[test]
     fn test_bundle_checksum_in_manifest() {
         let bundle = Bundle::builder("test")
@@ -896,4 +2334,388 @@ mod tests {
         assert_eq!(checksum.len(), 64);
         assert!(checksum.chars().all(|c| c.is_ascii_hexdigit()));
     }
+
+    fn valid_bundle() -> BundleBuilder {
+        Bundle::builder("users-service")
+            .version("1.2.0")
+            .add_policy(
+                "users_service.authz",
+                "package users_service.authz\n\ndefault allow := false",
+            )
+            .add_root("users_service")
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_bundle() {
+        let bundle = valid_bundle()
+            .add_data_file("users_service/data.json", r#"{"roles": []}"#)
+            .build();
+
+        assert_eq!(bundle.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_root_without_package() {
+        let bundle = valid_bundle().add_root("billing_service").build();
+
+        assert_eq!(
+            bundle.validate(),
+            Err(BundleValidationError::RootMismatch {
+                root: "billing_service".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_validate_accepts_data_only_root() {
+        let bundle = valid_bundle()
+            .add_root("users_admin")
+            .add_data_file("users_admin/admins.json", r#"["alice"]"#)
+            .build();
+
+        assert_eq!(bundle.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_root_must_match_whole_segment() {
+        let bundle = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("users_service.authz", "package users_service.authz")
+            .add_root("users")
+            .build();
+
+        assert!(matches!(
+            bundle.validate(),
+            Err(BundleValidationError::RootMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_unparseable_policy() {
+        let bundle = valid_bundle()
+            .add_policy(
+                "users_service.broken",
+                "package users_service.broken\nallow {{",
+            )
+            .build();
+
+        assert!(matches!(
+            bundle.validate(),
+            Err(BundleValidationError::InvalidPolicy { package, .. }) if package == "users_service.broken"
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_version() {
+        for version in ["1.0", "v1.0.0", "01.0.0", "1.0.0-", "latest"] {
+            let bundle = valid_bundle().version(version).build();
+            assert!(
+                matches!(
+                    bundle.validate(),
+                    Err(BundleValidationError::InvalidVersion { .. })
+                ),
+                "{version} should be rejected"
+            );
+        }
+
+        let bundle = valid_bundle().version("2.0.0-rc.1+build.5").build();
+        assert_eq!(bundle.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_name() {
+        let mut bundle = valid_bundle().build();
+        bundle.name = "Users_Service".to_string();
+
+        assert!(matches!(
+            bundle.validate(),
+            Err(BundleValidationError::InvalidName { .. })
+        ));
+
+        bundle.name = String::new();
+        assert!(matches!(
+            bundle.validate(),
+            Err(BundleValidationError::InvalidName { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_rejects_invalid_json_data_file() {
+        let bundle = valid_bundle()
+            .add_data_file("users_service/data.json", "{not json")
+            .add_data_file("users_service/notes.txt", "{not json either")
+            .build();
+
+        assert!(matches!(
+            bundle.validate(),
+            Err(BundleValidationError::InvalidDataFile { path, .. }) if path == "users_service/data.json"
+        ));
+    }
+
+    fn write_file(root: &Path, relative: &str, content: &str) {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_from_directory_recurses() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(
+            dir.path(),
+            "authz.rego",
+            "package users_service.authz\n\ndefault allow := false\n",
+        );
+        write_file(
+            dir.path(),
+            "roles/admin.rego",
+            "# Admin roles\npackage users_service.roles.admin\n",
+        );
+        write_file(dir.path(), "roles/data.json", r#"{"admin": ["read"]}"#);
+        write_file(dir.path(), "roles/other.json", "{}");
+        write_file(dir.path(), "README.md", "# Policies");
+
+        let bundle = BundleBuilder::from_directory(dir.path(), "users-service", "1.0.0")
+            .unwrap()
+            .build();
+
+        assert_eq!(bundle.name, "users-service");
+        assert_eq!(bundle.version, "1.0.0");
+        assert_eq!(bundle.policy_count(), 2);
+        assert!(bundle.has_policy("users_service.authz"));
+        assert!(bundle.has_policy("users_service.roles.admin"));
+        assert_eq!(bundle.data_files.len(), 1);
+        assert_eq!(
+            bundle.data_files["roles/data.json"],
+            r#"{"admin": ["read"]}"#
+        );
+    }
+
+    #[test]
+    fn test_from_directory_respects_bundleignore() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(
+            dir.path(),
+            ".bundleignore",
+            "# Skip tests and scratch work\n*_test.rego\nscratch/\n",
+        );
+        write_file(dir.path(), "authz.rego", "package users_service.authz\n");
+        write_file(
+            dir.path(),
+            "authz_test.rego",
+            "package users_service.authz_test\n",
+        );
+        write_file(
+            dir.path(),
+            "nested/authz_test.rego",
+            "package users_service.nested_test\n",
+        );
+        write_file(
+            dir.path(),
+            "scratch/draft.rego",
+            "package users_service.draft\n",
+        );
+        write_file(dir.path(), "scratch/data.json", "{}");
+
+        let bundle = BundleBuilder::from_directory(dir.path(), "users-service", "1.0.0")
+            .unwrap()
+            .build();
+
+        let mut packages: Vec<_> = bundle.policies.keys().cloned().collect();
+        packages.sort();
+        assert_eq!(packages, vec!["users_service.authz"]);
+        assert!(bundle.data_files.is_empty());
+    }
+
+    #[test]
+    fn test_from_directory_rejects_duplicate_package() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(dir.path(), "a.rego", "package users_service.authz\n");
+        write_file(dir.path(), "b/a.rego", "package users_service.authz\n");
+
+        let result = BundleBuilder::from_directory(dir.path(), "users-service", "1.0.0");
+
+        assert!(matches!(
+            result,
+            Err(BundleBuilderError::DuplicatePackage(package)) if package == "users_service.authz"
+        ));
+    }
+
+    #[test]
+    fn test_from_directory_rejects_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(dir.path(), "data.json", "{}");
+
+        let result = BundleBuilder::from_directory(dir.path(), "users-service", "1.0.0");
+
+        assert!(matches!(result, Err(BundleBuilderError::EmptyDirectory(_))));
+    }
+
+    #[test]
+    fn test_from_directory_missing_dir_is_io_error() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let result =
+            BundleBuilder::from_directory(dir.path().join("missing"), "users-service", "1.0.0");
+
+        assert!(matches!(result, Err(BundleBuilderError::IoError { .. })));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(b"*_test.rego", b"authz_test.rego"));
+        assert!(!glob_match(b"*_test.rego", b"nested/authz_test.rego"));
+        assert!(glob_match(b"**/*.rego", b"authz.rego"));
+        assert!(glob_match(b"**/*.rego", b"a/b/authz.rego"));
+        assert!(glob_match(b"scratch/?.rego", b"scratch/a.rego"));
+        assert!(!glob_match(b"scratch/?.rego", b"scratch/ab.rego"));
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let key = [42u8; 32];
+        let bundle = sample_bundle();
+
+        let encrypted = bundle.encrypt(&key).unwrap();
+        let restored = EncryptedBundle::from_bytes(&encrypted.to_bytes())
+            .unwrap()
+            .decrypt(&key)
+            .unwrap();
+
+        assert_eq!(restored.name, bundle.name);
+        assert_eq!(restored.version, bundle.version);
+        assert_eq!(restored.policies, bundle.policies);
+        assert_eq!(restored.data_files, bundle.data_files);
+    }
+
+    #[test]
+    fn test_encrypt_uses_fresh_nonce() {
+        let key = [42u8; 32];
+        let bundle = sample_bundle();
+
+        let first = bundle.encrypt(&key).unwrap();
+        let second = bundle.encrypt(&key).unwrap();
+
+        assert_ne!(
+            first.as_bytes()[..NONCE_LEN],
+            second.as_bytes()[..NONCE_LEN]
+        );
+    }
+
+    #[test]
+    fn test_decrypt_with_wrong_key_fails() {
+        let encrypted = sample_bundle().encrypt(&[1u8; 32]).unwrap();
+
+        assert_eq!(
+            encrypted.decrypt(&[2u8; 32]),
+            Err(CryptoError::DecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn test_decrypt_tampered_data_fails() {
+        let key = [42u8; 32];
+        let mut data = sample_bundle().encrypt(&key).unwrap().to_bytes();
+        let last = data.len() - 1;
+        data[last] ^= 0xff;
+
+        let encrypted = EncryptedBundle::from_bytes(&data).unwrap();
+        assert_eq!(encrypted.decrypt(&key), Err(CryptoError::DecryptionFailed));
+    }
+
+    #[test]
+    fn test_encrypted_bundle_rejects_truncated_data() {
+        let result = EncryptedBundle::from_bytes(&[0u8; NONCE_LEN + TAG_LEN - 1]);
+
+        assert!(matches!(
+            result,
+            Err(CryptoError::InvalidCiphertext { len: 27, min: 28 })
+        ));
+    }
+
+    /// Builds a tar.gz containing a valid manifest followed by the given
+    /// entries, whose names are written verbatim into the tar header.
+    fn raw_archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let manifest = serde_json::to_vec(&sample_bundle().generate_manifest()).unwrap();
+        let mut builder = Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (name, data) in
+            std::iter::once((".manifest", manifest.as_slice())).chain(entries.iter().copied())
+        {
+            let mut header = tar::Header::new_old();
+            header.as_old_mut().name[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn invalid_input_reason(result: crate::Result<Bundle>) -> String {
+        match result {
+            Err(crate::Error::InvalidInput { reason }) => reason,
+            other => panic!("expected InvalidInput, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_from_reader_rejects_parent_dir_entries() {
+        let archive = raw_archive(&[("../../etc/cron.d/x.json", b"{}")]);
+
+        let reason = invalid_input_reason(Bundle::from_bytes(&archive));
+        assert!(reason.contains("'../../etc/cron.d/x.json'"));
+        assert!(reason.contains("'..'"));
+    }
+
+    #[test]
+    fn test_from_reader_rejects_absolute_entries() {
+        let archive = raw_archive(&[("/etc/passwd.rego", b"package x\n")]);
+
+        let reason = invalid_input_reason(Bundle::from_bytes(&archive));
+        assert!(reason.contains("'/etc/passwd.rego' has an absolute path"));
+    }
+
+    #[test]
+    fn test_from_reader_rejects_binary_entries() {
+        let archive = raw_archive(&[("users_service/data.json", &[0xff, 0xfe, 0x00])]);
+
+        let reason = invalid_input_reason(Bundle::from_bytes(&archive));
+        assert_eq!(
+            reason,
+            "bundle entry 'users_service/data.json' is not valid UTF-8"
+        );
+    }
+
+    #[test]
+    fn test_from_reader_enforces_entry_limit() {
+        let archive = raw_archive(&[("a.json", b"{}"), ("b.json", b"{}")]);
+        let limits = ArchiveLimits::default().with_max_entries(2);
+
+        let reason =
+            invalid_input_reason(Bundle::from_reader_with_limits(archive.as_slice(), limits));
+        assert_eq!(reason, "bundle archive has more than 2 entries");
+    }
+
+    #[test]
+    fn test_from_reader_enforces_size_limit() {
+        let bomb = vec![b' '; 1024 * 1024];
+        let archive = raw_archive(&[("users_service/data.json", &bomb)]);
+        assert!(archive.len() < 16 * 1024);
+        let limits = ArchiveLimits::default().with_max_decompressed_size(64 * 1024);
+
+        let reason =
+            invalid_input_reason(Bundle::from_reader_with_limits(archive.as_slice(), limits));
+        assert!(reason.contains("'users_service/data.json' exceeds the decompressed size limit"));
+    }
+
+    #[test]
+    fn test_from_reader_accepts_bundle_within_limits() {
+        let bytes = sample_bundle().to_bytes().unwrap();
+        let limits = ArchiveLimits::default()
+            .with_max_entries(3)
+            .with_max_decompressed_size(bytes.len() as u64 * 4);
+
+        let bundle = Bundle::from_reader_with_limits(bytes.as_slice(), limits).unwrap();
+        assert_eq!(bundle.data_files.len(), 1);
+    }
 }
@@ -11,6 +11,7 @@
 //! - [`PolicyInput`] - Input schema for authorization requests (from `themis-platform-types`)
 //! - [`CallerIdentity`] - Identity types (from `themis-platform-types`)
 //! - [`signing`] - Ed25519 bundle signing and verification
+//! - [`BundleLimits`] - Size and complexity limits enforced on bundles
 //!
 //! ## Shared Platform Types
 //!
@@ -41,18 +42,28 @@
 
 pub mod bundle;
 pub mod error;
+pub mod limits;
 pub mod policy;
 pub mod signing;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
 pub mod validation;
 
 #[cfg(test)]
 mod proptest_tests;
 
 // Re-export main types at crate root
-pub use bundle::Bundle;
+pub use bundle::{
+    package_under_root, ArchiveLimits, Bundle, BundleDependency, BundleDiff, BundleValidationError,
+    CryptoError, EncryptedBundle, ManifestChange, PolicyDiff,
+};
 pub use error::{Error, Result};
+pub use limits::{BundleLimitError, BundleLimits, BundleSizes};
 pub use policy::Policy;
-pub use signing::{BundleSigner, BundleVerifier, SignedBundle, SigningError, SigningKeyPair};
+pub use signing::{
+    BundleSigner, BundleVerifier, PublicKey, SignedBundle, SigningAlgorithm, SigningError,
+    SigningKeyPair,
+};
 pub use validation::{Validate, ValidationError};
 
 // Re-export shared platform types from themis-platform-types
@@ -0,0 +1,410 @@
+//! Size and complexity limits for policy bundles.
+//!
+//! A single oversized data file can exhaust the memory of every instance
+//! activating the bundle, so bundles are measured with
+//! [`BundleSizes::measure`] and checked against [`BundleLimits`] both when
+//! they are built and again before they are pushed.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use eunomia_core::{Bundle, BundleLimits, BundleSizes};
+//!
+//! let bundle = Bundle::builder("users-service")
+//!     .version("1.0.0")
+//!     .add_policy("users_service.authz", "package users_service.authz\n\ndefault allow := false")
+//!     .add_data_file("users_service/roles.json", r#"{"admin": ["read", "write"]}"#)
+//!     .build();
+//!
+//! let sizes = BundleSizes::measure(&bundle).unwrap();
+//! assert_eq!(sizes.policy_rules["users_service.authz"], 1);
+//!
+//! let limits = BundleLimits::default().with_max_data_file_size(16);
+//! let err = sizes.check(&limits).unwrap_err();
+//! assert!(err.to_string().contains("users_service/roles.json"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::bundle::DEFAULT_MAX_DECOMPRESSED_SIZE;
+use crate::policy::rule_name;
+use crate::Bundle;
+
+/// Default cap on the size of a compressed bundle archive (10 MiB).
+pub const DEFAULT_MAX_COMPRESSED_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Default cap on the size of a single data file (8 MiB).
+pub const DEFAULT_MAX_DATA_FILE_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Default cap on the number of policies in a bundle.
+pub const DEFAULT_MAX_POLICIES: usize = 1_000;
+
+/// Default cap on the number of rules in a single policy.
+pub const DEFAULT_MAX_RULES_PER_POLICY: usize = 1_000;
+
+/// Limits on the size and complexity of a bundle.
+///
+/// The default uncompressed size limit matches the default
+/// [`ArchiveLimits`](crate::ArchiveLimits), so a bundle within the limits
+/// can always be read back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BundleLimits {
+    /// Maximum size in bytes of the compressed bundle archive.
+    pub max_compressed_size: u64,
+    /// Maximum size in bytes of the uncompressed bundle archive.
+    pub max_uncompressed_size: u64,
+    /// Maximum size in bytes of any single data file.
+    pub max_data_file_size: u64,
+    /// Maximum number of policies.
+    pub max_policies: usize,
+    /// Maximum number of rules in any single policy.
+    pub max_rules_per_policy: usize,
+}
+
+impl Default for BundleLimits {
+    fn default() -> Self {
+        Self {
+            max_compressed_size: DEFAULT_MAX_COMPRESSED_SIZE,
+            max_uncompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+            max_data_file_size: DEFAULT_MAX_DATA_FILE_SIZE,
+            max_policies: DEFAULT_MAX_POLICIES,
+            max_rules_per_policy: DEFAULT_MAX_RULES_PER_POLICY,
+        }
+    }
+}
+
+impl BundleLimits {
+    /// Sets the maximum compressed bundle size in bytes.
+    #[must_use]
+    pub const fn with_max_compressed_size(mut self, bytes: u64) -> Self {
+        self.max_compressed_size = bytes;
+        self
+    }
+
+    /// Sets the maximum uncompressed bundle size in bytes.
+    #[must_use]
+    pub const fn with_max_uncompressed_size(mut self, bytes: u64) -> Self {
+        self.max_uncompressed_size = bytes;
+        self
+    }
+
+    /// Sets the maximum data file size in bytes.
+    #[must_use]
+    pub const fn with_max_data_file_size(mut self, bytes: u64) -> Self {
+        self.max_data_file_size = bytes;
+        self
+    }
+
+    /// Sets the maximum number of policies.
+    #[must_use]
+    pub const fn with_max_policies(mut self, policies: usize) -> Self {
+        self.max_policies = policies;
+        self
+    }
+
+    /// Sets the maximum number of rules per policy.
+    #[must_use]
+    pub const fn with_max_rules_per_policy(mut self, rules: usize) -> Self {
+        self.max_rules_per_policy = rules;
+        self
+    }
+}
+
+/// Measured size and complexity of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BundleSizes {
+    /// Size in bytes of the compressed bundle archive.
+    pub compressed: u64,
+    /// Size in bytes of the uncompressed bundle archive.
+    pub uncompressed: u64,
+    /// Size in bytes of each data file, by path.
+    pub data_files: BTreeMap<String, u64>,
+    /// Number of rules in each policy, by package.
+    ///
+    /// Rules are counted by their unindented heads, so every definition of
+    /// an incremental rule counts, as does a `default` rule.
+    pub policy_rules: BTreeMap<String, usize>,
+}
+
+impl BundleSizes {
+    /// Measures a bundle by writing its archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be written.
+    pub fn measure(bundle: &Bundle) -> crate::Result<Self> {
+        Self::from_archive(bundle, &bundle.to_bytes()?)
+    }
+
+    /// Measures a bundle whose archive was already written to `archive`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `archive` is not a gzip stream.
+    pub fn from_archive(bundle: &Bundle, archive: &[u8]) -> crate::Result<Self> {
+        let uncompressed =
+            io::copy(&mut GzDecoder::new(archive), &mut io::sink()).map_err(|e| {
+                crate::Error::Io {
+                    message: format!("failed to decompress bundle archive: {e}"),
+                }
+            })?;
+
+        Ok(Self {
+            compressed: archive.len() as u64,
+            uncompressed,
+            data_files: bundle
+                .data_files
+                .iter()
+                .map(|(path, content)| (path.clone(), content.len() as u64))
+                .collect(),
+            policy_rules: bundle
+                .policies
+                .iter()
+                .map(|(package, source)| (package.clone(), count_rules(source)))
+                .collect(),
+        })
+    }
+
+    /// Returns the number of policies.
+    #[must_use]
+    pub fn policies(&self) -> usize {
+        self.policy_rules.len()
+    }
+
+    /// Returns the path and size of the largest data file, if any.
+    #[must_use]
+    pub fn largest_data_file(&self) -> Option<(&str, u64)> {
+        self.data_files
+            .iter()
+            .max_by_key(|(_, size)| **size)
+            .map(|(path, size)| (path.as_str(), *size))
+    }
+
+    /// Returns the package and rule count of the policy with the most
+    /// rules, if any.
+    #[must_use]
+    pub fn largest_policy(&self) -> Option<(&str, usize)> {
+        self.policy_rules
+            .iter()
+            .max_by_key(|(_, rules)| **rules)
+            .map(|(package, rules)| (package.as_str(), *rules))
+    }
+
+    /// Checks the sizes against `limits`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first limit exceeded, checking data files, then policies,
+    /// then the bundle as a whole.
+    pub fn check(&self, limits: &BundleLimits) -> Result<(), BundleLimitError> {
+        if let Some((path, &size)) = self
+            .data_files
+            .iter()
+            .find(|(_, &size)| size > limits.max_data_file_size)
+        {
+            return Err(BundleLimitError::DataFileTooLarge {
+                path: path.clone(),
+                size,
+                limit: limits.max_data_file_size,
+            });
+        }
+
+        if let Some((package, &rules)) = self
+            .policy_rules
+            .iter()
+            .find(|(_, &rules)| rules > limits.max_rules_per_policy)
+        {
+            return Err(BundleLimitError::TooManyRules {
+                package: package.clone(),
+                rules,
+                limit: limits.max_rules_per_policy,
+            });
+        }
+
+        if self.policies() > limits.max_policies {
+            return Err(BundleLimitError::TooManyPolicies {
+                policies: self.policies(),
+                limit: limits.max_policies,
+            });
+        }
+
+        if self.uncompressed > limits.max_uncompressed_size {
+            return Err(BundleLimitError::UncompressedTooLarge {
+                size: self.uncompressed,
+                limit: limits.max_uncompressed_size,
+            });
+        }
+
+        if self.compressed > limits.max_compressed_size {
+            return Err(BundleLimitError::CompressedTooLarge {
+                size: self.compressed,
+                limit: limits.max_compressed_size,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A bundle exceeds one of its [`BundleLimits`].
+///
+/// Each message names the limit's field, which is also its key in the
+/// `[limits]` section of `eunomia.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BundleLimitError {
+    /// A data file is larger than `max_data_file_size`.
+    #[error("Data file '{path}' is {size} bytes, over the max_data_file_size limit of {limit}")]
+    DataFileTooLarge {
+        /// Path of the data file within the bundle.
+        path: String,
+        /// Size of the data file in bytes.
+        size: u64,
+        /// The exceeded limit in bytes.
+        limit: u64,
+    },
+
+    /// A policy has more rules than `max_rules_per_policy`.
+    #[error(
+        "Policy '{package}' has {rules} rules, over the max_rules_per_policy limit of {limit}"
+    )]
+    TooManyRules {
+        /// Package of the policy.
+        package: String,
+        /// Number of rules in the policy.
+        rules: usize,
+        /// The exceeded limit.
+        limit: usize,
+    },
+
+    /// The bundle has more policies than `max_policies`.
+    #[error("Bundle has {policies} policies, over the max_policies limit of {limit}")]
+    TooManyPolicies {
+        /// Number of policies in the bundle.
+        policies: usize,
+        /// The exceeded limit.
+        limit: usize,
+    },
+
+    /// The uncompressed archive is larger than `max_uncompressed_size`.
+    #[error(
+        "Bundle is {size} bytes uncompressed, over the max_uncompressed_size limit of {limit}"
+    )]
+    UncompressedTooLarge {
+        /// Uncompressed size in bytes.
+        size: u64,
+        /// The exceeded limit in bytes.
+        limit: u64,
+    },
+
+    /// The compressed archive is larger than `max_compressed_size`.
+    #[error("Bundle is {size} bytes compressed, over the max_compressed_size limit of {limit}")]
+    CompressedTooLarge {
+        /// Compressed size in bytes.
+        size: u64,
+        /// The exceeded limit in bytes.
+        limit: u64,
+    },
+}
+
+/// Counts the rule heads of a Rego source: unindented lines that define a
+/// rule according to [`rule_name`].
+fn count_rules(source: &str) -> usize {
+    source
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter(|line| rule_name(line.trim()).is_some())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle() -> Bundle {
+        Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy(
+                "users_service.authz",
+                "package users_service.authz\n\
+                 import rego.v1\n\n\
+                 # Deny by default\n\
+                 default allow := false\n\n\
+                 allow if {\n    input.caller.type == \"admin\"\n}\n\n\
+                 allow if {\n    input.method == \"GET\"\n}\n",
+            )
+            .add_policy("users_service.helpers", "package users_service.helpers\n")
+            .add_data_file("users_service/roles.json", "[\"admin\"]")
+            .add_data_file("users_service/big.json", "[1, 2, 3, 4, 5, 6, 7, 8, 9]")
+            .build()
+    }
+
+    #[test]
+    fn test_measure() {
+        let bundle = bundle();
+        let sizes = BundleSizes::measure(&bundle).unwrap();
+
+        assert_eq!(sizes.compressed, bundle.to_bytes().unwrap().len() as u64);
+        assert!(sizes.uncompressed > sizes.compressed);
+        assert_eq!(sizes.policies(), 2);
+        assert_eq!(sizes.largest_policy(), Some(("users_service.authz", 3)));
+        assert_eq!(
+            sizes.largest_data_file(),
+            Some(("users_service/big.json", 27))
+        );
+        assert!(sizes.check(&BundleLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn test_count_rules_skips_unindented_body_lines() {
+        let source = "package authz\n\
+                      import rego.v1\n\n\
+                      default allow := false\n\n\
+                      allow if {\n\
+                      input.method == \"GET\"\n\
+                      }\n\n\
+                      admins[name] {\n    name := input.user\n}\n";
+        assert_eq!(count_rules(source), 3);
+    }
+
+    #[test]
+    fn test_check_names_offending_entry() {
+        let sizes = BundleSizes::measure(&bundle()).unwrap();
+        let check = |limits: BundleLimits| sizes.check(&limits).unwrap_err();
+
+        assert_eq!(
+            check(BundleLimits::default().with_max_data_file_size(20)),
+            BundleLimitError::DataFileTooLarge {
+                path: "users_service/big.json".to_string(),
+                size: 27,
+                limit: 20,
+            }
+        );
+        assert_eq!(
+            check(BundleLimits::default().with_max_rules_per_policy(2)),
+            BundleLimitError::TooManyRules {
+                package: "users_service.authz".to_string(),
+                rules: 3,
+                limit: 2,
+            }
+        );
+        assert_eq!(
+            check(BundleLimits::default().with_max_policies(1)),
+            BundleLimitError::TooManyPolicies {
+                policies: 2,
+                limit: 1,
+            }
+        );
+        assert!(matches!(
+            check(BundleLimits::default().with_max_uncompressed_size(100)),
+            BundleLimitError::UncompressedTooLarge { limit: 100, .. }
+        ));
+        let err = check(BundleLimits::default().with_max_compressed_size(100));
+        assert!(err.to_string().contains("max_compressed_size"), "{err}");
+    }
+}
@@ -165,6 +165,72 @@ impl Policy {
     }
 }
 
+/// Extracts the name of the rule defined on a trimmed line of Rego source.
+///
+/// Recognises `name := ...`, `name = ...`, `name if ...`, `name { ... }`
+/// and partial rules like `name[x] { ... }`, with or without a leading
+/// `default`. Returns `None` for `package` and `import` statements and lines
+/// that define no rule.
+///
+/// # Examples
+///
+/// ```rust
+/// use eunomia_core::policy::rule_name;
+///
+/// assert_eq!(rule_name("default allow := false").as_deref(), Some("allow"));
+/// assert_eq!(rule_name("import rego.v1"), None);
+/// ```
+#[must_use]
+pub fn rule_name(line: &str) -> Option<String> {
+    // Skip imports and package
+    if line.starts_with("import") || line.starts_with("package") {
+        return None;
+    }
+
+    // Skip `default` keyword but extract the name after it
+    let line = line.strip_prefix("default").map_or(line, str::trim);
+
+    // Find rule name (before := , = , if, {, [, or ()
+    let name_end = line
+        .find(":=")
+        .or_else(|| line.find(" = "))
+        .or_else(|| line.find(" if"))
+        .or_else(|| line.find('{'))
+        .or_else(|| line.find('['))
+        .or_else(|| line.find('('))?;
+
+    let name = line[..name_end].trim();
+
+    // Validate it looks like an identifier
+    if name.is_empty() || !name.chars().next()?.is_alphabetic() {
+        return None;
+    }
+
+    // Handle array/object rules like `arr[x]` -> `arr`
+    let name = name.split('[').next()?.trim();
+
+    if is_valid_identifier(name) {
+        Some(name.to_string())
+    } else {
+        None
+    }
+}
+
+/// Checks if a string is a valid Rego identifier.
+fn is_valid_identifier(s: &str) -> bool {
+    if s.is_empty() {
+        return false;
+    }
+    let mut chars = s.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    if !first.is_alphabetic() && first != '_' {
+        return false;
+    }
+    chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
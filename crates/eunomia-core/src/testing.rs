@@ -0,0 +1,17 @@
+//! Test helpers shared with the other workspace crates.
+//!
+//! Compiled for this crate's own tests and, through the `test-util` feature,
+//! for the tests of crates that list it in their dev-dependencies.
+
+use crate::Bundle;
+
+/// Returns a small `users-service` 1.0.0 bundle with one policy and one
+/// data file.
+#[must_use]
+pub fn sample_bundle() -> Bundle {
+    Bundle::builder("users-service")
+        .version("1.0.0")
+        .add_policy("users_service.authz", "package users_service.authz\n")
+        .add_data_file("users_service/data.json", r#"{"admins":["alice"]}"#)
+        .build()
+}
@@ -359,13 +359,13 @@ fn bench_key_operations(c: &mut Criterion) {
     });
 
     let key_pair = SigningKeyPair::generate();
-    let base64_key = key_pair.to_base64();
+    let base64_key = key_pair.to_base64().unwrap();
 
     group.bench_function("key_from_base64", |b| {
         b.iter(|| black_box(SigningKeyPair::from_base64(&base64_key)))
     });
 
-    let seed = key_pair.to_bytes();
+    let seed = key_pair.to_bytes().unwrap();
     group.bench_function("key_from_seed", |b| {
         b.iter(|| black_box(SigningKeyPair::from_seed(&seed)))
     });
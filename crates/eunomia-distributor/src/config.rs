@@ -3,10 +3,17 @@
 //! This module provides configuration options for the distributor service,
 //! including discovery sources, push settings, and health check parameters.
 
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
+use eunomia_core::signing::BundleVerifier;
+
+#[cfg(feature = "kubernetes")]
+use crate::discovery::KubernetesDiscovery;
 use crate::discovery::{Discovery, DiscoverySource, DnsDiscovery, StaticDiscovery};
-use crate::error::{DistributorError, Result};
+use crate::error::Result;
 use crate::health::HealthConfig;
 use crate::pusher::PushConfig;
 use crate::scheduler::SchedulerConfig;
@@ -26,6 +33,21 @@ pub struct DistributorConfig {
     /// Scheduler configuration.
     pub scheduler_config: SchedulerConfig,
 
+    /// File deployment state is persisted to, so deployments interrupted
+    /// by a restart can be recovered (kept in memory only when unset).
+    pub state_path: Option<PathBuf>,
+
+    /// Environment this distributor deploys to (e.g. `prod`).
+    pub environment: Option<String>,
+
+    /// Signatures bundles must carry to be deployed (not checked when
+    /// unset).
+    pub signatures: Option<SignatureConfig>,
+
+    /// Automatic rollback on health degradation after canary and rolling
+    /// stages (disabled when unset).
+    pub auto_rollback: Option<AutoRollbackConfig>,
+
     /// Enable TLS for connections.
     pub tls_enabled: bool,
 
@@ -51,15 +73,18 @@ impl DistributorConfig {
             DiscoverySource::Static { endpoints } => {
                 Ok(Box::new(StaticDiscovery::new(endpoints.clone())))
             }
-            DiscoverySource::Kubernetes { namespace, .. } => {
-                // For now, return an error - K8s discovery will be implemented later
-                Err(DistributorError::InvalidConfig {
-                    reason: format!(
-                        "Kubernetes discovery not yet implemented (namespace: {})",
-                        namespace.as_deref().unwrap_or("default")
-                    ),
-                })
-            }
+            #[cfg(feature = "kubernetes")]
+            DiscoverySource::Kubernetes {
+                namespace,
+                label_selector,
+                port_name,
+            } => Ok(Box::new(KubernetesDiscovery::lazy(
+                namespace.clone(),
+                label_selector.clone(),
+                port_name.clone(),
+            ))),
+            #[cfg(not(feature = "kubernetes"))]
+            DiscoverySource::Kubernetes { .. } => Err(crate::discovery::kubernetes_disabled()),
             DiscoverySource::Dns {
                 hosts,
                 port,
@@ -75,6 +100,81 @@ impl DistributorConfig {
     }
 }
 
+/// Configuration of automatic rollback after a deployment stage.
+///
+/// After each canary or rolling stage, the health of the instances deployed
+/// so far is sampled `min_samples` times over `health_check_window`. If more
+/// than `failure_rate_threshold` of the health checks fail, the service is
+/// rolled back to its previous version.
+#[derive(Debug, Clone)]
+pub struct AutoRollbackConfig {
+    /// How long to watch health after a stage.
+    pub health_check_window: Duration,
+
+    /// Fraction (0.0-1.0) of failed health checks that triggers a rollback.
+    pub failure_rate_threshold: f64,
+
+    /// Minimum number of health checks before a rollback is considered.
+    pub min_samples: usize,
+}
+
+impl Default for AutoRollbackConfig {
+    fn default() -> Self {
+        Self {
+            health_check_window: Duration::from_secs(60),
+            failure_rate_threshold: 0.5,
+            min_samples: 3,
+        }
+    }
+}
+
+impl AutoRollbackConfig {
+    /// Returns the time between health samples in the window.
+    pub fn sample_interval(&self) -> Duration {
+        self.health_check_window / u32::try_from(self.min_samples.max(1)).unwrap_or(u32::MAX)
+    }
+}
+
+/// Signature verification of bundles before they are deployed.
+///
+/// Bundles are fetched from the distributor's registry and must be signed
+/// by at least the verifier's threshold of trusted keys. Unsigned bundles
+/// are only deployed in the environments explicitly listed in
+/// `allow_unsigned_environments`.
+#[derive(Debug, Clone)]
+pub struct SignatureConfig {
+    /// Trusted public keys and the number of signatures required.
+    pub verifier: Arc<BundleVerifier>,
+
+    /// Environments in which bundles are deployed without verification.
+    pub allow_unsigned_environments: BTreeSet<String>,
+}
+
+impl SignatureConfig {
+    /// Requires bundles to be signed by keys trusted by `verifier`, in
+    /// every environment.
+    pub fn new(verifier: BundleVerifier) -> Self {
+        Self {
+            verifier: Arc::new(verifier),
+            allow_unsigned_environments: BTreeSet::new(),
+        }
+    }
+
+    /// Allows deploying unsigned bundles in `environment`.
+    #[must_use]
+    pub fn allow_unsigned_in(mut self, environment: impl Into<String>) -> Self {
+        self.allow_unsigned_environments.insert(environment.into());
+        self
+    }
+
+    /// Returns true if bundles must be verified in `environment`.
+    ///
+    /// A distributor without an environment always verifies.
+    pub fn is_required_in(&self, environment: Option<&str>) -> bool {
+        environment.is_none_or(|env| !self.allow_unsigned_environments.contains(env))
+    }
+}
+
 /// Discovery configuration.
 #[derive(Debug, Clone)]
 pub struct DiscoveryConfig {
@@ -111,6 +211,10 @@ pub struct DistributorConfigBuilder {
     push_config: Option<PushConfig>,
     health_config: Option<HealthConfig>,
     scheduler_config: Option<SchedulerConfig>,
+    state_path: Option<PathBuf>,
+    environment: Option<String>,
+    signatures: Option<SignatureConfig>,
+    auto_rollback: Option<AutoRollbackConfig>,
     tls_enabled: bool,
     tls_cert_path: Option<String>,
     tls_key_path: Option<String>,
@@ -142,6 +246,30 @@ impl DistributorConfigBuilder {
         self
     }
 
+    /// Sets the file deployment state is persisted to.
+    pub fn state_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_path = Some(path.into());
+        self
+    }
+
+    /// Sets the environment the distributor deploys to.
+    pub fn environment(mut self, environment: impl Into<String>) -> Self {
+        self.environment = Some(environment.into());
+        self
+    }
+
+    /// Requires bundles to be signed before they are deployed.
+    pub fn require_signatures(mut self, config: SignatureConfig) -> Self {
+        self.signatures = Some(config);
+        self
+    }
+
+    /// Enables automatic rollback on health degradation.
+    pub fn auto_rollback(mut self, config: AutoRollbackConfig) -> Self {
+        self.auto_rollback = Some(config);
+        self
+    }
+
     /// Enables TLS.
     pub fn tls(mut self, cert_path: String, key_path: String) -> Self {
         self.tls_enabled = true;
@@ -172,6 +300,10 @@ impl DistributorConfigBuilder {
             push_config: self.push_config.unwrap_or_default(),
             health_config: self.health_config.unwrap_or_default(),
             scheduler_config: self.scheduler_config.unwrap_or_default(),
+            state_path: self.state_path,
+            environment: self.environment,
+            signatures: self.signatures,
+            auto_rollback: self.auto_rollback,
             tls_enabled: self.tls_enabled,
             tls_cert_path: self.tls_cert_path,
             tls_key_path: self.tls_key_path,
@@ -206,6 +338,23 @@ mod tests {
         assert_eq!(config.ca_cert_path, Some("ca.pem".to_string()));
     }
 
+    #[test]
+    fn test_builder_auto_rollback() {
+        assert!(DistributorConfig::default().auto_rollback.is_none());
+
+        let config = DistributorConfig::builder()
+            .auto_rollback(AutoRollbackConfig {
+                health_check_window: Duration::from_secs(30),
+                failure_rate_threshold: 0.25,
+                min_samples: 6,
+            })
+            .build();
+
+        let auto_rollback = config.auto_rollback.unwrap();
+        assert!((auto_rollback.failure_rate_threshold - 0.25).abs() < f64::EPSILON);
+        assert_eq!(auto_rollback.sample_interval(), Duration::from_secs(5));
+    }
+
     #[test]
     fn test_builder_static_endpoints() {
         let config = DistributorConfig::builder()
@@ -229,6 +378,24 @@ mod tests {
         assert!(discovery.is_ok());
     }
 
+    #[test]
+    fn test_create_kubernetes_discovery() {
+        let config = DistributorConfig::builder()
+            .discovery(DiscoveryConfig {
+                source: DiscoverySource::Kubernetes {
+                    namespace: Some("default".to_string()),
+                    label_selector: Some("app=archimedes".to_string()),
+                    port_name: None,
+                },
+                ..DiscoveryConfig::default()
+            })
+            .build();
+
+        // The client is created on first use, so this works outside a cluster
+        let discovery = config.create_discovery();
+        assert_eq!(discovery.is_ok(), cfg!(feature = "kubernetes"));
+    }
+
     #[test]
     fn test_discovery_config_default() {
         let config = DiscoveryConfig::default();
@@ -6,6 +6,8 @@
 use async_trait::async_trait;
 use hickory_resolver::config::{ResolverConfig, ResolverOpts};
 use hickory_resolver::TokioAsyncResolver;
+#[cfg(feature = "kubernetes")]
+use k8s_openapi::api::core::v1::{Container, Pod};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -385,10 +387,30 @@ impl Discovery for DnsDiscovery {
     }
 }
 
+/// Name of the Archimedes container in discovered pods.
+#[cfg(feature = "kubernetes")]
+const ARCHIMEDES_CONTAINER: &str = "archimedes";
+
+/// Port used when a pod exposes no matching container port.
+#[cfg(feature = "kubernetes")]
+const DEFAULT_ARCHIMEDES_PORT: i32 = 8080;
+
+/// Pod labels that name the service, in order of preference.
+#[cfg(feature = "kubernetes")]
+const SERVICE_LABELS: &[&str] = &["app.kubernetes.io/name", "app"];
+
 /// Kubernetes-based instance discovery.
 ///
-/// Discovers Archimedes instances by watching Kubernetes Endpoints resources.
-/// Supports label selectors and namespace filtering.
+/// Discovers Archimedes instances by listing Kubernetes pods, filtered by
+/// namespace and label selector. Each ready pod becomes an [`Instance`] at
+/// the pod IP and the container port named `port_name`, falling back to the
+/// first port of the `archimedes` container.
+///
+/// By default the pod list is fetched on [`refresh`](Discovery::refresh).
+/// [`KubernetesDiscovery::watch_mode`] instead keeps it current from a pod
+/// watch.
+///
+/// Requires the `kubernetes` feature.
 ///
 /// # Example
 ///
@@ -404,14 +426,31 @@ impl Discovery for DnsDiscovery {
 ///
 /// let instances = discovery.all_instances().await?;
 /// ```
+#[cfg(feature = "kubernetes")]
 pub struct KubernetesDiscovery {
-    client: kube::Client,
+    client: tokio::sync::OnceCell<kube::Client>,
     namespace: Option<String>,
     label_selector: Option<String>,
     port_name: String,
     instances: Arc<RwLock<Vec<Instance>>>,
+    watch: Option<PodWatch>,
+}
+
+/// A background pod watch feeding a reflector store.
+#[cfg(feature = "kubernetes")]
+struct PodWatch {
+    store: kube::runtime::reflector::Store<Pod>,
+    task: tokio::task::JoinHandle<()>,
 }
 
+#[cfg(feature = "kubernetes")]
+impl Drop for PodWatch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(feature = "kubernetes")]
 impl KubernetesDiscovery {
     /// Creates a new Kubernetes discovery.
     ///
@@ -430,20 +469,13 @@ impl KubernetesDiscovery {
         label_selector: Option<String>,
         port_name: Option<String>,
     ) -> Result<Self> {
-        let client =
-            kube::Client::try_default()
-                .await
-                .map_err(|e| DistributorError::InvalidConfig {
-                    reason: format!("Failed to create Kubernetes client: {e}"),
-                })?;
-
-        Ok(Self {
+        let client = default_client().await?;
+        Ok(Self::with_client(
             client,
             namespace,
             label_selector,
-            port_name: port_name.unwrap_or_else(|| "grpc".to_string()),
-            instances: Arc::new(RwLock::new(Vec::new())),
-        })
+            port_name,
+        ))
     }
 
     /// Creates a Kubernetes discovery with an existing client.
@@ -456,107 +488,109 @@ impl KubernetesDiscovery {
         port_name: Option<String>,
     ) -> Self {
         Self {
-            client,
+            client: tokio::sync::OnceCell::new_with(Some(client)),
+            ..Self::lazy(namespace, label_selector, port_name)
+        }
+    }
+
+    /// Creates a Kubernetes discovery that creates its client on first use.
+    ///
+    /// Client errors are returned by the first discovery call instead of
+    /// here.
+    pub fn lazy(
+        namespace: Option<String>,
+        label_selector: Option<String>,
+        port_name: Option<String>,
+    ) -> Self {
+        Self {
+            client: tokio::sync::OnceCell::new(),
             namespace,
             label_selector,
             port_name: port_name.unwrap_or_else(|| "grpc".to_string()),
             instances: Arc::new(RwLock::new(Vec::new())),
+            watch: None,
         }
     }
 
-    /// Queries Kubernetes API for endpoints matching the configuration.
-    async fn query_endpoints(&self) -> Result<Vec<Instance>> {
-        use k8s_openapi::api::core::v1::Endpoints;
-        use kube::api::{Api, ListParams};
+    /// Creates a Kubernetes discovery that watches pods instead of listing
+    /// them on refresh.
+    ///
+    /// A background task follows pod changes through a `kube` runtime
+    /// watcher, so discovered instances are always current and
+    /// [`refresh`](Discovery::refresh) is a no-op. The watch restarts with
+    /// backoff after API errors and stops when the discovery is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if unable to create Kubernetes client.
+    pub async fn watch_mode(
+        namespace: Option<String>,
+        label_selector: Option<String>,
+        port_name: Option<String>,
+    ) -> Result<Self> {
+        Self::new(namespace, label_selector, port_name)
+            .await?
+            .watching()
+            .await
+    }
+
+    /// Starts the background pod watch.
+    async fn watching(mut self) -> Result<Self> {
+        use futures::StreamExt;
+        use kube::runtime::{reflector, watcher, WatchStreamExt};
 
-        let endpoints_api: Api<Endpoints> = match &self.namespace {
-            Some(ns) => Api::namespaced(self.client.clone(), ns),
-            None => Api::all(self.client.clone()),
+        let config = match &self.label_selector {
+            Some(selector) => watcher::Config::default().labels(selector),
+            None => watcher::Config::default(),
         };
+        let (store, writer) = reflector::store();
+        let pods = self.pods_api().await?;
+        let events = reflector(writer, watcher(pods, config)).default_backoff();
+
+        let task = tokio::spawn(async move {
+            futures::pin_mut!(events);
+            while let Some(event) = events.next().await {
+                if let Err(e) = event {
+                    tracing::warn!(error = %e, "Kubernetes pod watch error");
+                }
+            }
+        });
+
+        self.watch = Some(PodWatch { store, task });
+        Ok(self)
+    }
+
+    /// Returns the pod API for the configured namespace.
+    async fn pods_api(&self) -> Result<kube::Api<Pod>> {
+        let client = self.client.get_or_try_init(default_client).await?.clone();
+        Ok(match &self.namespace {
+            Some(ns) => kube::Api::namespaced(client, ns),
+            None => kube::Api::all(client),
+        })
+    }
+
+    /// Queries Kubernetes API for pods matching the configuration.
+    async fn query_pods(&self) -> Result<Vec<Instance>> {
+        use kube::api::ListParams;
 
         let list_params = match &self.label_selector {
             Some(selector) => ListParams::default().labels(selector),
             None => ListParams::default(),
         };
 
-        let endpoints_list = endpoints_api.list(&list_params).await.map_err(|e| {
-            DistributorError::InvalidConfig {
-                reason: format!("Failed to list Kubernetes endpoints: {e}"),
-            }
-        })?;
-
-        let mut instances = Vec::new();
+        let pods = self
+            .pods_api()
+            .await?
+            .list(&list_params)
+            .await
+            .map_err(|e| DistributorError::InvalidConfig {
+                reason: format!("Failed to list Kubernetes pods: {e}"),
+            })?;
 
-        for endpoints in endpoints_list {
-            let ep_name = endpoints.metadata.name.as_deref().unwrap_or("unknown");
-            let ep_namespace = endpoints.metadata.namespace.as_deref().unwrap_or("default");
-
-            if let Some(subsets) = endpoints.subsets {
-                for subset in subsets {
-                    // Find the port matching our port name
-                    let port = subset
-                        .ports
-                        .as_ref()
-                        .and_then(|ports| {
-                            ports.iter().find(|p| {
-                                p.name.as_ref().is_some_and(|name| name == &self.port_name)
-                            })
-                        })
-                        .map_or(8080, |p| p.port);
-
-                    // Process ready addresses
-                    if let Some(addresses) = subset.addresses {
-                        for addr in addresses {
-                            let ip = &addr.ip;
-                            let endpoint = format!("{ip}:{port}");
-
-                            // Generate instance ID from pod reference or IP
-                            let instance_id = addr
-                                .target_ref
-                                .as_ref()
-                                .and_then(|tr| tr.name.clone())
-                                .unwrap_or_else(|| format!("k8s-{ep_namespace}-{ep_name}-{ip}"));
-
-                            let mut metadata = InstanceMetadata::default();
-                            metadata
-                                .annotations
-                                .insert("k8s.namespace".to_string(), ep_namespace.to_string());
-                            metadata
-                                .annotations
-                                .insert("k8s.endpoint".to_string(), ep_name.to_string());
-                            metadata
-                                .annotations
-                                .insert("k8s.ip".to_string(), ip.clone());
-                            metadata
-                                .annotations
-                                .insert("k8s.port".to_string(), port.to_string());
-
-                            // Add node name if available
-                            if let Some(node) = &addr.node_name {
-                                metadata
-                                    .annotations
-                                    .insert("k8s.node".to_string(), node.clone());
-                            }
-
-                            // Add pod UID if available
-                            if let Some(target_ref) = &addr.target_ref {
-                                if let Some(uid) = &target_ref.uid {
-                                    metadata
-                                        .annotations
-                                        .insert("k8s.pod_uid".to_string(), uid.clone());
-                                }
-                            }
-
-                            // Set service name in metadata
-                            metadata.service = Some(ep_name.to_string());
-
-                            instances
-                                .push(Instance::new(instance_id, endpoint).with_metadata(metadata));
-                        }
-                    }
-                }
-            }
-        }
+        let instances: Vec<Instance> = pods
+            .iter()
+            .filter_map(|pod| pod_instance(pod, &self.port_name))
+            .collect();
 
         tracing::info!(
             namespace = ?self.namespace,
@@ -569,16 +603,89 @@ impl KubernetesDiscovery {
     }
 }
 
+/// Creates a client from the in-cluster config or the local kubeconfig.
+#[cfg(feature = "kubernetes")]
+async fn default_client() -> Result<kube::Client> {
+    kube::Client::try_default()
+        .await
+        .map_err(|e| DistributorError::InvalidConfig {
+            reason: format!("Failed to create Kubernetes client: {e}"),
+        })
+}
+
+/// Maps a ready pod to an instance.
+///
+/// Returns `None` for pods that are not ready, are being deleted, or have
+/// no IP yet.
+#[cfg(feature = "kubernetes")]
+fn pod_instance(pod: &Pod, port_name: &str) -> Option<Instance> {
+    let name = pod.metadata.name.as_deref()?;
+    let status = pod.status.as_ref()?;
+    let ip = status.pod_ip.as_deref()?;
+    let ready = status
+        .conditions
+        .iter()
+        .flatten()
+        .any(|c| c.type_ == "Ready" && c.status == "True");
+    if !ready || pod.metadata.deletion_timestamp.is_some() {
+        return None;
+    }
+
+    let containers = pod.spec.as_ref().map(|s| s.containers.as_slice());
+    let ports = |c: &Container| c.ports.clone().unwrap_or_default();
+    let port = containers
+        .into_iter()
+        .flatten()
+        .flat_map(ports)
+        .find(|p| p.name.as_deref() == Some(port_name))
+        .or_else(|| {
+            containers
+                .into_iter()
+                .flatten()
+                .find(|c| c.name == ARCHIMEDES_CONTAINER)
+                .and_then(|c| ports(c).into_iter().next())
+        })
+        .map_or(DEFAULT_ARCHIMEDES_PORT, |p| p.container_port);
+
+    let labels = pod.metadata.labels.clone().unwrap_or_default();
+    let mut metadata = InstanceMetadata {
+        service: SERVICE_LABELS
+            .iter()
+            .find_map(|label| labels.get(*label).cloned()),
+        labels: labels.into_iter().collect(),
+        namespace: pod.metadata.namespace.clone(),
+        pod_name: Some(name.to_string()),
+        node_name: pod.spec.as_ref().and_then(|s| s.node_name.clone()),
+        ..InstanceMetadata::default()
+    };
+    metadata
+        .annotations
+        .insert("k8s.ip".to_string(), ip.to_string());
+    metadata
+        .annotations
+        .insert("k8s.port".to_string(), port.to_string());
+    if let Some(uid) = &pod.metadata.uid {
+        metadata
+            .annotations
+            .insert("k8s.pod_uid".to_string(), uid.clone());
+    }
+
+    Some(Instance::new(name, format!("{ip}:{port}")).with_metadata(metadata))
+}
+
+#[cfg(feature = "kubernetes")]
 impl std::fmt::Debug for KubernetesDiscovery {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("KubernetesDiscovery")
             .field("namespace", &self.namespace)
             .field("label_selector", &self.label_selector)
             .field("port_name", &self.port_name)
+            .field("watch", &self.watch.is_some())
             .finish_non_exhaustive()
     }
 }
 
+#[cfg(feature = "kubernetes")]
 #[async_trait]
 impl Discovery for KubernetesDiscovery {
     async fn discover(&self, service: &str) -> Result<Vec<Instance>> {
@@ -591,6 +698,20 @@ impl Discovery for KubernetesDiscovery {
     }
 
     async fn all_instances(&self) -> Result<Vec<Instance>> {
+        if let Some(watch) = &self.watch {
+            watch
+                .store
+                .wait_until_ready()
+                .await
+                .map_err(|e| DistributorError::Internal(format!("Kubernetes pod watch: {e}")))?;
+            return Ok(watch
+                .store
+                .state()
+                .iter()
+                .filter_map(|pod| pod_instance(pod, &self.port_name))
+                .collect());
+        }
+
         let instances = self.instances.read().await;
         if instances.is_empty() {
             // If cache is empty, do a refresh first
@@ -604,7 +725,11 @@ impl Discovery for KubernetesDiscovery {
     }
 
     async fn refresh(&self) -> Result<()> {
-        let discovered = self.query_endpoints().await?;
+        // A watch keeps the store current on its own
+        if self.watch.is_some() {
+            return Ok(());
+        }
+        let discovered = self.query_pods().await?;
         let mut instances = self.instances.write().await;
         *instances = discovered;
         Ok(())
@@ -626,6 +751,7 @@ impl Discovery for KubernetesDiscovery {
 pub async fn create_discovery(source: DiscoverySource) -> Result<Box<dyn Discovery>> {
     match source {
         DiscoverySource::Static { endpoints } => Ok(Box::new(StaticDiscovery::new(endpoints))),
+        #[cfg(feature = "kubernetes")]
         DiscoverySource::Kubernetes {
             namespace,
             label_selector,
@@ -634,6 +760,8 @@ pub async fn create_discovery(source: DiscoverySource) -> Result<Box<dyn Discove
             let discovery = KubernetesDiscovery::new(namespace, label_selector, port_name).await?;
             Ok(Box::new(discovery))
         }
+        #[cfg(not(feature = "kubernetes"))]
+        DiscoverySource::Kubernetes { .. } => Err(kubernetes_disabled()),
         DiscoverySource::Dns {
             hosts,
             port,
@@ -648,6 +776,14 @@ pub async fn create_discovery(source: DiscoverySource) -> Result<Box<dyn Discove
     }
 }
 
+/// Error for Kubernetes discovery in builds without the `kubernetes` feature.
+#[cfg(not(feature = "kubernetes"))]
+pub(crate) fn kubernetes_disabled() -> DistributorError {
+    DistributorError::InvalidConfig {
+        reason: "Kubernetes discovery requires the `kubernetes` feature".to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -951,7 +1087,162 @@ mod tests {
         assert!(!instances.is_empty());
     }
 
+    #[cfg(feature = "kubernetes")]
+    fn pod(name: &str, ready: bool, ports: &serde_json::Value) -> Pod {
+        serde_json::from_value(serde_json::json!({
+            "metadata": {
+                "name": name,
+                "namespace": "prod",
+                "uid": format!("uid-{name}"),
+                "labels": {"app": "users-service", "deployment-group": "blue"},
+            },
+            "spec": {
+                "nodeName": "node-1",
+                "containers": [
+                    {"name": "sidecar", "ports": [{"name": "admin", "containerPort": 9901}]},
+                    {"name": "archimedes", "ports": ports},
+                ],
+            },
+            "status": {
+                "podIP": "10.0.0.7",
+                "conditions": [{"type": "Ready", "status": if ready { "True" } else { "False" }}],
+            },
+        }))
+        .unwrap()
+    }
+
+    #[cfg(feature = "kubernetes")]
+    #[test]
+    fn test_pod_instance() {
+        let ports = serde_json::json!([
+            {"name": "http", "containerPort": 8080},
+            {"name": "grpc", "containerPort": 9090},
+        ]);
+
+        let instance = pod_instance(&pod("users-7f9c", true, &ports), "grpc").unwrap();
+        assert_eq!(instance.id, "users-7f9c");
+        assert_eq!(instance.endpoint.to_uri(), "http://10.0.0.7:9090");
+        assert_eq!(instance.service(), Some("users-service"));
+        assert_eq!(instance.metadata.namespace.as_deref(), Some("prod"));
+        assert_eq!(instance.metadata.node_name.as_deref(), Some("node-1"));
+        assert_eq!(
+            instance.metadata.get_label("deployment-group"),
+            Some("blue")
+        );
+        assert_eq!(
+            instance
+                .metadata
+                .annotations
+                .get("k8s.pod_uid")
+                .map(String::as_str),
+            Some("uid-users-7f9c")
+        );
+
+        // Without a port of that name, the archimedes container's first port is used
+        let instance = pod_instance(&pod("users-7f9c", true, &ports), "metrics").unwrap();
+        assert_eq!(instance.endpoint.to_uri(), "http://10.0.0.7:8080");
+
+        // Named ports of other containers match too
+        let instance = pod_instance(&pod("users-7f9c", true, &ports), "admin").unwrap();
+        assert_eq!(instance.endpoint.to_uri(), "http://10.0.0.7:9901");
+    }
+
+    #[cfg(feature = "kubernetes")]
+    #[test]
+    fn test_pod_instance_skips_unready_pods() {
+        let ports = serde_json::json!([{"name": "grpc", "containerPort": 9090}]);
+        assert!(pod_instance(&pod("users-7f9c", false, &ports), "grpc").is_none());
+
+        let mut pending = pod("users-7f9c", true, &ports);
+        pending.status.as_mut().unwrap().pod_ip = None;
+        assert!(pod_instance(&pending, "grpc").is_none());
+    }
+
+    #[cfg(feature = "kubernetes")]
+    #[tokio::test]
+    async fn test_kubernetes_watch_mode() {
+        use http::{Request, Response};
+        use kube::client::Body;
+
+        let ports = serde_json::json!([{"name": "grpc", "containerPort": 9090}]);
+        let list = serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "PodList",
+            "metadata": {"resourceVersion": "1"},
+            "items": [pod("users-1", true, &ports)],
+        });
+        let mut added = pod("users-2", true, &ports);
+        added.metadata.resource_version = Some("2".to_string());
+        let event = serde_json::json!({"type": "ADDED", "object": added});
+
+        // A mock API server: the initial list, then one watch event once released
+        let (service, mut handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+        let (release, released) = tokio::sync::oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let (request, send) = handle.next_request().await.unwrap();
+            assert_eq!(request.uri().path(), "/api/v1/namespaces/prod/pods");
+            send.send_response(Response::new(Body::from(
+                serde_json::to_vec(&list).unwrap(),
+            )));
+
+            let (request, send) = handle.next_request().await.unwrap();
+            assert!(request.uri().query().unwrap().contains("watch=true"));
+            released.await.unwrap();
+            let mut body = serde_json::to_vec(&event).unwrap();
+            body.push(b'\n');
+            send.send_response(Response::new(Body::from(body)));
+
+            // Leave later watch requests pending
+            std::future::pending::<()>().await;
+        });
+
+        let discovery = KubernetesDiscovery::with_client(
+            kube::Client::new(service, "default"),
+            Some("prod".to_string()),
+            Some("app=users-service".to_string()),
+            None,
+        )
+        .watching()
+        .await
+        .unwrap();
+
+        let ids = |instances: Vec<Instance>| {
+            let mut ids: Vec<_> = instances.into_iter().map(|i| i.id).collect();
+            ids.sort();
+            ids
+        };
+        let instances = discovery.discover("users-service").await.unwrap();
+        assert_eq!(ids(instances), vec!["users-1"]);
+
+        release.send(()).unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while discovery.all_instances().await.unwrap().len() < 2 {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        discovery.refresh().await.unwrap();
+        let instances = discovery.discover("users-service").await.unwrap();
+        assert_eq!(ids(instances), vec!["users-1", "users-2"]);
+    }
+
+    #[cfg(not(feature = "kubernetes"))]
+    #[tokio::test]
+    async fn test_create_discovery_kubernetes_disabled() {
+        let source = DiscoverySource::Kubernetes {
+            namespace: None,
+            label_selector: None,
+            port_name: None,
+        };
+        let Err(err) = create_discovery(source).await else {
+            panic!("expected kubernetes discovery to be unavailable");
+        };
+        assert!(err.to_string().contains("`kubernetes` feature"));
+    }
+
     // This test requires a Kubernetes cluster and is ignored by default
+    #[cfg(feature = "kubernetes")]
     #[tokio::test]
     #[ignore = "requires Kubernetes cluster"]
     async fn test_kubernetes_discovery_integration() {
@@ -969,6 +1260,7 @@ mod tests {
         println!("Discovered {} instances", instances.len());
     }
 
+    #[cfg(feature = "kubernetes")]
     #[tokio::test]
     #[ignore = "requires Kubernetes cluster"]
     async fn test_create_discovery_kubernetes() {
@@ -1,12 +1,54 @@
 //! Error types for the distributor crate.
 //!
 //! This module defines all errors that can occur during policy distribution.
+//!
+//! # Error codes
+//!
+//! Every [`DistributorError`] has a stable code, returned by
+//! [`DistributorError::code`], for handling errors without matching on
+//! their messages. Codes are never reused or renumbered:
+//!
+//! | Code | Variant |
+//! |------|---------|
+//! | `EUN-D001` | [`NoInstancesFound`](DistributorError::NoInstancesFound) |
+//! | `EUN-D002` | [`NoHealthyInstances`](DistributorError::NoHealthyInstances) |
+//! | `EUN-D003` | [`InstanceUnreachable`](DistributorError::InstanceUnreachable) |
+//! | `EUN-D004` | [`PolicyRejected`](DistributorError::PolicyRejected) |
+//! | `EUN-D005` | [`DiscoveryFailed`](DistributorError::DiscoveryFailed) |
+//! | `EUN-D006` | [`HealthCheckFailed`](DistributorError::HealthCheckFailed) |
+//! | `EUN-D007` | [`DeploymentInProgress`](DistributorError::DeploymentInProgress) |
+//! | `EUN-D008` | [`DeploymentNotFound`](DistributorError::DeploymentNotFound) |
+//! | `EUN-D009` | [`InvalidConfig`](DistributorError::InvalidConfig) |
+//! | `EUN-D010` | [`Connection`](DistributorError::Connection) |
+//! | `EUN-D011` | [`Timeout`](DistributorError::Timeout) |
+//! | `EUN-D012` | [`Transport`](DistributorError::Transport) |
+//! | `EUN-D013` | [`GrpcStatus`](DistributorError::GrpcStatus) |
+//! | `EUN-D014` | [`RpcStatus`](DistributorError::RpcStatus) |
+//! | `EUN-D015` | [`Io`](DistributorError::Io) |
+//! | `EUN-D016` | [`StateError`](DistributorError::StateError) |
+//! | `EUN-D017` | [`InvalidOperation`](DistributorError::InvalidOperation) |
+//! | `EUN-D018` | [`CircuitOpen`](DistributorError::CircuitOpen) |
+//! | `EUN-D019` | [`VersionResolution`](DistributorError::VersionResolution) |
+//! | `EUN-D020` | [`Internal`](DistributorError::Internal) |
+//! | `EUN-D021` | [`Scheduler`](DistributorError::Scheduler) |
+//! | `EUN-D022` | [`BundleFetch`](DistributorError::BundleFetch) |
+//! | `EUN-D023` | [`BundleLimitExceeded`](DistributorError::BundleLimitExceeded) |
+//! | `EUN-D024` | [`SignatureVerificationFailed`](DistributorError::SignatureVerificationFailed) |
+//!
+//! Control-plane errors carry their code in the gRPC status details, as
+//! `{"code": "EUN-D008"}`.
 
 use thiserror::Error;
 
+use crate::scheduler::SchedulerError;
+
 /// Result type alias for distributor operations.
 pub type Result<T> = std::result::Result<T, DistributorError>;
 
+/// gRPC metadata key carrying the [`DistributorError::code`] of a failed
+/// control-plane call.
+pub const ERROR_CODE_METADATA: &str = "x-eunomia-error-code";
+
 /// Errors that can occur during policy distribution.
 #[derive(Error, Debug)]
 pub enum DistributorError {
@@ -17,6 +59,15 @@ pub enum DistributorError {
         service: String,
     },
 
+    /// Every instance of the target service is unhealthy.
+    #[error("no healthy instances for service '{service}': {excluded} excluded as unhealthy")]
+    NoHealthyInstances {
+        /// The service that had no healthy instances.
+        service: String,
+        /// Number of instances excluded as unhealthy.
+        excluded: usize,
+    },
+
     /// Instance is unreachable.
     #[error("instance '{instance_id}' at '{endpoint}' is unreachable: {reason}")]
     InstanceUnreachable {
@@ -97,6 +148,15 @@ pub enum DistributorError {
     #[error("gRPC error: {0}")]
     GrpcStatus(String),
 
+    /// An instance answered a gRPC call with an error status.
+    #[error("instance returned {code:?}: {message}")]
+    RpcStatus {
+        /// Status code.
+        code: tonic::Code,
+        /// Status message.
+        message: String,
+    },
+
     /// IO error.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -115,9 +175,67 @@ pub enum DistributorError {
         reason: String,
     },
 
+    /// The instance's push circuit is open after repeated failures.
+    #[error("circuit open for instance '{instance_id}', not pushing")]
+    CircuitOpen {
+        /// The instance whose circuit is open.
+        instance_id: String,
+    },
+
+    /// A deployment's version query could not be resolved in the registry.
+    #[error("failed to resolve version '{query}' for service '{service}': {source}")]
+    VersionResolution {
+        /// Service name.
+        service: String,
+        /// The version query.
+        query: String,
+        /// Underlying registry error, boxed to keep the error small.
+        #[source]
+        source: Box<eunomia_registry::RegistryError>,
+    },
+
     /// Internal error.
     #[error("internal error: {0}")]
     Internal(String),
+
+    /// A deployment could not be scheduled.
+    #[error("scheduler error: {0}")]
+    Scheduler(#[from] SchedulerError),
+
+    /// The bundle of a deployment could not be fetched from the registry.
+    #[error("failed to fetch bundle {service}@{version}: {source}")]
+    BundleFetch {
+        /// Service name.
+        service: String,
+        /// Bundle version.
+        version: String,
+        /// Underlying registry error, boxed to keep the error small.
+        #[source]
+        source: Box<eunomia_registry::RegistryError>,
+    },
+
+    /// A bundle exceeds the size or complexity limits for pushing.
+    #[error("bundle {service}@{version} exceeds its limits: {source}")]
+    BundleLimitExceeded {
+        /// Service name.
+        service: String,
+        /// Bundle version.
+        version: String,
+        /// The exceeded limit.
+        #[source]
+        source: eunomia_core::BundleLimitError,
+    },
+
+    /// A bundle is not signed by enough trusted keys to be deployed.
+    #[error("signature verification failed for bundle {service}@{version}: {reason}")]
+    SignatureVerificationFailed {
+        /// Service name.
+        service: String,
+        /// Bundle version.
+        version: String,
+        /// Why the bundle was refused.
+        reason: String,
+    },
 }
 
 /// Connection-related errors.
@@ -158,6 +276,15 @@ pub enum ConnectionError {
     },
 }
 
+impl From<tonic::Status> for DistributorError {
+    fn from(status: tonic::Status) -> Self {
+        Self::RpcStatus {
+            code: status.code(),
+            message: status.message().to_string(),
+        }
+    }
+}
+
 impl DistributorError {
     /// Returns true if this error is retryable.
     pub fn is_retryable(&self) -> bool {
@@ -170,23 +297,103 @@ impl DistributorError {
                 )
                 | Self::Timeout { .. }
                 | Self::Transport(_)
+                | Self::RpcStatus {
+                    code: tonic::Code::Unavailable | tonic::Code::DeadlineExceeded,
+                    ..
+                }
         )
     }
 
+    /// Returns the stable code of this error, e.g. `EUN-D001`.
+    ///
+    /// See the [module documentation](self#error-codes) for the codes.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::NoInstancesFound { .. } => "EUN-D001",
+            Self::NoHealthyInstances { .. } => "EUN-D002",
+            Self::InstanceUnreachable { .. } => "EUN-D003",
+            Self::PolicyRejected { .. } => "EUN-D004",
+            Self::DiscoveryFailed { .. } => "EUN-D005",
+            Self::HealthCheckFailed { .. } => "EUN-D006",
+            Self::DeploymentInProgress { .. } => "EUN-D007",
+            Self::DeploymentNotFound { .. } => "EUN-D008",
+            Self::InvalidConfig { .. } => "EUN-D009",
+            Self::Connection(_) => "EUN-D010",
+            Self::Timeout { .. } => "EUN-D011",
+            Self::Transport(_) => "EUN-D012",
+            Self::GrpcStatus(_) => "EUN-D013",
+            Self::RpcStatus { .. } => "EUN-D014",
+            Self::Io(_) => "EUN-D015",
+            Self::StateError { .. } => "EUN-D016",
+            Self::InvalidOperation { .. } => "EUN-D017",
+            Self::CircuitOpen { .. } => "EUN-D018",
+            Self::VersionResolution { .. } => "EUN-D019",
+            Self::Internal(_) => "EUN-D020",
+            Self::Scheduler(_) => "EUN-D021",
+            Self::BundleFetch { .. } => "EUN-D022",
+            Self::BundleLimitExceeded { .. } => "EUN-D023",
+            Self::SignatureVerificationFailed { .. } => "EUN-D024",
+        }
+    }
+
+    /// Converts this error into a gRPC status with its
+    /// [`grpc_code`](Self::grpc_code) and message, carrying its
+    /// [`code`](Self::code) in the [`ERROR_CODE_METADATA`] metadata.
+    #[must_use]
+    pub fn to_status(&self) -> tonic::Status {
+        self.to_status_with(tonic::Code::from(self.grpc_code()), self.to_string())
+    }
+
+    /// Converts this error into a gRPC status with the given code and
+    /// message, carrying its [`code`](Self::code) in the
+    /// [`ERROR_CODE_METADATA`] metadata.
+    #[must_use]
+    pub fn to_status_with(&self, code: tonic::Code, message: impl Into<String>) -> tonic::Status {
+        let mut status = tonic::Status::new(code, message);
+        status.metadata_mut().insert(
+            ERROR_CODE_METADATA,
+            tonic::metadata::MetadataValue::from_static(self.code()),
+        );
+        status
+    }
+
+    /// Returns the error code carried in the metadata of a status built by
+    /// [`to_status`](Self::to_status), if any.
+    #[must_use]
+    pub fn code_from_status(status: &tonic::Status) -> Option<String> {
+        let code = status.metadata().get(ERROR_CODE_METADATA)?;
+        code.to_str().ok().map(ToString::to_string)
+    }
+
     /// Returns the error code for gRPC responses.
     pub fn grpc_code(&self) -> i32 {
         match self {
-            Self::NoInstancesFound { .. } | Self::DeploymentNotFound { .. } => 5, // NOT_FOUND
+            Self::NoInstancesFound { .. }
+            | Self::DeploymentNotFound { .. }
+            | Self::VersionResolution { .. } => 5, // NOT_FOUND
             Self::PolicyRejected { .. }
             | Self::InvalidConfig { .. }
-            | Self::InvalidOperation { .. } => 3, // INVALID_ARGUMENT
-            Self::DeploymentInProgress { .. } => 6,                               // ALREADY_EXISTS
-            Self::Timeout { .. } => 4, // DEADLINE_EXCEEDED
-            Self::InstanceUnreachable { .. } | Self::Connection(_) | Self::Transport(_) => 14, // UNAVAILABLE
+            | Self::InvalidOperation { .. }
+            | Self::Scheduler(SchedulerError::InvalidSchedule { .. }) => 3, // INVALID_ARGUMENT
+            Self::BundleLimitExceeded { .. } | Self::SignatureVerificationFailed { .. } => {
+                9 // FAILED_PRECONDITION
+            }
+            Self::DeploymentInProgress { .. } => 6, // ALREADY_EXISTS
+            Self::Timeout { .. } => 4,              // DEADLINE_EXCEEDED
+            Self::Scheduler(SchedulerError::QueueFull) => 8, // RESOURCE_EXHAUSTED
+            Self::InstanceUnreachable { .. }
+            | Self::Connection(_)
+            | Self::Transport(_)
+            | Self::CircuitOpen { .. }
+            | Self::BundleFetch { .. }
+            | Self::NoHealthyInstances { .. } => 14, // UNAVAILABLE
+            Self::RpcStatus { code, .. } => *code as i32,
             Self::GrpcStatus(_)
             | Self::Io(_)
             | Self::StateError { .. }
             | Self::Internal(_)
+            | Self::Scheduler(SchedulerError::Persistence { .. })
             | Self::DiscoveryFailed { .. }
             | Self::HealthCheckFailed { .. } => 13, // INTERNAL
         }
@@ -205,6 +412,17 @@ mod tests {
         assert!(err.to_string().contains("test-service"));
     }
 
+    #[test]
+    fn test_rpc_status_is_retryable() {
+        let err = DistributorError::from(tonic::Status::unavailable("restarting"));
+        assert!(err.is_retryable());
+        assert_eq!(err.grpc_code(), 14);
+        assert_eq!(err.to_string(), "instance returned Unavailable: restarting");
+
+        assert!(DistributorError::from(tonic::Status::deadline_exceeded("slow")).is_retryable());
+        assert!(!DistributorError::from(tonic::Status::invalid_argument("bad")).is_retryable());
+    }
+
     #[test]
     fn test_instance_unreachable_is_retryable() {
         let err = DistributorError::InstanceUnreachable {
@@ -232,6 +450,30 @@ mod tests {
         assert!(err.is_retryable());
     }
 
+    #[test]
+    fn test_circuit_open_not_retryable() {
+        let err = DistributorError::CircuitOpen {
+            instance_id: "inst-1".to_string(),
+        };
+        assert!(!err.is_retryable());
+        assert_eq!(err.grpc_code(), 14);
+        assert!(err.to_string().contains("inst-1"));
+    }
+
+    #[test]
+    fn test_no_healthy_instances() {
+        let err = DistributorError::NoHealthyInstances {
+            service: "users-service".to_string(),
+            excluded: 3,
+        };
+        assert!(!err.is_retryable());
+        assert_eq!(err.grpc_code(), 14);
+        assert_eq!(
+            err.to_string(),
+            "no healthy instances for service 'users-service': 3 excluded as unhealthy"
+        );
+    }
+
     #[test]
     fn test_grpc_codes() {
         assert_eq!(
@@ -251,6 +493,174 @@ mod tests {
         );
     }
 
+    #[test]
+    #[allow(clippy::too_many_lines)]
+    fn test_error_codes_are_unique_and_stable() {
+        let s = String::new;
+        let errors = [
+            (
+                DistributorError::NoInstancesFound { service: s() },
+                "EUN-D001",
+            ),
+            (
+                DistributorError::NoHealthyInstances {
+                    service: s(),
+                    excluded: 0,
+                },
+                "EUN-D002",
+            ),
+            (
+                DistributorError::InstanceUnreachable {
+                    instance_id: s(),
+                    endpoint: s(),
+                    reason: s(),
+                },
+                "EUN-D003",
+            ),
+            (
+                DistributorError::PolicyRejected {
+                    instance_id: s(),
+                    reason: s(),
+                },
+                "EUN-D004",
+            ),
+            (
+                DistributorError::DiscoveryFailed {
+                    service: s(),
+                    reason: s(),
+                },
+                "EUN-D005",
+            ),
+            (
+                DistributorError::HealthCheckFailed {
+                    instance_id: s(),
+                    reason: s(),
+                },
+                "EUN-D006",
+            ),
+            (
+                DistributorError::DeploymentInProgress {
+                    service: s(),
+                    deployment_id: s(),
+                },
+                "EUN-D007",
+            ),
+            (
+                DistributorError::DeploymentNotFound { deployment_id: s() },
+                "EUN-D008",
+            ),
+            (DistributorError::InvalidConfig { reason: s() }, "EUN-D009"),
+            (
+                DistributorError::Connection(ConnectionError::ConnectionRefused { endpoint: s() }),
+                "EUN-D010",
+            ),
+            (DistributorError::Timeout { operation: s() }, "EUN-D011"),
+            (DistributorError::Transport(s()), "EUN-D012"),
+            (DistributorError::GrpcStatus(s()), "EUN-D013"),
+            (
+                DistributorError::from(tonic::Status::internal("")),
+                "EUN-D014",
+            ),
+            (DistributorError::Io(std::io::Error::other("")), "EUN-D015"),
+            (DistributorError::StateError { reason: s() }, "EUN-D016"),
+            (
+                DistributorError::InvalidOperation { reason: s() },
+                "EUN-D017",
+            ),
+            (
+                DistributorError::CircuitOpen { instance_id: s() },
+                "EUN-D018",
+            ),
+            (
+                DistributorError::VersionResolution {
+                    service: s(),
+                    query: s(),
+                    source: Box::new(eunomia_registry::RegistryError::CacheError { message: s() }),
+                },
+                "EUN-D019",
+            ),
+            (DistributorError::Internal(s()), "EUN-D020"),
+            (
+                DistributorError::Scheduler(SchedulerError::QueueFull),
+                "EUN-D021",
+            ),
+            (
+                DistributorError::BundleFetch {
+                    service: s(),
+                    version: s(),
+                    source: Box::new(eunomia_registry::RegistryError::CacheError { message: s() }),
+                },
+                "EUN-D022",
+            ),
+            (
+                DistributorError::BundleLimitExceeded {
+                    service: s(),
+                    version: s(),
+                    source: eunomia_core::BundleLimitError::TooManyPolicies {
+                        policies: 2,
+                        limit: 1,
+                    },
+                },
+                "EUN-D023",
+            ),
+            (
+                DistributorError::SignatureVerificationFailed {
+                    service: s(),
+                    version: s(),
+                    reason: s(),
+                },
+                "EUN-D024",
+            ),
+        ];
+
+        let mut codes = std::collections::HashSet::new();
+        for (err, code) in &errors {
+            assert_eq!(err.code(), *code, "{err:?}");
+            assert!(codes.insert(err.code()), "duplicate code {code}");
+        }
+    }
+
+    #[test]
+    fn test_status_carries_error_code() {
+        let err = DistributorError::DeploymentNotFound {
+            deployment_id: "deploy-1".to_string(),
+        };
+
+        let status = err.to_status();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert_eq!(status.message(), "deployment 'deploy-1' not found");
+        assert_eq!(
+            DistributorError::code_from_status(&status).as_deref(),
+            Some("EUN-D008")
+        );
+
+        assert_eq!(
+            status.metadata().get(ERROR_CODE_METADATA).unwrap(),
+            "EUN-D008"
+        );
+        assert!(status.details().is_empty());
+
+        let status = err.to_status_with(tonic::Code::Internal, "failed");
+        assert_eq!(
+            DistributorError::code_from_status(&status).as_deref(),
+            Some("EUN-D008")
+        );
+        assert_eq!(
+            DistributorError::code_from_status(&tonic::Status::internal("")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scheduler_grpc_codes() {
+        let invalid = SchedulerError::InvalidSchedule { reason: "r".into() };
+        assert_eq!(DistributorError::from(invalid).grpc_code(), 3);
+        assert_eq!(
+            DistributorError::from(SchedulerError::QueueFull).grpc_code(),
+            8
+        );
+    }
+
     #[test]
     fn test_connection_error_display() {
         let err = ConnectionError::ConnectFailed {
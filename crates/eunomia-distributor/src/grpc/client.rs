@@ -0,0 +1,239 @@
+//! gRPC client for the Control Plane service.
+//!
+//! Used by the CLI to query and drive a running control plane, with the
+//! same JSON-framed messages that [`ControlPlaneServiceServer`] serves.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use eunomia_distributor::grpc::ControlPlaneClient;
+//! use eunomia_distributor::grpc::types::GetDeploymentRequest;
+//!
+//! let mut client = ControlPlaneClient::connect("http://localhost:50052").await?;
+//! let deployment = client
+//!     .get_deployment(GetDeploymentRequest { deployment_id: "deploy-1".to_string() })
+//!     .await?
+//!     .into_inner();
+//! ```
+//!
+//! [`ControlPlaneServiceServer`]: super::ControlPlaneServiceServer
+
+#![allow(clippy::result_large_err)] // Status is from tonic, can't change its size
+
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{IntoRequest, Response, Status};
+
+use super::codec::JsonCodec;
+use super::control_plane::METHOD_PREFIX;
+use super::types::{
+    CancelScheduledDeploymentRequest, DeployPolicyRequest, DeployPolicyResponse, DeploymentRecord,
+    GetDeploymentRequest, GetInstanceHealthRequest, GetPolicyStatusRequest, InstanceHealthResponse,
+    ListDeploymentsRequest, ListDeploymentsResponse, ListInstancesRequest, ListInstancesResponse,
+    ListScheduledDeploymentsRequest, ListScheduledDeploymentsResponse, PauseDeploymentRequest,
+    PolicyStatusResponse, ResumeDeploymentRequest, RollbackPolicyRequest, RollbackPolicyResponse,
+    ScheduleDeploymentRequest, ScheduledDeploymentRecord,
+};
+
+/// Time allowed to connect to the control plane.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Client for the Control Plane gRPC service.
+#[derive(Debug, Clone)]
+pub struct ControlPlaneClient {
+    inner: tonic::client::Grpc<Channel>,
+}
+
+impl ControlPlaneClient {
+    /// Connects to the control plane at `endpoint`, such as
+    /// `http://localhost:50052`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the endpoint is invalid or cannot be reached.
+    pub async fn connect(endpoint: impl Into<String>) -> Result<Self, tonic::transport::Error> {
+        let channel = Endpoint::from_shared(endpoint.into())?
+            .connect_timeout(CONNECT_TIMEOUT)
+            .connect()
+            .await?;
+        Ok(Self::new(channel))
+    }
+
+    /// Creates a client sending calls over `channel`.
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            inner: tonic::client::Grpc::new(channel),
+        }
+    }
+
+    /// Sends a unary call to the `ControlPlane` method `method`.
+    async fn unary<Req, Res>(
+        &mut self,
+        request: impl IntoRequest<Req>,
+        method: &str,
+    ) -> Result<Response<Res>, Status>
+    where
+        Req: Serialize + Send + Sync + 'static,
+        Res: DeserializeOwned + Send + Sync + 'static,
+    {
+        self.inner
+            .ready()
+            .await
+            .map_err(|e| Status::unavailable(format!("control plane not ready: {e}")))?;
+        let path = PathAndQuery::try_from(format!("{METHOD_PREFIX}{method}"))
+            .map_err(|e| Status::internal(format!("invalid method path: {e}")))?;
+        self.inner
+            .unary(request.into_request(), path, JsonCodec::default())
+            .await
+    }
+
+    /// Deploys a policy to instances.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn deploy_policy(
+        &mut self,
+        request: impl IntoRequest<DeployPolicyRequest>,
+    ) -> Result<Response<DeployPolicyResponse>, Status> {
+        self.unary(request, "DeployPolicy").await
+    }
+
+    /// Rolls a policy back to a previous version.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn rollback_policy(
+        &mut self,
+        request: impl IntoRequest<RollbackPolicyRequest>,
+    ) -> Result<Response<RollbackPolicyResponse>, Status> {
+        self.unary(request, "RollbackPolicy").await
+    }
+
+    /// Gets the current policy status of a service.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn get_policy_status(
+        &mut self,
+        request: impl IntoRequest<GetPolicyStatusRequest>,
+    ) -> Result<Response<PolicyStatusResponse>, Status> {
+        self.unary(request, "GetPolicyStatus").await
+    }
+
+    /// Lists registered instances.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn list_instances(
+        &mut self,
+        request: impl IntoRequest<ListInstancesRequest>,
+    ) -> Result<Response<ListInstancesResponse>, Status> {
+        self.unary(request, "ListInstances").await
+    }
+
+    /// Gets the health of an instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn get_instance_health(
+        &mut self,
+        request: impl IntoRequest<GetInstanceHealthRequest>,
+    ) -> Result<Response<InstanceHealthResponse>, Status> {
+        self.unary(request, "GetInstanceHealth").await
+    }
+
+    /// Lists tracked deployments, newest first, one page at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn list_deployments(
+        &mut self,
+        request: impl IntoRequest<ListDeploymentsRequest>,
+    ) -> Result<Response<ListDeploymentsResponse>, Status> {
+        self.unary(request, "ListDeployments").await
+    }
+
+    /// Gets a tracked deployment with its per-instance results.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with, such as
+    /// `NOT_FOUND` for an unknown deployment.
+    pub async fn get_deployment(
+        &mut self,
+        request: impl IntoRequest<GetDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status> {
+        self.unary(request, "GetDeployment").await
+    }
+
+    /// Pauses an in-progress deployment before its next batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn pause_deployment(
+        &mut self,
+        request: impl IntoRequest<PauseDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status> {
+        self.unary(request, "PauseDeployment").await
+    }
+
+    /// Resumes a paused deployment.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn resume_deployment(
+        &mut self,
+        request: impl IntoRequest<ResumeDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status> {
+        self.unary(request, "ResumeDeployment").await
+    }
+
+    /// Schedules a deployment to start inside its time windows and outside
+    /// its freeze periods.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn schedule_deployment(
+        &mut self,
+        request: impl IntoRequest<ScheduleDeploymentRequest>,
+    ) -> Result<Response<ScheduledDeploymentRecord>, Status> {
+        self.unary(request, "ScheduleDeployment").await
+    }
+
+    /// Lists scheduled deployments that have not started yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn list_scheduled_deployments(
+        &mut self,
+        request: impl IntoRequest<ListScheduledDeploymentsRequest>,
+    ) -> Result<Response<ListScheduledDeploymentsResponse>, Status> {
+        self.unary(request, "ListScheduledDeployments").await
+    }
+
+    /// Cancels a scheduled deployment that has not started yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns the status the control plane failed the call with.
+    pub async fn cancel_scheduled_deployment(
+        &mut self,
+        request: impl IntoRequest<CancelScheduledDeploymentRequest>,
+    ) -> Result<Response<ScheduledDeploymentRecord>, Status> {
+        self.unary(request, "CancelScheduledDeployment").await
+    }
+}
@@ -10,19 +10,28 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use futures::Stream;
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 use tracing::{debug, info, instrument, warn};
 
+use super::codec::JsonCodec;
 use super::rate_limit::RateLimiterRegistry;
 use super::types::{
-    DeployPolicyRequest, DeployPolicyResponse, DeploymentEvent, DeploymentEventType,
-    DeploymentSummary, GetInstanceHealthRequest, GetPolicyStatusRequest, GrpcDeploymentState,
+    CancelScheduledDeploymentRequest, DeployPolicyRequest, DeployPolicyResponse, DeploymentEvent,
+    DeploymentEventType, DeploymentRecord, DeploymentSummary, GetDeploymentRequest,
+    GetInstanceHealthRequest, GetPolicyStatusRequest, GrpcDeploymentState, GrpcDeploymentStrategy,
     GrpcHealthState, GrpcStrategyType, InstanceDeploymentResult, InstanceHealthResponse,
-    InstanceInfo, InstancePolicyStatus, ListInstancesRequest, ListInstancesResponse,
-    PolicyStatusResponse, RollbackPolicyRequest, RollbackPolicyResponse, WatchDeploymentRequest,
+    InstanceInfo, InstancePolicyStatus, ListDeploymentsRequest, ListDeploymentsResponse,
+    ListInstancesRequest, ListInstancesResponse, ListScheduledDeploymentsRequest,
+    ListScheduledDeploymentsResponse, PauseDeploymentRequest, PolicyStatusResponse,
+    ResumeDeploymentRequest, RollbackPolicyRequest, RollbackPolicyResponse,
+    ScheduleDeploymentRequest, ScheduledDeploymentRecord, WatchDeploymentRequest,
 };
+use crate::error::DistributorError;
 use crate::events::{DeploymentEventData, EventBus, EventType};
-use crate::{DeploymentState, DeploymentStrategy, Distributor, HealthState};
+use crate::{
+    DeploymentFilter, DeploymentState, DeploymentStrategy, Distributor, HealthState, Instance,
+    ScheduleConstraints,
+};
 
 /// Control Plane gRPC service implementation.
 #[derive(Clone)]
@@ -91,6 +100,31 @@ impl ControlPlaneService {
         }
     }
 
+    /// Returns the health of an instance: the health monitor's view if it
+    /// has probed the instance, else the discovered status.
+    fn health_of(&self, instance: &Instance) -> HealthState {
+        self.distributor
+            .instance_health(&instance.id)
+            .map_or_else(|| instance.status.to_health_state(), |h| h.state)
+    }
+
+    /// Looks up a tracked deployment as a gRPC record.
+    async fn deployment_record(
+        &self,
+        deployment_id: &str,
+    ) -> Result<Response<DeploymentRecord>, Status> {
+        let info = self
+            .distributor
+            .get_deployment(deployment_id)
+            .await
+            .map_err(|e| match e {
+                DistributorError::DeploymentNotFound { .. } => e.to_status(),
+                e => e.to_status_with(Code::Internal, format!("Failed to get deployment: {e}")),
+            })?;
+
+        Ok(Response::new(DeploymentRecord::from(&info)))
+    }
+
     /// Convert to a tonic service.
     pub fn into_service(self) -> ControlPlaneServiceServer<Self> {
         ControlPlaneServiceServer::new(self)
@@ -110,40 +144,29 @@ impl ControlPlane for ControlPlaneService {
         // Check rate limit
         self.check_rate_limit("DeployPolicy")?;
 
+        #[cfg(feature = "eunomia-otel")]
+        let parent = crate::otel::extract_context(request.metadata());
         let req = request.into_inner();
         info!(
             "DeployPolicy request: service={}, version={}",
             req.service, req.version
         );
 
-        // Convert gRPC strategy to internal strategy
-        #[allow(clippy::cast_sign_loss)]
-        let strategy = req
-            .strategy
-            .map_or_else(DeploymentStrategy::immediate, |s| match s.strategy_type {
-                GrpcStrategyType::Canary => DeploymentStrategy::canary(
-                    s.canary_percentage.unsigned_abs(),
-                    Duration::from_secs(s.batch_delay_seconds.unsigned_abs()),
-                )
-                .with_max_failures(s.max_failures.unsigned_abs())
-                .with_auto_rollback(s.auto_rollback),
-                GrpcStrategyType::Rolling => DeploymentStrategy::rolling(
-                    s.rolling_batch_size.unsigned_abs() as usize,
-                    Duration::from_secs(s.batch_delay_seconds.unsigned_abs()),
-                )
-                .with_max_failures(s.max_failures.unsigned_abs())
-                .with_auto_rollback(s.auto_rollback),
-                _ => DeploymentStrategy::immediate(),
-            });
+        let strategy = strategy_from_grpc(req.strategy);
 
         // Track deployment timing
         let deploy_start = Instant::now();
 
-        // Execute deployment
-        let result = self
-            .distributor
-            .deploy(&req.service, &req.version, strategy)
-            .await;
+        // Execute deployment, in the caller's trace if it sent one. The
+        // deploy future is boxed: inlined into the service future, its type
+        // is too deep for the compiler with all features enabled.
+        let deployment = Box::pin(
+            self.distributor
+                .deploy(&req.service, &req.version, strategy),
+        );
+        #[cfg(feature = "eunomia-otel")]
+        let deployment = opentelemetry::trace::FutureExt::with_context(deployment, parent);
+        let result = deployment.await;
 
         // Calculate deployment duration (capped at i64::MAX for protobuf compatibility)
         #[allow(clippy::cast_possible_truncation)]
@@ -173,6 +196,7 @@ impl ControlPlane for ControlPlaneService {
                         .map(|r| InstanceDeploymentResult {
                             instance_id: r.instance_id.clone(),
                             success: matches!(r.status, crate::InstanceResultStatus::Success),
+                            skipped: matches!(r.status, crate::InstanceResultStatus::Skipped),
                             error_message: match r.status {
                                 crate::InstanceResultStatus::Failed(e) => e,
                                 _ => String::new(),
@@ -188,7 +212,7 @@ impl ControlPlane for ControlPlaneService {
             }
             Err(e) => {
                 warn!("Deploy policy failed: {e}");
-                Err(Status::internal(format!("Deployment failed: {e}")))
+                Err(e.to_status_with(Code::Internal, format!("Deployment failed: {e}")))
             }
         }
     }
@@ -244,6 +268,7 @@ impl ControlPlane for ControlPlaneService {
                         .map(|r| InstanceDeploymentResult {
                             instance_id: r.instance_id.clone(),
                             success: matches!(r.status, crate::InstanceResultStatus::Success),
+                            skipped: matches!(r.status, crate::InstanceResultStatus::Skipped),
                             error_message: match r.status {
                                 crate::InstanceResultStatus::Failed(e) => e,
                                 _ => String::new(),
@@ -257,7 +282,7 @@ impl ControlPlane for ControlPlaneService {
             }
             Err(e) => {
                 warn!("Rollback policy failed: {e}");
-                Err(Status::internal(format!("Rollback failed: {e}")))
+                Err(e.to_status_with(Code::Internal, format!("Rollback failed: {e}")))
             }
         }
     }
@@ -277,7 +302,7 @@ impl ControlPlane for ControlPlaneService {
             .distributor
             .get_status(&req.service)
             .await
-            .map_err(|e| Status::internal(format!("Failed to get status: {e}")))?;
+            .map_err(|e| e.to_status_with(Code::Internal, format!("Failed to get status: {e}")))?;
 
         let state = match status.state {
             DeploymentState::Pending => GrpcDeploymentState::Pending,
@@ -286,6 +311,8 @@ impl ControlPlane for ControlPlaneService {
             DeploymentState::Failed => GrpcDeploymentState::Failed,
             DeploymentState::RolledBack => GrpcDeploymentState::RolledBack,
             DeploymentState::Cancelled => GrpcDeploymentState::Cancelled,
+            DeploymentState::Paused => GrpcDeploymentState::Paused,
+            DeploymentState::Interrupted => GrpcDeploymentState::Interrupted,
         };
 
         // Get instances for the service to populate status
@@ -298,17 +325,13 @@ impl ControlPlane for ControlPlaneService {
         let instance_statuses: Vec<InstancePolicyStatus> = instances
             .iter()
             .map(|inst| {
-                let health = match inst.status.to_health_state() {
-                    HealthState::Unknown => GrpcHealthState::Unknown,
-                    HealthState::Healthy => GrpcHealthState::Healthy,
-                    HealthState::Unhealthy | HealthState::Unreachable => GrpcHealthState::Unhealthy,
-                    HealthState::Degraded => GrpcHealthState::Degraded,
-                };
+                let monitored = self.distributor.instance_health(&inst.id);
                 InstancePolicyStatus {
                     instance_id: inst.id.clone(),
                     version: inst.status.policy_version().unwrap_or_default().to_string(),
-                    health,
-                    last_updated: None,
+                    health: self.health_of(inst).into(),
+                    last_updated: monitored.as_ref().map(|h| h.last_checked),
+                    last_seen: monitored.and_then(|h| h.last_seen),
                 }
             })
             .collect();
@@ -345,20 +368,16 @@ impl ControlPlane for ControlPlaneService {
             self.distributor
                 .list_instances(&req.service_filter)
                 .await
-                .map_err(|e| Status::internal(format!("Failed to list instances: {e}")))?
+                .map_err(|e| {
+                    e.to_status_with(Code::Internal, format!("Failed to list instances: {e}"))
+                })?
         };
 
         // Filter by health if specified
         let instances: Vec<_> = if let Some(health_filter) = req.health_filter {
-            let target_health = match health_filter {
-                GrpcHealthState::Healthy => HealthState::Healthy,
-                GrpcHealthState::Unhealthy => HealthState::Unhealthy,
-                GrpcHealthState::Degraded => HealthState::Degraded,
-                GrpcHealthState::Unknown => HealthState::Unknown,
-            };
             instances
                 .into_iter()
-                .filter(|i| i.status.to_health_state() == target_health)
+                .filter(|i| GrpcHealthState::from(self.health_of(i)) == health_filter)
                 .collect()
         } else {
             instances
@@ -367,27 +386,13 @@ impl ControlPlane for ControlPlaneService {
         let response = ListInstancesResponse {
             instances: instances
                 .iter()
-                .map(|inst| {
-                    let health = match inst.status.to_health_state() {
-                        HealthState::Unknown => GrpcHealthState::Unknown,
-                        HealthState::Healthy => GrpcHealthState::Healthy,
-                        HealthState::Unhealthy | HealthState::Unreachable => {
-                            GrpcHealthState::Unhealthy
-                        }
-                        HealthState::Degraded => GrpcHealthState::Degraded,
-                    };
-                    InstanceInfo {
-                        instance_id: inst.id.clone(),
-                        endpoint: format!("{}:{}", inst.endpoint.host, inst.endpoint.port),
-                        services: inst.metadata.service.clone().into_iter().collect(),
-                        health,
-                        policy_version: inst
-                            .status
-                            .policy_version()
-                            .unwrap_or_default()
-                            .to_string(),
-                        metadata: inst.metadata.labels.clone(),
-                    }
+                .map(|inst| InstanceInfo {
+                    instance_id: inst.id.clone(),
+                    endpoint: format!("{}:{}", inst.endpoint.host, inst.endpoint.port),
+                    services: inst.metadata.service.clone().into_iter().collect(),
+                    health: self.health_of(inst).into(),
+                    policy_version: inst.status.policy_version().unwrap_or_default().to_string(),
+                    metadata: inst.metadata.labels.clone(),
                 })
                 .collect(),
         };
@@ -406,10 +411,224 @@ impl ControlPlane for ControlPlaneService {
         let req = request.into_inner();
         debug!("GetInstanceHealth request: instance_id={}", req.instance_id);
 
-        // For now, we don't have a direct get_instance method
-        Err(Status::unimplemented(
-            "get_instance_health not yet implemented",
-        ))
+        let health = self
+            .distributor
+            .instance_health(&req.instance_id)
+            .ok_or_else(|| {
+                Status::not_found(format!("no health data for instance '{}'", req.instance_id))
+            })?;
+
+        Ok(Response::new(InstanceHealthResponse {
+            instance_id: health.instance_id,
+            health: health.state.into(),
+            last_check: Some(health.last_checked),
+            error_message: if health.state == HealthState::Healthy {
+                String::new()
+            } else {
+                health.message.unwrap_or_default()
+            },
+        }))
+    }
+
+    #[instrument(skip(self, request), fields(service = %request.get_ref().service_filter))]
+    async fn list_deployments(
+        &self,
+        request: Request<ListDeploymentsRequest>,
+    ) -> Result<Response<ListDeploymentsResponse>, Status> {
+        // Check rate limit
+        self.check_rate_limit("ListDeployments")?;
+
+        let req = request.into_inner();
+        debug!(
+            "ListDeployments request: service_filter={:?}, page_size={}",
+            req.service_filter, req.page_size
+        );
+
+        let filter = DeploymentFilter {
+            service: Some(req.service_filter).filter(|s| !s.is_empty()),
+            state: match req.state_filter {
+                None | Some(GrpcDeploymentState::Unknown) => None,
+                Some(GrpcDeploymentState::Pending) => Some(DeploymentState::Pending),
+                Some(GrpcDeploymentState::InProgress) => Some(DeploymentState::InProgress),
+                Some(GrpcDeploymentState::Completed) => Some(DeploymentState::Completed),
+                Some(GrpcDeploymentState::Failed) => Some(DeploymentState::Failed),
+                Some(GrpcDeploymentState::RolledBack) => Some(DeploymentState::RolledBack),
+                Some(GrpcDeploymentState::Cancelled) => Some(DeploymentState::Cancelled),
+                Some(GrpcDeploymentState::Paused) => Some(DeploymentState::Paused),
+                Some(GrpcDeploymentState::Interrupted) => Some(DeploymentState::Interrupted),
+            },
+            started_after: req.start_time,
+            started_before: req.end_time,
+        };
+        let page_size = usize::try_from(req.page_size)
+            .map_err(|_| Status::invalid_argument("page_size must not be negative"))?;
+        let page_token = Some(req.page_token.as_str()).filter(|t| !t.is_empty());
+
+        let page = self
+            .distributor
+            .list_deployments(&filter, page_size, page_token)
+            .await
+            .map_err(|e| match e {
+                DistributorError::InvalidOperation { ref reason } => {
+                    e.to_status_with(Code::InvalidArgument, reason.as_str())
+                }
+                e => e.to_status_with(Code::Internal, format!("Failed to list deployments: {e}")),
+            })?;
+
+        Ok(Response::new(ListDeploymentsResponse {
+            deployments: page
+                .deployments
+                .iter()
+                .map(DeploymentRecord::from)
+                .collect(),
+            next_page_token: page.next_page_token.unwrap_or_default(),
+        }))
+    }
+
+    #[instrument(skip(self, request), fields(deployment_id = %request.get_ref().deployment_id))]
+    async fn get_deployment(
+        &self,
+        request: Request<GetDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status> {
+        // Check rate limit
+        self.check_rate_limit("GetDeployment")?;
+
+        let req = request.into_inner();
+        debug!("GetDeployment request: deployment_id={}", req.deployment_id);
+
+        self.deployment_record(&req.deployment_id).await
+    }
+
+    #[instrument(skip(self, request), fields(deployment_id = %request.get_ref().deployment_id))]
+    async fn pause_deployment(
+        &self,
+        request: Request<PauseDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status> {
+        // Check rate limit
+        self.check_rate_limit("PauseDeployment")?;
+
+        let req = request.into_inner();
+        info!(
+            "PauseDeployment request: deployment_id={}",
+            req.deployment_id
+        );
+
+        self.distributor
+            .pause_deployment(&req.deployment_id)
+            .await
+            .map_err(|e| transition_status("pause", e))?;
+        self.deployment_record(&req.deployment_id).await
+    }
+
+    #[instrument(skip(self, request), fields(deployment_id = %request.get_ref().deployment_id))]
+    async fn resume_deployment(
+        &self,
+        request: Request<ResumeDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status> {
+        // Check rate limit
+        self.check_rate_limit("ResumeDeployment")?;
+
+        let req = request.into_inner();
+        info!(
+            "ResumeDeployment request: deployment_id={}",
+            req.deployment_id
+        );
+
+        self.distributor
+            .resume_deployment(&req.deployment_id)
+            .await
+            .map_err(|e| transition_status("resume", e))?;
+        self.deployment_record(&req.deployment_id).await
+    }
+
+    #[instrument(skip(self, request), fields(service = %request.get_ref().service))]
+    async fn schedule_deployment(
+        &self,
+        request: Request<ScheduleDeploymentRequest>,
+    ) -> Result<Response<ScheduledDeploymentRecord>, Status> {
+        // Check rate limit
+        self.check_rate_limit("ScheduleDeployment")?;
+
+        let req = request.into_inner();
+        info!(
+            "ScheduleDeployment request: service={}, version={}",
+            req.service, req.version
+        );
+
+        let mut constraints = ScheduleConstraints {
+            not_before: req.not_before,
+            freezes: req.freezes.into_iter().map(Into::into).collect(),
+            ..ScheduleConstraints::default()
+        };
+        for window in &req.windows {
+            let window = window
+                .parse()
+                .map_err(|e| DistributorError::from(e).to_status())?;
+            constraints.windows.push(window);
+        }
+        let strategy = strategy_from_grpc(req.strategy);
+
+        let deployment = self
+            .distributor
+            .schedule_deploy(&req.service, &req.version, strategy, constraints)
+            .await
+            .map_err(|e| {
+                warn!("Schedule deployment failed: {e}");
+                e.to_status()
+            })?;
+
+        Ok(Response::new(ScheduledDeploymentRecord::from(&deployment)))
+    }
+
+    #[instrument(skip(self, request), fields(service = %request.get_ref().service_filter))]
+    async fn list_scheduled_deployments(
+        &self,
+        request: Request<ListScheduledDeploymentsRequest>,
+    ) -> Result<Response<ListScheduledDeploymentsResponse>, Status> {
+        // Check rate limit
+        self.check_rate_limit("ListScheduledDeployments")?;
+
+        let req = request.into_inner();
+        debug!(
+            "ListScheduledDeployments request: service_filter={:?}",
+            req.service_filter
+        );
+
+        let deployments = self
+            .distributor
+            .list_scheduled()
+            .await
+            .iter()
+            .filter(|d| req.service_filter.is_empty() || d.service == req.service_filter)
+            .map(ScheduledDeploymentRecord::from)
+            .collect();
+
+        Ok(Response::new(ListScheduledDeploymentsResponse {
+            deployments,
+        }))
+    }
+
+    #[instrument(skip(self, request), fields(scheduled_id = %request.get_ref().scheduled_id))]
+    async fn cancel_scheduled_deployment(
+        &self,
+        request: Request<CancelScheduledDeploymentRequest>,
+    ) -> Result<Response<ScheduledDeploymentRecord>, Status> {
+        // Check rate limit
+        self.check_rate_limit("CancelScheduledDeployment")?;
+
+        let req = request.into_inner();
+        info!(
+            "CancelScheduledDeployment request: scheduled_id={}",
+            req.scheduled_id
+        );
+
+        let deployment = self
+            .distributor
+            .cancel_scheduled(&req.scheduled_id)
+            .await
+            .map_err(|e| e.to_status())?;
+
+        Ok(Response::new(ScheduledDeploymentRecord::from(&deployment)))
     }
 
     type WatchDeploymentStream =
@@ -451,6 +670,45 @@ impl ControlPlane for ControlPlaneService {
     }
 }
 
+/// Converts a gRPC strategy to an internal strategy.
+#[allow(clippy::cast_sign_loss)]
+fn strategy_from_grpc(strategy: Option<GrpcDeploymentStrategy>) -> DeploymentStrategy {
+    strategy.map_or_else(DeploymentStrategy::immediate, |s| match s.strategy_type {
+        GrpcStrategyType::Canary => DeploymentStrategy::canary(
+            s.canary_percentage.unsigned_abs(),
+            Duration::from_secs(s.batch_delay_seconds.unsigned_abs()),
+        )
+        .with_max_failures(s.max_failures.unsigned_abs())
+        .with_auto_rollback(s.auto_rollback),
+        GrpcStrategyType::Rolling => DeploymentStrategy::rolling(
+            s.rolling_batch_size.unsigned_abs() as usize,
+            Duration::from_secs(s.batch_delay_seconds.unsigned_abs()),
+        )
+        .with_max_failures(s.max_failures.unsigned_abs())
+        .with_auto_rollback(s.auto_rollback),
+        GrpcStrategyType::BlueGreen => DeploymentStrategy::blue_green(Duration::from_secs(
+            s.batch_delay_seconds.unsigned_abs(),
+        ))
+        .with_max_failures(s.max_failures.unsigned_abs())
+        .with_auto_rollback(s.auto_rollback),
+        _ => DeploymentStrategy::immediate(),
+    })
+}
+
+/// Maps an error from pausing or resuming a deployment to a gRPC status.
+fn transition_status(action: &str, error: DistributorError) -> Status {
+    match error {
+        DistributorError::DeploymentNotFound { .. } => error.to_status(),
+        DistributorError::StateError { ref reason } => {
+            error.to_status_with(Code::FailedPrecondition, reason.as_str())
+        }
+        e => e.to_status_with(
+            Code::Internal,
+            format!("Failed to {action} deployment: {e}"),
+        ),
+    }
+}
+
 /// Convert internal event to gRPC event.
 fn convert_to_grpc_event(event: &DeploymentEventData) -> DeploymentEvent {
     let event_type = match event.event_type {
@@ -511,6 +769,49 @@ pub trait ControlPlane: Send + Sync + 'static {
         request: Request<GetInstanceHealthRequest>,
     ) -> Result<Response<InstanceHealthResponse>, Status>;
 
+    /// List tracked deployments, newest first, one page at a time.
+    async fn list_deployments(
+        &self,
+        request: Request<ListDeploymentsRequest>,
+    ) -> Result<Response<ListDeploymentsResponse>, Status>;
+
+    /// Get a tracked deployment with its per-instance results.
+    async fn get_deployment(
+        &self,
+        request: Request<GetDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status>;
+
+    /// Pause an in-progress deployment before its next batch.
+    async fn pause_deployment(
+        &self,
+        request: Request<PauseDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status>;
+
+    /// Resume a paused deployment.
+    async fn resume_deployment(
+        &self,
+        request: Request<ResumeDeploymentRequest>,
+    ) -> Result<Response<DeploymentRecord>, Status>;
+
+    /// Schedule a deployment to start inside its time windows and outside
+    /// its freeze periods.
+    async fn schedule_deployment(
+        &self,
+        request: Request<ScheduleDeploymentRequest>,
+    ) -> Result<Response<ScheduledDeploymentRecord>, Status>;
+
+    /// List scheduled deployments that have not started yet.
+    async fn list_scheduled_deployments(
+        &self,
+        request: Request<ListScheduledDeploymentsRequest>,
+    ) -> Result<Response<ListScheduledDeploymentsResponse>, Status>;
+
+    /// Cancel a scheduled deployment that has not started yet.
+    async fn cancel_scheduled_deployment(
+        &self,
+        request: Request<CancelScheduledDeploymentRequest>,
+    ) -> Result<Response<ScheduledDeploymentRecord>, Status>;
+
     /// Stream type for deployment events.
     type WatchDeploymentStream: Stream<Item = Result<DeploymentEvent, Status>> + Send;
 
@@ -558,19 +859,57 @@ impl<T: ControlPlane + Clone>
         std::task::Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, _req: tonic::codegen::http::Request<tonic::body::BoxBody>) -> Self::Future {
-        // For now, return unimplemented
-        // The actual implementation would route to the appropriate method
-        Box::pin(async move {
-            let response = tonic::codegen::http::Response::builder()
-                .status(501)
-                .body(tonic::body::empty_body())
-                .unwrap();
-            Ok(response)
-        })
+    fn call(&mut self, req: tonic::codegen::http::Request<tonic::body::BoxBody>) -> Self::Future {
+        let inner = self.inner.clone();
+
+        // Routes a unary call to the `ControlPlane` method of the same name
+        macro_rules! unary {
+            ($method:ident) => {
+                Box::pin(async move {
+                    let method = tower::service_fn(move |request| {
+                        let inner = inner.clone();
+                        async move { inner.$method(request).await }
+                    });
+                    let mut grpc = tonic::server::Grpc::new(JsonCodec::default());
+                    Ok(grpc.unary(method, req).await)
+                })
+            };
+        }
+
+        match req.uri().path().strip_prefix(METHOD_PREFIX) {
+            Some("DeployPolicy") => unary!(deploy_policy),
+            Some("RollbackPolicy") => unary!(rollback_policy),
+            Some("GetPolicyStatus") => unary!(get_policy_status),
+            Some("ListInstances") => unary!(list_instances),
+            Some("GetInstanceHealth") => unary!(get_instance_health),
+            Some("ListDeployments") => unary!(list_deployments),
+            Some("GetDeployment") => unary!(get_deployment),
+            Some("PauseDeployment") => unary!(pause_deployment),
+            Some("ResumeDeployment") => unary!(resume_deployment),
+            Some("ScheduleDeployment") => unary!(schedule_deployment),
+            Some("ListScheduledDeployments") => unary!(list_scheduled_deployments),
+            Some("CancelScheduledDeployment") => unary!(cancel_scheduled_deployment),
+            Some("WatchDeployment") => Box::pin(async move {
+                let method = tower::service_fn(move |request| {
+                    let inner = inner.clone();
+                    async move { inner.watch_deployment(request).await }
+                });
+                let mut grpc = tonic::server::Grpc::new(JsonCodec::default());
+                Ok(grpc.server_streaming(method, req).await)
+            }),
+            _ => Box::pin(async move {
+                Ok(
+                    Status::unimplemented(format!("unknown method {}", req.uri().path()))
+                        .into_http(),
+                )
+            }),
+        }
     }
 }
 
+/// Path prefix of the `ControlPlane` methods, `/<service name>/`.
+pub const METHOD_PREFIX: &str = "/control_plane.ControlPlane/";
+
 impl<T: ControlPlane + Clone> tonic::server::NamedService for ControlPlaneServiceServer<T> {
     const NAME: &'static str = "control_plane.ControlPlane";
 }
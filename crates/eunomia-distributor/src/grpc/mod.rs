@@ -4,6 +4,10 @@
 //! - `ControlPlaneService`: Deploy, rollback, and monitor policies
 //! - `PolicyReceiverService`: Handle policy updates from the registry
 //!
+//! [`ControlPlaneClient`] calls the Control Plane service of a running
+//! server. Until protobuf code generation is enabled, its messages are
+//! framed as JSON rather than protobuf.
+//!
 //! # Rate Limiting
 //!
 //! The server supports configurable rate limiting per endpoint:
@@ -37,16 +41,21 @@
 //! }
 //! ```
 
+mod client;
+mod codec;
 mod control_plane;
 mod policy_receiver;
 pub mod rate_limit;
 mod server;
+mod tls;
 pub mod types;
 
+pub use client::ControlPlaneClient;
 pub use control_plane::{ControlPlane, ControlPlaneService, ControlPlaneServiceServer};
 pub use policy_receiver::{PolicyReceiver, PolicyReceiverService, PolicyReceiverServiceServer};
 pub use rate_limit::{
     create_rate_limiter, EndpointRateLimits, EndpointStats, RateLimitConfig, RateLimitStats,
     RateLimiterRegistry, SharedRateLimiter, TokenBucket,
 };
-pub use server::{GrpcServer, GrpcServerConfig, GrpcServerError, GrpcServerHandle, TlsConfig};
+pub use server::{GrpcServer, GrpcServerConfig, GrpcServerError, GrpcServerHandle};
+pub use tls::{ClientAuth, TlsConfig, TlsFiles};
@@ -0,0 +1,467 @@
+//! TLS termination for the gRPC server.
+//!
+//! The server terminates TLS itself rather than through tonic, so that the
+//! certificates can be swapped while it runs and rejected handshakes can be
+//! logged with the peer address. Certificates loaded from files are reloaded
+//! on `SIGHUP` and whenever the files change; a reload that fails keeps the
+//! certificates in use.
+
+use std::collections::BTreeSet;
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::Stream;
+use notify::{RecursiveMode, Watcher};
+use parking_lot::{Mutex, RwLock};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio_rustls::rustls::crypto::ring;
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{self, RootCertStore, ServerConfig};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, info, warn};
+
+use super::server::GrpcServerError;
+
+/// Time allowed for a client to complete the TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay between a change to the certificate files and the reload, so that
+/// a rotation that writes several files is picked up at once.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Whether clients must present a certificate when mTLS is enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClientAuth {
+    /// Connections without a valid client certificate are rejected.
+    #[default]
+    Required,
+    /// Clients may connect without a certificate; a certificate that is
+    /// presented must still be valid.
+    Optional,
+}
+
+/// Files the PEM contents of a [`TlsConfig`] were read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsFiles {
+    /// Server certificate file.
+    pub cert_path: PathBuf,
+    /// Server private key file.
+    pub key_path: PathBuf,
+    /// CA certificate file for client verification.
+    pub ca_cert_path: Option<PathBuf>,
+}
+
+/// TLS configuration for the gRPC server.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    /// Server certificate in PEM format.
+    pub cert_pem: String,
+    /// Server private key in PEM format.
+    pub key_pem: String,
+    /// Optional CA certificate for client verification (enables mTLS).
+    pub ca_cert_pem: Option<String>,
+    /// Whether clients must present a certificate under mTLS.
+    pub client_auth: ClientAuth,
+    /// Files to reload the certificates from, if loaded from files.
+    pub files: Option<TlsFiles>,
+}
+
+impl TlsConfig {
+    /// Create a TLS configuration from a PEM certificate and private key.
+    pub fn new(cert_pem: impl Into<String>, key_pem: impl Into<String>) -> Self {
+        Self {
+            cert_pem: cert_pem.into(),
+            key_pem: key_pem.into(),
+            ca_cert_pem: None,
+            client_auth: ClientAuth::default(),
+            files: None,
+        }
+    }
+
+    /// Load the certificate and private key from PEM files.
+    ///
+    /// The server reloads certificates loaded from files on `SIGHUP` and
+    /// whenever the files change.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either file cannot be read.
+    pub fn from_files(
+        cert_path: impl Into<PathBuf>,
+        key_path: impl Into<PathBuf>,
+    ) -> Result<Self, GrpcServerError> {
+        let files = TlsFiles {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            ca_cert_path: None,
+        };
+        let mut tls = Self::new(read_pem(&files.cert_path)?, read_pem(&files.key_path)?);
+        tls.files = Some(files);
+        Ok(tls)
+    }
+
+    /// Verify client certificates against a CA (enables mTLS).
+    pub fn with_client_ca(mut self, ca_cert_pem: impl Into<String>) -> Self {
+        self.ca_cert_pem = Some(ca_cert_pem.into());
+        self
+    }
+
+    /// Verify client certificates against a CA read from a PEM file
+    /// (enables mTLS).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn with_client_ca_file(
+        mut self,
+        ca_cert_path: impl Into<PathBuf>,
+    ) -> Result<Self, GrpcServerError> {
+        let ca_cert_path = ca_cert_path.into();
+        self.ca_cert_pem = Some(read_pem(&ca_cert_path)?);
+        if let Some(files) = &mut self.files {
+            files.ca_cert_path = Some(ca_cert_path);
+        }
+        Ok(self)
+    }
+
+    /// Set whether clients must present a certificate under mTLS.
+    pub const fn with_client_auth(mut self, client_auth: ClientAuth) -> Self {
+        self.client_auth = client_auth;
+        self
+    }
+
+    /// Returns the configuration with the certificates re-read from their
+    /// files, or a copy if they were not loaded from files.
+    fn reloaded(&self) -> Result<Self, GrpcServerError> {
+        let mut tls = self.clone();
+        if let Some(files) = &self.files {
+            tls.cert_pem = read_pem(&files.cert_path)?;
+            tls.key_pem = read_pem(&files.key_path)?;
+            if let Some(ca_cert_path) = &files.ca_cert_path {
+                tls.ca_cert_pem = Some(read_pem(ca_cert_path)?);
+            }
+        }
+        Ok(tls)
+    }
+}
+
+fn read_pem(path: &Path) -> Result<String, GrpcServerError> {
+    std::fs::read_to_string(path)
+        .map_err(|e| GrpcServerError::TlsConfig(format!("failed to read {}: {e}", path.display())))
+}
+
+fn parse_certs(pem: &str, what: &str) -> Result<Vec<CertificateDer<'static>>, GrpcServerError> {
+    let certs = rustls_pemfile::certs(&mut pem.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| GrpcServerError::TlsConfig(format!("invalid {what}: {e}")))?;
+    if certs.is_empty() {
+        return Err(GrpcServerError::TlsConfig(format!(
+            "no certificate found in {what}"
+        )));
+    }
+    Ok(certs)
+}
+
+/// Builds the rustls configuration of a [`TlsConfig`].
+fn server_config(tls: &TlsConfig) -> Result<Arc<ServerConfig>, GrpcServerError> {
+    let provider = Arc::new(ring::default_provider());
+    let certs = parse_certs(&tls.cert_pem, "server certificate")?;
+    let key = rustls_pemfile::private_key(&mut tls.key_pem.as_bytes())
+        .map_err(|e| GrpcServerError::TlsConfig(format!("invalid server key: {e}")))?
+        .ok_or_else(|| GrpcServerError::TlsConfig("no private key found".to_string()))?;
+
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| GrpcServerError::TlsConfig(e.to_string()))?;
+    let builder = if let Some(ca_cert_pem) = &tls.ca_cert_pem {
+        let mut roots = RootCertStore::empty();
+        for cert in parse_certs(ca_cert_pem, "client CA certificate")? {
+            roots
+                .add(cert)
+                .map_err(|e| GrpcServerError::TlsConfig(format!("invalid client CA: {e}")))?;
+        }
+        let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+        let verifier = match tls.client_auth {
+            ClientAuth::Required => verifier,
+            ClientAuth::Optional => verifier.allow_unauthenticated(),
+        };
+        let verifier = verifier
+            .build()
+            .map_err(|e| GrpcServerError::TlsConfig(e.to_string()))?;
+        builder.with_client_cert_verifier(verifier)
+    } else {
+        builder.with_no_client_auth()
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .map_err(|e| GrpcServerError::TlsConfig(e.to_string()))?;
+    config.alpn_protocols = vec![b"h2".to_vec()];
+    Ok(Arc::new(config))
+}
+
+/// TLS settings that can be replaced while the server runs.
+#[derive(Debug)]
+pub struct ReloadableTls {
+    tls: Mutex<TlsConfig>,
+    server_config: RwLock<Arc<ServerConfig>>,
+}
+
+impl ReloadableTls {
+    pub fn new(tls: TlsConfig) -> Result<Self, GrpcServerError> {
+        let server_config = server_config(&tls)?;
+        Ok(Self {
+            tls: Mutex::new(tls),
+            server_config: RwLock::new(server_config),
+        })
+    }
+
+    fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(Arc::clone(&self.server_config.read()))
+    }
+
+    /// Re-reads the certificate files and uses them for new connections.
+    ///
+    /// Connections already established keep their certificates.
+    pub fn reload(&self) -> Result<(), GrpcServerError> {
+        let mut tls = self.tls.lock();
+        let reloaded = tls.reloaded()?;
+        *self.server_config.write() = server_config(&reloaded)?;
+        *tls = reloaded;
+        info!("Reloaded TLS certificates");
+        Ok(())
+    }
+
+    fn reload_logged(&self, trigger: &str) {
+        if let Err(e) = self.reload() {
+            warn!(
+                %trigger,
+                error = %e,
+                "Failed to reload TLS certificates, keeping the current ones"
+            );
+        }
+    }
+
+    /// Starts the tasks that reload the certificates on `SIGHUP` and when
+    /// their files change.
+    pub fn spawn_reload_tasks(self: &Arc<Self>) -> Vec<JoinHandle<()>> {
+        let Some(files) = self.tls.lock().files.clone() else {
+            return Vec::new();
+        };
+        let mut tasks = Vec::new();
+
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            match signal(SignalKind::hangup()) {
+                Ok(mut hangup) => {
+                    let tls = Arc::clone(self);
+                    tasks.push(tokio::spawn(async move {
+                        while hangup.recv().await.is_some() {
+                            tls.reload_logged("SIGHUP");
+                        }
+                    }));
+                }
+                Err(e) => warn!(error = %e, "Failed to listen for SIGHUP"),
+            }
+        }
+
+        match self.watch(&files) {
+            Ok(task) => tasks.push(task),
+            Err(e) => warn!(error = %e, "Failed to watch TLS certificate files"),
+        }
+        tasks
+    }
+
+    /// Watches the directories of the certificate files.
+    ///
+    /// Directories are watched rather than the files, so that files replaced
+    /// by a rename (as Kubernetes does for mounted secrets) are still seen.
+    fn watch(self: &Arc<Self>, files: &TlsFiles) -> notify::Result<JoinHandle<()>> {
+        let paths: Vec<&PathBuf> = std::iter::once(&files.cert_path)
+            .chain(std::iter::once(&files.key_path))
+            .chain(files.ca_cert_path.as_ref())
+            .collect();
+        let names: BTreeSet<_> = paths.iter().filter_map(|path| path.file_name()).collect();
+        let names: Vec<_> = names.into_iter().map(ToOwned::to_owned).collect();
+        let dirs: BTreeSet<_> = paths
+            .iter()
+            .map(|path| match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+                _ => PathBuf::from("."),
+            })
+            .collect();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else { return };
+                let relevant = event.paths.iter().any(|path| {
+                    path.file_name().is_some_and(|name| {
+                        names.iter().any(|n| n == name) || name.to_string_lossy().starts_with("..")
+                    })
+                });
+                if relevant && !event.kind.is_access() {
+                    let _ = tx.send(());
+                }
+            })?;
+        for dir in &dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        let tls = Arc::clone(self);
+        Ok(tokio::spawn(async move {
+            // The watcher stops when dropped
+            let _watcher = watcher;
+            while rx.recv().await.is_some() {
+                tokio::time::sleep(RELOAD_DEBOUNCE).await;
+                while rx.try_recv().is_ok() {}
+                tls.reload_logged("file change");
+            }
+        }))
+    }
+}
+
+/// TCP settings applied to accepted connections.
+#[derive(Debug, Clone, Copy)]
+pub struct TcpSettings {
+    pub nodelay: bool,
+    pub keepalive: Option<Duration>,
+}
+
+impl TcpSettings {
+    fn apply(self, stream: &TcpStream) -> io::Result<()> {
+        stream.set_nodelay(self.nodelay)?;
+        if let Some(keepalive) = self.keepalive {
+            let keepalive = socket2::TcpKeepalive::new().with_time(keepalive);
+            socket2::SockRef::from(stream).set_tcp_keepalive(&keepalive)?;
+        }
+        Ok(())
+    }
+}
+
+/// Accepts connections and completes their TLS handshakes.
+///
+/// Handshakes run concurrently, so a slow client cannot hold up others. Once
+/// the returned stream is dropped, the returned task closes the listener and
+/// ends.
+pub fn incoming(
+    listener: TcpListener,
+    tls: Arc<ReloadableTls>,
+    tcp: TcpSettings,
+) -> (
+    impl Stream<Item = io::Result<TlsStream<TcpStream>>>,
+    JoinHandle<()>,
+) {
+    let (tx, mut rx) = mpsc::channel(64);
+    let accept_task = tokio::spawn(accept_loop(listener, tls, tcp, tx));
+    let incoming = futures::stream::poll_fn(move |cx| rx.poll_recv(cx));
+    (incoming, accept_task)
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    tls: Arc<ReloadableTls>,
+    tcp: TcpSettings,
+    tx: mpsc::Sender<io::Result<TlsStream<TcpStream>>>,
+) {
+    loop {
+        let (stream, peer) = tokio::select! {
+            () = tx.closed() => break,
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!(error = %e, "Failed to accept connection");
+                    // Back off on errors such as running out of file descriptors
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            },
+        };
+        if let Err(e) = tcp.apply(&stream) {
+            debug!(%peer, error = %e, "Failed to apply TCP settings");
+        }
+
+        let acceptor = tls.acceptor();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => {
+                    let _ = tx.send(Ok(stream)).await;
+                }
+                Ok(Err(e)) => log_handshake_error(peer, &e),
+                Err(_) => debug!(%peer, "TLS handshake timed out"),
+            }
+        });
+    }
+    debug!("Stopped accepting TLS connections");
+}
+
+fn log_handshake_error(peer: SocketAddr, error: &io::Error) {
+    let rejected_cert = error
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<rustls::Error>())
+        .is_some_and(|e| {
+            matches!(
+                e,
+                rustls::Error::InvalidCertificate(_) | rustls::Error::NoCertificatesPresented
+            )
+        });
+    if rejected_cert {
+        warn!(%peer, error = %error, "Rejected client certificate");
+    } else {
+        debug!(%peer, error = %error, "TLS handshake failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tls_config_builder() {
+        let tls = TlsConfig::new("cert", "key")
+            .with_client_ca("ca")
+            .with_client_auth(ClientAuth::Optional);
+
+        assert_eq!(tls.cert_pem, "cert");
+        assert_eq!(tls.ca_cert_pem.as_deref(), Some("ca"));
+        assert_eq!(tls.client_auth, ClientAuth::Optional);
+        assert!(tls.files.is_none());
+    }
+
+    #[test]
+    fn test_tls_config_from_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("tls.crt"), "cert").unwrap();
+        std::fs::write(dir.path().join("tls.key"), "key").unwrap();
+        std::fs::write(dir.path().join("ca.crt"), "ca").unwrap();
+
+        let tls = TlsConfig::from_files(dir.path().join("tls.crt"), dir.path().join("tls.key"))
+            .unwrap()
+            .with_client_ca_file(dir.path().join("ca.crt"))
+            .unwrap();
+        assert_eq!(tls.key_pem, "key");
+        assert_eq!(tls.ca_cert_pem.as_deref(), Some("ca"));
+
+        std::fs::write(dir.path().join("tls.crt"), "rotated").unwrap();
+        assert_eq!(tls.reloaded().unwrap().cert_pem, "rotated");
+
+        let err = TlsConfig::from_files(dir.path().join("missing.crt"), "tls.key").unwrap_err();
+        assert!(err.to_string().contains("missing.crt"));
+    }
+
+    #[test]
+    fn test_invalid_pem_rejected() {
+        let err = ReloadableTls::new(TlsConfig::new("not a certificate", "not a key")).unwrap_err();
+        assert!(matches!(err, GrpcServerError::TlsConfig(_)));
+    }
+}
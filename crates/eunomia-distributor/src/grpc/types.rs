@@ -18,6 +18,8 @@ pub enum GrpcStrategyType {
     Canary = 2,
     /// Deploy in batches with delays between.
     Rolling = 3,
+    /// Deploy to the standby group, then swap it with the live group.
+    BlueGreen = 4,
 }
 
 impl From<i32> for GrpcStrategyType {
@@ -26,6 +28,7 @@ impl From<i32> for GrpcStrategyType {
             1 => Self::Immediate,
             2 => Self::Canary,
             3 => Self::Rolling,
+            4 => Self::BlueGreen,
             _ => Self::Unknown,
         }
     }
@@ -37,6 +40,7 @@ impl From<crate::StrategyType> for GrpcStrategyType {
             crate::StrategyType::Immediate => Self::Immediate,
             crate::StrategyType::Canary => Self::Canary,
             crate::StrategyType::Rolling => Self::Rolling,
+            crate::StrategyType::BlueGreen => Self::BlueGreen,
         }
     }
 }
@@ -59,6 +63,10 @@ pub enum GrpcDeploymentState {
     RolledBack = 5,
     /// Deployment was cancelled.
     Cancelled = 6,
+    /// Deployment is paused between batches.
+    Paused = 7,
+    /// Deployment was interrupted by a distributor restart.
+    Interrupted = 8,
 }
 
 impl From<i32> for GrpcDeploymentState {
@@ -70,6 +78,8 @@ impl From<i32> for GrpcDeploymentState {
             4 => Self::Failed,
             5 => Self::RolledBack,
             6 => Self::Cancelled,
+            7 => Self::Paused,
+            8 => Self::Interrupted,
             _ => Self::Unknown,
         }
     }
@@ -84,6 +94,8 @@ impl From<crate::DeploymentState> for GrpcDeploymentState {
             crate::DeploymentState::Failed => Self::Failed,
             crate::DeploymentState::RolledBack => Self::RolledBack,
             crate::DeploymentState::Cancelled => Self::Cancelled,
+            crate::DeploymentState::Paused => Self::Paused,
+            crate::DeploymentState::Interrupted => Self::Interrupted,
         }
     }
 }
@@ -193,6 +205,8 @@ pub struct InstanceDeploymentResult {
     pub instance_id: String,
     /// Whether the deployment succeeded.
     pub success: bool,
+    /// Whether the instance was skipped because it already ran the version.
+    pub skipped: bool,
     /// Error message (if failed).
     pub error_message: String,
     /// Previous policy version.
@@ -260,6 +274,9 @@ pub struct InstancePolicyStatus {
     pub health: GrpcHealthState,
     /// Last update time.
     pub last_updated: Option<DateTime<Utc>>,
+    /// When the instance last answered a health probe.
+    #[serde(default)]
+    pub last_seen: Option<DateTime<Utc>>,
 }
 
 /// List instances request.
@@ -337,6 +354,236 @@ pub struct DeploymentEvent {
     pub message: String,
 }
 
+/// List deployments request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListDeploymentsRequest {
+    /// Filter by service (optional).
+    pub service_filter: String,
+    /// Filter by deployment state (optional).
+    pub state_filter: Option<GrpcDeploymentState>,
+    /// Only deployments started at or after this time (optional).
+    pub start_time: Option<DateTime<Utc>>,
+    /// Only deployments started before this time (optional).
+    pub end_time: Option<DateTime<Utc>>,
+    /// Maximum deployments to return (0 = server default).
+    pub page_size: i32,
+    /// Token from a previous response (empty = first page).
+    pub page_token: String,
+}
+
+/// List deployments response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDeploymentsResponse {
+    /// Deployments, newest first.
+    pub deployments: Vec<DeploymentRecord>,
+    /// Token for the next page (empty = last page).
+    pub next_page_token: String,
+}
+
+/// Get deployment request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetDeploymentRequest {
+    /// Deployment ID.
+    pub deployment_id: String,
+}
+
+/// Pause deployment request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PauseDeploymentRequest {
+    /// Deployment ID.
+    pub deployment_id: String,
+}
+
+/// Resume deployment request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumeDeploymentRequest {
+    /// Deployment ID.
+    pub deployment_id: String,
+}
+
+/// A tracked deployment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    /// Deployment ID.
+    pub deployment_id: String,
+    /// Service name.
+    pub service: String,
+    /// Deployed version.
+    pub version: String,
+    /// Deployment strategy.
+    pub strategy: GrpcStrategyType,
+    /// Deployment state.
+    pub state: GrpcDeploymentState,
+    /// Summary of results.
+    pub summary: Option<DeploymentSummary>,
+    /// Start time.
+    pub started_at: Option<DateTime<Utc>>,
+    /// End time (if finished).
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Error message (if the deployment failed as a whole).
+    pub error_message: String,
+    /// Per-instance results.
+    pub instance_results: Vec<InstanceDeploymentResult>,
+}
+
+impl From<&crate::DeploymentInfo> for DeploymentRecord {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn from(info: &crate::DeploymentInfo) -> Self {
+        let millis = |end: std::time::Instant| {
+            end.saturating_duration_since(info.started_at)
+                .as_millis()
+                .min(i64::MAX as u128) as i64
+        };
+
+        let mut instance_results: Vec<_> = info
+            .instance_status
+            .values()
+            .map(|status| InstanceDeploymentResult {
+                instance_id: status.instance_id.clone(),
+                success: status.success,
+                skipped: status.skipped,
+                error_message: status.error.clone().unwrap_or_default(),
+                previous_version: String::new(),
+                duration_ms: millis(status.updated_at),
+            })
+            .collect();
+        instance_results.sort_by(|a, b| a.instance_id.cmp(&b.instance_id));
+
+        Self {
+            deployment_id: info.id.clone(),
+            service: info.service.clone(),
+            version: info.version.clone(),
+            strategy: info.strategy.into(),
+            state: info.state.clone().into(),
+            summary: Some(DeploymentSummary {
+                total_instances: info.total_instances as i32,
+                successful: info.successful as i32,
+                failed: info.failed as i32,
+                skipped: info.skipped as i32,
+                duration_ms: millis(info.ended_at.unwrap_or_else(std::time::Instant::now)),
+            }),
+            started_at: Some(info.created_at),
+            completed_at: info.finished_at,
+            error_message: info.error.clone().unwrap_or_default(),
+            instance_results,
+        }
+    }
+}
+
+/// Schedule deployment request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleDeploymentRequest {
+    /// Target service name.
+    pub service: String,
+    /// Version to deploy.
+    pub version: String,
+    /// Deployment strategy.
+    pub strategy: Option<GrpcDeploymentStrategy>,
+    /// Earliest time the deployment may start (optional).
+    pub not_before: Option<DateTime<Utc>>,
+    /// Weekly UTC windows the deployment may start in, such as
+    /// `Mon-Fri 09:00-16:00` (empty = any time).
+    pub windows: Vec<String>,
+    /// Periods the deployment must not start in.
+    pub freezes: Vec<GrpcFreezePeriod>,
+}
+
+/// A period in which no deployment may start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GrpcFreezePeriod {
+    /// When the freeze begins.
+    pub start: DateTime<Utc>,
+    /// When the freeze ends.
+    pub end: DateTime<Utc>,
+    /// Why deployments are frozen.
+    pub reason: String,
+}
+
+impl From<&crate::FreezePeriod> for GrpcFreezePeriod {
+    fn from(freeze: &crate::FreezePeriod) -> Self {
+        Self {
+            start: freeze.start,
+            end: freeze.end,
+            reason: freeze.reason.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<GrpcFreezePeriod> for crate::FreezePeriod {
+    fn from(freeze: GrpcFreezePeriod) -> Self {
+        Self {
+            start: freeze.start,
+            end: freeze.end,
+            reason: Some(freeze.reason).filter(|r| !r.is_empty()),
+        }
+    }
+}
+
+/// List scheduled deployments request.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListScheduledDeploymentsRequest {
+    /// Filter by service (optional).
+    pub service_filter: String,
+}
+
+/// List scheduled deployments response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListScheduledDeploymentsResponse {
+    /// Scheduled deployments, in launch order.
+    pub deployments: Vec<ScheduledDeploymentRecord>,
+}
+
+/// Cancel scheduled deployment request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelScheduledDeploymentRequest {
+    /// Scheduled deployment ID.
+    pub scheduled_id: String,
+}
+
+/// A deployment waiting for its schedule to allow it to start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledDeploymentRecord {
+    /// Scheduled deployment ID.
+    pub scheduled_id: String,
+    /// Service name.
+    pub service: String,
+    /// Version to deploy.
+    pub version: String,
+    /// Deployment strategy.
+    pub strategy: GrpcStrategyType,
+    /// Earliest time the deployment may start.
+    pub not_before: Option<DateTime<Utc>>,
+    /// Weekly UTC windows the deployment may start in.
+    pub windows: Vec<String>,
+    /// Periods the deployment must not start in.
+    pub freezes: Vec<GrpcFreezePeriod>,
+    /// When the deployment was scheduled.
+    pub scheduled_at: Option<DateTime<Utc>>,
+    /// When the deployment will next be allowed to start.
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl From<&crate::ScheduledDeployment> for ScheduledDeploymentRecord {
+    fn from(deployment: &crate::ScheduledDeployment) -> Self {
+        let constraints = &deployment.constraints;
+        Self {
+            scheduled_id: deployment.id.clone(),
+            service: deployment.service.clone(),
+            version: deployment.version.clone(),
+            strategy: deployment.strategy.strategy_type().into(),
+            not_before: constraints.not_before,
+            windows: constraints
+                .windows
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            freezes: constraints.freezes.iter().map(Into::into).collect(),
+            scheduled_at: Some(deployment.scheduled_at),
+            next_run_at: deployment.next_run_at(Utc::now()),
+        }
+    }
+}
+
 /// Deployment event types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(i32)]
@@ -453,6 +700,7 @@ mod tests {
         assert_eq!(GrpcStrategyType::from(1), GrpcStrategyType::Immediate);
         assert_eq!(GrpcStrategyType::from(2), GrpcStrategyType::Canary);
         assert_eq!(GrpcStrategyType::from(3), GrpcStrategyType::Rolling);
+        assert_eq!(GrpcStrategyType::from(4), GrpcStrategyType::BlueGreen);
         assert_eq!(GrpcStrategyType::from(99), GrpcStrategyType::Unknown);
     }
 
@@ -461,6 +709,11 @@ mod tests {
         assert_eq!(GrpcDeploymentState::from(1), GrpcDeploymentState::Pending);
         assert_eq!(GrpcDeploymentState::from(3), GrpcDeploymentState::Completed);
         assert_eq!(GrpcDeploymentState::from(4), GrpcDeploymentState::Failed);
+        assert_eq!(GrpcDeploymentState::from(7), GrpcDeploymentState::Paused);
+        assert_eq!(
+            GrpcDeploymentState::from(8),
+            GrpcDeploymentState::Interrupted
+        );
         assert_eq!(GrpcDeploymentState::from(99), GrpcDeploymentState::Unknown);
     }
 
@@ -487,4 +740,72 @@ mod tests {
         assert_eq!(parsed.service, "users");
         assert_eq!(parsed.version, "1.0.0");
     }
+
+    #[test]
+    fn test_scheduled_deployment_record() {
+        let start = Utc::now();
+        let constraints = crate::ScheduleConstraints::new()
+            .with_window("Sat,Sun 00:00-00:00".parse().unwrap())
+            .with_freeze(
+                GrpcFreezePeriod {
+                    start,
+                    end: start + chrono::Duration::days(30),
+                    reason: String::new(),
+                }
+                .into(),
+            );
+        let deployment = crate::ScheduledDeployment::new(
+            "sched-1",
+            "users",
+            "1.0.0",
+            crate::DeploymentStrategy::rolling(1, std::time::Duration::ZERO),
+        )
+        .with_constraints(constraints);
+
+        let record = ScheduledDeploymentRecord::from(&deployment);
+        assert_eq!(record.strategy, GrpcStrategyType::Rolling);
+        assert_eq!(record.windows, ["Sat,Sun 00:00-00:00"]);
+        assert_eq!(record.freezes[0].reason, "");
+        assert!(deployment.constraints.freezes[0].reason.is_none());
+        assert!(record.next_run_at.unwrap() >= start + chrono::Duration::days(30));
+    }
+
+    #[tokio::test]
+    async fn test_deployment_record_from_info() {
+        let tracker = crate::DeploymentTracker::new();
+        tracker
+            .start_deployment("deploy-1", "users", "1.0.0", crate::StrategyType::Canary, 2)
+            .await
+            .unwrap();
+        let result = crate::DeploymentResult::from_results(
+            "deploy-1",
+            vec![
+                crate::InstanceResult {
+                    instance_id: "inst-2".to_string(),
+                    status: crate::InstanceResultStatus::Failed("rejected".to_string()),
+                },
+                crate::InstanceResult {
+                    instance_id: "inst-1".to_string(),
+                    status: crate::InstanceResultStatus::Skipped,
+                },
+            ],
+        );
+        tracker
+            .complete_deployment("deploy-1", result)
+            .await
+            .unwrap();
+
+        let info = tracker.get_deployment("deploy-1").await.unwrap();
+        let record = DeploymentRecord::from(&info);
+
+        assert_eq!(record.strategy, GrpcStrategyType::Canary);
+        assert_eq!(record.state, GrpcDeploymentState::Failed);
+        assert_eq!(record.started_at, Some(info.created_at));
+        assert!(record.completed_at.is_some());
+        let summary = record.summary.unwrap();
+        assert_eq!((summary.failed, summary.skipped), (1, 1));
+        assert_eq!(record.instance_results[0].instance_id, "inst-1");
+        assert!(record.instance_results[0].skipped);
+        assert_eq!(record.instance_results[1].error_message, "rejected");
+    }
 }
@@ -1,10 +1,16 @@
 //! Health checking for Archimedes instances.
 //!
 //! This module provides health monitoring capabilities for tracking
-//! the state of Archimedes instances.
+//! the state of Archimedes instances, and the circuit breaker that stops
+//! pushing to instances that keep failing.
 
 use std::time::{Duration, Instant};
 
+use dashmap::DashMap;
+
+use crate::error::{DistributorError, Result};
+use crate::instance::InstanceId;
+
 /// Health state of an instance.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HealthState {
@@ -299,6 +305,216 @@ impl HealthTracker {
     }
 }
 
+/// Configuration for the per-instance push circuit breaker.
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Number of consecutive push failures that open the circuit.
+    pub failure_threshold: u32,
+
+    /// Number of successful probe pushes that close a half-open circuit.
+    pub success_threshold: u32,
+
+    /// How long an open circuit rejects pushes before allowing a probe.
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            success_threshold: 1,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Circuit breaker state of an instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Pushes are allowed.
+    Closed {
+        /// Consecutive failed pushes so far.
+        consecutive_failures: u32,
+    },
+
+    /// Pushes are rejected without contacting the instance.
+    Open {
+        /// When the circuit opened.
+        since: Instant,
+    },
+
+    /// One probe push at a time is allowed.
+    HalfOpen {
+        /// Successful probes so far.
+        successes: u32,
+
+        /// When the probe in flight started, if any.
+        probe_started: Option<Instant>,
+    },
+}
+
+impl CircuitState {
+    /// Returns a string representation for display.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Closed { .. } => "closed",
+            Self::Open { .. } => "open",
+            Self::HalfOpen { .. } => "half-open",
+        }
+    }
+}
+
+impl Default for CircuitState {
+    fn default() -> Self {
+        Self::Closed {
+            consecutive_failures: 0,
+        }
+    }
+}
+
+impl std::fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Per-instance circuit breaker for policy pushes.
+///
+/// After [`CircuitBreakerConfig::failure_threshold`] consecutive failures an
+/// instance's circuit opens and pushes to it are rejected for
+/// [`CircuitBreakerConfig::open_duration`]. The circuit then turns half-open
+/// and lets one probe push through at a time; a failed probe opens it again,
+/// and [`CircuitBreakerConfig::success_threshold`] successful probes close
+/// it.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    states: DashMap<InstanceId, CircuitState>,
+}
+
+impl CircuitBreaker {
+    /// Creates a circuit breaker with all circuits closed.
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            states: DashMap::new(),
+        }
+    }
+
+    /// Checks whether a push to the instance may proceed.
+    ///
+    /// Moves an open circuit whose open duration has elapsed to half-open
+    /// and claims its probe. A probe that never reports back is replaced
+    /// after another open duration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DistributorError::CircuitOpen`] if the circuit is open or
+    /// its probe is already in flight.
+    pub fn allow(&self, instance_id: &str) -> Result<()> {
+        let mut state = self.states.entry(instance_id.to_string()).or_default();
+        let now = Instant::now();
+
+        let allowed = match *state {
+            CircuitState::Closed { .. } => true,
+            CircuitState::Open { since } => {
+                let elapsed = now.duration_since(since) >= self.config.open_duration;
+                if elapsed {
+                    *state = CircuitState::HalfOpen {
+                        successes: 0,
+                        probe_started: Some(now),
+                    };
+                }
+                elapsed
+            }
+            CircuitState::HalfOpen {
+                successes,
+                probe_started,
+            } => {
+                let free = probe_started
+                    .is_none_or(|at| now.duration_since(at) >= self.config.open_duration);
+                if free {
+                    *state = CircuitState::HalfOpen {
+                        successes,
+                        probe_started: Some(now),
+                    };
+                }
+                free
+            }
+        };
+        drop(state);
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(DistributorError::CircuitOpen {
+                instance_id: instance_id.to_string(),
+            })
+        }
+    }
+
+    /// Records a successful push to the instance.
+    pub fn record_success(&self, instance_id: &str) {
+        let mut state = self.states.entry(instance_id.to_string()).or_default();
+        *state = match *state {
+            CircuitState::HalfOpen { successes, .. }
+                if successes + 1 < self.config.success_threshold =>
+            {
+                CircuitState::HalfOpen {
+                    successes: successes + 1,
+                    probe_started: None,
+                }
+            }
+            CircuitState::Open { since } => CircuitState::Open { since },
+            CircuitState::Closed { .. } | CircuitState::HalfOpen { .. } => CircuitState::default(),
+        };
+    }
+
+    /// Records a failed push to the instance.
+    pub fn record_failure(&self, instance_id: &str) {
+        let mut state = self.states.entry(instance_id.to_string()).or_default();
+        let opened = match *state {
+            CircuitState::Closed {
+                consecutive_failures,
+            } if consecutive_failures + 1 < self.config.failure_threshold => {
+                *state = CircuitState::Closed {
+                    consecutive_failures: consecutive_failures + 1,
+                };
+                false
+            }
+            CircuitState::Open { .. } => false,
+            CircuitState::Closed { .. } | CircuitState::HalfOpen { .. } => {
+                *state = CircuitState::Open {
+                    since: Instant::now(),
+                };
+                true
+            }
+        };
+        drop(state);
+
+        if opened {
+            tracing::warn!(
+                instance_id = %instance_id,
+                open_secs = self.config.open_duration.as_secs(),
+                "push circuit opened"
+            );
+        }
+    }
+
+    /// Returns the circuit state of the instance.
+    pub fn state(&self, instance_id: &str) -> CircuitState {
+        self.states
+            .get(instance_id)
+            .map(|state| *state)
+            .unwrap_or_default()
+    }
+
+    /// Closes the instance's circuit, forgetting its failures.
+    pub fn reset(&self, instance_id: &str) {
+        self.states.remove(instance_id);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -445,4 +661,108 @@ mod tests {
         // No checks yet - check is due
         assert!(tracker.is_check_due());
     }
+
+    fn breaker(open_duration: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            success_threshold: 2,
+            open_duration,
+        })
+    }
+
+    #[test]
+    fn test_circuit_opens_after_consecutive_failures() {
+        let breaker = breaker(Duration::from_secs(30));
+        assert_eq!(breaker.state("inst-1"), CircuitState::default());
+
+        breaker.record_failure("inst-1");
+        breaker.record_failure("inst-1");
+        assert_eq!(
+            breaker.state("inst-1"),
+            CircuitState::Closed {
+                consecutive_failures: 2
+            }
+        );
+        assert!(breaker.allow("inst-1").is_ok());
+
+        breaker.record_failure("inst-1");
+        assert_eq!(breaker.state("inst-1").as_str(), "open");
+        assert!(matches!(
+            breaker.allow("inst-1"),
+            Err(DistributorError::CircuitOpen { instance_id }) if instance_id == "inst-1"
+        ));
+
+        // Other instances are unaffected
+        assert!(breaker.allow("inst-2").is_ok());
+    }
+
+    #[test]
+    fn test_circuit_success_resets_failures() {
+        let breaker = breaker(Duration::from_secs(30));
+        breaker.record_failure("inst-1");
+        breaker.record_failure("inst-1");
+        breaker.record_success("inst-1");
+        breaker.record_failure("inst-1");
+        breaker.record_failure("inst-1");
+        assert_eq!(
+            breaker.state("inst-1"),
+            CircuitState::Closed {
+                consecutive_failures: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_circuit_half_open_allows_one_probe() {
+        let breaker = breaker(Duration::ZERO);
+        for _ in 0..3 {
+            breaker.record_failure("inst-1");
+        }
+        assert_eq!(breaker.state("inst-1").as_str(), "open");
+
+        // The open duration has elapsed: the first push is the probe
+        assert!(breaker.allow("inst-1").is_ok());
+        assert_eq!(breaker.state("inst-1").as_str(), "half-open");
+
+        // Probes need two successes to close the circuit
+        breaker.record_success("inst-1");
+        assert!(matches!(
+            breaker.state("inst-1"),
+            CircuitState::HalfOpen {
+                successes: 1,
+                probe_started: None
+            }
+        ));
+        assert!(breaker.allow("inst-1").is_ok());
+        breaker.record_success("inst-1");
+        assert_eq!(breaker.state("inst-1"), CircuitState::default());
+    }
+
+    #[test]
+    fn test_circuit_half_open_rejects_concurrent_probe() {
+        let breaker = breaker(Duration::from_millis(20));
+        for _ in 0..3 {
+            breaker.record_failure("inst-1");
+        }
+        assert!(breaker.allow("inst-1").is_err());
+
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.allow("inst-1").is_ok());
+        assert!(breaker.allow("inst-1").is_err());
+    }
+
+    #[test]
+    fn test_circuit_failed_probe_reopens() {
+        let breaker = breaker(Duration::ZERO);
+        for _ in 0..3 {
+            breaker.record_failure("inst-1");
+        }
+        assert!(breaker.allow("inst-1").is_ok());
+
+        breaker.record_failure("inst-1");
+        assert_eq!(breaker.state("inst-1").as_str(), "open");
+
+        breaker.reset("inst-1");
+        assert_eq!(breaker.state("inst-1"), CircuitState::default());
+    }
 }
@@ -29,7 +29,7 @@
 //! - **Push Distribution**: Actively pushes policy updates to Archimedes instances
 //! - **Instance Discovery**: Discovers and tracks Archimedes instances (K8s, DNS, static)
 //! - **Health Monitoring**: Monitors instance health and policy status
-//! - **Deployment Strategies**: Supports immediate, canary, and rolling deployments
+//! - **Deployment Strategies**: Supports immediate, canary, rolling, and blue-green deployments
 //! - **Rollback**: Automatic and manual rollback capabilities
 //!
 //! # Architecture
@@ -80,54 +80,94 @@ pub mod events;
 pub mod grpc;
 pub mod health;
 pub mod instance;
+pub mod monitor;
+#[cfg(feature = "eunomia-otel")]
+pub mod otel;
 pub mod pusher;
 pub mod rollback;
 pub mod scheduler;
 pub mod state;
 pub mod strategy;
 
+use eunomia_audit::{AuditLogger, DistributionEvent};
 use eunomia_metrics::MetricsRegistry;
-use std::time::Instant;
+use eunomia_registry::RegistryClient;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
 
 // Re-export main types at crate root
-pub use config::DistributorConfig;
-pub use discovery::{
-    create_discovery, Discovery, DiscoverySource, DnsDiscovery, KubernetesDiscovery,
-    StaticDiscovery,
-};
-pub use error::{DistributorError, Result};
+pub use config::{AutoRollbackConfig, DistributorConfig, SignatureConfig};
+#[cfg(feature = "kubernetes")]
+pub use discovery::KubernetesDiscovery;
+pub use discovery::{create_discovery, Discovery, DiscoverySource, DnsDiscovery, StaticDiscovery};
+pub use error::{DistributorError, Result, ERROR_CODE_METADATA};
 pub use events::{DeploymentEventData, EventBus, EventSubscriber, EventType, FilteredSubscriber};
-pub use health::{HealthCheck, HealthConfig, HealthState};
+pub use health::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitState, HealthCheck, HealthConfig, HealthState,
+};
 pub use instance::{Instance, InstanceId, InstanceMetadata, InstanceStatus};
-pub use pusher::{PolicyPusher, PushConfig, PushResult};
+pub use monitor::{HealthMonitor, HealthMonitorHandle, InstanceHealth};
+pub use pusher::{
+    GrpcTransport, KeepaliveConfig, PolicyPusher, PolicyUpdate, PushConfig, PushResult,
+    PushTransport, SimulatedTransport,
+};
 pub use rollback::{
     RollbackConfig, RollbackController, RollbackResult, RollbackTrigger, VersionHistory,
 };
-pub use scheduler::{DeploymentScheduler, SchedulerConfig};
-pub use state::{DeploymentState, DeploymentTracker};
-pub use strategy::{DeploymentStrategy, StrategyType};
+pub use scheduler::{
+    DeploymentScheduler, FreezePeriod, ScheduleConstraints, ScheduledDeployment, SchedulerConfig,
+    SchedulerHandle, TimeWindow,
+};
+pub use state::{
+    DeploymentFilter, DeploymentInfo, DeploymentPage, DeploymentState, DeploymentTracker,
+    InstanceDeploymentStatus,
+};
+pub use strategy::{
+    CanaryMode, CanarySelection, DeploymentGroup, DeploymentHooks, DeploymentStrategy, HookFuture,
+    PostDeployHook, PreDeployHook, StrategyType, DEFAULT_MAX_PAUSE_DURATION,
+    DEPLOYMENT_GROUP_LABEL,
+};
+
+/// How often a paused rolling deployment checks whether it was resumed.
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(500);
 
 /// The main distributor service.
 ///
 /// Coordinates policy distribution across multiple Archimedes instances
 /// using configurable deployment strategies.
 pub struct Distributor {
-    #[allow(dead_code)]
     config: DistributorConfig,
-    discovery: Box<dyn Discovery>,
-    pusher: PolicyPusher,
-    #[allow(dead_code)]
+    discovery: Arc<dyn Discovery>,
+    pusher: Arc<PolicyPusher>,
     scheduler: DeploymentScheduler,
     state: DeploymentTracker,
+    health_monitor: Arc<HealthMonitor>,
+    audit_logger: Option<Arc<AuditLogger>>,
+    /// Registry that deployment version queries are resolved against.
+    registry: Option<Arc<RegistryClient>>,
+    /// Blue-green group assignments made by this distributor, which take
+    /// precedence over discovered labels.
+    deployment_groups: RwLock<HashMap<InstanceId, DeploymentGroup>>,
 }
 
 impl Distributor {
     /// Creates a new distributor with the given configuration.
     pub async fn new(config: DistributorConfig) -> Result<Self> {
-        let discovery = config.create_discovery()?;
-        let pusher = PolicyPusher::new(config.push_config.clone());
-        let scheduler = DeploymentScheduler::new(config.scheduler_config.clone());
-        let state = DeploymentTracker::new();
+        let discovery: Arc<dyn Discovery> = Arc::from(config.create_discovery()?);
+        let pusher = Arc::new(PolicyPusher::new(config.push_config.clone()));
+        let scheduler = DeploymentScheduler::load(config.scheduler_config.clone())?;
+        let state = match &config.state_path {
+            Some(path) => DeploymentTracker::with_persistence(path.clone()),
+            None => DeploymentTracker::new(),
+        };
+        let health_monitor = Arc::new(HealthMonitor::new(
+            config.health_config.clone(),
+            discovery.clone(),
+            pusher.clone(),
+        ));
 
         Ok(Self {
             config,
@@ -135,28 +175,448 @@ impl Distributor {
             pusher,
             scheduler,
             state,
+            health_monitor,
+            audit_logger: None,
+            registry: None,
+            deployment_groups: RwLock::new(HashMap::new()),
         })
     }
 
+    /// Sets the audit logger for automatic rollback and hook failure events.
+    #[must_use]
+    pub fn with_audit_logger(mut self, logger: Arc<AuditLogger>) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    /// Sets the transport policies are pushed through, in place of the
+    /// [`GrpcTransport`] of [`PolicyPusher::new`].
+    #[must_use]
+    pub fn with_transport(mut self, transport: Arc<dyn PushTransport>) -> Self {
+        self.pusher =
+            Arc::new(PolicyPusher::new(self.config.push_config.clone()).with_transport(transport));
+        self.health_monitor = Arc::new(HealthMonitor::new(
+            self.config.health_config.clone(),
+            self.discovery.clone(),
+            self.pusher.clone(),
+        ));
+        self
+    }
+
+    /// Sets the registry that [`deploy`](Self::deploy) resolves version
+    /// queries against.
+    #[must_use]
+    pub fn with_registry(mut self, registry: Arc<RegistryClient>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
     /// Deploys a policy version to all discovered instances.
     ///
     /// # Arguments
     ///
     /// * `service` - Target service name
-    /// * `version` - Policy version to deploy
-    /// * `strategy` - Deployment strategy (immediate, canary, rolling)
+    /// * `version` - Policy version to deploy, or a version query such as
+    ///   `^1.2` or `stable` if a registry is set
+    /// * `strategy` - Deployment strategy (immediate, canary, rolling, blue-green)
     ///
     /// # Returns
     ///
     /// A deployment result containing success/failure counts and details.
+    ///
+    /// # Automatic Rollback
+    ///
+    /// With [`DistributorConfig::auto_rollback`] set, canary and rolling
+    /// deployments whose strategy enables
+    /// [`DeploymentStrategy::auto_rollback`] watch instance health after each
+    /// stage. If health degrades, the deployment halts and fails with
+    /// `health_degraded` set on the result; the service is then rolled back
+    /// to its previous version, if it has one, and the result has
+    /// `rollback_triggered` set.
+    ///
+    /// # Hooks
+    ///
+    /// The strategy's [`DeploymentHooks`] run around the deployment, except
+    /// for dry runs. A failing `pre_deploy` hook aborts the deployment before
+    /// instance discovery; hook failures are logged as
+    /// `DeploymentFailed` audit events.
+    ///
+    /// # Version Queries
+    ///
+    /// With a registry set through [`with_registry`](Self::with_registry),
+    /// `version` is resolved with
+    /// [`RegistryClient::resolve_version`] before anything else, so ranges
+    /// and channel tags deploy the version they resolve to. Without one it
+    /// is deployed as given.
+    ///
+    /// # Bundle Limits
+    ///
+    /// With a registry set, the bundle of the resolved version is fetched
+    /// and checked with [`PolicyPusher::check_bundle`] before anything is
+    /// pushed; a bundle over [`PushConfig::bundle_limits`] fails the
+    /// deployment with [`DistributorError::BundleLimitExceeded`].
+    ///
+    /// # Signature Verification
+    ///
+    /// With [`DistributorConfig::signatures`] set, the bundle must be signed
+    /// by enough keys trusted by its verifier, unless the distributor's
+    /// environment allows unsigned bundles; otherwise the deployment fails
+    /// with [`DistributorError::SignatureVerificationFailed`]. The IDs of
+    /// the verified keys are recorded in [`DeploymentInfo::signed_by`] and
+    /// in the audit events of the deployment's hooks and automatic
+    /// rollback.
+    ///
+    /// # Tracing
+    ///
+    /// With the `eunomia-otel` feature, the deployment is traced as an
+    /// `eunomia.deploy` span with an `eunomia.push_instance` child span per
+    /// instance push; see the `otel` module.
     pub async fn deploy(
         &self,
         service: &str,
         version: &str,
         strategy: DeploymentStrategy,
+    ) -> Result<DeploymentResult> {
+        #[cfg(feature = "eunomia-otel")]
+        let attributes = otel::deploy_attributes(service, version, strategy.strategy_type());
+        // Boxed so that callers awaiting a deployment do not carry its
+        // whole state machine in their own futures
+        let deployment = Box::pin(self.deploy_untraced(service, version, strategy));
+        #[cfg(feature = "eunomia-otel")]
+        let deployment = otel::in_span(otel::DEPLOY_SPAN, attributes, deployment);
+        deployment.await
+    }
+
+    /// Runs [`deploy`](Self::deploy) without tracing it.
+    async fn deploy_untraced(
+        &self,
+        service: &str,
+        version: &str,
+        strategy: DeploymentStrategy,
+    ) -> Result<DeploymentResult> {
+        let version = &self.resolve_version(service, version).await?;
+        let (update, signed_by) = self.check_bundle(service, version).await?;
+
+        let hooks = if strategy.dry_run() {
+            DeploymentHooks::default()
+        } else {
+            strategy.hooks().clone()
+        };
+
+        if let Some(pre_deploy) = &hooks.pre_deploy {
+            if let Err(e) = pre_deploy(service, version).await {
+                tracing::warn!(
+                    service = %service,
+                    version = %version,
+                    error = %e,
+                    "pre-deploy hook failed, aborting deployment"
+                );
+                self.log_audit(
+                    &DistributionEvent::deployment_failed(
+                        service,
+                        version,
+                        &format!("pre-deploy hook failed: {e}"),
+                    )
+                    .with_signed_by(&signed_by),
+                );
+                return Err(e);
+            }
+        }
+
+        let mut result = self.run_deployment(update, strategy).await?;
+        if !signed_by.is_empty() && !result.dry_run {
+            self.record_signers(&result.deployment_id, signed_by.clone())
+                .await;
+        }
+        if result.health_degraded {
+            result.rollback_triggered = self
+                .auto_rollback(&result.deployment_id, service, version, &signed_by)
+                .await;
+        }
+
+        if let Some(post_deploy) = &hooks.post_deploy {
+            if let Err(e) = post_deploy(&result).await {
+                tracing::warn!(
+                    deployment_id = %result.deployment_id,
+                    error = %e,
+                    "post-deploy hook failed"
+                );
+                self.log_audit(
+                    &DistributionEvent::deployment_failed(
+                        service,
+                        version,
+                        &format!("post-deploy hook failed: {e}"),
+                    )
+                    .with_correlation_id(&result.deployment_id)
+                    .with_signed_by(&signed_by),
+                );
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Resolves a version query against the registry, if one is set.
+    async fn resolve_version(&self, service: &str, query: &str) -> Result<String> {
+        let Some(registry) = &self.registry else {
+            return Ok(query.to_string());
+        };
+
+        let version = registry
+            .resolve_version(service, query)
+            .await
+            .map_err(|source| DistributorError::VersionResolution {
+                service: service.to_string(),
+                query: query.to_string(),
+                source: Box::new(source),
+            })?;
+        if version != query {
+            tracing::info!(
+                service = %service,
+                query = %query,
+                version = %version,
+                "Resolved version query"
+            );
+        }
+        Ok(version)
+    }
+
+    /// Returns the update pushed to deploy `version` of `service`, pointing
+    /// at its bundle in the registry if one is set.
+    fn policy_update(&self, service: &str, version: &str) -> PolicyUpdate {
+        let mut update = PolicyUpdate::new(service, version);
+        if let Some(registry) = &self.registry {
+            let config = registry.config();
+            let host = config
+                .url
+                .split_once("://")
+                .map_or(config.url.as_str(), |(_, host)| host)
+                .trim_end_matches('/');
+            update.bundle_url = format!("{host}/{}:{version}", config.repository_name(service));
+        }
+        update
+    }
+
+    /// Checks the bundle of a version before it is deployed: against the
+    /// push limits if a registry is set to fetch it from, and for trusted
+    /// signatures if [`DistributorConfig::signatures`] requires them.
+    ///
+    /// Returns the update to push, carrying the checksum, manifest and
+    /// signatures of the bundle if it was fetched, and the IDs of the keys
+    /// whose signatures were verified.
+    async fn check_bundle(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<(PolicyUpdate, Vec<String>)> {
+        let mut update = self.policy_update(service, version);
+        let refuse = |reason: String| DistributorError::SignatureVerificationFailed {
+            service: service.to_string(),
+            version: version.to_string(),
+            reason,
+        };
+        let environment = self.config.environment.as_deref();
+        let verifier = match &self.config.signatures {
+            Some(signatures) if signatures.is_required_in(environment) => {
+                Some(&signatures.verifier)
+            }
+            Some(_) => {
+                tracing::debug!(
+                    service = %service,
+                    version = %version,
+                    environment = ?environment,
+                    "Unsigned bundles allowed, skipping signature verification"
+                );
+                None
+            }
+            None => None,
+        };
+        let Some(registry) = &self.registry else {
+            if verifier.is_some() {
+                return Err(refuse("no registry to fetch the bundle from".to_string()));
+            }
+            return Ok((update, Vec::new()));
+        };
+
+        let signed = registry
+            .fetch_signed(service, version)
+            .await
+            .map_err(|source| DistributorError::BundleFetch {
+                service: service.to_string(),
+                version: version.to_string(),
+                source: Box::new(source),
+            })?;
+        let sizes = self.pusher.check_bundle(&signed.bundle)?;
+        tracing::debug!(
+            service = %service,
+            version = %version,
+            compressed_bytes = sizes.compressed,
+            uncompressed_bytes = sizes.uncompressed,
+            "Bundle within push limits"
+        );
+        update.bundle_checksum = signed.bundle.compute_checksum();
+        update.manifest =
+            serde_json::to_vec(&signed.bundle.generate_manifest()).unwrap_or_default();
+        if signed.is_signed() {
+            update.signature = signed
+                .signatures
+                .to_json()
+                .map(String::into_bytes)
+                .unwrap_or_default();
+        }
+
+        let Some(verifier) = verifier else {
+            return Ok((update, Vec::new()));
+        };
+        if !signed.is_signed() {
+            return Err(refuse("bundle is not signed".to_string()));
+        }
+        verifier
+            .verify(&signed)
+            .map_err(|e| refuse(e.to_string()))?;
+        let mut key_ids = verifier.verify_all(&signed).unwrap_or_default();
+        key_ids.sort();
+        key_ids.dedup();
+        tracing::info!(
+            service = %service,
+            version = %version,
+            signed_by = ?key_ids,
+            "Bundle signatures verified"
+        );
+        Ok((update, key_ids))
+    }
+
+    /// Records the keys that signed the bundle of a deployment in its
+    /// state.
+    async fn record_signers(&self, deployment_id: &str, key_ids: Vec<String>) {
+        if let Err(e) = self.state.record_signers(deployment_id, key_ids).await {
+            tracing::warn!(
+                deployment_id = %deployment_id,
+                error = %e,
+                "failed to record bundle signers"
+            );
+        }
+    }
+
+    /// Rolls back a service to a previous policy version.
+    pub async fn rollback(&self, service: &str, target_version: &str) -> Result<DeploymentResult> {
+        let start = Instant::now();
+
+        // Use immediate strategy for rollbacks
+        let result = self
+            .run_deployment(
+                self.policy_update(service, target_version),
+                DeploymentStrategy::immediate(),
+            )
+            .await;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let duration_ms = start.elapsed().as_millis().min(u128::from(u64::MAX)) as u64;
+
+        // Record rollback metric
+        MetricsRegistry::global().distributor().record_rollback(
+            service,
+            result.is_ok(),
+            duration_ms,
+        );
+
+        result
+    }
+
+    /// Rolls a service back to the version before a deployment whose health
+    /// degraded, and marks the deployment as rolled back.
+    ///
+    /// Returns false if there is no version to roll back to, leaving the
+    /// deployment failed. The rollback audit events name the keys in
+    /// `signed_by`.
+    async fn auto_rollback(
+        &self,
+        deployment_id: &str,
+        service: &str,
+        version: &str,
+        signed_by: &[String],
+    ) -> bool {
+        let previous = match self.state.get_deployment(deployment_id).await {
+            Ok(info) => info.previous_version,
+            Err(_) => None,
+        };
+        let Some(previous) = previous else {
+            tracing::warn!(
+                deployment_id = %deployment_id,
+                service = %service,
+                "health degraded but no previous version to roll back to, deployment halted"
+            );
+            return false;
+        };
+
+        tracing::warn!(
+            deployment_id = %deployment_id,
+            service = %service,
+            from_version = %version,
+            to_version = %previous,
+            "health degraded after deployment, rolling back"
+        );
+        self.log_audit(
+            &DistributionEvent::rollback_started(service, version, &previous)
+                .with_correlation_id(deployment_id)
+                .with_signed_by(signed_by),
+        );
+
+        let success = match self.rollback(service, &previous).await {
+            Ok(result) => result.is_fully_successful(),
+            Err(e) => {
+                tracing::error!(
+                    deployment_id = %deployment_id,
+                    error = %e,
+                    "automatic rollback failed"
+                );
+                false
+            }
+        };
+        self.log_audit(
+            &DistributionEvent::rollback_completed(service, &previous, success)
+                .with_correlation_id(deployment_id)
+                .with_signed_by(signed_by),
+        );
+
+        if let Err(e) = self.state.mark_rolled_back(deployment_id).await {
+            tracing::warn!(
+                deployment_id = %deployment_id,
+                error = %e,
+                "failed to mark deployment as rolled back"
+            );
+        }
+        true
+    }
+
+    /// Logs an event to the audit logger, if one is set.
+    fn log_audit(&self, event: &DistributionEvent) {
+        if let Some(logger) = &self.audit_logger {
+            if let Err(e) = logger.log(event) {
+                tracing::warn!(
+                    error = %e,
+                    service = %event.service,
+                    "failed to log distribution event"
+                );
+            }
+        }
+    }
+
+    /// Runs a deployment without rolling it back.
+    #[allow(clippy::too_many_lines)]
+    async fn run_deployment(
+        &self,
+        update: PolicyUpdate,
+        strategy: DeploymentStrategy,
     ) -> Result<DeploymentResult> {
         let start = Instant::now();
-        let deployment_id = uuid::Uuid::now_v7().to_string();
+        let update = update.with_deployment_id(uuid::Uuid::now_v7().to_string());
+        let PolicyUpdate {
+            deployment_id,
+            service,
+            version,
+            ..
+        } = &update;
         let strategy_type = strategy.strategy_type();
 
         tracing::info!(
@@ -164,19 +624,22 @@ impl Distributor {
             service = %service,
             version = %version,
             strategy = ?strategy_type,
+            dry_run = strategy.dry_run(),
             "starting policy deployment"
         );
 
         // Record deployment start metric
-        MetricsRegistry::global()
-            .distributor()
-            .record_deployment(service, &strategy_type.to_string());
+        if !strategy.dry_run() {
+            MetricsRegistry::global()
+                .distributor()
+                .record_deployment(service, &strategy_type.to_string());
+        }
 
         // Discover target instances
-        let instances = self.discovery.discover(service).await?;
+        let instances = self.apply_deployment_groups(self.discovery.discover(service).await?);
         if instances.is_empty() {
             return Err(DistributorError::NoInstancesFound {
-                service: service.to_string(),
+                service: service.clone(),
             });
         }
 
@@ -186,35 +649,65 @@ impl Distributor {
             "discovered target instances"
         );
 
+        let instances = if strategy.include_unhealthy() {
+            instances
+        } else {
+            self.exclude_unhealthy(deployment_id, service, instances)?
+        };
+
+        // A dry run reports the plan without tracking or pushing anything
+        if strategy.dry_run() {
+            return self
+                .deploy_dry_run(deployment_id, version, &instances, strategy.force())
+                .await;
+        }
+
         // Track deployment state
+        let _in_flight = MetricsRegistry::global()
+            .distributor()
+            .track_deployment(service, instances.len());
         self.state
-            .start_deployment(&deployment_id, service, version, instances.len())
+            .start_deployment(
+                deployment_id,
+                service,
+                version,
+                strategy_type,
+                instances.len(),
+            )
             .await?;
 
         // Execute deployment based on strategy
         let result = match strategy_type {
             StrategyType::Immediate => {
-                self.deploy_immediate(&deployment_id, service, version, &instances)
-                    .await
-            }
-            StrategyType::Canary => {
-                self.deploy_canary(&deployment_id, service, version, &instances, &strategy)
-                    .await
-            }
-            StrategyType::Rolling => {
-                self.deploy_rolling(&deployment_id, service, version, &instances, &strategy)
+                self.deploy_immediate(&update, &instances, strategy.force())
                     .await
             }
+            StrategyType::Canary => self.deploy_canary(&update, &instances, &strategy).await,
+            StrategyType::Rolling => self.deploy_rolling(&update, &instances, &strategy).await,
+            StrategyType::BlueGreen => self.deploy_blue_green(&update, &instances, &strategy).await,
         };
 
         #[allow(clippy::cast_possible_truncation)]
         let duration_ms = start.elapsed().as_millis().min(u128::from(u64::MAX)) as u64;
 
         // Update final state and record metrics
+        let outcome = match &result {
+            Ok(r) if r.is_fully_successful() => "success",
+            Ok(_) => "failure",
+            Err(_) => "error",
+        };
+        MetricsRegistry::global()
+            .distributor()
+            .record_deployment_outcome(
+                service,
+                &strategy_type.to_string(),
+                outcome,
+                start.elapsed(),
+            );
         match &result {
             Ok(r) => {
                 self.state
-                    .complete_deployment(&deployment_id, r.clone())
+                    .complete_deployment(deployment_id, r.clone())
                     .await?;
                 MetricsRegistry::global().distributor().record_push(
                     service,
@@ -225,7 +718,7 @@ impl Distributor {
             }
             Err(e) => {
                 self.state
-                    .fail_deployment(&deployment_id, e.to_string())
+                    .fail_deployment(deployment_id, e.to_string())
                     .await?;
                 MetricsRegistry::global().distributor().record_push(
                     service,
@@ -239,123 +732,519 @@ impl Distributor {
         result
     }
 
-    /// Rolls back a service to a previous policy version.
-    pub async fn rollback(&self, service: &str, target_version: &str) -> Result<DeploymentResult> {
-        let start = Instant::now();
-
-        // Use immediate strategy for rollbacks
-        let result = self
-            .deploy(service, target_version, DeploymentStrategy::immediate())
-            .await;
-
-        #[allow(clippy::cast_possible_truncation)]
-        let duration_ms = start.elapsed().as_millis().min(u128::from(u64::MAX)) as u64;
-
-        // Record rollback metric
-        MetricsRegistry::global().distributor().record_rollback(
-            service,
-            result.is_ok(),
-            duration_ms,
-        );
-
-        result
-    }
-
     /// Gets the current deployment status for a service.
     pub async fn get_status(&self, service: &str) -> Result<ServiceStatus> {
         self.state.get_service_status(service).await
     }
 
-    /// Lists all known instances for a service.
-    pub async fn list_instances(&self, service: &str) -> Result<Vec<Instance>> {
-        self.discovery.discover(service).await
+    /// Gets a tracked deployment by ID, with its per-instance results.
+    pub async fn get_deployment(&self, deployment_id: &str) -> Result<DeploymentInfo> {
+        self.state.get_deployment(deployment_id).await
     }
 
-    // Private deployment methods
-
-    async fn deploy_immediate(
+    /// Lists tracked deployments matching a filter, newest first.
+    ///
+    /// See [`DeploymentTracker::list_deployments`] for paging.
+    pub async fn list_deployments(
         &self,
-        deployment_id: &str,
-        service: &str,
-        version: &str,
-        instances: &[Instance],
-    ) -> Result<DeploymentResult> {
-        let mut results = Vec::new();
-
-        // Push to all instances in parallel
-        let futures: Vec<_> = instances
-            .iter()
-            .map(|instance| self.pusher.push(instance, service, version))
-            .collect();
-
-        let push_results = futures::future::join_all(futures).await;
-
-        for (instance, push_result) in instances.iter().zip(push_results) {
-            results.push(InstanceResult {
-                instance_id: instance.id.clone(),
-                status: match push_result {
-                    Ok(_) => InstanceResultStatus::Success,
-                    Err(ref e) => InstanceResultStatus::Failed(e.to_string()),
-                },
-            });
+        filter: &DeploymentFilter,
+        page_size: usize,
+        page_token: Option<&str>,
+    ) -> Result<DeploymentPage> {
+        self.state
+            .list_deployments(filter, page_size, page_token)
+            .await
+    }
 
-            self.state
-                .update_instance(deployment_id, &instance.id, push_result.is_ok())
-                .await?;
+    /// Returns the IDs of deployments that were still running when the
+    /// distributor last stopped, oldest first.
+    ///
+    /// Only a distributor with [`DistributorConfig::state_path`] set
+    /// remembers deployments across restarts. Interrupted deployments are
+    /// not resumed; their instances may run either version until an
+    /// operator redeploys or rolls back the service.
+    pub async fn recover_interrupted_deployments(&self) -> Result<Vec<String>> {
+        let ids = self.state.interrupted_deployments().await;
+        for id in &ids {
+            let info = self.state.get_deployment(id).await?;
+            tracing::warn!(
+                deployment_id = %id,
+                service = %info.service,
+                version = %info.version,
+                successful = info.successful,
+                total = info.total_instances,
+                "deployment was interrupted by a distributor restart"
+            );
         }
+        Ok(ids)
+    }
 
-        Ok(DeploymentResult::from_results(deployment_id, results))
+    /// Pauses an in-progress deployment.
+    ///
+    /// A rolling deployment stops after its current batch and waits to be
+    /// resumed for at most [`DeploymentStrategy::max_pause_duration`].
+    pub async fn pause_deployment(&self, deployment_id: &str) -> Result<()> {
+        self.state.pause_deployment(deployment_id).await?;
+        tracing::info!(deployment_id = %deployment_id, "deployment paused");
+        Ok(())
     }
 
-    async fn deploy_canary(
+    /// Resumes a paused deployment.
+    pub async fn resume_deployment(&self, deployment_id: &str) -> Result<()> {
+        self.state.resume_deployment(deployment_id).await?;
+        tracing::info!(deployment_id = %deployment_id, "deployment resumed");
+        Ok(())
+    }
+
+    /// Schedules a deployment to start once `constraints` allow it.
+    ///
+    /// Scheduled deployments are launched by the task started with
+    /// [`start_scheduler`](Self::start_scheduler), as if by
+    /// [`deploy`](Self::deploy). With
+    /// [`SchedulerConfig::persistence_path`] set they survive a restart,
+    /// but without the strategy's [`DeploymentHooks`].
+    ///
+    /// Returns the scheduled deployment as queued. Its ID is not the ID of
+    /// the deployment it launches.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the constraints never allow the deployment to start, the
+    /// queue is full or the queue cannot be persisted.
+    pub async fn schedule_deploy(
         &self,
-        deployment_id: &str,
         service: &str,
         version: &str,
-        instances: &[Instance],
-        strategy: &DeploymentStrategy,
-    ) -> Result<DeploymentResult> {
-        let canary_count = strategy.canary_count(instances.len());
-        let (canary_instances, remaining) = instances.split_at(canary_count.min(instances.len()));
+        strategy: DeploymentStrategy,
+        constraints: ScheduleConstraints,
+    ) -> Result<ScheduledDeployment> {
+        let next_run_at = constraints
+            .next_allowed(chrono::Utc::now())
+            .ok_or_else(|| scheduler::SchedulerError::InvalidSchedule {
+                reason: "the constraints never allow the deployment to start".to_string(),
+            })?;
+
+        let id = uuid::Uuid::now_v7().to_string();
+        let deployment =
+            ScheduledDeployment::new(&id, service, version, strategy).with_constraints(constraints);
+        self.scheduler.enqueue(deployment.clone()).await?;
 
         tracing::info!(
-            deployment_id = %deployment_id,
-            canary_count = canary_instances.len(),
-            remaining_count = remaining.len(),
-            "deploying to canary instances first"
+            scheduled_id = %id,
+            service = %service,
+            version = %version,
+            next_run_at = %next_run_at,
+            "deployment scheduled"
         );
+        Ok(deployment)
+    }
 
-        // Deploy to canary instances
-        let canary_result = self
-            .deploy_immediate(deployment_id, service, version, canary_instances)
-            .await?;
+    /// Lists the scheduled deployments that have not been launched yet, in
+    /// launch order.
+    pub async fn list_scheduled(&self) -> Vec<ScheduledDeployment> {
+        self.scheduler.list_queued().await
+    }
 
-        if !canary_result.is_fully_successful() {
-            tracing::warn!(
-                deployment_id = %deployment_id,
+    /// Cancels a scheduled deployment that has not been launched yet.
+    pub async fn cancel_scheduled(&self, scheduled_id: &str) -> Result<ScheduledDeployment> {
+        let deployment = self.scheduler.remove(scheduled_id).await.ok_or_else(|| {
+            DistributorError::DeploymentNotFound {
+                deployment_id: scheduled_id.to_string(),
+            }
+        })?;
+        tracing::info!(scheduled_id = %scheduled_id, "scheduled deployment cancelled");
+        Ok(deployment)
+    }
+
+    /// Starts launching scheduled deployments in the background.
+    ///
+    /// Every [`SchedulerConfig::poll_interval`], each scheduled deployment
+    /// whose constraints allow it to start is launched, up to
+    /// [`SchedulerConfig::max_concurrent`] at a time.
+    ///
+    /// Launching stops when [`SchedulerHandle::shutdown`] is called or the
+    /// handle is dropped.
+    pub fn start_scheduler(self: &Arc<Self>) -> SchedulerHandle {
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+        let distributor = Arc::clone(self);
+
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(distributor.scheduler.config().poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    // Resolves on shutdown and when the handle is dropped
+                    _ = &mut shutdown_rx => break,
+                    _ = interval.tick() => distributor.launch_due_deployments().await,
+                }
+            }
+
+            tracing::debug!("deployment scheduler stopped");
+        });
+
+        SchedulerHandle::new(shutdown_tx, task)
+    }
+
+    /// Launches every scheduled deployment that may start now.
+    async fn launch_due_deployments(self: &Arc<Self>) {
+        while let Some(scheduled) = self.scheduler.dequeue().await {
+            let distributor = Arc::clone(self);
+            tokio::spawn(async move {
+                let ScheduledDeployment {
+                    id,
+                    service,
+                    version,
+                    strategy,
+                    ..
+                } = scheduled;
+                tracing::info!(
+                    scheduled_id = %id,
+                    service = %service,
+                    version = %version,
+                    "launching scheduled deployment"
+                );
+
+                match distributor.deploy(&service, &version, strategy).await {
+                    Ok(result) => tracing::info!(
+                        scheduled_id = %id,
+                        deployment_id = %result.deployment_id,
+                        "scheduled deployment finished"
+                    ),
+                    Err(e) => tracing::warn!(
+                        scheduled_id = %id,
+                        error = %e,
+                        "scheduled deployment failed"
+                    ),
+                }
+                distributor.scheduler.complete(&id).await;
+            });
+        }
+    }
+
+    /// Returns the health monitor, which is idle until started with
+    /// [`Distributor::start_health_monitor`] (as [`grpc::GrpcServer::run`]
+    /// does) or probed directly.
+    pub fn health_monitor(&self) -> &Arc<HealthMonitor> {
+        &self.health_monitor
+    }
+
+    /// Starts monitoring instance health in the background.
+    ///
+    /// While the monitor runs, deployments leave out instances it reports as
+    /// unhealthy or unreachable, unless the strategy sets
+    /// [`DeploymentStrategy::with_include_unhealthy`].
+    pub fn start_health_monitor(&self) -> HealthMonitorHandle {
+        self.health_monitor.clone().start()
+    }
+
+    /// Returns the monitored health of an instance, if it has been probed.
+    pub fn instance_health(&self, instance_id: &str) -> Option<InstanceHealth> {
+        self.health_monitor.health(instance_id)
+    }
+
+    /// Lists all known instances for a service.
+    ///
+    /// The [`DEPLOYMENT_GROUP_LABEL`] label reflects the blue-green group
+    /// swaps made by this distributor.
+    pub async fn list_instances(&self, service: &str) -> Result<Vec<Instance>> {
+        let instances = self.discovery.discover(service).await?;
+        Ok(self.apply_deployment_groups(instances))
+    }
+
+    /// Overrides the deployment group labels of discovered instances with
+    /// the groups assigned by blue-green deployments.
+    fn apply_deployment_groups(&self, mut instances: Vec<Instance>) -> Vec<Instance> {
+        let groups = self.deployment_groups.read();
+        for instance in &mut instances {
+            if let Some(group) = groups.get(&instance.id) {
+                instance.metadata.labels.insert(
+                    DEPLOYMENT_GROUP_LABEL.to_string(),
+                    group.as_str().to_string(),
+                );
+            }
+        }
+        instances
+    }
+
+    // Private deployment methods
+
+    /// Removes instances the health monitor reports as unhealthy.
+    ///
+    /// Fails if no instance is left.
+    fn exclude_unhealthy(
+        &self,
+        deployment_id: &str,
+        service: &str,
+        instances: Vec<Instance>,
+    ) -> Result<Vec<Instance>> {
+        let (excluded, instances): (Vec<_>, Vec<_>) = instances
+            .into_iter()
+            .partition(|instance| self.health_monitor.is_excluded(&instance.id));
+
+        if !excluded.is_empty() {
+            tracing::warn!(
+                deployment_id = %deployment_id,
+                excluded = ?excluded.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(),
+                "excluding unhealthy instances from deployment"
+            );
+        }
+        if instances.is_empty() {
+            return Err(DistributorError::NoHealthyInstances {
+                service: service.to_string(),
+                excluded: excluded.len(),
+            });
+        }
+        Ok(instances)
+    }
+
+    /// Returns the policy version the instance reports, if it can be
+    /// queried.
+    async fn current_version(&self, instance: &Instance) -> Option<String> {
+        self.pusher
+            .health_check(instance)
+            .await
+            .ok()
+            .and_then(|health| health.policy_version)
+    }
+
+    /// Returns true if the instance is healthy and reports `version` as its
+    /// active policy version.
+    async fn serves_version(&self, instance: &Instance, version: &str) -> Result<bool> {
+        let health = self.pusher.health_check(instance).await?;
+        Ok(health.state == HealthState::Healthy
+            && health.policy_version.as_deref() == Some(version))
+    }
+
+    /// Health checks all instances in parallel and reports what a
+    /// deployment would do to each, without pushing.
+    ///
+    /// Instances that are neither unhealthy nor unreachable would be pushed
+    /// to, and instances already at `version` would be skipped unless
+    /// `force` is set. Every planned action is logged.
+    async fn deploy_dry_run(
+        &self,
+        deployment_id: &str,
+        version: &str,
+        instances: &[Instance],
+        force: bool,
+    ) -> Result<DeploymentResult> {
+        let checks = futures::future::join_all(
+            instances
+                .iter()
+                .map(|instance| self.pusher.health_check(instance)),
+        )
+        .await;
+
+        let results = instances
+            .iter()
+            .zip(checks)
+            .map(|(instance, check)| {
+                let status = match check {
+                    Ok(health) if !force && health.policy_version.as_deref() == Some(version) => {
+                        InstanceResultStatus::Skipped
+                    }
+                    Ok(health) => match (health.state, health.message) {
+                        (HealthState::Unhealthy | HealthState::Unreachable, Some(message)) => {
+                            InstanceResultStatus::Failed(format!(
+                                "instance is {}: {message}",
+                                health.state
+                            ))
+                        }
+                        (HealthState::Unhealthy | HealthState::Unreachable, None) => {
+                            InstanceResultStatus::Failed(format!("instance is {}", health.state))
+                        }
+                        _ => InstanceResultStatus::DryRunSuccess,
+                    },
+                    Err(e) => InstanceResultStatus::Failed(e.to_string()),
+                };
+
+                let action = match &status {
+                    InstanceResultStatus::Skipped => "skip, already at target version",
+                    InstanceResultStatus::Failed(_) => "fail, instance is unhealthy",
+                    _ => "push",
+                };
+                tracing::info!(
+                    deployment_id = %deployment_id,
+                    instance_id = %instance.id,
+                    endpoint = %instance.endpoint.to_uri(),
+                    version = %version,
+                    "dry run: would {action}"
+                );
+
+                InstanceResult {
+                    instance_id: instance.id.clone(),
+                    status,
+                }
+            })
+            .collect();
+
+        Ok(DeploymentResult {
+            dry_run: true,
+            ..DeploymentResult::from_results(deployment_id, results)
+        })
+    }
+
+    /// Pushes to all instances in parallel, at most
+    /// [`PushConfig::max_concurrent_pushes`] at a time.
+    ///
+    /// Unless `force` is set, instances that already report `version` are
+    /// skipped instead of pushed to again. Results are in instance order.
+    async fn deploy_immediate(
+        &self,
+        update: &PolicyUpdate,
+        instances: &[Instance],
+        force: bool,
+    ) -> Result<DeploymentResult> {
+        let PolicyUpdate {
+            deployment_id,
+            version,
+            ..
+        } = update;
+        let mut results = Vec::new();
+        let permits = Semaphore::new(self.config.push_config.max_concurrent_pushes.max(1));
+
+        let mut targets = Vec::new();
+        if force {
+            targets.extend(instances);
+        } else {
+            let versions = futures::future::join_all(instances.iter().map(|instance| async {
+                let _permit = permits.acquire().await;
+                self.current_version(instance).await
+            }))
+            .await;
+            for (instance, current) in instances.iter().zip(versions) {
+                if current.as_deref() == Some(version) {
+                    tracing::debug!(
+                        deployment_id = %deployment_id,
+                        instance_id = %instance.id,
+                        "instance already at target version, skipping"
+                    );
+                    results.push(InstanceResult {
+                        instance_id: instance.id.clone(),
+                        status: InstanceResultStatus::Skipped,
+                    });
+                } else {
+                    targets.push(instance);
+                }
+            }
+        }
+
+        // Push to all remaining instances in parallel
+        let push_results = futures::future::join_all(targets.iter().map(|instance| async {
+            let _permit = permits.acquire().await;
+            self.pusher.push(instance, update).await
+        }))
+        .await;
+
+        for (instance, push_result) in targets.into_iter().zip(push_results) {
+            results.push(InstanceResult {
+                instance_id: instance.id.clone(),
+                status: match push_result {
+                    Ok(ref r) if r.success => InstanceResultStatus::Success,
+                    Ok(ref r) => InstanceResultStatus::Failed(
+                        r.error.clone().unwrap_or_else(|| "push failed".to_string()),
+                    ),
+                    Err(ref e) => InstanceResultStatus::Failed(e.to_string()),
+                },
+            });
+
+            let success = push_result.is_ok_and(|r| r.success);
+            self.state
+                .update_instance(deployment_id, &instance.id, success)
+                .await?;
+        }
+
+        Ok(DeploymentResult::from_results(deployment_id, results))
+    }
+
+    async fn deploy_canary(
+        &self,
+        update: &PolicyUpdate,
+        instances: &[Instance],
+        strategy: &DeploymentStrategy,
+    ) -> Result<DeploymentResult> {
+        let PolicyUpdate {
+            deployment_id,
+            service,
+            version,
+            ..
+        } = update;
+        let canary_count = strategy.canary_count(instances.len());
+        let canary_instances = strategy.canary_selection().select(instances, canary_count);
+        if canary_instances.is_empty() && !instances.is_empty() {
+            return Err(DistributorError::InvalidOperation {
+                reason: format!(
+                    "no instances match the canary selection {:?}",
+                    strategy.canary_selection()
+                ),
+            });
+        }
+        let canary_ids: Vec<InstanceId> = canary_instances.iter().map(|i| i.id.clone()).collect();
+        let remaining: Vec<Instance> = instances
+            .iter()
+            .filter(|instance| !canary_ids.contains(&instance.id))
+            .cloned()
+            .collect();
+        self.state
+            .record_canaries(deployment_id, canary_ids.clone())
+            .await?;
+
+        tracing::info!(
+            deployment_id = %deployment_id,
+            canaries = ?canary_ids,
+            remaining_count = remaining.len(),
+            "deploying to canary instances first"
+        );
+
+        // Deploy to canary instances
+        let mut canary_result = self
+            .deploy_immediate(update, &canary_instances, strategy.force())
+            .await?;
+        canary_result.canary_instances = canary_ids;
+
+        if !canary_result.is_fully_successful() {
+            tracing::warn!(
+                deployment_id = %deployment_id,
                 failed_count = canary_result.failed,
                 "canary deployment failed, aborting"
             );
             return Ok(canary_result);
         }
 
-        // Wait for canary duration
-        if let Some(duration) = strategy.canary_duration() {
+        // Wait for canary duration, watching canary health meanwhile
+        let canary_duration = strategy.canary_duration().unwrap_or_default();
+        if !canary_duration.is_zero() {
             tracing::info!(
                 deployment_id = %deployment_id,
-                duration_secs = duration.as_secs(),
+                duration_secs = canary_duration.as_secs(),
                 "waiting for canary validation period"
             );
-            tokio::time::sleep(duration).await;
+        }
+        if self
+            .stage_degraded(
+                deployment_id,
+                service,
+                &canary_instances,
+                strategy,
+                canary_duration,
+            )
+            .await?
+        {
+            return Ok(DeploymentResult {
+                health_degraded: true,
+                ..canary_result
+            });
+        }
 
-            // Perform health checks
-            for instance in canary_instances {
-                let health = self.pusher.health_check(instance).await?;
-                if health.state != HealthState::Healthy {
+        // Perform health checks
+        if strategy.canary_duration().is_some() {
+            for instance in &canary_instances {
+                if !self.serves_version(instance, version).await? {
                     tracing::warn!(
                         deployment_id = %deployment_id,
                         instance_id = %instance.id,
-                        "canary instance unhealthy, aborting"
+                        "canary instance unhealthy or not on the new version, aborting"
                     );
                     return Ok(canary_result);
                 }
@@ -364,7 +1253,7 @@ impl Distributor {
 
         // Deploy to remaining instances
         let remaining_result = self
-            .deploy_immediate(deployment_id, service, version, remaining)
+            .deploy_immediate(update, &remaining, strategy.force())
             .await?;
 
         // Merge results
@@ -373,14 +1262,18 @@ impl Distributor {
 
     async fn deploy_rolling(
         &self,
-        deployment_id: &str,
-        service: &str,
-        version: &str,
+        update: &PolicyUpdate,
         instances: &[Instance],
         strategy: &DeploymentStrategy,
     ) -> Result<DeploymentResult> {
+        let PolicyUpdate {
+            deployment_id,
+            service,
+            ..
+        } = update;
         let batch_size = strategy.batch_size().unwrap_or(1);
         let mut all_results = Vec::new();
+        let mut health_degraded = false;
 
         for (batch_num, batch) in instances.chunks(batch_size).enumerate() {
             tracing::info!(
@@ -391,7 +1284,7 @@ impl Distributor {
             );
 
             let batch_result = self
-                .deploy_immediate(deployment_id, service, version, batch)
+                .deploy_immediate(update, batch, strategy.force())
                 .await?;
 
             all_results.extend(batch_result.instance_results);
@@ -406,13 +1299,235 @@ impl Distributor {
                 break;
             }
 
+            let deployed = &instances[..(batch_num * batch_size + batch.len())];
+            if self
+                .stage_degraded(deployment_id, service, deployed, strategy, Duration::ZERO)
+                .await?
+            {
+                health_degraded = true;
+                break;
+            }
+
             // Wait between batches
             if let Some(delay) = strategy.batch_delay() {
                 tokio::time::sleep(delay).await;
             }
+
+            self.wait_while_paused(deployment_id, strategy.max_pause_duration())
+                .await?;
         }
 
-        Ok(DeploymentResult::from_results(deployment_id, all_results))
+        Ok(DeploymentResult {
+            health_degraded,
+            ..DeploymentResult::from_results(deployment_id, all_results)
+        })
+    }
+
+    /// Waits at least `min_wait`, watching the health of the instances
+    /// deployed so far over the longer of `min_wait` and the
+    /// [`AutoRollbackConfig::health_check_window`], and returns true if the
+    /// failure rate exceeds the threshold.
+    ///
+    /// Only waits, and returns false, unless automatic rollback is
+    /// configured and enabled by the strategy.
+    async fn stage_degraded(
+        &self,
+        deployment_id: &str,
+        service: &str,
+        instances: &[Instance],
+        strategy: &DeploymentStrategy,
+        min_wait: Duration,
+    ) -> Result<bool> {
+        let config = self
+            .config
+            .auto_rollback
+            .as_ref()
+            .filter(|_| strategy.auto_rollback() && !instances.is_empty());
+        let Some(config) = config else {
+            if !min_wait.is_zero() {
+                tokio::time::sleep(min_wait).await;
+            }
+            return Ok(false);
+        };
+
+        let window = config.health_check_window.max(min_wait);
+        let interval = window / u32::try_from(config.min_samples.max(1)).unwrap_or(u32::MAX);
+        let mut checks = 0usize;
+        let mut failures = 0usize;
+        for _ in 0..config.min_samples.max(1) {
+            tokio::time::sleep(interval).await;
+
+            // Re-discover so health reflects the instances' latest status
+            let current = self.discovery.discover(service).await?;
+            for instance in instances {
+                let instance = current
+                    .iter()
+                    .find(|i| i.id == instance.id)
+                    .unwrap_or(instance);
+                let healthy = self
+                    .pusher
+                    .health_check(instance)
+                    .await
+                    .is_ok_and(|health| health.state.is_operational());
+                checks += 1;
+                if !healthy {
+                    failures += 1;
+                }
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let failure_rate = failures as f64 / checks as f64;
+        let degraded = checks >= config.min_samples && failure_rate > config.failure_rate_threshold;
+        if degraded {
+            tracing::warn!(
+                deployment_id = %deployment_id,
+                checks,
+                failures,
+                threshold = config.failure_rate_threshold,
+                "health degraded after deployment stage"
+            );
+        }
+        Ok(degraded)
+    }
+
+    /// Waits until a paused deployment is resumed.
+    ///
+    /// Fails if the deployment stays paused longer than `max_pause`.
+    async fn wait_while_paused(
+        &self,
+        deployment_id: &str,
+        max_pause: Option<Duration>,
+    ) -> Result<()> {
+        if !self.state.is_paused(deployment_id).await {
+            return Ok(());
+        }
+
+        let max_pause = max_pause.unwrap_or(DEFAULT_MAX_PAUSE_DURATION);
+        let deadline = tokio::time::Instant::now() + max_pause;
+        tracing::info!(
+            deployment_id = %deployment_id,
+            max_pause_secs = max_pause.as_secs(),
+            "waiting for paused deployment to resume"
+        );
+
+        while self.state.is_paused(deployment_id).await {
+            if tokio::time::Instant::now() >= deadline {
+                return Err(DistributorError::Timeout {
+                    operation: format!(
+                        "deployment {deployment_id} was not resumed within {}s",
+                        max_pause.as_secs()
+                    ),
+                });
+            }
+            tokio::time::sleep(PAUSE_POLL_INTERVAL).await;
+        }
+
+        Ok(())
+    }
+
+    async fn deploy_blue_green(
+        &self,
+        update: &PolicyUpdate,
+        instances: &[Instance],
+        strategy: &DeploymentStrategy,
+    ) -> Result<DeploymentResult> {
+        let PolicyUpdate {
+            deployment_id,
+            service,
+            version,
+            ..
+        } = update;
+        let mut blue = Vec::new();
+        let mut green = Vec::new();
+        for instance in instances {
+            match instance
+                .metadata
+                .get_label(DEPLOYMENT_GROUP_LABEL)
+                .and_then(DeploymentGroup::from_label)
+            {
+                Some(DeploymentGroup::Blue) => blue.push(instance.clone()),
+                Some(DeploymentGroup::Green) => green.push(instance.clone()),
+                None => {
+                    return Err(DistributorError::InvalidOperation {
+                        reason: format!(
+                            "instance '{}' is not labeled {DEPLOYMENT_GROUP_LABEL}=blue|green",
+                            instance.id
+                        ),
+                    });
+                }
+            }
+        }
+
+        if green.is_empty() {
+            return Err(DistributorError::InvalidOperation {
+                reason: format!("no green instances found for service '{service}'"),
+            });
+        }
+
+        tracing::info!(
+            deployment_id = %deployment_id,
+            blue_count = blue.len(),
+            green_count = green.len(),
+            "deploying to green instances"
+        );
+
+        // Deploy to the standby group
+        let mut result = self
+            .deploy_immediate(update, &green, strategy.force())
+            .await?;
+        result.blue_count = blue.len();
+        result.green_count = green.len();
+
+        if !result.is_fully_successful() {
+            tracing::warn!(
+                deployment_id = %deployment_id,
+                failed_count = result.failed,
+                "green deployment failed, keeping blue live"
+            );
+            return Ok(result);
+        }
+
+        if let Some(wait) = strategy.health_wait() {
+            tracing::info!(
+                deployment_id = %deployment_id,
+                wait_secs = wait.as_secs(),
+                "waiting before green health checks"
+            );
+            tokio::time::sleep(wait).await;
+        }
+
+        // Every green instance must be healthy on the new version before the swap
+        for instance in &green {
+            if !self.serves_version(instance, version).await? {
+                tracing::warn!(
+                    deployment_id = %deployment_id,
+                    instance_id = %instance.id,
+                    "green instance unhealthy, keeping blue live"
+                );
+                return Ok(result);
+            }
+        }
+
+        // Swap both groups under a single lock so they change together
+        {
+            let mut groups = self.deployment_groups.write();
+            for instance in &blue {
+                groups.insert(instance.id.clone(), DeploymentGroup::Green);
+            }
+            for instance in &green {
+                groups.insert(instance.id.clone(), DeploymentGroup::Blue);
+            }
+        }
+
+        tracing::info!(
+            deployment_id = %deployment_id,
+            "swapped blue and green instances"
+        );
+
+        result.blue_count = green.len();
+        result.green_count = blue.len();
+        Ok(result)
     }
 }
 
@@ -433,14 +1548,43 @@ pub struct DeploymentResult {
 
     /// Per-instance results
     pub instance_results: Vec<InstanceResult>,
+
+    /// Number of instances in the blue (live) group after a blue-green
+    /// deployment (zero for other strategies)
+    pub blue_count: usize,
+
+    /// Number of instances in the green (standby) group after a blue-green
+    /// deployment (zero for other strategies)
+    pub green_count: usize,
+
+    /// Whether this was a dry run, in which nothing was pushed
+    pub dry_run: bool,
+
+    /// Whether health degraded after a deployment stage, halting the
+    /// deployment before the remaining instances
+    pub health_degraded: bool,
+
+    /// Whether the service was rolled back to its previous version after
+    /// health degraded
+    pub rollback_triggered: bool,
+
+    /// Instances chosen as canaries (empty for other strategies)
+    pub canary_instances: Vec<InstanceId>,
 }
 
 impl DeploymentResult {
     /// Creates a result from individual instance results.
+    ///
+    /// Dry-run successes count as successful.
     pub fn from_results(deployment_id: &str, results: Vec<InstanceResult>) -> Self {
         let successful = results
             .iter()
-            .filter(|r| matches!(r.status, InstanceResultStatus::Success))
+            .filter(|r| {
+                matches!(
+                    r.status,
+                    InstanceResultStatus::Success | InstanceResultStatus::DryRunSuccess
+                )
+            })
             .count();
         let failed = results
             .iter()
@@ -457,12 +1601,21 @@ impl DeploymentResult {
             failed,
             skipped,
             instance_results: results,
+            blue_count: 0,
+            green_count: 0,
+            dry_run: false,
+            health_degraded: false,
+            rollback_triggered: false,
+            canary_instances: Vec::new(),
         }
     }
 
-    /// Returns true if all instances were successfully updated.
+    /// Returns true if no instance failed and health did not degrade.
+    ///
+    /// Skipped instances were already at the target version, so they count
+    /// as successful.
     pub fn is_fully_successful(&self) -> bool {
-        self.failed == 0 && self.skipped == 0
+        self.failed == 0 && !self.health_degraded
     }
 
     /// Merges two deployment results.
@@ -470,7 +1623,12 @@ impl DeploymentResult {
         self.successful += other.successful;
         self.failed += other.failed;
         self.skipped += other.skipped;
+        self.blue_count += other.blue_count;
+        self.green_count += other.green_count;
+        self.health_degraded |= other.health_degraded;
+        self.rollback_triggered |= other.rollback_triggered;
         self.instance_results.extend(other.instance_results);
+        self.canary_instances.extend(other.canary_instances);
         self
     }
 }
@@ -496,6 +1654,9 @@ pub enum InstanceResultStatus {
 
     /// Skipped (e.g., already at target version)
     Skipped,
+
+    /// Would have been updated (dry run)
+    DryRunSuccess,
 }
 
 /// Status of a service's policy deployment.
@@ -516,3 +1677,1441 @@ pub struct ServiceStatus {
     /// Per-instance status
     pub instances: Vec<InstanceStatus>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::time::Duration;
+
+    /// Discovery that returns a fixed instance list.
+    struct MockDiscovery {
+        instances: Vec<Instance>,
+    }
+
+    #[async_trait]
+    impl Discovery for MockDiscovery {
+        async fn discover(&self, _service: &str) -> Result<Vec<Instance>> {
+            Ok(self.instances.clone())
+        }
+
+        async fn all_instances(&self) -> Result<Vec<Instance>> {
+            Ok(self.instances.clone())
+        }
+
+        async fn refresh(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn instance(id: &str, group: &str, healthy: bool) -> Instance {
+        let mut instance = Instance::new(id, "localhost:8080").with_metadata(
+            InstanceMetadata::for_service("users-service")
+                .with_label(DEPLOYMENT_GROUP_LABEL, group),
+        );
+        instance.update_status(if healthy {
+            InstanceStatus::Healthy {
+                policy_version: Some("1.0.0".to_string()),
+                last_check: Instant::now(),
+            }
+        } else {
+            InstanceStatus::Unhealthy {
+                reason: "policy load failed".to_string(),
+                since: Instant::now(),
+            }
+        });
+        instance
+    }
+
+    /// Discovery that reports its instances as unhealthy after a number of
+    /// healthy discoveries.
+    struct DegradingDiscovery {
+        instances: Vec<Instance>,
+        healthy_calls: usize,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Discovery for DegradingDiscovery {
+        async fn discover(&self, _service: &str) -> Result<Vec<Instance>> {
+            let calls = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if calls < self.healthy_calls {
+                return Ok(self.instances.clone());
+            }
+            Ok(self
+                .instances
+                .iter()
+                .map(|i| instance(&i.id, "blue", false))
+                .collect())
+        }
+
+        async fn all_instances(&self) -> Result<Vec<Instance>> {
+            self.discover("users-service").await
+        }
+
+        async fn refresh(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    async fn distributor(instances: Vec<Instance>) -> Distributor {
+        distributor_with(
+            DistributorConfig::default(),
+            Arc::new(MockDiscovery { instances }),
+        )
+        .await
+    }
+
+    async fn distributor_with(
+        config: DistributorConfig,
+        discovery: Arc<dyn Discovery>,
+    ) -> Distributor {
+        let mut distributor = Distributor::new(config)
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default()));
+        distributor.health_monitor = Arc::new(HealthMonitor::new(
+            HealthConfig::builder().unhealthy_threshold(1).build(),
+            discovery.clone(),
+            distributor.pusher.clone(),
+        ));
+        distributor.discovery = discovery;
+        distributor
+    }
+
+    async fn groups(distributor: &Distributor) -> Vec<(String, String)> {
+        let mut groups: Vec<_> = distributor
+            .list_instances("users-service")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|i| {
+                let group = i
+                    .metadata
+                    .get_label(DEPLOYMENT_GROUP_LABEL)
+                    .unwrap()
+                    .to_string();
+                (i.id, group)
+            })
+            .collect();
+        groups.sort();
+        groups
+    }
+
+    fn labeled(id: &str, group: &str) -> (String, String) {
+        (id.to_string(), group.to_string())
+    }
+
+    fn pushed_ids(result: &DeploymentResult) -> Vec<&str> {
+        let mut ids: Vec<_> = result
+            .instance_results
+            .iter()
+            .map(|r| r.instance_id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_blue_green_swaps_groups() {
+        let distributor = distributor(vec![
+            instance("blue-1", "blue", true),
+            instance("blue-2", "blue", true),
+            instance("green-1", "green", true),
+            instance("green-2", "green", true),
+            instance("green-3", "green", true),
+        ])
+        .await;
+        let strategy = DeploymentStrategy::blue_green(Duration::ZERO);
+
+        let result = distributor
+            .deploy("users-service", "2.0.0", strategy.clone())
+            .await
+            .unwrap();
+        assert!(result.is_fully_successful());
+        assert_eq!(pushed_ids(&result), vec!["green-1", "green-2", "green-3"]);
+        assert_eq!(result.blue_count, 3);
+        assert_eq!(result.green_count, 2);
+
+        assert_eq!(
+            groups(&distributor).await,
+            vec![
+                labeled("blue-1", "green"),
+                labeled("blue-2", "green"),
+                labeled("green-1", "blue"),
+                labeled("green-2", "blue"),
+                labeled("green-3", "blue"),
+            ]
+        );
+
+        // The next deployment goes to the former blue group
+        let result = distributor
+            .deploy("users-service", "3.0.0", strategy)
+            .await
+            .unwrap();
+        assert_eq!(pushed_ids(&result), vec!["blue-1", "blue-2"]);
+        assert_eq!(result.blue_count, 2);
+        assert_eq!(result.green_count, 3);
+    }
+
+    #[tokio::test]
+    async fn test_blue_green_unhealthy_green_keeps_blue_live() {
+        let distributor = distributor(vec![
+            instance("blue-1", "blue", true),
+            instance("green-1", "green", true),
+            instance("green-2", "green", false),
+        ])
+        .await;
+
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::blue_green(Duration::ZERO),
+            )
+            .await
+            .unwrap();
+        assert_eq!(pushed_ids(&result), vec!["green-1", "green-2"]);
+        assert_eq!(result.blue_count, 1);
+        assert_eq!(result.green_count, 2);
+
+        assert_eq!(
+            groups(&distributor).await,
+            vec![
+                labeled("blue-1", "blue"),
+                labeled("green-1", "green"),
+                labeled("green-2", "green"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_blue_green_requires_groups() {
+        let strategy = DeploymentStrategy::blue_green(Duration::ZERO);
+
+        let unlabeled = distributor(vec![
+            instance("blue-1", "blue", true),
+            instance("other-1", "canary", true),
+        ])
+        .await;
+        let err = unlabeled
+            .deploy("users-service", "2.0.0", strategy.clone())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("other-1"));
+
+        let blue_only = distributor(vec![
+            instance("blue-1", "blue", true),
+            instance("blue-2", "blue", true),
+        ])
+        .await;
+        let err = blue_only
+            .deploy("users-service", "2.0.0", strategy)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no green instances"));
+    }
+
+    #[tokio::test]
+    async fn test_canary_confirms_new_version() {
+        let distributor = distributor(vec![
+            instance("inst-1", "blue", true),
+            instance("inst-2", "blue", true),
+            instance("inst-3", "blue", true),
+        ])
+        .await;
+
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::canary_fixed(1, Duration::ZERO),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_fully_successful());
+        assert_eq!(pushed_ids(&result), vec!["inst-1", "inst-2", "inst-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_canary_selection_is_recorded() {
+        let distributor = distributor(vec![
+            instance("inst-1", "blue", true),
+            instance("inst-2", "blue", true),
+            instance("inst-3", "green", true),
+        ])
+        .await;
+
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::canary_fixed(1, Duration::ZERO).with_canary_selection(
+                    CanarySelection::Labeled {
+                        key: DEPLOYMENT_GROUP_LABEL.to_string(),
+                        value: "green".to_string(),
+                    },
+                ),
+            )
+            .await
+            .unwrap();
+        // The canary is pushed to first
+        assert_eq!(result.instance_results[0].instance_id, "inst-3");
+        assert_eq!(pushed_ids(&result), vec!["inst-1", "inst-2", "inst-3"]);
+        assert_eq!(result.canary_instances, vec!["inst-3".to_string()]);
+        let info = distributor
+            .get_deployment(&result.deployment_id)
+            .await
+            .unwrap();
+        assert_eq!(info.canary_instances, vec!["inst-3".to_string()]);
+
+        let err = distributor
+            .deploy(
+                "users-service",
+                "3.0.0",
+                DeploymentStrategy::canary_fixed(1, Duration::ZERO)
+                    .with_canary_selection(CanarySelection::Explicit(vec!["inst-9".to_string()])),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DistributorError::InvalidOperation { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_skips_instances_at_target_version() {
+        let distributor = distributor(vec![
+            instance("inst-1", "blue", true),
+            instance("inst-2", "blue", true),
+        ])
+        .await;
+
+        // Both instances report 1.0.0 already
+        let result = distributor
+            .deploy("users-service", "1.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+        assert_eq!(result.skipped, 2);
+        assert_eq!(result.successful, 0);
+        assert!(result.is_fully_successful());
+
+        let result = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+        assert_eq!(result.successful, 2);
+
+        // Re-running the deployment finds the new version active
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::rolling(1, Duration::ZERO),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.skipped, 2);
+        assert!(result
+            .instance_results
+            .iter()
+            .all(|r| matches!(r.status, InstanceResultStatus::Skipped)));
+
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::immediate().with_force(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.successful, 2);
+        assert_eq!(result.skipped, 0);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_checks_without_pushing() {
+        let distributor = distributor(vec![
+            instance("inst-1", "blue", true),
+            instance("inst-2", "blue", true),
+            instance("inst-3", "blue", false),
+        ])
+        .await;
+
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::rolling(1, Duration::ZERO).with_dry_run(),
+            )
+            .await
+            .unwrap();
+        assert!(result.dry_run);
+        assert_eq!(result.successful, 2);
+        assert_eq!(result.failed, 1);
+
+        // The unhealthy instance was health checked
+        let failed = result
+            .instance_results
+            .iter()
+            .find(|r| r.instance_id == "inst-3")
+            .unwrap();
+        let InstanceResultStatus::Failed(ref error) = failed.status else {
+            panic!("expected a failed result, got {:?}", failed.status);
+        };
+        assert!(error.contains("policy load failed"));
+        assert!(result
+            .instance_results
+            .iter()
+            .filter(|r| r.instance_id != "inst-3")
+            .all(|r| matches!(r.status, InstanceResultStatus::DryRunSuccess)));
+
+        // Nothing was pushed or tracked
+        for id in ["inst-1", "inst-2"] {
+            let instance = instance(id, "blue", true);
+            assert_eq!(
+                distributor.current_version(&instance).await.as_deref(),
+                Some("1.0.0")
+            );
+        }
+        assert!(distributor
+            .get_deployment(&result.deployment_id)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_skipped_instances() {
+        let distributor = distributor(vec![instance("inst-1", "blue", true)]).await;
+
+        let result = distributor
+            .deploy(
+                "users-service",
+                "1.0.0",
+                DeploymentStrategy::immediate().with_dry_run(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.skipped, 1);
+
+        let result = distributor
+            .deploy(
+                "users-service",
+                "1.0.0",
+                DeploymentStrategy::immediate()
+                    .with_force(true)
+                    .with_dry_run(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.successful, 1);
+        assert!(result.is_fully_successful());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_excludes_unhealthy_instances() {
+        let distributor = distributor(vec![
+            instance("inst-1", "blue", true),
+            instance("inst-2", "blue", false),
+        ])
+        .await;
+
+        // Without health data every instance is deployed to
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::immediate().with_dry_run(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(pushed_ids(&result), vec!["inst-1", "inst-2"]);
+
+        distributor.health_monitor().probe_all().await.unwrap();
+        assert_eq!(
+            distributor.instance_health("inst-2").unwrap().state,
+            HealthState::Unhealthy
+        );
+
+        let result = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+        assert_eq!(pushed_ids(&result), vec!["inst-1"]);
+
+        let result = distributor
+            .deploy(
+                "users-service",
+                "3.0.0",
+                DeploymentStrategy::immediate().with_include_unhealthy(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(pushed_ids(&result), vec!["inst-1", "inst-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_fails_without_healthy_instances() {
+        let distributor = distributor(vec![instance("inst-1", "blue", false)]).await;
+        distributor.health_monitor().probe_all().await.unwrap();
+
+        let err = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DistributorError::NoHealthyInstances { excluded: 1, .. }
+        ));
+    }
+
+    /// Spawns a rolling deployment to three instances, one per batch, and
+    /// pauses it after the first batch.
+    async fn start_paused_rollout(
+        distributor: &Arc<Distributor>,
+        strategy: DeploymentStrategy,
+    ) -> tokio::task::JoinHandle<Result<DeploymentResult>> {
+        let task = tokio::spawn({
+            let distributor = distributor.clone();
+            async move { distributor.deploy("users-service", "2.0.0", strategy).await }
+        });
+
+        // The rollout sleeps for the batch delay after the first batch
+        let deployment_id = loop {
+            if let Some(info) = distributor.state.list_active().await.first() {
+                break info.id.clone();
+            }
+            tokio::task::yield_now().await;
+        };
+        distributor.pause_deployment(&deployment_id).await.unwrap();
+        task
+    }
+
+    async fn active_version(distributor: &Distributor, id: &str) -> Option<String> {
+        distributor
+            .pusher
+            .health_check(&instance(id, "blue", true))
+            .await
+            .unwrap()
+            .policy_version
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rolling_deployment_pause_resume() {
+        let distributor = Arc::new(
+            distributor(vec![
+                instance("inst-1", "blue", true),
+                instance("inst-2", "blue", true),
+                instance("inst-3", "blue", true),
+            ])
+            .await,
+        );
+
+        let task = start_paused_rollout(
+            &distributor,
+            DeploymentStrategy::rolling(1, Duration::from_secs(10)),
+        )
+        .await;
+        let deployment_id = distributor.state.list_active().await[0].id.clone();
+
+        tokio::time::sleep(Duration::from_secs(600)).await;
+        assert!(!task.is_finished());
+        assert_eq!(
+            distributor.get_status("users-service").await.unwrap().state,
+            DeploymentState::Paused
+        );
+        assert_eq!(
+            active_version(&distributor, "inst-1").await.as_deref(),
+            Some("2.0.0")
+        );
+        assert_eq!(
+            active_version(&distributor, "inst-2").await.as_deref(),
+            Some("1.0.0")
+        );
+
+        distributor.resume_deployment(&deployment_id).await.unwrap();
+        let result = task.await.unwrap().unwrap();
+        assert_eq!(result.successful, 3);
+        assert_eq!(
+            distributor.get_status("users-service").await.unwrap().state,
+            DeploymentState::Completed
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rolling_deployment_pause_times_out() {
+        let distributor = Arc::new(
+            distributor(vec![
+                instance("inst-1", "blue", true),
+                instance("inst-2", "blue", true),
+                instance("inst-3", "blue", true),
+            ])
+            .await,
+        );
+
+        let task = start_paused_rollout(
+            &distributor,
+            DeploymentStrategy::rolling(1, Duration::from_secs(10))
+                .with_max_pause_duration(Duration::from_secs(60)),
+        )
+        .await;
+
+        let err = task.await.unwrap().unwrap_err();
+        assert!(matches!(err, DistributorError::Timeout { .. }));
+        assert_eq!(
+            distributor.get_status("users-service").await.unwrap().state,
+            DeploymentState::Failed
+        );
+        assert_eq!(
+            active_version(&distributor, "inst-2").await.as_deref(),
+            Some("1.0.0")
+        );
+    }
+
+    /// Creates a distributor with automatic rollback whose three instances
+    /// turn unhealthy after `healthy_calls` discoveries.
+    async fn degrading_distributor(healthy_calls: usize) -> Distributor {
+        let config = DistributorConfig::builder()
+            .auto_rollback(AutoRollbackConfig {
+                health_check_window: Duration::from_secs(30),
+                failure_rate_threshold: 0.5,
+                min_samples: 3,
+            })
+            .build();
+        let discovery = DegradingDiscovery {
+            instances: vec![
+                instance("inst-1", "blue", true),
+                instance("inst-2", "blue", true),
+                instance("inst-3", "blue", true),
+            ],
+            healthy_calls,
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        };
+        distributor_with(config, Arc::new(discovery)).await
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_canary_health_degradation_rolls_back() {
+        use eunomia_audit::InMemoryBackend;
+
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = Arc::new(AuditLogger::builder().with_backend(backend.clone()).build());
+        // Healthy for the first deployment and the canary's discovery
+        let distributor = degrading_distributor(2).await.with_audit_logger(logger);
+
+        distributor
+            .deploy("users-service", "1.1.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::canary_fixed(1, Duration::ZERO),
+            )
+            .await
+            .unwrap();
+        assert!(result.health_degraded);
+        assert!(result.rollback_triggered);
+        assert_eq!(pushed_ids(&result), vec!["inst-1"]);
+
+        let deployment = distributor
+            .get_deployment(&result.deployment_id)
+            .await
+            .unwrap();
+        assert_eq!(deployment.state, DeploymentState::RolledBack);
+        let status = distributor.get_status("users-service").await.unwrap();
+        assert_eq!(status.current_version.as_deref(), Some("1.1.0"));
+        assert_eq!(status.state, DeploymentState::Completed);
+        for id in ["inst-1", "inst-2", "inst-3"] {
+            assert_eq!(
+                active_version(&distributor, id).await.as_deref(),
+                Some("1.1.0")
+            );
+        }
+
+        let events = backend.events();
+        assert_eq!(events.len(), 2);
+        assert!(events[0].contains("rollback_started"));
+        assert!(events[0].contains(&result.deployment_id));
+        assert!(events[1].contains("rollback_completed"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rolling_deployment_healthy_does_not_roll_back() {
+        let distributor = degrading_distributor(usize::MAX).await;
+
+        distributor
+            .deploy("users-service", "1.1.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::rolling(1, Duration::ZERO),
+            )
+            .await
+            .unwrap();
+        assert!(!result.health_degraded);
+        assert!(!result.rollback_triggered);
+        assert_eq!(result.successful, 3);
+
+        let status = distributor.get_status("users-service").await.unwrap();
+        assert_eq!(status.current_version.as_deref(), Some("2.0.0"));
+        assert_eq!(status.previous_version.as_deref(), Some("1.1.0"));
+        assert_eq!(status.state, DeploymentState::Completed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_health_degradation_without_rollback_target() {
+        let distributor = degrading_distributor(1).await;
+
+        // Nothing to roll back to
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::rolling(1, Duration::ZERO),
+            )
+            .await
+            .unwrap();
+        assert!(result.health_degraded);
+        assert!(!result.rollback_triggered);
+        assert!(!result.is_fully_successful());
+        assert_eq!(pushed_ids(&result), vec!["inst-1"]);
+        let deployment = distributor
+            .get_deployment(&result.deployment_id)
+            .await
+            .unwrap();
+        assert_eq!(deployment.state, DeploymentState::Failed);
+        assert!(deployment.error.is_some());
+        assert_eq!(
+            distributor.get_status("users-service").await.unwrap().state,
+            DeploymentState::Failed
+        );
+
+        // Strategies can opt out of automatic rollback
+        let result = distributor
+            .deploy(
+                "users-service",
+                "3.0.0",
+                DeploymentStrategy::rolling(1, Duration::ZERO).with_auto_rollback(false),
+            )
+            .await
+            .unwrap();
+        assert!(!result.health_degraded);
+        assert!(!result.rollback_triggered);
+        assert_eq!(result.successful, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_canary_duration_is_the_health_check_window() {
+        let distributor = degrading_distributor(usize::MAX).await;
+
+        // The 30s health check window is watched during the canary duration
+        let start = tokio::time::Instant::now();
+        let result = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::canary_fixed(1, Duration::from_secs(30)),
+            )
+            .await
+            .unwrap();
+        assert!(result.is_fully_successful());
+        assert!(start.elapsed() < Duration::from_secs(31));
+
+        // A longer canary duration spreads the samples over it
+        let start = tokio::time::Instant::now();
+        distributor
+            .deploy(
+                "users-service",
+                "3.0.0",
+                DeploymentStrategy::canary_fixed(1, Duration::from_secs(90)),
+            )
+            .await
+            .unwrap();
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_secs(90) && elapsed < Duration::from_secs(91));
+    }
+
+    #[tokio::test]
+    async fn test_deployment_hooks() {
+        use eunomia_audit::InMemoryBackend;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = Arc::new(AuditLogger::builder().with_backend(backend.clone()).build());
+        let distributor = distributor(vec![
+            instance("inst-1", "blue", true),
+            instance("inst-2", "blue", true),
+        ])
+        .await
+        .with_audit_logger(logger);
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let deployed = Arc::new(AtomicUsize::new(0));
+        let hooks = DeploymentHooks::new()
+            .with_pre_deploy({
+                let calls = calls.clone();
+                move |_service, _version| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Box::pin(async { Ok(()) })
+                }
+            })
+            .with_post_deploy({
+                let deployed = deployed.clone();
+                move |result| {
+                    deployed.fetch_add(result.successful, Ordering::SeqCst);
+                    Box::pin(async { Ok(()) })
+                }
+            });
+
+        distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::immediate().with_hooks(hooks.clone()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(deployed.load(Ordering::SeqCst), 2);
+
+        // Dry runs do not run hooks
+        distributor
+            .deploy(
+                "users-service",
+                "3.0.0",
+                DeploymentStrategy::immediate()
+                    .with_hooks(hooks)
+                    .with_dry_run(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(backend.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failing_pre_deploy_hook_aborts() {
+        use eunomia_audit::InMemoryBackend;
+
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = Arc::new(AuditLogger::builder().with_backend(backend.clone()).build());
+        let distributor = distributor(vec![instance("inst-1", "blue", true)])
+            .await
+            .with_audit_logger(logger);
+
+        let hooks = DeploymentHooks::new().with_pre_deploy(|_service, version| {
+            let reason = format!("version {version} is not in the registry");
+            Box::pin(async move { Err(DistributorError::InvalidOperation { reason }) })
+        });
+        let err = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::immediate().with_hooks(hooks),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not in the registry"));
+
+        // Nothing was pushed or tracked
+        assert_eq!(
+            active_version(&distributor, "inst-1").await.as_deref(),
+            Some("1.0.0")
+        );
+        let status = distributor.get_status("users-service").await.unwrap();
+        assert!(status.current_version.is_none());
+        let events = backend.events();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].contains("deployment_failed"));
+        assert!(events[0].contains("pre-deploy hook failed"));
+    }
+
+    /// Simulated transport recording the highest number of pushes in
+    /// flight at once.
+    #[derive(Default)]
+    struct ConcurrencyTransport {
+        simulated: SimulatedTransport,
+        in_flight: std::sync::atomic::AtomicUsize,
+        max_in_flight: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PushTransport for ConcurrencyTransport {
+        async fn send(&self, instance: &Instance, update: &PolicyUpdate) -> Result<()> {
+            use std::sync::atomic::Ordering;
+
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            let result = self.simulated.send(instance, update).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            result
+        }
+
+        async fn active_version(&self, instance: &Instance) -> Result<Option<String>> {
+            self.simulated.active_version(instance).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deploy_limits_concurrent_pushes() {
+        use std::sync::atomic::Ordering;
+
+        let instances: Vec<_> = (1..=7)
+            .map(|i| instance(&format!("inst-{i}"), "blue", true))
+            .collect();
+        let push_config = PushConfig::builder().max_concurrent_pushes(3).build();
+        let config = DistributorConfig::builder()
+            .push_config(push_config)
+            .build();
+
+        for (strategy, limit) in [
+            (DeploymentStrategy::immediate(), 3),
+            (DeploymentStrategy::rolling(5, Duration::ZERO), 3),
+            (DeploymentStrategy::rolling(2, Duration::ZERO), 2),
+        ] {
+            let mut distributor = distributor_with(
+                config.clone(),
+                Arc::new(MockDiscovery {
+                    instances: instances.clone(),
+                }),
+            )
+            .await;
+            let transport = Arc::new(ConcurrencyTransport::default());
+            distributor.pusher = Arc::new(
+                PolicyPusher::new(distributor.config.push_config.clone())
+                    .with_transport(transport.clone()),
+            );
+
+            let result = distributor
+                .deploy("users-service", "2.0.0", strategy)
+                .await
+                .unwrap();
+            assert_eq!(result.successful, 7);
+            assert_eq!(transport.max_in_flight.load(Ordering::SeqCst), limit);
+
+            // Results line up with the instances
+            let ids: Vec<_> = result
+                .instance_results
+                .iter()
+                .map(|r| r.instance_id.as_str())
+                .collect();
+            let expected: Vec<_> = instances.iter().map(|i| i.id.as_str()).collect();
+            assert_eq!(ids, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deploy_resolves_version_queries() {
+        use eunomia_registry::{BundleCache, CacheConfig, RegistryConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let bundle = eunomia_core::Bundle::builder("users-service")
+            .version("2.1.0")
+            .build();
+        for version in ["v1.4.0", "v2.0.0", "v2.1.0", "v2.2.0-rc.1"] {
+            cache.put("users-service", version, &bundle).unwrap();
+        }
+        let registry = RegistryClient::new(RegistryConfig::new("http://127.0.0.1:9"))
+            .unwrap()
+            .with_cache(cache)
+            .with_offline(true);
+        let distributor = distributor(vec![instance("inst-1", "blue", true)])
+            .await
+            .with_registry(Arc::new(registry));
+
+        // The pre-deploy hook sees the resolved version
+        let hooks = DeploymentHooks::new().with_pre_deploy(|_service, version| {
+            let reason = format!("deploying {version}");
+            Box::pin(async move { Err(DistributorError::InvalidOperation { reason }) })
+        });
+        let strategy = DeploymentStrategy::immediate().with_hooks(hooks);
+        let err = distributor
+            .deploy("users-service", "^2", strategy.clone())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("deploying v2.1.0"), "{err}");
+
+        let err = distributor
+            .deploy("users-service", "^3", strategy)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DistributorError::VersionResolution { .. }));
+        assert!(
+            err.to_string()
+                .contains("(available: v1.4.0, v2.0.0, v2.1.0, v2.2.0-rc.1)"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deploy_checks_bundle_limits() {
+        use eunomia_core::{BundleLimitError, BundleLimits};
+        use eunomia_registry::{BundleCache, CacheConfig, RegistryConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let bundle = eunomia_core::Bundle::builder("users-service")
+            .version("2.0.0")
+            .add_data_file(
+                "users_service/users.json",
+                "[\"alice\", \"bob\", \"carol\"]",
+            )
+            .build();
+        cache.put("users-service", "v2.0.0", &bundle).unwrap();
+        let registry = RegistryClient::new(RegistryConfig::new("http://127.0.0.1:9"))
+            .unwrap()
+            .with_cache(cache)
+            .with_offline(true);
+        let push_config = PushConfig::builder()
+            .bundle_limits(BundleLimits::default().with_max_data_file_size(16))
+            .build();
+        let distributor = distributor_with(
+            DistributorConfig::builder()
+                .push_config(push_config)
+                .build(),
+            Arc::new(MockDiscovery {
+                instances: vec![instance("inst-1", "blue", true)],
+            }),
+        )
+        .await
+        .with_registry(Arc::new(registry));
+
+        let err = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap_err();
+        let DistributorError::BundleLimitExceeded { source, .. } = &err else {
+            panic!("unexpected error: {err}");
+        };
+        assert!(matches!(
+            source,
+            BundleLimitError::DataFileTooLarge { path, .. } if path == "users_service/users.json"
+        ));
+        assert_eq!(err.code(), "EUN-D023");
+        assert_eq!(
+            active_version(&distributor, "inst-1").await.as_deref(),
+            Some("1.0.0")
+        );
+    }
+
+    /// Returns a distributor fetching `users-service` 2.0.0 from an offline
+    /// registry cache, signed by `signer` if given.
+    async fn signed_bundle_distributor(
+        config: DistributorConfig,
+        signer: Option<&eunomia_core::signing::BundleSigner>,
+    ) -> (Distributor, tempfile::TempDir) {
+        use eunomia_registry::{BundleCache, CacheConfig, RegistryConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let bundle = eunomia_core::Bundle::builder("users-service")
+            .version("2.0.0")
+            .build();
+        cache.put("users-service", "v2.0.0", &bundle).unwrap();
+        if let Some(signer) = signer {
+            cache
+                .put_signatures("users-service", "v2.0.0", &signer.sign(&bundle).signatures)
+                .unwrap();
+        }
+        let registry = RegistryClient::new(RegistryConfig::new("http://127.0.0.1:9"))
+            .unwrap()
+            .with_cache(cache)
+            .with_offline(true);
+        let distributor = distributor_with(
+            config,
+            Arc::new(MockDiscovery {
+                instances: vec![instance("inst-1", "blue", true)],
+            }),
+        )
+        .await
+        .with_registry(Arc::new(registry));
+        (distributor, dir)
+    }
+
+    #[tokio::test]
+    async fn test_deploy_requires_signed_bundle() {
+        use eunomia_core::signing::{BundleVerifier, SigningKeyPair};
+
+        let mut verifier = BundleVerifier::new();
+        verifier.add_public_key("release-key", SigningKeyPair::generate().verifying_key());
+        let config = DistributorConfig::builder()
+            .environment("prod")
+            .require_signatures(SignatureConfig::new(verifier).allow_unsigned_in("dev"))
+            .build();
+        let (distributor, _dir) = signed_bundle_distributor(config, None).await;
+
+        let err = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            DistributorError::SignatureVerificationFailed { .. }
+        ));
+        assert_eq!(err.code(), "EUN-D024");
+        assert_eq!(
+            active_version(&distributor, "inst-1").await.as_deref(),
+            Some("1.0.0")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deploy_records_bundle_signers() {
+        use eunomia_audit::InMemoryBackend;
+        use eunomia_core::signing::{BundleSigner, BundleVerifier, SigningKeyPair};
+
+        let key_pair = SigningKeyPair::generate();
+        let signer = BundleSigner::from_key_pair(&key_pair, "release-key".to_string());
+        let mut verifier = BundleVerifier::new();
+        verifier.add_public_key("release-key", key_pair.verifying_key());
+        let config = DistributorConfig::builder()
+            .require_signatures(SignatureConfig::new(verifier))
+            .build();
+        let (distributor, _dir) = signed_bundle_distributor(config, Some(&signer)).await;
+        let backend = Arc::new(InMemoryBackend::new());
+        let logger = Arc::new(AuditLogger::builder().with_backend(backend.clone()).build());
+        let distributor = distributor.with_audit_logger(logger);
+
+        let result = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+
+        // Signers are recorded in the state without an audit event
+        assert!(result.is_fully_successful());
+        let info = distributor
+            .get_deployment(&result.deployment_id)
+            .await
+            .unwrap();
+        assert_eq!(info.signed_by, vec!["release-key".to_string()]);
+        assert!(backend.events().is_empty());
+
+        // The events the deployment logs name them
+        let hooks = DeploymentHooks::new().with_post_deploy(|_result| {
+            Box::pin(async {
+                Err(DistributorError::InvalidOperation {
+                    reason: "smoke test failed".to_string(),
+                })
+            })
+        });
+        distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::immediate().with_hooks(hooks),
+            )
+            .await
+            .unwrap();
+        let events = backend.events();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].contains("post-deploy hook failed"));
+        assert!(events[0].contains("\"signed_by\":[\"release-key\"]"));
+    }
+
+    /// Transport recording the updates it is sent, each of which is
+    /// activated.
+    #[derive(Default)]
+    struct RecordingTransport {
+        updates: parking_lot::Mutex<Vec<PolicyUpdate>>,
+    }
+
+    #[async_trait]
+    impl PushTransport for RecordingTransport {
+        async fn send(&self, _instance: &Instance, update: &PolicyUpdate) -> Result<()> {
+            self.updates.lock().push(update.clone());
+            Ok(())
+        }
+
+        async fn active_version(&self, _instance: &Instance) -> Result<Option<String>> {
+            Ok(self
+                .updates
+                .lock()
+                .last()
+                .map(|update| update.version.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deploy_pushes_bundle_details() {
+        use eunomia_core::signing::{BundleSigner, SigningKeyPair};
+
+        let signer =
+            BundleSigner::from_key_pair(&SigningKeyPair::generate(), "release-key".to_string());
+        let (mut distributor, _dir) =
+            signed_bundle_distributor(DistributorConfig::default(), Some(&signer)).await;
+        let transport = Arc::new(RecordingTransport::default());
+        distributor.pusher = Arc::new(
+            PolicyPusher::new(distributor.config.push_config.clone())
+                .with_transport(transport.clone()),
+        );
+
+        let result = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+        assert!(result.is_fully_successful());
+
+        let updates = transport.updates.lock();
+        assert_eq!(updates.len(), 1);
+        let update = &updates[0];
+        assert_eq!(update.deployment_id, result.deployment_id);
+        assert_eq!(update.bundle_url, "127.0.0.1:9/users-service:v2.0.0");
+        assert_eq!(update.bundle_checksum.len(), 64);
+        assert!(!update.manifest.is_empty());
+        assert!(String::from_utf8_lossy(&update.signature).contains("signatures"));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_unsigned_bundle_in_allowed_environment() {
+        use eunomia_core::signing::BundleVerifier;
+
+        let config = DistributorConfig::builder()
+            .environment("dev")
+            .require_signatures(
+                SignatureConfig::new(BundleVerifier::new()).allow_unsigned_in("dev"),
+            )
+            .build();
+        let (distributor, _dir) = signed_bundle_distributor(config, None).await;
+
+        let result = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+
+        assert!(result.is_fully_successful());
+        let info = distributor
+            .get_deployment(&result.deployment_id)
+            .await
+            .unwrap();
+        assert!(info.signed_by.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_requires_registry_for_signatures() {
+        use eunomia_core::signing::BundleVerifier;
+
+        let distributor = distributor_with(
+            DistributorConfig::builder()
+                .require_signatures(SignatureConfig::new(BundleVerifier::new()))
+                .build(),
+            Arc::new(MockDiscovery {
+                instances: vec![instance("inst-1", "blue", true)],
+            }),
+        )
+        .await;
+
+        let err = distributor
+            .deploy("users-service", "2.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "EUN-D024");
+    }
+
+    #[tokio::test]
+    async fn test_deployment_history() {
+        let distributor = distributor(vec![
+            instance("inst-1", "blue", true),
+            instance("inst-2", "blue", true),
+        ])
+        .await;
+
+        let first = distributor
+            .deploy("users-service", "1.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+        let second = distributor
+            .deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::rolling(1, Duration::ZERO),
+            )
+            .await
+            .unwrap();
+
+        let info = distributor
+            .get_deployment(&second.deployment_id)
+            .await
+            .unwrap();
+        assert_eq!(info.version, "2.0.0");
+        assert_eq!(info.strategy, StrategyType::Rolling);
+        assert_eq!(info.state, DeploymentState::Completed);
+        assert_eq!(info.instance_status.len(), 2);
+
+        let page = distributor
+            .list_deployments(
+                &DeploymentFilter {
+                    service: Some("users-service".to_string()),
+                    ..Default::default()
+                },
+                1,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.deployments[0].id, second.deployment_id);
+
+        let page = distributor
+            .list_deployments(
+                &DeploymentFilter::default(),
+                1,
+                page.next_page_token.as_deref(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.deployments[0].id, first.deployment_id);
+        assert_eq!(page.deployments[0].skipped, 2);
+        assert!(page.next_page_token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recover_interrupted_deployments() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = DistributorConfig::builder()
+            .static_endpoints(vec!["instance-1:8080".to_string()])
+            .state_path(dir.path().join(state::STATE_FILE_NAME))
+            .build();
+
+        let distributor = Distributor::new(config.clone())
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default()));
+        let done = distributor
+            .deploy("users-service", "1.0.0", DeploymentStrategy::immediate())
+            .await
+            .unwrap();
+        // Simulate a restart in the middle of a deployment
+        distributor
+            .state
+            .start_deployment(
+                "deploy-2",
+                "users-service",
+                "2.0.0",
+                StrategyType::Immediate,
+                1,
+            )
+            .await
+            .unwrap();
+        assert!(distributor
+            .recover_interrupted_deployments()
+            .await
+            .unwrap()
+            .is_empty());
+        drop(distributor);
+
+        let distributor = Distributor::new(config).await.unwrap();
+        assert_eq!(
+            distributor.recover_interrupted_deployments().await.unwrap(),
+            ["deploy-2"]
+        );
+        let info = distributor
+            .get_deployment(&done.deployment_id)
+            .await
+            .unwrap();
+        assert_eq!(info.state, DeploymentState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_deployments() {
+        let config = DistributorConfig::builder()
+            .scheduler_config(
+                SchedulerConfig::builder()
+                    .poll_interval(Duration::from_millis(10))
+                    .build(),
+            )
+            .build();
+        let discovery = Arc::new(MockDiscovery {
+            instances: vec![instance("inst-1", "blue", true)],
+        });
+        let distributor = Arc::new(distributor_with(config, discovery).await);
+
+        let now = chrono::Utc::now();
+        let due = distributor
+            .schedule_deploy(
+                "users-service",
+                "2.0.0",
+                DeploymentStrategy::immediate(),
+                ScheduleConstraints::new().not_before(now + chrono::Duration::milliseconds(100)),
+            )
+            .await
+            .unwrap()
+            .id;
+        let frozen = distributor
+            .schedule_deploy(
+                "users-service",
+                "3.0.0",
+                DeploymentStrategy::immediate(),
+                ScheduleConstraints::new()
+                    .with_freeze(FreezePeriod::new(now, now + chrono::Duration::hours(1))),
+            )
+            .await
+            .unwrap()
+            .id;
+        assert_eq!(distributor.list_scheduled().await.len(), 2);
+
+        let handle = distributor.start_scheduler();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while distributor.list_scheduled().await.len() > 1 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(distributor.list_scheduled().await[0].id, frozen);
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while active_version(&distributor, "inst-1").await.as_deref() != Some("2.0.0") {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            distributor.cancel_scheduled(&frozen).await.unwrap().version,
+            "3.0.0"
+        );
+        assert!(distributor.list_scheduled().await.is_empty());
+        let err = distributor.cancel_scheduled(&due).await.unwrap_err();
+        assert!(matches!(err, DistributorError::DeploymentNotFound { .. }));
+
+        assert!(handle.is_running());
+        handle.shutdown().await;
+    }
+
+    #[test]
+    fn test_from_results_has_no_groups() {
+        let result = DeploymentResult::from_results(
+            "deploy-1",
+            vec![InstanceResult {
+                instance_id: "inst-1".to_string(),
+                status: InstanceResultStatus::Success,
+            }],
+        );
+        assert_eq!(result.successful, 1);
+        assert_eq!(result.blue_count, 0);
+        assert_eq!(result.green_count, 0);
+    }
+
+    #[test]
+    fn test_skipped_is_fully_successful() {
+        let result = DeploymentResult::from_results(
+            "deploy-1",
+            vec![
+                InstanceResult {
+                    instance_id: "inst-1".to_string(),
+                    status: InstanceResultStatus::Success,
+                },
+                InstanceResult {
+                    instance_id: "inst-2".to_string(),
+                    status: InstanceResultStatus::Skipped,
+                },
+            ],
+        );
+        assert_eq!(result.skipped, 1);
+        assert!(result.is_fully_successful());
+    }
+}
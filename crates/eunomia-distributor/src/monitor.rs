@@ -0,0 +1,413 @@
+//! Background health monitoring of Archimedes instances.
+//!
+//! The [`HealthMonitor`] probes every discovered instance at
+//! [`HealthConfig::check_interval`] and tracks its health with a
+//! [`HealthTracker`], so an instance only changes state after
+//! `unhealthy_threshold` consecutive failures or `healthy_threshold`
+//! consecutive successes.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let monitor = Arc::new(HealthMonitor::new(config, discovery, pusher));
+//! let handle = monitor.clone().start();
+//!
+//! // Later
+//! if let Some(health) = monitor.health("users-1") {
+//!     println!("{}: {}", health.instance_id, health.state);
+//! }
+//! handle.shutdown().await;
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use eunomia_metrics::MetricsRegistry;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::discovery::Discovery;
+use crate::error::Result;
+use crate::health::{HealthCheck, HealthConfig, HealthState, HealthTracker};
+use crate::instance::InstanceId;
+use crate::pusher::PolicyPusher;
+
+/// Health of a monitored instance.
+#[derive(Debug, Clone)]
+pub struct InstanceHealth {
+    /// Instance identifier.
+    pub instance_id: InstanceId,
+
+    /// Health state after applying the thresholds.
+    pub state: HealthState,
+
+    /// Policy version reported by the last probe.
+    pub policy_version: Option<String>,
+
+    /// Details from the last probe, if any.
+    pub message: Option<String>,
+
+    /// When the instance was last probed.
+    pub last_checked: DateTime<Utc>,
+
+    /// When the instance last answered a probe (unset if it never has).
+    pub last_seen: Option<DateTime<Utc>>,
+
+    /// Number of consecutive failed probes.
+    pub consecutive_failures: u32,
+}
+
+impl InstanceHealth {
+    /// Returns true if new deployments should leave the instance out.
+    pub fn is_excluded(&self) -> bool {
+        matches!(
+            self.state,
+            HealthState::Unhealthy | HealthState::Unreachable
+        )
+    }
+}
+
+/// Tracking state of one instance.
+#[derive(Debug)]
+struct MonitoredInstance {
+    tracker: HealthTracker,
+    last_checked: DateTime<Utc>,
+    last_seen: Option<DateTime<Utc>>,
+}
+
+/// Periodically probes all discovered instances and keeps their health.
+pub struct HealthMonitor {
+    config: HealthConfig,
+    discovery: Arc<dyn Discovery>,
+    pusher: Arc<PolicyPusher>,
+    instances: DashMap<InstanceId, MonitoredInstance>,
+}
+
+impl std::fmt::Debug for HealthMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthMonitor")
+            .field("config", &self.config)
+            .field("instances", &self.instances.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl HealthMonitor {
+    /// Creates a monitor that probes the instances of `discovery` with
+    /// `pusher`.
+    pub fn new(
+        config: HealthConfig,
+        discovery: Arc<dyn Discovery>,
+        pusher: Arc<PolicyPusher>,
+    ) -> Self {
+        Self {
+            config,
+            discovery,
+            pusher,
+            instances: DashMap::new(),
+        }
+    }
+
+    /// Starts probing in the background every
+    /// [`HealthConfig::check_interval`].
+    ///
+    /// Probing stops when [`HealthMonitorHandle::shutdown`] is called or the
+    /// handle is dropped.
+    pub fn start(self: Arc<Self>) -> HealthMonitorHandle {
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.config.check_interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            loop {
+                tokio::select! {
+                    // Resolves on shutdown and when the handle is dropped
+                    _ = &mut shutdown_rx => break,
+                    _ = interval.tick() => {
+                        if let Err(e) = self.probe_all().await {
+                            tracing::warn!(error = %e, "health probe round failed");
+                        }
+                    }
+                }
+            }
+
+            tracing::debug!("health monitor stopped");
+        });
+
+        HealthMonitorHandle {
+            shutdown_tx: Some(shutdown_tx),
+            task: Some(task),
+        }
+    }
+
+    /// Probes every discovered instance once, concurrently.
+    ///
+    /// Probes that take longer than [`HealthConfig::timeout`] count as
+    /// unreachable. Instances that are no longer discovered are forgotten.
+    pub async fn probe_all(&self) -> Result<()> {
+        let instances = self.discovery.all_instances().await?;
+
+        let checks = futures::future::join_all(instances.iter().map(|instance| async {
+            tokio::time::timeout(self.config.timeout, self.pusher.health_check(instance))
+                .await
+                .unwrap_or_else(|_| {
+                    Ok(HealthCheck::unreachable(format!(
+                        "health check timed out after {:?}",
+                        self.config.timeout
+                    )))
+                })
+                .unwrap_or_else(|e| HealthCheck::unreachable(e.to_string()))
+        }))
+        .await;
+
+        let now = Utc::now();
+        for (instance, check) in instances.iter().zip(checks) {
+            let mut entry = self
+                .instances
+                .entry(instance.id.clone())
+                .or_insert_with(|| MonitoredInstance {
+                    tracker: HealthTracker::new(self.config.clone()),
+                    last_checked: now,
+                    last_seen: None,
+                });
+
+            let previous = entry.tracker.state;
+            if matches!(
+                check.state,
+                HealthState::Healthy | HealthState::Degraded | HealthState::Unhealthy
+            ) {
+                entry.last_seen = Some(now);
+            }
+            entry.last_checked = now;
+            entry.tracker.record(check);
+
+            if entry.tracker.state != previous {
+                tracing::info!(
+                    instance_id = %instance.id,
+                    from = %previous,
+                    to = %entry.tracker.state,
+                    "instance health changed"
+                );
+            }
+        }
+
+        self.instances
+            .retain(|id, _| instances.iter().any(|instance| instance.id == *id));
+
+        MetricsRegistry::global()
+            .distributor()
+            .record_instance_health(instances.iter().map(|instance| {
+                let healthy = self
+                    .instances
+                    .get(&instance.id)
+                    .is_some_and(|entry| entry.tracker.state.is_operational());
+                (instance.metadata.service.as_deref(), healthy)
+            }));
+
+        Ok(())
+    }
+
+    /// Returns the health of an instance, if it has been probed.
+    pub fn health(&self, instance_id: &str) -> Option<InstanceHealth> {
+        self.instances
+            .get(instance_id)
+            .map(|entry| Self::snapshot_of(entry.key(), entry.value()))
+    }
+
+    /// Returns the health of every probed instance.
+    pub fn snapshot(&self) -> HashMap<InstanceId, InstanceHealth> {
+        self.instances
+            .iter()
+            .map(|entry| {
+                (
+                    entry.key().clone(),
+                    Self::snapshot_of(entry.key(), entry.value()),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns true if the instance is known to be unhealthy or unreachable.
+    pub fn is_excluded(&self, instance_id: &str) -> bool {
+        self.health(instance_id)
+            .is_some_and(|health| health.is_excluded())
+    }
+
+    fn snapshot_of(instance_id: &InstanceId, monitored: &MonitoredInstance) -> InstanceHealth {
+        let last_check = monitored.tracker.last_check.as_ref();
+        InstanceHealth {
+            instance_id: instance_id.clone(),
+            state: monitored.tracker.state,
+            policy_version: last_check.and_then(|check| check.policy_version.clone()),
+            message: last_check.and_then(|check| check.message.clone()),
+            last_checked: monitored.last_checked,
+            last_seen: monitored.last_seen,
+            consecutive_failures: monitored.tracker.consecutive_failures,
+        }
+    }
+}
+
+/// Handle to a running [`HealthMonitor`].
+///
+/// Dropping the handle stops the monitor after its current probe round.
+#[derive(Debug)]
+pub struct HealthMonitorHandle {
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl HealthMonitorHandle {
+    /// Stops the monitor and waits for its current probe round to finish.
+    pub async fn shutdown(mut self) {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+    }
+
+    /// Returns true while the monitor is running.
+    pub fn is_running(&self) -> bool {
+        self.task.as_ref().is_some_and(|task| !task.is_finished())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instance::{Instance, InstanceStatus};
+    use crate::pusher::PushConfig;
+    use async_trait::async_trait;
+    use parking_lot::RwLock;
+    use std::time::{Duration, Instant};
+
+    struct MockDiscovery {
+        instances: RwLock<Vec<Instance>>,
+    }
+
+    #[async_trait]
+    impl Discovery for MockDiscovery {
+        async fn discover(&self, _service: &str) -> Result<Vec<Instance>> {
+            Ok(self.instances.read().clone())
+        }
+
+        async fn all_instances(&self) -> Result<Vec<Instance>> {
+            Ok(self.instances.read().clone())
+        }
+
+        async fn refresh(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn instance(id: &str, healthy: bool) -> Instance {
+        let mut instance = Instance::new(id, "localhost:8080");
+        instance.update_status(if healthy {
+            InstanceStatus::Healthy {
+                policy_version: Some("1.0.0".to_string()),
+                last_check: Instant::now(),
+            }
+        } else {
+            InstanceStatus::Unhealthy {
+                reason: "policy load failed".to_string(),
+                since: Instant::now(),
+            }
+        });
+        instance
+    }
+
+    fn monitor(instances: Vec<Instance>) -> (Arc<HealthMonitor>, Arc<MockDiscovery>) {
+        let discovery = Arc::new(MockDiscovery {
+            instances: RwLock::new(instances),
+        });
+        let config = HealthConfig::builder()
+            .check_interval(Duration::from_millis(10))
+            .unhealthy_threshold(2)
+            .healthy_threshold(1)
+            .build();
+        let monitor = HealthMonitor::new(
+            config,
+            discovery.clone(),
+            Arc::new(PolicyPusher::new(PushConfig::default())),
+        );
+        (Arc::new(monitor), discovery)
+    }
+
+    #[tokio::test]
+    async fn test_probe_applies_thresholds() {
+        let (monitor, _) = monitor(vec![instance("inst-1", true), instance("inst-2", false)]);
+        assert!(monitor.health("inst-1").is_none());
+
+        monitor.probe_all().await.unwrap();
+        let healthy = monitor.health("inst-1").unwrap();
+        assert_eq!(healthy.state, HealthState::Healthy);
+        assert_eq!(healthy.policy_version.as_deref(), Some("1.0.0"));
+        assert!(healthy.last_seen.is_some());
+
+        // One failure is below the unhealthy threshold
+        let unhealthy = monitor.health("inst-2").unwrap();
+        assert_eq!(unhealthy.state, HealthState::Unknown);
+        assert_eq!(unhealthy.consecutive_failures, 1);
+        assert!(!monitor.is_excluded("inst-2"));
+
+        monitor.probe_all().await.unwrap();
+        let unhealthy = monitor.health("inst-2").unwrap();
+        assert_eq!(unhealthy.state, HealthState::Unhealthy);
+        assert_eq!(unhealthy.message.as_deref(), Some("policy load failed"));
+        assert!(monitor.is_excluded("inst-2"));
+        assert!(!monitor.is_excluded("inst-1"));
+        assert!(!monitor.is_excluded("unknown"));
+    }
+
+    #[tokio::test]
+    async fn test_probe_forgets_removed_instances() {
+        let (monitor, discovery) =
+            monitor(vec![instance("inst-1", true), instance("inst-2", true)]);
+
+        monitor.probe_all().await.unwrap();
+        assert_eq!(monitor.snapshot().len(), 2);
+
+        discovery.instances.write().pop();
+        monitor.probe_all().await.unwrap();
+        let snapshot = monitor.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert!(snapshot.contains_key("inst-1"));
+    }
+
+    #[tokio::test]
+    async fn test_monitor_runs_until_shutdown() {
+        let (monitor, _) = monitor(vec![instance("inst-1", false)]);
+
+        let handle = monitor.clone().start();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !monitor.is_excluded("inst-1") {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("instance never marked unhealthy");
+        assert!(handle.is_running());
+
+        handle.shutdown().await;
+        assert_eq!(Arc::strong_count(&monitor), 1);
+    }
+
+    #[tokio::test]
+    async fn test_monitor_stops_when_handle_dropped() {
+        let (monitor, _) = monitor(vec![instance("inst-1", true)]);
+
+        drop(monitor.clone().start());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while Arc::strong_count(&monitor) > 1 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("monitor task did not stop");
+    }
+}
@@ -0,0 +1,188 @@
+//! OpenTelemetry tracing of deployments.
+//!
+//! With the `eunomia-otel` feature, every
+//! [`Distributor::deploy`](crate::Distributor::deploy) call is traced as a
+//! [`DEPLOY_SPAN`] span, with a [`PUSH_INSTANCE_SPAN`] child span for each
+//! push to an instance. Spans are created with the tracer named
+//! [`TRACER_NAME`] from the global tracer provider, so they are exported
+//! wherever the application's provider sends them.
+//!
+//! The trace context of a `DeployPolicy` call is read from its gRPC
+//! metadata with the global text map propagator, so deployments join the
+//! caller's trace; clients add it with [`inject_context`].
+//!
+//! The metadata carriers are implemented here on the `opentelemetry`
+//! propagation API rather than taken from `tonic-opentelemetry`, which the
+//! offline build cannot fetch.
+
+use std::future::Future;
+
+use opentelemetry::global;
+use opentelemetry::propagation::{Extractor, Injector};
+use opentelemetry::trace::{FutureExt, Status, TraceContextExt, Tracer};
+use opentelemetry::{Context, KeyValue};
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
+
+use crate::error::Result;
+use crate::instance::Instance;
+use crate::pusher::PushResult;
+use crate::strategy::StrategyType;
+use crate::DeploymentResult;
+
+/// Name of the tracer spans are created with.
+pub const TRACER_NAME: &str = "eunomia-distributor";
+
+/// Span of a [`Distributor::deploy`](crate::Distributor::deploy) call.
+pub const DEPLOY_SPAN: &str = "eunomia.deploy";
+
+/// Span of a push to a single instance, a child of [`DEPLOY_SPAN`].
+pub const PUSH_INSTANCE_SPAN: &str = "eunomia.push_instance";
+
+/// Returns the trace context carried in gRPC request metadata.
+///
+/// Without a propagated context, the current context is returned.
+pub fn extract_context(metadata: &MetadataMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&MetadataExtractor(metadata)))
+}
+
+/// Adds the trace context `cx` to gRPC request metadata.
+pub fn inject_context(cx: &Context, metadata: &mut MetadataMap) {
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(cx, &mut MetadataInjector(metadata));
+    });
+}
+
+/// Reads propagated fields from gRPC metadata.
+struct MetadataExtractor<'a>(&'a MetadataMap);
+
+impl Extractor for MetadataExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0
+            .keys()
+            .filter_map(|key| match key {
+                tonic::metadata::KeyRef::Ascii(key) => Some(key.as_str()),
+                tonic::metadata::KeyRef::Binary(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Writes propagated fields to gRPC metadata.
+struct MetadataInjector<'a>(&'a mut MetadataMap);
+
+impl Injector for MetadataInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(key), Ok(value)) = (
+            MetadataKey::from_bytes(key.as_bytes()),
+            MetadataValue::try_from(value),
+        ) {
+            self.0.insert(key, value);
+        }
+    }
+}
+
+/// Result of a traced operation.
+pub(crate) trait Traced {
+    /// Records the result on the span of the operation.
+    fn record(&self, cx: &Context);
+}
+
+impl Traced for Result<DeploymentResult> {
+    fn record(&self, cx: &Context) {
+        let span = cx.span();
+        match self {
+            Ok(result) => {
+                span.set_attribute(KeyValue::new(
+                    "eunomia.deployment_id",
+                    result.deployment_id.clone(),
+                ));
+                span.set_attribute(KeyValue::new(
+                    "eunomia.successful",
+                    count(result.successful),
+                ));
+                span.set_attribute(KeyValue::new("eunomia.failed", count(result.failed)));
+                if !result.is_fully_successful() {
+                    span.set_status(Status::error(format!(
+                        "{} instance(s) failed",
+                        result.failed
+                    )));
+                }
+            }
+            Err(e) => span.set_status(Status::error(e.to_string())),
+        }
+    }
+}
+
+impl Traced for Result<PushResult> {
+    fn record(&self, cx: &Context) {
+        let span = cx.span();
+        match self {
+            Ok(result) => {
+                span.set_attribute(KeyValue::new(
+                    "eunomia.attempts",
+                    i64::from(result.attempts),
+                ));
+                if let Some(error) = &result.error {
+                    span.set_status(Status::error(error.clone()));
+                }
+            }
+            Err(e) => span.set_status(Status::error(e.to_string())),
+        }
+    }
+}
+
+/// Runs `operation` in a new span named `name`, a child of the current
+/// context when it is first polled.
+pub(crate) async fn in_span<F>(
+    name: &'static str,
+    attributes: Vec<KeyValue>,
+    operation: F,
+) -> F::Output
+where
+    F: Future,
+    F::Output: Traced,
+{
+    let tracer = global::tracer(TRACER_NAME);
+    let span = tracer
+        .span_builder(name)
+        .with_attributes(attributes)
+        .start(&tracer);
+    let cx = Context::current_with_span(span);
+
+    let output = operation.with_context(cx.clone()).await;
+    output.record(&cx);
+    cx.span().end();
+    output
+}
+
+/// Converts an instance count to an attribute value.
+fn count(n: usize) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+/// Returns the attributes of a [`DEPLOY_SPAN`].
+pub(crate) fn deploy_attributes(
+    service: &str,
+    version: &str,
+    strategy: StrategyType,
+) -> Vec<KeyValue> {
+    vec![
+        KeyValue::new("eunomia.service", service.to_string()),
+        KeyValue::new("eunomia.version", version.to_string()),
+        KeyValue::new("eunomia.strategy", strategy.to_string()),
+    ]
+}
+
+/// Returns the attributes of a [`PUSH_INSTANCE_SPAN`].
+pub(crate) fn push_attributes(instance: &Instance, service: &str, version: &str) -> Vec<KeyValue> {
+    vec![
+        KeyValue::new("eunomia.service", service.to_string()),
+        KeyValue::new("eunomia.version", version.to_string()),
+        KeyValue::new("eunomia.instance_id", instance.id.clone()),
+        KeyValue::new("eunomia.endpoint", instance.endpoint.to_string()),
+    ]
+}
@@ -2,12 +2,42 @@
 //!
 //! This module provides the client for pushing policy bundles
 //! to Archimedes instances via gRPC.
+//!
+//! A push only counts as successful once the instance reports the pushed
+//! version as active, as queried through the transport with
+//! [`PushTransport::active_version`]: an instance can accept a bundle and
+//! still fail to activate it.
+//!
+//! Transient failures, such as connection errors and `UNAVAILABLE` or
+//! `DEADLINE_EXCEEDED` statuses while a pod restarts, are retried with
+//! exponential backoff and jitter. Bundles the instance rejects are not
+//! retried.
+//!
+//! [`GrpcTransport`] pushes over gRPC, keeping one channel per instance
+//! with HTTP/2 keepalive pings ([`PushConfig::keepalive`]) so that idle
+//! connections behind NAT or load balancers are not dropped silently. It is
+//! the transport of [`PolicyPusher::new`]; [`SimulatedTransport`] stands in
+//! for it where there are no real instances to push to.
+//!
+//! Bundles are checked against [`PushConfig::bundle_limits`] with
+//! [`PolicyPusher::check_bundle`] before they are pushed, so a bundle that
+//! slipped past the limits at build time never reaches an instance.
 
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use async_trait::async_trait;
+use eunomia_core::{Bundle, BundleLimits, BundleSizes};
+use parking_lot::RwLock;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+
 use crate::error::{DistributorError, Result};
-use crate::health::HealthCheck;
-use crate::instance::Instance;
+use crate::health::{CircuitBreaker, CircuitBreakerConfig, CircuitState, HealthCheck};
+use crate::instance::{Instance, InstanceId};
 use eunomia_metrics::MetricsRegistry;
 
 /// Configuration for the policy pusher.
@@ -19,24 +49,91 @@ pub struct PushConfig {
     /// Request timeout.
     pub request_timeout: Duration,
 
-    /// Number of retries for failed pushes.
+    /// Maximum number of push attempts per instance, the first one
+    /// included.
     pub max_retries: u32,
 
-    /// Delay between retries.
+    /// Delay before the first retry, doubled for every further retry.
     pub retry_delay: Duration,
 
+    /// Upper bound of the delay between retries.
+    pub max_retry_delay: Duration,
+
+    /// Fraction (`0.0..=1.0`) of each retry delay that is randomly taken
+    /// off, so instances restarted together are not retried in lockstep.
+    pub retry_jitter: f64,
+
+    /// Overall time allowed for pushing to an instance, retries included.
+    pub push_deadline: Option<Duration>,
+
+    /// Maximum number of instances pushed to at the same time within a
+    /// deployment or deployment batch.
+    pub max_concurrent_pushes: usize,
+
     /// Enable compression for policy bundles.
     pub compression_enabled: bool,
+
+    /// How long to wait for an instance to report the pushed version.
+    pub activation_timeout: Duration,
+
+    /// Delay between activation status checks.
+    pub activation_poll_interval: Duration,
+
+    /// Per-instance circuit breaker settings.
+    pub circuit_breaker: CircuitBreakerConfig,
+
+    /// Size and complexity limits bundles must stay within to be pushed.
+    pub bundle_limits: BundleLimits,
+
+    /// HTTP/2 keepalive of gRPC push connections (disabled when unset).
+    pub keepalive: Option<KeepaliveConfig>,
+}
+
+/// HTTP/2 keepalive settings of gRPC push connections.
+///
+/// Pings keep long-lived connections to instances alive through NAT
+/// gateways and load balancers that drop idle connections, and detect dead
+/// connections before a push is sent over them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepaliveConfig {
+    /// Interval between keepalive pings.
+    pub interval: Duration,
+
+    /// How long to wait for a ping to be acknowledged before the
+    /// connection is closed.
+    pub timeout: Duration,
+
+    /// Whether pings are sent while no push is in flight.
+    pub permit_without_calls: bool,
+}
+
+impl Default for KeepaliveConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(10),
+            permit_without_calls: true,
+        }
+    }
 }
 
 impl Default for PushConfig {
     fn default() -> Self {
         Self {
-            connect_timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
             request_timeout: Duration::from_secs(30),
             max_retries: 3,
             retry_delay: Duration::from_millis(500),
+            max_retry_delay: Duration::from_secs(10),
+            retry_jitter: 0.2,
+            push_deadline: Some(Duration::from_secs(120)),
+            max_concurrent_pushes: 20,
             compression_enabled: true,
+            activation_timeout: Duration::from_secs(30),
+            activation_poll_interval: Duration::from_secs(1),
+            circuit_breaker: CircuitBreakerConfig::default(),
+            bundle_limits: BundleLimits::default(),
+            keepalive: Some(KeepaliveConfig::default()),
         }
     }
 }
@@ -46,6 +143,40 @@ impl PushConfig {
     pub fn builder() -> PushConfigBuilder {
         PushConfigBuilder::default()
     }
+
+    /// Returns the delay before retrying after failed attempt `attempt`
+    /// (1-based), given a random `sample` in `0.0..1.0` for the jitter.
+    #[allow(clippy::cast_possible_truncation)]
+    fn retry_backoff(&self, attempt: u32, sample: f64) -> Duration {
+        let base_ms = self.retry_delay.as_millis() as u64;
+        let backoff_ms = base_ms
+            .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_retry_delay.as_millis() as u64);
+        let jitter = self.retry_jitter.clamp(0.0, 1.0) * sample;
+        Duration::from_millis(backoff_ms).mul_f64(1.0 - jitter)
+    }
+
+    /// Returns a gRPC endpoint for `uri` with the connect timeout and
+    /// keepalive settings applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DistributorError::InvalidConfig`] if `uri` is not a valid
+    /// URI.
+    pub fn grpc_endpoint(&self, uri: &str) -> Result<Endpoint> {
+        let mut endpoint = Endpoint::from_shared(uri.to_string())
+            .map_err(|e| DistributorError::InvalidConfig {
+                reason: format!("invalid instance endpoint '{uri}': {e}"),
+            })?
+            .connect_timeout(self.connect_timeout);
+        if let Some(keepalive) = &self.keepalive {
+            endpoint = endpoint
+                .http2_keep_alive_interval(keepalive.interval)
+                .keep_alive_timeout(keepalive.timeout)
+                .keep_alive_while_idle(keepalive.permit_without_calls);
+        }
+        Ok(endpoint)
+    }
 }
 
 /// Builder for `PushConfig`.
@@ -55,7 +186,16 @@ pub struct PushConfigBuilder {
     request_timeout: Option<Duration>,
     max_retries: Option<u32>,
     retry_delay: Option<Duration>,
+    max_retry_delay: Option<Duration>,
+    retry_jitter: Option<f64>,
+    push_deadline: Option<Duration>,
+    max_concurrent_pushes: Option<usize>,
     compression_enabled: Option<bool>,
+    activation_timeout: Option<Duration>,
+    activation_poll_interval: Option<Duration>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    bundle_limits: Option<BundleLimits>,
+    keepalive: Option<KeepaliveConfig>,
 }
 
 impl PushConfigBuilder {
@@ -71,24 +211,78 @@ impl PushConfigBuilder {
         self
     }
 
-    /// Sets the maximum number of retries.
+    /// Sets the maximum number of push attempts.
     pub fn max_retries(mut self, retries: u32) -> Self {
         self.max_retries = Some(retries);
         self
     }
 
-    /// Sets the retry delay.
+    /// Sets the delay before the first retry.
     pub fn retry_delay(mut self, delay: Duration) -> Self {
         self.retry_delay = Some(delay);
         self
     }
 
+    /// Sets the upper bound of the delay between retries.
+    pub fn max_retry_delay(mut self, delay: Duration) -> Self {
+        self.max_retry_delay = Some(delay);
+        self
+    }
+
+    /// Sets the fraction of each retry delay that is randomly taken off.
+    pub fn retry_jitter(mut self, jitter: f64) -> Self {
+        self.retry_jitter = Some(jitter);
+        self
+    }
+
+    /// Sets the overall time allowed for pushing to an instance.
+    pub fn push_deadline(mut self, deadline: Duration) -> Self {
+        self.push_deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the maximum number of instances pushed to at the same time.
+    pub fn max_concurrent_pushes(mut self, limit: usize) -> Self {
+        self.max_concurrent_pushes = Some(limit);
+        self
+    }
+
     /// Enables or disables compression.
     pub fn compression(mut self, enabled: bool) -> Self {
         self.compression_enabled = Some(enabled);
         self
     }
 
+    /// Sets how long to wait for an instance to report the pushed version.
+    pub fn activation_timeout(mut self, timeout: Duration) -> Self {
+        self.activation_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the delay between activation status checks.
+    pub fn activation_poll_interval(mut self, interval: Duration) -> Self {
+        self.activation_poll_interval = Some(interval);
+        self
+    }
+
+    /// Sets the per-instance circuit breaker settings.
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Sets the bundle size and complexity limits.
+    pub fn bundle_limits(mut self, limits: BundleLimits) -> Self {
+        self.bundle_limits = Some(limits);
+        self
+    }
+
+    /// Sets the HTTP/2 keepalive of gRPC push connections.
+    pub fn keepalive(mut self, config: KeepaliveConfig) -> Self {
+        self.keepalive = Some(config);
+        self
+    }
+
     /// Builds the configuration.
     pub fn build(self) -> PushConfig {
         let defaults = PushConfig::default();
@@ -97,10 +291,306 @@ impl PushConfigBuilder {
             request_timeout: self.request_timeout.unwrap_or(defaults.request_timeout),
             max_retries: self.max_retries.unwrap_or(defaults.max_retries),
             retry_delay: self.retry_delay.unwrap_or(defaults.retry_delay),
+            max_retry_delay: self.max_retry_delay.unwrap_or(defaults.max_retry_delay),
+            retry_jitter: self.retry_jitter.unwrap_or(defaults.retry_jitter),
+            push_deadline: self.push_deadline.or(defaults.push_deadline),
+            max_concurrent_pushes: self
+                .max_concurrent_pushes
+                .unwrap_or(defaults.max_concurrent_pushes),
             compression_enabled: self
                 .compression_enabled
                 .unwrap_or(defaults.compression_enabled),
+            activation_timeout: self
+                .activation_timeout
+                .unwrap_or(defaults.activation_timeout),
+            activation_poll_interval: self
+                .activation_poll_interval
+                .unwrap_or(defaults.activation_poll_interval),
+            circuit_breaker: self.circuit_breaker.unwrap_or(defaults.circuit_breaker),
+            bundle_limits: self.bundle_limits.unwrap_or(defaults.bundle_limits),
+            keepalive: self.keepalive.or(defaults.keepalive),
+        }
+    }
+}
+
+/// Sends policy bundles to instances.
+///
+/// [`PolicyPusher`] handles retries, activation checks and circuit breaking;
+/// a transport only performs a single attempt.
+#[async_trait]
+pub trait PushTransport: Send + Sync {
+    /// Sends a policy version to an instance.
+    ///
+    /// Errors for which [`DistributorError::is_retryable`] is true are
+    /// retried.
+    async fn send(&self, instance: &Instance, update: &PolicyUpdate) -> Result<()>;
+
+    /// Returns the policy version active on an instance, or `None` if it
+    /// has none loaded.
+    async fn active_version(&self, instance: &Instance) -> Result<Option<String>>;
+}
+
+/// Policy update pushed to an instance, as sent in a
+/// `PolicyReceiver.UpdatePolicy` call.
+///
+/// Bundle fields are left empty when the bundle is not known to the
+/// distributor, such as when no registry is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyUpdate {
+    /// Service name.
+    pub service: String,
+    /// Policy version to activate.
+    pub version: String,
+    /// OCI reference the instance downloads the bundle from.
+    pub bundle_url: String,
+    /// SHA-256 checksum of the bundle contents.
+    pub bundle_checksum: String,
+    /// Bundle manifest, as JSON.
+    pub manifest: Vec<u8>,
+    /// Bundle signatures, as JSON.
+    pub signature: Vec<u8>,
+    /// Deployment the update belongs to, for correlation.
+    pub deployment_id: String,
+}
+
+impl PolicyUpdate {
+    /// Creates an update activating `version` of `service`.
+    pub fn new(service: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            version: version.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the deployment the update belongs to.
+    #[must_use]
+    pub fn with_deployment_id(mut self, deployment_id: impl Into<String>) -> Self {
+        self.deployment_id = deployment_id.into();
+        self
+    }
+}
+
+/// Transport simulating pushes, for running without real instances.
+///
+/// Pushes to instances marked unreachable fail; all others succeed and
+/// activate the pushed version at once. Instances not pushed to yet report
+/// the policy version of their status.
+#[derive(Debug, Default)]
+pub struct SimulatedTransport {
+    activated: RwLock<HashMap<InstanceId, String>>,
+}
+
+#[async_trait]
+impl PushTransport for SimulatedTransport {
+    async fn send(&self, instance: &Instance, update: &PolicyUpdate) -> Result<()> {
+        // Check if instance is reachable first
+        if let crate::instance::InstanceStatus::Unreachable { last_error, .. } = &instance.status {
+            return Err(DistributorError::InstanceUnreachable {
+                instance_id: instance.id.clone(),
+                endpoint: instance.endpoint.to_uri(),
+                reason: last_error.clone(),
+            });
+        }
+
+        // Simulate connection timeout
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        tracing::debug!(
+            instance_id = %instance.id,
+            endpoint = %instance.endpoint.to_uri(),
+            "simulated push"
+        );
+
+        self.activated
+            .write()
+            .insert(instance.id.clone(), update.version.clone());
+        Ok(())
+    }
+
+    async fn active_version(&self, instance: &Instance) -> Result<Option<String>> {
+        if let Some(version) = self.activated.read().get(&instance.id) {
+            return Ok(Some(version.clone()));
         }
+        Ok(match &instance.status {
+            crate::instance::InstanceStatus::Healthy { policy_version, .. } => {
+                policy_version.clone()
+            }
+            _ => None,
+        })
+    }
+}
+
+/// gRPC path of the instance's `PolicyReceiver.UpdatePolicy` call.
+const UPDATE_POLICY_PATH: &str = "/eunomia.control.v1.PolicyReceiver/UpdatePolicy";
+
+/// gRPC path of the instance's `PolicyReceiver.GetCurrentPolicy` call.
+const GET_CURRENT_POLICY_PATH: &str = "/eunomia.control.v1.PolicyReceiver/GetCurrentPolicy";
+
+/// `eunomia.control.v1.UpdatePolicyRequest`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct UpdatePolicyMessage {
+    /// Service name.
+    #[prost(string, tag = "1")]
+    service: String,
+    /// Policy version to activate.
+    #[prost(string, tag = "2")]
+    version: String,
+    /// Bundle download URL.
+    #[prost(string, tag = "3")]
+    bundle_url: String,
+    /// Bundle checksum for verification.
+    #[prost(string, tag = "4")]
+    bundle_checksum: String,
+    /// Bundle manifest.
+    #[prost(bytes = "vec", tag = "5")]
+    manifest: Vec<u8>,
+    /// Bundle signature.
+    #[prost(bytes = "vec", tag = "6")]
+    signature: Vec<u8>,
+    /// Deployment ID for correlation.
+    #[prost(string, tag = "7")]
+    deployment_id: String,
+}
+
+impl From<&PolicyUpdate> for UpdatePolicyMessage {
+    fn from(update: &PolicyUpdate) -> Self {
+        Self {
+            service: update.service.clone(),
+            version: update.version.clone(),
+            bundle_url: update.bundle_url.clone(),
+            bundle_checksum: update.bundle_checksum.clone(),
+            manifest: update.manifest.clone(),
+            signature: update.signature.clone(),
+            deployment_id: update.deployment_id.clone(),
+        }
+    }
+}
+
+/// `eunomia.control.v1.UpdatePolicyResponse`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct UpdatePolicyReply {
+    /// Whether the instance accepted the policy.
+    #[prost(bool, tag = "1")]
+    success: bool,
+    /// Reason the policy was rejected.
+    #[prost(string, tag = "2")]
+    error_message: String,
+}
+
+/// `eunomia.control.v1.GetCurrentPolicyRequest`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct GetCurrentPolicyMessage {
+    /// Service name.
+    #[prost(string, tag = "1")]
+    service: String,
+}
+
+/// `eunomia.control.v1.CurrentPolicyResponse`, without the load time and
+/// statistics.
+#[derive(Clone, PartialEq, prost::Message)]
+struct CurrentPolicyReply {
+    /// Service name.
+    #[prost(string, tag = "1")]
+    service: String,
+    /// Currently loaded version, empty if none.
+    #[prost(string, tag = "2")]
+    version: String,
+    /// Bundle checksum.
+    #[prost(string, tag = "3")]
+    bundle_checksum: String,
+}
+
+/// Transport pushing policies with the instances' `PolicyReceiver` gRPC
+/// service.
+///
+/// One channel is kept per instance endpoint and reused across pushes. It
+/// connects lazily, with the connect timeout and keepalive settings of the
+/// [`PushConfig`] the transport was created from. Request timeouts are
+/// enforced by [`PolicyPusher`].
+pub struct GrpcTransport {
+    config: PushConfig,
+    channels: RwLock<HashMap<String, Channel>>,
+}
+
+impl std::fmt::Debug for GrpcTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GrpcTransport")
+            .field("keepalive", &self.config.keepalive)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GrpcTransport {
+    /// Creates a transport with the connection settings of `config`.
+    pub fn new(config: &PushConfig) -> Self {
+        Self {
+            config: config.clone(),
+            channels: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the channel to `uri`, creating it on first use.
+    fn channel(&self, uri: &str) -> Result<Channel> {
+        if let Some(channel) = self.channels.read().get(uri) {
+            return Ok(channel.clone());
+        }
+        let channel = self.config.grpc_endpoint(uri)?.connect_lazy();
+        self.channels
+            .write()
+            .insert(uri.to_string(), channel.clone());
+        Ok(channel)
+    }
+
+    /// Returns a client for the instance, ready to send a request.
+    async fn client(&self, instance: &Instance) -> Result<tonic::client::Grpc<Channel>> {
+        let mut client = tonic::client::Grpc::new(self.channel(&instance.endpoint.to_uri())?);
+        client
+            .ready()
+            .await
+            .map_err(|e| DistributorError::Transport(e.to_string()))?;
+        Ok(client)
+    }
+}
+
+#[async_trait]
+impl PushTransport for GrpcTransport {
+    async fn send(&self, instance: &Instance, update: &PolicyUpdate) -> Result<()> {
+        let mut client = self.client(instance).await?;
+        let request = tonic::Request::new(UpdatePolicyMessage::from(update));
+        let response: tonic::Response<UpdatePolicyReply> = client
+            .unary(
+                request,
+                PathAndQuery::from_static(UPDATE_POLICY_PATH),
+                ProstCodec::default(),
+            )
+            .await?;
+
+        let reply = response.into_inner();
+        if !reply.success {
+            return Err(DistributorError::PolicyRejected {
+                instance_id: instance.id.clone(),
+                reason: reply.error_message,
+            });
+        }
+        Ok(())
+    }
+
+    async fn active_version(&self, instance: &Instance) -> Result<Option<String>> {
+        let mut client = self.client(instance).await?;
+        let request = tonic::Request::new(GetCurrentPolicyMessage {
+            service: instance.metadata.service.clone().unwrap_or_default(),
+        });
+        let response: tonic::Response<CurrentPolicyReply> = client
+            .unary(
+                request,
+                PathAndQuery::from_static(GET_CURRENT_POLICY_PATH),
+                ProstCodec::default(),
+            )
+            .await?;
+
+        let version = response.into_inner().version;
+        Ok((!version.is_empty()).then_some(version))
     }
 }
 
@@ -129,73 +619,187 @@ pub struct PushResult {
 /// Policy pusher client.
 ///
 /// Handles pushing policy bundles to individual Archimedes instances
-/// with retry logic and health checking.
+/// with retry logic, health checking and a per-instance circuit breaker.
 pub struct PolicyPusher {
     config: PushConfig,
+    transport: Arc<dyn PushTransport>,
+    circuit_breaker: CircuitBreaker,
 }
 
 impl PolicyPusher {
-    /// Creates a new policy pusher.
+    /// Creates a new policy pusher sending over gRPC through a
+    /// [`GrpcTransport`] with the connection settings of `config`.
     pub fn new(config: PushConfig) -> Self {
-        Self { config }
+        Self {
+            circuit_breaker: CircuitBreaker::new(config.circuit_breaker.clone()),
+            transport: Arc::new(GrpcTransport::new(&config)),
+            config,
+        }
+    }
+
+    /// Creates a pusher simulating pushes through a [`SimulatedTransport`].
+    pub fn simulated(config: PushConfig) -> Self {
+        Self::new(config).with_transport(Arc::new(SimulatedTransport::default()))
+    }
+
+    /// Sets the transport pushes are sent through.
+    #[must_use]
+    pub fn with_transport(mut self, transport: Arc<dyn PushTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Checks a bundle against [`PushConfig::bundle_limits`] before it is
+    /// pushed, returning its measured sizes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DistributorError::BundleLimitExceeded`] naming the exceeded
+    /// limit, or an error if the bundle archive cannot be written.
+    pub fn check_bundle(&self, bundle: &Bundle) -> Result<BundleSizes> {
+        let sizes =
+            BundleSizes::measure(bundle).map_err(|e| DistributorError::InvalidOperation {
+                reason: format!("failed to measure bundle {}: {e}", bundle.name),
+            })?;
+        sizes.check(&self.config.bundle_limits).map_err(|source| {
+            DistributorError::BundleLimitExceeded {
+                service: bundle.name.clone(),
+                version: bundle.version.clone(),
+                source,
+            }
+        })?;
+        Ok(sizes)
+    }
+
+    /// Returns the circuit breaker state of an instance.
+    pub fn circuit_state(&self, instance_id: &str) -> CircuitState {
+        self.circuit_breaker.state(instance_id)
     }
 
     /// Pushes a policy to an instance.
     ///
+    /// After the push is accepted, the instance's status is polled until it
+    /// reports `version`. If it does not within
+    /// [`PushConfig::activation_timeout`], the push fails with
+    /// `activation timeout, instance still on <version>`.
+    ///
+    /// Attempts failing with a retryable error are retried up to
+    /// [`PushConfig::max_retries`] attempts in total, with exponential
+    /// backoff, as long as [`PushConfig::push_deadline`] allows. The error
+    /// of a failed push summarizes the attempts, as in
+    /// `failed after 4 attempts: <last error>`.
+    ///
+    /// Every failed attempt counts towards the instance's circuit breaker.
+    /// While the circuit is open, the push fails immediately with
+    /// [`DistributorError::CircuitOpen`] and the instance is not contacted.
+    ///
     /// # Arguments
     ///
     /// * `instance` - Target instance
-    /// * `service` - Service name
-    /// * `version` - Policy version to push
+    /// * `update` - Policy update to push
     ///
     /// # Returns
     ///
     /// Result of the push operation.
-    pub async fn push(
+    pub async fn push(&self, instance: &Instance, update: &PolicyUpdate) -> Result<PushResult> {
+        let push = self.push_with_retries(instance, update);
+        #[cfg(feature = "eunomia-otel")]
+        let push = crate::otel::in_span(
+            crate::otel::PUSH_INSTANCE_SPAN,
+            crate::otel::push_attributes(instance, &update.service, &update.version),
+            push,
+        );
+        let result = push.await;
+
+        if !result.as_ref().is_ok_and(|r| r.success) {
+            MetricsRegistry::global()
+                .distributor()
+                .record_push_failure(&update.service, &instance.endpoint.to_string());
+        }
+
+        result
+    }
+
+    /// Pushes a policy to an instance, retrying failed attempts.
+    async fn push_with_retries(
         &self,
         instance: &Instance,
-        service: &str,
-        version: &str,
+        update: &PolicyUpdate,
     ) -> Result<PushResult> {
+        let version = update.version.as_str();
         let start = Instant::now();
+        let deadline = self.config.push_deadline.map(|deadline| start + deadline);
         let mut attempts = 0;
         let mut last_error: Option<String> = None;
+        let mut deadline_exceeded = false;
 
         while attempts < self.config.max_retries {
+            self.circuit_breaker.allow(&instance.id)?;
             attempts += 1;
 
-            match self.try_push(instance, service, version).await {
+            match self.try_push(instance, update).await {
                 Ok(()) => {
+                    let error = match self.confirm_activation(instance, version).await {
+                        Ok(()) => None,
+                        Err(reported) => Some(format!(
+                            "activation timeout, instance still on {}",
+                            reported.as_deref().unwrap_or("unknown version")
+                        )),
+                    };
+                    if let Some(ref error) = error {
+                        self.circuit_breaker.record_failure(&instance.id);
+                        tracing::warn!(
+                            instance_id = %instance.id,
+                            version = %version,
+                            error = %error,
+                            "instance did not activate pushed policy"
+                        );
+                    } else {
+                        self.circuit_breaker.record_success(&instance.id);
+                    }
                     return Ok(PushResult {
                         instance_id: instance.id.clone(),
-                        success: true,
+                        success: error.is_none(),
                         duration: start.elapsed(),
                         version: version.to_string(),
                         attempts,
-                        error: None,
+                        error,
                     });
                 }
                 Err(e) => {
+                    self.circuit_breaker.record_failure(&instance.id);
                     last_error = Some(e.to_string());
 
-                    if !e.is_retryable() {
+                    if !e.is_retryable() || attempts >= self.config.max_retries {
                         break;
                     }
 
-                    if attempts < self.config.max_retries {
-                        tracing::debug!(
-                            instance_id = %instance.id,
-                            attempt = attempts,
-                            error = %e,
-                            "push failed, retrying"
-                        );
-                        tokio::time::sleep(self.config.retry_delay).await;
+                    let delay = self.config.retry_backoff(attempts, rand::random());
+                    if deadline.is_some_and(|deadline| Instant::now() + delay >= deadline) {
+                        deadline_exceeded = true;
+                        break;
                     }
+                    tracing::warn!(
+                        instance_id = %instance.id,
+                        attempt = attempts,
+                        max_attempts = self.config.max_retries,
+                        delay_ms = delay.as_millis(),
+                        error = %e,
+                        "push failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
 
-        let error = last_error.unwrap_or_else(|| "unknown error".to_string());
+        let mut error = format!(
+            "failed after {attempts} attempt{}: {}",
+            if attempts == 1 { "" } else { "s" },
+            last_error.as_deref().unwrap_or("unknown error")
+        );
+        if deadline_exceeded {
+            error.push_str(" (push deadline exceeded)");
+        }
         Ok(PushResult {
             instance_id: instance.id.clone(),
             success: false,
@@ -212,7 +816,7 @@ impl PolicyPusher {
 
         // Simulate health check (actual gRPC implementation will be added later)
         // For now, we return a mock response based on instance state
-        let result = match &instance.status {
+        let mut result = match &instance.status {
             crate::instance::InstanceStatus::Healthy { policy_version, .. } => Ok(
                 HealthCheck::healthy(policy_version.clone(), start.elapsed()),
             ),
@@ -225,6 +829,15 @@ impl PolicyPusher {
             _ => Ok(HealthCheck::unknown()),
         };
 
+        // Operational instances report their active version themselves
+        if let Ok(check) = &mut result {
+            if check.state.is_operational() {
+                if let Ok(Some(version)) = self.active_version(instance).await {
+                    check.policy_version = Some(version);
+                }
+            }
+        }
+
         // Record health check metric
         let healthy = result.as_ref().is_ok_and(|h| h.state.is_operational());
         MetricsRegistry::global()
@@ -234,49 +847,99 @@ impl PolicyPusher {
         result
     }
 
-    /// Internal push implementation (will use gRPC in full implementation).
-    async fn try_push(&self, instance: &Instance, _service: &str, _version: &str) -> Result<()> {
-        // Check if instance is reachable first
-        if let crate::instance::InstanceStatus::Unreachable { last_error, .. } = &instance.status {
-            return Err(DistributorError::InstanceUnreachable {
-                instance_id: instance.id.clone(),
-                endpoint: instance.endpoint.to_uri(),
-                reason: last_error.clone(),
-            });
-        }
-
-        // Simulate connection timeout
-        tokio::time::sleep(Duration::from_millis(10)).await;
+    /// Polls the instance until it reports `version` as its active policy.
+    ///
+    /// Returns the last reported version if activation times out.
+    async fn confirm_activation(
+        &self,
+        instance: &Instance,
+        version: &str,
+    ) -> std::result::Result<(), Option<String>> {
+        wait_for_version(
+            version,
+            self.config.activation_timeout,
+            self.config.activation_poll_interval,
+            || async { self.active_version(instance).await.ok().flatten() },
+        )
+        .await
+    }
 
-        // In the real implementation, this would:
-        // 1. Connect to the instance's gRPC endpoint
-        // 2. Call PolicyReceiver.UpdatePolicy
-        // 3. Wait for acknowledgment
-        // 4. Handle errors appropriately
+    /// Queries the policy version active on an instance through the
+    /// transport, bounded by the request timeout.
+    async fn active_version(&self, instance: &Instance) -> Result<Option<String>> {
+        tokio::time::timeout(
+            self.config.request_timeout,
+            self.transport.active_version(instance),
+        )
+        .await
+        .map_err(|_| DistributorError::Timeout {
+            operation: format!("version query to {}", instance.id),
+        })?
+    }
 
-        tracing::debug!(
-            instance_id = %instance.id,
-            endpoint = %instance.endpoint.to_uri(),
-            "simulated push (gRPC implementation pending)"
-        );
+    /// Makes a single push attempt through the transport, bounded by the
+    /// request timeout.
+    async fn try_push(&self, instance: &Instance, update: &PolicyUpdate) -> Result<()> {
+        tokio::time::timeout(
+            self.config.request_timeout,
+            self.transport.send(instance, update),
+        )
+        .await
+        .map_err(|_| DistributorError::Timeout {
+            operation: format!("push to {}", instance.id),
+        })??;
 
         Ok(())
     }
 }
 
+/// Calls `probe` every `interval` until it returns `version`, for at most
+/// `timeout`.
+///
+/// Returns the last version `probe` returned if `version` was never reported.
+async fn wait_for_version<F, Fut>(
+    version: &str,
+    timeout: Duration,
+    interval: Duration,
+    mut probe: F,
+) -> std::result::Result<(), Option<String>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Option<String>>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        let reported = probe().await;
+        if reported.as_deref() == Some(version) {
+            return Ok(());
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(reported);
+        }
+        tokio::time::sleep(interval.min(remaining)).await;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::health::HealthState;
     use crate::instance::{Instance, InstanceMetadata, InstanceStatus};
+    use std::sync::atomic::{AtomicU32, Ordering};
 
     #[test]
     fn test_push_config_default() {
         let config = PushConfig::default();
-        assert_eq!(config.connect_timeout, Duration::from_secs(10));
+        assert_eq!(config.connect_timeout, Duration::from_secs(5));
         assert_eq!(config.request_timeout, Duration::from_secs(30));
+        assert_eq!(config.keepalive, Some(KeepaliveConfig::default()));
         assert_eq!(config.max_retries, 3);
         assert!(config.compression_enabled);
+        assert_eq!(config.activation_timeout, Duration::from_secs(30));
+        assert_eq!(config.activation_poll_interval, Duration::from_secs(1));
+        assert_eq!(config.max_concurrent_pushes, 20);
     }
 
     #[test]
@@ -298,7 +961,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_push_to_healthy_instance() {
-        let pusher = PolicyPusher::new(PushConfig::default());
+        let pusher = PolicyPusher::simulated(PushConfig::default());
 
         let mut instance = Instance::new("inst-1", "localhost:8080")
             .with_metadata(InstanceMetadata::for_service("test-service"));
@@ -307,7 +970,9 @@ mod tests {
             last_check: std::time::Instant::now(),
         });
 
-        let result = pusher.push(&instance, "test-service", "1.0.0").await;
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await;
         assert!(result.is_ok());
 
         let push_result = result.unwrap();
@@ -317,6 +982,110 @@ mod tests {
         assert!(push_result.error.is_none());
     }
 
+    #[tokio::test]
+    async fn test_push_reports_activated_version() {
+        let pusher = PolicyPusher::simulated(PushConfig::default());
+
+        let mut instance = Instance::new("inst-1", "localhost:8080");
+        instance.update_status(InstanceStatus::Healthy {
+            policy_version: Some("0.9.0".to_string()),
+            last_check: std::time::Instant::now(),
+        });
+
+        let push_result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .unwrap();
+        assert!(push_result.success);
+
+        let check = pusher.health_check(&instance).await.unwrap();
+        assert_eq!(check.policy_version, Some("1.0.0".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_version_activated() {
+        let mut reports = vec!["1.0.0", "0.9.0", "0.9.0"];
+        let result = wait_for_version(
+            "1.0.0",
+            Duration::from_secs(5),
+            Duration::from_millis(1),
+            || {
+                let reported = reports.pop().map(String::from);
+                async move { reported }
+            },
+        )
+        .await;
+
+        assert_eq!(result, Ok(()));
+        assert!(reports.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_version_timeout() {
+        let mut probes = 0;
+        let result = wait_for_version(
+            "1.0.0",
+            Duration::from_millis(30),
+            Duration::from_millis(5),
+            || {
+                probes += 1;
+                async { Some("0.9.0".to_string()) }
+            },
+        )
+        .await;
+
+        assert_eq!(result, Err(Some("0.9.0".to_string())));
+        assert!(probes > 1);
+    }
+
+    #[test]
+    fn test_push_config_builder_activation() {
+        let config = PushConfig::builder()
+            .activation_timeout(Duration::from_secs(5))
+            .activation_poll_interval(Duration::from_millis(250))
+            .build();
+
+        assert_eq!(config.activation_timeout, Duration::from_secs(5));
+        assert_eq!(config.activation_poll_interval, Duration::from_millis(250));
+        assert_eq!(config.max_retries, 3);
+    }
+
+    #[test]
+    fn test_check_bundle() {
+        use eunomia_core::{BundleLimitError, BundleLimits};
+
+        let bundle = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("users_service.authz", "package users_service.authz")
+            .add_policy("users_service.admin", "package users_service.admin")
+            .build();
+
+        let sizes = PolicyPusher::new(PushConfig::default())
+            .check_bundle(&bundle)
+            .unwrap();
+        assert_eq!(sizes.policies(), 2);
+
+        let config = PushConfig::builder()
+            .bundle_limits(BundleLimits::default().with_max_policies(1))
+            .build();
+        let err = PolicyPusher::new(config).check_bundle(&bundle).unwrap_err();
+        assert!(matches!(
+            err,
+            DistributorError::BundleLimitExceeded {
+                source: BundleLimitError::TooManyPolicies {
+                    policies: 2,
+                    limit: 1
+                },
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "bundle users-service@1.0.0 exceeds its limits: \
+             Bundle has 2 policies, over the max_policies limit of 1"
+        );
+    }
+
     #[tokio::test]
     async fn test_push_to_unreachable_instance() {
         let config = PushConfig {
@@ -324,7 +1093,7 @@ mod tests {
             retry_delay: Duration::from_millis(10),
             ..PushConfig::default()
         };
-        let pusher = PolicyPusher::new(config);
+        let pusher = PolicyPusher::simulated(config);
 
         let mut instance = Instance::new("inst-2", "localhost:9999");
         instance.update_status(InstanceStatus::Unreachable {
@@ -333,7 +1102,9 @@ mod tests {
             failure_count: 3,
         });
 
-        let result = pusher.push(&instance, "test-service", "1.0.0").await;
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await;
         assert!(result.is_ok());
 
         let push_result = result.unwrap();
@@ -341,6 +1112,273 @@ mod tests {
         assert!(push_result.error.is_some());
     }
 
+    #[allow(clippy::float_cmp)] // counters hold whole numbers
+    #[tokio::test]
+    async fn test_push_failures_are_counted_per_endpoint() {
+        let metrics = MetricsRegistry::global().distributor();
+        let pusher = PolicyPusher::simulated(PushConfig::builder().max_retries(2).build());
+
+        let healthy = Instance::new("inst-1", "localhost:8080");
+        let mut unreachable = Instance::new("inst-2", "localhost:9999");
+        unreachable.update_status(InstanceStatus::Unreachable {
+            last_error: "connection refused".to_string(),
+            since: std::time::Instant::now(),
+            failure_count: 3,
+        });
+
+        pusher
+            .push(&healthy, &PolicyUpdate::new("counted-service", "1.0.0"))
+            .await
+            .unwrap();
+        let result = pusher
+            .push(&unreachable, &PolicyUpdate::new("counted-service", "1.0.0"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.attempts, 2);
+        assert_eq!(
+            metrics.get_push_failure_count("counted-service", "localhost:9999"),
+            1.0
+        );
+        assert_eq!(
+            metrics.get_push_failure_count("counted-service", "localhost:8080"),
+            0.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_push_circuit_opens_for_failing_instance() {
+        let config = PushConfig::builder()
+            .max_retries(2)
+            .retry_delay(Duration::from_millis(1))
+            .circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 3,
+                success_threshold: 1,
+                open_duration: Duration::from_secs(30),
+            })
+            .build();
+        let pusher = PolicyPusher::simulated(config);
+
+        let mut instance = Instance::new("inst-2", "localhost:9999");
+        instance.update_status(InstanceStatus::Unreachable {
+            last_error: "connection refused".to_string(),
+            since: std::time::Instant::now(),
+            failure_count: 3,
+        });
+
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .unwrap();
+        assert_eq!(result.attempts, 2);
+        assert_eq!(pusher.circuit_state("inst-2").as_str(), "closed");
+
+        // The third consecutive failure opens the circuit and stops retrying
+        let err = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DistributorError::CircuitOpen { .. }));
+        assert_eq!(pusher.circuit_state("inst-2").as_str(), "open");
+
+        // While open, even a recovered instance is not contacted
+        instance.update_status(InstanceStatus::Healthy {
+            policy_version: None,
+            last_check: std::time::Instant::now(),
+        });
+        assert!(pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_push_probe_closes_circuit() {
+        let config = PushConfig::builder()
+            .max_retries(1)
+            .circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 1,
+                success_threshold: 1,
+                open_duration: Duration::ZERO,
+            })
+            .build();
+        let pusher = PolicyPusher::simulated(config);
+
+        let mut instance = Instance::new("inst-1", "localhost:8080");
+        instance.update_status(InstanceStatus::Unreachable {
+            last_error: "connection refused".to_string(),
+            since: std::time::Instant::now(),
+            failure_count: 1,
+        });
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(pusher.circuit_state("inst-1").as_str(), "open");
+
+        instance.update_status(InstanceStatus::Healthy {
+            policy_version: None,
+            last_check: std::time::Instant::now(),
+        });
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(pusher.circuit_state("inst-1"), CircuitState::default());
+    }
+
+    /// Transport answering each attempt (counting from 1) with `respond`.
+    struct MockTransport {
+        attempts: AtomicU32,
+        respond: fn(u32) -> Result<()>,
+    }
+
+    impl MockTransport {
+        fn new(respond: fn(u32) -> Result<()>) -> Arc<Self> {
+            Arc::new(Self {
+                attempts: AtomicU32::new(0),
+                respond,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl PushTransport for MockTransport {
+        async fn send(&self, _instance: &Instance, _update: &PolicyUpdate) -> Result<()> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst) + 1;
+            (self.respond)(attempt)
+        }
+
+        async fn active_version(&self, _instance: &Instance) -> Result<Option<String>> {
+            // Every test pushes 1.0.0, which accepted pushes activate
+            Ok(Some("1.0.0".to_string()))
+        }
+    }
+
+    fn retrying_pusher(transport: Arc<MockTransport>) -> PolicyPusher {
+        let config = PushConfig::builder()
+            .max_retries(4)
+            .retry_delay(Duration::from_millis(1))
+            .activation_timeout(Duration::ZERO)
+            .circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 10,
+                ..CircuitBreakerConfig::default()
+            })
+            .build();
+        PolicyPusher::new(config).with_transport(transport)
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let config = PushConfig::builder()
+            .retry_delay(Duration::from_millis(100))
+            .max_retry_delay(Duration::from_millis(350))
+            .retry_jitter(0.5)
+            .build();
+
+        let delays: Vec<_> = (1..=4).map(|n| config.retry_backoff(n, 0.0)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 350, 350].map(Duration::from_millis).to_vec()
+        );
+        // Jitter takes off at most the configured fraction
+        assert_eq!(config.retry_backoff(2, 0.5), Duration::from_millis(150));
+        assert_eq!(config.retry_backoff(1, 1.0), Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_push_retries_transient_failures() {
+        let transport = MockTransport::new(|attempt| match attempt {
+            1 => Err(tonic::Status::unavailable("pod restarting").into()),
+            2 => Err(DistributorError::Connection(
+                crate::error::ConnectionError::ConnectionRefused {
+                    endpoint: "localhost:8080".to_string(),
+                },
+            )),
+            _ => Ok(()),
+        });
+        let pusher = retrying_pusher(transport.clone());
+        let instance = Instance::new("inst-1", "localhost:8080");
+
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.attempts, 3);
+        assert_eq!(transport.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_push_summarizes_exhausted_attempts() {
+        let transport =
+            MockTransport::new(|_| Err(tonic::Status::deadline_exceeded("no answer").into()));
+        let pusher = retrying_pusher(transport);
+        let instance = Instance::new("inst-1", "localhost:8080");
+
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(result.attempts, 4);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("failed after 4 attempts: instance returned DeadlineExceeded: no answer")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_push_does_not_retry_rejections() {
+        let rejections: [fn(u32) -> Result<()>; 2] = [
+            |_| Err(tonic::Status::invalid_argument("malformed bundle").into()),
+            |_| {
+                Err(DistributorError::PolicyRejected {
+                    instance_id: "inst-1".to_string(),
+                    reason: "signature verification failed".to_string(),
+                })
+            },
+        ];
+
+        for respond in rejections {
+            let transport = MockTransport::new(respond);
+            let pusher = retrying_pusher(transport.clone());
+            let instance = Instance::new("inst-1", "localhost:8080");
+
+            let result = pusher
+                .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+                .await
+                .unwrap();
+            assert_eq!(result.attempts, 1);
+            assert_eq!(transport.attempts.load(Ordering::SeqCst), 1);
+            assert!(result
+                .error
+                .unwrap()
+                .starts_with("failed after 1 attempt: "));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_push_stops_at_deadline() {
+        let config = PushConfig::builder()
+            .max_retries(5)
+            .retry_delay(Duration::from_secs(10))
+            .push_deadline(Duration::from_secs(1))
+            .build();
+        let transport = MockTransport::new(|_| Err(tonic::Status::unavailable("down").into()));
+        let pusher = PolicyPusher::new(config).with_transport(transport);
+        let instance = Instance::new("inst-1", "localhost:8080");
+
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("test-service", "1.0.0"))
+            .await
+            .unwrap();
+        assert_eq!(result.attempts, 1);
+        assert!(result.error.unwrap().ends_with("(push deadline exceeded)"));
+    }
+
     #[tokio::test]
     async fn test_health_check_healthy_instance() {
         let pusher = PolicyPusher::new(PushConfig::default());
@@ -418,4 +1456,241 @@ mod tests {
         assert_eq!(result.attempts, 3);
         assert!(result.error.is_some());
     }
+
+    #[test]
+    fn test_grpc_endpoint() {
+        let keepalive = KeepaliveConfig {
+            interval: Duration::from_secs(15),
+            timeout: Duration::from_secs(5),
+            permit_without_calls: false,
+        };
+        let config = PushConfig::builder().keepalive(keepalive).build();
+        assert_eq!(config.keepalive, Some(keepalive));
+
+        let endpoint = config.grpc_endpoint("http://localhost:8080").unwrap();
+        assert_eq!(endpoint.uri(), "http://localhost:8080/");
+
+        let err = config.grpc_endpoint("not a uri").unwrap_err();
+        assert!(matches!(err, DistributorError::InvalidConfig { .. }));
+    }
+
+    /// `PolicyReceiver` that answers only after ten seconds.
+    #[derive(Clone)]
+    struct SlowReceiver;
+
+    impl tonic::server::NamedService for SlowReceiver {
+        const NAME: &'static str = "eunomia.control.v1.PolicyReceiver";
+    }
+
+    impl tower::Service<http::Request<tonic::body::BoxBody>> for SlowReceiver {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = std::pin::Pin<
+            Box<
+                dyn std::future::Future<Output = std::result::Result<Self::Response, Self::Error>>
+                    + Send,
+            >,
+        >;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::result::Result<(), Self::Error>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _request: http::Request<tonic::body::BoxBody>) -> Self::Future {
+            Box::pin(async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok(tonic::Status::unavailable("too slow").into_http())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grpc_push_request_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming =
+            tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(SlowReceiver)
+                .serve_with_incoming(incoming),
+        );
+
+        let config = PushConfig::builder()
+            .request_timeout(Duration::from_millis(200))
+            .build();
+        let pusher = PolicyPusher::new(config);
+        let instance = Instance::new("inst-1", addr.to_string());
+
+        let started = std::time::Instant::now();
+        let err = pusher
+            .try_push(&instance, &PolicyUpdate::new("users-service", "1.0.0"))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DistributorError::Timeout { .. }), "{err:?}");
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    /// `PolicyReceiver` that accepts every update and records it, starting
+    /// on 0.9.0 and activating the updates it accepts if `activate` is set.
+    #[derive(Clone)]
+    struct RecordingReceiver {
+        updates: Arc<parking_lot::Mutex<Vec<UpdatePolicyMessage>>>,
+        active: Arc<parking_lot::Mutex<String>>,
+        activate: bool,
+    }
+
+    impl RecordingReceiver {
+        fn new(activate: bool) -> Self {
+            Self {
+                updates: Arc::default(),
+                active: Arc::new(parking_lot::Mutex::new("0.9.0".to_string())),
+                activate,
+            }
+        }
+
+        /// Serves the receiver on a local port, returning its address.
+        async fn serve(&self) -> std::net::SocketAddr {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let incoming =
+                tonic::transport::server::TcpIncoming::from_listener(listener, true, None).unwrap();
+            tokio::spawn(
+                tonic::transport::Server::builder()
+                    .add_service(self.clone())
+                    .serve_with_incoming(incoming),
+            );
+            addr
+        }
+    }
+
+    impl tonic::server::NamedService for RecordingReceiver {
+        const NAME: &'static str = "eunomia.control.v1.PolicyReceiver";
+    }
+
+    impl tonic::server::UnaryService<UpdatePolicyMessage> for RecordingReceiver {
+        type Response = UpdatePolicyReply;
+        type Future = std::future::Ready<
+            std::result::Result<tonic::Response<UpdatePolicyReply>, tonic::Status>,
+        >;
+
+        fn call(&mut self, request: tonic::Request<UpdatePolicyMessage>) -> Self::Future {
+            let update = request.into_inner();
+            if self.activate {
+                self.active.lock().clone_from(&update.version);
+            }
+            self.updates.lock().push(update);
+            std::future::ready(Ok(tonic::Response::new(UpdatePolicyReply {
+                success: true,
+                error_message: String::new(),
+            })))
+        }
+    }
+
+    impl tonic::server::UnaryService<GetCurrentPolicyMessage> for RecordingReceiver {
+        type Response = CurrentPolicyReply;
+        type Future = std::future::Ready<
+            std::result::Result<tonic::Response<CurrentPolicyReply>, tonic::Status>,
+        >;
+
+        fn call(&mut self, request: tonic::Request<GetCurrentPolicyMessage>) -> Self::Future {
+            std::future::ready(Ok(tonic::Response::new(CurrentPolicyReply {
+                service: request.into_inner().service,
+                version: self.active.lock().clone(),
+                bundle_checksum: String::new(),
+            })))
+        }
+    }
+
+    impl tower::Service<http::Request<tonic::body::BoxBody>> for RecordingReceiver {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = std::pin::Pin<
+            Box<
+                dyn std::future::Future<Output = std::result::Result<Self::Response, Self::Error>>
+                    + Send,
+            >,
+        >;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::result::Result<(), Self::Error>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, request: http::Request<tonic::body::BoxBody>) -> Self::Future {
+            let receiver = self.clone();
+            Box::pin(async move {
+                if request.uri().path() == GET_CURRENT_POLICY_PATH {
+                    let codec =
+                        ProstCodec::<CurrentPolicyReply, GetCurrentPolicyMessage>::default();
+                    return Ok(tonic::server::Grpc::new(codec)
+                        .unary(receiver, request)
+                        .await);
+                }
+                let codec = ProstCodec::<UpdatePolicyReply, UpdatePolicyMessage>::default();
+                Ok(tonic::server::Grpc::new(codec)
+                    .unary(receiver, request)
+                    .await)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grpc_push_sends_full_update() {
+        let receiver = RecordingReceiver::new(true);
+        let addr = receiver.serve().await;
+
+        let pusher = PolicyPusher::new(PushConfig::default());
+        let instance = Instance::new("inst-1", addr.to_string());
+        let update = PolicyUpdate {
+            bundle_url: "registry.example.com/users-service:1.0.0".to_string(),
+            bundle_checksum: "abc123".to_string(),
+            manifest: b"{}".to_vec(),
+            signature: b"[]".to_vec(),
+            ..PolicyUpdate::new("users-service", "1.0.0").with_deployment_id("deploy-1")
+        };
+
+        let result = pusher.push(&instance, &update).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let updates = receiver.updates.lock();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0], UpdatePolicyMessage::from(&update));
+        assert_eq!(updates[0].deployment_id, "deploy-1");
+        assert_eq!(updates[0].bundle_checksum, "abc123");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_push_fails_when_instance_never_activates() {
+        let receiver = RecordingReceiver::new(false);
+        let addr = receiver.serve().await;
+
+        let config = PushConfig::builder()
+            .activation_timeout(Duration::from_millis(300))
+            .activation_poll_interval(Duration::from_millis(50))
+            .build();
+        let pusher = PolicyPusher::new(config);
+        let instance = Instance::new("inst-1", addr.to_string())
+            .with_metadata(InstanceMetadata::for_service("users-service"));
+
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("users-service", "1.0.0"))
+            .await
+            .unwrap();
+
+        // The bundle was accepted, but the instance stayed on its old version
+        assert_eq!(receiver.updates.lock().len(), 1);
+        assert!(!result.success);
+        assert_eq!(result.attempts, 1);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("activation timeout, instance still on 0.9.0")
+        );
+    }
 }
@@ -2,13 +2,41 @@
 //!
 //! This module provides scheduling capabilities for coordinating
 //! policy deployments across multiple instances.
+//!
+//! # Scheduled Deployments
+//!
+//! A [`ScheduledDeployment`] carries [`ScheduleConstraints`]: an earliest
+//! start time, weekly [`TimeWindow`]s in which it may start, and
+//! [`FreezePeriod`]s in which it must not. The scheduler only hands out
+//! deployments whose constraints allow them to start now; see
+//! [`Distributor::start_scheduler`](crate::Distributor::start_scheduler).
+//!
+//! With [`SchedulerConfig::persistence_path`] set, the queue is written to
+//! that file on every change and read back on start, so queued deployments
+//! survive a restart.
 
 use std::collections::VecDeque;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{oneshot, RwLock};
+use tokio::task::JoinHandle;
 
 use crate::strategy::DeploymentStrategy;
 
+/// How often the scheduler checks for deployments that may start by
+/// default.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How many freezes and closed windows [`ScheduleConstraints::next_allowed`]
+/// skips before giving up.
+const MAX_SCHEDULE_STEPS: usize = 1000;
+
 /// Configuration for the deployment scheduler.
 #[derive(Debug, Clone)]
 pub struct SchedulerConfig {
@@ -20,6 +48,12 @@ pub struct SchedulerConfig {
 
     /// Enable prioritization of deployments.
     pub enable_priority: bool,
+
+    /// How often queued deployments are checked against their constraints.
+    pub poll_interval: Duration,
+
+    /// File the queue is persisted to (in memory only when unset).
+    pub persistence_path: Option<PathBuf>,
 }
 
 impl Default for SchedulerConfig {
@@ -28,6 +62,8 @@ impl Default for SchedulerConfig {
             max_concurrent: 5,
             max_queue_size: 100,
             enable_priority: true,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            persistence_path: None,
         }
     }
 }
@@ -45,6 +81,8 @@ pub struct SchedulerConfigBuilder {
     max_concurrent: Option<usize>,
     max_queue_size: Option<usize>,
     enable_priority: Option<bool>,
+    poll_interval: Option<Duration>,
+    persistence_path: Option<PathBuf>,
 }
 
 impl SchedulerConfigBuilder {
@@ -66,6 +104,19 @@ impl SchedulerConfigBuilder {
         self
     }
 
+    /// Sets how often queued deployments are checked against their
+    /// constraints.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Sets the file the queue is persisted to.
+    pub fn persistence_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.persistence_path = Some(path.into());
+        self
+    }
+
     /// Builds the configuration.
     pub fn build(self) -> SchedulerConfig {
         let defaults = SchedulerConfig::default();
@@ -73,12 +124,15 @@ impl SchedulerConfigBuilder {
             max_concurrent: self.max_concurrent.unwrap_or(defaults.max_concurrent),
             max_queue_size: self.max_queue_size.unwrap_or(defaults.max_queue_size),
             enable_priority: self.enable_priority.unwrap_or(defaults.enable_priority),
+            poll_interval: self.poll_interval.unwrap_or(defaults.poll_interval),
+            persistence_path: self.persistence_path,
         }
     }
 }
 
 /// Priority level for deployments.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DeploymentPriority {
     /// Low priority - can wait.
     Low = 0,
@@ -94,8 +148,272 @@ pub enum DeploymentPriority {
     Critical = 3,
 }
 
+/// A weekly window, in UTC, in which deployments may start.
+///
+/// A window whose end is at or before its start closes on the next day, so
+/// `22:00-02:00` spans midnight and `00:00-00:00` is the whole day.
+///
+/// Windows parse from and display as `[DAYS ]HH:MM-HH:MM`, where `DAYS` is
+/// a comma-separated list of days and day ranges:
+///
+/// ```
+/// use eunomia_distributor::scheduler::TimeWindow;
+///
+/// let window: TimeWindow = "Mon-Fri 09:00-16:00".parse().unwrap();
+/// assert_eq!(window.days.len(), 5);
+/// assert_eq!(window.to_string(), "Mon,Tue,Wed,Thu,Fri 09:00-16:00");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Days the window opens on (every day when empty).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>,
+
+    /// Time of day the window opens.
+    pub start: NaiveTime,
+
+    /// Time of day the window closes.
+    pub end: NaiveTime,
+}
+
+impl TimeWindow {
+    /// Creates a window that opens every day from `start` to `end`.
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self {
+            days: Vec::new(),
+            start,
+            end,
+        }
+    }
+
+    /// Restricts the window to the given days.
+    #[must_use]
+    pub fn on(mut self, days: impl IntoIterator<Item = Weekday>) -> Self {
+        self.days = days.into_iter().collect();
+        self
+    }
+
+    /// Returns true if the window is open at `at`.
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.next_open(at) == Some(at)
+    }
+
+    /// Returns the earliest time at or after `at` at which the window is
+    /// open.
+    pub fn next_open(&self, at: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        // A window opened yesterday may still be open.
+        let mut date = at.date_naive().pred_opt()?;
+        for _ in 0..9 {
+            if self.days.is_empty() || self.days.contains(&date.weekday()) {
+                let (start, end) = self.interval(date);
+                if at < end {
+                    return Some(start.max(at));
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    /// Returns when the window opening on `date` opens and closes.
+    fn interval(&self, date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let end_date = if self.end > self.start {
+            date
+        } else {
+            date.succ_opt().unwrap_or(date)
+        };
+        (
+            date.and_time(self.start).and_utc(),
+            end_date.and_time(self.end).and_utc(),
+        )
+    }
+}
+
+impl FromStr for TimeWindow {
+    type Err = SchedulerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| SchedulerError::InvalidSchedule {
+            reason: format!("invalid time window '{s}': {reason}"),
+        };
+        let (days, times) = match s.trim().rsplit_once(char::is_whitespace) {
+            Some((days, times)) => (parse_days(days.trim()).map_err(invalid)?, times),
+            None => (Vec::new(), s.trim()),
+        };
+        let (start, end) = times
+            .split_once('-')
+            .ok_or_else(|| invalid("expected HH:MM-HH:MM".to_string()))?;
+        let time = |t: &str| {
+            NaiveTime::parse_from_str(t, "%H:%M")
+                .map_err(|_| invalid(format!("'{t}' is not a time of day (HH:MM)")))
+        };
+
+        Ok(Self {
+            days,
+            start: time(start)?,
+            end: time(end)?,
+        })
+    }
+}
+
+/// Parses a comma-separated list of days and day ranges such as `Mon-Fri`.
+fn parse_days(s: &str) -> Result<Vec<Weekday>, String> {
+    let day = |d: &str| {
+        d.trim()
+            .parse::<Weekday>()
+            .map_err(|_| format!("'{}' is not a day of the week", d.trim()))
+    };
+    let mut days = Vec::new();
+    for part in s.split(',') {
+        if let Some((first, last)) = part.split_once('-') {
+            let (mut current, last) = (day(first)?, day(last)?);
+            while current != last {
+                days.push(current);
+                current = current.succ();
+            }
+            days.push(last);
+        } else {
+            days.push(day(part)?);
+        }
+    }
+    Ok(days)
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.days.is_empty() {
+            let days: Vec<_> = self.days.iter().map(ToString::to_string).collect();
+            write!(f, "{} ", days.join(","))?;
+        }
+        write!(
+            f,
+            "{}-{}",
+            self.start.format("%H:%M"),
+            self.end.format("%H:%M")
+        )
+    }
+}
+
+/// A period, such as a release freeze, in which no deployment may start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FreezePeriod {
+    /// When the freeze begins.
+    pub start: DateTime<Utc>,
+
+    /// When the freeze ends.
+    pub end: DateTime<Utc>,
+
+    /// Why deployments are frozen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl FreezePeriod {
+    /// Creates a freeze from `start` until `end`.
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            end,
+            reason: None,
+        }
+    }
+
+    /// Sets why deployments are frozen.
+    #[must_use]
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Returns true if the freeze is in effect at `at`.
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+/// When a scheduled deployment may start.
+///
+/// A deployment may start once `not_before` has passed, inside any of the
+/// `windows` (at any time when there are none), and outside all `freezes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleConstraints {
+    /// Earliest time the deployment may start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<DateTime<Utc>>,
+
+    /// Windows the deployment may start in.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<TimeWindow>,
+
+    /// Periods the deployment must not start in.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub freezes: Vec<FreezePeriod>,
+}
+
+impl ScheduleConstraints {
+    /// Creates constraints that allow starting at any time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the earliest time the deployment may start.
+    #[must_use]
+    pub fn not_before(mut self, at: DateTime<Utc>) -> Self {
+        self.not_before = Some(at);
+        self
+    }
+
+    /// Adds a window the deployment may start in.
+    #[must_use]
+    pub fn with_window(mut self, window: TimeWindow) -> Self {
+        self.windows.push(window);
+        self
+    }
+
+    /// Adds a period the deployment must not start in.
+    #[must_use]
+    pub fn with_freeze(mut self, freeze: FreezePeriod) -> Self {
+        self.freezes.push(freeze);
+        self
+    }
+
+    /// Returns true if the deployment may start at `at`.
+    pub fn allows(&self, at: DateTime<Utc>) -> bool {
+        self.next_allowed(at) == Some(at)
+    }
+
+    /// Returns the earliest time at or after `from` at which the deployment
+    /// may start, or `None` if the constraints never allow it.
+    pub fn next_allowed(&self, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut at = self
+            .not_before
+            .map_or(from, |not_before| not_before.max(from));
+        for _ in 0..MAX_SCHEDULE_STEPS {
+            if let Some(freeze_end) = self
+                .freezes
+                .iter()
+                .filter(|freeze| freeze.contains(at))
+                .map(|freeze| freeze.end)
+                .max()
+            {
+                at = freeze_end;
+                continue;
+            }
+            if !self.windows.is_empty() {
+                let open = self.windows.iter().filter_map(|w| w.next_open(at)).min()?;
+                if open > at {
+                    at = open;
+                    continue;
+                }
+            }
+            return Some(at);
+        }
+        None
+    }
+}
+
 /// A scheduled deployment request.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScheduledDeployment {
     /// Unique deployment ID.
     pub id: String,
@@ -112,7 +430,15 @@ pub struct ScheduledDeployment {
     /// Priority level.
     pub priority: DeploymentPriority,
 
-    /// When this deployment was queued.
+    /// When the deployment may start.
+    #[serde(default)]
+    pub constraints: ScheduleConstraints,
+
+    /// When this deployment was scheduled.
+    pub scheduled_at: DateTime<Utc>,
+
+    /// When this deployment was queued (by this process).
+    #[serde(skip, default = "std::time::Instant::now")]
     pub queued_at: std::time::Instant,
 }
 
@@ -130,6 +456,8 @@ impl ScheduledDeployment {
             version: version.into(),
             strategy,
             priority: DeploymentPriority::default(),
+            constraints: ScheduleConstraints::default(),
+            scheduled_at: Utc::now(),
             queued_at: std::time::Instant::now(),
         }
     }
@@ -139,6 +467,19 @@ impl ScheduledDeployment {
         self.priority = priority;
         self
     }
+
+    /// Sets when the deployment may start.
+    #[must_use]
+    pub fn with_constraints(mut self, constraints: ScheduleConstraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Returns when the deployment will next be allowed to start, at or
+    /// after `now`.
+    pub fn next_run_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.constraints.next_allowed(now)
+    }
 }
 
 /// Scheduler for managing deployment order and concurrency.
@@ -149,7 +490,7 @@ pub struct DeploymentScheduler {
 }
 
 impl DeploymentScheduler {
-    /// Creates a new scheduler.
+    /// Creates a new scheduler with an empty queue.
     pub fn new(config: SchedulerConfig) -> Self {
         Self {
             config,
@@ -158,7 +499,33 @@ impl DeploymentScheduler {
         }
     }
 
+    /// Creates a scheduler with the queue persisted at
+    /// [`SchedulerConfig::persistence_path`], if that file exists.
+    pub fn load(config: SchedulerConfig) -> Result<Self, SchedulerError> {
+        let queue = match &config.persistence_path {
+            Some(path) if path.exists() => read_queue(path)?,
+            _ => VecDeque::new(),
+        };
+        if !queue.is_empty() {
+            tracing::info!(queued = queue.len(), "restored scheduled deployments");
+        }
+
+        Ok(Self {
+            config,
+            queue: Arc::new(RwLock::new(queue)),
+            active_count: Arc::new(RwLock::new(0)),
+        })
+    }
+
+    /// Returns the scheduler configuration.
+    pub fn config(&self) -> &SchedulerConfig {
+        &self.config
+    }
+
     /// Enqueues a deployment.
+    ///
+    /// Fails without queuing the deployment if the queue cannot be
+    /// persisted.
     pub async fn enqueue(&self, deployment: ScheduledDeployment) -> Result<(), SchedulerError> {
         let mut queue = self.queue.write().await;
 
@@ -166,6 +533,7 @@ impl DeploymentScheduler {
             return Err(SchedulerError::QueueFull);
         }
 
+        let id = deployment.id.clone();
         if self.config.enable_priority {
             // Insert based on priority
             let pos = queue
@@ -177,18 +545,31 @@ impl DeploymentScheduler {
             queue.push_back(deployment);
         }
 
+        if let Err(e) = self.persist(&queue) {
+            queue.retain(|d| d.id != id);
+            return Err(e);
+        }
         Ok(())
     }
 
-    /// Dequeues the next deployment if capacity is available.
+    /// Dequeues the next deployment that may start now, if capacity is
+    /// available.
     pub async fn dequeue(&self) -> Option<ScheduledDeployment> {
+        self.dequeue_at(Utc::now()).await
+    }
+
+    /// Dequeues the next deployment whose constraints allow it to start at
+    /// `now`, if capacity is available.
+    pub async fn dequeue_at(&self, now: DateTime<Utc>) -> Option<ScheduledDeployment> {
         let active = *self.active_count.read().await;
         if active >= self.config.max_concurrent {
             return None;
         }
 
         let mut queue = self.queue.write().await;
-        let deployment = queue.pop_front()?;
+        let pos = queue.iter().position(|d| d.constraints.allows(now))?;
+        let deployment = queue.remove(pos)?;
+        self.persist_logged(&queue);
 
         let mut active = self.active_count.write().await;
         *active += 1;
@@ -221,19 +602,100 @@ impl DeploymentScheduler {
     pub async fn clear_queue(&self) {
         let mut queue = self.queue.write().await;
         queue.clear();
+        self.persist_logged(&queue);
     }
 
     /// Removes a specific deployment from the queue.
     pub async fn remove(&self, deployment_id: &str) -> Option<ScheduledDeployment> {
         let mut queue = self.queue.write().await;
         let pos = queue.iter().position(|d| d.id == deployment_id)?;
-        queue.remove(pos)
+        let deployment = queue.remove(pos);
+        self.persist_logged(&queue);
+        deployment
     }
 
     /// Lists all queued deployments.
     pub async fn list_queued(&self) -> Vec<ScheduledDeployment> {
         self.queue.read().await.iter().cloned().collect()
     }
+
+    /// Writes the queue to the persistence file, if one is configured.
+    fn persist(&self, queue: &VecDeque<ScheduledDeployment>) -> Result<(), SchedulerError> {
+        let Some(path) = &self.config.persistence_path else {
+            return Ok(());
+        };
+        let persistence = |source| SchedulerError::Persistence {
+            path: path.clone(),
+            source,
+        };
+
+        let json =
+            serde_json::to_vec_pretty(queue).map_err(|e| persistence(std::io::Error::other(e)))?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(persistence)?;
+        }
+        // Write a sibling file and rename it, so a crash never leaves a
+        // truncated queue behind.
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json).map_err(persistence)?;
+        std::fs::rename(&tmp, path).map_err(persistence)
+    }
+
+    /// Writes the queue to the persistence file, logging failures.
+    ///
+    /// Used where the queue already changed and the change cannot be undone.
+    fn persist_logged(&self, queue: &VecDeque<ScheduledDeployment>) {
+        if let Err(e) = self.persist(queue) {
+            tracing::warn!(error = %e, "failed to persist scheduled deployments");
+        }
+    }
+}
+
+/// Reads a queue written by [`DeploymentScheduler::persist`].
+fn read_queue(path: &Path) -> Result<VecDeque<ScheduledDeployment>, SchedulerError> {
+    let persistence = |source| SchedulerError::Persistence {
+        path: path.to_path_buf(),
+        source,
+    };
+    let json = std::fs::read(path).map_err(persistence)?;
+    serde_json::from_slice(&json).map_err(|e| persistence(std::io::Error::other(e)))
+}
+
+/// Handle to the task started by
+/// [`Distributor::start_scheduler`](crate::Distributor::start_scheduler).
+#[derive(Debug)]
+pub struct SchedulerHandle {
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl SchedulerHandle {
+    /// Wraps a scheduler task that stops when `shutdown_tx` fires or is
+    /// dropped.
+    pub(crate) fn new(shutdown_tx: oneshot::Sender<()>, task: JoinHandle<()>) -> Self {
+        Self {
+            shutdown_tx: Some(shutdown_tx),
+            task: Some(task),
+        }
+    }
+
+    /// Stops launching scheduled deployments and waits for the task to
+    /// exit.
+    ///
+    /// Deployments that were already launched keep running.
+    pub async fn shutdown(mut self) {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            let _ = shutdown_tx.send(());
+        }
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+    }
+
+    /// Returns true while the scheduler is running.
+    pub fn is_running(&self) -> bool {
+        self.task.as_ref().is_some_and(|task| !task.is_finished())
+    }
 }
 
 /// Errors that can occur during scheduling.
@@ -242,6 +704,23 @@ pub enum SchedulerError {
     /// Queue is full.
     #[error("deployment queue is full")]
     QueueFull,
+
+    /// The schedule is malformed or never allows the deployment to start.
+    #[error("invalid schedule: {reason}")]
+    InvalidSchedule {
+        /// Why the schedule is invalid.
+        reason: String,
+    },
+
+    /// The queue could not be read from or written to its file.
+    #[error("failed to persist scheduled deployments to {}: {source}", path.display())]
+    Persistence {
+        /// The persistence file.
+        path: PathBuf,
+        /// Underlying error.
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 #[cfg(test)]
@@ -474,4 +953,188 @@ mod tests {
         scheduler.complete("deploy-1").await;
         assert!(scheduler.has_capacity().await);
     }
+
+    fn at(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_time_window_parse_and_display() {
+        let window: TimeWindow = "Mon-Fri 09:00-16:00".parse().unwrap();
+        assert_eq!(
+            window.days,
+            [
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri
+            ]
+        );
+        assert_eq!(window.to_string(), "Mon,Tue,Wed,Thu,Fri 09:00-16:00");
+
+        let window: TimeWindow = "sat,Sun 22:00-02:00".parse().unwrap();
+        assert_eq!(window.days, [Weekday::Sat, Weekday::Sun]);
+        assert_eq!(window.to_string().parse::<TimeWindow>().unwrap(), window);
+
+        // Ranges wrap around the week
+        let window: TimeWindow = "Fri-Mon 00:00-00:00".parse().unwrap();
+        assert_eq!(window.days.len(), 4);
+
+        let window: TimeWindow = "09:00-16:00".parse().unwrap();
+        assert!(window.days.is_empty());
+
+        for invalid in ["Mon-Fri", "Mon-Fri 9-16", "Someday 09:00-16:00", ""] {
+            let err = invalid.parse::<TimeWindow>().unwrap_err();
+            assert!(
+                matches!(err, SchedulerError::InvalidSchedule { .. }),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_time_window_next_open() {
+        // 2026-03-06 is a Friday
+        let weekdays: TimeWindow = "Mon-Fri 09:00-16:00".parse().unwrap();
+        let friday_noon = at("2026-03-06T12:00:00Z");
+        assert!(weekdays.contains(friday_noon));
+        assert_eq!(
+            weekdays.next_open(at("2026-03-06T08:00:00Z")),
+            Some(at("2026-03-06T09:00:00Z"))
+        );
+        // Closed for the weekend
+        assert_eq!(
+            weekdays.next_open(at("2026-03-06T16:00:00Z")),
+            Some(at("2026-03-09T09:00:00Z"))
+        );
+
+        // Opened on Friday, still open early on Saturday
+        let overnight: TimeWindow = "Fri 22:00-02:00".parse().unwrap();
+        assert!(overnight.contains(at("2026-03-07T01:00:00Z")));
+        assert!(!overnight.contains(at("2026-03-07T02:00:00Z")));
+        assert_eq!(
+            overnight.next_open(at("2026-03-07T02:00:00Z")),
+            Some(at("2026-03-13T22:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn test_constraints_next_allowed() {
+        let now = at("2026-03-06T12:00:00Z");
+        assert!(ScheduleConstraints::new().allows(now));
+
+        let constraints = ScheduleConstraints::new().not_before(at("2026-03-06T13:00:00Z"));
+        assert!(!constraints.allows(now));
+        assert_eq!(
+            constraints.next_allowed(now),
+            Some(at("2026-03-06T13:00:00Z"))
+        );
+
+        // A freeze over the rest of Friday pushes the deployment past the
+        // weekend into Monday's window
+        let constraints = ScheduleConstraints::new()
+            .with_window("Mon-Fri 09:00-16:00".parse().unwrap())
+            .with_freeze(
+                FreezePeriod::new(now, at("2026-03-09T10:00:00Z")).with_reason("release freeze"),
+            );
+        assert_eq!(
+            constraints.next_allowed(now),
+            Some(at("2026-03-09T10:00:00Z"))
+        );
+
+        let constraints = ScheduleConstraints::new()
+            .with_window("Mon 09:00-10:00".parse().unwrap())
+            .with_window("Sat 09:00-10:00".parse().unwrap());
+        assert_eq!(
+            constraints.next_allowed(now),
+            Some(at("2026-03-07T09:00:00Z"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_dequeues_due_deployments() {
+        let scheduler = DeploymentScheduler::new(SchedulerConfig::default());
+        let now = at("2026-03-06T12:00:00Z");
+
+        let later = ScheduledDeployment::new(
+            "deploy-later",
+            "service",
+            "1.0.0",
+            DeploymentStrategy::immediate(),
+        )
+        .with_constraints(ScheduleConstraints::new().not_before(at("2026-03-06T13:00:00Z")));
+        scheduler.enqueue(later).await.unwrap();
+        let now_deployment = ScheduledDeployment::new(
+            "deploy-now",
+            "service",
+            "1.0.0",
+            DeploymentStrategy::immediate(),
+        );
+        scheduler.enqueue(now_deployment).await.unwrap();
+
+        assert_eq!(scheduler.dequeue_at(now).await.unwrap().id, "deploy-now");
+        assert!(scheduler.dequeue_at(now).await.is_none());
+        assert_eq!(
+            scheduler
+                .dequeue_at(at("2026-03-06T13:00:00Z"))
+                .await
+                .unwrap()
+                .id,
+            "deploy-later"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_persists_queue() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = SchedulerConfig::builder()
+            .persistence_path(dir.path().join("state/scheduled.json"))
+            .build();
+
+        let scheduler = DeploymentScheduler::load(config.clone()).unwrap();
+        let constraints = ScheduleConstraints::new()
+            .with_window("Mon-Fri 09:00-16:00".parse().unwrap())
+            .with_freeze(FreezePeriod::new(
+                at("2026-03-06T00:00:00Z"),
+                at("2026-03-07T00:00:00Z"),
+            ));
+        for id in ["deploy-1", "deploy-2"] {
+            let strategy = DeploymentStrategy::rolling(2, std::time::Duration::from_secs(30));
+            let deployment = ScheduledDeployment::new(id, "service", "1.0.0", strategy)
+                .with_constraints(constraints.clone());
+            scheduler.enqueue(deployment).await.unwrap();
+        }
+        scheduler.remove("deploy-1").await.unwrap();
+
+        // A restarted scheduler picks up the queue
+        let restored = DeploymentScheduler::load(config).unwrap();
+        let queued = restored.list_queued().await;
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].id, "deploy-2");
+        assert_eq!(queued[0].constraints, constraints);
+        assert_eq!(queued[0].strategy.batch_size(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_enqueue_fails_when_not_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        let scheduler = DeploymentScheduler::new(
+            SchedulerConfig::builder()
+                .persistence_path(file.join("scheduled.json"))
+                .build(),
+        );
+
+        let deployment = ScheduledDeployment::new(
+            "deploy-1",
+            "service",
+            "1.0.0",
+            DeploymentStrategy::immediate(),
+        );
+        let err = scheduler.enqueue(deployment).await.unwrap_err();
+        assert!(matches!(err, SchedulerError::Persistence { .. }));
+        assert_eq!(scheduler.queue_length().await, 0);
+    }
 }
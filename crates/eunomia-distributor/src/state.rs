@@ -1,17 +1,41 @@
 //! Deployment state tracking.
 //!
 //! This module tracks the state of ongoing and completed deployments.
-
-use std::collections::HashMap;
+//!
+//! A tracker created with [`DeploymentTracker::with_persistence`] writes
+//! its deployments to a JSON file after every state transition and
+//! instance result, and reads them back when created again, so a restarted
+//! distributor still knows about earlier deployments. Files are written on
+//! the blocking thread pool once the deployments are unlocked. Deployments
+//! that were still running when the distributor stopped are marked
+//! [`DeploymentState::Interrupted`], with the instances they reached, for
+//! an operator to look into.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::sync::RwLock;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
 
 use crate::error::{DistributorError, Result};
-use crate::DeploymentResult;
+use crate::{DeploymentResult, InstanceId, InstanceResultStatus, StrategyType};
+
+/// Page size used when a listing does not ask for one.
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// Largest page a listing returns, whatever the requested size.
+pub const MAX_PAGE_SIZE: usize = 500;
+
+/// Conventional name of the file deployment state is persisted to.
+pub const STATE_FILE_NAME: &str = "deployment-state.json";
 
 /// State of a deployment.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DeploymentState {
     /// Deployment has not started.
     Pending,
@@ -19,6 +43,9 @@ pub enum DeploymentState {
     /// Deployment is in progress.
     InProgress,
 
+    /// Deployment is paused between batches until resumed.
+    Paused,
+
     /// Deployment completed successfully.
     Completed,
 
@@ -30,6 +57,9 @@ pub enum DeploymentState {
 
     /// Deployment was cancelled.
     Cancelled,
+
+    /// Deployment was still running when the distributor stopped.
+    Interrupted,
 }
 
 impl DeploymentState {
@@ -37,7 +67,7 @@ impl DeploymentState {
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
-            Self::Completed | Self::Failed | Self::RolledBack | Self::Cancelled
+            Self::Completed | Self::Failed | Self::RolledBack | Self::Cancelled | Self::Interrupted
         )
     }
 
@@ -46,10 +76,12 @@ impl DeploymentState {
         match self {
             Self::Pending => "pending",
             Self::InProgress => "in_progress",
+            Self::Paused => "paused",
             Self::Completed => "completed",
             Self::Failed => "failed",
             Self::RolledBack => "rolled_back",
             Self::Cancelled => "cancelled",
+            Self::Interrupted => "interrupted",
         }
     }
 }
@@ -61,7 +93,10 @@ impl std::fmt::Display for DeploymentState {
 }
 
 /// Information about a tracked deployment.
-#[derive(Debug, Clone)]
+///
+/// The monotonic `started_at` and `ended_at` times are not persisted; they
+/// are derived from the wall-clock times when deployments are loaded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentInfo {
     /// Unique deployment ID.
     pub id: String,
@@ -72,6 +107,12 @@ pub struct DeploymentInfo {
     /// Target version.
     pub version: String,
 
+    /// Version the service ran before this deployment, if known.
+    pub previous_version: Option<String>,
+
+    /// Strategy the deployment uses.
+    pub strategy: StrategyType,
+
     /// Current state.
     pub state: DeploymentState,
 
@@ -84,21 +125,40 @@ pub struct DeploymentInfo {
     /// Number of failed instances.
     pub failed: usize,
 
+    /// Number of instances skipped because they already ran the version.
+    pub skipped: usize,
+
     /// Per-instance status.
     pub instance_status: HashMap<String, InstanceDeploymentStatus>,
 
     /// When the deployment started.
+    #[serde(skip, default = "Instant::now")]
     pub started_at: Instant,
 
     /// When the deployment ended (if terminal).
+    #[serde(skip)]
     pub ended_at: Option<Instant>,
 
+    /// Wall-clock time the deployment started.
+    pub created_at: DateTime<Utc>,
+
+    /// Wall-clock time the deployment ended (if terminal).
+    pub finished_at: Option<DateTime<Utc>>,
+
     /// Error message (if failed).
     pub error: Option<String>,
+
+    /// IDs of the keys whose signatures on the bundle were verified.
+    #[serde(default)]
+    pub signed_by: Vec<String>,
+
+    /// Instances chosen as canaries (for canary deployments).
+    #[serde(default)]
+    pub canary_instances: Vec<InstanceId>,
 }
 
 /// Status of a deployment for a single instance.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstanceDeploymentStatus {
     /// Instance ID.
     pub instance_id: String,
@@ -106,13 +166,53 @@ pub struct InstanceDeploymentStatus {
     /// Whether deployment was successful.
     pub success: bool,
 
+    /// Whether the instance was skipped because it already ran the version.
+    pub skipped: bool,
+
     /// Error message if failed.
     pub error: Option<String>,
 
     /// When this instance was updated.
+    #[serde(skip, default = "Instant::now")]
     pub updated_at: Instant,
 }
 
+/// Criteria for listing deployments. Unset fields match every deployment.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentFilter {
+    /// Only deployments of this service.
+    pub service: Option<String>,
+
+    /// Only deployments in this state.
+    pub state: Option<DeploymentState>,
+
+    /// Only deployments started at or after this time.
+    pub started_after: Option<DateTime<Utc>>,
+
+    /// Only deployments started before this time.
+    pub started_before: Option<DateTime<Utc>>,
+}
+
+impl DeploymentFilter {
+    /// Returns true if the deployment meets every criterion.
+    pub fn matches(&self, info: &DeploymentInfo) -> bool {
+        self.service.as_ref().is_none_or(|s| *s == info.service)
+            && self.state.as_ref().is_none_or(|s| *s == info.state)
+            && self.started_after.is_none_or(|t| info.created_at >= t)
+            && self.started_before.is_none_or(|t| info.created_at < t)
+    }
+}
+
+/// One page of a deployment listing.
+#[derive(Debug, Clone)]
+pub struct DeploymentPage {
+    /// Deployments on this page, newest first.
+    pub deployments: Vec<DeploymentInfo>,
+
+    /// Token for the next page, if there are more deployments.
+    pub next_page_token: Option<String>,
+}
+
 /// Tracks the state of multiple deployments.
 pub struct DeploymentTracker {
     /// Active and recent deployments.
@@ -123,16 +223,28 @@ pub struct DeploymentTracker {
 
     /// Maximum number of completed deployments to keep.
     max_history: usize,
+
+    /// File deployments are persisted to, if any.
+    persistence_path: Option<PathBuf>,
+
+    /// Number of snapshots taken for persistence.
+    snapshots: AtomicU64,
+
+    /// Number of the newest snapshot written to the persistence file.
+    persisted: Mutex<u64>,
+}
+
+/// Serialized deployments waiting to be persisted.
+struct Snapshot {
+    /// Position of the snapshot in the order of the changes.
+    number: u64,
+    json: Vec<u8>,
 }
 
 impl DeploymentTracker {
     /// Creates a new deployment tracker.
     pub fn new() -> Self {
-        Self {
-            deployments: Arc::new(RwLock::new(HashMap::new())),
-            service_deployments: Arc::new(RwLock::new(HashMap::new())),
-            max_history: 100,
-        }
+        Self::with_history_limit(100)
     }
 
     /// Creates a tracker with custom history limit.
@@ -141,7 +253,88 @@ impl DeploymentTracker {
             deployments: Arc::new(RwLock::new(HashMap::new())),
             service_deployments: Arc::new(RwLock::new(HashMap::new())),
             max_history,
+            persistence_path: None,
+            snapshots: AtomicU64::new(0),
+            persisted: Mutex::new(0),
+        }
+    }
+
+    /// Creates a tracker persisting its deployments to `path`
+    /// (conventionally [`STATE_FILE_NAME`]).
+    ///
+    /// Deployments already in the file are loaded, and those that were not
+    /// finished are marked [`DeploymentState::Interrupted`]. A file that
+    /// cannot be read is moved aside with a `.corrupt` extension and the
+    /// tracker starts empty.
+    pub fn with_persistence(path: PathBuf) -> Self {
+        let mut deployments = match read_deployments(&path) {
+            Ok(deployments) => deployments,
+            Err(e) => {
+                let corrupt = path.with_extension("corrupt");
+                tracing::warn!(
+                    path = %path.display(),
+                    moved_to = %corrupt.display(),
+                    error = %e,
+                    "failed to load deployment state"
+                );
+                let _ = std::fs::rename(&path, &corrupt);
+                HashMap::new()
+            }
+        };
+
+        let mut interrupted = 0;
+        for info in deployments.values_mut() {
+            info.started_at = instant_at(info.created_at);
+            info.ended_at = info.finished_at.map(instant_at);
+            if !info.state.is_terminal() {
+                info.error = Some(format!(
+                    "distributor stopped while the deployment was {}",
+                    info.state
+                ));
+                info.state = DeploymentState::Interrupted;
+                info.ended_at = Some(Instant::now());
+                info.finished_at = Some(Utc::now());
+                interrupted += 1;
+            }
+        }
+
+        // The current deployment of a service is the last one started
+        let mut latest: HashMap<String, &DeploymentInfo> = HashMap::new();
+        for info in deployments.values() {
+            let current = latest.entry(info.service.clone()).or_insert(info);
+            if page_key(info) > page_key(current) {
+                *current = info;
+            }
+        }
+        let service_deployments = latest
+            .into_iter()
+            .map(|(service, info)| (service, info.id.clone()))
+            .collect();
+
+        if !deployments.is_empty() {
+            tracing::info!(
+                deployments = deployments.len(),
+                interrupted,
+                "restored deployment state"
+            );
         }
+
+        if interrupted > 0 {
+            if let Err(e) =
+                serialize_deployments(&deployments).and_then(|json| write_state(&path, &json))
+            {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %e,
+                    "failed to persist deployment state"
+                );
+            }
+        }
+        let mut tracker = Self::with_history_limit(100);
+        tracker.persistence_path = Some(path);
+        tracker.deployments = Arc::new(RwLock::new(deployments));
+        tracker.service_deployments = Arc::new(RwLock::new(service_deployments));
+        tracker
     }
 
     /// Starts tracking a new deployment.
@@ -150,10 +343,12 @@ impl DeploymentTracker {
         deployment_id: &str,
         service: &str,
         version: &str,
+        strategy: StrategyType,
         total_instances: usize,
     ) -> Result<()> {
         // Check for existing deployment
         let service_deployments = self.service_deployments.read().await;
+        let mut previous_version = None;
         if let Some(existing_id) = service_deployments.get(service) {
             let deployments = self.deployments.read().await;
             if let Some(existing) = deployments.get(existing_id) {
@@ -163,6 +358,12 @@ impl DeploymentTracker {
                         deployment_id: existing_id.clone(),
                     });
                 }
+                // Only a completed deployment replaced the running version
+                previous_version = if existing.state == DeploymentState::Completed {
+                    Some(existing.version.clone())
+                } else {
+                    existing.previous_version.clone()
+                };
             }
         }
         drop(service_deployments);
@@ -172,18 +373,28 @@ impl DeploymentTracker {
             id: deployment_id.to_string(),
             service: service.to_string(),
             version: version.to_string(),
+            previous_version,
+            strategy,
             state: DeploymentState::InProgress,
             total_instances,
             successful: 0,
             failed: 0,
+            skipped: 0,
             instance_status: HashMap::new(),
             started_at: Instant::now(),
             ended_at: None,
+            created_at: Utc::now(),
+            finished_at: None,
             error: None,
+            signed_by: Vec::new(),
+            canary_instances: Vec::new(),
         };
 
         let mut deployments = self.deployments.write().await;
         deployments.insert(deployment_id.to_string(), info);
+        let snapshot = self.snapshot(&deployments);
+        drop(deployments);
+        self.persist(snapshot).await;
 
         let mut service_deployments = self.service_deployments.write().await;
         service_deployments.insert(service.to_string(), deployment_id.to_string());
@@ -208,6 +419,7 @@ impl DeploymentTracker {
         let status = InstanceDeploymentStatus {
             instance_id: instance_id.to_string(),
             success,
+            skipped: false,
             error: None,
             updated_at: Instant::now(),
         };
@@ -219,11 +431,18 @@ impl DeploymentTracker {
         } else {
             info.failed += 1;
         }
+        let snapshot = self.snapshot(&deployments);
+        drop(deployments);
+        self.persist(snapshot).await;
 
         Ok(())
     }
 
-    /// Marks a deployment as completed.
+    /// Marks a deployment as completed, recording the final result of
+    /// every instance.
+    ///
+    /// A deployment with failed instances, or halted because health
+    /// degraded, is marked as failed instead.
     pub async fn complete_deployment(
         &self,
         deployment_id: &str,
@@ -236,16 +455,43 @@ impl DeploymentTracker {
             }
         })?;
 
-        info.state = if result.failed == 0 {
+        info.state = if result.is_fully_successful() {
             DeploymentState::Completed
         } else {
             DeploymentState::Failed
         };
+        if result.health_degraded {
+            info.error = Some("health degraded after a deployment stage".to_string());
+        }
         info.successful = result.successful;
         info.failed = result.failed;
+        info.skipped = result.skipped;
         info.ended_at = Some(Instant::now());
+        info.finished_at = Some(Utc::now());
+
+        for instance in result.instance_results {
+            let status = info
+                .instance_status
+                .entry(instance.instance_id.clone())
+                .or_insert_with(|| InstanceDeploymentStatus {
+                    instance_id: instance.instance_id,
+                    success: false,
+                    skipped: false,
+                    error: None,
+                    updated_at: Instant::now(),
+                });
+            status.success = matches!(instance.status, InstanceResultStatus::Success);
+            status.skipped = matches!(instance.status, InstanceResultStatus::Skipped);
+            status.error = match instance.status {
+                InstanceResultStatus::Failed(error) => Some(error),
+                _ => None,
+            };
+        }
 
         self.cleanup_old_deployments(&mut deployments);
+        let snapshot = self.snapshot(&deployments);
+        drop(deployments);
+        self.persist(snapshot).await;
 
         Ok(())
     }
@@ -262,6 +508,48 @@ impl DeploymentTracker {
         info.state = DeploymentState::Failed;
         info.error = Some(error);
         info.ended_at = Some(Instant::now());
+        info.finished_at = Some(Utc::now());
+        let snapshot = self.snapshot(&deployments);
+        drop(deployments);
+        self.persist(snapshot).await;
+
+        Ok(())
+    }
+
+    /// Records the keys that signed the bundle of a deployment.
+    pub async fn record_signers(&self, deployment_id: &str, key_ids: Vec<String>) -> Result<()> {
+        let mut deployments = self.deployments.write().await;
+        let info = deployments.get_mut(deployment_id).ok_or_else(|| {
+            DistributorError::DeploymentNotFound {
+                deployment_id: deployment_id.to_string(),
+            }
+        })?;
+
+        info.signed_by = key_ids;
+        let snapshot = self.snapshot(&deployments);
+        drop(deployments);
+        self.persist(snapshot).await;
+
+        Ok(())
+    }
+
+    /// Records the instances chosen as canaries for a deployment.
+    pub async fn record_canaries(
+        &self,
+        deployment_id: &str,
+        instance_ids: Vec<InstanceId>,
+    ) -> Result<()> {
+        let mut deployments = self.deployments.write().await;
+        let info = deployments.get_mut(deployment_id).ok_or_else(|| {
+            DistributorError::DeploymentNotFound {
+                deployment_id: deployment_id.to_string(),
+            }
+        })?;
+
+        info.canary_instances = instance_ids;
+        let snapshot = self.snapshot(&deployments);
+        drop(deployments);
+        self.persist(snapshot).await;
 
         Ok(())
     }
@@ -281,21 +569,25 @@ impl DeploymentTracker {
         let service_deployments = self.service_deployments.read().await;
         let deployment_id = service_deployments.get(service);
 
-        let (state, current_version) = if let Some(id) = deployment_id {
+        let (state, current_version, previous_version) = if let Some(id) = deployment_id {
             let deployments = self.deployments.read().await;
             deployments
                 .get(id)
-                .map_or((DeploymentState::Pending, None), |info| {
-                    (info.state.clone(), Some(info.version.clone()))
+                .map_or((DeploymentState::Pending, None, None), |info| {
+                    (
+                        info.state.clone(),
+                        Some(info.version.clone()),
+                        info.previous_version.clone(),
+                    )
                 })
         } else {
-            (DeploymentState::Pending, None)
+            (DeploymentState::Pending, None, None)
         };
 
         Ok(crate::ServiceStatus {
             service: service.to_string(),
             current_version,
-            previous_version: None, // Would track this in a more complete implementation
+            previous_version,
             state,
             instances: Vec::new(), // Would populate from discovery
         })
@@ -311,6 +603,114 @@ impl DeploymentTracker {
             .collect()
     }
 
+    /// Lists deployments matching a filter, newest first, one page at a time.
+    ///
+    /// A `page_size` of zero uses [`DEFAULT_PAGE_SIZE`]; larger sizes are
+    /// capped at [`MAX_PAGE_SIZE`]. Pass the `next_page_token` of a page to
+    /// get the page after it. Tokens stay valid while deployments are added.
+    pub async fn list_deployments(
+        &self,
+        filter: &DeploymentFilter,
+        page_size: usize,
+        page_token: Option<&str>,
+    ) -> Result<DeploymentPage> {
+        let after = page_token.map(parse_page_token).transpose()?;
+        let page_size = match page_size {
+            0 => DEFAULT_PAGE_SIZE,
+            n => n.min(MAX_PAGE_SIZE),
+        };
+
+        let deployments = self.deployments.read().await;
+        let mut matching: Vec<_> = deployments
+            .values()
+            .filter(|d| filter.matches(d))
+            .filter(|d| after.as_ref().is_none_or(|key| page_key(d) < *key))
+            .collect();
+        matching.sort_by(|a, b| page_key(b).cmp(&page_key(a)));
+
+        let next_page_token = (matching.len() > page_size).then(|| {
+            let (time, id) = page_key(matching[page_size - 1]);
+            format!("{time}:{id}")
+        });
+        let deployments = matching.into_iter().take(page_size).cloned().collect();
+
+        Ok(DeploymentPage {
+            deployments,
+            next_page_token,
+        })
+    }
+
+    /// Pauses an in-progress deployment.
+    ///
+    /// Rolling deployments stop before their next batch until the
+    /// deployment is resumed.
+    pub async fn pause_deployment(&self, deployment_id: &str) -> Result<()> {
+        self.transition(
+            deployment_id,
+            &DeploymentState::InProgress,
+            DeploymentState::Paused,
+        )
+        .await
+    }
+
+    /// Resumes a paused deployment.
+    pub async fn resume_deployment(&self, deployment_id: &str) -> Result<()> {
+        self.transition(
+            deployment_id,
+            &DeploymentState::Paused,
+            DeploymentState::InProgress,
+        )
+        .await
+    }
+
+    /// Returns true if the deployment is paused.
+    pub async fn is_paused(&self, deployment_id: &str) -> bool {
+        let deployments = self.deployments.read().await;
+        deployments
+            .get(deployment_id)
+            .is_some_and(|d| d.state == DeploymentState::Paused)
+    }
+
+    /// Marks a deployment that failed on degraded health as rolled back.
+    pub async fn mark_rolled_back(&self, deployment_id: &str) -> Result<()> {
+        self.transition(
+            deployment_id,
+            &DeploymentState::Failed,
+            DeploymentState::RolledBack,
+        )
+        .await
+    }
+
+    /// Moves a deployment from state `from` to state `to`.
+    async fn transition(
+        &self,
+        deployment_id: &str,
+        from: &DeploymentState,
+        to: DeploymentState,
+    ) -> Result<()> {
+        let mut deployments = self.deployments.write().await;
+        let info = deployments.get_mut(deployment_id).ok_or_else(|| {
+            DistributorError::DeploymentNotFound {
+                deployment_id: deployment_id.to_string(),
+            }
+        })?;
+
+        if info.state != *from {
+            return Err(DistributorError::StateError {
+                reason: format!(
+                    "deployment {deployment_id} is {}, expected {from}",
+                    info.state
+                ),
+            });
+        }
+
+        info.state = to;
+        let snapshot = self.snapshot(&deployments);
+        drop(deployments);
+        self.persist(snapshot).await;
+        Ok(())
+    }
+
     /// Cancels a deployment.
     pub async fn cancel_deployment(&self, deployment_id: &str) -> Result<()> {
         let mut deployments = self.deployments.write().await;
@@ -328,10 +728,80 @@ impl DeploymentTracker {
 
         info.state = DeploymentState::Cancelled;
         info.ended_at = Some(Instant::now());
+        info.finished_at = Some(Utc::now());
+        let snapshot = self.snapshot(&deployments);
+        drop(deployments);
+        self.persist(snapshot).await;
 
         Ok(())
     }
 
+    /// Returns the IDs of deployments interrupted by a distributor restart,
+    /// oldest first.
+    pub async fn interrupted_deployments(&self) -> Vec<String> {
+        let deployments = self.deployments.read().await;
+        let mut interrupted: Vec<_> = deployments
+            .values()
+            .filter(|d| d.state == DeploymentState::Interrupted)
+            .collect();
+        interrupted.sort_by_key(|d| page_key(d));
+        interrupted.into_iter().map(|d| d.id.clone()).collect()
+    }
+
+    /// Serializes the deployments for [`persist`](Self::persist), if a
+    /// persistence file is configured.
+    ///
+    /// Called with the deployments locked, so snapshots are numbered in the
+    /// order of the changes.
+    fn snapshot(&self, deployments: &HashMap<String, DeploymentInfo>) -> Option<Snapshot> {
+        let path = self.persistence_path.as_ref()?;
+        match serialize_deployments(deployments) {
+            Ok(json) => Some(Snapshot {
+                number: self.snapshots.fetch_add(1, Ordering::Relaxed) + 1,
+                json,
+            }),
+            Err(e) => {
+                tracing::warn!(
+                    path = %path.display(),
+                    error = %e,
+                    "failed to serialize deployment state"
+                );
+                None
+            }
+        }
+    }
+
+    /// Writes a snapshot to the persistence file on the blocking thread
+    /// pool, logging failures.
+    ///
+    /// Called once the deployments are unlocked. A snapshot older than the
+    /// one already written is dropped, so concurrent changes coalesce into
+    /// the newest state.
+    async fn persist(&self, snapshot: Option<Snapshot>) {
+        let (Some(path), Some(snapshot)) = (&self.persistence_path, snapshot) else {
+            return;
+        };
+        let mut persisted = self.persisted.lock().await;
+        if *persisted >= snapshot.number {
+            return;
+        }
+
+        let target = path.clone();
+        let Snapshot { number, json } = snapshot;
+        let written = tokio::task::spawn_blocking(move || write_state(&target, &json))
+            .await
+            .map_err(std::io::Error::other)
+            .and_then(|result| result);
+        match written {
+            Ok(()) => *persisted = number,
+            Err(e) => tracing::warn!(
+                path = %path.display(),
+                error = %e,
+                "failed to persist deployment state"
+            ),
+        }
+    }
+
     fn cleanup_old_deployments(&self, deployments: &mut HashMap<String, DeploymentInfo>) {
         if deployments.len() <= self.max_history {
             return;
@@ -353,6 +823,59 @@ impl DeploymentTracker {
     }
 }
 
+/// Reads deployments written by [`write_state`]; a missing file holds
+/// no deployments.
+fn read_deployments(path: &Path) -> std::io::Result<HashMap<String, DeploymentInfo>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let json = std::fs::read(path)?;
+    serde_json::from_slice(&json).map_err(std::io::Error::other)
+}
+
+/// Serializes deployments for [`write_state`], keyed and sorted by ID.
+fn serialize_deployments(
+    deployments: &HashMap<String, DeploymentInfo>,
+) -> std::io::Result<Vec<u8>> {
+    let sorted: BTreeMap<_, _> = deployments.iter().collect();
+    serde_json::to_vec_pretty(&sorted).map_err(std::io::Error::other)
+}
+
+/// Writes serialized deployments to `path`.
+fn write_state(path: &Path, json: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Write a sibling file and rename it, so a crash never leaves a
+    // truncated state file behind.
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, json)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Returns the monotonic time corresponding to a past wall-clock time.
+fn instant_at(time: DateTime<Utc>) -> Instant {
+    let elapsed = (Utc::now() - time).to_std().unwrap_or_default();
+    Instant::now()
+        .checked_sub(elapsed)
+        .unwrap_or_else(Instant::now)
+}
+
+/// Sort key of a deployment in listings: start time, then ID.
+fn page_key(info: &DeploymentInfo) -> (i64, &str) {
+    (info.created_at.timestamp_micros(), &info.id)
+}
+
+/// Parses a page token produced by [`DeploymentTracker::list_deployments`].
+fn parse_page_token(token: &str) -> Result<(i64, &str)> {
+    token
+        .split_once(':')
+        .and_then(|(time, id)| Some((time.parse().ok()?, id)))
+        .ok_or_else(|| DistributorError::InvalidOperation {
+            reason: format!("invalid page token '{token}'"),
+        })
+}
+
 impl Default for DeploymentTracker {
     fn default() -> Self {
         Self::new()
@@ -367,18 +890,22 @@ mod tests {
     fn test_deployment_state_is_terminal() {
         assert!(!DeploymentState::Pending.is_terminal());
         assert!(!DeploymentState::InProgress.is_terminal());
+        assert!(!DeploymentState::Paused.is_terminal());
         assert!(DeploymentState::Completed.is_terminal());
         assert!(DeploymentState::Failed.is_terminal());
         assert!(DeploymentState::RolledBack.is_terminal());
         assert!(DeploymentState::Cancelled.is_terminal());
+        assert!(DeploymentState::Interrupted.is_terminal());
     }
 
     #[test]
     fn test_deployment_state_display() {
         assert_eq!(DeploymentState::Pending.to_string(), "pending");
         assert_eq!(DeploymentState::InProgress.to_string(), "in_progress");
+        assert_eq!(DeploymentState::Paused.to_string(), "paused");
         assert_eq!(DeploymentState::Completed.to_string(), "completed");
         assert_eq!(DeploymentState::Failed.to_string(), "failed");
+        assert_eq!(DeploymentState::Interrupted.to_string(), "interrupted");
     }
 
     #[tokio::test]
@@ -386,7 +913,13 @@ mod tests {
         let tracker = DeploymentTracker::new();
 
         let result = tracker
-            .start_deployment("deploy-1", "my-service", "1.0.0", 3)
+            .start_deployment(
+                "deploy-1",
+                "my-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                3,
+            )
             .await;
         assert!(result.is_ok());
 
@@ -403,13 +936,25 @@ mod tests {
 
         // Start first deployment
         tracker
-            .start_deployment("deploy-1", "my-service", "1.0.0", 3)
+            .start_deployment(
+                "deploy-1",
+                "my-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                3,
+            )
             .await
             .unwrap();
 
         // Try to start second deployment for same service
         let result = tracker
-            .start_deployment("deploy-2", "my-service", "2.0.0", 3)
+            .start_deployment(
+                "deploy-2",
+                "my-service",
+                "2.0.0",
+                StrategyType::Immediate,
+                3,
+            )
             .await;
 
         assert!(result.is_err());
@@ -425,7 +970,13 @@ mod tests {
         let tracker = DeploymentTracker::new();
 
         tracker
-            .start_deployment("deploy-1", "my-service", "1.0.0", 2)
+            .start_deployment(
+                "deploy-1",
+                "my-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                2,
+            )
             .await
             .unwrap();
 
@@ -453,7 +1004,13 @@ mod tests {
         let tracker = DeploymentTracker::new();
 
         tracker
-            .start_deployment("deploy-1", "my-service", "1.0.0", 2)
+            .start_deployment(
+                "deploy-1",
+                "my-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                2,
+            )
             .await
             .unwrap();
 
@@ -463,6 +1020,12 @@ mod tests {
             failed: 0,
             skipped: 0,
             instance_results: Vec::new(),
+            blue_count: 0,
+            green_count: 0,
+            dry_run: false,
+            health_degraded: false,
+            rollback_triggered: false,
+            canary_instances: Vec::new(),
         };
 
         tracker
@@ -480,7 +1043,13 @@ mod tests {
         let tracker = DeploymentTracker::new();
 
         tracker
-            .start_deployment("deploy-1", "my-service", "1.0.0", 2)
+            .start_deployment(
+                "deploy-1",
+                "my-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                2,
+            )
             .await
             .unwrap();
 
@@ -499,7 +1068,13 @@ mod tests {
         let tracker = DeploymentTracker::new();
 
         tracker
-            .start_deployment("deploy-1", "my-service", "1.0.0", 2)
+            .start_deployment(
+                "deploy-1",
+                "my-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                2,
+            )
             .await
             .unwrap();
 
@@ -509,17 +1084,49 @@ mod tests {
         assert_eq!(info.state, DeploymentState::Cancelled);
     }
 
+    #[tokio::test]
+    async fn test_tracker_pause_resume_deployment() {
+        let tracker = DeploymentTracker::new();
+
+        tracker
+            .start_deployment("deploy-1", "my-service", "1.0.0", StrategyType::Rolling, 4)
+            .await
+            .unwrap();
+        assert!(!tracker.is_paused("deploy-1").await);
+
+        tracker.pause_deployment("deploy-1").await.unwrap();
+        assert!(tracker.is_paused("deploy-1").await);
+        let status = tracker.get_service_status("my-service").await.unwrap();
+        assert_eq!(status.state, DeploymentState::Paused);
+
+        // Only in-progress deployments can be paused
+        let err = tracker.pause_deployment("deploy-1").await.unwrap_err();
+        assert!(matches!(err, DistributorError::StateError { .. }));
+
+        tracker.resume_deployment("deploy-1").await.unwrap();
+        assert!(!tracker.is_paused("deploy-1").await);
+        let info = tracker.get_deployment("deploy-1").await.unwrap();
+        assert_eq!(info.state, DeploymentState::InProgress);
+
+        // Only paused deployments can be resumed
+        let err = tracker.resume_deployment("deploy-1").await.unwrap_err();
+        assert!(matches!(err, DistributorError::StateError { .. }));
+
+        let err = tracker.pause_deployment("missing").await.unwrap_err();
+        assert!(matches!(err, DistributorError::DeploymentNotFound { .. }));
+    }
+
     #[tokio::test]
     async fn test_tracker_list_active() {
         let tracker = DeploymentTracker::new();
 
         // Start two deployments
         tracker
-            .start_deployment("deploy-1", "service-1", "1.0.0", 2)
+            .start_deployment("deploy-1", "service-1", "1.0.0", StrategyType::Immediate, 2)
             .await
             .unwrap();
         tracker
-            .start_deployment("deploy-2", "service-2", "1.0.0", 2)
+            .start_deployment("deploy-2", "service-2", "1.0.0", StrategyType::Immediate, 2)
             .await
             .unwrap();
 
@@ -530,6 +1137,12 @@ mod tests {
             failed: 0,
             skipped: 0,
             instance_results: Vec::new(),
+            blue_count: 0,
+            green_count: 0,
+            dry_run: false,
+            health_degraded: false,
+            rollback_triggered: false,
+            canary_instances: Vec::new(),
         };
         tracker
             .complete_deployment("deploy-1", result)
@@ -547,7 +1160,13 @@ mod tests {
         let tracker = DeploymentTracker::new();
 
         tracker
-            .start_deployment("deploy-1", "my-service", "1.0.0", 2)
+            .start_deployment(
+                "deploy-1",
+                "my-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                2,
+            )
             .await
             .unwrap();
 
@@ -563,7 +1182,13 @@ mod tests {
 
         // First deployment
         tracker
-            .start_deployment("deploy-1", "my-service", "1.0.0", 2)
+            .start_deployment(
+                "deploy-1",
+                "my-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                2,
+            )
             .await
             .unwrap();
 
@@ -574,6 +1199,12 @@ mod tests {
             failed: 0,
             skipped: 0,
             instance_results: Vec::new(),
+            blue_count: 0,
+            green_count: 0,
+            dry_run: false,
+            health_degraded: false,
+            rollback_triggered: false,
+            canary_instances: Vec::new(),
         };
         tracker
             .complete_deployment("deploy-1", result)
@@ -582,8 +1213,355 @@ mod tests {
 
         // Second deployment should now work
         let result = tracker
-            .start_deployment("deploy-2", "my-service", "2.0.0", 2)
+            .start_deployment(
+                "deploy-2",
+                "my-service",
+                "2.0.0",
+                StrategyType::Immediate,
+                2,
+            )
             .await;
         assert!(result.is_ok());
+
+        let status = tracker.get_service_status("my-service").await.unwrap();
+        assert_eq!(status.current_version.as_deref(), Some("2.0.0"));
+        assert_eq!(status.previous_version.as_deref(), Some("1.0.0"));
+    }
+
+    #[tokio::test]
+    async fn test_tracker_mark_rolled_back() {
+        let tracker = DeploymentTracker::new();
+        for (id, version) in [("deploy-1", "1.0.0"), ("deploy-2", "2.0.0")] {
+            tracker
+                .start_deployment(id, "my-service", version, StrategyType::Canary, 1)
+                .await
+                .unwrap();
+            let result = crate::DeploymentResult {
+                health_degraded: id == "deploy-2",
+                ..crate::DeploymentResult::from_results(id, Vec::new())
+            };
+            tracker.complete_deployment(id, result).await.unwrap();
+        }
+        let info = tracker.get_deployment("deploy-2").await.unwrap();
+        assert_eq!(info.state, DeploymentState::Failed);
+        assert!(info.error.is_some());
+        assert!(tracker.mark_rolled_back("deploy-1").await.is_err());
+
+        tracker.mark_rolled_back("deploy-2").await.unwrap();
+        let info = tracker.get_deployment("deploy-2").await.unwrap();
+        assert_eq!(info.state, DeploymentState::RolledBack);
+        assert!(tracker.mark_rolled_back("deploy-2").await.is_err());
+
+        // A rolled-back deployment never replaced the version before it
+        tracker
+            .start_deployment(
+                "deploy-3",
+                "my-service",
+                "3.0.0",
+                StrategyType::Immediate,
+                1,
+            )
+            .await
+            .unwrap();
+        let info = tracker.get_deployment("deploy-3").await.unwrap();
+        assert_eq!(info.previous_version.as_deref(), Some("1.0.0"));
+    }
+
+    /// Starts and completes a deployment per service, in order.
+    async fn tracker_with_history(services: &[&str]) -> DeploymentTracker {
+        let tracker = DeploymentTracker::new();
+        for (i, service) in services.iter().enumerate() {
+            let id = format!("deploy-{i}");
+            tracker
+                .start_deployment(&id, service, "1.0.0", StrategyType::Rolling, 1)
+                .await
+                .unwrap();
+            let result = crate::DeploymentResult::from_results(
+                &id,
+                vec![crate::InstanceResult {
+                    instance_id: "inst-1".to_string(),
+                    status: InstanceResultStatus::Success,
+                }],
+            );
+            tracker.complete_deployment(&id, result).await.unwrap();
+        }
+        tracker
+    }
+
+    #[tokio::test]
+    async fn test_tracker_complete_records_instance_results() {
+        let tracker = DeploymentTracker::new();
+        tracker
+            .start_deployment("deploy-1", "my-service", "1.0.0", StrategyType::Canary, 3)
+            .await
+            .unwrap();
+
+        let result = crate::DeploymentResult::from_results(
+            "deploy-1",
+            vec![
+                crate::InstanceResult {
+                    instance_id: "inst-1".to_string(),
+                    status: InstanceResultStatus::Success,
+                },
+                crate::InstanceResult {
+                    instance_id: "inst-2".to_string(),
+                    status: InstanceResultStatus::Failed("rejected".to_string()),
+                },
+                crate::InstanceResult {
+                    instance_id: "inst-3".to_string(),
+                    status: InstanceResultStatus::Skipped,
+                },
+            ],
+        );
+        tracker
+            .complete_deployment("deploy-1", result)
+            .await
+            .unwrap();
+
+        let info = tracker.get_deployment("deploy-1").await.unwrap();
+        assert_eq!(info.strategy, StrategyType::Canary);
+        assert_eq!(info.state, DeploymentState::Failed);
+        assert_eq!((info.successful, info.failed, info.skipped), (1, 1, 1));
+        assert!(info.finished_at.is_some_and(|t| t >= info.created_at));
+        assert!(info.instance_status["inst-1"].success);
+        assert_eq!(
+            info.instance_status["inst-2"].error.as_deref(),
+            Some("rejected")
+        );
+        assert!(info.instance_status["inst-3"].skipped);
+    }
+
+    #[tokio::test]
+    async fn test_tracker_list_deployments_paginates() {
+        let tracker = tracker_with_history(&["svc-a", "svc-b", "svc-c", "svc-d", "svc-e"]).await;
+
+        let mut ids = Vec::new();
+        let mut token = None;
+        loop {
+            let page = tracker
+                .list_deployments(&DeploymentFilter::default(), 2, token.as_deref())
+                .await
+                .unwrap();
+            assert!(page.deployments.len() <= 2);
+            ids.extend(page.deployments.into_iter().map(|d| d.id));
+            token = page.next_page_token;
+            if token.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(
+            ids,
+            ["deploy-4", "deploy-3", "deploy-2", "deploy-1", "deploy-0"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tracker_list_deployments_filters() {
+        let tracker = tracker_with_history(&["svc-a", "svc-b", "svc-a"]).await;
+        tracker
+            .start_deployment("deploy-3", "svc-c", "2.0.0", StrategyType::Immediate, 1)
+            .await
+            .unwrap();
+
+        let list = |filter: DeploymentFilter| {
+            let tracker = &tracker;
+            async move {
+                let page = tracker.list_deployments(&filter, 0, None).await.unwrap();
+                page.deployments
+                    .into_iter()
+                    .map(|d| d.id)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(
+            list(DeploymentFilter {
+                service: Some("svc-a".to_string()),
+                ..Default::default()
+            })
+            .await,
+            ["deploy-2", "deploy-0"]
+        );
+        assert_eq!(
+            list(DeploymentFilter {
+                state: Some(DeploymentState::InProgress),
+                ..Default::default()
+            })
+            .await,
+            ["deploy-3"]
+        );
+        assert!(list(DeploymentFilter {
+            started_after: Some(Utc::now() + chrono::Duration::hours(1)),
+            ..Default::default()
+        })
+        .await
+        .is_empty());
+        assert_eq!(
+            list(DeploymentFilter {
+                started_before: Some(Utc::now()),
+                ..Default::default()
+            })
+            .await
+            .len(),
+            4
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tracker_list_deployments_invalid_token() {
+        let tracker = DeploymentTracker::new();
+
+        let result = tracker
+            .list_deployments(&DeploymentFilter::default(), 10, Some("not-a-token"))
+            .await;
+        assert!(matches!(
+            result,
+            Err(DistributorError::InvalidOperation { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tracker_persistence_recovers_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE_NAME);
+
+        let tracker = DeploymentTracker::with_persistence(path.clone());
+        tracker
+            .start_deployment(
+                "deploy-1",
+                "users-service",
+                "1.0.0",
+                StrategyType::Immediate,
+                2,
+            )
+            .await
+            .unwrap();
+        let result = DeploymentResult {
+            deployment_id: "deploy-1".to_string(),
+            successful: 2,
+            failed: 0,
+            skipped: 0,
+            instance_results: Vec::new(),
+            blue_count: 0,
+            green_count: 0,
+            dry_run: false,
+            health_degraded: false,
+            rollback_triggered: false,
+            canary_instances: Vec::new(),
+        };
+        tracker
+            .complete_deployment("deploy-1", result)
+            .await
+            .unwrap();
+        tracker
+            .start_deployment(
+                "deploy-2",
+                "users-service",
+                "1.1.0",
+                StrategyType::Rolling,
+                2,
+            )
+            .await
+            .unwrap();
+        tracker
+            .update_instance("deploy-2", "instance-1", true)
+            .await
+            .unwrap();
+        tracker
+            .start_deployment(
+                "deploy-3",
+                "orders-service",
+                "2.0.0",
+                StrategyType::Immediate,
+                1,
+            )
+            .await
+            .unwrap();
+        tracker.cancel_deployment("deploy-3").await.unwrap();
+        drop(tracker);
+
+        let tracker = DeploymentTracker::with_persistence(path.clone());
+
+        let completed = tracker.get_deployment("deploy-1").await.unwrap();
+        assert_eq!(completed.state, DeploymentState::Completed);
+        assert!(completed.finished_at.is_some());
+        let interrupted = tracker.get_deployment("deploy-2").await.unwrap();
+        assert_eq!(interrupted.state, DeploymentState::Interrupted);
+        assert_eq!(interrupted.version, "1.1.0");
+        assert_eq!(interrupted.previous_version.as_deref(), Some("1.0.0"));
+        assert_eq!(interrupted.strategy, StrategyType::Rolling);
+        assert!(interrupted.error.is_some());
+        assert_eq!(interrupted.successful, 1);
+        assert!(interrupted.instance_status["instance-1"].success);
+        let cancelled = tracker.get_deployment("deploy-3").await.unwrap();
+        assert_eq!(cancelled.state, DeploymentState::Cancelled);
+        assert_eq!(tracker.interrupted_deployments().await, ["deploy-2"]);
+
+        // The service's current deployment survives, and is no longer in
+        // progress
+        let status = tracker.get_service_status("users-service").await.unwrap();
+        assert_eq!(status.state, DeploymentState::Interrupted);
+        tracker
+            .start_deployment(
+                "deploy-4",
+                "users-service",
+                "1.1.0",
+                StrategyType::Immediate,
+                2,
+            )
+            .await
+            .unwrap();
+
+        // Interrupted state was written back
+        drop(tracker);
+        let tracker = DeploymentTracker::with_persistence(path);
+        assert_eq!(
+            tracker.interrupted_deployments().await,
+            ["deploy-2", "deploy-4"]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_tracker_persistence_keeps_newest_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE_NAME);
+        let tracker = Arc::new(DeploymentTracker::with_persistence(path.clone()));
+
+        let starts = (0..20).map(|i| {
+            let tracker = Arc::clone(&tracker);
+            tokio::spawn(async move {
+                tracker
+                    .start_deployment(
+                        &format!("deploy-{i}"),
+                        &format!("service-{i}"),
+                        "1.0.0",
+                        StrategyType::Immediate,
+                        1,
+                    )
+                    .await
+            })
+        });
+        for start in futures::future::join_all(starts).await {
+            start.unwrap().unwrap();
+        }
+        drop(tracker);
+
+        // Writes finishing out of order never replace a newer state
+        let tracker = DeploymentTracker::with_persistence(path);
+        assert_eq!(tracker.interrupted_deployments().await.len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_tracker_persistence_moves_corrupt_file_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE_NAME);
+        std::fs::write(&path, "not json").unwrap();
+
+        let tracker = DeploymentTracker::with_persistence(path.clone());
+
+        assert!(tracker.list_active().await.is_empty());
+        assert!(path.with_extension("corrupt").exists());
+        assert!(!path.exists());
     }
 }
@@ -3,16 +3,44 @@
 //! This module provides different strategies for deploying policies
 //! across Archimedes instances.
 
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
+use futures::future::BoxFuture;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::error::DistributorError;
+use crate::instance::{Instance, InstanceId};
+use crate::DeploymentResult;
+
+/// Instance label that assigns an instance to a blue-green deployment group.
+pub const DEPLOYMENT_GROUP_LABEL: &str = "deployment-group";
+
+/// How long a paused rolling deployment waits to be resumed by default.
+pub const DEFAULT_MAX_PAUSE_DURATION: Duration = Duration::from_secs(30 * 60);
+
 /// Deployment strategy configuration.
-#[derive(Debug, Clone)]
+///
+/// Serializes without its [`DeploymentHooks`], which are code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+// The flags are independent builder switches, and grouping them would
+// change the serialized form of persisted scheduled deployments
+#[allow(clippy::struct_excessive_bools)]
 pub struct DeploymentStrategy {
     /// Type of deployment strategy.
     strategy_type: StrategyType,
 
-    /// Canary percentage (for canary deployments).
-    canary_percentage: Option<u32>,
+    /// How many instances receive the canary (for canary deployments).
+    canary_mode: Option<CanaryMode>,
+
+    /// Which instances receive the canary (for canary deployments).
+    #[serde(default)]
+    canary_selection: CanarySelection,
 
     /// Canary duration (how long to wait for canary validation).
     canary_duration: Option<Duration>,
@@ -23,11 +51,31 @@ pub struct DeploymentStrategy {
     /// Delay between batches (for rolling deployments).
     batch_delay: Option<Duration>,
 
+    /// How long to wait for a paused deployment to resume (for rolling
+    /// deployments).
+    max_pause_duration: Option<Duration>,
+
+    /// Time to wait before health checking the green group (for blue-green deployments).
+    health_wait: Option<Duration>,
+
     /// Maximum number of failures before aborting.
     max_failures: Option<u32>,
 
     /// Automatically rollback on failure.
     auto_rollback: bool,
+
+    /// Push to instances that already report the target version.
+    force: bool,
+
+    /// Only check instances, without pushing.
+    dry_run: bool,
+
+    /// Deploy to instances the health monitor reports as unhealthy.
+    include_unhealthy: bool,
+
+    /// Callbacks run before and after the deployment.
+    #[serde(skip)]
+    hooks: DeploymentHooks,
 }
 
 impl DeploymentStrategy {
@@ -37,12 +85,19 @@ impl DeploymentStrategy {
     pub fn immediate() -> Self {
         Self {
             strategy_type: StrategyType::Immediate,
-            canary_percentage: None,
+            canary_mode: None,
+            canary_selection: CanarySelection::First,
             canary_duration: None,
             batch_size: None,
             batch_delay: None,
+            max_pause_duration: None,
+            health_wait: None,
             max_failures: None,
             auto_rollback: false,
+            force: false,
+            dry_run: false,
+            include_unhealthy: false,
+            hooks: DeploymentHooks::default(),
         }
     }
 
@@ -53,14 +108,51 @@ impl DeploymentStrategy {
     /// * `percentage` - Percentage of instances for canary (1-100)
     /// * `duration` - Duration to wait for canary validation
     pub fn canary(percentage: u32, duration: Duration) -> Self {
+        Self::canary_with_mode(
+            CanaryMode::Percentage(f64::from(percentage.clamp(1, 100))),
+            duration,
+        )
+    }
+
+    /// Creates a canary deployment strategy with a fractional percentage.
+    ///
+    /// The canary always includes at least one instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - Percentage of instances for canary (0.0-100.0)
+    /// * `duration` - Duration to wait for canary validation
+    pub fn canary_percent(percent: f64, duration: Duration) -> Self {
+        Self::canary_with_mode(CanaryMode::Percentage(percent.clamp(0.0, 100.0)), duration)
+    }
+
+    /// Creates a canary deployment strategy with a fixed number of canary
+    /// instances.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of canary instances (at least 1)
+    /// * `duration` - Duration to wait for canary validation
+    pub fn canary_fixed(count: usize, duration: Duration) -> Self {
+        Self::canary_with_mode(CanaryMode::AbsoluteCount(count.max(1)), duration)
+    }
+
+    fn canary_with_mode(mode: CanaryMode, duration: Duration) -> Self {
         Self {
             strategy_type: StrategyType::Canary,
-            canary_percentage: Some(percentage.clamp(1, 100)),
+            canary_mode: Some(mode),
+            canary_selection: CanarySelection::First,
             canary_duration: Some(duration),
             batch_size: None,
             batch_delay: None,
+            max_pause_duration: None,
+            health_wait: None,
             max_failures: None,
             auto_rollback: true,
+            force: false,
+            dry_run: false,
+            include_unhealthy: false,
+            hooks: DeploymentHooks::default(),
         }
     }
 
@@ -73,12 +165,48 @@ impl DeploymentStrategy {
     pub fn rolling(batch_size: usize, delay: Duration) -> Self {
         Self {
             strategy_type: StrategyType::Rolling,
-            canary_percentage: None,
+            canary_mode: None,
+            canary_selection: CanarySelection::First,
             canary_duration: None,
             batch_size: Some(batch_size.max(1)),
             batch_delay: Some(delay),
+            max_pause_duration: Some(DEFAULT_MAX_PAUSE_DURATION),
+            health_wait: None,
             max_failures: None,
             auto_rollback: true,
+            force: false,
+            dry_run: false,
+            include_unhealthy: false,
+            hooks: DeploymentHooks::default(),
+        }
+    }
+
+    /// Creates a blue-green deployment strategy.
+    ///
+    /// Instances are split into a live "blue" group and a standby "green"
+    /// group by their [`DEPLOYMENT_GROUP_LABEL`] label. The policy is pushed
+    /// to the green group, and once every green instance is healthy the two
+    /// groups swap roles.
+    ///
+    /// # Arguments
+    ///
+    /// * `health_wait` - Duration to wait before health checking the green group
+    pub fn blue_green(health_wait: Duration) -> Self {
+        Self {
+            strategy_type: StrategyType::BlueGreen,
+            canary_mode: None,
+            canary_selection: CanarySelection::First,
+            canary_duration: None,
+            batch_size: None,
+            batch_delay: None,
+            max_pause_duration: None,
+            health_wait: Some(health_wait),
+            max_failures: None,
+            auto_rollback: false,
+            force: false,
+            dry_run: false,
+            include_unhealthy: false,
+            hooks: DeploymentHooks::default(),
         }
     }
 
@@ -88,15 +216,31 @@ impl DeploymentStrategy {
     }
 
     /// Returns the canary count for a given total number of instances.
+    ///
+    /// Percentages are rounded up. The count is at least 1 and at most
+    /// `total`.
     #[allow(
         clippy::cast_precision_loss,
         clippy::cast_possible_truncation,
         clippy::cast_sign_loss
     )]
     pub fn canary_count(&self, total: usize) -> usize {
-        self.canary_percentage.map_or(1, |pct| {
-            ((total as f64) * (f64::from(pct) / 100.0)).ceil() as usize
-        })
+        let count = match self.canary_mode {
+            Some(CanaryMode::Percentage(pct)) => ((total as f64) * pct / 100.0).ceil() as usize,
+            Some(CanaryMode::AbsoluteCount(count)) => count,
+            None => 1,
+        };
+        count.max(1).min(total)
+    }
+
+    /// Returns how the canary size is determined.
+    pub fn canary_mode(&self) -> Option<CanaryMode> {
+        self.canary_mode
+    }
+
+    /// Returns how canary instances are chosen.
+    pub fn canary_selection(&self) -> &CanarySelection {
+        &self.canary_selection
     }
 
     /// Returns the canary duration.
@@ -114,6 +258,17 @@ impl DeploymentStrategy {
         self.batch_delay
     }
 
+    /// Returns how long a paused deployment waits to be resumed before it
+    /// fails.
+    pub fn max_pause_duration(&self) -> Option<Duration> {
+        self.max_pause_duration
+    }
+
+    /// Returns the time to wait before health checking the green group.
+    pub fn health_wait(&self) -> Option<Duration> {
+        self.health_wait
+    }
+
     /// Returns the maximum number of failures before aborting.
     pub fn max_failures(&self) -> Option<u32> {
         self.max_failures
@@ -124,21 +279,248 @@ impl DeploymentStrategy {
         self.auto_rollback
     }
 
+    /// Returns whether instances already at the target version are pushed
+    /// to again.
+    pub fn force(&self) -> bool {
+        self.force
+    }
+
+    /// Returns whether this is a dry run, which checks instances without
+    /// pushing to them.
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Returns whether instances the health monitor reports as unhealthy
+    /// are deployed to.
+    pub fn include_unhealthy(&self) -> bool {
+        self.include_unhealthy
+    }
+
+    /// Sets how canary instances are chosen (the first instances discovered
+    /// by default).
+    pub fn with_canary_selection(mut self, selection: CanarySelection) -> Self {
+        self.canary_selection = selection;
+        self
+    }
+
     /// Sets the maximum number of failures.
     pub fn with_max_failures(mut self, max: u32) -> Self {
         self.max_failures = Some(max);
         self
     }
 
+    /// Sets how long a paused deployment waits to be resumed before it
+    /// fails.
+    pub fn with_max_pause_duration(mut self, duration: Duration) -> Self {
+        self.max_pause_duration = Some(duration);
+        self
+    }
+
     /// Sets the auto-rollback behavior.
     pub fn with_auto_rollback(mut self, enabled: bool) -> Self {
         self.auto_rollback = enabled;
         self
     }
+
+    /// Sets whether instances that already report the target version are
+    /// pushed to again instead of skipped.
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Sets whether instances the health monitor reports as unhealthy are
+    /// deployed to instead of excluded.
+    pub fn with_include_unhealthy(mut self, include: bool) -> Self {
+        self.include_unhealthy = include;
+        self
+    }
+
+    /// Makes this a dry run: instances are discovered and health checked,
+    /// and the planned action for each is reported, but nothing is pushed.
+    pub fn with_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Sets the callbacks run before and after the deployment.
+    pub fn with_hooks(mut self, hooks: DeploymentHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Returns the deployment hooks.
+    pub fn hooks(&self) -> &DeploymentHooks {
+        &self.hooks
+    }
+}
+
+/// Future returned by a deployment hook.
+pub type HookFuture = BoxFuture<'static, Result<(), DistributorError>>;
+
+/// Hook called with the service and version before a deployment.
+pub type PreDeployHook = Arc<dyn Fn(&str, &str) -> HookFuture + Send + Sync>;
+
+/// Hook called with the final result after a deployment.
+pub type PostDeployHook = Arc<dyn Fn(&DeploymentResult) -> HookFuture + Send + Sync>;
+
+/// Callbacks run around a deployment, e.g. to validate the version in a
+/// registry or to notify an on-call system.
+///
+/// A failing `pre_deploy` hook aborts the deployment before any instance is
+/// discovered. A failing `post_deploy` hook is logged, but the deployment
+/// result is still returned.
+#[derive(Clone, Default)]
+pub struct DeploymentHooks {
+    /// Called with the service and version before instance discovery.
+    pub pre_deploy: Option<PreDeployHook>,
+
+    /// Called with the deployment result once the deployment finished.
+    pub post_deploy: Option<PostDeployHook>,
+}
+
+impl DeploymentHooks {
+    /// Creates hooks that do nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the hook called before instance discovery.
+    pub fn with_pre_deploy<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, &str) -> HookFuture + Send + Sync + 'static,
+    {
+        self.pre_deploy = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets the hook called with the deployment result.
+    pub fn with_post_deploy<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&DeploymentResult) -> HookFuture + Send + Sync + 'static,
+    {
+        self.post_deploy = Some(Arc::new(hook));
+        self
+    }
+}
+
+impl fmt::Debug for DeploymentHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeploymentHooks")
+            .field("pre_deploy", &self.pre_deploy.is_some())
+            .field("post_deploy", &self.post_deploy.is_some())
+            .finish()
+    }
+}
+
+/// How the number of canary instances is determined.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanaryMode {
+    /// A fixed number of instances.
+    AbsoluteCount(usize),
+
+    /// A percentage (0.0-100.0) of the instances, rounded up.
+    Percentage(f64),
+}
+
+/// How canary instances are chosen among the discovered instances.
+///
+/// Discovery often returns instances grouped by zone, so taking the first
+/// ones concentrates the risk of a canary in one zone; the other selections
+/// spread or pin it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanarySelection {
+    /// The first instances in discovery order.
+    #[default]
+    First,
+
+    /// A random sample, the same for the same seed and set of instances.
+    ///
+    /// Sampled with `ChaCha8Rng`, whose output is fixed across platforms and
+    /// `rand` releases, unlike `StdRng`.
+    Random {
+        /// Seed of the sample.
+        seed: u64,
+    },
+
+    /// Instances taken from each value of a label (e.g. a zone) in turn.
+    SpreadByLabel {
+        /// Label whose values the canaries are spread across.
+        key: String,
+    },
+
+    /// Only instances with the label `key=value`, in discovery order.
+    Labeled {
+        /// Label key.
+        key: String,
+        /// Required label value.
+        value: String,
+    },
+
+    /// Exactly the instances with these IDs, regardless of the canary size.
+    Explicit(Vec<InstanceId>),
+}
+
+impl CanarySelection {
+    /// Chooses up to `count` canaries among `instances`.
+    ///
+    /// [`Labeled`](Self::Labeled) and [`Explicit`](Self::Explicit) choose
+    /// fewer if fewer instances match; `Explicit` ignores `count`.
+    pub fn select(&self, instances: &[Instance], count: usize) -> Vec<Instance> {
+        match self {
+            Self::First => instances.iter().take(count).cloned().collect(),
+            Self::Random { seed } => {
+                // Sorted first so the sample does not depend on discovery order
+                let mut candidates: Vec<&Instance> = instances.iter().collect();
+                candidates.sort_by(|a, b| a.id.cmp(&b.id));
+                candidates.shuffle(&mut ChaCha8Rng::seed_from_u64(*seed));
+                candidates.into_iter().take(count).cloned().collect()
+            }
+            Self::SpreadByLabel { key } => {
+                let mut groups: BTreeMap<Option<&str>, VecDeque<&Instance>> = BTreeMap::new();
+                for instance in instances {
+                    groups
+                        .entry(instance.metadata.get_label(key))
+                        .or_default()
+                        .push_back(instance);
+                }
+
+                let mut selected = Vec::with_capacity(count);
+                while selected.len() < count {
+                    let round = groups
+                        .values_mut()
+                        .filter_map(VecDeque::pop_front)
+                        .take(count - selected.len())
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if round.is_empty() {
+                        break;
+                    }
+                    selected.extend(round);
+                }
+                selected
+            }
+            Self::Labeled { key, value } => instances
+                .iter()
+                .filter(|instance| instance.metadata.get_label(key) == Some(value.as_str()))
+                .take(count)
+                .cloned()
+                .collect(),
+            Self::Explicit(ids) => instances
+                .iter()
+                .filter(|instance| ids.contains(&instance.id))
+                .cloned()
+                .collect(),
+        }
+    }
 }
 
 /// Type of deployment strategy.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum StrategyType {
     /// Deploy to all instances immediately.
     Immediate,
@@ -148,6 +530,9 @@ pub enum StrategyType {
 
     /// Deploy in batches with delays.
     Rolling,
+
+    /// Deploy to the standby group, then swap it with the live group.
+    BlueGreen,
 }
 
 impl std::fmt::Display for StrategyType {
@@ -156,8 +541,52 @@ impl std::fmt::Display for StrategyType {
             Self::Immediate => write!(f, "immediate"),
             Self::Canary => write!(f, "canary"),
             Self::Rolling => write!(f, "rolling"),
+            Self::BlueGreen => write!(f, "blue-green"),
+        }
+    }
+}
+
+/// Group of an instance in a blue-green deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeploymentGroup {
+    /// The group currently serving traffic.
+    Blue,
+
+    /// The standby group that receives new policies.
+    Green,
+}
+
+impl DeploymentGroup {
+    /// Parses a [`DEPLOYMENT_GROUP_LABEL`] label value.
+    pub fn from_label(value: &str) -> Option<Self> {
+        match value {
+            "blue" => Some(Self::Blue),
+            "green" => Some(Self::Green),
+            _ => None,
         }
     }
+
+    /// Returns the label value for this group.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Blue => "blue",
+            Self::Green => "green",
+        }
+    }
+
+    /// Returns the other group.
+    pub fn swapped(self) -> Self {
+        match self {
+            Self::Blue => Self::Green,
+            Self::Green => Self::Blue,
+        }
+    }
+}
+
+impl std::fmt::Display for DeploymentGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 #[cfg(test)]
@@ -177,7 +606,7 @@ mod tests {
     fn test_canary_strategy() {
         let strategy = DeploymentStrategy::canary(10, Duration::from_secs(300));
         assert_eq!(strategy.strategy_type(), StrategyType::Canary);
-        assert_eq!(strategy.canary_percentage, Some(10));
+        assert_eq!(strategy.canary_mode(), Some(CanaryMode::Percentage(10.0)));
         assert_eq!(strategy.canary_duration(), Some(Duration::from_secs(300)));
         assert!(strategy.auto_rollback());
     }
@@ -199,13 +628,142 @@ mod tests {
         assert_eq!(strategy.canary_count(1), 1);
     }
 
+    #[test]
+    fn test_canary_percent_count() {
+        let ten = DeploymentStrategy::canary_percent(10.0, Duration::from_secs(60));
+        assert_eq!(ten.canary_count(10), 1);
+        assert_eq!(ten.canary_count(17), 2); // 1.7 rounds up
+        assert_eq!(ten.canary_count(100), 10);
+
+        let small = DeploymentStrategy::canary_percent(0.5, Duration::from_secs(60));
+        assert_eq!(small.canary_count(10), 1);
+        assert_eq!(small.canary_count(17), 1);
+        assert_eq!(small.canary_count(100), 1);
+
+        let third = DeploymentStrategy::canary_percent(33.3, Duration::from_secs(60));
+        assert_eq!(third.canary_count(10), 4);
+        assert_eq!(third.canary_count(17), 6);
+        assert_eq!(third.canary_count(100), 34);
+
+        // Every percentage gives at least one canary
+        for percent in [0.0, 0.1, 1.0, 5.0, 50.0, 100.0] {
+            let strategy = DeploymentStrategy::canary_percent(percent, Duration::from_secs(60));
+            for total in [10, 17, 100] {
+                let count = strategy.canary_count(total);
+                assert!(
+                    (1..=total).contains(&count),
+                    "{percent}% of {total}: {count}"
+                );
+            }
+        }
+    }
+
+    fn zoned_instances() -> Vec<Instance> {
+        ["a", "a", "a", "b", "b", "c"]
+            .iter()
+            .enumerate()
+            .map(|(i, zone)| {
+                Instance::new(format!("inst-{i}"), format!("10.0.0.{i}:8080")).with_metadata(
+                    crate::instance::InstanceMetadata::default()
+                        .with_label("zone", *zone)
+                        .with_label("canary", if i == 4 { "true" } else { "false" }),
+                )
+            })
+            .collect()
+    }
+
+    fn ids(instances: &[Instance]) -> Vec<&str> {
+        instances.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_canary_selection_first_and_spread() {
+        let instances = zoned_instances();
+
+        let first = CanarySelection::First.select(&instances, 3);
+        assert_eq!(ids(&first), ["inst-0", "inst-1", "inst-2"]);
+
+        let spread = CanarySelection::SpreadByLabel {
+            key: "zone".to_string(),
+        };
+        assert_eq!(
+            ids(&spread.select(&instances, 3)),
+            ["inst-0", "inst-3", "inst-5"]
+        );
+        assert_eq!(
+            ids(&spread.select(&instances, 5)),
+            ["inst-0", "inst-3", "inst-5", "inst-1", "inst-4"]
+        );
+    }
+
+    #[test]
+    fn test_canary_selection_random_is_seeded() {
+        let instances = zoned_instances();
+        let selection = CanarySelection::Random { seed: 42 };
+
+        // Pinned, so a change of sampling is noticed
+        let sample = selection.select(&instances, 3);
+        assert_eq!(ids(&sample), ["inst-0", "inst-2", "inst-5"]);
+        let mut reversed = instances.clone();
+        reversed.reverse();
+        assert_eq!(ids(&selection.select(&reversed, 3)), ids(&sample));
+
+        let samples: std::collections::HashSet<_> = (0..20)
+            .map(|seed| ids(&CanarySelection::Random { seed }.select(&instances, 3)).join(","))
+            .collect();
+        assert!(samples.len() > 1);
+    }
+
+    #[test]
+    fn test_canary_selection_labeled_and_explicit() {
+        let instances = zoned_instances();
+
+        let labeled = CanarySelection::Labeled {
+            key: "canary".to_string(),
+            value: "true".to_string(),
+        };
+        assert_eq!(ids(&labeled.select(&instances, 3)), ["inst-4"]);
+
+        let explicit = CanarySelection::Explicit(vec!["inst-5".to_string(), "unknown".to_string()]);
+        assert_eq!(ids(&explicit.select(&instances, 3)), ["inst-5"]);
+    }
+
+    #[test]
+    fn test_canary_percent_clamped() {
+        let high = DeploymentStrategy::canary_percent(250.0, Duration::from_secs(60));
+        assert_eq!(high.canary_mode(), Some(CanaryMode::Percentage(100.0)));
+        assert_eq!(high.canary_count(17), 17);
+
+        let low = DeploymentStrategy::canary_percent(-5.0, Duration::from_secs(60));
+        assert_eq!(low.canary_mode(), Some(CanaryMode::Percentage(0.0)));
+        assert_eq!(low.canary_count(17), 1);
+    }
+
+    #[test]
+    fn test_canary_fixed_count() {
+        let strategy = DeploymentStrategy::canary_fixed(3, Duration::from_secs(60));
+        assert_eq!(strategy.strategy_type(), StrategyType::Canary);
+        assert_eq!(strategy.canary_mode(), Some(CanaryMode::AbsoluteCount(3)));
+        assert_eq!(strategy.canary_count(10), 3);
+        assert_eq!(strategy.canary_count(2), 2);
+
+        let zero = DeploymentStrategy::canary_fixed(0, Duration::from_secs(60));
+        assert_eq!(zero.canary_count(17), 1);
+    }
+
     #[test]
     fn test_canary_percentage_clamped() {
         let strategy_high = DeploymentStrategy::canary(150, Duration::from_secs(60));
-        assert_eq!(strategy_high.canary_percentage, Some(100));
+        assert_eq!(
+            strategy_high.canary_mode(),
+            Some(CanaryMode::Percentage(100.0))
+        );
 
         let strategy_low = DeploymentStrategy::canary(0, Duration::from_secs(60));
-        assert_eq!(strategy_low.canary_percentage, Some(1));
+        assert_eq!(
+            strategy_low.canary_mode(),
+            Some(CanaryMode::Percentage(1.0))
+        );
     }
 
     #[test]
@@ -214,7 +772,14 @@ mod tests {
         assert_eq!(strategy.strategy_type(), StrategyType::Rolling);
         assert_eq!(strategy.batch_size(), Some(5));
         assert_eq!(strategy.batch_delay(), Some(Duration::from_secs(10)));
+        assert_eq!(
+            strategy.max_pause_duration(),
+            Some(DEFAULT_MAX_PAUSE_DURATION)
+        );
         assert!(strategy.auto_rollback());
+
+        let strategy = strategy.with_max_pause_duration(Duration::from_secs(60));
+        assert_eq!(strategy.max_pause_duration(), Some(Duration::from_secs(60)));
     }
 
     #[test]
@@ -223,12 +788,61 @@ mod tests {
         assert_eq!(strategy.batch_size(), Some(1)); // Minimum is 1
     }
 
+    #[test]
+    fn test_blue_green_strategy() {
+        let strategy = DeploymentStrategy::blue_green(Duration::from_secs(30));
+        assert_eq!(strategy.strategy_type(), StrategyType::BlueGreen);
+        assert_eq!(strategy.health_wait(), Some(Duration::from_secs(30)));
+        assert!(strategy.batch_size().is_none());
+        assert!(!strategy.auto_rollback());
+    }
+
+    #[test]
+    fn test_deployment_group() {
+        assert_eq!(
+            DeploymentGroup::from_label("blue"),
+            Some(DeploymentGroup::Blue)
+        );
+        assert_eq!(
+            DeploymentGroup::from_label("green"),
+            Some(DeploymentGroup::Green)
+        );
+        assert_eq!(DeploymentGroup::from_label("red"), None);
+        assert_eq!(DeploymentGroup::Blue.swapped(), DeploymentGroup::Green);
+        assert_eq!(DeploymentGroup::Green.to_string(), "green");
+    }
+
     #[test]
     fn test_with_max_failures() {
         let strategy = DeploymentStrategy::rolling(5, Duration::from_secs(10)).with_max_failures(3);
         assert_eq!(strategy.max_failures(), Some(3));
     }
 
+    #[test]
+    fn test_with_force() {
+        assert!(!DeploymentStrategy::immediate().force());
+        assert!(DeploymentStrategy::immediate().with_force(true).force());
+        assert!(DeploymentStrategy::blue_green(Duration::ZERO)
+            .with_force(true)
+            .force());
+    }
+
+    #[test]
+    fn test_with_include_unhealthy() {
+        assert!(!DeploymentStrategy::immediate().include_unhealthy());
+        assert!(DeploymentStrategy::immediate()
+            .with_include_unhealthy(true)
+            .include_unhealthy());
+    }
+
+    #[test]
+    fn test_with_dry_run() {
+        assert!(!DeploymentStrategy::canary(10, Duration::ZERO).dry_run());
+        assert!(DeploymentStrategy::canary(10, Duration::ZERO)
+            .with_dry_run()
+            .dry_run());
+    }
+
     #[test]
     fn test_with_auto_rollback() {
         let strategy = DeploymentStrategy::immediate().with_auto_rollback(true);
@@ -244,6 +858,7 @@ mod tests {
         assert_eq!(StrategyType::Immediate.to_string(), "immediate");
         assert_eq!(StrategyType::Canary.to_string(), "canary");
         assert_eq!(StrategyType::Rolling.to_string(), "rolling");
+        assert_eq!(StrategyType::BlueGreen.to_string(), "blue-green");
     }
 
     #[test]
@@ -22,7 +22,7 @@ use eunomia_distributor::grpc::types::{
     ListInstancesRequest,
 };
 use eunomia_distributor::grpc::{ControlPlane, ControlPlaneService};
-use eunomia_distributor::{Distributor, DistributorConfig};
+use eunomia_distributor::{Distributor, DistributorConfig, SimulatedTransport};
 
 // =============================================================================
 // Test Constants
@@ -71,7 +71,12 @@ async fn create_test_distributor(endpoints: Vec<String>) -> Arc<Distributor> {
     let config = DistributorConfig::builder()
         .static_endpoints(endpoints)
         .build();
-    Arc::new(Distributor::new(config).await.unwrap())
+    Arc::new(
+        Distributor::new(config)
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default())),
+    )
 }
 
 /// Create a bundle from policy source
@@ -490,7 +495,7 @@ async fn test_e2e_concurrent_bundle_signing() {
     // Spawn multiple concurrent signings
     for i in 0..10 {
         let bundle_clone = bundle.clone();
-        let kp_clone = SigningKeyPair::from_seed(&key_pair.to_bytes()).unwrap();
+        let kp_clone = SigningKeyPair::from_seed(&key_pair.to_bytes().unwrap()).unwrap();
         let success = Arc::clone(&success_count);
 
         let handle = tokio::spawn(async move {
@@ -599,7 +604,7 @@ async fn test_e2e_key_pair_generation() {
     );
 
     // Roundtrip through base64
-    let base64 = key_pair1.to_base64();
+    let base64 = key_pair1.to_base64().unwrap();
     let restored = SigningKeyPair::from_base64(&base64).expect("Should restore from base64");
 
     assert_eq!(
@@ -21,7 +21,7 @@ use eunomia_distributor::grpc::types::{
     RollbackPolicyRequest,
 };
 use eunomia_distributor::grpc::{ControlPlane, ControlPlaneService};
-use eunomia_distributor::{Distributor, DistributorConfig};
+use eunomia_distributor::{Distributor, DistributorConfig, SimulatedTransport};
 
 // =============================================================================
 // Test Constants
@@ -43,7 +43,12 @@ async fn create_empty_distributor() -> Arc<Distributor> {
     let config = DistributorConfig::builder()
         .static_endpoints(vec![])
         .build();
-    Arc::new(Distributor::new(config).await.unwrap())
+    Arc::new(
+        Distributor::new(config)
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default())),
+    )
 }
 
 /// Create a test distributor with unreachable endpoints
@@ -55,7 +60,12 @@ async fn create_unreachable_distributor() -> Arc<Distributor> {
             "http://unreachable-host-2:9999".to_string(),
         ])
         .build();
-    Arc::new(Distributor::new(config).await.unwrap())
+    Arc::new(
+        Distributor::new(config)
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default())),
+    )
 }
 
 /// Create a test bundle
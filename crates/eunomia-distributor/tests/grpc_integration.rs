@@ -1,7 +1,7 @@
 //! gRPC Integration Tests for Eunomia Distributor
 //!
-//! These tests verify the gRPC service logic works correctly.
-//! Note: Full protocol-level tests require tonic codegen which is pending.
+//! These tests verify the gRPC service logic works correctly, and that
+//! `ControlPlaneClient` reaches it through a running `GrpcServer`.
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -9,19 +9,36 @@ use std::time::Duration;
 use tonic::Request;
 
 use eunomia_distributor::grpc::types::{
-    DeployPolicyRequest, GetPolicyStatusRequest, GrpcDeploymentStrategy, GrpcHealthState,
-    GrpcStrategyType, HealthCheckRequest, ListInstancesRequest, RollbackPolicyRequest,
-    UpdatePolicyRequest,
+    CancelScheduledDeploymentRequest, DeployPolicyRequest, GetDeploymentRequest,
+    GetInstanceHealthRequest, GetPolicyStatusRequest, GrpcDeploymentState, GrpcDeploymentStrategy,
+    GrpcFreezePeriod, GrpcHealthState, GrpcStrategyType, HealthCheckRequest,
+    ListDeploymentsRequest, ListInstancesRequest, ListScheduledDeploymentsRequest,
+    PauseDeploymentRequest, ResumeDeploymentRequest, RollbackPolicyRequest,
+    ScheduleDeploymentRequest, UpdatePolicyRequest,
+};
+use eunomia_distributor::grpc::{
+    ControlPlane, ControlPlaneClient, ControlPlaneService, GrpcServer, GrpcServerConfig,
+};
+use eunomia_distributor::{
+    Distributor, DistributorConfig, DistributorError, SchedulerConfig, SimulatedTransport,
 };
-use eunomia_distributor::grpc::{ControlPlane, ControlPlaneService, GrpcServerConfig};
-use eunomia_distributor::{Distributor, DistributorConfig};
 
 /// Create a test distributor with static discovery.
 async fn create_test_distributor(endpoints: Vec<String>) -> Arc<Distributor> {
     let config = DistributorConfig::builder()
         .static_endpoints(endpoints)
         .build();
-    Arc::new(Distributor::new(config).await.unwrap())
+    Arc::new(
+        Distributor::new(config)
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default())),
+    )
+}
+
+fn free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().port()
 }
 
 // =============================================================================
@@ -164,6 +181,406 @@ async fn test_control_plane_rollback() {
     assert!(response.is_err());
 }
 
+#[tokio::test]
+async fn test_control_plane_deployment_history() {
+    let distributor = create_test_distributor(vec![
+        "localhost:8080".to_string(),
+        "localhost:8081".to_string(),
+    ])
+    .await;
+    let service = ControlPlaneService::new(distributor);
+
+    let mut ids = Vec::new();
+    for version in ["1.0.0", "1.1.0"] {
+        let request = DeployPolicyRequest {
+            service: "users-service".to_string(),
+            version: version.to_string(),
+            strategy: None,
+            target_instances: vec![],
+            reason: "History test".to_string(),
+        };
+        let response = service.deploy_policy(Request::new(request)).await.unwrap();
+        ids.push(response.into_inner().deployment_id);
+    }
+
+    // One deployment per page, newest first
+    let request = ListDeploymentsRequest {
+        service_filter: "users-service".to_string(),
+        page_size: 1,
+        ..Default::default()
+    };
+    let first = service
+        .list_deployments(Request::new(request.clone()))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(first.deployments.len(), 1);
+    assert_eq!(first.deployments[0].deployment_id, ids[1]);
+    assert_eq!(first.deployments[0].version, "1.1.0");
+    assert_eq!(first.deployments[0].strategy, GrpcStrategyType::Immediate);
+    assert!(!first.next_page_token.is_empty());
+
+    let second = service
+        .list_deployments(Request::new(ListDeploymentsRequest {
+            page_token: first.next_page_token,
+            ..request
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(second.deployments[0].deployment_id, ids[0]);
+    assert!(second.next_page_token.is_empty());
+
+    let failed = service
+        .list_deployments(Request::new(ListDeploymentsRequest {
+            state_filter: Some(GrpcDeploymentState::Failed),
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(failed.deployments.is_empty());
+
+    let record = service
+        .get_deployment(Request::new(GetDeploymentRequest {
+            deployment_id: ids[0].clone(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(record.state, GrpcDeploymentState::Completed);
+    assert_eq!(record.instance_results.len(), 2);
+    assert!(record.started_at.is_some());
+    assert!(record.completed_at.is_some());
+    assert_eq!(record.summary.unwrap().total_instances, 2);
+}
+
+#[tokio::test]
+async fn test_control_plane_deployment_history_errors() {
+    let distributor = create_test_distributor(vec![]).await;
+    let service = ControlPlaneService::new(distributor);
+
+    let status = service
+        .get_deployment(Request::new(GetDeploymentRequest {
+            deployment_id: "missing".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+    assert_eq!(
+        DistributorError::code_from_status(&status).as_deref(),
+        Some("EUN-D008")
+    );
+
+    let status = service
+        .list_deployments(Request::new(ListDeploymentsRequest {
+            page_token: "garbage".to_string(),
+            ..Default::default()
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert_eq!(
+        DistributorError::code_from_status(&status).as_deref(),
+        Some("EUN-D017")
+    );
+}
+
+#[tokio::test]
+async fn test_control_plane_client_over_grpc() {
+    let distributor = create_test_distributor(vec!["localhost:8080".to_string()]).await;
+    let addr = format!("127.0.0.1:{}", free_port()).parse().unwrap();
+    let handle = GrpcServer::new(distributor, GrpcServerConfig::new(addr))
+        .run()
+        .await
+        .unwrap();
+    // Give the server time to bind
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut client = ControlPlaneClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap();
+    let deployed = client
+        .deploy_policy(DeployPolicyRequest {
+            service: "users-service".to_string(),
+            version: "1.0.0".to_string(),
+            strategy: None,
+            target_instances: vec![],
+            reason: "Client test".to_string(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let listed = client
+        .list_deployments(ListDeploymentsRequest {
+            service_filter: "users-service".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(listed.deployments.len(), 1);
+    assert_eq!(listed.deployments[0].deployment_id, deployed.deployment_id);
+
+    let record = client
+        .get_deployment(GetDeploymentRequest {
+            deployment_id: deployed.deployment_id,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(record.state, GrpcDeploymentState::Completed);
+
+    let status = client
+        .get_deployment(GetDeploymentRequest {
+            deployment_id: "missing".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+    assert_eq!(
+        DistributorError::code_from_status(&status).as_deref(),
+        Some("EUN-D008")
+    );
+
+    drop(client);
+    handle.shutdown();
+}
+
+#[tokio::test]
+async fn test_server_runs_health_monitor() {
+    let distributor = create_test_distributor(vec!["localhost:8080".to_string()]).await;
+    let instance = distributor.list_instances("users-service").await.unwrap()[0].clone();
+    assert!(distributor.instance_health(&instance.id).is_none());
+
+    let addr = format!("127.0.0.1:{}", free_port()).parse().unwrap();
+    let handle = GrpcServer::new(Arc::clone(&distributor), GrpcServerConfig::new(addr))
+        .run()
+        .await
+        .unwrap();
+    // The monitor probes as soon as it starts
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(distributor.instance_health(&instance.id).is_some());
+
+    handle.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn test_server_launches_scheduled_deployments() {
+    let config = DistributorConfig::builder()
+        .static_endpoints(vec!["localhost:8080".to_string()])
+        .scheduler_config(
+            SchedulerConfig::builder()
+                .poll_interval(Duration::from_millis(10))
+                .build(),
+        )
+        .build();
+    let distributor = Arc::new(
+        Distributor::new(config)
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default())),
+    );
+
+    let addr = format!("127.0.0.1:{}", free_port()).parse().unwrap();
+    let handle = GrpcServer::new(Arc::clone(&distributor), GrpcServerConfig::new(addr))
+        .run()
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut client = ControlPlaneClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap();
+    let scheduled = client
+        .schedule_deployment(ScheduleDeploymentRequest {
+            service: "users-service".to_string(),
+            version: "1.1.0".to_string(),
+            not_before: Some(chrono::Utc::now() + chrono::Duration::milliseconds(100)),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(scheduled.version, "1.1.0");
+    assert!(scheduled.scheduled_at.is_some());
+
+    // The server's scheduler launches the deployment once it is due
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !distributor.list_scheduled().await.is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    drop(client);
+    handle.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn test_control_plane_pause_resume_errors() {
+    let distributor = create_test_distributor(vec!["localhost:8080".to_string()]).await;
+    let service = ControlPlaneService::new(distributor);
+
+    let status = service
+        .pause_deployment(Request::new(PauseDeploymentRequest {
+            deployment_id: "missing".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    // A finished deployment can be neither paused nor resumed
+    let response = service
+        .deploy_policy(Request::new(DeployPolicyRequest {
+            service: "users-service".to_string(),
+            version: "1.0.0".to_string(),
+            strategy: None,
+            target_instances: vec![],
+            reason: "Pause test".to_string(),
+        }))
+        .await
+        .unwrap();
+    let deployment_id = response.into_inner().deployment_id;
+
+    let status = service
+        .pause_deployment(Request::new(PauseDeploymentRequest {
+            deployment_id: deployment_id.clone(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+    let status = service
+        .resume_deployment(Request::new(ResumeDeploymentRequest { deployment_id }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    assert_eq!(
+        DistributorError::code_from_status(&status).as_deref(),
+        Some("EUN-D016")
+    );
+}
+
+#[tokio::test]
+async fn test_control_plane_scheduled_deployments() {
+    let distributor = create_test_distributor(vec!["localhost:8080".to_string()]).await;
+    let service = ControlPlaneService::new(distributor);
+
+    let now = chrono::Utc::now();
+    let freeze_end = now + chrono::Duration::days(7);
+    let scheduled = service
+        .schedule_deployment(Request::new(ScheduleDeploymentRequest {
+            service: "users-service".to_string(),
+            version: "1.1.0".to_string(),
+            strategy: Some(GrpcDeploymentStrategy {
+                strategy_type: GrpcStrategyType::Rolling,
+                ..Default::default()
+            }),
+            windows: vec!["Mon-Fri 09:00-16:00".to_string()],
+            freezes: vec![GrpcFreezePeriod {
+                start: now,
+                end: freeze_end,
+                reason: "release freeze".to_string(),
+            }],
+            ..Default::default()
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(scheduled.strategy, GrpcStrategyType::Rolling);
+    assert_eq!(scheduled.windows, ["Mon,Tue,Wed,Thu,Fri 09:00-16:00"]);
+    assert!(scheduled.next_run_at.unwrap() >= freeze_end);
+
+    let listed = service
+        .list_scheduled_deployments(Request::new(ListScheduledDeploymentsRequest {
+            service_filter: "users-service".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(listed.deployments.len(), 1);
+    assert_eq!(listed.deployments[0].scheduled_id, scheduled.scheduled_id);
+    assert_eq!(listed.deployments[0].freezes[0].reason, "release freeze");
+
+    let other = service
+        .list_scheduled_deployments(Request::new(ListScheduledDeploymentsRequest {
+            service_filter: "orders-service".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(other.deployments.is_empty());
+
+    let cancelled = service
+        .cancel_scheduled_deployment(Request::new(CancelScheduledDeploymentRequest {
+            scheduled_id: scheduled.scheduled_id.clone(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(cancelled.version, "1.1.0");
+
+    let status = service
+        .cancel_scheduled_deployment(Request::new(CancelScheduledDeploymentRequest {
+            scheduled_id: scheduled.scheduled_id,
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+}
+
+#[tokio::test]
+async fn test_control_plane_schedule_invalid_window() {
+    let distributor = create_test_distributor(vec!["localhost:8080".to_string()]).await;
+    let service = ControlPlaneService::new(distributor);
+
+    let status = service
+        .schedule_deployment(Request::new(ScheduleDeploymentRequest {
+            service: "users-service".to_string(),
+            version: "1.1.0".to_string(),
+            windows: vec!["weekdays 9-5".to_string()],
+            ..Default::default()
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert_eq!(
+        DistributorError::code_from_status(&status).as_deref(),
+        Some("EUN-D021")
+    );
+}
+
+#[tokio::test]
+async fn test_control_plane_instance_health() {
+    let distributor = create_test_distributor(vec!["localhost:9090".to_string()]).await;
+    let service = ControlPlaneService::new(distributor.clone());
+
+    // Not probed yet
+    let status = service
+        .get_instance_health(Request::new(GetInstanceHealthRequest {
+            instance_id: "static-0".to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    distributor.health_monitor().probe_all().await.unwrap();
+
+    let response = service
+        .get_instance_health(Request::new(GetInstanceHealthRequest {
+            instance_id: "static-0".to_string(),
+        }))
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(response.instance_id, "static-0");
+    assert_eq!(response.health, GrpcHealthState::Unknown);
+    assert!(response.last_check.is_some());
+}
+
 // =============================================================================
 // Policy Receiver Service Logic Tests
 // =============================================================================
@@ -470,6 +887,10 @@ fn test_strategy_type_conversion() {
         GrpcStrategyType::from(StrategyType::Rolling),
         GrpcStrategyType::Rolling
     );
+    assert_eq!(
+        GrpcStrategyType::from(StrategyType::BlueGreen),
+        GrpcStrategyType::BlueGreen
+    );
 }
 
 #[test]
@@ -501,6 +922,14 @@ fn test_deployment_state_conversion() {
         GrpcDeploymentState::from(DeploymentState::Cancelled),
         GrpcDeploymentState::Cancelled
     );
+    assert_eq!(
+        GrpcDeploymentState::from(DeploymentState::Paused),
+        GrpcDeploymentState::Paused
+    );
+    assert_eq!(
+        GrpcDeploymentState::from(DeploymentState::Interrupted),
+        GrpcDeploymentState::Interrupted
+    );
 }
 
 #[test]
@@ -534,9 +963,9 @@ fn test_health_state_conversion() {
 #[tokio::test]
 async fn test_bundle_push_to_healthy_instance() {
     use eunomia_distributor::instance::{Instance, InstanceMetadata, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
-    let pusher = PolicyPusher::new(PushConfig::default());
+    let pusher = PolicyPusher::simulated(PushConfig::default());
 
     // Create a healthy instance
     let mut instance = Instance::new("archimedes-1", "localhost:9091")
@@ -547,7 +976,9 @@ async fn test_bundle_push_to_healthy_instance() {
     });
 
     // Push new version
-    let result = pusher.push(&instance, "payment-service", "v2.0.0").await;
+    let result = pusher
+        .push(&instance, &PolicyUpdate::new("payment-service", "v2.0.0"))
+        .await;
 
     assert!(result.is_ok());
     let push_result = result.unwrap();
@@ -562,9 +993,9 @@ async fn test_bundle_push_to_healthy_instance() {
 #[tokio::test]
 async fn test_bundle_push_to_multiple_instances() {
     use eunomia_distributor::instance::{Instance, InstanceMetadata, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
-    let pusher = PolicyPusher::new(PushConfig::default());
+    let pusher = PolicyPusher::simulated(PushConfig::default());
 
     // Create multiple healthy instances
     let instances: Vec<Instance> = (1..=5)
@@ -581,9 +1012,10 @@ async fn test_bundle_push_to_multiple_instances() {
         .collect();
 
     // Push to all instances concurrently
+    let update = PolicyUpdate::new("users-service", "v2.0.0");
     let futures: Vec<_> = instances
         .iter()
-        .map(|inst| pusher.push(inst, "users-service", "v2.0.0"))
+        .map(|inst| pusher.push(inst, &update))
         .collect();
 
     let results = futures::future::join_all(futures).await;
@@ -605,14 +1037,14 @@ async fn test_bundle_push_to_multiple_instances() {
 #[tokio::test]
 async fn test_bundle_push_retry_on_unreachable() {
     use eunomia_distributor::instance::{Instance, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
     // Configure for quick retries
     let config = PushConfig::builder()
         .max_retries(3)
         .retry_delay(Duration::from_millis(10))
         .build();
-    let pusher = PolicyPusher::new(config);
+    let pusher = PolicyPusher::simulated(config);
 
     // Create an unreachable instance
     let mut instance = Instance::new("archimedes-down", "192.168.1.99:9091");
@@ -623,7 +1055,9 @@ async fn test_bundle_push_retry_on_unreachable() {
     });
 
     // Push should fail after retries
-    let result = pusher.push(&instance, "test-service", "v1.0.0").await;
+    let result = pusher
+        .push(&instance, &PolicyUpdate::new("test-service", "v1.0.0"))
+        .await;
 
     assert!(result.is_ok()); // Returns Ok with failure status
     let push_result = result.unwrap();
@@ -637,10 +1071,10 @@ async fn test_bundle_push_retry_on_unreachable() {
 #[tokio::test]
 async fn test_bundle_push_with_compression() {
     use eunomia_distributor::instance::{Instance, InstanceMetadata, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
     let config = PushConfig::builder().compression(true).build();
-    let pusher = PolicyPusher::new(config);
+    let pusher = PolicyPusher::simulated(config);
 
     let mut instance = Instance::new("archimedes-1", "localhost:9091")
         .with_metadata(InstanceMetadata::for_service("orders-service"));
@@ -649,7 +1083,9 @@ async fn test_bundle_push_with_compression() {
         last_check: std::time::Instant::now(),
     });
 
-    let result = pusher.push(&instance, "orders-service", "v1.1.0").await;
+    let result = pusher
+        .push(&instance, &PolicyUpdate::new("orders-service", "v1.1.0"))
+        .await;
 
     assert!(result.is_ok());
     assert!(result.unwrap().success);
@@ -659,13 +1095,13 @@ async fn test_bundle_push_with_compression() {
 #[tokio::test]
 async fn test_bundle_push_custom_timeouts() {
     use eunomia_distributor::instance::{Instance, InstanceMetadata, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
     let config = PushConfig::builder()
         .connect_timeout(Duration::from_secs(5))
         .request_timeout(Duration::from_secs(15))
         .build();
-    let pusher = PolicyPusher::new(config);
+    let pusher = PolicyPusher::simulated(config);
 
     let mut instance = Instance::new("archimedes-1", "localhost:9091")
         .with_metadata(InstanceMetadata::for_service("inventory-service"));
@@ -674,7 +1110,9 @@ async fn test_bundle_push_custom_timeouts() {
         last_check: std::time::Instant::now(),
     });
 
-    let result = pusher.push(&instance, "inventory-service", "v2.1.0").await;
+    let result = pusher
+        .push(&instance, &PolicyUpdate::new("inventory-service", "v2.1.0"))
+        .await;
 
     assert!(result.is_ok());
     let push_result = result.unwrap();
@@ -687,9 +1125,9 @@ async fn test_bundle_push_custom_timeouts() {
 async fn test_bundle_push_health_check_workflow() {
     use eunomia_distributor::health::HealthState;
     use eunomia_distributor::instance::{Instance, InstanceMetadata, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
-    let pusher = PolicyPusher::new(PushConfig::default());
+    let pusher = PolicyPusher::simulated(PushConfig::default());
 
     let mut instance = Instance::new("archimedes-1", "localhost:9091")
         .with_metadata(InstanceMetadata::for_service("auth-service"));
@@ -707,7 +1145,9 @@ async fn test_bundle_push_health_check_workflow() {
 
     // Then push if healthy
     if health_check.state == HealthState::Healthy {
-        let result = pusher.push(&instance, "auth-service", "v1.1.0").await;
+        let result = pusher
+            .push(&instance, &PolicyUpdate::new("auth-service", "v1.1.0"))
+            .await;
         assert!(result.is_ok());
         assert!(result.unwrap().success);
     }
@@ -717,9 +1157,9 @@ async fn test_bundle_push_health_check_workflow() {
 #[tokio::test]
 async fn test_bundle_push_initial_deployment() {
     use eunomia_distributor::instance::{Instance, InstanceMetadata, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
-    let pusher = PolicyPusher::new(PushConfig::default());
+    let pusher = PolicyPusher::simulated(PushConfig::default());
 
     // Instance with no prior policy version (fresh deployment)
     let mut instance = Instance::new("archimedes-new", "localhost:9092")
@@ -729,7 +1169,9 @@ async fn test_bundle_push_initial_deployment() {
         last_check: std::time::Instant::now(),
     });
 
-    let result = pusher.push(&instance, "new-service", "v1.0.0").await;
+    let result = pusher
+        .push(&instance, &PolicyUpdate::new("new-service", "v1.0.0"))
+        .await;
 
     assert!(result.is_ok());
     let push_result = result.unwrap();
@@ -741,9 +1183,9 @@ async fn test_bundle_push_initial_deployment() {
 #[tokio::test]
 async fn test_bundle_push_tracks_duration() {
     use eunomia_distributor::instance::{Instance, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
-    let pusher = PolicyPusher::new(PushConfig::default());
+    let pusher = PolicyPusher::simulated(PushConfig::default());
 
     let mut instance = Instance::new("archimedes-1", "localhost:9091");
     instance.update_status(InstanceStatus::Healthy {
@@ -751,7 +1193,9 @@ async fn test_bundle_push_tracks_duration() {
         last_check: std::time::Instant::now(),
     });
 
-    let result = pusher.push(&instance, "metrics-service", "v1.0.0").await;
+    let result = pusher
+        .push(&instance, &PolicyUpdate::new("metrics-service", "v1.0.0"))
+        .await;
 
     assert!(result.is_ok());
     let push_result = result.unwrap();
@@ -763,9 +1207,9 @@ async fn test_bundle_push_tracks_duration() {
 #[tokio::test]
 async fn test_bundle_push_to_unhealthy_instance() {
     use eunomia_distributor::instance::{Instance, InstanceStatus};
-    use eunomia_distributor::pusher::{PolicyPusher, PushConfig};
+    use eunomia_distributor::pusher::{PolicyPusher, PolicyUpdate, PushConfig};
 
-    let pusher = PolicyPusher::new(PushConfig::default());
+    let pusher = PolicyPusher::simulated(PushConfig::default());
 
     let mut instance = Instance::new("archimedes-sick", "localhost:9091");
     instance.update_status(InstanceStatus::Unhealthy {
@@ -774,7 +1218,9 @@ async fn test_bundle_push_to_unhealthy_instance() {
     });
 
     // Push should still be attempted to unhealthy (but not unreachable) instances
-    let result = pusher.push(&instance, "test-service", "v1.0.0").await;
+    let result = pusher
+        .push(&instance, &PolicyUpdate::new("test-service", "v1.0.0"))
+        .await;
 
     assert!(result.is_ok());
     // Unhealthy instances might still accept pushes (they're reachable)
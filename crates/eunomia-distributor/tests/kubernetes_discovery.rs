@@ -0,0 +1,146 @@
+//! Kubernetes Discovery Tests
+//!
+//! These tests run `KubernetesDiscovery` against a mock Kubernetes API
+//! server, so no cluster is needed:
+//! - Pod listing by namespace and label selector
+//! - Mapping ready pods to instances
+//! - Re-listing on refresh
+//!
+//! Run with: `cargo test --features kubernetes --test kubernetes_discovery`
+
+use http::{Request, Response};
+use kube::client::Body;
+use serde_json::{json, Value};
+use tower_test::mock::Handle;
+
+use eunomia_distributor::{Discovery, KubernetesDiscovery};
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Builds a pod of the users service with an Archimedes container.
+fn pod(name: &str, ip: &str, ready: bool) -> Value {
+    json!({
+        "apiVersion": "v1",
+        "kind": "Pod",
+        "metadata": {
+            "name": name,
+            "namespace": "prod",
+            "labels": {"app.kubernetes.io/name": "users-service"},
+        },
+        "spec": {
+            "containers": [{
+                "name": "archimedes",
+                "ports": [
+                    {"name": "http", "containerPort": 8080},
+                    {"name": "grpc", "containerPort": 9090},
+                ],
+            }],
+        },
+        "status": {
+            "podIP": ip,
+            "conditions": [{"type": "Ready", "status": if ready { "True" } else { "False" }}],
+        },
+    })
+}
+
+/// Answers the next request with a pod list and returns the request URI.
+async fn serve_pods(handle: &mut Handle<Request<Body>, Response<Body>>, pods: &[Value]) -> String {
+    let (request, send) = handle.next_request().await.expect("no request");
+    let list = json!({
+        "apiVersion": "v1",
+        "kind": "PodList",
+        "metadata": {"resourceVersion": "1"},
+        "items": pods,
+    });
+    send.send_response(Response::new(Body::from(
+        serde_json::to_vec(&list).unwrap(),
+    )));
+    request.uri().to_string()
+}
+
+fn discovery(
+    namespace: Option<&str>,
+) -> (KubernetesDiscovery, Handle<Request<Body>, Response<Body>>) {
+    let (service, handle) = tower_test::mock::pair::<Request<Body>, Response<Body>>();
+    let discovery = KubernetesDiscovery::with_client(
+        kube::Client::new(service, "default"),
+        namespace.map(str::to_string),
+        Some("app.kubernetes.io/name=users-service".to_string()),
+        Some("grpc".to_string()),
+    );
+    (discovery, handle)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[tokio::test]
+async fn test_lists_ready_pods() {
+    let (discovery, mut handle) = discovery(Some("prod"));
+
+    let server = tokio::spawn(async move {
+        serve_pods(
+            &mut handle,
+            &[
+                pod("users-1", "10.0.0.1", true),
+                pod("users-2", "10.0.0.2", true),
+                pod("users-3", "10.0.0.3", false),
+            ],
+        )
+        .await
+    });
+
+    let mut instances = discovery.discover("users-service").await.unwrap();
+    instances.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let uri = server.await.unwrap();
+    assert!(uri.starts_with("/api/v1/namespaces/prod/pods?"));
+    assert!(uri.contains("labelSelector=app.kubernetes.io%2Fname%3Dusers-service"));
+
+    assert_eq!(instances.len(), 2);
+    assert_eq!(instances[0].id, "users-1");
+    assert_eq!(instances[0].endpoint.to_uri(), "http://10.0.0.1:9090");
+    assert_eq!(instances[0].metadata.namespace.as_deref(), Some("prod"));
+    assert_eq!(instances[1].id, "users-2");
+    assert_eq!(instances[1].endpoint.to_uri(), "http://10.0.0.2:9090");
+
+    assert!(discovery
+        .discover("orders-service")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_all_namespaces() {
+    let (discovery, mut handle) = discovery(None);
+
+    let server = tokio::spawn(async move { serve_pods(&mut handle, &[]).await });
+
+    discovery.refresh().await.unwrap();
+    assert!(server.await.unwrap().starts_with("/api/v1/pods?"));
+}
+
+#[tokio::test]
+async fn test_refresh_relists_pods() {
+    let (discovery, mut handle) = discovery(Some("prod"));
+
+    let server = tokio::spawn(async move {
+        serve_pods(&mut handle, &[pod("users-1", "10.0.0.1", true)]).await;
+        serve_pods(&mut handle, &[pod("users-2", "10.0.0.2", true)]).await;
+    });
+
+    let instances = discovery.all_instances().await.unwrap();
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].id, "users-1");
+
+    discovery.refresh().await.unwrap();
+    server.await.unwrap();
+
+    let instances = discovery.all_instances().await.unwrap();
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].id, "users-2");
+}
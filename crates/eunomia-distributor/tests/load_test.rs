@@ -128,7 +128,7 @@ async fn run_concurrent_signing_test(concurrency: usize) {
 
     for i in 0..concurrency {
         let bundle_clone = bundle.clone();
-        let kp_clone = SigningKeyPair::from_seed(&key_pair.to_bytes()).unwrap();
+        let kp_clone = SigningKeyPair::from_seed(&key_pair.to_bytes().unwrap()).unwrap();
         let metrics_clone = Arc::clone(&metrics);
 
         let handle = tokio::spawn(async move {
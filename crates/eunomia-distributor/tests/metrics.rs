@@ -0,0 +1,107 @@
+//! Metrics endpoint tests (requires the `metrics` feature).
+//!
+//! Runs a deployment through a distributor and scrapes the global metrics
+//! registry from the `/metrics` endpoint served by the gRPC server.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use eunomia_distributor::grpc::{GrpcServer, GrpcServerConfig, GrpcServerError};
+use eunomia_distributor::{DeploymentStrategy, Distributor, DistributorConfig, SimulatedTransport};
+use eunomia_metrics::MetricsRegistry;
+
+fn free_port() -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+async fn scrape(port: u16) -> (reqwest::StatusCode, String) {
+    let response = reqwest::get(format!("http://127.0.0.1:{port}/metrics"))
+        .await
+        .unwrap();
+    (response.status(), response.text().await.unwrap())
+}
+
+#[allow(clippy::float_cmp)] // counters hold whole numbers
+#[tokio::test]
+async fn test_deployment_is_counted_on_metrics_endpoint() {
+    let config = DistributorConfig::builder()
+        .static_endpoints(vec![
+            "instance-1:8080".to_string(),
+            "instance-2:8080".to_string(),
+        ])
+        .build();
+    let distributor = Arc::new(
+        Distributor::new(config)
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default())),
+    );
+
+    let grpc_addr: SocketAddr = format!("127.0.0.1:{}", free_port()).parse().unwrap();
+    let metrics_port = free_port();
+    let handle = GrpcServer::new(Arc::clone(&distributor), GrpcServerConfig::new(grpc_addr))
+        .with_metrics_port(metrics_port)
+        .run()
+        .await
+        .unwrap();
+
+    let (status, body) = scrape(metrics_port).await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert!(
+        !body.contains(r#"eunomia_deployments_total{outcome="success",service="users-service""#),
+        "{body}"
+    );
+
+    let result = distributor
+        .deploy("users-service", "1.0.0", DeploymentStrategy::immediate())
+        .await
+        .unwrap();
+    assert!(result.is_fully_successful());
+    distributor.health_monitor().probe_all().await.unwrap();
+
+    let (_, body) = scrape(metrics_port).await;
+    assert!(
+        body.contains(
+            r#"eunomia_deployments_total{outcome="success",service="users-service",strategy="immediate"} 1"#
+        ),
+        "{body}"
+    );
+    assert!(body.contains("eunomia_deployment_duration_seconds_bucket"));
+    assert!(body.contains("eunomia_instances_total"), "{body}");
+    assert!(
+        body.contains(r#"eunomia_instances_in_flight{service="users-service"} 0"#),
+        "{body}"
+    );
+    let metrics = MetricsRegistry::global().distributor();
+    assert_eq!(
+        metrics.get_push_failure_count("users-service", "instance-1:8080"),
+        0.0
+    );
+    assert_eq!(
+        metrics.get_deployment_count("users-service", "immediate", "success"),
+        1.0
+    );
+
+    let response = reqwest::get(format!("http://127.0.0.1:{metrics_port}/other"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    handle.shutdown_and_wait().await;
+}
+
+#[tokio::test]
+async fn test_metrics_port_in_use_fails_start() {
+    let config = DistributorConfig::builder().build();
+    let distributor = Arc::new(Distributor::new(config).await.unwrap());
+    let grpc_addr: SocketAddr = format!("127.0.0.1:{}", free_port()).parse().unwrap();
+    let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+
+    let err = GrpcServer::new(distributor, GrpcServerConfig::new(grpc_addr))
+        .with_metrics_port(taken.local_addr().unwrap().port())
+        .run()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, GrpcServerError::Bind { .. }), "{err}");
+}
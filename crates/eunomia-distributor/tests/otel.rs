@@ -0,0 +1,113 @@
+//! OpenTelemetry tracing tests (requires the `eunomia-otel` feature).
+//!
+//! Installs a global tracer provider exporting to memory and checks the
+//! spans of deployments, both called directly and through the gRPC control
+//! plane with a propagated trace context.
+
+use std::sync::{Arc, OnceLock};
+
+use opentelemetry::trace::{Span, SpanId, TraceContextExt, Tracer};
+use opentelemetry::{global, Context, Value};
+use opentelemetry_sdk::export::trace::SpanData;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+use opentelemetry_sdk::trace::TracerProvider;
+use tonic::Request;
+
+use eunomia_distributor::grpc::types::DeployPolicyRequest;
+use eunomia_distributor::grpc::{ControlPlane, ControlPlaneService};
+use eunomia_distributor::otel::{inject_context, DEPLOY_SPAN, PUSH_INSTANCE_SPAN};
+use eunomia_distributor::{DeploymentStrategy, Distributor, DistributorConfig, SimulatedTransport};
+
+/// Installs the global tracer provider once per test binary.
+fn exporter() -> &'static InMemorySpanExporter {
+    static EXPORTER: OnceLock<InMemorySpanExporter> = OnceLock::new();
+    EXPORTER.get_or_init(|| {
+        let exporter = InMemorySpanExporter::default();
+        global::set_tracer_provider(
+            TracerProvider::builder()
+                .with_simple_exporter(exporter.clone())
+                .build(),
+        );
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        exporter
+    })
+}
+
+/// Returns the finished spans of deployments to `service`.
+fn spans_of(service: &str) -> Vec<SpanData> {
+    exporter()
+        .get_finished_spans()
+        .unwrap()
+        .into_iter()
+        .filter(|span| {
+            span.attributes.iter().any(|kv| {
+                kv.key.as_str() == "eunomia.service" && kv.value == Value::from(service.to_string())
+            })
+        })
+        .collect()
+}
+
+async fn distributor(endpoints: &[&str]) -> Arc<Distributor> {
+    let config = DistributorConfig::builder()
+        .static_endpoints(endpoints.iter().map(ToString::to_string).collect())
+        .build();
+    Arc::new(
+        Distributor::new(config)
+            .await
+            .unwrap()
+            .with_transport(Arc::new(SimulatedTransport::default())),
+    )
+}
+
+#[tokio::test]
+async fn test_deploy_creates_deploy_and_push_spans() {
+    exporter();
+    let distributor = distributor(&["instance-1:8080", "instance-2:8080"]).await;
+
+    distributor
+        .deploy("users-service", "1.0.0", DeploymentStrategy::immediate())
+        .await
+        .unwrap();
+
+    let spans = spans_of("users-service");
+    let deploy: Vec<_> = spans.iter().filter(|s| s.name == DEPLOY_SPAN).collect();
+    let pushes: Vec<_> = spans
+        .iter()
+        .filter(|s| s.name == PUSH_INSTANCE_SPAN)
+        .collect();
+    assert_eq!(deploy.len(), 1);
+    assert_eq!(deploy[0].parent_span_id, SpanId::INVALID);
+    assert_eq!(pushes.len(), 2);
+    for push in pushes {
+        assert_eq!(push.parent_span_id, deploy[0].span_context.span_id());
+        assert_eq!(
+            push.span_context.trace_id(),
+            deploy[0].span_context.trace_id()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_deploy_policy_joins_propagated_trace() {
+    exporter();
+    let service = ControlPlaneService::new(distributor(&["instance-1:8080"]).await);
+
+    let caller = global::tracer("test").start("caller");
+    let caller_context = caller.span_context().clone();
+    let mut request = Request::new(DeployPolicyRequest {
+        service: "orders-service".to_string(),
+        version: "1.0.0".to_string(),
+        strategy: None,
+        target_instances: vec![],
+        reason: "traced deployment".to_string(),
+    });
+    inject_context(&Context::current_with_span(caller), request.metadata_mut());
+
+    service.deploy_policy(request).await.unwrap();
+
+    let spans = spans_of("orders-service");
+    let deploy = spans.iter().find(|s| s.name == DEPLOY_SPAN).unwrap();
+    assert_eq!(deploy.span_context.trace_id(), caller_context.trace_id());
+    assert_eq!(deploy.parent_span_id, caller_context.span_id());
+}
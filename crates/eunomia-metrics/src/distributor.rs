@@ -1,8 +1,16 @@
 //! Distributor metrics for policy push operations.
 
-use prometheus::{CounterVec, Histogram, HistogramOpts, HistogramVec, Opts, Registry};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use prometheus::{
+    CounterVec, Histogram, HistogramOpts, HistogramVec, IntGauge, IntGaugeVec, Opts, Registry,
+};
 use tracing::warn;
 
+/// `service` label of instances whose service is not known.
+const UNKNOWN_SERVICE: &str = "unknown";
+
 /// Metrics for the policy distributor.
 ///
 /// Tracks push success/failure rates, latency, and instance health.
@@ -28,9 +36,48 @@ pub struct DistributorMetrics {
 
     /// Instance health check results (labeled by instance, status).
     health_checks_total: CounterVec,
+
+    /// Finished deployments (labeled by service, strategy, outcome).
+    deployment_outcomes: CounterVec,
+
+    /// Deployment duration histogram (labeled by service, strategy).
+    deployment_duration_seconds: HistogramVec,
+
+    /// Instances targeted by deployments in progress (labeled by service).
+    instances_in_flight: IntGaugeVec,
+
+    /// Failed pushes (labeled by service, endpoint).
+    push_failures_total: CounterVec,
+
+    /// Operational instances in the last probe round (labeled by service).
+    instances_healthy: IntGaugeVec,
+
+    /// Instances in the last probe round (labeled by service).
+    instances_total: IntGaugeVec,
 }
 
 impl DistributorMetrics {
+    /// Finished deployments, by `service`, `strategy` and `outcome`
+    /// (`success`, `failure` or `error`).
+    pub const DEPLOYMENTS_TOTAL: &'static str = "eunomia_deployments_total";
+
+    /// Time taken by finished deployments, by `service` and `strategy`.
+    pub const DEPLOYMENT_DURATION_SECONDS: &'static str = "eunomia_deployment_duration_seconds";
+
+    /// Instances targeted by deployments of a `service` in progress.
+    pub const INSTANCES_IN_FLIGHT: &'static str = "eunomia_instances_in_flight";
+
+    /// Failed pushes, by `service` and instance `endpoint`. A push counts
+    /// once however many attempts it took.
+    pub const PUSH_FAILURES_TOTAL: &'static str = "eunomia_push_failures_total";
+
+    /// Instances of a `service` that were healthy or degraded in the last
+    /// health probe round.
+    pub const INSTANCES_HEALTHY: &'static str = "eunomia_instances_healthy";
+
+    /// Instances of a `service` probed in the last health probe round.
+    pub const INSTANCES_TOTAL: &'static str = "eunomia_instances_total";
+
     /// Creates new distributor metrics and registers them with the given registry.
     pub(crate) fn new(registry: &Registry) -> Self {
         let pushes_total = CounterVec::new(
@@ -102,6 +149,57 @@ impl DistributorMetrics {
         )
         .expect("metric can be created");
 
+        let deployment_outcomes = CounterVec::new(
+            Opts::new(
+                Self::DEPLOYMENTS_TOTAL,
+                "Total number of finished policy deployments",
+            ),
+            &["service", "strategy", "outcome"],
+        )
+        .expect("metric can be created");
+
+        let deployment_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                Self::DEPLOYMENT_DURATION_SECONDS,
+                "Policy deployment duration in seconds",
+            )
+            .buckets(vec![
+                0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0, 900.0, 3600.0,
+            ]),
+            &["service", "strategy"],
+        )
+        .expect("metric can be created");
+
+        let instances_in_flight = IntGaugeVec::new(
+            Opts::new(
+                Self::INSTANCES_IN_FLIGHT,
+                "Number of instances targeted by deployments in progress",
+            ),
+            &["service"],
+        )
+        .expect("metric can be created");
+
+        let push_failures_total = CounterVec::new(
+            Opts::new(
+                Self::PUSH_FAILURES_TOTAL,
+                "Total number of failed policy pushes",
+            ),
+            &["service", "endpoint"],
+        )
+        .expect("metric can be created");
+
+        let instances_healthy = IntGaugeVec::new(
+            Opts::new(Self::INSTANCES_HEALTHY, "Number of healthy instances"),
+            &["service"],
+        )
+        .expect("metric can be created");
+
+        let instances_total = IntGaugeVec::new(
+            Opts::new(Self::INSTANCES_TOTAL, "Number of monitored instances"),
+            &["service"],
+        )
+        .expect("metric can be created");
+
         // Register metrics
         if let Err(e) = registry.register(Box::new(pushes_total.clone())) {
             warn!("Failed to register pushes_total: {e}");
@@ -124,6 +222,24 @@ impl DistributorMetrics {
         if let Err(e) = registry.register(Box::new(health_checks_total.clone())) {
             warn!("Failed to register health_checks_total: {e}");
         }
+        if let Err(e) = registry.register(Box::new(deployment_outcomes.clone())) {
+            warn!("Failed to register deployment_outcomes: {e}");
+        }
+        if let Err(e) = registry.register(Box::new(deployment_duration_seconds.clone())) {
+            warn!("Failed to register deployment_duration_seconds: {e}");
+        }
+        if let Err(e) = registry.register(Box::new(instances_in_flight.clone())) {
+            warn!("Failed to register instances_in_flight: {e}");
+        }
+        if let Err(e) = registry.register(Box::new(push_failures_total.clone())) {
+            warn!("Failed to register push_failures_total: {e}");
+        }
+        if let Err(e) = registry.register(Box::new(instances_healthy.clone())) {
+            warn!("Failed to register instances_healthy: {e}");
+        }
+        if let Err(e) = registry.register(Box::new(instances_total.clone())) {
+            warn!("Failed to register instances_total: {e}");
+        }
 
         Self {
             pushes_total,
@@ -133,6 +249,12 @@ impl DistributorMetrics {
             rollback_duration_ms,
             active_deployments,
             health_checks_total,
+            deployment_outcomes,
+            deployment_duration_seconds,
+            instances_in_flight,
+            push_failures_total,
+            instances_healthy,
+            instances_total,
         }
     }
 
@@ -177,6 +299,77 @@ impl DistributorMetrics {
             .inc();
     }
 
+    /// Records a finished deployment.
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - Service name
+    /// * `strategy` - Deployment strategy
+    /// * `outcome` - `success` if the policy reached every instance,
+    ///   `failure` if pushes to some failed, `error` if the deployment was
+    ///   aborted
+    /// * `duration` - Deployment duration
+    pub fn record_deployment_outcome(
+        &self,
+        service: &str,
+        strategy: &str,
+        outcome: &str,
+        duration: Duration,
+    ) {
+        self.deployment_outcomes
+            .with_label_values(&[service, strategy, outcome])
+            .inc();
+        self.deployment_duration_seconds
+            .with_label_values(&[service, strategy])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Counts the `instances` targeted by a deployment of `service` as in
+    /// flight until the returned guard is dropped.
+    #[must_use]
+    pub fn track_deployment(&self, service: &str, instances: usize) -> InFlightGuard {
+        let gauge = self.instances_in_flight.with_label_values(&[service]);
+        let instances = i64::try_from(instances).unwrap_or(i64::MAX);
+        gauge.add(instances);
+        InFlightGuard { gauge, instances }
+    }
+
+    /// Records a failed push to the instance at `endpoint`.
+    pub fn record_push_failure(&self, service: &str, endpoint: &str) {
+        self.push_failures_total
+            .with_label_values(&[service, endpoint])
+            .inc();
+    }
+
+    /// Records a health probe round, given the service (if known) of each
+    /// probed instance and whether it is operational.
+    ///
+    /// Services without instances in the round are no longer reported.
+    pub fn record_instance_health<'a>(
+        &self,
+        instances: impl IntoIterator<Item = (Option<&'a str>, bool)>,
+    ) {
+        let mut counts: HashMap<&str, (i64, i64)> = HashMap::new();
+        for (service, healthy) in instances {
+            let (healthy_count, total) = counts
+                .entry(service.unwrap_or(UNKNOWN_SERVICE))
+                .or_default();
+            *healthy_count += i64::from(healthy);
+            *total += 1;
+        }
+
+        self.instances_healthy.reset();
+        self.instances_total.reset();
+        for (service, (healthy, total)) in counts {
+            self.instances_healthy
+                .with_label_values(&[service])
+                .set(healthy);
+            self.instances_total
+                .with_label_values(&[service])
+                .set(total);
+        }
+    }
+
     /// Records a health check result.
     pub fn record_health_check(&self, instance: &str, healthy: bool) {
         let status = if healthy { "healthy" } else { "unhealthy" };
@@ -202,6 +395,52 @@ impl DistributorMetrics {
             .with_label_values(&[service, status])
             .get()
     }
+
+    /// Returns the number of finished deployments with these labels.
+    #[must_use]
+    pub fn get_deployment_count(&self, service: &str, strategy: &str, outcome: &str) -> f64 {
+        self.deployment_outcomes
+            .with_label_values(&[service, strategy, outcome])
+            .get()
+    }
+
+    /// Returns the number of instances of a service in flight.
+    #[must_use]
+    pub fn get_instances_in_flight(&self, service: &str) -> i64 {
+        self.instances_in_flight.with_label_values(&[service]).get()
+    }
+
+    /// Returns the number of failed pushes to an instance endpoint.
+    #[must_use]
+    pub fn get_push_failure_count(&self, service: &str, endpoint: &str) -> f64 {
+        self.push_failures_total
+            .with_label_values(&[service, endpoint])
+            .get()
+    }
+
+    /// Returns the healthy and total instances of a service in the last
+    /// probe round.
+    #[must_use]
+    pub fn get_instance_counts(&self, service: &str) -> (i64, i64) {
+        (
+            self.instances_healthy.with_label_values(&[service]).get(),
+            self.instances_total.with_label_values(&[service]).get(),
+        )
+    }
+}
+
+/// Instances of a deployment counted in
+/// [`DistributorMetrics::INSTANCES_IN_FLIGHT`] until dropped.
+#[derive(Debug)]
+pub struct InFlightGuard {
+    gauge: IntGauge,
+    instances: i64,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.gauge.sub(self.instances);
+    }
 }
 
 #[cfg(test)]
@@ -271,4 +510,81 @@ mod tests {
         metrics.record_deployment("test-service", "canary");
         // Counter increments, verify it doesn't panic
     }
+
+    #[test]
+    fn test_record_deployment_outcome() {
+        let (registry, metrics) = create_test_registry();
+
+        metrics.record_deployment_outcome(
+            "test-service",
+            "canary",
+            "success",
+            Duration::from_millis(1500),
+        );
+        metrics.record_deployment_outcome(
+            "test-service",
+            "canary",
+            "failure",
+            Duration::from_secs(2),
+        );
+
+        assert_eq!(
+            metrics.get_deployment_count("test-service", "canary", "success"),
+            1.0
+        );
+        let families = registry.gather();
+        let duration = families
+            .iter()
+            .find(|f| f.get_name() == DistributorMetrics::DEPLOYMENT_DURATION_SECONDS)
+            .unwrap();
+        assert_eq!(
+            duration.get_metric()[0].get_histogram().get_sample_count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_push_metrics() {
+        let (_, metrics) = create_test_registry();
+
+        let in_flight = metrics.track_deployment("test-service", 3);
+        let other = metrics.track_deployment("test-service", 2);
+        assert_eq!(metrics.get_instances_in_flight("test-service"), 5);
+        drop(in_flight);
+        assert_eq!(metrics.get_instances_in_flight("test-service"), 2);
+        drop(other);
+        assert_eq!(metrics.get_instances_in_flight("test-service"), 0);
+
+        metrics.record_push_failure("test-service", "instance-1:8080");
+        assert_eq!(
+            metrics.get_push_failure_count("test-service", "instance-1:8080"),
+            1.0
+        );
+        assert_eq!(
+            metrics.get_push_failure_count("test-service", "instance-2:8080"),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_record_instance_health_replaces_previous_round() {
+        let (registry, metrics) = create_test_registry();
+
+        metrics.record_instance_health([
+            (Some("users-service"), true),
+            (Some("users-service"), false),
+            (Some("orders-service"), true),
+        ]);
+        assert_eq!(metrics.get_instance_counts("users-service"), (1, 2));
+        assert_eq!(metrics.get_instance_counts("orders-service"), (1, 1));
+
+        metrics.record_instance_health([(None, true)]);
+        let families = registry.gather();
+        let total = families
+            .iter()
+            .find(|f| f.get_name() == DistributorMetrics::INSTANCES_TOTAL)
+            .unwrap();
+        assert_eq!(total.get_metric().len(), 1);
+        assert_eq!(total.get_metric()[0].get_label()[0].get_value(), "unknown");
+    }
 }
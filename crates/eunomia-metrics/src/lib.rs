@@ -65,7 +65,7 @@ mod registry;
 mod server;
 
 pub use compiler::CompilerMetrics;
-pub use distributor::DistributorMetrics;
+pub use distributor::{DistributorMetrics, InFlightGuard};
 pub use registry::RegistryMetrics;
 pub use server::{serve_metrics, MetricsServerConfig, MetricsServerHandle};
 
@@ -31,11 +31,32 @@ pub struct RegistryMetrics {
 }
 
 impl RegistryMetrics {
+    /// Bundle publishes, by `service` and `status` (`success` or
+    /// `failure`).
+    pub const PUBLISHES_TOTAL: &'static str = "eunomia_registry_publishes_total";
+
+    /// Time taken by bundle publishes, by `service`.
+    pub const PUBLISH_DURATION_MILLISECONDS: &'static str =
+        "eunomia_registry_publish_duration_milliseconds";
+
+    /// Bundle fetches from the registry, by `service` and `status`
+    /// (`success` or `failure`). Fetches served from the cache are not
+    /// included.
+    pub const FETCHES_TOTAL: &'static str = "eunomia_registry_fetches_total";
+
+    /// Time taken by bundle fetches from the registry, by `service`.
+    pub const FETCH_DURATION_MILLISECONDS: &'static str =
+        "eunomia_registry_fetch_duration_milliseconds";
+
+    /// Bundle cache operations, by `operation` (`hit`, `miss` or
+    /// `eviction`).
+    pub const CACHE_OPERATIONS_TOTAL: &'static str = "eunomia_registry_cache_operations_total";
+
     /// Creates new registry metrics and registers them with the given registry.
     pub(crate) fn new(registry: &Registry) -> Self {
         let publishes_total = CounterVec::new(
             Opts::new(
-                "eunomia_registry_publishes_total",
+                Self::PUBLISHES_TOTAL,
                 "Total number of bundle publish operations",
             ),
             &["service", "status"],
@@ -44,7 +65,7 @@ impl RegistryMetrics {
 
         let publish_duration_ms = HistogramVec::new(
             HistogramOpts::new(
-                "eunomia_registry_publish_duration_milliseconds",
+                Self::PUBLISH_DURATION_MILLISECONDS,
                 "Bundle publish duration in milliseconds",
             )
             .buckets(vec![
@@ -56,7 +77,7 @@ impl RegistryMetrics {
 
         let fetches_total = CounterVec::new(
             Opts::new(
-                "eunomia_registry_fetches_total",
+                Self::FETCHES_TOTAL,
                 "Total number of bundle fetch operations",
             ),
             &["service", "status"],
@@ -65,7 +86,7 @@ impl RegistryMetrics {
 
         let fetch_duration_ms = HistogramVec::new(
             HistogramOpts::new(
-                "eunomia_registry_fetch_duration_milliseconds",
+                Self::FETCH_DURATION_MILLISECONDS,
                 "Bundle fetch duration in milliseconds",
             )
             .buckets(vec![
@@ -77,7 +98,7 @@ impl RegistryMetrics {
 
         let cache_operations_total = CounterVec::new(
             Opts::new(
-                "eunomia_registry_cache_operations_total",
+                Self::CACHE_OPERATIONS_TOTAL,
                 "Total number of cache operations",
             ),
             &["operation"], // hit, miss, eviction
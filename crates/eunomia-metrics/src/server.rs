@@ -36,6 +36,7 @@ impl Default for MetricsServerConfig {
 }
 
 /// Handle for the running metrics server.
+#[derive(Debug)]
 pub struct MetricsServerHandle {
     /// Shutdown signal sender.
     shutdown_tx: oneshot::Sender<()>,
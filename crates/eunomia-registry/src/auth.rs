@@ -0,0 +1,287 @@
+//! Bearer token challenge flow for OCI registries.
+//!
+//! Registries such as Docker Hub, GHCR and Harbor reject anonymous or
+//! basic-authenticated requests with `401 Unauthorized` and a
+//! `WWW-Authenticate: Bearer realm=...,service=...,scope=...` challenge.
+//! The client must fetch a short-lived token from the realm and retry the
+//! request with `Authorization: Bearer <token>`.
+//!
+//! This module parses those challenges and caches the issued tokens per
+//! repository and scope until they expire.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+/// Token lifetime assumed when the token service omits `expires_in`.
+///
+/// The Docker token specification mandates a minimum of 60 seconds.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::from_secs(60);
+
+/// Margin subtracted from a token's lifetime so it is refreshed before the
+/// registry starts rejecting it.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(5);
+
+/// A parsed `WWW-Authenticate: Bearer` challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BearerChallenge {
+    /// Token service URL.
+    pub realm: String,
+    /// Service name to request the token for.
+    pub service: Option<String>,
+    /// Scope the registry requires (e.g. `repository:foo:pull`).
+    pub scope: Option<String>,
+}
+
+impl BearerChallenge {
+    /// Parses a `WWW-Authenticate` header value.
+    ///
+    /// Returns `None` if the header is not a Bearer challenge or has no realm.
+    #[must_use]
+    pub fn parse(header: &str) -> Option<Self> {
+        let header = header.trim();
+        let (scheme, params) = header.split_once(char::is_whitespace)?;
+        if !scheme.eq_ignore_ascii_case("bearer") {
+            return None;
+        }
+
+        let mut realm = None;
+        let mut service = None;
+        let mut scope = None;
+
+        for (key, value) in parse_params(params) {
+            match key.to_ascii_lowercase().as_str() {
+                "realm" => realm = Some(value),
+                "service" => service = Some(value),
+                "scope" => scope = Some(value),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            realm: realm?,
+            service,
+            scope,
+        })
+    }
+}
+
+/// Splits `key="value",key=value` auth parameters, honouring commas inside
+/// quoted values (scopes such as `repository:foo:pull,push` contain them).
+fn parse_params(params: &str) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let mut rest = params.trim();
+
+    while !rest.is_empty() {
+        let Some((key, after_key)) = rest.split_once('=') else {
+            break;
+        };
+        let key = key.trim().trim_start_matches(',').trim().to_string();
+        let after_key = after_key.trim_start();
+
+        let (value, remaining) = after_key.strip_prefix('"').map_or_else(
+            || {
+                after_key.find(',').map_or_else(
+                    || (after_key.trim().to_string(), ""),
+                    |end| (after_key[..end].trim().to_string(), &after_key[end..]),
+                )
+            },
+            |quoted| {
+                quoted.find('"').map_or_else(
+                    || (quoted.to_string(), ""),
+                    |end| (quoted[..end].to_string(), &quoted[end + 1..]),
+                )
+            },
+        );
+
+        result.push((key, value));
+        rest = remaining.trim_start().trim_start_matches(',').trim_start();
+    }
+
+    result
+}
+
+/// Response body returned by a registry token service.
+#[derive(Debug, Deserialize)]
+pub struct TokenResponse {
+    /// Token to use in the `Authorization` header.
+    #[serde(default)]
+    token: Option<String>,
+    /// OAuth2-compatible alias for `token`.
+    #[serde(default)]
+    access_token: Option<String>,
+    /// Token lifetime in seconds.
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+impl TokenResponse {
+    /// Returns the issued token, preferring `token` over `access_token`.
+    #[must_use]
+    pub fn into_token(self) -> Option<(String, Duration)> {
+        let lifetime = self
+            .expires_in
+            .map_or(DEFAULT_TOKEN_LIFETIME, Duration::from_secs);
+        self.token
+            .or(self.access_token)
+            .filter(|t| !t.is_empty())
+            .map(|t| (t, lifetime))
+    }
+}
+
+#[derive(Clone)]
+struct CachedToken {
+    token: String,
+    expires_at: Instant,
+}
+
+/// Bearer tokens cached per repository and scope.
+#[derive(Default)]
+pub struct TokenCache {
+    tokens: Mutex<HashMap<String, CachedToken>>,
+}
+
+impl std::fmt::Debug for TokenCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print the tokens themselves.
+        let entries = self.tokens.lock().map_or(0, |tokens| tokens.len());
+        f.debug_struct("TokenCache")
+            .field("entries", &entries)
+            .finish()
+    }
+}
+
+impl TokenCache {
+    /// Creates an empty token cache.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached token for `scope` if it has not expired.
+    pub fn get(&self, scope: &str) -> Option<String> {
+        let mut tokens = self.tokens.lock().ok()?;
+        match tokens.get(scope) {
+            Some(cached) if cached.expires_at > Instant::now() => Some(cached.token.clone()),
+            Some(_) => {
+                tokens.remove(scope);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Caches `token` for `scope` for the given lifetime.
+    pub fn insert(&self, scope: impl Into<String>, token: impl Into<String>, lifetime: Duration) {
+        let lifetime = lifetime.saturating_sub(EXPIRY_MARGIN);
+        if let Ok(mut tokens) = self.tokens.lock() {
+            tokens.insert(
+                scope.into(),
+                CachedToken {
+                    token: token.into(),
+                    expires_at: Instant::now() + lifetime,
+                },
+            );
+        }
+    }
+
+    /// Removes the cached token for `scope`.
+    pub fn invalidate(&self, scope: &str) {
+        if let Ok(mut tokens) = self.tokens.lock() {
+            tokens.remove(scope);
+        }
+    }
+}
+
+/// Returns the token scope for an operation on `repository`.
+#[must_use]
+pub fn repository_scope(repository: &str, actions: &str) -> String {
+    format!("repository:{repository}:{actions}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_challenge() {
+        let challenge = BearerChallenge::parse(
+            r#"Bearer realm="https://auth.docker.io/token",service="registry.docker.io",scope="repository:policies/users-service:pull,push""#,
+        )
+        .unwrap();
+
+        assert_eq!(challenge.realm, "https://auth.docker.io/token");
+        assert_eq!(challenge.service.as_deref(), Some("registry.docker.io"));
+        assert_eq!(
+            challenge.scope.as_deref(),
+            Some("repository:policies/users-service:pull,push")
+        );
+    }
+
+    #[test]
+    fn test_parse_challenge_unquoted_and_case_insensitive() {
+        let challenge =
+            BearerChallenge::parse("bearer Realm=https://ghcr.io/token, service=ghcr.io").unwrap();
+
+        assert_eq!(challenge.realm, "https://ghcr.io/token");
+        assert_eq!(challenge.service.as_deref(), Some("ghcr.io"));
+        assert_eq!(challenge.scope, None);
+    }
+
+    #[test]
+    fn test_parse_challenge_rejects_other_schemes() {
+        assert_eq!(BearerChallenge::parse(r#"Basic realm="registry""#), None);
+        assert_eq!(BearerChallenge::parse(r#"Bearer service="x""#), None);
+        assert_eq!(BearerChallenge::parse(""), None);
+    }
+
+    #[test]
+    fn test_token_response_prefers_token() {
+        let response: TokenResponse =
+            serde_json::from_str(r#"{"token":"a","access_token":"b","expires_in":300}"#).unwrap();
+        assert_eq!(
+            response.into_token(),
+            Some(("a".to_string(), Duration::from_secs(300)))
+        );
+
+        let response: TokenResponse = serde_json::from_str(r#"{"access_token":"b"}"#).unwrap();
+        assert_eq!(
+            response.into_token(),
+            Some(("b".to_string(), DEFAULT_TOKEN_LIFETIME))
+        );
+
+        let response: TokenResponse = serde_json::from_str("{}").unwrap();
+        assert_eq!(response.into_token(), None);
+    }
+
+    #[test]
+    fn test_token_cache_expiry() {
+        let cache = TokenCache::new();
+        let scope = repository_scope("policies/users-service", "pull");
+
+        cache.insert(&scope, "fresh", Duration::from_secs(300));
+        assert_eq!(cache.get(&scope).as_deref(), Some("fresh"));
+        assert_eq!(cache.get("repository:other:pull"), None);
+
+        // Lifetimes shorter than the refresh margin expire immediately.
+        cache.insert(&scope, "stale", Duration::from_secs(1));
+        assert_eq!(cache.get(&scope), None);
+    }
+
+    #[test]
+    fn test_token_cache_invalidate() {
+        let cache = TokenCache::new();
+        cache.insert("scope", "token", Duration::from_secs(300));
+        cache.invalidate("scope");
+        assert_eq!(cache.get("scope"), None);
+    }
+
+    #[test]
+    fn test_token_cache_debug_hides_tokens() {
+        let cache = TokenCache::new();
+        cache.insert("scope", "secret-token", Duration::from_secs(300));
+        assert!(!format!("{cache:?}").contains("secret-token"));
+    }
+}
@@ -1,12 +1,21 @@
 //! Local bundle cache for offline access and performance.
 //!
 //! Provides a file-based cache with LRU eviction for policy bundles.
+//!
+//! Entries are evicted when they outlive [`CacheConfig::ttl`], and the
+//! least recently used entry is evicted when more than
+//! [`CacheConfig::max_entries`] are cached. The cache directory may also be
+//! trimmed to [`CacheConfig::max_size`] bytes, oldest files first.
 
 use crate::error::RegistryError;
+use eunomia_core::signing::SignatureFile;
 use eunomia_core::Bundle;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
 
 /// Configuration for the bundle cache.
 #[derive(Debug, Clone)]
@@ -17,20 +26,30 @@ pub struct CacheConfig {
     /// Maximum cache size in bytes (default: 1GB).
     pub max_size: u64,
 
-    /// Time-to-live for cache entries (default: 7 days).
-    pub ttl: Duration,
+    /// Maximum number of cached bundles (default: 1000).
+    pub max_entries: usize,
+
+    /// Time-to-live for cache entries, from when they were cached (default:
+    /// 7 days). Entries never expire when unset.
+    pub ttl: Option<Duration>,
 
     /// Enable cache integrity verification.
     pub verify_checksums: bool,
+
+    /// Compare the cached manifest digest with the registry before serving
+    /// a cached bundle, so re-pushed tags are not served stale.
+    pub validate_digest: bool,
 }
 
 impl Default for CacheConfig {
     fn default() -> Self {
         Self {
             dir: dirs_default_cache_dir(),
-            max_size: 1024 * 1024 * 1024,               // 1GB
-            ttl: Duration::from_secs(7 * 24 * 60 * 60), // 7 days
+            max_size: 1024 * 1024 * 1024, // 1GB
+            max_entries: 1000,
+            ttl: Some(Duration::from_secs(7 * 24 * 60 * 60)), // 7 days
             verify_checksums: true,
+            validate_digest: true,
         }
     }
 }
@@ -52,10 +71,24 @@ impl CacheConfig {
         self
     }
 
+    /// Sets the maximum number of cached bundles (at least one).
+    #[must_use]
+    pub const fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = if max_entries == 0 { 1 } else { max_entries };
+        self
+    }
+
     /// Sets the TTL for cache entries.
     #[must_use]
     pub const fn with_ttl(mut self, ttl: Duration) -> Self {
-        self.ttl = ttl;
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Keeps cache entries until they are evicted for space.
+    #[must_use]
+    pub const fn without_ttl(mut self) -> Self {
+        self.ttl = None;
         self
     }
 
@@ -65,6 +98,13 @@ impl CacheConfig {
         self.verify_checksums = verify;
         self
     }
+
+    /// Enables or disables manifest digest validation on fetch.
+    #[must_use]
+    pub const fn with_validate_digest(mut self, validate: bool) -> Self {
+        self.validate_digest = validate;
+        self
+    }
 }
 
 /// Default cache directory.
@@ -74,10 +114,40 @@ fn dirs_default_cache_dir() -> PathBuf {
         .join("eunomia")
 }
 
+/// Service and version of a cached bundle.
+type EntryKey = (String, String);
+
+/// Bookkeeping of a cached bundle.
+#[derive(Debug, Clone, Copy)]
+struct CacheEntry {
+    /// When the bundle was cached.
+    cached_at: Instant,
+    /// When the bundle was last cached or read.
+    last_access: Instant,
+}
+
+/// Cache usage counters, for monitoring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups that found a usable bundle.
+    pub hits: u64,
+
+    /// Lookups that found no bundle, or an expired or corrupt one.
+    pub misses: u64,
+
+    /// Entries evicted because they expired or the cache was full.
+    pub evictions: u64,
+}
+
 /// File-based bundle cache with LRU eviction.
 #[derive(Debug)]
 pub struct BundleCache {
     config: CacheConfig,
+    /// Cached bundles, loaded from the cache directory on creation.
+    entries: Mutex<HashMap<EntryKey, CacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
 }
 
 impl BundleCache {
@@ -114,7 +184,47 @@ impl BundleCache {
             }
         })?;
 
-        Ok(Self { config })
+        let cache = Self::empty(config);
+        let now = SystemTime::now();
+        let mut entries = cache.lock_entries();
+        for entry in walkdir::WalkDir::new(cache.config.dir.join("bundles"))
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            let (Some(key), Ok(modified)) = (
+                entry_key(entry.path()),
+                entry
+                    .metadata()
+                    .map_err(std::io::Error::from)
+                    .and_then(|m| m.modified()),
+            ) else {
+                continue;
+            };
+            let age = now.duration_since(modified).unwrap_or_default();
+            let cached_at = Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+            entries.insert(
+                key,
+                CacheEntry {
+                    cached_at,
+                    last_access: cached_at,
+                },
+            );
+        }
+        drop(entries);
+
+        Ok(cache)
+    }
+
+    /// Creates a cache with no entries, without touching the cache
+    /// directory.
+    fn empty(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
     }
 
     /// Returns the cache configuration.
@@ -123,6 +233,28 @@ impl BundleCache {
         &self.config
     }
 
+    /// Returns the hit, miss and eviction counts of this cache.
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the number of cached bundles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock_entries().len()
+    }
+
+    /// Returns true if no bundles are cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Returns the path to a cached bundle file.
     #[must_use]
     pub fn bundle_path(&self, service: &str, version: &str) -> PathBuf {
@@ -155,22 +287,39 @@ impl BundleCache {
 
     /// Retrieves a bundle from the cache.
     ///
-    /// Returns `None` if the bundle is not cached or has expired.
+    /// Returns `None` if the bundle is not cached or has expired. Expired
+    /// entries are evicted first.
     ///
     /// # Errors
     ///
     /// Returns an error if the cached bundle is corrupt or cannot be read.
     pub fn get(&self, service: &str, version: &str) -> Result<Option<Bundle>, RegistryError> {
+        self.evict_expired()?;
+
+        let bundle = self.load(service, version)?;
+        let counter = if bundle.is_some() {
+            self.touch(service, version);
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(bundle)
+    }
+
+    /// Reads a cached bundle, verifying its checksum if enabled.
+    fn load(&self, service: &str, version: &str) -> Result<Option<Bundle>, RegistryError> {
         let bundle_path = self.bundle_path(service, version);
 
         if !bundle_path.exists() {
             return Ok(None);
         }
 
-        // Check TTL
+        // Check TTL of files cached by other processes
         if self.is_expired(&bundle_path)? {
             tracing::debug!(service, version, "Cache entry expired, removing");
             self.invalidate(service, version)?;
+            self.evictions.fetch_add(1, Ordering::Relaxed);
             return Ok(None);
         }
 
@@ -211,6 +360,24 @@ impl BundleCache {
     ///
     /// Returns an error if the bundle cannot be written to disk.
     pub fn put(&self, service: &str, version: &str, bundle: &Bundle) -> Result<(), RegistryError> {
+        self.put_with_digest(service, version, bundle, None)
+    }
+
+    /// Stores a bundle in the cache along with the registry manifest digest
+    /// it was fetched from.
+    ///
+    /// Any signatures cached for a previous entry are discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be written to disk.
+    pub fn put_with_digest(
+        &self,
+        service: &str,
+        version: &str,
+        bundle: &Bundle,
+        digest: Option<&str>,
+    ) -> Result<(), RegistryError> {
         let bundle_path = self.bundle_path(service, version);
         let manifest_path = self.manifest_path(service, version);
 
@@ -222,6 +389,14 @@ impl BundleCache {
             })?;
         }
 
+        let sig_path = self.signature_path(service, version);
+        if sig_path.exists() {
+            std::fs::remove_file(&sig_path).map_err(|e| RegistryError::IoError {
+                path: sig_path,
+                source: e,
+            })?;
+        }
+
         // Write bundle
         bundle
             .write_to_file(&bundle_path)
@@ -233,6 +408,7 @@ impl BundleCache {
         let manifest_data = serde_json::json!({
             "version": version,
             "checksum": bundle.compute_checksum(),
+            "digest": digest,
             "cached_at": chrono::Utc::now().to_rfc3339(),
         });
 
@@ -247,18 +423,151 @@ impl BundleCache {
 
         tracing::debug!(service, version, "Cached bundle");
 
+        let now = Instant::now();
+        self.lock_entries().insert(
+            (service.to_string(), version.to_string()),
+            CacheEntry {
+                cached_at: now,
+                last_access: now,
+            },
+        );
+        self.evict_least_recently_used()?;
+
         // Enforce size limit
         self.enforce_size_limit()?;
 
         Ok(())
     }
 
+    /// Stores the signatures for a cached bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature file cannot be written.
+    pub fn put_signatures(
+        &self,
+        service: &str,
+        version: &str,
+        signatures: &SignatureFile,
+    ) -> Result<(), RegistryError> {
+        let sig_path = self.signature_path(service, version);
+        if let Some(parent) = sig_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| RegistryError::IoError {
+                path: parent.to_path_buf(),
+                source: e,
+            })?;
+        }
+
+        let json = signatures
+            .to_json()
+            .map_err(|e| RegistryError::CacheError {
+                message: format!("Failed to serialize signatures: {e}"),
+            })?;
+        std::fs::write(&sig_path, json).map_err(|e| RegistryError::IoError {
+            path: sig_path,
+            source: e,
+        })
+    }
+
+    /// Retrieves the cached signatures for a bundle.
+    ///
+    /// Returns `None` if no signatures were cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature file exists but cannot be read.
+    pub fn get_signatures(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<Option<SignatureFile>, RegistryError> {
+        let sig_path = self.signature_path(service, version);
+        if !sig_path.exists() {
+            return Ok(None);
+        }
+
+        let json = std::fs::read_to_string(&sig_path).map_err(|e| RegistryError::IoError {
+            path: sig_path,
+            source: e,
+        })?;
+        SignatureFile::from_json(&json)
+            .map(Some)
+            .map_err(|e| RegistryError::CacheError {
+                message: format!("Failed to load cached signatures: {e}"),
+            })
+    }
+
+    /// Returns the manifest digest recorded when the entry was cached.
+    ///
+    /// Returns `None` if the entry is not cached or was stored without a
+    /// digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache metadata cannot be read.
+    pub fn cached_digest(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<Option<String>, RegistryError> {
+        let manifest_path = self.manifest_path(service, version);
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+        Self::read_manifest_field(&manifest_path, "digest")
+    }
+
+    /// Lists the versions of `service` with an unexpired cached bundle,
+    /// sorted by tag.
+    ///
+    /// Used to resolve version queries without contacting the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory cannot be read.
+    pub fn cached_versions(&self, service: &str) -> Result<Vec<String>, RegistryError> {
+        let service_dir = self.config.dir.join("bundles").join(service);
+        if !service_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = std::fs::read_dir(&service_dir).map_err(|e| RegistryError::IoError {
+            path: service_dir.clone(),
+            source: e,
+        })?;
+
+        let mut versions = Vec::new();
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let Some(version) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".bundle.tar.gz"))
+            else {
+                continue;
+            };
+            if !self.is_expired(&path)? {
+                versions.push(version.to_string());
+            }
+        }
+        versions.sort();
+
+        Ok(versions)
+    }
+
     /// Removes a specific entry from the cache.
     ///
     /// # Errors
     ///
     /// Returns an error if files cannot be deleted.
     pub fn invalidate(&self, service: &str, version: &str) -> Result<(), RegistryError> {
+        self.lock_entries()
+            .remove(&(service.to_string(), version.to_string()));
+        self.remove_files(service, version)
+    }
+
+    /// Deletes the files of an entry.
+    fn remove_files(&self, service: &str, version: &str) -> Result<(), RegistryError> {
         let bundle_path = self.bundle_path(service, version);
         let manifest_path = self.manifest_path(service, version);
         let sig_path = self.signature_path(service, version);
@@ -290,6 +599,7 @@ impl BundleCache {
                     .map_err(|e| RegistryError::IoError { path, source: e })?;
             }
         }
+        self.lock_entries().clear();
 
         tracing::info!("Cache cleared");
         Ok(())
@@ -324,6 +634,9 @@ impl BundleCache {
         // Enforce size limit
         stats.size_evicted = self.enforce_size_limit()?;
 
+        self.lock_entries()
+            .retain(|(service, version), _| self.bundle_path(service, version).exists());
+
         Ok(stats)
     }
 
@@ -366,11 +679,77 @@ impl BundleCache {
         })?;
 
         let age = modified.elapsed().unwrap_or(Duration::ZERO);
-        Ok(age > self.config.ttl)
+        Ok(self.config.ttl.is_some_and(|ttl| age > ttl))
+    }
+
+    /// Evicts the entries that outlived the TTL.
+    fn evict_expired(&self) -> Result<(), RegistryError> {
+        let Some(ttl) = self.config.ttl else {
+            return Ok(());
+        };
+        let expired: Vec<EntryKey> = self
+            .lock_entries()
+            .iter()
+            .filter(|(_, entry)| entry.cached_at.elapsed() > ttl)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for (service, version) in expired {
+            tracing::debug!(service, version, "Cache entry expired, evicting");
+            self.invalidate(&service, &version)?;
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Evicts the least recently used entries while there are more than
+    /// [`CacheConfig::max_entries`].
+    fn evict_least_recently_used(&self) -> Result<(), RegistryError> {
+        loop {
+            let lru = {
+                let entries = self.lock_entries();
+                if entries.len() <= self.config.max_entries {
+                    return Ok(());
+                }
+                entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_access)
+                    .map(|(key, _)| key.clone())
+            };
+            let Some((service, version)) = lru else {
+                return Ok(());
+            };
+            tracing::debug!(service, version, "Evicting least recently used cache entry");
+            self.invalidate(&service, &version)?;
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records a read of an entry.
+    fn touch(&self, service: &str, version: &str) {
+        let now = Instant::now();
+        self.lock_entries()
+            .entry((service.to_string(), version.to_string()))
+            .and_modify(|entry| entry.last_access = now)
+            .or_insert(CacheEntry {
+                cached_at: now,
+                last_access: now,
+            });
+    }
+
+    fn lock_entries(&self) -> MutexGuard<'_, HashMap<EntryKey, CacheEntry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
     }
 
     /// Reads the stored checksum from a manifest file.
     fn read_stored_checksum(path: &Path) -> Result<Option<String>, RegistryError> {
+        Self::read_manifest_field(path, "checksum")
+    }
+
+    /// Reads a string field from a cache manifest file.
+    fn read_manifest_field(path: &Path, field: &str) -> Result<Option<String>, RegistryError> {
         let content = std::fs::read_to_string(path).map_err(|e| RegistryError::IoError {
             path: path.to_path_buf(),
             source: e,
@@ -378,7 +757,7 @@ impl BundleCache {
 
         let manifest: serde_json::Value = serde_json::from_str(&content)?;
         Ok(manifest
-            .get("checksum")
+            .get(field)
             .and_then(|v| v.as_str())
             .map(String::from))
     }
@@ -436,6 +815,13 @@ impl BundleCache {
     }
 }
 
+/// Returns the service and version of a cached bundle file.
+fn entry_key(path: &Path) -> Option<EntryKey> {
+    let version = path.file_name()?.to_str()?.strip_suffix(".bundle.tar.gz")?;
+    let service = path.parent()?.file_name()?.to_str()?;
+    Some((service.to_string(), version.to_string()))
+}
+
 /// Statistics from a cache prune operation.
 #[derive(Debug, Default)]
 pub struct PruneStats {
@@ -454,7 +840,10 @@ mod tests {
     fn test_cache_config_default() {
         let config = CacheConfig::default();
         assert_eq!(config.max_size, 1024 * 1024 * 1024);
+        assert_eq!(config.max_entries, 1000);
+        assert!(config.ttl.is_some());
         assert!(config.verify_checksums);
+        assert!(config.validate_digest);
     }
 
     #[test]
@@ -462,18 +851,20 @@ mod tests {
         let config = CacheConfig::new("/tmp/test-cache")
             .with_max_size(100 * 1024 * 1024)
             .with_ttl(Duration::from_secs(3600))
-            .with_verify_checksums(false);
+            .with_verify_checksums(false)
+            .with_validate_digest(false);
 
         assert_eq!(config.dir, PathBuf::from("/tmp/test-cache"));
         assert_eq!(config.max_size, 100 * 1024 * 1024);
-        assert_eq!(config.ttl, Duration::from_secs(3600));
+        assert_eq!(config.ttl, Some(Duration::from_secs(3600)));
         assert!(!config.verify_checksums);
+        assert!(!config.validate_digest);
     }
 
     #[test]
     fn test_bundle_path() {
         let config = CacheConfig::new("/cache");
-        let cache = BundleCache { config };
+        let cache = BundleCache::empty(config);
 
         let path = cache.bundle_path("users-service", "v1.2.0");
         assert_eq!(
@@ -485,7 +876,7 @@ mod tests {
     #[test]
     fn test_manifest_path() {
         let config = CacheConfig::new("/cache");
-        let cache = BundleCache { config };
+        let cache = BundleCache::empty(config);
 
         let path = cache.manifest_path("users-service", "v1.2.0");
         assert_eq!(
@@ -497,7 +888,7 @@ mod tests {
     #[test]
     fn test_signature_path() {
         let config = CacheConfig::new("/cache");
-        let cache = BundleCache { config };
+        let cache = BundleCache::empty(config);
 
         let path = cache.signature_path("users-service", "v1.2.0");
         assert_eq!(
@@ -505,4 +896,137 @@ mod tests {
             PathBuf::from("/cache/signatures/users-service/v1.2.0.sig")
         );
     }
+
+    #[test]
+    fn test_put_with_digest_records_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let bundle = Bundle::builder("users-service").version("1.2.0").build();
+
+        cache
+            .put_with_digest("users-service", "v1.2.0", &bundle, Some("sha256:abc"))
+            .unwrap();
+        assert_eq!(
+            cache.cached_digest("users-service", "v1.2.0").unwrap(),
+            Some("sha256:abc".to_string())
+        );
+
+        cache.put("users-service", "v1.2.0", &bundle).unwrap();
+        assert_eq!(
+            cache.cached_digest("users-service", "v1.2.0").unwrap(),
+            None
+        );
+        assert_eq!(
+            cache.cached_digest("users-service", "v9.9.9").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_signatures_roundtrip_and_reset_on_put() {
+        use eunomia_core::signing::{BundleSigner, SigningKeyPair};
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let bundle = Bundle::builder("users-service").version("1.2.0").build();
+        let signer = BundleSigner::from_key_pair(&SigningKeyPair::generate(), "k".to_string());
+        let signed = signer.sign(&bundle);
+
+        cache.put("users-service", "v1.2.0", &bundle).unwrap();
+        cache
+            .put_signatures("users-service", "v1.2.0", &signed.signatures)
+            .unwrap();
+        let cached = cache.get_signatures("users-service", "v1.2.0").unwrap();
+        assert_eq!(cached.map(|s| s.len()), Some(1));
+
+        // Re-caching the bundle drops signatures from the previous entry
+        cache.put("users-service", "v1.2.0", &bundle).unwrap();
+        assert!(cache
+            .get_signatures("users-service", "v1.2.0")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_cached_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let bundle = Bundle::builder("common-policies").version("1.2.0").build();
+
+        assert!(cache.cached_versions("common").unwrap().is_empty());
+
+        cache.put("common", "v1.3.0", &bundle).unwrap();
+        cache.put("common", "v1.2.0", &bundle).unwrap();
+        cache.put("other", "v2.0.0", &bundle).unwrap();
+
+        assert_eq!(
+            cache.cached_versions("common").unwrap(),
+            vec!["v1.2.0", "v1.3.0"]
+        );
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path()).with_max_entries(2)).unwrap();
+        let bundle = Bundle::builder("users-service").version("1.0.0").build();
+
+        cache.put("users-service", "v1.0.0", &bundle).unwrap();
+        cache.put("users-service", "v1.1.0", &bundle).unwrap();
+        // Reading the oldest entry makes v1.1.0 the least recently used
+        assert!(cache.get("users-service", "v1.0.0").unwrap().is_some());
+        cache.put("users-service", "v1.2.0", &bundle).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("users-service", "v1.1.0").unwrap().is_none());
+        assert!(!cache.bundle_path("users-service", "v1.1.0").exists());
+        assert!(cache.get("users-service", "v1.0.0").unwrap().is_some());
+        assert!(cache.get("users-service", "v1.2.0").unwrap().is_some());
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 3,
+                misses: 1,
+                evictions: 1,
+            }
+        );
+
+        // Entries already on disk are loaded, and count toward the limit
+        let cache = BundleCache::new(CacheConfig::new(dir.path()).with_max_entries(2)).unwrap();
+        assert_eq!(cache.len(), 2);
+        cache.put("users-service", "v1.3.0", &bundle).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_evicts_expired_entry_on_get() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = CacheConfig::new(dir.path()).with_ttl(Duration::from_millis(50));
+        let cache = BundleCache::new(config).unwrap();
+        let bundle = Bundle::builder("users-service").version("1.0.0").build();
+
+        cache.put("users-service", "v1.0.0", &bundle).unwrap();
+        assert!(cache.get("users-service", "v1.0.0").unwrap().is_some());
+
+        std::thread::sleep(Duration::from_millis(100));
+        // Any lookup evicts expired entries
+        assert!(cache.get("users-service", "v2.0.0").unwrap().is_none());
+
+        assert!(cache.is_empty());
+        assert!(!cache.bundle_path("users-service", "v1.0.0").exists());
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                hits: 1,
+                misses: 1,
+                evictions: 1,
+            }
+        );
+
+        let cache = BundleCache::new(CacheConfig::new(dir.path()).without_ttl()).unwrap();
+        cache.put("users-service", "v1.0.0", &bundle).unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(cache.get("users-service", "v1.0.0").unwrap().is_some());
+    }
 }
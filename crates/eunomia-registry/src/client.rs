@@ -3,16 +3,33 @@
 //! This module provides the main client interface for interacting with
 //! OCI-compatible container registries, with exponential backoff retry logic
 //! for transient failures.
+//!
+//! Registries that answer with a `WWW-Authenticate: Bearer` challenge are
+//! handled transparently: the client fetches a token from the challenge
+//! realm (using basic credentials if configured), caches it per repository
+//! and scope, and retries the request.
 
+use crate::auth::{repository_scope, BearerChallenge, TokenCache, TokenResponse};
 use crate::cache::BundleCache;
 use crate::config::{RegistryAuth, RegistryConfig};
+#[cfg(feature = "aws")]
+use crate::ecr::EcrTokenProvider;
 use crate::error::RegistryError;
-use crate::oci::{Descriptor, Manifest, MediaType, TagList};
+use crate::oci::{Catalog, Descriptor, ImageIndex, Manifest, MediaType, TagList};
 use crate::version::{VersionQuery, VersionResolver};
-use eunomia_core::Bundle;
+use eunomia_core::signing::{BundleVerifier, SignatureFile};
+use eunomia_core::{Bundle, EncryptedBundle, SignedBundle};
 use eunomia_metrics::MetricsRegistry;
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use futures::stream::{self, StreamExt};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_RANGE, CONTENT_TYPE, LOCATION, RANGE,
+    WWW_AUTHENTICATE,
+};
+use reqwest::StatusCode;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+#[cfg(feature = "aws")]
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Configuration for retry behavior with exponential backoff.
@@ -51,6 +68,46 @@ impl RetryConfig {
     }
 }
 
+/// Blobs and manifest of a bundle publish, built by
+/// [`RegistryClient::prepare_publish`] without contacting the registry.
+#[derive(Debug, Clone)]
+pub struct PreparedPublish {
+    /// The bundle layer, encrypted if the client has an encryption key.
+    pub bundle_blob: Vec<u8>,
+    /// The signature layer, if the bundle is signed.
+    pub signature_blob: Option<Vec<u8>>,
+    /// The manifest pushed under the version tag.
+    pub manifest: Manifest,
+}
+
+impl PreparedPublish {
+    /// Returns the digest of the bundle layer.
+    #[must_use]
+    pub fn bundle_digest(&self) -> &str {
+        &self.manifest.layers[0].digest
+    }
+
+    /// Returns the digest of the manifest as it is pushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be serialized.
+    pub fn manifest_digest(&self) -> Result<String, RegistryError> {
+        Ok(RegistryClient::compute_digest(&serde_json::to_vec(
+            &self.manifest,
+        )?))
+    }
+}
+
+/// Bundles fetched by [`RegistryClient::fetch_many`].
+#[derive(Debug)]
+pub struct FetchManyResult {
+    /// Result of each fetch, in the order requested.
+    pub bundles: Vec<Result<Bundle, RegistryError>>,
+    /// Number of fetches that failed.
+    pub failed_count: usize,
+}
+
 /// Client for interacting with OCI-compatible bundle registries.
 #[derive(Debug)]
 pub struct RegistryClient {
@@ -58,14 +115,39 @@ pub struct RegistryClient {
     http: reqwest::Client,
     version_resolver: VersionResolver,
     cache: Option<BundleCache>,
+    offline: bool,
+    tokens: TokenCache,
+    #[cfg(feature = "aws")]
+    ecr: Option<Arc<EcrTokenProvider>>,
+    /// Clients for the configured mirrors, in fallback order.
+    mirrors: Vec<Self>,
+    /// Verifier for fetched bundles, if signature verification is enabled.
+    verifier: Option<BundleVerifier>,
 }
 
+/// Header carrying the content digest of a manifest.
+const DOCKER_CONTENT_DIGEST: &str = "Docker-Content-Digest";
+
+/// Token scope actions for read-only operations.
+const PULL: &str = "pull";
+/// Token scope actions for uploads.
+const PUSH: &str = "pull,push";
+/// Token scope actions for deletions.
+const DELETE: &str = "delete";
+/// Token scope for listing the registry catalog.
+const CATALOG_SCOPE: &str = "registry:catalog:*";
+
 impl RegistryClient {
     /// Creates a new registry client with the given configuration.
     ///
     /// # Errors
     ///
-    /// Returns an error if the HTTP client cannot be created.
+    /// Returns an error if the HTTP client cannot be created, or if
+    /// [`RegistryAuth::AwsEcr`] is configured without the `aws` feature.
+    ///
+    /// With ECR authentication, the first authorization token is requested
+    /// in the background when called inside a Tokio runtime; a failed
+    /// exchange is reported by the first registry request.
     ///
     /// # Examples
     ///
@@ -77,13 +159,61 @@ impl RegistryClient {
     /// # Ok::<(), eunomia_registry::RegistryError>(())
     /// ```
     pub fn new(config: RegistryConfig) -> Result<Self, RegistryError> {
+        #[cfg(not(feature = "aws"))]
+        if matches!(config.auth, RegistryAuth::AwsEcr { .. }) {
+            return Err(RegistryError::UnsupportedApi {
+                feature: "AWS ECR authentication (enable the `aws` feature of eunomia-registry)"
+                    .to_string(),
+            });
+        }
+
         let http = Self::build_http_client(&config)?;
 
+        #[cfg(feature = "aws")]
+        let ecr = match &config.auth {
+            RegistryAuth::AwsEcr {
+                region,
+                registry_id,
+            } => {
+                let provider = Arc::new(EcrTokenProvider::new(region, registry_id.clone()));
+                prefetch_ecr_credentials(&provider);
+                Some(provider)
+            }
+            _ => None,
+        };
+
+        let verifier = if config.verify_signatures {
+            let mut verifier = BundleVerifier::new();
+            for (key_id, public_key) in &config.trusted_keys {
+                verifier
+                    .add_public_key_base64(key_id, public_key)
+                    .map_err(|e| RegistryError::InvalidTrustedKey {
+                        key_id: key_id.clone(),
+                        message: e.to_string(),
+                    })?;
+            }
+            Some(verifier)
+        } else {
+            None
+        };
+
+        let mirrors = config
+            .mirrors
+            .iter()
+            .map(|mirror| Self::new(config.for_mirror(mirror)))
+            .collect::<Result<_, _>>()?;
+
         Ok(Self {
             config,
             http,
             version_resolver: VersionResolver::new(),
             cache: None,
+            offline: false,
+            tokens: TokenCache::new(),
+            #[cfg(feature = "aws")]
+            ecr,
+            mirrors,
+            verifier,
         })
     }
 
@@ -94,6 +224,31 @@ impl RegistryClient {
         self
     }
 
+    /// Enables or disables offline mode.
+    ///
+    /// In offline mode [`fetch`](Self::fetch) serves bundles only from the
+    /// cache, without contacting the registry, and returns
+    /// [`RegistryError::Offline`] when an entry is missing.
+    #[must_use]
+    pub const fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Returns true if the client is in offline mode.
+    #[must_use]
+    pub const fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Sets whether version queries other than exact versions can resolve
+    /// to prereleases; see [`VersionResolver::with_prereleases`].
+    #[must_use]
+    pub const fn with_prereleases(mut self, include: bool) -> Self {
+        self.version_resolver = self.version_resolver.with_prereleases(include);
+        self
+    }
+
     /// Returns the registry configuration.
     #[must_use]
     pub const fn config(&self) -> &RegistryConfig {
@@ -115,11 +270,9 @@ impl RegistryClient {
         let url = format!("{}/v2/{repo}/manifests/{version}", self.config.url);
 
         let response = self
-            .http
-            .head(&url)
-            .headers(self.auth_headers()?)
-            .header(ACCEPT, MediaType::OCI_MANIFEST)
-            .send()
+            .send_with_auth(&repo, PULL, || {
+                self.http.head(&url).header(ACCEPT, MediaType::OCI_MANIFEST)
+            })
             .await?;
 
         Ok(response.status().is_success())
@@ -143,10 +296,7 @@ impl RegistryClient {
         let url = format!("{}/v2/{repo}/tags/list", self.config.url);
 
         let response = self
-            .http
-            .get(&url)
-            .headers(self.auth_headers()?)
-            .send()
+            .send_with_auth(&repo, PULL, || self.http.get(&url))
             .await?;
 
         if !response.status().is_success() {
@@ -163,6 +313,44 @@ impl RegistryClient {
         Ok(tag_list.tags)
     }
 
+    /// Lists the services that have repositories in the registry.
+    ///
+    /// Uses the registry catalog, which not every registry exposes. With a
+    /// namespace configured, only repositories in the namespace are listed,
+    /// without the namespace prefix. Registries that page the catalog return
+    /// only their first page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the catalog cannot be retrieved.
+    pub async fn list_services(&self) -> Result<Vec<String>, RegistryError> {
+        let url = format!("{}/v2/_catalog", self.config.url);
+
+        let response = self
+            .send_with_scope(CATALOG_SCOPE.to_string(), || self.http.get(&url))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(RegistryError::HttpError {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        let catalog: Catalog = response.json().await?;
+        let prefix = if self.config.namespace.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", self.config.namespace)
+        };
+        Ok(catalog
+            .repositories
+            .iter()
+            .filter_map(|repo| repo.strip_prefix(&prefix))
+            .map(ToString::to_string)
+            .collect())
+    }
+
     /// Gets the latest version of a bundle.
     ///
     /// # Arguments
@@ -171,13 +359,14 @@ impl RegistryClient {
     ///
     /// # Returns
     ///
-    /// The latest semantic version tag.
+    /// The latest semantic version tag, skipping prereleases unless the
+    /// client was created [`with_prereleases`](Self::with_prereleases).
     ///
     /// # Errors
     ///
     /// Returns an error if no versions are found.
     pub async fn get_latest_version(&self, service: &str) -> Result<String, RegistryError> {
-        let tags = self.list_tags(service).await?;
+        let tags = self.available_versions(service, "latest").await?;
         self.version_resolver
             .resolve(&VersionQuery::Latest, &tags, service)
     }
@@ -187,15 +376,21 @@ impl RegistryClient {
     /// # Arguments
     ///
     /// * `service` - Service name.
-    /// * `query` - Version query string (e.g., "latest", "v1.2", "v1.2.3").
+    /// * `query` - Version query string (e.g., "latest", "v1.2", "v1.2.3",
+    ///   "^1.2", "stable").
     ///
     /// # Returns
     ///
-    /// The resolved version tag.
+    /// The resolved version tag. A channel tag resolves to the version tag
+    /// it points at: the version its manifest is annotated with, or else the
+    /// highest version tag with the same manifest digest.
     ///
     /// # Errors
     ///
     /// Returns an error if the version query is invalid or cannot be resolved.
+    /// In offline mode the query is resolved against cached versions, and
+    /// [`RegistryError::Offline`] is returned if none are cached; channel
+    /// tags then resolve to themselves.
     pub async fn resolve_version(
         &self,
         service: &str,
@@ -207,8 +402,71 @@ impl RegistryClient {
             return Ok(query.to_string());
         }
 
-        let tags = self.list_tags(service).await?;
-        self.version_resolver.resolve(&query, &tags, service)
+        let tags = self.available_versions(service, &query.to_string()).await?;
+        let resolved = self.version_resolver.resolve(&query, &tags, service)?;
+
+        if query.is_channel() && !self.offline {
+            return self.follow_channel(service, &resolved, &tags).await;
+        }
+        Ok(resolved)
+    }
+
+    /// Returns the version tag a channel tag points at.
+    async fn follow_channel(
+        &self,
+        service: &str,
+        channel: &str,
+        tags: &[String],
+    ) -> Result<String, RegistryError> {
+        let (manifest, digest) = self.fetch_manifest(service, channel).await?;
+        let versions = VersionResolver::versions(tags);
+
+        let annotated = manifest
+            .annotations
+            .as_ref()
+            .and_then(|annotations| annotations.get("org.opencontainers.image.version"));
+        if let Some((_, tag)) = versions
+            .iter()
+            .find(|(_, tag)| annotated.is_some_and(|version| version == tag))
+        {
+            return Ok((*tag).to_string());
+        }
+
+        for (_, tag) in &versions {
+            if self.manifest_digest(service, tag).await?.as_deref() == Some(digest.as_str()) {
+                return Ok((*tag).to_string());
+            }
+        }
+
+        Err(RegistryError::VersionResolutionFailed {
+            service: service.to_string(),
+            query: channel.to_string(),
+            message: "channel tag does not point at a version tag".to_string(),
+        })
+    }
+
+    /// Returns the tags a version query for `service` is resolved against:
+    /// the registry's tags, or the cached versions when offline.
+    async fn available_versions(
+        &self,
+        service: &str,
+        query: &str,
+    ) -> Result<Vec<String>, RegistryError> {
+        if !self.offline {
+            return self.list_tags(service).await;
+        }
+
+        let versions = match &self.cache {
+            Some(cache) => cache.cached_versions(service)?,
+            None => Vec::new(),
+        };
+        if versions.is_empty() {
+            return Err(RegistryError::Offline {
+                service: service.to_string(),
+                version: query.to_string(),
+            });
+        }
+        Ok(versions)
     }
 
     /// Fetches a bundle from the registry.
@@ -226,29 +484,182 @@ impl RegistryClient {
     ///
     /// # Errors
     ///
-    /// Returns an error if the bundle cannot be fetched or is corrupt.
-    #[allow(clippy::cast_possible_truncation)]
+    /// Returns an error if the bundle cannot be fetched or is corrupt,
+    /// [`RegistryError::SignatureVerificationFailed`] if signature
+    /// verification is enabled and the bundle is not signed by a trusted
+    /// key, or [`RegistryError::Offline`] if the client is offline and the
+    /// bundle is not cached.
     pub async fn fetch(&self, service: &str, version: &str) -> Result<Bundle, RegistryError> {
+        self.fetch_signed(service, version)
+            .await
+            .map(|signed| signed.bundle)
+    }
+
+    /// Fetches several bundles in parallel, e.g. to pre-warm instances.
+    ///
+    /// Each `(service, version)` request is fetched like [`Self::fetch`],
+    /// with at most `concurrency` fetches (at least one) running at a time.
+    /// A fetch taking longer than `timeout_per_fetch` fails with
+    /// [`RegistryError::Timeout`]. A failed fetch does not stop the others;
+    /// results are returned in the order of `requests`.
+    pub async fn fetch_many(
+        &self,
+        requests: Vec<(String, String)>,
+        concurrency: usize,
+        timeout_per_fetch: Option<Duration>,
+    ) -> FetchManyResult {
+        let mut results: Vec<_> = stream::iter(requests.into_iter().enumerate())
+            .map(|(index, (service, version))| async move {
+                let fetch = self.fetch(&service, &version);
+                let result = match timeout_per_fetch {
+                    Some(timeout) => {
+                        tokio::time::timeout(timeout, fetch)
+                            .await
+                            .unwrap_or_else(|_| {
+                                Err(RegistryError::Timeout {
+                                    service: service.clone(),
+                                    version: version.clone(),
+                                    timeout,
+                                })
+                            })
+                    }
+                    None => fetch.await,
+                };
+                (index, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+        results.sort_by_key(|(index, _)| *index);
+
+        let bundles: Vec<_> = results.into_iter().map(|(_, result)| result).collect();
+        let failed_count = bundles.iter().filter(|result| result.is_err()).count();
+        FetchManyResult {
+            bundles,
+            failed_count,
+        }
+    }
+
+    /// Fetches a bundle and reports which registry it came from.
+    ///
+    /// Like [`Self::fetch`], the primary registry is tried first and each
+    /// configured mirror in turn while the previous one is unavailable. The
+    /// returned URL is that of the registry that served the bundle; bundles
+    /// served from the cache report the primary registry's URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the last registry tried, or
+    /// [`RegistryError::Offline`] if the client is offline and the bundle is
+    /// not cached.
+    pub async fn fetch_with_fallback(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<(Bundle, String), RegistryError> {
+        self.fetch_signed_with_source(service, version)
+            .await
+            .map(|(signed, source)| (signed.bundle, source))
+    }
+
+    /// Fetches a bundle together with the digest of the OCI manifest it was
+    /// published under, e.g. to pin it for provenance.
+    ///
+    /// Bundles served from the cache report the manifest digest recorded
+    /// when they were cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be fetched like
+    /// [`Self::fetch`], or [`RegistryError::CacheError`] if the client is
+    /// offline and the cached bundle has no recorded manifest digest.
+    pub async fn fetch_with_digest(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<(Bundle, String), RegistryError> {
+        let bundle = self.fetch(service, version).await?;
+
+        let cached = match &self.cache {
+            Some(cache) => cache.cached_digest(service, version)?,
+            None => None,
+        };
+        let digest = match cached {
+            Some(digest) => digest,
+            None if self.offline => {
+                return Err(RegistryError::CacheError {
+                    message: format!("no manifest digest cached for {service}:{version}"),
+                })
+            }
+            None => self.fetch_raw_manifest(service, version).await?.1,
+        };
+        Ok((bundle, digest))
+    }
+
+    /// Fetches a bundle together with its signatures.
+    ///
+    /// Signatures are read from the manifest's signature layer if present,
+    /// otherwise from the signature embedded in the bundle archive. They are
+    /// only verified when [`RegistryConfig::verify_signatures`] is set;
+    /// otherwise use a [`BundleVerifier`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle or its signature layer cannot be
+    /// fetched or is corrupt, or [`RegistryError::Offline`] if the client is
+    /// offline and the bundle is not cached.
+    pub async fn fetch_signed(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<SignedBundle, RegistryError> {
+        self.fetch_signed_with_source(service, version)
+            .await
+            .map(|(signed, _)| signed)
+    }
+
+    /// Fetches a signed bundle from the cache, the registry or a mirror,
+    /// returning the URL of the registry it came from.
+    #[allow(clippy::cast_possible_truncation)]
+    async fn fetch_signed_with_source(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<(SignedBundle, String), RegistryError> {
         let start = Instant::now();
 
         // Check cache first
         if let Some(ref cache) = self.cache {
             if let Some(bundle) = cache.get(service, version)? {
-                MetricsRegistry::global().registry().record_cache_hit();
-                return Ok(bundle);
+                if self.offline || self.cache_is_current(cache, service, version).await? {
+                    MetricsRegistry::global().registry().record_cache_hit();
+                    let signed = match cache.get_signatures(service, version)? {
+                        Some(signatures) => SignedBundle::new(bundle, signatures),
+                        None => SignedBundle::from_embedded(bundle),
+                    };
+                    self.verify_signatures(service, version, &signed)?;
+                    return Ok((signed, self.config.url.clone()));
+                }
             }
             MetricsRegistry::global().registry().record_cache_miss();
         }
 
-        let result = self.fetch_internal(service, version).await;
+        if self.offline {
+            return Err(RegistryError::Offline {
+                service: service.to_string(),
+                version: version.to_string(),
+            });
+        }
+
+        let result = self.fetch_from_sources(service, version).await;
         let duration_ms = start.elapsed().as_millis().min(u128::from(u64::MAX)) as u64;
 
         match &result {
-            Ok(bundle) => {
+            Ok((signed, _)) => {
                 MetricsRegistry::global()
                     .registry()
                     .record_fetch(service, true, duration_ms);
-                let bundle_bytes = bundle.to_bytes().unwrap_or_default();
+                let bundle_bytes = signed.bundle.to_bytes().unwrap_or_default();
                 MetricsRegistry::global()
                     .registry()
                     .record_bundle_download(service, bundle_bytes.len() as u64);
@@ -263,10 +674,47 @@ impl RegistryClient {
         result
     }
 
-    /// Internal fetch implementation (no metrics).
-    async fn fetch_internal(&self, service: &str, version: &str) -> Result<Bundle, RegistryError> {
+    /// Fetches from the registry, falling back to each mirror in order while
+    /// the previous source is unavailable, and returns the URL of the source
+    /// that answered.
+    async fn fetch_from_sources(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<(SignedBundle, String), RegistryError> {
+        let cache = self.cache.as_ref();
+        let mut source = &self.config.url;
+        let mut result = self.fetch_internal(service, version, cache).await;
+
+        for mirror in &self.mirrors {
+            match &result {
+                Err(e) if Self::is_unavailable(e) => tracing::warn!(
+                    service,
+                    version,
+                    registry = %source,
+                    mirror = %mirror.config.url,
+                    error = %e,
+                    "Registry unavailable, falling back to mirror"
+                ),
+                _ => break,
+            }
+            source = &mirror.config.url;
+            result = mirror.fetch_internal(service, version, cache).await;
+        }
+
+        result.map(|signed| (signed, source.clone()))
+    }
+
+    /// Internal fetch implementation (no metrics), storing the result in
+    /// `cache` if given.
+    async fn fetch_internal(
+        &self,
+        service: &str,
+        version: &str,
+        cache: Option<&BundleCache>,
+    ) -> Result<SignedBundle, RegistryError> {
         // Fetch manifest
-        let manifest = self.fetch_manifest(service, version).await?;
+        let (manifest, manifest_digest) = self.fetch_manifest(service, version).await?;
 
         // Find bundle layer
         let bundle_layer = manifest
@@ -300,20 +748,95 @@ impl RegistryClient {
             });
         }
 
-        // Parse bundle
-        let bundle =
-            Bundle::from_bytes(&bundle_data).map_err(|e| RegistryError::InvalidBundle {
-                message: format!("Failed to parse bundle: {e}"),
-            })?;
+        // Decrypt and parse bundle
+        let bundle = self.decode_bundle(&bundle_layer.media_type, &bundle_data)?;
+
+        // Prefer the signature layer; fall back to the embedded signature
+        let layer_signatures = match manifest.signature_layer() {
+            Some(layer) => Some(self.fetch_signatures(service, layer).await?),
+            None => None,
+        };
+
+        let has_layer_signatures = layer_signatures.is_some();
+        let signed = match layer_signatures {
+            Some(signatures) => SignedBundle::new(bundle, signatures),
+            None => SignedBundle::from_embedded(bundle),
+        };
+
+        // Never cache a bundle that fails verification
+        self.verify_signatures(service, version, &signed)?;
 
         // Cache if enabled
-        if let Some(ref cache) = self.cache {
-            if let Err(e) = cache.put(service, version, &bundle) {
+        if let Some(cache) = cache {
+            let cached = cache
+                .put_with_digest(service, version, &signed.bundle, Some(&manifest_digest))
+                .and_then(|()| {
+                    if has_layer_signatures {
+                        cache.put_signatures(service, version, &signed.signatures)
+                    } else {
+                        Ok(())
+                    }
+                });
+            if let Err(e) = cached {
                 tracing::warn!(error = %e, "Failed to cache bundle");
             }
         }
 
-        Ok(bundle)
+        Ok(signed)
+    }
+
+    /// Checks a fetched bundle against the trusted keys when signature
+    /// verification is enabled.
+    fn verify_signatures(
+        &self,
+        service: &str,
+        version: &str,
+        signed: &SignedBundle,
+    ) -> Result<(), RegistryError> {
+        let Some(ref verifier) = self.verifier else {
+            return Ok(());
+        };
+
+        let reason = if signed.is_signed() {
+            match verifier.verify(signed) {
+                Ok(()) => return Ok(()),
+                Err(e) => e.to_string(),
+            }
+        } else {
+            "bundle is not signed".to_string()
+        };
+
+        Err(RegistryError::SignatureVerificationFailed {
+            service: service.to_string(),
+            version: version.to_string(),
+            reason,
+        })
+    }
+
+    /// Fetches and parses a signature layer.
+    async fn fetch_signatures(
+        &self,
+        service: &str,
+        layer: &Descriptor,
+    ) -> Result<SignatureFile, RegistryError> {
+        let data = self.fetch_blob(service, &layer.digest).await?;
+
+        let actual_digest = Self::compute_digest(&data);
+        if actual_digest != layer.digest {
+            return Err(RegistryError::InvalidBundle {
+                message: format!(
+                    "Signature layer digest mismatch: expected {}, got {actual_digest}",
+                    layer.digest
+                ),
+            });
+        }
+
+        let json = std::str::from_utf8(&data).map_err(|e| RegistryError::InvalidBundle {
+            message: format!("Signature layer is not UTF-8: {e}"),
+        })?;
+        SignatureFile::from_json(json).map_err(|e| RegistryError::InvalidBundle {
+            message: format!("Failed to parse signature layer: {e}"),
+        })
     }
 
     /// Publishes a bundle to the registry.
@@ -327,16 +850,97 @@ impl RegistryClient {
     /// # Errors
     ///
     /// Returns an error if the bundle cannot be pushed.
-    #[allow(clippy::cast_possible_truncation)]
     pub async fn publish(
         &self,
         service: &str,
         version: &str,
         bundle: &Bundle,
     ) -> Result<String, RegistryError> {
+        self.publish_with_progress(service, version, bundle, |_, _| {})
+            .await
+    }
+
+    /// Publishes a bundle to the registry, reporting upload progress.
+    ///
+    /// Bundles larger than [`RegistryConfig::chunk_size`] are uploaded in
+    /// chunks; `on_progress` is called with `(bytes_uploaded, total_bytes)`
+    /// after each chunk is accepted, or once for single-request uploads.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be pushed.
+    pub async fn publish_with_progress<F>(
+        &self,
+        service: &str,
+        version: &str,
+        bundle: &Bundle,
+        on_progress: F,
+    ) -> Result<String, RegistryError>
+    where
+        F: Fn(u64, u64) + Sync,
+    {
+        self.publish_with_signatures(service, version, bundle, None, &on_progress)
+            .await
+    }
+
+    /// Publishes a signed bundle to the registry.
+    ///
+    /// The signatures are pushed as a separate
+    /// [`MediaType::EUNOMIA_SIGNATURE`] layer next to the bundle, so all of
+    /// them survive even when the bundle is encrypted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle or signatures cannot be pushed.
+    pub async fn publish_signed(
+        &self,
+        service: &str,
+        version: &str,
+        signed: &SignedBundle,
+    ) -> Result<String, RegistryError> {
+        self.publish_signed_with_progress(service, version, signed, |_, _| {})
+            .await
+    }
+
+    /// Publishes a signed bundle to the registry, reporting bundle upload
+    /// progress as in [`publish_with_progress`](Self::publish_with_progress).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle or signatures cannot be pushed.
+    pub async fn publish_signed_with_progress<F>(
+        &self,
+        service: &str,
+        version: &str,
+        signed: &SignedBundle,
+        on_progress: F,
+    ) -> Result<String, RegistryError>
+    where
+        F: Fn(u64, u64) + Sync,
+    {
+        let signatures = signed.is_signed().then_some(&signed.signatures);
+        self.publish_with_signatures(service, version, &signed.bundle, signatures, &on_progress)
+            .await
+    }
+
+    /// Publishes a bundle and optional signature layer, recording metrics.
+    #[allow(clippy::cast_possible_truncation)]
+    async fn publish_with_signatures<F>(
+        &self,
+        service: &str,
+        version: &str,
+        bundle: &Bundle,
+        signatures: Option<&SignatureFile>,
+        on_progress: &F,
+    ) -> Result<String, RegistryError>
+    where
+        F: Fn(u64, u64) + Sync,
+    {
         let start = Instant::now();
 
-        let result = self.publish_internal(service, version, bundle).await;
+        let result = self
+            .publish_internal(service, version, bundle, signatures, on_progress)
+            .await;
         let duration_ms = start.elapsed().as_millis().min(u128::from(u64::MAX)) as u64;
 
         match &result {
@@ -356,67 +960,298 @@ impl RegistryClient {
     }
 
     /// Internal publish implementation (no metrics).
-    async fn publish_internal(
+    async fn publish_internal<F>(
         &self,
         service: &str,
         version: &str,
         bundle: &Bundle,
-    ) -> Result<String, RegistryError> {
-        // Serialize bundle
-        let bundle_data = bundle.to_bytes().map_err(|e| RegistryError::UploadFailed {
-            message: format!("Failed to serialize bundle: {e}"),
-        })?;
-
-        let bundle_digest = Self::compute_digest(&bundle_data);
-        let bundle_size = bundle_data.len() as u64;
+        signatures: Option<&SignatureFile>,
+        on_progress: &F,
+    ) -> Result<String, RegistryError>
+    where
+        F: Fn(u64, u64) + Sync,
+    {
+        let prepared = self.prepare_publish(service, version, bundle, signatures)?;
+        let bundle_digest = prepared.bundle_digest().to_string();
 
         // Upload bundle blob
-        self.upload_blob(service, &bundle_data, &bundle_digest)
+        self.upload_blob(service, &prepared.bundle_blob, &bundle_digest, on_progress)
             .await?;
 
-        // Create manifest
-        let bundle_descriptor =
-            Descriptor::new(MediaType::eunomia_bundle(), &bundle_digest, bundle_size)
-                .with_annotation(
-                    "org.opencontainers.image.title",
-                    format!("{service}-{version}.bundle.tar.gz"),
-                );
-
-        let manifest = Manifest::for_bundle(bundle_descriptor, None)
-            .with_annotation("org.opencontainers.image.version", version)
-            .with_annotation(
-                "org.opencontainers.image.created",
-                chrono::Utc::now().to_rfc3339(),
-            );
+        // Upload signature layer
+        if let Some(blob) = &prepared.signature_blob {
+            let digest = Self::compute_digest(blob);
+            self.upload_blob(service, blob, &digest, &|_, _| {}).await?;
+        }
 
         // Push manifest
-        self.push_manifest(service, version, &manifest).await?;
+        self.push_manifest(service, version, &prepared.manifest)
+            .await?;
 
         tracing::info!(service, version, digest = %bundle_digest, "Published bundle");
 
         Ok(bundle_digest)
     }
 
-    /// Deletes a bundle from the registry.
+    /// Builds the blobs and manifest that publishing `bundle` as
+    /// `service:version` would push, without contacting the registry.
     ///
-    /// # Arguments
-    ///
-    /// * `service` - Service name.
-    /// * `version` - Version reference to delete.
+    /// The bundle is encrypted if the client has an encryption key, and
+    /// `signatures` become a separate signature layer.
     ///
     /// # Errors
     ///
-    /// Returns an error if the bundle cannot be deleted.
-    pub async fn delete(&self, service: &str, version: &str) -> Result<(), RegistryError> {
-        let repo = self.config.repository_name(service);
-        let url = format!("{}/v2/{repo}/manifests/{version}", self.config.url);
-
-        let response = self
-            .http
-            .delete(&url)
-            .headers(self.auth_headers()?)
-            .send()
-            .await?;
+    /// Returns an error if the bundle or signatures cannot be serialized or
+    /// encrypted.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn prepare_publish(
+        &self,
+        service: &str,
+        version: &str,
+        bundle: &Bundle,
+        signatures: Option<&SignatureFile>,
+    ) -> Result<PreparedPublish, RegistryError> {
+        // Serialize (and optionally encrypt) bundle
+        let (media_type, bundle_blob) = if let Some(key) = &self.config.encryption_key {
+            let encrypted =
+                bundle
+                    .encrypt(key.as_bytes())
+                    .map_err(|e| RegistryError::UploadFailed {
+                        message: format!("Failed to encrypt bundle: {e}"),
+                    })?;
+            (MediaType::eunomia_encrypted_bundle(), encrypted.to_bytes())
+        } else {
+            let data = bundle.to_bytes().map_err(|e| RegistryError::UploadFailed {
+                message: format!("Failed to serialize bundle: {e}"),
+            })?;
+            (MediaType::eunomia_bundle(), data)
+        };
+
+        let bundle_descriptor = Descriptor::new(
+            media_type,
+            Self::compute_digest(&bundle_blob),
+            bundle_blob.len() as u64,
+        )
+        .with_annotation(
+            "org.opencontainers.image.title",
+            format!("{service}-{version}.bundle.tar.gz"),
+        );
+
+        let signature_blob = signatures
+            .map(|signatures| {
+                signatures.to_json().map(String::into_bytes).map_err(|e| {
+                    RegistryError::UploadFailed {
+                        message: format!("Failed to serialize signatures: {e}"),
+                    }
+                })
+            })
+            .transpose()?;
+        let signature_descriptor = signature_blob.as_ref().map(|blob| {
+            Descriptor::new(
+                MediaType::eunomia_signature(),
+                Self::compute_digest(blob),
+                blob.len() as u64,
+            )
+        });
+
+        let manifest = Manifest::for_bundle(bundle_descriptor, signature_descriptor)
+            .with_annotation("org.opencontainers.image.version", version)
+            .with_annotation(
+                "org.opencontainers.image.created",
+                chrono::Utc::now().to_rfc3339(),
+            );
+
+        Ok(PreparedPublish {
+            bundle_blob,
+            signature_blob,
+            manifest,
+        })
+    }
+
+    /// Parses a fetched bundle layer, decrypting it if necessary.
+    fn decode_bundle(&self, media_type: &MediaType, data: &[u8]) -> Result<Bundle, RegistryError> {
+        if media_type.as_str() != MediaType::EUNOMIA_ENCRYPTED_BUNDLE {
+            return Bundle::from_bytes(data).map_err(|e| RegistryError::InvalidBundle {
+                message: format!("Failed to parse bundle: {e}"),
+            });
+        }
+
+        let key =
+            self.config
+                .encryption_key
+                .as_ref()
+                .ok_or_else(|| RegistryError::InvalidBundle {
+                    message: "Bundle is encrypted but no encryption key is configured".to_string(),
+                })?;
+
+        EncryptedBundle::from_bytes(data)
+            .and_then(|encrypted| encrypted.decrypt(key.as_bytes()))
+            .map_err(|e| RegistryError::InvalidBundle {
+                message: format!("Failed to decrypt bundle: {e}"),
+            })
+    }
+
+    /// Attaches an artifact (e.g. an SBOM or attestation) to a published
+    /// bundle using the OCI referrers model.
+    ///
+    /// The artifact is uploaded as a blob and referenced from a new manifest
+    /// whose `artifactType` is `media_type` and whose `subject` points at the
+    /// manifest of `service:version`. The manifest is pushed by digest, so
+    /// it does not replace any tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::NotFound`] if the bundle does not exist, or
+    /// an error if the artifact or its manifest cannot be uploaded.
+    ///
+    /// # Returns
+    ///
+    /// The digest of the referrer manifest.
+    pub async fn attach_referrer(
+        &self,
+        service: &str,
+        version: &str,
+        artifact: &[u8],
+        media_type: &str,
+        annotations: HashMap<String, String>,
+    ) -> Result<String, RegistryError> {
+        let subject = self.manifest_descriptor(service, version).await?;
+
+        let artifact_digest = Self::compute_digest(artifact);
+        self.upload_blob(service, artifact, &artifact_digest, &|_, _| {})
+            .await?;
+
+        let mut manifest = Manifest::new(vec![Descriptor::new(
+            MediaType::new(media_type),
+            &artifact_digest,
+            artifact.len() as u64,
+        )]);
+        manifest.artifact_type = Some(media_type.to_string());
+        manifest.subject = Some(subject);
+        if !annotations.is_empty() {
+            manifest.annotations = Some(annotations);
+        }
+
+        let manifest_json = serde_json::to_vec(&manifest)?;
+        let manifest_digest = Self::compute_digest(&manifest_json);
+        self.push_manifest_bytes(service, &manifest_digest, manifest_json)
+            .await?;
+
+        tracing::info!(
+            service,
+            version,
+            media_type,
+            digest = %manifest_digest,
+            "Attached referrer"
+        );
+
+        Ok(manifest_digest)
+    }
+
+    /// Lists the artifacts attached to a bundle through the OCI referrers
+    /// API (`GET /v2/<repo>/referrers/<digest>`).
+    ///
+    /// Each descriptor points at a referrer manifest; its
+    /// [`referrer_type`](Descriptor::referrer_type) is the artifact type
+    /// given to [`attach_referrer`](Self::attach_referrer).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::NotFound`] if the bundle does not exist,
+    /// [`RegistryError::UnsupportedApi`] if the registry does not implement
+    /// the referrers API, or an error if the request fails.
+    pub async fn list_referrers(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<Vec<Descriptor>, RegistryError> {
+        let subject = self.manifest_descriptor(service, version).await?;
+
+        let repo = self.config.repository_name(service);
+        let url = format!("{}/v2/{repo}/referrers/{}", self.config.url, subject.digest);
+
+        let response = self
+            .send_with_auth(&repo, PULL, || {
+                self.http.get(&url).header(ACCEPT, MediaType::OCI_INDEX)
+            })
+            .await?;
+
+        match response.status().as_u16() {
+            404 => {
+                return Err(RegistryError::UnsupportedApi {
+                    feature: "OCI referrers API".to_string(),
+                })
+            }
+            status if !response.status().is_success() => {
+                return Err(RegistryError::HttpError {
+                    status,
+                    message: response.text().await.unwrap_or_default(),
+                });
+            }
+            _ => {}
+        }
+
+        let index: ImageIndex = response.json().await?;
+        Ok(index.manifests)
+    }
+
+    /// Returns a descriptor for the manifest of `service:version`, used as
+    /// the `subject` of referrer manifests.
+    async fn manifest_descriptor(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<Descriptor, RegistryError> {
+        let repo = self.config.repository_name(service);
+        let url = format!("{}/v2/{repo}/manifests/{version}", self.config.url);
+
+        let response = self
+            .send_with_auth(&repo, PULL, || {
+                self.http.get(&url).header(ACCEPT, MediaType::OCI_MANIFEST)
+            })
+            .await?;
+
+        if response.status().as_u16() == 404 {
+            return Err(RegistryError::NotFound {
+                service: service.to_string(),
+                version: version.to_string(),
+            });
+        }
+        if !response.status().is_success() {
+            return Err(RegistryError::HttpError {
+                status: response.status().as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        let header_digest = Self::content_digest(&response);
+        let body = response.bytes().await?;
+        let digest = header_digest.unwrap_or_else(|| Self::compute_digest(&body));
+
+        Ok(Descriptor::new(
+            MediaType::new(MediaType::OCI_MANIFEST),
+            digest,
+            body.len() as u64,
+        ))
+    }
+
+    /// Deletes a bundle from the registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - Service name.
+    /// * `version` - Version reference to delete.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be deleted.
+    pub async fn delete(&self, service: &str, version: &str) -> Result<(), RegistryError> {
+        let repo = self.config.repository_name(service);
+        let url = format!("{}/v2/{repo}/manifests/{version}", self.config.url);
+
+        let response = self
+            .send_with_auth(&repo, DELETE, || self.http.delete(&url))
+            .await?;
 
         if !response.status().is_success() {
             return Err(RegistryError::HttpError {
@@ -433,12 +1268,250 @@ impl RegistryClient {
         Ok(())
     }
 
-    /// Fetches a manifest from the registry with exponential backoff retry.
+    /// Copies `src_service:src_version` to `dst_service:dst_version` within
+    /// this registry, e.g. to promote a bundle from a staging namespace to
+    /// production.
+    ///
+    /// Blobs are mounted from the source repository instead of being
+    /// downloaded and uploaded again; they are only re-uploaded if the
+    /// registry refuses the mount. The manifest is pushed unchanged, so the
+    /// copy has the same digest as the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::NotFound`] if the source does not exist, or
+    /// an error if a blob or the manifest cannot be pushed.
+    pub async fn copy(
+        &self,
+        src_service: &str,
+        src_version: &str,
+        dst_service: &str,
+        dst_version: &str,
+    ) -> Result<(), RegistryError> {
+        let (manifest_json, _) = self.fetch_raw_manifest(src_service, src_version).await?;
+        let manifest: Manifest = serde_json::from_slice(&manifest_json)?;
+
+        if self.config.repository_name(src_service) != self.config.repository_name(dst_service) {
+            for blob in manifest.config.iter().chain(&manifest.layers) {
+                self.mount_blob(src_service, dst_service, &blob.digest)
+                    .await?;
+            }
+        }
+
+        self.push_manifest_bytes(dst_service, dst_version, manifest_json)
+            .await?;
+
+        tracing::info!(
+            src_service,
+            src_version,
+            dst_service,
+            dst_version,
+            "Copied bundle"
+        );
+        Ok(())
+    }
+
+    /// Copies `service:version` from the registry of `src` to the registry
+    /// of `dst`, e.g. to promote a bundle to a production registry.
+    ///
+    /// Blobs already present in the destination are not uploaded again.
+    /// The manifest is pushed unchanged, so the copy has the same digest as
+    /// the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::NotFound`] if the source does not exist, or
+    /// an error if a blob or the manifest cannot be copied.
+    pub async fn copy_between_registries(
+        src: &Self,
+        dst: &Self,
+        service: &str,
+        version: &str,
+    ) -> Result<(), RegistryError> {
+        let (manifest_json, _) = src.fetch_raw_manifest(service, version).await?;
+        let manifest: Manifest = serde_json::from_slice(&manifest_json)?;
+
+        for blob in manifest.config.iter().chain(&manifest.layers) {
+            if dst.blob_exists(service, &blob.digest).await? {
+                continue;
+            }
+            let data = src.fetch_blob(service, &blob.digest).await?;
+            dst.upload_blob(service, &data, &blob.digest, &|_, _| {})
+                .await?;
+        }
+
+        dst.push_manifest_bytes(service, version, manifest_json)
+            .await?;
+
+        tracing::info!(
+            service,
+            version,
+            src = %src.config.url,
+            dst = %dst.config.url,
+            "Copied bundle between registries"
+        );
+        Ok(())
+    }
+
+    /// Mounts the blob `digest` of `src_service`'s repository into
+    /// `dst_service`'s, uploading it if the registry refuses the mount.
+    async fn mount_blob(
+        &self,
+        src_service: &str,
+        dst_service: &str,
+        digest: &str,
+    ) -> Result<(), RegistryError> {
+        let src_repo = self.config.repository_name(src_service);
+        let dst_repo = self.config.repository_name(dst_service);
+        let url = format!(
+            "{}/v2/{dst_repo}/blobs/uploads/?mount={digest}&from={src_repo}",
+            self.config.url
+        );
+        let scope = format!(
+            "{} {}",
+            repository_scope(&dst_repo, PUSH),
+            repository_scope(&src_repo, PULL)
+        );
+
+        let response = self.send_with_scope(scope, || self.http.post(&url)).await?;
+
+        let status = response.status().as_u16();
+        if status != 201 && status != 202 {
+            return Err(RegistryError::UploadFailed {
+                message: format!("Failed to mount blob {digest}: {}", response.status()),
+            });
+        }
+
+        // A refused mount opens a regular upload session (202 with an upload
+        // location); a 201, or a 202 pointing at the blob, means it was mounted
+        let session = self
+            .upload_location(&response)?
+            .filter(|location| status == 202 && location.contains("/blobs/uploads/"));
+        let Some(session) = session else {
+            tracing::debug!(digest, from = %src_repo, to = %dst_repo, "Mounted blob");
+            return Ok(());
+        };
+
+        let data = self.fetch_blob(src_service, digest).await?;
+        self.finish_upload(&dst_repo, &session, digest, &data).await
+    }
+
+    /// Returns true if the blob `digest` exists in `service`'s repository.
+    async fn blob_exists(&self, service: &str, digest: &str) -> Result<bool, RegistryError> {
+        let repo = self.config.repository_name(service);
+        let url = format!("{}/v2/{repo}/blobs/{digest}", self.config.url);
+
+        let response = self
+            .send_with_auth(&repo, PULL, || self.http.head(&url))
+            .await?;
+
+        match response.status().as_u16() {
+            404 => Ok(false),
+            _ if response.status().is_success() => Ok(true),
+            status => Err(RegistryError::HttpError {
+                status,
+                message: "blob HEAD failed".to_string(),
+            }),
+        }
+    }
+
+    /// Returns true if a cached entry still matches the registry.
+    ///
+    /// Compares the digest recorded in the cache with the one reported by a
+    /// `HEAD` of the manifest, so a re-pushed tag is fetched again. If the
+    /// registry cannot be reached or does not report a digest, the cached
+    /// entry is trusted.
+    async fn cache_is_current(
+        &self,
+        cache: &BundleCache,
+        service: &str,
+        version: &str,
+    ) -> Result<bool, RegistryError> {
+        if !cache.config().validate_digest {
+            return Ok(true);
+        }
+
+        let cached = cache.cached_digest(service, version)?;
+        match self.manifest_digest(service, version).await {
+            Ok(Some(remote)) => {
+                let current = cached.as_deref() == Some(remote.as_str());
+                if !current {
+                    tracing::debug!(
+                        service,
+                        version,
+                        cached = ?cached,
+                        remote,
+                        "Cached bundle is stale"
+                    );
+                }
+                Ok(current)
+            }
+            Ok(None) => Ok(true),
+            Err(e) => {
+                tracing::warn!(
+                    service,
+                    version,
+                    error = %e,
+                    "Could not validate cached bundle digest, using cache"
+                );
+                Ok(true)
+            }
+        }
+    }
+
+    /// Returns the manifest digest reported by a `HEAD` request, or `None`
+    /// if the registry does not send `Docker-Content-Digest`.
+    async fn manifest_digest(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<Option<String>, RegistryError> {
+        let repo = self.config.repository_name(service);
+        let url = format!("{}/v2/{repo}/manifests/{version}", self.config.url);
+
+        let response = self
+            .send_with_auth(&repo, PULL, || {
+                self.http.head(&url).header(ACCEPT, MediaType::OCI_MANIFEST)
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(RegistryError::HttpError {
+                status: response.status().as_u16(),
+                message: "manifest HEAD failed".to_string(),
+            });
+        }
+
+        Ok(Self::content_digest(&response))
+    }
+
+    /// Returns the `Docker-Content-Digest` header of a response.
+    fn content_digest(response: &reqwest::Response) -> Option<String> {
+        response
+            .headers()
+            .get(DOCKER_CONTENT_DIGEST)
+            .and_then(|v| v.to_str().ok())
+            .map(ToString::to_string)
+    }
+
+    /// Fetches a manifest and its digest from the registry with exponential
+    /// backoff retry.
     async fn fetch_manifest(
         &self,
         service: &str,
         version: &str,
-    ) -> Result<Manifest, RegistryError> {
+    ) -> Result<(Manifest, String), RegistryError> {
+        let (body, digest) = self.fetch_raw_manifest(service, version).await?;
+        Ok((serde_json::from_slice(&body)?, digest))
+    }
+
+    /// Fetches the serialized manifest and its digest, retrying like
+    /// [`fetch_manifest`](Self::fetch_manifest).
+    async fn fetch_raw_manifest(
+        &self,
+        service: &str,
+        version: &str,
+    ) -> Result<(Vec<u8>, String), RegistryError> {
         let retry_config = RetryConfig::default();
         let mut last_error = None;
 
@@ -480,20 +1553,21 @@ impl RegistryClient {
     }
 
     /// Internal implementation of manifest fetching (without retry logic).
+    ///
+    /// The digest is taken from `Docker-Content-Digest`, falling back to the
+    /// SHA-256 of the response body.
     async fn fetch_manifest_internal(
         &self,
         service: &str,
         version: &str,
-    ) -> Result<Manifest, RegistryError> {
+    ) -> Result<(Vec<u8>, String), RegistryError> {
         let repo = self.config.repository_name(service);
         let url = format!("{}/v2/{repo}/manifests/{version}", self.config.url);
 
         let response = self
-            .http
-            .get(&url)
-            .headers(self.auth_headers()?)
-            .header(ACCEPT, MediaType::OCI_MANIFEST)
-            .send()
+            .send_with_auth(&repo, PULL, || {
+                self.http.get(&url).header(ACCEPT, MediaType::OCI_MANIFEST)
+            })
             .await?;
 
         if !response.status().is_success() {
@@ -509,7 +1583,11 @@ impl RegistryClient {
             });
         }
 
-        response.json().await.map_err(Into::into)
+        let header_digest = Self::content_digest(&response);
+        let body = response.bytes().await?;
+        let digest = header_digest.unwrap_or_else(|| Self::compute_digest(&body));
+
+        Ok((body.to_vec(), digest))
     }
 
     /// Fetches a blob from the registry with exponential backoff retry.
@@ -563,10 +1641,7 @@ impl RegistryClient {
         let url = format!("{}/v2/{repo}/blobs/{digest}", self.config.url);
 
         let response = self
-            .http
-            .get(&url)
-            .headers(self.auth_headers()?)
-            .send()
+            .send_with_auth(&repo, PULL, || self.http.get(&url))
             .await?;
 
         if !response.status().is_success() {
@@ -584,22 +1659,52 @@ impl RegistryClient {
     }
 
     /// Uploads a blob to the registry.
-    async fn upload_blob(
+    ///
+    /// Blobs no larger than the configured chunk size are sent with a single
+    /// `PUT`. Larger blobs are sent as a series of `PATCH` requests followed
+    /// by a finalizing `PUT`, following the OCI chunked upload protocol.
+    async fn upload_blob<F>(
         &self,
         service: &str,
         data: &[u8],
         digest: &str,
-    ) -> Result<(), RegistryError> {
+        on_progress: &F,
+    ) -> Result<(), RegistryError>
+    where
+        F: Fn(u64, u64) + Sync,
+    {
         let repo = self.config.repository_name(service);
+        let total = data.len() as u64;
+        let chunk_size = self.config.chunk_size.max(1);
+
+        let mut location = self.start_upload(&repo).await?;
+
+        if data.len() <= chunk_size {
+            self.finish_upload(&repo, &location, digest, data).await?;
+            on_progress(total, total);
+            return Ok(());
+        }
+
+        let mut offset = 0;
+        while offset < data.len() {
+            let end = (offset + chunk_size).min(data.len());
+            let (next_location, next_offset) = self
+                .upload_chunk(&repo, &location, data, offset, end)
+                .await?;
+            location = next_location;
+            offset = next_offset;
+            on_progress(offset as u64, total);
+        }
+
+        self.finish_upload(&repo, &location, digest, &[]).await
+    }
 
-        // Start upload session
+    /// Opens an upload session and returns its location.
+    async fn start_upload(&self, repo: &str) -> Result<String, RegistryError> {
         let start_url = format!("{}/v2/{repo}/blobs/uploads/", self.config.url);
 
         let response = self
-            .http
-            .post(&start_url)
-            .headers(self.auth_headers()?)
-            .send()
+            .send_with_auth(repo, PUSH, || self.http.post(&start_url))
             .await?;
 
         if !response.status().is_success() && response.status().as_u16() != 202 {
@@ -608,59 +1713,248 @@ impl RegistryClient {
             });
         }
 
-        // Get upload location
-        let location = response
-            .headers()
-            .get("location")
-            .and_then(|v| v.to_str().ok())
+        self.upload_location(&response)?
             .ok_or_else(|| RegistryError::UploadFailed {
                 message: "No upload location returned".to_string(),
-            })?;
+            })
+    }
 
-        // Complete upload with PUT
-        let upload_url = if location.contains('?') {
-            format!("{location}&digest={digest}")
-        } else {
-            format!("{location}?digest={digest}")
-        };
+    /// Uploads `data[offset..end]`, retrying transient failures with
+    /// exponential backoff.
+    ///
+    /// After a failed attempt the registry is asked how much of the blob it
+    /// has received, so the retry resumes from there rather than resending
+    /// bytes that already arrived. Returns the next upload location and the
+    /// offset of the first byte not yet uploaded.
+    async fn upload_chunk(
+        &self,
+        repo: &str,
+        location: &str,
+        data: &[u8],
+        offset: usize,
+        end: usize,
+    ) -> Result<(String, usize), RegistryError> {
+        let retry_config = RetryConfig::default();
+        let mut location = location.to_string();
+        let mut offset = offset;
+        let mut attempt = 0;
+
+        loop {
+            let error = match self
+                .patch_chunk(repo, &location, &data[offset..end], offset)
+                .await
+            {
+                Ok(result) => return Ok(result),
+                Err(e) if attempt < retry_config.max_retries && Self::is_transient(&e) => e,
+                Err(e) => return Err(e),
+            };
+
+            let delay = retry_config.delay_for_attempt(attempt);
+            tracing::warn!(
+                attempt = attempt + 1,
+                offset,
+                delay_ms = delay.as_millis(),
+                error = %error,
+                "Retrying blob chunk upload after transient error"
+            );
+            tokio::time::sleep(delay).await;
+
+            if let Ok((status_location, received)) = self.upload_status(repo, &location).await {
+                location = status_location;
+                if received >= end {
+                    return Ok((location, received));
+                }
+                if received > offset {
+                    offset = received;
+                }
+            }
+
+            attempt += 1;
+        }
+    }
+
+    /// Sends one chunk with `PATCH`.
+    async fn patch_chunk(
+        &self,
+        repo: &str,
+        location: &str,
+        chunk: &[u8],
+        offset: usize,
+    ) -> Result<(String, usize), RegistryError> {
+        let content_range = format!("{offset}-{}", offset + chunk.len() - 1);
 
         let response = self
-            .http
-            .put(&upload_url)
-            .headers(self.auth_headers()?)
-            .header(CONTENT_TYPE, "application/octet-stream")
-            .body(data.to_vec())
-            .send()
+            .send_with_auth(repo, PUSH, || {
+                self.http
+                    .patch(location)
+                    .header(CONTENT_TYPE, "application/octet-stream")
+                    .header(CONTENT_RANGE, &content_range)
+                    .body(chunk.to_vec())
+            })
             .await?;
 
-        if !response.status().is_success() && response.status().as_u16() != 201 {
-            return Err(RegistryError::UploadFailed {
-                message: format!("Failed to upload blob: {}", response.status()),
+        let status = response.status();
+        if status.as_u16() != 202 && !status.is_success() {
+            let message = format!(
+                "Failed to upload chunk {content_range}: {status}: {}",
+                response.text().await.unwrap_or_default()
+            );
+            return Err(if status.is_server_error() || status.as_u16() == 429 {
+                RegistryError::HttpError {
+                    status: status.as_u16(),
+                    message,
+                }
+            } else {
+                RegistryError::UploadFailed { message }
             });
         }
 
-        Ok(())
+        let next_location = self
+            .upload_location(&response)?
+            .unwrap_or_else(|| location.to_string());
+        let next_offset = Self::range_end(&response).unwrap_or(offset + chunk.len());
+
+        Ok((next_location, next_offset))
     }
 
-    /// Pushes a manifest to the registry.
+    /// Queries an upload session for the number of bytes received.
+    async fn upload_status(
+        &self,
+        repo: &str,
+        location: &str,
+    ) -> Result<(String, usize), RegistryError> {
+        let response = self
+            .send_with_auth(repo, PUSH, || self.http.get(location))
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(RegistryError::HttpError {
+                status: response.status().as_u16(),
+                message: "Failed to query upload status".to_string(),
+            });
+        }
+
+        let next_location = self
+            .upload_location(&response)?
+            .unwrap_or_else(|| location.to_string());
+        Ok((next_location, Self::range_end(&response).unwrap_or(0)))
+    }
+
+    /// Completes an upload session with `PUT`, sending any remaining `data`.
+    async fn finish_upload(
+        &self,
+        repo: &str,
+        location: &str,
+        digest: &str,
+        data: &[u8],
+    ) -> Result<(), RegistryError> {
+        let upload_url = if location.contains('?') {
+            format!("{location}&digest={digest}")
+        } else {
+            format!("{location}?digest={digest}")
+        };
+
+        let response = self
+            .send_with_auth(repo, PUSH, || {
+                self.http
+                    .put(&upload_url)
+                    .header(CONTENT_TYPE, "application/octet-stream")
+                    .body(data.to_vec())
+            })
+            .await?;
+
+        if !response.status().is_success() && response.status().as_u16() != 201 {
+            return Err(RegistryError::UploadFailed {
+                message: format!("Failed to upload blob: {}", response.status()),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the absolute upload location from a response, if present.
+    ///
+    /// Registries commonly return a path relative to the registry URL.
+    fn upload_location(
+        &self,
+        response: &reqwest::Response,
+    ) -> Result<Option<String>, RegistryError> {
+        let Some(location) = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|v| v.to_str().ok())
+        else {
+            return Ok(None);
+        };
+
+        url::Url::parse(&self.config.url)
+            .and_then(|base| base.join(location))
+            .map(|url| Some(url.to_string()))
+            .map_err(|_| RegistryError::InvalidUrl {
+                url: location.to_string(),
+            })
+    }
+
+    /// Returns the offset after the last byte acknowledged in a `Range`
+    /// header (`0-<last>`, optionally prefixed with `bytes=`).
+    fn range_end(response: &reqwest::Response) -> Option<usize> {
+        let range = response.headers().get(RANGE)?.to_str().ok()?;
+        parse_range_end(range)
+    }
+
+    /// Returns true for errors worth retrying: connection failures, rate
+    /// limiting and server errors.
+    const fn is_transient(error: &RegistryError) -> bool {
+        match error {
+            RegistryError::ConnectionFailed { .. } => true,
+            RegistryError::HttpError { status, .. } => {
+                *status == 0 || *status == 429 || *status >= 500
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true for errors that mean the registry could not serve the
+    /// request at all, so a mirror should be tried: connection failures,
+    /// requests that got no response, and server errors.
+    const fn is_unavailable(error: &RegistryError) -> bool {
+        match error {
+            RegistryError::ConnectionFailed { .. } => true,
+            RegistryError::HttpError { status, .. } => *status == 0 || *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Pushes a manifest to the registry.
     async fn push_manifest(
         &self,
         service: &str,
         version: &str,
         manifest: &Manifest,
+    ) -> Result<(), RegistryError> {
+        let manifest_json = serde_json::to_vec(manifest)?;
+        self.push_manifest_bytes(service, version, manifest_json)
+            .await
+    }
+
+    /// Pushes serialized manifest bytes under `reference` (a tag or the
+    /// manifest's own digest).
+    async fn push_manifest_bytes(
+        &self,
+        service: &str,
+        version: &str,
+        manifest_json: Vec<u8>,
     ) -> Result<(), RegistryError> {
         let repo = self.config.repository_name(service);
         let url = format!("{}/v2/{repo}/manifests/{version}", self.config.url);
 
-        let manifest_json = serde_json::to_vec(manifest)?;
-
         let response = self
-            .http
-            .put(&url)
-            .headers(self.auth_headers()?)
-            .header(CONTENT_TYPE, MediaType::OCI_MANIFEST)
-            .body(manifest_json)
-            .send()
+            .send_with_auth(&repo, PUSH, || {
+                self.http
+                    .put(&url)
+                    .header(CONTENT_TYPE, MediaType::OCI_MANIFEST)
+                    .body(manifest_json.clone())
+            })
             .await?;
 
         if !response.status().is_success() && response.status().as_u16() != 201 {
@@ -732,6 +2026,156 @@ impl RegistryClient {
             })
     }
 
+    /// Sends a request, answering a Bearer challenge if the registry issues one.
+    ///
+    /// `build` is called once per attempt so the request can be replayed after
+    /// a token has been obtained. A cached token for the repository and scope
+    /// is attached up front; if the registry still answers `401` with a
+    /// `WWW-Authenticate: Bearer` challenge, the cached token is discarded, a
+    /// new one is requested from the challenge realm, and the request is
+    /// retried once. With ECR authentication, a plain `401` instead refreshes
+    /// the ECR credentials and retries once. Any other response is returned
+    /// unchanged.
+    async fn send_with_auth<F>(
+        &self,
+        repo: &str,
+        actions: &str,
+        build: F,
+    ) -> Result<reqwest::Response, RegistryError>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        self.send_with_scope(repository_scope(repo, actions), build)
+            .await
+    }
+
+    /// Sends a request like [`send_with_auth`](Self::send_with_auth), with
+    /// tokens cached under `scope`.
+    async fn send_with_scope<F>(
+        &self,
+        scope: String,
+        build: F,
+    ) -> Result<reqwest::Response, RegistryError>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let response = build()
+            .headers(
+                self.request_headers(self.tokens.get(&scope).as_deref())
+                    .await?,
+            )
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let Some(challenge) = response
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(BearerChallenge::parse)
+        else {
+            #[cfg(feature = "aws")]
+            if let Some(ref ecr) = self.ecr {
+                // ECR credentials can be revoked before they expire; refresh once.
+                ecr.invalidate().await;
+                return build()
+                    .headers(self.request_headers(None).await?)
+                    .send()
+                    .await
+                    .map_err(Into::into);
+            }
+            return Ok(response);
+        };
+
+        tracing::debug!(realm = %challenge.realm, %scope, "Registry issued bearer challenge");
+        self.tokens.invalidate(&scope);
+
+        let (token, lifetime) = self.request_token(&challenge, &scope).await?;
+        self.tokens.insert(&scope, &token, lifetime);
+
+        build()
+            .headers(self.request_headers(Some(&token)).await?)
+            .send()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Requests a token from the realm named in a Bearer challenge.
+    async fn request_token(
+        &self,
+        challenge: &BearerChallenge,
+        scope: &str,
+    ) -> Result<(String, Duration), RegistryError> {
+        let mut query = Vec::new();
+        if let Some(ref service) = challenge.service {
+            query.push(("service", service.as_str()));
+        }
+        query.push(("scope", challenge.scope.as_deref().unwrap_or(scope)));
+
+        let mut request = self.http.get(&challenge.realm).query(&query);
+        match &self.config.auth {
+            RegistryAuth::Basic { username, password } => {
+                request = request.basic_auth(username, Some(password));
+            }
+            RegistryAuth::Bearer { token } => {
+                request = request.bearer_auth(token);
+            }
+            _ => {}
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(RegistryError::AuthenticationFailed {
+                message: format!(
+                    "Token request to {} failed: {}",
+                    challenge.realm,
+                    response.status()
+                ),
+            });
+        }
+
+        let body: TokenResponse = response.json().await?;
+        body.into_token()
+            .ok_or_else(|| RegistryError::AuthenticationFailed {
+                message: format!("Token service {} returned no token", challenge.realm),
+            })
+    }
+
+    /// Returns the configured auth headers, with `token` (if any) overriding
+    /// the `Authorization` header.
+    async fn request_headers(&self, token: Option<&str>) -> Result<HeaderMap, RegistryError> {
+        let mut headers = self.resolve_auth_headers().await?;
+        if let Some(token) = token {
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {token}")).map_err(|_| {
+                    RegistryError::AuthenticationFailed {
+                        message: "Registry issued an invalid token".to_string(),
+                    }
+                })?,
+            );
+        }
+        Ok(headers)
+    }
+
+    /// Creates authentication headers, resolving credentials that require a
+    /// network exchange (AWS ECR) before falling back to [`Self::auth_headers`].
+    #[allow(clippy::unused_async)]
+    async fn resolve_auth_headers(&self) -> Result<HeaderMap, RegistryError> {
+        #[cfg(feature = "aws")]
+        if let Some(ref ecr) = self.ecr {
+            let (username, password) = ecr.credentials().await?;
+            let mut headers = HeaderMap::new();
+            headers.insert(AUTHORIZATION, basic_auth_value(&username, &password)?);
+            return Ok(headers);
+        }
+
+        self.auth_headers()
+    }
+
     /// Creates authentication headers based on configuration.
     fn auth_headers(&self) -> Result<HeaderMap, RegistryError> {
         let mut headers = HeaderMap::new();
@@ -739,18 +2183,7 @@ impl RegistryClient {
         match &self.config.auth {
             RegistryAuth::None => {}
             RegistryAuth::Basic { username, password } => {
-                let credentials = base64::Engine::encode(
-                    &base64::engine::general_purpose::STANDARD,
-                    format!("{username}:{password}"),
-                );
-                headers.insert(
-                    AUTHORIZATION,
-                    HeaderValue::from_str(&format!("Basic {credentials}")).map_err(|_| {
-                        RegistryError::AuthenticationFailed {
-                            message: "Invalid credentials".to_string(),
-                        }
-                    })?,
-                );
+                headers.insert(AUTHORIZATION, basic_auth_value(username, password)?);
             }
             RegistryAuth::Bearer { token } => {
                 headers.insert(
@@ -763,8 +2196,8 @@ impl RegistryClient {
                 );
             }
             RegistryAuth::AwsEcr { .. } | RegistryAuth::GcpArtifact { .. } => {
-                // Cloud provider auth would be implemented here
-                // For now, return error as not yet implemented
+                // ECR credentials are resolved in `resolve_auth_headers`;
+                // GCP Artifact Registry is not yet implemented
                 return Err(RegistryError::UnsupportedApi {
                     feature: "Cloud provider authentication".to_string(),
                 });
@@ -775,16 +2208,59 @@ impl RegistryClient {
     }
 
     /// Computes SHA-256 digest of data.
-    fn compute_digest(data: &[u8]) -> String {
+    pub(crate) fn compute_digest(data: &[u8]) -> String {
         let mut hasher = Sha256::new();
         hasher.update(data);
         format!("sha256:{}", hex::encode(hasher.finalize()))
     }
 }
 
+/// Parses the end of an upload `Range` header into the next byte offset.
+fn parse_range_end(range: &str) -> Option<usize> {
+    let range = range.trim();
+    let range = range.strip_prefix("bytes=").unwrap_or(range);
+    let (_, last) = range.split_once('-')?;
+    last.trim().parse::<usize>().ok().map(|last| last + 1)
+}
+
+/// Requests ECR credentials in the background so the first registry call
+/// does not wait for the token exchange. Errors are only logged here; they
+/// resurface as [`RegistryError::AuthenticationFailed`] on the next request.
+#[cfg(feature = "aws")]
+fn prefetch_ecr_credentials(provider: &Arc<EcrTokenProvider>) {
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+
+    let provider = Arc::clone(provider);
+    runtime.spawn(async move {
+        if let Err(e) = provider.credentials().await {
+            tracing::warn!(error = %e, "Failed to prefetch ECR credentials");
+        }
+    });
+}
+
+/// Builds a `Basic` authorization header value.
+fn basic_auth_value(username: &str, password: &str) -> Result<HeaderValue, RegistryError> {
+    let credentials = base64::Engine::encode(
+        &base64::engine::general_purpose::STANDARD,
+        format!("{username}:{password}"),
+    );
+    HeaderValue::from_str(&format!("Basic {credentials}")).map_err(|_| {
+        RegistryError::AuthenticationFailed {
+            message: "Invalid credentials".to_string(),
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::CacheConfig;
+    use crate::config::EncryptionKey;
+    use crate::test_support::{spawn_mock_registry, MockRequest, MockResponse};
+    use eunomia_core::testing::sample_bundle;
+    use eunomia_core::{BundleSigner, SigningKeyPair};
 
     #[test]
     fn test_client_creation() {
@@ -832,4 +2308,1138 @@ mod tests {
         let auth = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
         assert_eq!(auth, "Bearer my-token");
     }
+
+    #[cfg(not(feature = "aws"))]
+    #[test]
+    fn test_aws_ecr_requires_feature() {
+        let config = RegistryConfig::new("https://123456789012.dkr.ecr.us-east-1.amazonaws.com")
+            .with_auth(RegistryAuth::aws_ecr("us-east-1"));
+        let err = RegistryClient::new(config).unwrap_err();
+
+        assert!(matches!(err, RegistryError::UnsupportedApi { .. }));
+        assert!(err.to_string().contains("`aws` feature"));
+    }
+
+    #[cfg(feature = "aws")]
+    #[test]
+    fn test_aws_ecr_client_creation() {
+        let config = RegistryConfig::new("https://123456789012.dkr.ecr.us-east-1.amazonaws.com")
+            .with_auth(RegistryAuth::aws_ecr("us-east-1"));
+        let client = RegistryClient::new(config).unwrap();
+
+        assert!(client.ecr.is_some());
+    }
+
+    #[test]
+    fn test_parse_range_end() {
+        assert_eq!(parse_range_end("0-1023"), Some(1024));
+        assert_eq!(parse_range_end("bytes=0-9"), Some(10));
+        assert_eq!(parse_range_end("garbage"), None);
+    }
+
+    #[tokio::test]
+    async fn test_bearer_challenge_flow_caches_token() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let tokens_issued = Arc::new(AtomicUsize::new(0));
+        let issued = Arc::clone(&tokens_issued);
+
+        // Requires a bearer token obtained from the realm with basic credentials
+        let base = spawn_mock_registry(move |req| {
+            let authorization = req.header("authorization").unwrap_or_default();
+            if req.path.starts_with("/token") {
+                assert!(authorization.starts_with("Basic "));
+                assert!(req.path.contains("service=test-registry"));
+                let n = issued.fetch_add(1, Ordering::SeqCst) + 1;
+                MockResponse::json(&format!(r#"{{"token":"token-{n}","expires_in":300}}"#))
+            } else if authorization.starts_with("Bearer token-") {
+                MockResponse::json(r#"{"name":"policies/users-service","tags":["v1.0.0"]}"#)
+            } else {
+                MockResponse::status(401).header(
+                    "WWW-Authenticate",
+                    format!(
+                        r#"Bearer realm="{}/token",service="test-registry",scope="repository:policies/users-service:pull""#,
+                        req.base_url()
+                    ),
+                )
+            }
+        })
+        .await;
+
+        let config = RegistryConfig::new(base)
+            .with_namespace("policies")
+            .with_auth(RegistryAuth::basic("user", "pass"));
+        let client = RegistryClient::new(config).unwrap();
+
+        let tags = client.list_tags("users-service").await.unwrap();
+        assert_eq!(tags, vec!["v1.0.0"]);
+        assert_eq!(tokens_issued.load(Ordering::SeqCst), 1);
+
+        // The cached token is reused for the same repository and scope.
+        assert!(client.exists("users-service", "v1.0.0").await.unwrap());
+        assert_eq!(tokens_issued.load(Ordering::SeqCst), 1);
+
+        // An expired token is refreshed transparently.
+        client.tokens.insert(
+            repository_scope("policies/users-service", PULL),
+            "expired",
+            Duration::ZERO,
+        );
+        let tags = client.list_tags("users-service").await.unwrap();
+        assert_eq!(tags, vec!["v1.0.0"]);
+        assert_eq!(tokens_issued.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_services_strips_namespace() {
+        let base = spawn_mock_registry(|req| {
+            assert_eq!(req.path, "/v2/_catalog");
+            MockResponse::json(
+                r#"{"repositories":["policies/orders","policies/users-service","other/app"]}"#,
+            )
+        })
+        .await;
+
+        let client =
+            RegistryClient::new(RegistryConfig::new(&base).with_namespace("policies")).unwrap();
+        let services = client.list_services().await.unwrap();
+        assert_eq!(services, vec!["orders", "users-service"]);
+
+        let client = RegistryClient::new(RegistryConfig::new(&base)).unwrap();
+        assert_eq!(client.list_services().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    // The mock registry holds its lock for each whole request
+    #[allow(clippy::significant_drop_tightening)]
+    async fn test_chunked_upload_retries_failed_chunk() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Default)]
+        struct Upload {
+            received: Vec<u8>,
+            patches: usize,
+            failed_once: bool,
+            finalized_digest: Option<String>,
+        }
+
+        let upload = Arc::new(Mutex::new(Upload::default()));
+        let state = Arc::clone(&upload);
+
+        let base = spawn_mock_registry(move |req| {
+            let mut upload = state.lock().unwrap();
+            let range = |len: usize| format!("0-{}", len - 1);
+            match req.method.as_str() {
+                "POST" => MockResponse::status(202)
+                    .header("Location", "/v2/policies/big/blobs/uploads/session-1"),
+                "PATCH" => {
+                    upload.patches += 1;
+                    // The second chunk fails once with a transient error
+                    if upload.patches == 2 && !upload.failed_once {
+                        upload.failed_once = true;
+                        return MockResponse::status(503);
+                    }
+                    let start: usize = req
+                        .header("content-range")
+                        .and_then(|r| r.split('-').next())
+                        .and_then(|s| s.parse().ok())
+                        .unwrap();
+                    assert_eq!(start, upload.received.len(), "chunks must be contiguous");
+                    upload.received.extend_from_slice(&req.body);
+                    MockResponse::status(202)
+                        .header("Location", "/v2/policies/big/blobs/uploads/session-1")
+                        .header("Range", range(upload.received.len()))
+                }
+                "GET" => MockResponse::status(204)
+                    .header("Location", "/v2/policies/big/blobs/uploads/session-1")
+                    .header("Range", range(upload.received.len())),
+                "PUT" => {
+                    assert!(req.body.is_empty());
+                    upload.finalized_digest =
+                        req.path.split_once("digest=").map(|(_, d)| d.to_string());
+                    MockResponse::status(201)
+                }
+                _ => MockResponse::status(405),
+            }
+        })
+        .await;
+
+        let client = RegistryClient::new(
+            RegistryConfig::new(base)
+                .with_namespace("policies")
+                .with_chunk_size(10),
+        )
+        .unwrap();
+
+        let data: Vec<u8> = (0..35u8).collect();
+        let digest = RegistryClient::compute_digest(&data);
+        let progress = Mutex::new(Vec::new());
+
+        client
+            .upload_blob("big", &data, &digest, &|sent, total| {
+                progress.lock().unwrap().push((sent, total));
+            })
+            .await
+            .unwrap();
+
+        let upload = upload.lock().unwrap();
+        assert_eq!(upload.received, data);
+        assert_eq!(upload.patches, 5);
+        assert_eq!(upload.finalized_digest.as_deref(), Some(digest.as_str()));
+        assert_eq!(
+            *progress.lock().unwrap(),
+            vec![(10, 35), (20, 35), (30, 35), (35, 35)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_small_blob_uses_single_put() {
+        use std::sync::{Arc, Mutex};
+
+        let methods = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&methods);
+
+        let base = spawn_mock_registry(move |req| {
+            seen.lock().unwrap().push(req.method.clone());
+            match req.method.as_str() {
+                "POST" => MockResponse::status(202)
+                    .header("Location", "/v2/small/blobs/uploads/session-1?state=abc"),
+                "PUT" => {
+                    assert_eq!(req.body, b"tiny");
+                    assert!(req.path.contains("?state=abc&digest=sha256:"));
+                    MockResponse::status(201)
+                }
+                _ => MockResponse::status(405),
+            }
+        })
+        .await;
+
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+        let digest = RegistryClient::compute_digest(b"tiny");
+
+        client
+            .upload_blob("small", b"tiny", &digest, &|_, _| {})
+            .await
+            .unwrap();
+
+        assert_eq!(*methods.lock().unwrap(), vec!["POST", "PUT"]);
+    }
+
+    /// Starts a mock registry that stores uploaded blobs and manifests in
+    /// memory and serves them back.
+    async fn spawn_storage_registry() -> (
+        String,
+        std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>>,
+    ) {
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+
+        let store: Arc<Mutex<HashMap<String, Vec<u8>>>> = Arc::default();
+        let objects = Arc::clone(&store);
+
+        let base = spawn_mock_registry(move |req| {
+            let mut objects = objects.lock().unwrap();
+            let (path, query) = req.path.split_once('?').unwrap_or((&req.path, ""));
+            match req.method.as_str() {
+                "POST" => MockResponse::status(202).header("Location", format!("{path}session")),
+                "PUT" if path.contains("/blobs/uploads/") => {
+                    let digest = query.trim_start_matches("digest=");
+                    let repo = path.split("/blobs/").next().unwrap_or_default();
+                    objects.insert(format!("{repo}/blobs/{digest}"), req.body.clone());
+                    MockResponse::status(201)
+                }
+                "PUT" => {
+                    objects.insert(path.to_string(), req.body.clone());
+                    MockResponse::status(201)
+                }
+                "GET" if path.ends_with("/tags/list") => {
+                    let manifests = path.replace("/tags/list", "/manifests/");
+                    let tags: Vec<&str> = objects
+                        .keys()
+                        .filter_map(|key| key.strip_prefix(&manifests))
+                        .collect();
+                    MockResponse::json(&serde_json::json!({ "name": "", "tags": tags }).to_string())
+                }
+                "GET" | "HEAD" => objects.get(path).map_or_else(
+                    || MockResponse::status(404),
+                    |body| {
+                        MockResponse::status(200)
+                            .header(
+                                "Docker-Content-Digest",
+                                RegistryClient::compute_digest(body),
+                            )
+                            .body(body)
+                    },
+                ),
+                _ => MockResponse::status(405),
+            }
+        })
+        .await;
+
+        (base, store)
+    }
+
+    /// Starts a mock registry serving `manifest` for `staging/users-service`
+    /// and answering blob mounts with `mount`, recording every request.
+    async fn spawn_mount_registry(
+        manifest: Vec<u8>,
+        blob: Vec<u8>,
+        mount: MockResponse,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<MockRequest>>>) {
+        use std::sync::{Arc, Mutex};
+
+        let requests: Arc<Mutex<Vec<MockRequest>>> = Arc::default();
+        let seen = Arc::clone(&requests);
+
+        let base = spawn_mock_registry(move |req| {
+            seen.lock().unwrap().push(req.clone());
+            match req.method.as_str() {
+                "GET" if req.path == "/v2/staging/users-service/manifests/1.0.0" => {
+                    MockResponse::status(200).body(&manifest)
+                }
+                "GET" if req.path.starts_with("/v2/staging/users-service/blobs/") => {
+                    MockResponse::status(200).body(&blob)
+                }
+                "POST" if req.path.contains("mount=") => mount.clone(),
+                "PUT" => MockResponse::status(201),
+                _ => MockResponse::status(404),
+            }
+        })
+        .await;
+
+        (base, requests)
+    }
+
+    fn mount_fixture() -> (Vec<u8>, Vec<u8>) {
+        let blob = b"bundle".to_vec();
+        let layer = Descriptor::new(
+            MediaType::eunomia_bundle(),
+            RegistryClient::compute_digest(&blob),
+            blob.len() as u64,
+        );
+        let manifest = serde_json::to_vec(&Manifest::for_bundle(layer, None)).unwrap();
+        (manifest, blob)
+    }
+
+    #[tokio::test]
+    async fn test_copy_mounts_blobs_without_reuploading() {
+        let (manifest, blob) = mount_fixture();
+        let digest = RegistryClient::compute_digest(&blob);
+        let (base, requests) = spawn_mount_registry(
+            manifest.clone(),
+            blob,
+            MockResponse::status(202).header(
+                "Location",
+                format!("/v2/production/users-service/blobs/{digest}"),
+            ),
+        )
+        .await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+
+        client
+            .copy(
+                "staging/users-service",
+                "1.0.0",
+                "production/users-service",
+                "1.0.0",
+            )
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap().clone();
+        let mount = requests.iter().find(|r| r.method == "POST").unwrap();
+        assert!(mount
+            .path
+            .starts_with("/v2/production/users-service/blobs/uploads/"));
+        assert!(mount
+            .path
+            .ends_with(&format!("?mount={digest}&from=staging/users-service")));
+        assert!(!requests.iter().any(|r| r.path.contains("/blobs/sha256:")));
+        let puts: Vec<_> = requests.iter().filter(|r| r.method == "PUT").collect();
+        assert_eq!(puts.len(), 1);
+        assert_eq!(puts[0].path, "/v2/production/users-service/manifests/1.0.0");
+        assert_eq!(puts[0].body, manifest);
+    }
+
+    #[tokio::test]
+    async fn test_copy_uploads_blob_when_mount_is_refused() {
+        let (manifest, blob) = mount_fixture();
+        let (base, requests) = spawn_mount_registry(
+            manifest,
+            blob.clone(),
+            MockResponse::status(202).header(
+                "Location",
+                "/v2/production/users-service/blobs/uploads/session",
+            ),
+        )
+        .await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+
+        client
+            .copy(
+                "staging/users-service",
+                "1.0.0",
+                "production/users-service",
+                "2.0.0",
+            )
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap().clone();
+        let upload = requests
+            .iter()
+            .find(|r| r.method == "PUT" && r.path.contains("/blobs/uploads/session"))
+            .unwrap();
+        assert_eq!(upload.body, blob);
+        assert!(
+            requests
+                .iter()
+                .any(|r| r.method == "PUT"
+                    && r.path == "/v2/production/users-service/manifests/2.0.0")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_copy_within_repository_only_pushes_manifest() {
+        let (manifest, blob) = mount_fixture();
+        let (base, requests) =
+            spawn_mount_registry(manifest, blob, MockResponse::status(500)).await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+
+        client
+            .copy(
+                "staging/users-service",
+                "1.0.0",
+                "staging/users-service",
+                "stable",
+            )
+            .await
+            .unwrap();
+
+        let methods: Vec<_> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.method.clone())
+            .collect();
+        assert_eq!(methods, vec!["GET", "PUT"]);
+    }
+
+    #[tokio::test]
+    async fn test_copy_between_registries_skips_existing_blobs() {
+        let (src_base, _) = spawn_storage_registry().await;
+        let (dst_base, dst_store) = spawn_storage_registry().await;
+        let src = RegistryClient::new(RegistryConfig::new(src_base)).unwrap();
+        let dst = RegistryClient::new(RegistryConfig::new(dst_base)).unwrap();
+        let bundle = sample_bundle();
+        src.publish("users-service", "1.0.0", &bundle)
+            .await
+            .unwrap();
+
+        RegistryClient::copy_between_registries(&src, &dst, "users-service", "1.0.0")
+            .await
+            .unwrap();
+
+        let fetched = dst.fetch("users-service", "1.0.0").await.unwrap();
+        assert_eq!(fetched.compute_checksum(), bundle.compute_checksum());
+        assert_eq!(
+            src.manifest_digest("users-service", "1.0.0").await.unwrap(),
+            dst.manifest_digest("users-service", "1.0.0").await.unwrap()
+        );
+
+        // Replace the copied blob; a second copy must not upload it again
+        let blob_key = dst_store
+            .lock()
+            .unwrap()
+            .keys()
+            .find(|key| key.contains("/blobs/"))
+            .cloned()
+            .unwrap();
+        dst_store
+            .lock()
+            .unwrap()
+            .insert(blob_key.clone(), b"sentinel".to_vec());
+        RegistryClient::copy_between_registries(&src, &dst, "users-service", "1.0.0")
+            .await
+            .unwrap();
+        assert_eq!(dst_store.lock().unwrap()[&blob_key], b"sentinel");
+    }
+
+    /// Starts a mock registry serving `svc-<i>:1.0.0` for each delay in
+    /// `delays`, answering manifest requests after that delay, and records
+    /// the interval each request is in flight.
+    async fn spawn_slow_registry(
+        delays: Vec<Duration>,
+    ) -> (
+        String,
+        std::sync::Arc<std::sync::Mutex<Vec<(Instant, Instant)>>>,
+    ) {
+        use std::sync::{Arc, Mutex};
+
+        let client = RegistryClient::new(RegistryConfig::new("http://localhost")).unwrap();
+        let objects: HashMap<String, (Vec<u8>, Duration)> = delays
+            .iter()
+            .enumerate()
+            .flat_map(|(i, delay)| {
+                let service = format!("svc-{i}");
+                let bundle = Bundle::builder(&service).version("1.0.0").build();
+                let prepared = client
+                    .prepare_publish(&service, "1.0.0", &bundle, None)
+                    .unwrap();
+                [
+                    (
+                        format!("/v2/{service}/manifests/1.0.0"),
+                        (serde_json::to_vec(&prepared.manifest).unwrap(), *delay),
+                    ),
+                    (
+                        format!("/v2/{service}/blobs/{}", prepared.bundle_digest()),
+                        (prepared.bundle_blob, Duration::ZERO),
+                    ),
+                ]
+            })
+            .collect();
+
+        let in_flight: Arc<Mutex<Vec<(Instant, Instant)>>> = Arc::default();
+        let seen = Arc::clone(&in_flight);
+        let base = spawn_mock_registry(move |req| {
+            let Some((body, delay)) = objects.get(&req.path) else {
+                return MockResponse::status(404);
+            };
+            let now = Instant::now();
+            seen.lock().unwrap().push((now, now + *delay));
+            MockResponse::status(200).body(body).delay(*delay)
+        })
+        .await;
+
+        (base, in_flight)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_many_respects_concurrency_limit() {
+        let delays = [40, 10, 30, 20, 50, 10]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        let (base, in_flight) = spawn_slow_registry(delays).await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+        let mut requests: Vec<_> = (0..6)
+            .map(|i| (format!("svc-{i}"), "1.0.0".to_string()))
+            .collect();
+        requests.insert(3, ("missing".to_string(), "1.0.0".to_string()));
+
+        let result = client.fetch_many(requests, 2, None).await;
+
+        assert_eq!(result.bundles.len(), 7);
+        assert_eq!(result.failed_count, 1);
+        assert!(matches!(
+            result.bundles[3],
+            Err(RegistryError::NotFound { .. })
+        ));
+        let names: Vec<_> = result
+            .bundles
+            .iter()
+            .filter_map(|bundle| bundle.as_ref().ok())
+            .map(|bundle| bundle.name.clone())
+            .collect();
+        assert_eq!(
+            names,
+            ["svc-0", "svc-1", "svc-2", "svc-3", "svc-4", "svc-5"]
+        );
+
+        let in_flight = in_flight.lock().unwrap().clone();
+        let max_concurrent = in_flight
+            .iter()
+            .map(|(start, _)| {
+                in_flight
+                    .iter()
+                    .filter(|(s, e)| s <= start && start < e)
+                    .count()
+            })
+            .max()
+            .unwrap();
+        assert_eq!(max_concurrent, 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_many_times_out_slow_fetches() {
+        let delays = vec![Duration::ZERO, Duration::from_secs(5)];
+        let (base, _) = spawn_slow_registry(delays).await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+        let requests = vec![
+            ("svc-0".to_string(), "1.0.0".to_string()),
+            ("svc-1".to_string(), "1.0.0".to_string()),
+        ];
+
+        let result = client
+            .fetch_many(requests, 4, Some(Duration::from_millis(200)))
+            .await;
+
+        assert_eq!(result.failed_count, 1);
+        assert!(result.bundles[0].is_ok());
+        let err = result.bundles[1].as_ref().unwrap_err();
+        assert!(matches!(err, RegistryError::Timeout { service, .. } if service == "svc-1"));
+        assert_eq!(err.code(), "EUN-R020");
+    }
+
+    #[test]
+    fn test_prepare_publish_builds_manifest() {
+        let client =
+            RegistryClient::new(RegistryConfig::new("https://registry.example.com")).unwrap();
+        let bundle = sample_bundle();
+        let signed = BundleSigner::from_key_pair(&SigningKeyPair::generate(), "ci".to_string())
+            .sign(&bundle);
+
+        let prepared = client
+            .prepare_publish("users", "v1.0.0", &bundle, Some(&signed.signatures))
+            .unwrap();
+
+        assert_eq!(
+            prepared.bundle_digest(),
+            RegistryClient::compute_digest(&prepared.bundle_blob)
+        );
+        let layers = &prepared.manifest.layers;
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[0].media_type.as_str(), MediaType::EUNOMIA_BUNDLE);
+        assert_eq!(
+            layers[0].annotations.as_ref().unwrap()["org.opencontainers.image.title"],
+            "users-v1.0.0.bundle.tar.gz"
+        );
+        assert_eq!(
+            layers[1].digest,
+            RegistryClient::compute_digest(prepared.signature_blob.as_ref().unwrap())
+        );
+        let annotations = prepared.manifest.annotations.as_ref().unwrap();
+        assert_eq!(annotations["org.opencontainers.image.version"], "v1.0.0");
+        assert!(prepared.manifest_digest().unwrap().starts_with("sha256:"));
+
+        let unsigned = client
+            .prepare_publish("users", "v1.0.0", &bundle, None)
+            .unwrap();
+        assert_eq!(unsigned.manifest.layers.len(), 1);
+        assert!(unsigned.signature_blob.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_publish_and_fetch_round_trip() {
+        let (base, store) = spawn_storage_registry().await;
+        let key = EncryptionKey::new([9u8; 32]);
+        let client =
+            RegistryClient::new(RegistryConfig::new(base).with_encryption_key(key)).unwrap();
+        let bundle = sample_bundle();
+
+        client.publish("users", "v1.0.0", &bundle).await.unwrap();
+
+        let manifest: Manifest =
+            serde_json::from_slice(&store.lock().unwrap()["/v2/users/manifests/v1.0.0"]).unwrap();
+        let layer = manifest.bundle_layer().unwrap();
+        assert_eq!(
+            layer.media_type.as_str(),
+            MediaType::EUNOMIA_ENCRYPTED_BUNDLE
+        );
+        let stored = store.lock().unwrap()[&format!("/v2/users/blobs/{}", layer.digest)].clone();
+        assert!(Bundle::from_bytes(&stored).is_err());
+
+        let fetched = client.fetch("users", "v1.0.0").await.unwrap();
+        assert_eq!(fetched.name, bundle.name);
+        assert_eq!(fetched.policies, bundle.policies);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_falls_back_to_mirror_when_registry_unavailable() {
+        let primary = spawn_mock_registry(|_| MockResponse::status(503)).await;
+        let (mirror, _store) = spawn_storage_registry().await;
+        let bundle = sample_bundle();
+        RegistryClient::new(RegistryConfig::new(&mirror).with_namespace("policies"))
+            .unwrap()
+            .publish("users", "v1.0.0", &bundle)
+            .await
+            .unwrap();
+
+        let client = RegistryClient::new(
+            RegistryConfig::new(primary)
+                .with_namespace("policies")
+                .with_mirror(&mirror),
+        )
+        .unwrap();
+
+        let (fetched, source) = client.fetch_with_fallback("users", "v1.0.0").await.unwrap();
+        assert_eq!(source, mirror);
+        assert_eq!(fetched.policies, bundle.policies);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_does_not_fall_back_on_not_found() {
+        let (primary, _primary_store) = spawn_storage_registry().await;
+        let (mirror, _store) = spawn_storage_registry().await;
+        RegistryClient::new(RegistryConfig::new(&mirror))
+            .unwrap()
+            .publish("users", "v1.0.0", &sample_bundle())
+            .await
+            .unwrap();
+
+        let client = RegistryClient::new(RegistryConfig::new(primary).with_mirror(mirror)).unwrap();
+
+        let result = client.fetch_with_fallback("users", "v1.0.0").await;
+        assert!(matches!(result, Err(RegistryError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_encrypted_bundle_requires_matching_key() {
+        let (base, _store) = spawn_storage_registry().await;
+        let publisher = RegistryClient::new(
+            RegistryConfig::new(&base).with_encryption_key(EncryptionKey::new([1u8; 32])),
+        )
+        .unwrap();
+        publisher
+            .publish("users", "v1.0.0", &sample_bundle())
+            .await
+            .unwrap();
+
+        let no_key = RegistryClient::new(RegistryConfig::new(&base)).unwrap();
+        let err = no_key.fetch("users", "v1.0.0").await.unwrap_err();
+        assert!(err.to_string().contains("no encryption key"));
+
+        let wrong_key = RegistryClient::new(
+            RegistryConfig::new(&base).with_encryption_key(EncryptionKey::new([2u8; 32])),
+        )
+        .unwrap();
+        let err = wrong_key.fetch("users", "v1.0.0").await.unwrap_err();
+        assert!(err.to_string().contains("decryption failed"));
+    }
+
+    fn cached_client(base: &str, cache_dir: &std::path::Path, validate: bool) -> RegistryClient {
+        let cache =
+            BundleCache::new(CacheConfig::new(cache_dir).with_validate_digest(validate)).unwrap();
+        RegistryClient::new(RegistryConfig::new(base))
+            .unwrap()
+            .with_cache(cache)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_with_digest_reports_manifest_digest() {
+        let (base, _store) = spawn_storage_registry().await;
+        let dir = tempfile::tempdir().unwrap();
+        let bundle = sample_bundle();
+        let uncached = RegistryClient::new(RegistryConfig::new(&base)).unwrap();
+        let layer_digest = uncached.publish("users", "v1.0.0", &bundle).await.unwrap();
+        let (manifest, _) = uncached
+            .fetch_raw_manifest("users", "v1.0.0")
+            .await
+            .unwrap();
+        let published = RegistryClient::compute_digest(&manifest);
+        assert_ne!(published, layer_digest);
+
+        let (fetched, digest) = uncached.fetch_with_digest("users", "v1.0.0").await.unwrap();
+        assert_eq!(fetched.policies, bundle.policies);
+        assert_eq!(digest, published);
+
+        // Cached bundles report the digest recorded with them, also offline
+        let client = cached_client(&base, dir.path(), true);
+        client.fetch("users", "v1.0.0").await.unwrap();
+        let offline = client.with_offline(true);
+        let (_, digest) = offline.fetch_with_digest("users", "v1.0.0").await.unwrap();
+        assert_eq!(digest, published);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_refreshes_cache_when_tag_is_repushed() {
+        let (base, _store) = spawn_storage_registry().await;
+        let dir = tempfile::tempdir().unwrap();
+        let client = cached_client(&base, dir.path(), true);
+
+        let original = sample_bundle();
+        client.publish("users", "v1.0.0", &original).await.unwrap();
+        let cached = client.fetch("users", "v1.0.0").await.unwrap();
+        assert_eq!(cached.policies, original.policies);
+
+        let repushed = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy(
+                "users_service.authz",
+                "package users_service.authz\n\nx := 1\n",
+            )
+            .build();
+        client.publish("users", "v1.0.0", &repushed).await.unwrap();
+
+        let fetched = client.fetch("users", "v1.0.0").await.unwrap();
+        assert_eq!(fetched.policies, repushed.policies);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_trusts_cache_without_digest_validation() {
+        let (base, _store) = spawn_storage_registry().await;
+        let dir = tempfile::tempdir().unwrap();
+        let client = cached_client(&base, dir.path(), false);
+
+        let original = sample_bundle();
+        client.publish("users", "v1.0.0", &original).await.unwrap();
+        client.fetch("users", "v1.0.0").await.unwrap();
+
+        let repushed = Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy(
+                "users_service.authz",
+                "package users_service.authz\n\nx := 1\n",
+            )
+            .build();
+        client.publish("users", "v1.0.0", &repushed).await.unwrap();
+
+        let fetched = client.fetch("users", "v1.0.0").await.unwrap();
+        assert_eq!(fetched.policies, original.policies);
+    }
+
+    #[allow(clippy::float_cmp)] // counters hold whole numbers
+    #[tokio::test]
+    async fn test_metrics_record_cache_lookups_and_fetches() {
+        let (base, _store) = spawn_storage_registry().await;
+        let dir = tempfile::tempdir().unwrap();
+        let client = cached_client(&base, dir.path(), false);
+        let metrics = MetricsRegistry::global().registry();
+        let hits = metrics.get_cache_operation_count("hit");
+
+        client
+            .publish("metered", "v1.0.0", &sample_bundle())
+            .await
+            .unwrap();
+        client.fetch("metered", "v1.0.0").await.unwrap();
+        client.fetch("metered", "v1.0.0").await.unwrap();
+
+        // The second fetch is served from the cache, not the registry
+        assert_eq!(metrics.get_publish_count("metered", true), 1.0);
+        assert_eq!(metrics.get_fetch_count("metered", true), 1.0);
+        assert!(metrics.get_cache_operation_count("hit") > hits);
+    }
+
+    #[tokio::test]
+    async fn test_offline_fetch_serves_only_from_cache() {
+        let (base, _store) = spawn_storage_registry().await;
+        let dir = tempfile::tempdir().unwrap();
+
+        let offline = cached_client("http://127.0.0.1:9", dir.path(), true).with_offline(true);
+        let err = offline.fetch("users", "v1.0.0").await.unwrap_err();
+        assert!(matches!(err, RegistryError::Offline { .. }));
+
+        let online = cached_client(&base, dir.path(), true);
+        let bundle = sample_bundle();
+        online.publish("users", "v1.0.0", &bundle).await.unwrap();
+        online.fetch("users", "v1.0.0").await.unwrap();
+
+        assert!(offline.is_offline());
+        let cached = offline.fetch("users", "v1.0.0").await.unwrap();
+        assert_eq!(cached.policies, bundle.policies);
+    }
+
+    #[tokio::test]
+    async fn test_offline_resolve_uses_cached_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let offline = cached_client("http://127.0.0.1:9", dir.path(), true).with_offline(true);
+
+        let err = offline.resolve_version("users", "^1.0").await.unwrap_err();
+        assert!(matches!(err, RegistryError::Offline { .. }));
+
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let bundle = sample_bundle();
+        for version in ["v1.0.0", "v1.2.0", "v2.0.0"] {
+            cache.put("users", version, &bundle).unwrap();
+        }
+
+        assert_eq!(
+            offline.resolve_version("users", "^1.0").await.unwrap(),
+            "v1.2.0"
+        );
+        assert_eq!(offline.get_latest_version("users").await.unwrap(), "v2.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_resolve_version_follows_channel_tags() {
+        let (base, store) = spawn_storage_registry().await;
+        let client = RegistryClient::new(RegistryConfig::new(&base)).unwrap();
+        for version in ["v1.0.0", "v1.1.0", "v1.2.0-rc.1"] {
+            client
+                .publish("users", version, &sample_bundle())
+                .await
+                .unwrap();
+        }
+        let manifest = |tag: &str| -> (String, Vec<u8>) {
+            store
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(key, _)| key.ends_with(&format!("/manifests/{tag}")))
+                .map(|(key, body)| (key.clone(), body.clone()))
+                .unwrap()
+        };
+
+        // `stable` is annotated with the version it was published as
+        let (key, body) = manifest("v1.0.0");
+        store
+            .lock()
+            .unwrap()
+            .insert(key.replace("v1.0.0", "stable"), body);
+        assert_eq!(
+            client.resolve_version("users", "stable").await.unwrap(),
+            "v1.0.0"
+        );
+
+        // Without the annotation, the manifest digest identifies the version
+        let (key, body) = manifest("v1.1.0");
+        let mut unannotated: Manifest = serde_json::from_slice(&body).unwrap();
+        unannotated.annotations = None;
+        let body = serde_json::to_vec(&unannotated).unwrap();
+        store.lock().unwrap().insert(key.clone(), body.clone());
+        store
+            .lock()
+            .unwrap()
+            .insert(key.replace("v1.1.0", "beta"), body);
+        assert_eq!(
+            client.resolve_version("users", "beta").await.unwrap(),
+            "v1.1.0"
+        );
+
+        // Prereleases are skipped unless asked for
+        assert_eq!(client.get_latest_version("users").await.unwrap(), "v1.1.0");
+        let client = client.with_prereleases(true);
+        assert_eq!(
+            client.resolve_version("users", "^1.1").await.unwrap(),
+            "v1.2.0-rc.1"
+        );
+        let err = client.resolve_version("users", "^2").await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No version of users matches '^2' (available: v1.0.0, v1.1.0, v1.2.0-rc.1)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signed_publish_attaches_signature_layer() {
+        let (base, store) = spawn_storage_registry().await;
+        let key_pair = SigningKeyPair::generate();
+        let signed =
+            BundleSigner::from_key_pair(&key_pair, "release".to_string()).sign(&sample_bundle());
+
+        // Encrypt so the embedded signature is not readable without the key
+        let publisher = RegistryClient::new(
+            RegistryConfig::new(&base).with_encryption_key(EncryptionKey::new([3u8; 32])),
+        )
+        .unwrap();
+        publisher
+            .publish_signed("users", "v1.0.0", &signed)
+            .await
+            .unwrap();
+
+        let manifest: Manifest =
+            serde_json::from_slice(&store.lock().unwrap()["/v2/users/manifests/v1.0.0"]).unwrap();
+        let layer = manifest.signature_layer().unwrap();
+        let stored = store.lock().unwrap()[&format!("/v2/users/blobs/{}", layer.digest)].clone();
+        let signatures = SignatureFile::from_json(std::str::from_utf8(&stored).unwrap()).unwrap();
+        assert_eq!(signatures.signatures[0].key_id, "release");
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BundleCache::new(CacheConfig::new(dir.path())).unwrap();
+        let fetcher = RegistryClient::new(
+            RegistryConfig::new(&base).with_encryption_key(EncryptionKey::new([3u8; 32])),
+        )
+        .unwrap()
+        .with_cache(cache);
+
+        let mut verifier = BundleVerifier::new();
+        verifier.add_public_key("release", key_pair.verifying_key());
+
+        let fetched = fetcher.fetch_signed("users", "v1.0.0").await.unwrap();
+        verifier.verify(&fetched).unwrap();
+
+        // Signatures are served from the cache as well
+        let cached = fetcher
+            .with_offline(true)
+            .fetch_signed("users", "v1.0.0")
+            .await
+            .unwrap();
+        verifier.verify(&cached).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_attach_referrer_points_at_bundle_manifest() {
+        let (base, store) = spawn_storage_registry().await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+        client
+            .publish("users", "v1.0.0", &sample_bundle())
+            .await
+            .unwrap();
+        let sbom = br#"{"bomFormat":"CycloneDX"}"#;
+
+        let digest = client
+            .attach_referrer(
+                "users",
+                "v1.0.0",
+                sbom,
+                "application/vnd.cyclonedx+json",
+                HashMap::from([("org.example.kind".to_string(), "sbom".to_string())]),
+            )
+            .await
+            .unwrap();
+
+        let objects = store.lock().unwrap().clone();
+        let bundle_manifest = &objects["/v2/users/manifests/v1.0.0"];
+        let referrer: Manifest =
+            serde_json::from_slice(&objects[&format!("/v2/users/manifests/{digest}")]).unwrap();
+        let subject = referrer.subject.unwrap();
+        assert_eq!(
+            subject.digest,
+            RegistryClient::compute_digest(bundle_manifest)
+        );
+        assert_eq!(subject.size, bundle_manifest.len() as u64);
+        assert_eq!(
+            referrer.artifact_type.as_deref(),
+            Some("application/vnd.cyclonedx+json")
+        );
+        assert_eq!(
+            objects[&format!("/v2/users/blobs/{}", referrer.layers[0].digest)],
+            sbom
+        );
+        assert_eq!(referrer.annotations.unwrap()["org.example.kind"], "sbom");
+    }
+
+    #[tokio::test]
+    async fn test_list_referrers_returns_index_descriptors() {
+        let manifest = serde_json::to_vec(&Manifest::new(Vec::new())).unwrap();
+        let subject = RegistryClient::compute_digest(&manifest);
+        let referrers_path = format!("/v2/users/referrers/{subject}");
+
+        let base = spawn_mock_registry(move |req| match req.path.as_str() {
+            "/v2/users/manifests/v1.0.0" => MockResponse::status(200).body(&manifest),
+            path if path == referrers_path => MockResponse::json(
+                r#"{
+                    "schemaVersion": 2,
+                    "mediaType": "application/vnd.oci.image.index.v1+json",
+                    "manifests": [{
+                        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                        "digest": "sha256:sbom",
+                        "size": 321,
+                        "artifactType": "application/spdx+json",
+                        "annotations": {"org.example.kind": "sbom"}
+                    }]
+                }"#,
+            ),
+            _ => MockResponse::status(404),
+        })
+        .await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+
+        let referrers = client.list_referrers("users", "v1.0.0").await.unwrap();
+
+        assert_eq!(referrers.len(), 1);
+        assert_eq!(referrers[0].digest, "sha256:sbom");
+        assert_eq!(referrers[0].size, 321);
+        assert_eq!(
+            referrers[0].referrer_type.as_deref(),
+            Some("application/spdx+json")
+        );
+        assert!(matches!(
+            client.list_referrers("users", "v2.0.0").await,
+            Err(RegistryError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_referrers_reports_unsupported_registry() {
+        let (base, _store) = spawn_storage_registry().await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+        client
+            .publish("users", "v1.0.0", &sample_bundle())
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            client.list_referrers("users", "v1.0.0").await,
+            Err(RegistryError::UnsupportedApi { .. })
+        ));
+    }
+
+    /// Publishes `signed` to a fresh storage registry and returns a client
+    /// for it that verifies signatures against `key_pair`.
+    async fn verifying_client(signed: &SignedBundle, key_pair: &SigningKeyPair) -> RegistryClient {
+        let (base, _store) = spawn_storage_registry().await;
+        RegistryClient::new(RegistryConfig::new(&base))
+            .unwrap()
+            .publish_signed("users", "v1.0.0", signed)
+            .await
+            .unwrap();
+
+        RegistryClient::new(
+            RegistryConfig::new(base)
+                .with_trusted_key("release", key_pair.public_key_base64())
+                .with_verify_signatures(true),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fetch_verifies_trusted_signature() {
+        let key_pair = SigningKeyPair::generate();
+        let signed =
+            BundleSigner::from_key_pair(&key_pair, "release".to_string()).sign(&sample_bundle());
+        let client = verifying_client(&signed, &key_pair).await;
+
+        let fetched = client.fetch("users", "v1.0.0").await.unwrap();
+        assert_eq!(fetched.policies, signed.bundle.policies);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_tampered_bundle() {
+        let key_pair = SigningKeyPair::generate();
+        let signed =
+            BundleSigner::from_key_pair(&key_pair, "release".to_string()).sign(&sample_bundle());
+        let mut tampered = signed.bundle.clone();
+        tampered.policies.insert(
+            "users_service.authz".to_string(),
+            "package users_service.authz\nallow := true\n".to_string(),
+        );
+        let client =
+            verifying_client(&SignedBundle::new(tampered, signed.signatures), &key_pair).await;
+
+        let result = client.fetch("users", "v1.0.0").await;
+        assert!(matches!(
+            result,
+            Err(RegistryError::SignatureVerificationFailed { ref service, .. }) if service == "users"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_unsigned_bundle_when_verifying() {
+        let key_pair = SigningKeyPair::generate();
+        let client = verifying_client(&SignedBundle::unsigned(sample_bundle()), &key_pair).await;
+
+        let err = client.fetch("users", "v1.0.0").await.unwrap_err();
+        assert!(err.to_string().ends_with("bundle is not signed"));
+    }
+
+    #[test]
+    fn test_invalid_trusted_key_is_rejected() {
+        let config = RegistryConfig::new("https://example.com")
+            .with_trusted_key("release", "not-a-key")
+            .with_verify_signatures(true);
+
+        assert!(matches!(
+            RegistryClient::new(config),
+            Err(RegistryError::InvalidTrustedKey { key_id, .. }) if key_id == "release"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_signed_without_signature_layer_is_unsigned() {
+        let (base, store) = spawn_storage_registry().await;
+        let client = RegistryClient::new(RegistryConfig::new(base)).unwrap();
+        client
+            .publish("users", "v1.0.0", &sample_bundle())
+            .await
+            .unwrap();
+
+        let manifest: Manifest =
+            serde_json::from_slice(&store.lock().unwrap()["/v2/users/manifests/v1.0.0"]).unwrap();
+        assert!(manifest.signature_layer().is_none());
+
+        let fetched = client.fetch_signed("users", "v1.0.0").await.unwrap();
+        assert!(!fetched.is_signed());
+    }
 }
@@ -1,8 +1,11 @@
 //! Configuration types for registry client.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::error::RegistryError;
+
 /// Configuration for the registry client.
 #[derive(Debug, Clone)]
 pub struct RegistryConfig {
@@ -23,8 +26,27 @@ pub struct RegistryConfig {
 
     /// User agent string.
     pub user_agent: String,
+
+    /// Blobs larger than this are uploaded in chunks of this many bytes.
+    pub chunk_size: usize,
+
+    /// Key used to encrypt bundles on publish and decrypt them on fetch.
+    pub encryption_key: Option<EncryptionKey>,
+
+    /// Mirrors tried in order when the registry is unavailable on fetch.
+    pub mirrors: Vec<MirrorConfig>,
+
+    /// Whether fetched bundles must carry a valid signature from a trusted key.
+    pub verify_signatures: bool,
+
+    /// Public keys trusted for signature verification, by key ID (base64,
+    /// as accepted by `BundleVerifier::add_public_key_base64`).
+    pub trusted_keys: HashMap<String, String>,
 }
 
+/// Default upload chunk size (5 MiB).
+pub const DEFAULT_CHUNK_SIZE: usize = 5 * 1024 * 1024;
+
 impl RegistryConfig {
     /// Creates a new registry configuration with the given URL.
     ///
@@ -45,6 +67,11 @@ impl RegistryConfig {
             timeout: Duration::from_secs(30),
             tls: None,
             user_agent: format!("eunomia-registry/{}", env!("CARGO_PKG_VERSION")),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            encryption_key: None,
+            mirrors: Vec::new(),
+            verify_signatures: false,
+            trusted_keys: HashMap::new(),
         }
     }
 
@@ -86,6 +113,112 @@ impl RegistryConfig {
         self
     }
 
+    /// Sets the upload chunk size in bytes.
+    ///
+    /// Blobs up to this size are uploaded with a single `PUT`; larger blobs
+    /// use the OCI chunked upload protocol. A size of zero is treated as one
+    /// byte.
+    #[must_use]
+    pub const fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets the key used to encrypt bundles at rest in the registry.
+    ///
+    /// Bundles are encrypted with AES-256-GCM before upload and pushed with
+    /// the [`MediaType::EUNOMIA_ENCRYPTED_BUNDLE`] layer type. Encrypted
+    /// bundles are decrypted transparently on fetch; unencrypted bundles
+    /// are still accepted.
+    ///
+    /// [`MediaType::EUNOMIA_ENCRYPTED_BUNDLE`]: crate::MediaType::EUNOMIA_ENCRYPTED_BUNDLE
+    #[must_use]
+    pub const fn with_encryption_key(mut self, key: EncryptionKey) -> Self {
+        self.encryption_key = Some(key);
+        self
+    }
+
+    /// Enables or disables signature verification on fetch.
+    ///
+    /// When enabled, every fetched bundle (including bundles served from
+    /// the cache) must be signed by one of the
+    /// [`trusted_keys`](Self::trusted_keys), or the fetch fails with
+    /// [`SignatureVerificationFailed`].
+    ///
+    /// [`SignatureVerificationFailed`]: crate::RegistryError::SignatureVerificationFailed
+    #[must_use]
+    pub const fn with_verify_signatures(mut self, verify: bool) -> Self {
+        self.verify_signatures = verify;
+        self
+    }
+
+    /// Adds a trusted public key (base64) used to verify bundle signatures.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use eunomia_registry::RegistryConfig;
+    ///
+    /// let config = RegistryConfig::new("https://registry.example.com")
+    ///     .with_trusted_key("release", "11qYAYKxCrfVS/7TyWQHOg7hcvPapiMlrwIaaPcHURo=")
+    ///     .with_verify_signatures(true);
+    /// assert!(config.trusted_keys.contains_key("release"));
+    /// ```
+    #[must_use]
+    pub fn with_trusted_key(
+        mut self,
+        key_id: impl Into<String>,
+        public_key_base64: impl Into<String>,
+    ) -> Self {
+        self.trusted_keys
+            .insert(key_id.into(), public_key_base64.into());
+        self
+    }
+
+    /// Adds a mirror that shares this registry's namespace, authentication
+    /// and TLS settings.
+    ///
+    /// Fetches fall back to mirrors, in the order they were added, when the
+    /// registry fails with a connection error or a `5xx` response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use eunomia_registry::RegistryConfig;
+    ///
+    /// let config = RegistryConfig::new("https://registry.example.com")
+    ///     .with_mirror("https://mirror.example.com");
+    /// assert_eq!(config.mirrors[0].url, "https://mirror.example.com");
+    /// ```
+    #[must_use]
+    pub fn with_mirror(self, mirror_url: impl Into<String>) -> Self {
+        self.with_mirror_config(MirrorConfig::new(mirror_url))
+    }
+
+    /// Adds a mirror with its own namespace, authentication or TLS settings.
+    #[must_use]
+    pub fn with_mirror_config(mut self, mirror: MirrorConfig) -> Self {
+        self.mirrors.push(mirror);
+        self
+    }
+
+    /// Returns the configuration used to talk to `mirror`: this
+    /// configuration with the mirror's URL and overrides applied, and no
+    /// mirrors of its own.
+    pub(crate) fn for_mirror(&self, mirror: &MirrorConfig) -> Self {
+        Self {
+            url: mirror.url.clone(),
+            namespace: mirror
+                .namespace
+                .clone()
+                .unwrap_or_else(|| self.namespace.clone()),
+            auth: mirror.auth.clone().unwrap_or_else(|| self.auth.clone()),
+            tls: mirror.tls.clone().or_else(|| self.tls.clone()),
+            mirrors: Vec::new(),
+            ..self.clone()
+        }
+    }
+
     /// Returns the full repository name for a service.
     ///
     /// # Examples
@@ -110,6 +243,84 @@ impl RegistryConfig {
     }
 }
 
+/// A registry mirror used as a fetch fallback.
+///
+/// Settings left unset are inherited from the primary [`RegistryConfig`].
+#[derive(Debug, Clone)]
+pub struct MirrorConfig {
+    /// Mirror URL (e.g., "<https://mirror.example.com>").
+    pub url: String,
+
+    /// Namespace prefix override.
+    pub namespace: Option<String>,
+
+    /// Authentication override.
+    pub auth: Option<RegistryAuth>,
+
+    /// TLS configuration override.
+    pub tls: Option<TlsConfig>,
+}
+
+impl MirrorConfig {
+    /// Creates a mirror that inherits every setting from the primary registry.
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            namespace: None,
+            auth: None,
+            tls: None,
+        }
+    }
+
+    /// Overrides the namespace prefix.
+    #[must_use]
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Overrides the authentication method.
+    #[must_use]
+    pub fn with_auth(mut self, auth: RegistryAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Overrides the TLS configuration.
+    #[must_use]
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+}
+
+/// A 256-bit key for bundle encryption at rest.
+///
+/// The key bytes are never printed by `Debug`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    /// Creates a key from raw bytes.
+    #[must_use]
+    pub const fn new(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the raw key bytes.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EncryptionKey(<redacted>)")
+    }
+}
+
 /// Authentication methods for registry access.
 #[derive(Debug, Clone)]
 pub enum RegistryAuth {
@@ -134,6 +345,9 @@ pub enum RegistryAuth {
     AwsEcr {
         /// AWS region.
         region: String,
+        /// Account ID of the registry, if it differs from the caller's
+        /// default registry.
+        registry_id: Option<String>,
     },
 
     /// GCP Artifact Registry authentication (uses ADC).
@@ -179,11 +393,22 @@ impl RegistryAuth {
         }
     }
 
-    /// Creates AWS ECR authentication.
+    /// Creates AWS ECR authentication for the caller's default registry.
     #[must_use]
     pub fn aws_ecr(region: impl Into<String>) -> Self {
         Self::AwsEcr {
             region: region.into(),
+            registry_id: None,
+        }
+    }
+
+    /// Creates AWS ECR authentication for the registry owned by the given
+    /// AWS account (e.g. a cross-account registry).
+    #[must_use]
+    pub fn aws_ecr_registry(region: impl Into<String>, registry_id: impl Into<String>) -> Self {
+        Self::AwsEcr {
+            region: region.into(),
+            registry_id: Some(registry_id.into()),
         }
     }
 
@@ -258,6 +483,167 @@ impl TlsConfig {
     }
 }
 
+/// Endpoint of the S3-compatible XML API of Google Cloud Storage.
+const GCS_ENDPOINT: &str = "https://storage.googleapis.com";
+
+/// Location of bundles in S3-compatible object storage.
+///
+/// Used by [`S3Store`](crate::S3Store), which needs the `s3` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Config {
+    /// Bucket name.
+    pub bucket: String,
+
+    /// Key prefix of every object, without leading or trailing slashes.
+    pub prefix: String,
+
+    /// Endpoint URL, for S3-compatible services other than AWS.
+    pub endpoint: Option<String>,
+
+    /// Region; defaults to the AWS configuration, then `us-east-1`.
+    pub region: Option<String>,
+
+    /// Whether buckets are addressed in the URL path instead of the host
+    /// name, as most self-hosted services require.
+    pub path_style: bool,
+
+    /// Credentials used to sign requests.
+    pub credentials: S3Credentials,
+}
+
+impl S3Config {
+    /// Creates a configuration for the root of `bucket`.
+    #[must_use]
+    pub fn new(bucket: impl Into<String>) -> Self {
+        Self {
+            bucket: bucket.into(),
+            prefix: String::new(),
+            endpoint: None,
+            region: None,
+            path_style: false,
+            credentials: S3Credentials::Environment,
+        }
+    }
+
+    /// Parses a store URL: `s3://bucket/prefix`, or `gs://bucket/prefix`
+    /// for Google Cloud Storage through its S3-compatible API.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::InvalidUrl`] if the URL has another scheme
+    /// or no bucket.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use eunomia_registry::S3Config;
+    ///
+    /// let config = S3Config::from_url("s3://policies/prod/bundles/").unwrap();
+    /// assert_eq!(config.bucket, "policies");
+    /// assert_eq!(config.prefix, "prod/bundles");
+    /// ```
+    pub fn from_url(url: &str) -> Result<Self, RegistryError> {
+        let invalid = || RegistryError::InvalidUrl {
+            url: url.to_string(),
+        };
+        let (scheme, location) = url.split_once("://").ok_or_else(invalid)?;
+        let (bucket, prefix) = location.split_once('/').unwrap_or((location, ""));
+        if bucket.is_empty() {
+            return Err(invalid());
+        }
+
+        let config = Self::new(bucket).with_prefix(prefix);
+        match scheme {
+            "s3" => Ok(config),
+            "gs" => Ok(config.with_endpoint(GCS_ENDPOINT).with_region("auto")),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Sets the key prefix.
+    #[must_use]
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.trim_matches('/').to_string();
+        self
+    }
+
+    /// Sets the endpoint URL.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Sets the region.
+    #[must_use]
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Sets whether buckets are addressed in the URL path.
+    #[must_use]
+    pub const fn with_path_style(mut self, path_style: bool) -> Self {
+        self.path_style = path_style;
+        self
+    }
+
+    /// Sets the credentials.
+    #[must_use]
+    pub fn with_credentials(mut self, credentials: S3Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+}
+
+/// Credentials for S3-compatible object storage.
+///
+/// The secret key is never printed by `Debug`.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub enum S3Credentials {
+    /// The standard AWS credential chain: environment variables, shared
+    /// config/profile, web identity or instance profile.
+    #[default]
+    Environment,
+
+    /// A fixed access key, e.g. the HMAC key of a GCS service account.
+    Static {
+        /// Access key ID.
+        access_key_id: String,
+        /// Secret access key.
+        secret_access_key: String,
+        /// Session token of temporary credentials.
+        session_token: Option<String>,
+    },
+}
+
+impl S3Credentials {
+    /// Creates static credentials from an access key.
+    #[must_use]
+    pub fn access_key(
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        Self::Static {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for S3Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Environment => f.write_str("Environment"),
+            Self::Static { access_key_id, .. } => f
+                .debug_struct("Static")
+                .field("access_key_id", access_key_id)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,6 +654,13 @@ mod tests {
         assert_eq!(config.url, "https://example.com");
         assert!(config.namespace.is_empty());
         assert_eq!(config.timeout, Duration::from_secs(30));
+        assert_eq!(config.chunk_size, DEFAULT_CHUNK_SIZE);
+    }
+
+    #[test]
+    fn test_config_with_chunk_size() {
+        let config = RegistryConfig::new("https://example.com").with_chunk_size(1024);
+        assert_eq!(config.chunk_size, 1024);
     }
 
     #[test]
@@ -291,6 +684,30 @@ mod tests {
         assert_eq!(config.repository_name("users-service"), "users-service");
     }
 
+    #[test]
+    fn test_mirror_inherits_primary_settings() {
+        let config = RegistryConfig::new("https://primary.example.com")
+            .with_namespace("policies")
+            .with_auth(RegistryAuth::bearer("token"))
+            .with_mirror("https://mirror-a.example.com")
+            .with_mirror_config(
+                MirrorConfig::new("https://mirror-b.example.com")
+                    .with_namespace("mirrored")
+                    .with_auth(RegistryAuth::None),
+            );
+        assert_eq!(config.mirrors.len(), 2);
+
+        let inherited = config.for_mirror(&config.mirrors[0]);
+        assert_eq!(inherited.url, "https://mirror-a.example.com");
+        assert_eq!(inherited.namespace, "policies");
+        assert!(matches!(inherited.auth, RegistryAuth::Bearer { .. }));
+        assert!(inherited.mirrors.is_empty());
+
+        let overridden = config.for_mirror(&config.mirrors[1]);
+        assert_eq!(overridden.namespace, "mirrored");
+        assert!(matches!(overridden.auth, RegistryAuth::None));
+    }
+
     #[test]
     fn test_basic_auth() {
         let auth = RegistryAuth::basic("user", "pass");
@@ -310,6 +727,21 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_aws_ecr_auth() {
+        let auth = RegistryAuth::aws_ecr("us-east-1");
+        assert!(matches!(
+            auth,
+            RegistryAuth::AwsEcr { region, registry_id: None } if region == "us-east-1"
+        ));
+
+        let auth = RegistryAuth::aws_ecr_registry("eu-west-1", "123456789012");
+        assert!(matches!(
+            auth,
+            RegistryAuth::AwsEcr { registry_id: Some(id), .. } if id == "123456789012"
+        ));
+    }
+
     #[test]
     fn test_tls_config() {
         let tls = TlsConfig::new()
@@ -321,4 +753,34 @@ mod tests {
         assert_eq!(tls.client_key, Some(PathBuf::from("/path/to/client.key")));
         assert!(!tls.insecure_skip_verify);
     }
+
+    #[test]
+    fn test_s3_config_from_url() {
+        let config = S3Config::from_url("s3://policies").unwrap();
+        assert_eq!(config.bucket, "policies");
+        assert_eq!(config.prefix, "");
+        assert_eq!(config.endpoint, None);
+
+        let config = S3Config::from_url("gs://policies/prod/").unwrap();
+        assert_eq!(config.prefix, "prod");
+        assert_eq!(config.endpoint.as_deref(), Some(GCS_ENDPOINT));
+
+        for url in ["policies/prod", "s3:///prod", "https://policies/prod"] {
+            assert!(
+                matches!(
+                    S3Config::from_url(url),
+                    Err(RegistryError::InvalidUrl { .. })
+                ),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_s3_credentials_debug_hides_secret() {
+        let credentials = S3Credentials::access_key("AKIDEXAMPLE", "secret-key");
+        let debug = format!("{credentials:?}");
+        assert!(debug.contains("AKIDEXAMPLE"));
+        assert!(!debug.contains("secret-key"));
+    }
 }
@@ -0,0 +1,307 @@
+//! AWS ECR authentication.
+//!
+//! ECR does not accept IAM credentials directly. Clients exchange them for a
+//! registry password via the `GetAuthorizationToken` API; the returned token
+//! is base64-encoded `AWS:<password>` and is valid for 12 hours. This module
+//! performs that exchange using the standard AWS credential chain
+//! (environment, shared config/profile, web identity, instance profile) and
+//! caches the result until shortly before it expires.
+
+use std::fmt;
+
+use aws_sdk_ecr::config::Region;
+use aws_sdk_ecr::error::DisplayErrorContext;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::Mutex;
+
+use crate::error::RegistryError;
+
+/// Lifetime assumed when ECR does not report an expiry.
+const DEFAULT_TOKEN_LIFETIME: Duration = Duration::hours(12);
+
+/// Credentials are refreshed this long before ECR reports they expire.
+const REFRESH_MARGIN: Duration = Duration::minutes(5);
+
+/// Registry credentials decoded from an ECR authorization token.
+#[derive(Clone)]
+struct EcrCredentials {
+    username: String,
+    password: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl EcrCredentials {
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        now + REFRESH_MARGIN < self.expires_at
+    }
+}
+
+/// Exchanges AWS credentials for ECR registry credentials and caches them.
+pub struct EcrTokenProvider {
+    region: String,
+    registry_id: Option<String>,
+    endpoint_url: Option<String>,
+    cached: Mutex<Option<EcrCredentials>>,
+}
+
+impl fmt::Debug for EcrTokenProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcrTokenProvider")
+            .field("region", &self.region)
+            .field("registry_id", &self.registry_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EcrTokenProvider {
+    /// Creates a provider for the given AWS region and, optionally, the
+    /// account ID of the registry to authenticate against.
+    pub fn new(region: impl Into<String>, registry_id: Option<String>) -> Self {
+        Self {
+            region: region.into(),
+            registry_id,
+            endpoint_url: None,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Sends `GetAuthorizationToken` to `url` instead of the regional ECR
+    /// endpoint. Deployments override the endpoint through the standard
+    /// `AWS_ENDPOINT_URL_ECR` variable instead.
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_endpoint_url(mut self, url: impl Into<String>) -> Self {
+        self.endpoint_url = Some(url.into());
+        self
+    }
+
+    /// Returns `(username, password)` for registry basic authentication,
+    /// fetching a new authorization token if the cached one is missing or
+    /// about to expire.
+    pub async fn credentials(&self) -> Result<(String, String), RegistryError> {
+        // Held while fetching, so concurrent callers share one token request
+        let mut cached = self.cached.lock().await;
+
+        if let Some(creds) = cached.as_ref().filter(|c| c.is_fresh(Utc::now())) {
+            return Ok((creds.username.clone(), creds.password.clone()));
+        }
+
+        tracing::debug!(region = %self.region, "Requesting ECR authorization token");
+        let creds = self.fetch().await?;
+        let result = (creds.username.clone(), creds.password.clone());
+        *cached = Some(creds);
+        drop(cached);
+
+        Ok(result)
+    }
+
+    /// Discards the cached credentials so the next call fetches new ones.
+    pub async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+
+    async fn fetch(&self) -> Result<EcrCredentials, RegistryError> {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(Region::new(self.region.clone()));
+        if let Some(ref url) = self.endpoint_url {
+            loader = loader.endpoint_url(url);
+        }
+        let client = aws_sdk_ecr::Client::new(&loader.load().await);
+
+        // AWS now treats `registryIds` as advisory (tokens are valid for every
+        // registry the principal can access), but it still selects the proxy
+        // endpoint reported for cross-account registries.
+        #[allow(deprecated)]
+        let request = client
+            .get_authorization_token()
+            .set_registry_ids(self.registry_id.clone().map(|id| vec![id]));
+
+        let output = request
+            .send()
+            .await
+            .map_err(|e| RegistryError::AuthenticationFailed {
+                message: format!(
+                    "ECR GetAuthorizationToken failed: {}",
+                    DisplayErrorContext(&e)
+                ),
+            })?;
+
+        let data = output.authorization_data().first().ok_or_else(|| {
+            RegistryError::AuthenticationFailed {
+                message: "ECR returned no authorization data".to_string(),
+            }
+        })?;
+
+        let token =
+            data.authorization_token()
+                .ok_or_else(|| RegistryError::AuthenticationFailed {
+                    message: "ECR returned no authorization token".to_string(),
+                })?;
+
+        let (username, password) = decode_authorization_token(token)?;
+        let expires_at = data
+            .expires_at()
+            .and_then(|t| DateTime::from_timestamp(t.secs(), 0))
+            .unwrap_or_else(|| Utc::now() + DEFAULT_TOKEN_LIFETIME);
+
+        Ok(EcrCredentials {
+            username,
+            password,
+            expires_at,
+        })
+    }
+}
+
+/// Decodes an ECR authorization token (`base64("AWS:<password>")`).
+fn decode_authorization_token(token: &str) -> Result<(String, String), RegistryError> {
+    let invalid = |reason: &str| RegistryError::AuthenticationFailed {
+        message: format!("Invalid ECR authorization token: {reason}"),
+    };
+
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(token.trim())
+        .map_err(|_| invalid("not base64"))?;
+    let decoded = String::from_utf8(decoded).map_err(|_| invalid("not UTF-8"))?;
+
+    let (username, password) = decoded
+        .split_once(':')
+        .ok_or_else(|| invalid("missing ':' separator"))?;
+
+    Ok((username.to_string(), password.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex as StdMutex};
+
+    use super::*;
+    use crate::test_support::{spawn_mock_registry, MockRequest, MockResponse};
+
+    /// `X-Amz-Target` of the ECR `GetAuthorizationToken` operation.
+    const GET_AUTHORIZATION_TOKEN: &str =
+        "AmazonEC2ContainerRegistry_V20150921.GetAuthorizationToken";
+
+    /// Starts a mock ECR API that answers every `GetAuthorizationToken`
+    /// call with `response` and records the requests it receives.
+    async fn spawn_mock_ecr(response: MockResponse) -> (String, Arc<StdMutex<Vec<MockRequest>>>) {
+        // The SDK resolves credentials from the environment before signing.
+        std::env::set_var("AWS_ACCESS_KEY_ID", "AKIDEXAMPLE");
+        std::env::set_var(
+            "AWS_SECRET_ACCESS_KEY",
+            "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY",
+        );
+
+        let requests = Arc::new(StdMutex::new(Vec::new()));
+        let seen = Arc::clone(&requests);
+        let base = spawn_mock_registry(move |req| {
+            seen.lock().unwrap().push(req.clone());
+            if req.header("x-amz-target") == Some(GET_AUTHORIZATION_TOKEN) {
+                response.clone()
+            } else {
+                MockResponse::status(400)
+            }
+        })
+        .await;
+
+        (base, requests)
+    }
+
+    fn ecr_json(status: u16, body: &str) -> MockResponse {
+        MockResponse::status(status)
+            .header("Content-Type", "application/x-amz-json-1.1")
+            .body(body.as_bytes())
+    }
+
+    #[test]
+    fn test_decode_authorization_token() {
+        let token = base64::engine::general_purpose::STANDARD.encode("AWS:secret:with:colons");
+        let (username, password) = decode_authorization_token(&token).unwrap();
+
+        assert_eq!(username, "AWS");
+        assert_eq!(password, "secret:with:colons");
+    }
+
+    #[test]
+    fn test_decode_authorization_token_invalid() {
+        assert!(decode_authorization_token("not base64!").is_err());
+
+        let token = base64::engine::general_purpose::STANDARD.encode("no-separator");
+        assert!(decode_authorization_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_credentials_freshness() {
+        let now = Utc::now();
+        let creds = EcrCredentials {
+            username: "AWS".to_string(),
+            password: "secret".to_string(),
+            expires_at: now + Duration::hours(12),
+        };
+
+        assert!(creds.is_fresh(now));
+        assert!(!creds.is_fresh(now + Duration::hours(12) - Duration::minutes(1)));
+    }
+
+    #[tokio::test]
+    async fn test_cached_credentials_are_reused() {
+        let provider = EcrTokenProvider::new("us-east-1", None);
+        *provider.cached.lock().await = Some(EcrCredentials {
+            username: "AWS".to_string(),
+            password: "cached".to_string(),
+            expires_at: Utc::now() + Duration::hours(1),
+        });
+
+        let (username, password) = provider.credentials().await.unwrap();
+        assert_eq!(username, "AWS");
+        assert_eq!(password, "cached");
+    }
+
+    #[tokio::test]
+    async fn test_credentials_from_mock_ecr() {
+        let token = base64::engine::general_purpose::STANDARD.encode("AWS:ecr-password");
+        let expires_at = (Utc::now() + Duration::hours(12)).timestamp();
+        let body = serde_json::json!({
+            "authorizationData": [{
+                "authorizationToken": token,
+                "expiresAt": expires_at,
+                "proxyEndpoint": "https://123456789012.dkr.ecr.us-east-1.amazonaws.com",
+            }]
+        });
+        let (base, requests) = spawn_mock_ecr(ecr_json(200, &body.to_string())).await;
+
+        let provider = EcrTokenProvider::new("us-east-1", Some("123456789012".to_string()))
+            .with_endpoint_url(base);
+        let (username, password) = provider.credentials().await.unwrap();
+        assert_eq!(username, "AWS");
+        assert_eq!(password, "ecr-password");
+
+        // The token is cached until shortly before it expires
+        provider.credentials().await.unwrap();
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert!(String::from_utf8_lossy(&requests[0].body).contains("123456789012"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_ecr_error_fails_authentication() {
+        let body = r#"{"__type":"AccessDeniedException","message":"User is not authorized"}"#;
+        let (base, _) = spawn_mock_ecr(ecr_json(400, body)).await;
+
+        let provider = EcrTokenProvider::new("us-east-1", None).with_endpoint_url(base);
+        let err = provider.credentials().await.unwrap_err();
+
+        assert!(matches!(err, RegistryError::AuthenticationFailed { .. }));
+        assert!(err.to_string().contains("User is not authorized"));
+    }
+
+    #[test]
+    fn test_debug_hides_credentials() {
+        let provider = EcrTokenProvider::new("eu-west-1", None);
+        let debug = format!("{provider:?}");
+
+        assert!(debug.contains("eu-west-1"));
+        assert!(!debug.contains("password"));
+    }
+}
@@ -1,6 +1,35 @@
 //! Error types for registry operations.
+//!
+//! # Error codes
+//!
+//! Every [`RegistryError`] has a stable code, returned by
+//! [`RegistryError::code`]. Codes are never reused or renumbered:
+//!
+//! | Code | Variant |
+//! |------|---------|
+//! | `EUN-R001` | [`ConnectionFailed`](RegistryError::ConnectionFailed) |
+//! | `EUN-R002` | [`AuthenticationFailed`](RegistryError::AuthenticationFailed) |
+//! | `EUN-R003` | [`NotFound`](RegistryError::NotFound) |
+//! | `EUN-R004` | [`InvalidBundle`](RegistryError::InvalidBundle) |
+//! | `EUN-R005` | [`ChecksumMismatch`](RegistryError::ChecksumMismatch) |
+//! | `EUN-R006` | [`NoMatchingVersion`](RegistryError::NoMatchingVersion) |
+//! | `EUN-R007` | [`VersionResolutionFailed`](RegistryError::VersionResolutionFailed) |
+//! | `EUN-R008` | [`CacheError`](RegistryError::CacheError) |
+//! | `EUN-R009` | [`IoError`](RegistryError::IoError) |
+//! | `EUN-R010` | [`HttpError`](RegistryError::HttpError) |
+//! | `EUN-R011` | [`JsonError`](RegistryError::JsonError) |
+//! | `EUN-R012` | [`InvalidUrl`](RegistryError::InvalidUrl) |
+//! | `EUN-R013` | [`InvalidReference`](RegistryError::InvalidReference) |
+//! | `EUN-R014` | [`UploadFailed`](RegistryError::UploadFailed) |
+//! | `EUN-R015` | [`ManifestPushFailed`](RegistryError::ManifestPushFailed) |
+//! | `EUN-R016` | [`Offline`](RegistryError::Offline) |
+//! | `EUN-R017` | [`SignatureVerificationFailed`](RegistryError::SignatureVerificationFailed) |
+//! | `EUN-R018` | [`InvalidTrustedKey`](RegistryError::InvalidTrustedKey) |
+//! | `EUN-R019` | [`UnsupportedApi`](RegistryError::UnsupportedApi) |
+//! | `EUN-R020` | [`Timeout`](RegistryError::Timeout) |
 
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur during registry operations.
@@ -52,6 +81,20 @@ pub enum RegistryError {
         actual: String,
     },
 
+    /// No tag satisfies a version constraint.
+    #[error(
+        "No version of {service} matches '{constraint}' (available: {})",
+        list_versions(available)
+    )]
+    NoMatchingVersion {
+        /// Service name.
+        service: String,
+        /// Version constraint.
+        constraint: String,
+        /// Version tags of the service, lowest first.
+        available: Vec<String>,
+    },
+
     /// Version resolution failed.
     #[error("Failed to resolve version '{query}' for {service}: {message}")]
     VersionResolutionFailed {
@@ -129,12 +172,83 @@ pub enum RegistryError {
         message: String,
     },
 
+    /// The client is offline and the bundle is not in the local cache.
+    #[error("Bundle {service}:{version} is not cached and the registry client is offline")]
+    Offline {
+        /// Service name.
+        service: String,
+        /// Version.
+        version: String,
+    },
+
+    /// A fetched bundle's signatures did not verify against the trusted keys.
+    #[error("Signature verification failed for {service}:{version}: {reason}")]
+    SignatureVerificationFailed {
+        /// Service name.
+        service: String,
+        /// Version.
+        version: String,
+        /// Why verification failed.
+        reason: String,
+    },
+
+    /// A configured trusted key could not be parsed.
+    #[error("Invalid trusted key '{key_id}': {message}")]
+    InvalidTrustedKey {
+        /// Key ID.
+        key_id: String,
+        /// Error message.
+        message: String,
+    },
+
     /// Registry API not supported.
     #[error("Registry does not support required API: {feature}")]
     UnsupportedApi {
         /// Feature name.
         feature: String,
     },
+
+    /// Fetching a bundle took longer than allowed.
+    #[error("Fetching {service}:{version} timed out after {timeout:?}")]
+    Timeout {
+        /// Service name.
+        service: String,
+        /// Version reference.
+        version: String,
+        /// Time allowed for the fetch.
+        timeout: Duration,
+    },
+}
+
+impl RegistryError {
+    /// Returns the stable code of this error, e.g. `EUN-R003`.
+    ///
+    /// See the [module documentation](self#error-codes) for the codes.
+    #[must_use]
+    pub const fn code(&self) -> &'static str {
+        match self {
+            Self::ConnectionFailed { .. } => "EUN-R001",
+            Self::AuthenticationFailed { .. } => "EUN-R002",
+            Self::NotFound { .. } => "EUN-R003",
+            Self::InvalidBundle { .. } => "EUN-R004",
+            Self::ChecksumMismatch { .. } => "EUN-R005",
+            Self::NoMatchingVersion { .. } => "EUN-R006",
+            Self::VersionResolutionFailed { .. } => "EUN-R007",
+            Self::CacheError { .. } => "EUN-R008",
+            Self::IoError { .. } => "EUN-R009",
+            Self::HttpError { .. } => "EUN-R010",
+            Self::JsonError { .. } => "EUN-R011",
+            Self::InvalidUrl { .. } => "EUN-R012",
+            Self::InvalidReference { .. } => "EUN-R013",
+            Self::UploadFailed { .. } => "EUN-R014",
+            Self::ManifestPushFailed { .. } => "EUN-R015",
+            Self::Offline { .. } => "EUN-R016",
+            Self::SignatureVerificationFailed { .. } => "EUN-R017",
+            Self::InvalidTrustedKey { .. } => "EUN-R018",
+            Self::UnsupportedApi { .. } => "EUN-R019",
+            Self::Timeout { .. } => "EUN-R020",
+        }
+    }
 }
 
 impl From<reqwest::Error> for RegistryError {
@@ -176,10 +290,164 @@ impl From<std::io::Error> for RegistryError {
     }
 }
 
+/// Formats the versions listed by [`RegistryError::NoMatchingVersion`],
+/// keeping the highest ones.
+fn list_versions(versions: &[String]) -> String {
+    const SHOWN: usize = 10;
+    match versions.len() {
+        0 => "none".to_string(),
+        n if n <= SHOWN => versions.join(", "),
+        n => format!("{} older, {}", n - SHOWN, versions[n - SHOWN..].join(", ")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_error_display_no_matching_version() {
+        let err = |available: Vec<String>| RegistryError::NoMatchingVersion {
+            service: "users-service".to_string(),
+            constraint: "^2".to_string(),
+            available,
+        };
+        assert_eq!(
+            err(vec![]).to_string(),
+            "No version of users-service matches '^2' (available: none)"
+        );
+
+        let versions = (0..12).map(|minor| format!("v1.{minor}.0")).collect();
+        let message = err(versions).to_string();
+        assert!(
+            message.contains("(available: 2 older, v1.2.0, v1.3.0,"),
+            "{message}"
+        );
+        assert!(message.ends_with("v1.10.0, v1.11.0)"), "{message}");
+    }
+
+    /// Returns one error of every variant, with its expected code.
+    #[allow(clippy::too_many_lines)]
+    fn every_error() -> Vec<(RegistryError, &'static str)> {
+        let s = String::new;
+        let reqwest_error = reqwest::Client::new().get("not a url").build().unwrap_err();
+        let json_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        vec![
+            (
+                RegistryError::ConnectionFailed {
+                    url: s(),
+                    source: reqwest_error,
+                },
+                "EUN-R001",
+            ),
+            (
+                RegistryError::AuthenticationFailed { message: s() },
+                "EUN-R002",
+            ),
+            (
+                RegistryError::NotFound {
+                    service: s(),
+                    version: s(),
+                },
+                "EUN-R003",
+            ),
+            (RegistryError::InvalidBundle { message: s() }, "EUN-R004"),
+            (
+                RegistryError::ChecksumMismatch {
+                    service: s(),
+                    version: s(),
+                    expected: s(),
+                    actual: s(),
+                },
+                "EUN-R005",
+            ),
+            (
+                RegistryError::NoMatchingVersion {
+                    service: s(),
+                    constraint: s(),
+                    available: vec![],
+                },
+                "EUN-R006",
+            ),
+            (
+                RegistryError::VersionResolutionFailed {
+                    service: s(),
+                    query: s(),
+                    message: s(),
+                },
+                "EUN-R007",
+            ),
+            (RegistryError::CacheError { message: s() }, "EUN-R008"),
+            (
+                RegistryError::from(std::io::Error::other("disk full")),
+                "EUN-R009",
+            ),
+            (
+                RegistryError::HttpError {
+                    status: 500,
+                    message: s(),
+                },
+                "EUN-R010",
+            ),
+            (RegistryError::from(json_error), "EUN-R011"),
+            (RegistryError::InvalidUrl { url: s() }, "EUN-R012"),
+            (
+                RegistryError::InvalidReference { reference: s() },
+                "EUN-R013",
+            ),
+            (RegistryError::UploadFailed { message: s() }, "EUN-R014"),
+            (
+                RegistryError::ManifestPushFailed {
+                    service: s(),
+                    version: s(),
+                    message: s(),
+                },
+                "EUN-R015",
+            ),
+            (
+                RegistryError::Offline {
+                    service: s(),
+                    version: s(),
+                },
+                "EUN-R016",
+            ),
+            (
+                RegistryError::SignatureVerificationFailed {
+                    service: s(),
+                    version: s(),
+                    reason: s(),
+                },
+                "EUN-R017",
+            ),
+            (
+                RegistryError::InvalidTrustedKey {
+                    key_id: s(),
+                    message: s(),
+                },
+                "EUN-R018",
+            ),
+            (RegistryError::UnsupportedApi { feature: s() }, "EUN-R019"),
+            (
+                RegistryError::Timeout {
+                    service: s(),
+                    version: s(),
+                    timeout: Duration::from_secs(1),
+                },
+                "EUN-R020",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_error_codes_are_unique_and_stable() {
+        let errors = every_error();
+        let mut codes = std::collections::HashSet::new();
+        for (err, code) in &errors {
+            assert_eq!(err.code(), *code, "{err:?}");
+            assert!(codes.insert(err.code()), "duplicate code {code}");
+        }
+    }
+
     #[test]
     fn test_error_display_not_found() {
         let err = RegistryError::NotFound {
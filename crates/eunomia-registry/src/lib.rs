@@ -11,6 +11,16 @@
 //! - **Multiple Auth Methods**: Basic, Bearer token, AWS ECR, GCP Artifact Registry
 //! - **Local Caching**: File-based cache with LRU eviction
 //! - **Version Resolution**: Semantic version resolution and tag management
+//! - **Promotion**: Copy bundles between repositories (by blob mount) or
+//!   registries without rebuilding them
+//!
+//! ## Cargo Features
+//!
+//! - `aws`: enables [`RegistryAuth::AwsEcr`], which exchanges AWS credentials
+//!   for ECR registry credentials via the AWS SDK
+//! - `s3`: enables `S3Store`, a [`BundleStore`] that keeps bundles in
+//!   S3-compatible object storage (AWS S3, Google Cloud Storage, `MinIO`)
+//!   for environments without a registry
 //!
 //! ## Quick Start
 //!
@@ -56,16 +66,29 @@
 #![warn(clippy::pedantic)]
 #![warn(clippy::nursery)]
 
+mod auth;
 mod cache;
 mod client;
 mod config;
+#[cfg(feature = "aws")]
+mod ecr;
 mod error;
 mod oci;
+#[cfg(feature = "s3")]
+mod s3;
+mod store;
+#[cfg(test)]
+mod test_support;
 mod version;
 
-pub use cache::{BundleCache, CacheConfig};
-pub use client::RegistryClient;
-pub use config::{RegistryAuth, RegistryConfig, TlsConfig};
+pub use cache::{BundleCache, CacheConfig, CacheStats};
+pub use client::{FetchManyResult, PreparedPublish, RegistryClient};
+pub use config::{
+    EncryptionKey, MirrorConfig, RegistryAuth, RegistryConfig, S3Config, S3Credentials, TlsConfig,
+};
 pub use error::RegistryError;
 pub use oci::{Descriptor, Manifest, MediaType};
+#[cfg(feature = "s3")]
+pub use s3::S3Store;
+pub use store::BundleStore;
 pub use version::{VersionQuery, VersionResolver};
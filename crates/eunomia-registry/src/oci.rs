@@ -19,6 +19,10 @@ impl MediaType {
     /// Eunomia policy bundle media type.
     pub const EUNOMIA_BUNDLE: &'static str = "application/vnd.eunomia.policy.bundle.v1+tar.gz";
 
+    /// Eunomia policy bundle encrypted with AES-256-GCM.
+    pub const EUNOMIA_ENCRYPTED_BUNDLE: &'static str =
+        "application/vnd.eunomia.policy.bundle.v1+tar.gz+encrypted";
+
     /// Eunomia policy manifest media type.
     pub const EUNOMIA_MANIFEST: &'static str = "application/vnd.eunomia.policy.manifest.v1+json";
 
@@ -43,6 +47,12 @@ impl MediaType {
         Self::new(Self::EUNOMIA_BUNDLE)
     }
 
+    /// Creates the encrypted Eunomia bundle media type.
+    #[must_use]
+    pub fn eunomia_encrypted_bundle() -> Self {
+        Self::new(Self::EUNOMIA_ENCRYPTED_BUNDLE)
+    }
+
     /// Creates the Eunomia manifest media type.
     #[must_use]
     pub fn eunomia_manifest() -> Self {
@@ -111,6 +121,11 @@ pub struct Descriptor {
     /// Optional annotations (key-value metadata).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub annotations: Option<std::collections::HashMap<String, String>>,
+
+    /// Artifact type of the referenced manifest, as reported by the
+    /// referrers API (OCI 1.1+).
+    #[serde(rename = "artifactType", skip_serializing_if = "Option::is_none")]
+    pub referrer_type: Option<String>,
 }
 
 impl Descriptor {
@@ -135,6 +150,7 @@ impl Descriptor {
             size,
             urls: None,
             annotations: None,
+            referrer_type: None,
         }
     }
 
@@ -252,12 +268,15 @@ impl Manifest {
         self
     }
 
-    /// Returns the bundle layer descriptor, if present.
+    /// Returns the bundle layer descriptor, plain or encrypted, if present.
     #[must_use]
     pub fn bundle_layer(&self) -> Option<&Descriptor> {
-        self.layers
-            .iter()
-            .find(|d| d.media_type.as_str() == MediaType::EUNOMIA_BUNDLE)
+        self.layers.iter().find(|d| {
+            matches!(
+                d.media_type.as_str(),
+                MediaType::EUNOMIA_BUNDLE | MediaType::EUNOMIA_ENCRYPTED_BUNDLE
+            )
+        })
     }
 
     /// Returns the signature layer descriptor, if present.
@@ -269,6 +288,23 @@ impl Manifest {
     }
 }
 
+/// OCI image index, as returned by the `/v2/<name>/referrers/<digest>`
+/// endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageIndex {
+    /// Schema version (always 2).
+    pub schema_version: u32,
+
+    /// Media type of this index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<MediaType>,
+
+    /// Descriptors of the indexed manifests.
+    #[serde(default)]
+    pub manifests: Vec<Descriptor>,
+}
+
 /// Response from the `/v2/<name>/tags/list` endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TagList {
@@ -279,6 +315,14 @@ pub struct TagList {
     pub tags: Vec<String>,
 }
 
+/// Response from the `/v2/_catalog` endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Catalog {
+    /// Repository names.
+    #[serde(default)]
+    pub repositories: Vec<String>,
+}
+
 /// Error response from registry API.
 #[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -358,6 +402,18 @@ mod tests {
         assert!(manifest.signature_layer().is_some());
     }
 
+    #[test]
+    fn test_manifest_encrypted_bundle_layer() {
+        let bundle = Descriptor::new(MediaType::eunomia_encrypted_bundle(), "sha256:enc", 1000);
+
+        let manifest = Manifest::for_bundle(bundle, None);
+
+        assert_eq!(
+            manifest.bundle_layer().map(|d| d.media_type.as_str()),
+            Some(MediaType::EUNOMIA_ENCRYPTED_BUNDLE)
+        );
+    }
+
     #[test]
     fn test_manifest_serialization() {
         let layer = Descriptor::new(MediaType::eunomia_bundle(), "sha256:abc", 100);
@@ -370,6 +426,30 @@ mod tests {
         assert!(json.contains("layers"));
     }
 
+    #[test]
+    fn test_image_index_deserialization() {
+        let json = r#"{
+            "schemaVersion": 2,
+            "mediaType": "application/vnd.oci.image.index.v1+json",
+            "manifests": [{
+                "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                "digest": "sha256:sbom",
+                "size": 512,
+                "artifactType": "application/vnd.cyclonedx+json"
+            }]
+        }"#;
+
+        let index: ImageIndex = serde_json::from_str(json).unwrap();
+        assert_eq!(index.manifests.len(), 1);
+        assert_eq!(
+            index.manifests[0].referrer_type.as_deref(),
+            Some("application/vnd.cyclonedx+json")
+        );
+
+        let descriptor = serde_json::to_value(&index.manifests[0]).unwrap();
+        assert_eq!(descriptor["artifactType"], "application/vnd.cyclonedx+json");
+    }
+
     #[test]
     fn test_tag_list_deserialization() {
         let json = r#"{
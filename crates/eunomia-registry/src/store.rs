@@ -0,0 +1,90 @@
+//! Storage backends for published bundles.
+//!
+//! [`BundleStore`] is what publishing and fetching need from wherever
+//! bundles live, keyed by service and version. [`RegistryClient`] stores
+//! them in an OCI registry; with the `s3` feature,
+//! [`S3Store`](crate::S3Store) stores them in S3-compatible object storage
+//! for environments without a registry.
+
+use async_trait::async_trait;
+use eunomia_core::SignedBundle;
+
+use crate::client::RegistryClient;
+use crate::error::RegistryError;
+
+/// A place bundles are published to and fetched from.
+#[async_trait]
+pub trait BundleStore: Send + Sync {
+    /// Stores `bundle` as `service:version`, replacing any bundle already
+    /// stored under that version, and returns its digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle cannot be serialized or uploaded.
+    async fn put(
+        &self,
+        service: &str,
+        version: &str,
+        bundle: &SignedBundle,
+    ) -> Result<String, RegistryError>;
+
+    /// Retrieves the bundle stored as `service:version`; `version` may also
+    /// be the digest returned by [`put`](Self::put).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::NotFound`] if there is no such bundle, and
+    /// [`RegistryError::ChecksumMismatch`] if the stored bundle does not
+    /// match the digest recorded when it was stored.
+    async fn get(&self, service: &str, version: &str) -> Result<SignedBundle, RegistryError>;
+
+    /// Returns true if a bundle is stored as `service:version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be reached.
+    async fn exists(&self, service: &str, version: &str) -> Result<bool, RegistryError>;
+
+    /// Lists the versions stored for `service`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be reached.
+    async fn list_versions(&self, service: &str) -> Result<Vec<String>, RegistryError>;
+
+    /// Deletes the bundle stored as `service:version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegistryError::NotFound`] if there is no such bundle, or an
+    /// error if it cannot be deleted.
+    async fn delete(&self, service: &str, version: &str) -> Result<(), RegistryError>;
+}
+
+#[async_trait]
+impl BundleStore for RegistryClient {
+    async fn put(
+        &self,
+        service: &str,
+        version: &str,
+        bundle: &SignedBundle,
+    ) -> Result<String, RegistryError> {
+        self.publish_signed(service, version, bundle).await
+    }
+
+    async fn get(&self, service: &str, version: &str) -> Result<SignedBundle, RegistryError> {
+        self.fetch_signed(service, version).await
+    }
+
+    async fn exists(&self, service: &str, version: &str) -> Result<bool, RegistryError> {
+        Self::exists(self, service, version).await
+    }
+
+    async fn list_versions(&self, service: &str) -> Result<Vec<String>, RegistryError> {
+        self.list_tags(service).await
+    }
+
+    async fn delete(&self, service: &str, version: &str) -> Result<(), RegistryError> {
+        Self::delete(self, service, version).await
+    }
+}
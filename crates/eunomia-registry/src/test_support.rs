@@ -0,0 +1,182 @@
+//! Minimal in-process HTTP server for exercising the registry client in tests.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request received by the mock registry.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// HTTP method (e.g. `GET`).
+    pub method: String,
+    /// Request target including the query string.
+    pub path: String,
+    /// Request headers with lowercase names.
+    pub headers: Vec<(String, String)>,
+    /// Request body.
+    pub body: Vec<u8>,
+}
+
+impl MockRequest {
+    /// Returns the first header named `name` (case-insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the base URL the client used to reach the server.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.header("host").unwrap_or_default())
+    }
+}
+
+/// A response returned by the mock registry.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    delay: Duration,
+}
+
+impl MockResponse {
+    /// Creates an empty response with the given status.
+    pub const fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Vec::new(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Creates a `200 OK` JSON response.
+    pub fn json(body: &str) -> Self {
+        Self::status(200)
+            .header("Content-Type", "application/json")
+            .body(body.as_bytes())
+    }
+
+    /// Adds a response header.
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Sets the response body.
+    pub fn body(mut self, body: &[u8]) -> Self {
+        self.body = body.to_vec();
+        self
+    }
+
+    /// Delays sending the response, simulating a slow registry.
+    pub const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Starts a mock registry that answers every request with `handler`.
+///
+/// Returns the base URL (`http://127.0.0.1:<port>`). The server runs until
+/// the test's runtime shuts down.
+pub async fn spawn_mock_registry<H>(handler: H) -> String
+where
+    H: Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let handler = Arc::new(handler);
+
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let handler = Arc::clone(&handler);
+            tokio::spawn(async move {
+                let _ = serve_connection(socket, handler.as_ref()).await;
+            });
+        }
+    });
+
+    base
+}
+
+async fn serve_connection<H>(mut socket: TcpStream, handler: &H) -> std::io::Result<()>
+where
+    H: Fn(&MockRequest) -> MockResponse + Sync,
+{
+    let Some(request) = read_request(&mut socket).await? else {
+        return Ok(());
+    };
+    let response = handler(&request);
+    tokio::time::sleep(response.delay).await;
+
+    let mut head = format!("HTTP/1.1 {} Mock\r\n", response.status);
+    for (name, value) in &response.headers {
+        let _ = write!(head, "{name}: {value}\r\n");
+    }
+    let _ = write!(
+        head,
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    );
+
+    socket.write_all(head.as_bytes()).await?;
+    if request.method != "HEAD" {
+        socket.write_all(&response.body).await?;
+    }
+    socket.shutdown().await
+}
+
+async fn read_request(socket: &mut TcpStream) -> std::io::Result<Option<MockRequest>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        let n = socket.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(n, v)| (n.trim().to_ascii_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .iter()
+        .find(|(n, _)| n == "content-length")
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    let mut body = buf[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let n = socket.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+
+    Ok(Some(MockRequest {
+        method,
+        path,
+        headers,
+        body,
+    }))
+}
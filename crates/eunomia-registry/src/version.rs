@@ -5,10 +5,22 @@
 //! - `v1.2.3` → Exact version match
 //! - `v1.2` → Latest patch in minor version
 //! - `v1` → Latest minor/patch in major version
+//! - `^1.2.0`, `~1.2.0`, `>=1.0.0 <2.0.0` → Highest version satisfying the
+//!   constraint
 //! - `sha256:abc...` → Exact digest match
+//! - `stable`, `beta` → Channel tag, an alias of some version tag
+//!
+//! Prerelease versions (`v2.0.0-rc.1`) are skipped by every query except an
+//! exact match, unless the resolver is created with
+//! [`VersionResolver::with_prereleases`].
+
+use semver::{Version, VersionReq};
 
 use crate::error::RegistryError;
 
+/// Characters that can start a version constraint.
+const CONSTRAINT_OPERATORS: &[char] = &['^', '~', '<', '>', '=', '*'];
+
 /// A version query that can be resolved to a specific version.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VersionQuery {
@@ -26,6 +38,13 @@ pub enum VersionQuery {
 
     /// Content digest (e.g., "sha256:abc123...").
     Digest(String),
+
+    /// Semantic version constraint (e.g., "^1.2.0" or ">=1.0.0 <2.0.0").
+    Constraint(VersionReq),
+
+    /// Channel tag (e.g., "stable"), pointing at the same manifest as a
+    /// version tag.
+    Channel(String),
 }
 
 impl VersionQuery {
@@ -44,6 +63,12 @@ impl VersionQuery {
     ///
     /// let query = VersionQuery::parse("v1").unwrap();
     /// assert!(matches!(query, VersionQuery::Major(1)));
+    ///
+    /// let query = VersionQuery::parse(">=1.0.0 <2.0.0").unwrap();
+    /// assert!(matches!(query, VersionQuery::Constraint(_)));
+    ///
+    /// let query = VersionQuery::parse("stable").unwrap();
+    /// assert!(matches!(query, VersionQuery::Channel(_)));
     /// ```
     ///
     /// # Errors
@@ -51,6 +76,9 @@ impl VersionQuery {
     /// Returns an error if the version string cannot be parsed.
     pub fn parse(input: &str) -> Result<Self, RegistryError> {
         let input = input.trim();
+        let invalid = || RegistryError::InvalidReference {
+            reference: input.to_string(),
+        };
 
         if input.eq_ignore_ascii_case("latest") {
             return Ok(Self::Latest);
@@ -61,41 +89,26 @@ impl VersionQuery {
             return Ok(Self::Digest(input.to_string()));
         }
 
+        if input.starts_with(CONSTRAINT_OPERATORS) || input.contains([' ', ',']) {
+            return VersionReq::parse(&normalize_constraint(input))
+                .map(Self::Constraint)
+                .map_err(|_| invalid());
+        }
+
         // Remove optional 'v' prefix
         let version_str = input.strip_prefix('v').unwrap_or(input);
 
-        // Parse version components
-        let parts: Vec<&str> = version_str.split('.').collect();
-
-        match parts.len() {
-            1 => {
-                let major =
-                    parts[0]
-                        .parse::<u64>()
-                        .map_err(|_| RegistryError::InvalidReference {
-                            reference: input.to_string(),
-                        })?;
-                Ok(Self::Major(major))
-            }
-            2 => {
-                let major =
-                    parts[0]
-                        .parse::<u64>()
-                        .map_err(|_| RegistryError::InvalidReference {
-                            reference: input.to_string(),
-                        })?;
-                let minor =
-                    parts[1]
-                        .parse::<u64>()
-                        .map_err(|_| RegistryError::InvalidReference {
-                            reference: input.to_string(),
-                        })?;
-                Ok(Self::Minor(major, minor))
-            }
-            3 => Ok(Self::Exact(format!("v{version_str}"))),
-            _ => Err(RegistryError::InvalidReference {
-                reference: input.to_string(),
-            }),
+        if Version::parse(version_str).is_ok() {
+            return Ok(Self::Exact(format!("v{version_str}")));
+        }
+
+        // Parse partial version components
+        let parts: Option<Vec<u64>> = version_str.split('.').map(|p| p.parse().ok()).collect();
+        match parts.as_deref() {
+            Some(&[major]) => Ok(Self::Major(major)),
+            Some(&[major, minor]) => Ok(Self::Minor(major, minor)),
+            _ if is_channel_tag(input) => Ok(Self::Channel(input.to_string())),
+            _ => Err(invalid()),
         }
     }
 
@@ -110,6 +123,12 @@ impl VersionQuery {
     pub const fn is_latest(&self) -> bool {
         matches!(self, Self::Latest)
     }
+
+    /// Returns true if this query names a channel tag.
+    #[must_use]
+    pub const fn is_channel(&self) -> bool {
+        matches!(self, Self::Channel(_))
+    }
 }
 
 impl std::fmt::Display for VersionQuery {
@@ -120,23 +139,86 @@ impl std::fmt::Display for VersionQuery {
             Self::Major(m) => write!(f, "v{m}"),
             Self::Minor(m, n) => write!(f, "v{m}.{n}"),
             Self::Digest(d) => write!(f, "{d}"),
+            Self::Constraint(req) => write!(f, "{req}"),
+            Self::Channel(tag) => write!(f, "{tag}"),
         }
     }
 }
 
+/// Returns true if `input` is a valid OCI tag that starts with a letter, and
+/// so cannot be mistaken for a version.
+fn is_channel_tag(input: &str) -> bool {
+    input.len() <= 128
+        && input.starts_with(|c: char| c.is_ascii_alphabetic())
+        && input
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
+/// Rewrites a constraint into the comma-separated form `semver` parses:
+/// comparators may be separated by spaces, operators may be followed by a
+/// space, and versions may carry a `v` prefix (`>= v1.0.0 <v2.0.0`).
+fn normalize_constraint(input: &str) -> String {
+    let mut comparators = Vec::new();
+    let mut pending = String::new();
+
+    for token in input
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+    {
+        pending.push_str(token);
+        if token.chars().all(|c| CONSTRAINT_OPERATORS.contains(&c)) && token != "*" {
+            continue;
+        }
+
+        let version_start = pending.find(|c| !CONSTRAINT_OPERATORS.contains(&c));
+        if let Some(i) = version_start.filter(|&i| pending[i..].starts_with('v')) {
+            pending.remove(i);
+        }
+        comparators.push(std::mem::take(&mut pending));
+    }
+
+    comparators.join(", ")
+}
+
 /// Resolves version queries to specific versions.
 #[derive(Debug, Clone)]
-pub struct VersionResolver;
+pub struct VersionResolver {
+    include_prereleases: bool,
+}
 
 impl VersionResolver {
-    /// Creates a new version resolver.
+    /// Creates a new version resolver that skips prereleases.
     #[must_use]
     pub const fn new() -> Self {
-        Self
+        Self {
+            include_prereleases: false,
+        }
+    }
+
+    /// Sets whether prerelease versions can satisfy `latest`, major, minor
+    /// and constraint queries.
+    ///
+    /// A prerelease satisfies a constraint if its release does, so with
+    /// prereleases included `^1.2` matches `v1.4.0-rc.1`.
+    #[must_use]
+    pub const fn with_prereleases(mut self, include: bool) -> Self {
+        self.include_prereleases = include;
+        self
+    }
+
+    /// Returns true if prerelease versions are considered.
+    #[must_use]
+    pub const fn includes_prereleases(&self) -> bool {
+        self.include_prereleases
     }
 
     /// Resolves a version query against a list of available tags.
     ///
+    /// Channel tags resolve to themselves if they exist; following them to
+    /// the version they point at needs the registry, see
+    /// [`RegistryClient::resolve_version`](crate::RegistryClient::resolve_version).
+    ///
     /// # Arguments
     ///
     /// * `query` - The version query to resolve.
@@ -152,14 +234,14 @@ impl VersionResolver {
     /// use eunomia_registry::{VersionQuery, VersionResolver};
     ///
     /// let resolver = VersionResolver::new();
-    /// let tags = vec!["v1.0.0", "v1.1.0", "v1.2.0", "v2.0.0"];
+    /// let tags = vec!["v1.0.0", "v1.1.0", "v1.2.0", "v2.0.0", "v2.1.0-rc.1"];
     ///
     /// // Exact match
     /// let query = VersionQuery::Exact("v1.1.0".to_string());
     /// let resolved = resolver.resolve(&query, &tags, "test").unwrap();
     /// assert_eq!(resolved, "v1.1.0");
     ///
-    /// // Latest
+    /// // Latest, skipping the prerelease
     /// let query = VersionQuery::Latest;
     /// let resolved = resolver.resolve(&query, &tags, "test").unwrap();
     /// assert_eq!(resolved, "v2.0.0");
@@ -172,7 +254,9 @@ impl VersionResolver {
     ///
     /// # Errors
     ///
-    /// Returns an error if the version query cannot be resolved.
+    /// Returns [`RegistryError::NotFound`] if an exact version or channel
+    /// tag does not exist, and [`RegistryError::NoMatchingVersion`], listing
+    /// the available versions, if no version satisfies the query.
     pub fn resolve(
         &self,
         query: &VersionQuery,
@@ -181,130 +265,79 @@ impl VersionResolver {
     ) -> Result<String, RegistryError> {
         match query {
             VersionQuery::Digest(d) => Ok(d.clone()),
-            VersionQuery::Exact(v) => {
-                if available_tags.iter().any(|t| t.as_ref() == v) {
-                    Ok(v.clone())
+            VersionQuery::Exact(tag) | VersionQuery::Channel(tag) => {
+                if available_tags.iter().any(|t| t.as_ref() == tag) {
+                    Ok(tag.clone())
                 } else {
                     Err(RegistryError::NotFound {
                         service: service.to_string(),
-                        version: v.clone(),
+                        version: tag.clone(),
                     })
                 }
             }
-            VersionQuery::Latest => Self::find_latest(available_tags, service),
+            VersionQuery::Latest => self.find_highest(query, available_tags, service, |_| true),
             VersionQuery::Major(major) => {
-                Self::find_latest_in_major(*major, available_tags, service)
+                self.find_highest(query, available_tags, service, |v| v.major == *major)
             }
             VersionQuery::Minor(major, minor) => {
-                Self::find_latest_in_minor(*major, *minor, available_tags, service)
+                self.find_highest(query, available_tags, service, |v| {
+                    v.major == *major && v.minor == *minor
+                })
+            }
+            VersionQuery::Constraint(req) => {
+                self.find_highest(query, available_tags, service, |v| self.satisfies(req, v))
             }
         }
     }
 
-    /// Finds the latest version from available tags.
-    fn find_latest(tags: &[impl AsRef<str>], service: &str) -> Result<String, RegistryError> {
-        let mut semver_tags: Vec<(u64, u64, u64, &str)> = tags
-            .iter()
-            .filter_map(|t| {
-                let tag = t.as_ref();
-                Self::parse_semver(tag).map(|(major, minor, patch)| (major, minor, patch, tag))
-            })
-            .collect();
-
-        semver_tags.sort_by(|a, b| {
-            a.0.cmp(&b.0)
-                .then_with(|| a.1.cmp(&b.1))
-                .then_with(|| a.2.cmp(&b.2))
-                .reverse()
-        });
-
-        semver_tags
-            .first()
-            .map(|(_, _, _, tag)| (*tag).to_string())
-            .ok_or_else(|| RegistryError::VersionResolutionFailed {
-                service: service.to_string(),
-                query: "latest".to_string(),
-                message: "No valid semantic versions found".to_string(),
-            })
+    /// Returns true if a version satisfies a constraint.
+    fn satisfies(&self, req: &VersionReq, version: &Version) -> bool {
+        req.matches(version)
+            || (self.include_prereleases
+                && !version.pre.is_empty()
+                && req.matches(&Version::new(version.major, version.minor, version.patch)))
     }
 
-    /// Finds the latest version within a major version.
-    fn find_latest_in_major(
-        major: u64,
+    /// Finds the highest version accepted by `matches`.
+    fn find_highest(
+        &self,
+        query: &VersionQuery,
         tags: &[impl AsRef<str>],
         service: &str,
+        matches: impl Fn(&Version) -> bool,
     ) -> Result<String, RegistryError> {
-        let mut matching: Vec<(u64, u64, &str)> = tags
-            .iter()
-            .filter_map(|t| {
-                let tag = t.as_ref();
-                let (m, minor, patch) = Self::parse_semver(tag)?;
-                if m == major {
-                    Some((minor, patch, tag))
-                } else {
-                    None
+        Self::versions(tags)
+            .into_iter()
+            .filter(|(version, _)| self.include_prereleases || version.pre.is_empty())
+            .find(|(version, _)| matches(version))
+            .map(|(_, tag)| tag.to_string())
+            .ok_or_else(|| {
+                let mut available: Vec<String> = Self::versions(tags)
+                    .into_iter()
+                    .map(|(_, tag)| tag.to_string())
+                    .collect();
+                available.reverse();
+                RegistryError::NoMatchingVersion {
+                    service: service.to_string(),
+                    constraint: query.to_string(),
+                    available,
                 }
             })
-            .collect();
-
-        matching.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)).reverse());
-
-        matching
-            .first()
-            .map(|(_, _, tag)| (*tag).to_string())
-            .ok_or_else(|| RegistryError::VersionResolutionFailed {
-                service: service.to_string(),
-                query: format!("v{major}"),
-                message: format!("No versions found for major version {major}"),
-            })
     }
 
-    /// Finds the latest version within a minor version.
-    fn find_latest_in_minor(
-        major: u64,
-        minor: u64,
-        tags: &[impl AsRef<str>],
-        service: &str,
-    ) -> Result<String, RegistryError> {
-        let mut matching: Vec<(u64, &str)> = tags
+    /// Returns the tags that are semantic versions (with an optional `v`
+    /// prefix), highest first.
+    pub(crate) fn versions(tags: &[impl AsRef<str>]) -> Vec<(Version, &str)> {
+        let mut versions: Vec<(Version, &str)> = tags
             .iter()
-            .filter_map(|t| {
-                let tag = t.as_ref();
-                let (m, n, patch) = Self::parse_semver(tag)?;
-                if m == major && n == minor {
-                    Some((patch, tag))
-                } else {
-                    None
-                }
+            .filter_map(|tag| {
+                let tag = tag.as_ref();
+                let version = Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()?;
+                Some((version, tag))
             })
             .collect();
-
-        matching.sort_by(|a, b| a.0.cmp(&b.0).reverse());
-
-        matching
-            .first()
-            .map(|(_, tag)| (*tag).to_string())
-            .ok_or_else(|| RegistryError::VersionResolutionFailed {
-                service: service.to_string(),
-                query: format!("v{major}.{minor}"),
-                message: format!("No versions found for v{major}.{minor}.x"),
-            })
-    }
-
-    /// Parses a semantic version string into (major, minor, patch).
-    fn parse_semver(tag: &str) -> Option<(u64, u64, u64)> {
-        let version = tag.strip_prefix('v').unwrap_or(tag);
-        let parts: Vec<&str> = version.split('.').collect();
-
-        if parts.len() != 3 {
-            return None;
-        }
-
-        let major = parts[0].parse().ok()?;
-        let minor = parts[1].parse().ok()?;
-        let patch = parts[2].split('-').next()?.parse().ok()?;
-
-        Some((major, minor, patch))
+        versions.sort_by(|a, b| b.0.cmp(&a.0));
+        versions
     }
 }
 
@@ -417,6 +450,132 @@ mod tests {
         assert_eq!(result, "sha256:abc123");
     }
 
+    #[test]
+    fn test_parse_constraint() {
+        for input in [
+            "^1.2.0",
+            "~1.2.0",
+            ">=1.0.0 <2.0.0",
+            ">= v1.0.0, < v2.0.0",
+            "=1.2.3",
+        ] {
+            let query = VersionQuery::parse(input).unwrap();
+            assert!(matches!(query, VersionQuery::Constraint(_)), "{input}");
+        }
+
+        assert!(VersionQuery::parse("^not-a-version").is_err());
+    }
+
+    #[test]
+    fn test_resolve_constraint() {
+        let resolver = VersionResolver::new();
+        let tags = vec!["v1.0.0", "v1.2.3", "v2.0.0"];
+
+        let cases = [
+            ("^1.0.0", "v1.2.3"),
+            ("^1.2.0", "v1.2.3"),
+            ("^2", "v2.0.0"),
+            ("~1.2.0", "v1.2.3"),
+            ("~1.0.0", "v1.0.0"),
+            (">=1.0.0 <2.0.0", "v1.2.3"),
+            (">=1.0.0", "v2.0.0"),
+            ("<1.2.3", "v1.0.0"),
+            ("=1.2.3", "v1.2.3"),
+        ];
+        for (constraint, expected) in cases {
+            let query = VersionQuery::parse(constraint).unwrap();
+            let version = resolver.resolve(&query, &tags, "test").unwrap();
+            assert_eq!(version, expected, "{constraint}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_constraint_no_match() {
+        let resolver = VersionResolver::new();
+        let tags = vec!["v1.0.0", "v1.2.3", "v2.0.0", "latest"];
+        let query = VersionQuery::parse("^3.0.0").unwrap();
+
+        let err = resolver
+            .resolve(&query, &tags, "users-service")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::NoMatchingVersion { ref service, ref constraint, .. }
+                if service == "users-service" && constraint == "^3.0.0"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "No version of users-service matches '^3.0.0' (available: v1.0.0, v1.2.3, v2.0.0)"
+        );
+    }
+
+    #[test]
+    fn test_parse_prerelease_and_channel() {
+        let query = VersionQuery::parse("v2.0.0-rc.1").unwrap();
+        assert_eq!(query, VersionQuery::Exact("v2.0.0-rc.1".to_string()));
+
+        for channel in ["stable", "beta-2", "release_candidate", "vnext"] {
+            let query = VersionQuery::parse(channel).unwrap();
+            assert_eq!(query, VersionQuery::Channel(channel.to_string()));
+            assert!(query.is_channel());
+            assert_eq!(query.to_string(), channel);
+        }
+
+        for invalid in ["1.2.x", "-stable", "1.2.3.4", "stable/1"] {
+            assert!(VersionQuery::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_skips_prereleases() {
+        let tags = vec!["v1.2.0", "v1.3.0-rc.1", "v2.0.0-beta.1", "stable"];
+        let default = VersionResolver::new();
+        let prereleases = VersionResolver::new().with_prereleases(true);
+        assert!(!default.includes_prereleases());
+
+        let cases = [
+            ("latest", "v1.2.0", "v2.0.0-beta.1"),
+            ("v1", "v1.2.0", "v1.3.0-rc.1"),
+            ("^1.2", "v1.2.0", "v1.3.0-rc.1"),
+            ("~1.2.0", "v1.2.0", "v1.2.0"),
+        ];
+        for (query, released, prerelease) in cases {
+            let query = VersionQuery::parse(query).unwrap();
+            assert_eq!(default.resolve(&query, &tags, "svc").unwrap(), released);
+            assert_eq!(
+                prereleases.resolve(&query, &tags, "svc").unwrap(),
+                prerelease
+            );
+        }
+
+        // Exact prereleases are always found
+        let query = VersionQuery::parse("v1.3.0-rc.1").unwrap();
+        assert_eq!(
+            default.resolve(&query, &tags, "svc").unwrap(),
+            "v1.3.0-rc.1"
+        );
+
+        // Only prereleases match
+        let query = VersionQuery::parse("v2").unwrap();
+        let err = default.resolve(&query, &tags, "svc").unwrap_err();
+        assert!(err.to_string().contains("v2.0.0-beta.1"), "{err}");
+    }
+
+    #[test]
+    fn test_resolve_channel() {
+        let resolver = VersionResolver::new();
+        let tags = vec!["v1.0.0", "stable"];
+
+        let query = VersionQuery::parse("stable").unwrap();
+        assert_eq!(resolver.resolve(&query, &tags, "svc").unwrap(), "stable");
+
+        let query = VersionQuery::parse("beta").unwrap();
+        assert!(matches!(
+            resolver.resolve(&query, &tags, "svc"),
+            Err(RegistryError::NotFound { .. })
+        ));
+    }
+
     #[test]
     fn test_version_query_display() {
         assert_eq!(VersionQuery::Latest.to_string(), "latest");
@@ -430,5 +589,9 @@ mod tests {
             VersionQuery::Digest("sha256:abc".to_string()).to_string(),
             "sha256:abc"
         );
+        assert_eq!(
+            VersionQuery::parse(">=1.0.0 <2.0.0").unwrap().to_string(),
+            ">=1.0.0, <2.0.0"
+        );
     }
 }
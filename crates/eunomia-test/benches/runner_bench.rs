@@ -0,0 +1,79 @@
+//! Performance Benchmarks for the Eunomia Test Runner
+//!
+//! These benchmarks compare sequential and parallel execution of a policy
+//! test suite with several packages, each worker loading its own engine.
+//!
+//! Run with: `cargo bench -p eunomia-test`
+
+use std::path::PathBuf;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use eunomia_test::{DiscoveredTest, TestRunner, TestSuite};
+
+// =============================================================================
+// Test Data
+// =============================================================================
+
+/// Builds a suite of `packages` packages with `tests` passing tests each.
+fn create_suite(packages: usize, tests: usize) -> TestSuite {
+    let mut suite = TestSuite::new("/policies");
+
+    for p in 0..packages {
+        let package = format!("bench_{p}_test");
+        let file = PathBuf::from(format!("{package}.rego"));
+        let rules = (0..tests)
+            .map(|t| {
+                format!(
+                    "test_{t} if {{\n    roles := {{\"admin\", \"viewer\", \"editor\"}}\n    \
+                     count({{r | some r in roles; startswith(r, \"a\")}}) == 1\n}}"
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let imports = "import future.keywords.if\nimport future.keywords.in";
+        suite.add_policy_file(
+            file.clone(),
+            format!("package {package}\n\n{imports}\n\n{rules}"),
+        );
+
+        for t in 0..tests {
+            suite.add_test(DiscoveredTest {
+                file: file.clone(),
+                package: package.clone(),
+                name: format!("test_{t}"),
+                qualified_name: format!("data.{package}.test_{t}"),
+                policy_file: None,
+                description: None,
+            });
+        }
+    }
+
+    suite
+}
+
+// =============================================================================
+// Benchmarks
+// =============================================================================
+
+fn bench_run_suite(c: &mut Criterion) {
+    let mut group = c.benchmark_group("run_suite");
+
+    let suite = create_suite(8, 10);
+    let runner = TestRunner::default();
+
+    for parallelism in [1, 2, 4, 8] {
+        group.bench_with_input(
+            BenchmarkId::new("parallelism", parallelism),
+            &parallelism,
+            |b, &parallelism| {
+                b.iter(|| black_box(runner.run_suite_parallel(&suite, parallelism).unwrap()));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_run_suite);
+
+criterion_main!(benches);
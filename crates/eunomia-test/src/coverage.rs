@@ -1,11 +1,21 @@
 //! Policy coverage analysis.
 //!
-//! This module provides coverage tracking for policy tests.
+//! This module provides coverage tracking for policy tests. Reports are
+//! built from the line coverage collected by the Rego engine (see
+//! [`TestConfig::with_coverage`](crate::TestConfig::with_coverage)) and can
+//! be exported in LCOV format for CI tooling, or as a static HTML report
+//! with [`CoverageReporter`].
 
 use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::PathBuf;
 
+use eunomia_compiler::PolicyCoverage;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{Result, TestError};
+use crate::reporter::escape_xml;
+
 /// Coverage data for a policy.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CoverageReport {
@@ -32,6 +42,23 @@ pub struct FileCoverage {
     pub total_lines: usize,
     /// Coverage percentage for this file.
     pub coverage_percent: f64,
+    /// Coverage of the rules defined in this file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<RuleCoverage>,
+    /// Source text of the file, if known, for annotated reports.
+    #[serde(skip)]
+    pub source: Option<String>,
+}
+
+/// Coverage of a single rule definition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleCoverage {
+    /// Rule name.
+    pub name: String,
+    /// Line of the rule head (1-based).
+    pub line: usize,
+    /// Whether any line of the rule was evaluated.
+    pub covered: bool,
 }
 
 impl CoverageReport {
@@ -57,11 +84,106 @@ impl CoverageReport {
         }
     }
 
+    /// Returns the percentage of executable lines covered across all files.
+    #[must_use]
+    pub const fn overall_percent(&self) -> f64 {
+        self.total_coverage
+    }
+
+    /// Combines two reports, e.g. of separate test suites.
+    ///
+    /// A line or rule is covered if it is covered in either report.
+    #[must_use]
+    pub fn merge(mut self, other: Self) -> Self {
+        for (path, file) in other.files {
+            let file = match self.files.remove(&path) {
+                Some(existing) => existing.merge(file),
+                None => file,
+            };
+            self.files.insert(path, file);
+        }
+
+        let mut report = Self::new();
+        for file in self.files.into_values() {
+            report.add_file(file);
+        }
+        report
+    }
+
     /// Returns true if coverage meets the threshold.
     #[must_use]
     pub fn meets_threshold(&self, threshold: f64) -> bool {
         self.total_coverage >= threshold
     }
+
+    /// Builds a report from engine coverage data, including rule coverage.
+    #[must_use]
+    pub fn from_policies<'a>(policies: impl IntoIterator<Item = &'a PolicyCoverage>) -> Self {
+        let mut report = Self::new();
+        for policy in policies {
+            let mut file = FileCoverage::from_lines(
+                &policy.file,
+                policy.covered.iter().copied(),
+                policy.not_covered.iter().copied(),
+            );
+            file.collect_rules(&policy.source);
+            file.source = Some(policy.source.clone());
+            report.add_file(file);
+        }
+        report
+    }
+
+    /// Returns the files sorted by path.
+    #[must_use]
+    pub fn sorted_files(&self) -> Vec<&FileCoverage> {
+        let mut files: Vec<_> = self.files.values().collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+
+    /// Renders the report in LCOV tracefile format.
+    ///
+    /// Rules are emitted as functions (`FN`/`FNDA`) and executable lines as
+    /// `DA` records with a hit count of 0 or 1.
+    #[must_use]
+    pub fn to_lcov(&self) -> String {
+        let mut out = String::new();
+
+        for file in self.sorted_files() {
+            let _ = writeln!(out, "TN:");
+            let _ = writeln!(out, "SF:{}", file.path);
+
+            for rule in &file.rules {
+                let _ = writeln!(out, "FN:{},{}", rule.line, rule.name);
+            }
+            for rule in &file.rules {
+                let _ = writeln!(out, "FNDA:{},{}", u8::from(rule.covered), rule.name);
+            }
+            let _ = writeln!(out, "FNF:{}", file.rules.len());
+            let _ = writeln!(
+                out,
+                "FNH:{}",
+                file.rules.iter().filter(|r| r.covered).count()
+            );
+
+            let mut lines: Vec<(usize, bool)> = file
+                .covered_lines
+                .iter()
+                .map(|&l| (l, true))
+                .chain(file.uncovered_lines.iter().map(|&l| (l, false)))
+                .collect();
+            lines.sort_unstable();
+            for (line, covered) in lines {
+                let _ = writeln!(out, "DA:{line},{}", u8::from(covered));
+            }
+
+            let _ = writeln!(out, "LF:{}", file.total_lines);
+            let _ = writeln!(out, "LH:{}", file.covered_lines.len());
+            let _ = writeln!(out, "end_of_record");
+        }
+
+        out
+    }
 }
 
 impl FileCoverage {
@@ -74,7 +196,97 @@ impl FileCoverage {
             covered_lines: Vec::new(),
             uncovered_lines: (1..=total_lines).collect(),
             coverage_percent: 0.0,
+            rules: Vec::new(),
+            source: None,
+        }
+    }
+
+    /// Creates file coverage from explicit sets of executable lines.
+    ///
+    /// Unlike [`new`](Self::new), lines that appear in neither set (comments,
+    /// blank lines) do not count towards the total.
+    #[must_use]
+    pub fn from_lines(
+        path: impl Into<String>,
+        covered: impl IntoIterator<Item = usize>,
+        uncovered: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        let mut covered_lines: Vec<usize> = covered.into_iter().collect();
+        let mut uncovered_lines: Vec<usize> = uncovered.into_iter().collect();
+        covered_lines.sort_unstable();
+        covered_lines.dedup();
+        uncovered_lines.sort_unstable();
+        uncovered_lines.dedup();
+        uncovered_lines.retain(|l| covered_lines.binary_search(l).is_err());
+
+        let mut file = Self {
+            path: path.into(),
+            total_lines: covered_lines.len() + uncovered_lines.len(),
+            covered_lines,
+            uncovered_lines,
+            coverage_percent: 0.0,
+            rules: Vec::new(),
+            source: None,
+        };
+        file.recalculate_percent();
+        file
+    }
+
+    /// Combines the coverage of the same file from two reports.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        let mut file = Self::from_lines(
+            self.path,
+            self.covered_lines.into_iter().chain(other.covered_lines),
+            self.uncovered_lines
+                .into_iter()
+                .chain(other.uncovered_lines),
+        );
+
+        file.rules = self.rules;
+        for rule in other.rules {
+            match file
+                .rules
+                .iter_mut()
+                .find(|r| r.line == rule.line && r.name == rule.name)
+            {
+                Some(existing) => existing.covered |= rule.covered,
+                None => file.rules.push(rule),
+            }
         }
+        file.rules.sort_by_key(|r| r.line);
+        file.source = self.source.or(other.source);
+        file
+    }
+
+    /// Determines which rules in `source` were exercised.
+    ///
+    /// A rule counts as covered when any line from its head up to the next
+    /// top-level definition was evaluated.
+    pub fn collect_rules(&mut self, source: &str) {
+        let heads: Vec<(usize, String)> = source
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| rule_head_name(line).map(|name| (i + 1, name)))
+            .collect();
+
+        self.rules = heads
+            .iter()
+            .enumerate()
+            .map(|(idx, (line, name))| {
+                let end = heads.get(idx + 1).map_or(usize::MAX, |(next, _)| *next);
+                RuleCoverage {
+                    name: name.clone(),
+                    line: *line,
+                    covered: self.covered_lines.iter().any(|l| (*line..end).contains(l)),
+                }
+            })
+            .collect();
+    }
+
+    /// Returns the rules that were never exercised.
+    pub fn uncovered_rules(&self) -> impl Iterator<Item = &RuleCoverage> {
+        self.rules.iter().filter(|r| !r.covered)
     }
 
     /// Marks a line as covered.
@@ -103,6 +315,187 @@ impl FileCoverage {
     }
 }
 
+/// Writes coverage reports as static HTML.
+///
+/// The report is a single `index.html` with the overall coverage, a table
+/// of files, and each file's source with covered lines highlighted green
+/// and uncovered lines red.
+#[derive(Debug, Clone)]
+pub struct CoverageReporter {
+    /// Directory the report is written to.
+    output_dir: PathBuf,
+}
+
+impl CoverageReporter {
+    /// Creates a reporter writing to `output_dir`.
+    #[must_use]
+    pub fn new(output_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            output_dir: output_dir.into(),
+        }
+    }
+
+    /// Writes the report, creating the output directory if needed.
+    ///
+    /// Returns the path of the written `index.html`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or file cannot be written.
+    pub fn write(&self, report: &CoverageReport) -> Result<PathBuf> {
+        std::fs::create_dir_all(&self.output_dir).map_err(|source| TestError::Io {
+            path: self.output_dir.clone(),
+            source,
+        })?;
+
+        let index = self.output_dir.join("index.html");
+        std::fs::write(&index, Self::render(report)).map_err(|source| TestError::Io {
+            path: index.clone(),
+            source,
+        })?;
+        Ok(index)
+    }
+
+    /// Renders a report as an HTML document.
+    #[must_use]
+    pub fn render(report: &CoverageReport) -> String {
+        let files = report.sorted_files();
+        let mut html = String::from(HTML_HEAD);
+
+        let _ = writeln!(
+            html,
+            "<h1>Policy coverage</h1>\n<p class=\"total\">Total: <strong>{:.1}%</strong> \
+             ({} of {} lines)</p>",
+            report.overall_percent(),
+            report.covered_lines,
+            report.total_lines
+        );
+
+        html.push_str(
+            "<table>\n<tr><th>File</th><th>Lines</th><th>Rules</th><th>Coverage</th></tr>\n",
+        );
+        for (i, file) in files.iter().enumerate() {
+            let _ = writeln!(
+                html,
+                "<tr><td><a href=\"#file-{i}\">{}</a></td><td>{}/{}</td><td>{}/{}</td>\
+                 <td>{:.1}%</td></tr>",
+                escape_xml(&file.path),
+                file.covered_lines.len(),
+                file.total_lines,
+                file.rules.iter().filter(|r| r.covered).count(),
+                file.rules.len(),
+                file.coverage_percent
+            );
+        }
+        html.push_str("</table>\n");
+
+        for (i, file) in files.iter().enumerate() {
+            Self::render_file(&mut html, i, file);
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    fn render_file(html: &mut String, index: usize, file: &FileCoverage) {
+        let _ = writeln!(
+            html,
+            "<section id=\"file-{index}\">\n<h2>{} <span>{:.1}%</span></h2>",
+            escape_xml(&file.path),
+            file.coverage_percent
+        );
+
+        if !file.rules.is_empty() {
+            html.push_str("<ul class=\"rules\">\n");
+            for rule in &file.rules {
+                let _ = writeln!(
+                    html,
+                    "<li class=\"{}\">{} (line {})</li>",
+                    line_class(rule.covered),
+                    escape_xml(&rule.name),
+                    rule.line
+                );
+            }
+            html.push_str("</ul>\n");
+        }
+
+        match &file.source {
+            Some(source) => {
+                html.push_str("<pre>");
+                for (i, text) in source.lines().enumerate() {
+                    let line = i + 1;
+                    let class = if file.covered_lines.contains(&line) {
+                        line_class(true)
+                    } else if file.uncovered_lines.contains(&line) {
+                        line_class(false)
+                    } else {
+                        "plain"
+                    };
+                    let _ = writeln!(
+                        html,
+                        "<span class=\"{class}\"><span class=\"no\">{line:>4}</span> {}</span>",
+                        escape_xml(text)
+                    );
+                }
+                html.push_str("</pre>\n");
+            }
+            None => html.push_str("<p>Source not available.</p>\n"),
+        }
+
+        html.push_str("</section>\n");
+    }
+}
+
+/// CSS class of a covered or uncovered line or rule.
+const fn line_class(covered: bool) -> &'static str {
+    if covered {
+        "covered"
+    } else {
+        "uncovered"
+    }
+}
+
+const HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Policy coverage</title>
+<style>
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }
+pre { line-height: 1.4; }
+pre span { display: block; }
+.no { display: inline; color: #888; user-select: none; }
+.covered { background: #e6ffed; }
+.uncovered { background: #ffeef0; }
+</style>
+</head>
+<body>
+"#;
+
+/// Returns the name of the rule defined on a top-level line, if any.
+fn rule_head_name(line: &str) -> Option<String> {
+    if line.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let line = line.strip_prefix("default ").unwrap_or(line).trim_start();
+    if line.starts_with("package ") || line.starts_with("import ") {
+        return None;
+    }
+
+    let name: String = line
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        None
+    } else {
+        Some(name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +561,116 @@ mod tests {
         assert!(report.meets_threshold(70.0));
         assert!(!report.meets_threshold(90.0));
     }
+
+    const POLICY: &str = r#"package authz
+
+import future.keywords.if
+
+default allow := false
+
+allow if {
+    input.caller.role == "admin"
+}
+
+allow if {
+    input.caller.role == "owner"
+}
+"#;
+
+    fn policy_coverage() -> PolicyCoverage {
+        PolicyCoverage {
+            file: "authz.rego".to_string(),
+            source: POLICY.to_string(),
+            covered: [5, 7, 8].into_iter().collect(),
+            not_covered: [11, 12].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn test_file_coverage_from_lines() {
+        let coverage = FileCoverage::from_lines("test.rego", [3, 1, 3], [2, 4, 3]);
+
+        assert_eq!(coverage.covered_lines, vec![1, 3]);
+        assert_eq!(coverage.uncovered_lines, vec![2, 4]);
+        assert_eq!(coverage.total_lines, 4);
+        assert!((coverage.coverage_percent - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_report_from_policies_tracks_rules() {
+        let report = CoverageReport::from_policies(&[policy_coverage()]);
+        let file = &report.files["authz.rego"];
+
+        assert_eq!(report.total_lines, 5);
+        assert_eq!(report.covered_lines, 3);
+        assert!((report.total_coverage - 60.0).abs() < f64::EPSILON);
+
+        let rules: Vec<_> = file
+            .rules
+            .iter()
+            .map(|r| (r.name.as_str(), r.line, r.covered))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![("allow", 5, true), ("allow", 7, true), ("allow", 11, false)]
+        );
+        assert_eq!(file.uncovered_rules().count(), 1);
+    }
+
+    #[test]
+    fn test_merge_reports() {
+        let first = CoverageReport::from_policies(&[policy_coverage()]);
+        let second = CoverageReport::from_policies(&[PolicyCoverage {
+            covered: std::iter::once(11).collect(),
+            not_covered: [5, 7, 8, 12].into_iter().collect(),
+            ..policy_coverage()
+        }]);
+
+        let merged = first.merge(second);
+        let file = &merged.files["authz.rego"];
+
+        assert_eq!(file.covered_lines, vec![5, 7, 8, 11]);
+        assert_eq!(file.uncovered_lines, vec![12]);
+        assert!((merged.overall_percent() - 80.0).abs() < f64::EPSILON);
+        assert_eq!(file.uncovered_rules().count(), 0);
+        assert!(file.source.is_some());
+    }
+
+    #[test]
+    fn test_html_report() {
+        let report = CoverageReport::from_policies(&[policy_coverage()]);
+        let html = CoverageReporter::render(&report);
+
+        assert!(html.contains("Total: <strong>60.0%</strong> (3 of 5 lines)"));
+        assert!(html.contains(
+            "<tr><td><a href=\"#file-0\">authz.rego</a></td><td>3/5</td><td>2/3</td>\
+             <td>60.0%</td></tr>"
+        ));
+        assert!(html.contains("<li class=\"covered\">allow (line 5)</li>"));
+        assert!(html.contains("<li class=\"uncovered\">allow (line 11)</li>"));
+        assert!(html.contains(
+            "<span class=\"uncovered\"><span class=\"no\">  12</span>     \
+             input.caller.role == &quot;owner&quot;</span>"
+        ));
+        assert!(html.contains("<span class=\"plain\"><span class=\"no\">   1</span> package"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let index = CoverageReporter::new(dir.path().join("report"))
+            .write(&report)
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(index).unwrap(), html);
+    }
+
+    #[test]
+    fn test_to_lcov() {
+        let report = CoverageReport::from_policies(&[policy_coverage()]);
+        let lcov = report.to_lcov();
+
+        assert!(lcov.starts_with("TN:\nSF:authz.rego\n"));
+        assert!(lcov.contains("FN:11,allow\n"));
+        assert!(lcov.contains("FNF:3\nFNH:2\n"));
+        assert!(lcov.contains("DA:7,1\nDA:8,1\nDA:11,0\nDA:12,0\n"));
+        assert!(lcov.contains("LF:5\nLH:3\n"));
+        assert!(lcov.ends_with("end_of_record\n"));
+    }
 }
@@ -350,9 +350,16 @@ impl TestDiscovery {
             let is_yaml =
                 extension.eq_ignore_ascii_case("yaml") || extension.eq_ignore_ascii_case("yml");
 
-            if is_json && file_name.contains("fixture") {
+            // Fixture files are named `*fixture*` or live in a `fixtures/` directory
+            let is_fixture = file_name.contains("fixture")
+                || path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .is_some_and(|dir| dir == "fixtures");
+
+            if is_json && is_fixture {
                 Self::process_fixture_file(path, FixtureFormat::Json, suite);
-            } else if is_yaml && file_name.contains("fixture") {
+            } else if is_yaml && is_fixture {
                 Self::process_fixture_file(path, FixtureFormat::Yaml, suite);
             }
             // Also check for data files (data.json, data.yaml)
@@ -477,7 +484,7 @@ impl Default for TestDiscovery {
 }
 
 /// Extracts the package name from source.
-fn extract_package(source: &str) -> Option<String> {
+pub(crate) fn extract_package(source: &str) -> Option<String> {
     for line in source.lines() {
         let trimmed = line.trim();
 
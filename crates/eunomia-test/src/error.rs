@@ -73,6 +73,21 @@ pub enum TestError {
     /// YAML error.
     #[error("YAML error: {0}")]
     YamlError(#[from] serde_yaml::Error),
+
+    /// TOML error.
+    #[error("TOML error: {0}")]
+    TomlError(String),
+
+    /// Fuzzing found an input for which a query misbehaved.
+    #[error("Fuzzing {query} failed for input {input}: {reason}")]
+    FuzzFailure {
+        /// The fuzzed query.
+        query: String,
+        /// The minimal failing input, as JSON.
+        input: String,
+        /// Why the input failed.
+        reason: String,
+    },
 }
 
 #[cfg(test)]
@@ -1,14 +1,86 @@
 //! Test fixtures for policy testing.
 //!
 //! Fixtures provide test input data and expected outcomes for policy evaluation.
+//!
+//! # Table-driven fixtures
+//!
+//! A fixture file can declare a target `query` and a list of `cases`. Each
+//! case is evaluated against that query with its own input (and optional
+//! data) and compared to `expect`, which may be a boolean or any JSON value:
+//!
+//! ```yaml
+//! query: data.authz.allow
+//! cases:
+//!   - name: admin_can_delete
+//!     input: { caller: { roles: [admin] }, method: DELETE }
+//!     expect: true
+//!   - name: guest_denied
+//!     input: { caller: { roles: [] }, method: DELETE }
+//!     expect: false
+//! ```
+//!
+//! # Matrix fixtures
+//!
+//! A fixture file can also run a Rego test once per row of a [`TestMatrix`],
+//! with the row's input. The test is named by its qualified name; each row is
+//! reported as `<test>[row N]`, in addition to the test's own run:
+//!
+//! ```yaml
+//! matrix:
+//!   test: data.authz_test.test_read_allowed
+//!   base: { method: GET }
+//!   axes:
+//!     - name: caller.type
+//!       values: [user, service]
+//!     - name: operation_id
+//!       values: [getUser, listUsers]
+//! ```
+//!
+//! # Fixture schema
+//!
+//! A YAML fixture file with a `tests` list is a [`FixtureSchema`]: each test
+//! evaluates the `allow` rule of the package in `policy_file`, resolved
+//! relative to the fixture file, and can also check the `reason` the policy
+//! gives for a denial. Unknown fields are rejected:
+//!
+//! ```yaml
+//! description: Order service authorization
+//! policy_file: ../authz.rego
+//! tests:
+//!   - name: admin_can_delete
+//!     input: { caller: { roles: [admin] }, method: DELETE }
+//!     expected_allow: true
+//!   - name: guest_denied
+//!     input: { caller: { roles: [] }, method: DELETE }
+//!     expected_allow: false
+//!     expected_deny_reason: "access denied: no matching permission"
+//! ```
+//!
+//! # Decision snapshots
+//!
+//! A [`SnapshotStore`] records the decision of a query for a set of
+//! reference inputs, so that changes to a policy that flip any of them can be
+//! spotted with [`TestRunner::run_snapshot_tests`](crate::TestRunner::run_snapshot_tests).
+//! Snapshot files are TOML if their extension is `.toml`, and JSON otherwise:
+//!
+//! ```toml
+//! query = "data.authz.allow"
+//!
+//! [[decisions]]
+//! allowed = true
+//!
+//! [decisions.input.caller]
+//! roles = ["admin"]
+//! ```
 
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Result, TestError};
+use crate::test_utils::TestMatrix;
 
 /// A test fixture containing input and expected output for a policy test.
 ///
@@ -97,6 +169,167 @@ impl TestFixture {
         self.data.insert(path.into(), data);
         self
     }
+
+    /// Parses a fixture from a YAML test entry of a [`FixtureSchema`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the YAML does not match [`FixtureTest`].
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let test: FixtureTest = serde_yaml::from_str(yaml)?;
+        Ok(test.into())
+    }
+}
+
+/// A fixture file evaluating the `allow` rule of a single policy.
+///
+/// See the [module documentation](self#fixture-schema) for the format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureSchema {
+    /// Description of what the fixture file tests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Policy under test, relative to the fixture file.
+    pub policy_file: PathBuf,
+
+    /// The tests in this file.
+    pub tests: Vec<FixtureTest>,
+}
+
+/// A single test of a [`FixtureSchema`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureTest {
+    /// Name of the test.
+    pub name: String,
+
+    /// Input for the evaluation.
+    #[serde(default = "empty_object")]
+    pub input: serde_json::Value,
+
+    /// Expected `allow` decision.
+    pub expected_allow: bool,
+
+    /// Expected `reason` of a denial.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_deny_reason: Option<String>,
+}
+
+impl From<FixtureTest> for TestFixture {
+    fn from(test: FixtureTest) -> Self {
+        Self {
+            expected_reason: test.expected_deny_reason,
+            ..Self::new(test.name)
+                .with_input(test.input)
+                .expect_allowed(test.expected_allow)
+        }
+    }
+}
+
+impl FixtureSchema {
+    /// Returns true if `yaml` is a fixture file in this schema rather than
+    /// a [`FixtureSet`], i.e. it has a top-level `tests` list.
+    #[must_use]
+    pub fn matches(yaml: &str) -> bool {
+        serde_yaml::from_str::<serde_yaml::Value>(yaml)
+            .is_ok_and(|value| value.get("tests").is_some())
+    }
+
+    /// Parses a fixture file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the YAML does not match the schema.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    /// Loads a fixture file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not match the
+    /// schema.
+    pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| TestError::FileReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Self::from_yaml(&content)
+    }
+
+    /// Returns the tests as fixtures.
+    #[must_use]
+    pub fn fixtures(&self) -> Vec<TestFixture> {
+        self.tests.iter().cloned().map(TestFixture::from).collect()
+    }
+}
+
+/// A single case in a table-driven fixture file.
+///
+/// # Examples
+///
+/// ```rust
+/// use eunomia_test::FixtureCase;
+/// use serde_json::json;
+///
+/// let case = FixtureCase::new("admin_allowed", json!(true))
+///     .with_input(json!({ "caller": { "roles": ["admin"] } }));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureCase {
+    /// Name of the case.
+    pub name: String,
+
+    /// Description of what this case tests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Input for the evaluation.
+    #[serde(default = "empty_object")]
+    pub input: serde_json::Value,
+
+    /// Data merged into the policy data for this case only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+
+    /// Expected query result: a boolean or any JSON value.
+    pub expect: serde_json::Value,
+}
+
+impl FixtureCase {
+    /// Creates a case expecting `expect` with an empty input.
+    #[must_use]
+    pub fn new(name: impl Into<String>, expect: serde_json::Value) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            input: empty_object(),
+            data: None,
+            expect,
+        }
+    }
+
+    /// Sets the input.
+    #[must_use]
+    pub fn with_input(mut self, input: serde_json::Value) -> Self {
+        self.input = input;
+        self
+    }
+
+    /// Sets the data for this case.
+    #[must_use]
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+}
+
+fn empty_object() -> serde_json::Value {
+    serde_json::Value::Object(serde_json::Map::new())
 }
 
 /// A collection of test fixtures.
@@ -111,7 +344,33 @@ pub struct FixtureSet {
     pub package: Option<String>,
 
     /// The fixtures in this set.
+    #[serde(default)]
     pub fixtures: Vec<TestFixture>,
+
+    /// Query evaluated by table-driven [`cases`](Self::cases), e.g.
+    /// `data.authz.allow`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+
+    /// Table-driven cases evaluated against [`query`](Self::query).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cases: Vec<FixtureCase>,
+
+    /// Test run once per row of an input matrix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matrix: Option<MatrixFixture>,
+}
+
+/// A Rego test to run with every input of a [`TestMatrix`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatrixFixture {
+    /// Qualified name of the test rule, e.g.
+    /// `data.authz_test.test_read_allowed`; the `data.` prefix is optional.
+    pub test: String,
+
+    /// The input matrix.
+    #[serde(flatten)]
+    pub matrix: TestMatrix,
 }
 
 impl FixtureSet {
@@ -142,16 +401,40 @@ impl FixtureSet {
         self
     }
 
-    /// Returns the number of fixtures in this set.
+    /// Sets the query evaluated by table-driven cases.
+    #[must_use]
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Adds a table-driven case to the set.
+    #[must_use]
+    pub fn add_case(mut self, case: FixtureCase) -> Self {
+        self.cases.push(case);
+        self
+    }
+
+    /// Sets the test run once per row of `matrix`.
+    #[must_use]
+    pub fn with_matrix(mut self, test: impl Into<String>, matrix: TestMatrix) -> Self {
+        self.matrix = Some(MatrixFixture {
+            test: test.into(),
+            matrix,
+        });
+        self
+    }
+
+    /// Returns the number of fixtures and cases in this set.
     #[must_use]
     pub const fn len(&self) -> usize {
-        self.fixtures.len()
+        self.fixtures.len() + self.cases.len()
     }
 
-    /// Returns true if there are no fixtures.
+    /// Returns true if there are no fixtures or cases.
     #[must_use]
     pub const fn is_empty(&self) -> bool {
-        self.fixtures.is_empty()
+        self.fixtures.is_empty() && self.cases.is_empty()
     }
 
     /// Loads fixtures from a JSON file.
@@ -187,6 +470,182 @@ impl FixtureSet {
     }
 }
 
+/// Decisions of a query for a set of reference inputs, stored in a file.
+///
+/// New inputs can be added with any decision and recorded by running the
+/// snapshot tests with [`TestConfig::update_snapshots`](crate::TestConfig::update_snapshots)
+/// set. TOML cannot represent `null`, so inputs holding nulls must be stored
+/// as JSON.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use eunomia_test::SnapshotStore;
+/// use serde_json::json;
+///
+/// let mut store = SnapshotStore::new("snapshots/authz.json", "data.authz.allow");
+/// store.record(json!({"caller": {"roles": ["admin"]}}), true);
+/// store.save()?;
+/// # Ok::<(), eunomia_test::TestError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotStore {
+    path: PathBuf,
+    query: String,
+    decisions: Vec<(serde_json::Value, bool)>,
+}
+
+/// On-disk representation of a [`SnapshotStore`].
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile {
+    query: String,
+    #[serde(default)]
+    decisions: Vec<SnapshotDecision>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotDecision {
+    // Before `input` so that TOML writes it ahead of the input table
+    allowed: bool,
+    input: serde_json::Value,
+}
+
+impl SnapshotStore {
+    /// Creates an empty store for `query`, saved to `path`.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>, query: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            query: query.into(),
+            decisions: Vec::new(),
+        }
+    }
+
+    /// Loads a store from a TOML or JSON snapshot file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| TestError::FileReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+
+        let file: SnapshotFile = if is_toml(path) {
+            toml::from_str(&content).map_err(|e| TestError::TomlError(e.to_string()))?
+        } else {
+            serde_json::from_str(&content)?
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            query: file.query,
+            decisions: file
+                .decisions
+                .into_iter()
+                .map(|decision| (decision.input, decision.allowed))
+                .collect(),
+        })
+    }
+
+    /// Loads every `.toml` and `.json` snapshot file in a directory, sorted
+    /// by file name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read or a snapshot file
+    /// fails to load.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Self>> {
+        let dir = dir.as_ref();
+        let io_error = |source| TestError::Io {
+            path: dir.to_path_buf(),
+            source,
+        };
+
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            let is_snapshot = path
+                .extension()
+                .is_some_and(|ext| ext == "toml" || ext == "json");
+            if is_snapshot && path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        paths.iter().map(Self::load).collect()
+    }
+
+    /// Writes the store to its file, creating parent directories as needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the decisions cannot be serialized or the file
+    /// cannot be written.
+    pub fn save(&self) -> Result<()> {
+        let file = SnapshotFile {
+            query: self.query.clone(),
+            decisions: self
+                .decisions
+                .iter()
+                .map(|(input, allowed)| SnapshotDecision {
+                    allowed: *allowed,
+                    input: input.clone(),
+                })
+                .collect(),
+        };
+        let content = if is_toml(&self.path) {
+            toml::to_string_pretty(&file).map_err(|e| TestError::TomlError(e.to_string()))?
+        } else {
+            serde_json::to_string_pretty(&file)? + "\n"
+        };
+
+        let io_error = |source| TestError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        fs::write(&self.path, content).map_err(io_error)
+    }
+
+    /// Records the decision for an input.
+    pub fn record(&mut self, input: serde_json::Value, allowed: bool) {
+        self.decisions.push((input, allowed));
+    }
+
+    /// Replaces all stored decisions.
+    pub fn set_decisions(&mut self, decisions: Vec<(serde_json::Value, bool)>) {
+        self.decisions = decisions;
+    }
+
+    /// Returns the stored decisions, in input order.
+    #[must_use]
+    pub fn decisions(&self) -> &[(serde_json::Value, bool)] {
+        &self.decisions
+    }
+
+    /// Returns the query the decisions were made by.
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns the path of the snapshot file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "toml")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,4 +699,135 @@ mod tests {
         assert_eq!(fixture.name, deserialized.name);
         assert_eq!(fixture.expected_allowed, deserialized.expected_allowed);
     }
+
+    #[test]
+    fn test_table_fixture_from_yaml() {
+        let yaml = r"
+query: data.authz.allow
+cases:
+  - name: admin_allowed
+    input:
+      caller:
+        roles: [admin]
+    expect: true
+  - name: reason_reported
+    data:
+      blocked: [guest]
+    expect:
+      allowed: false
+      reason: blocked
+";
+        let set: FixtureSet = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(set.query.as_deref(), Some("data.authz.allow"));
+        assert!(set.fixtures.is_empty());
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.cases[0].expect, json!(true));
+        assert_eq!(set.cases[1].input, json!({}));
+        assert_eq!(set.cases[1].data, Some(json!({"blocked": ["guest"]})));
+        assert_eq!(set.cases[1].expect["reason"], json!("blocked"));
+    }
+
+    #[test]
+    fn test_fixture_schema_from_yaml() {
+        let yaml = r"
+description: Authorization
+policy_file: ../authz.rego
+tests:
+  - name: admin_allowed
+    input:
+      caller:
+        roles: [admin]
+    expected_allow: true
+  - name: guest_denied
+    expected_allow: false
+    expected_deny_reason: no matching permission
+";
+        assert!(FixtureSchema::matches(yaml));
+        let schema = FixtureSchema::from_yaml(yaml).unwrap();
+
+        assert_eq!(schema.policy_file, PathBuf::from("../authz.rego"));
+        let fixtures = schema.fixtures();
+        assert_eq!(fixtures.len(), 2);
+        assert!(fixtures[0].expected_allowed);
+        assert_eq!(fixtures[1].input, json!({}));
+        assert_eq!(
+            fixtures[1].expected_reason.as_deref(),
+            Some("no matching permission")
+        );
+
+        let fixture = TestFixture::from_yaml("name: t\nexpected_allow: true\n").unwrap();
+        assert!(fixture.expected_allowed);
+
+        // Typos are rejected rather than silently ignored
+        assert!(TestFixture::from_yaml("name: t\nexpected_allowed: true\n").is_err());
+        assert!(!FixtureSchema::matches(
+            "query: data.authz.allow\ncases: []\n"
+        ));
+    }
+
+    #[test]
+    fn test_matrix_fixture_from_yaml() {
+        let yaml = r"
+package: authz_test
+matrix:
+  test: authz_test.test_read_allowed
+  axes:
+    - name: caller.type
+      values: [user, service]
+    - name: operation_id
+      values: [getUser, listUsers, searchUsers]
+";
+        let set: FixtureSet = serde_yaml::from_str(yaml).unwrap();
+
+        let matrix = set.matrix.unwrap();
+        assert_eq!(matrix.test, "authz_test.test_read_allowed");
+        assert_eq!(matrix.matrix.base, json!({}));
+        assert_eq!(matrix.matrix.generate().len(), 6);
+    }
+
+    #[test]
+    fn test_snapshot_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        for name in ["authz.json", "nested/authz.toml"] {
+            let path = dir.path().join(name);
+            let mut store = SnapshotStore::new(&path, "data.authz.allow");
+            store.record(json!({"caller": {"roles": ["admin"], "id": 7}}), true);
+            store.record(json!({"caller": {"roles": []}}), false);
+            store.save().unwrap();
+
+            assert_eq!(SnapshotStore::load(&path).unwrap(), store, "{name}");
+        }
+
+        let content = std::fs::read_to_string(dir.path().join("nested/authz.toml")).unwrap();
+        assert!(
+            content.starts_with("query = \"data.authz.allow\""),
+            "{content}"
+        );
+        assert!(content.contains("[[decisions]]"), "{content}");
+    }
+
+    #[test]
+    fn test_snapshot_store_load_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml = r#"query = "data.b.allow"
+
+[[decisions]]
+allowed = true
+input = { user = "alice" }
+"#;
+        std::fs::write(dir.path().join("b.toml"), toml).unwrap();
+        std::fs::write(dir.path().join("a.json"), r#"{"query": "data.a.allow"}"#).unwrap();
+        std::fs::write(dir.path().join("notes.md"), "not a snapshot").unwrap();
+
+        let stores = SnapshotStore::load_dir(dir.path()).unwrap();
+        assert_eq!(stores.len(), 2);
+        assert_eq!(stores[0].query(), "data.a.allow");
+        assert!(stores[0].decisions().is_empty());
+        assert_eq!(stores[1].decisions(), &[(json!({"user": "alice"}), true)]);
+
+        std::fs::write(dir.path().join("c.json"), "{").unwrap();
+        assert!(SnapshotStore::load_dir(dir.path()).is_err());
+    }
 }
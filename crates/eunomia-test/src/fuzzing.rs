@@ -0,0 +1,344 @@
+//! Property-based fuzzing of policy inputs.
+//!
+//! [`PolicyFuzzer`] evaluates a query against randomly generated inputs
+//! and checks that every evaluation succeeds with a boolean decision. A
+//! rule without a `default` is undefined for inputs none of its
+//! definitions match, so fuzzing catches it even if every hand-written
+//! test passes.
+//!
+//! Invariants over the decisions, such as "anonymous callers are always
+//! denied", are checked with [`PolicyFuzzer::assert_invariant`].
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use eunomia_compiler::RegoEngine;
+//! use eunomia_test::{prop_input_strategy, PolicyFuzzer};
+//!
+//! let mut engine = RegoEngine::new();
+//! engine.add_policy_from_file("policies/authz.rego")?;
+//!
+//! let fuzzer = PolicyFuzzer::new();
+//! fuzzer.assert_never_panics(&mut engine, "data.authz.allow", prop_input_strategy())?;
+//! fuzzer.assert_denies_anonymous(&mut engine, "data.authz.allow")?;
+//! ```
+
+use std::cell::RefCell;
+
+use eunomia_compiler::{EvalResult, RegoEngine};
+use eunomia_core::CallerIdentity;
+use proptest::prelude::*;
+use proptest::test_runner::{TestCaseError, TestError as ProptestError, TestRunner};
+use serde_json::Value;
+use themis_platform_types::identity::{ApiKeyIdentity, SpiffeIdentity, UserIdentity};
+
+use crate::error::{Result, TestError};
+use crate::test_utils::InputBuilder;
+
+pub use proptest::test_runner::Config as ProptestConfig;
+
+/// Number of inputs a [`PolicyFuzzer`] evaluates by default.
+pub const DEFAULT_FUZZ_RUNS: u32 = 1000;
+
+/// Returns the default fuzzing configuration: [`DEFAULT_FUZZ_RUNS`] cases
+/// and no persisted failures.
+#[must_use]
+pub fn default_fuzz_config() -> ProptestConfig {
+    ProptestConfig {
+        cases: DEFAULT_FUZZ_RUNS,
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    }
+}
+
+/// Strategy generating caller identities of all four kinds, with arbitrary
+/// field values.
+pub fn prop_caller_strategy() -> impl Strategy<Value = CallerIdentity> {
+    let name = "[a-z][a-z0-9-]{0,15}";
+    let role = prop_oneof!["(admin|editor|viewer|owner)", "[a-z_]{1,12}"];
+
+    prop_oneof![
+        (
+            name,
+            proptest::option::of("[a-z]{1,10}@[a-z]{1,10}\\.(com|org|io)"),
+            proptest::option::of("[A-Za-z ]{1,20}"),
+            proptest::collection::vec(role, 0..4),
+            proptest::collection::vec(name, 0..3),
+            proptest::option::of(name),
+        )
+            .prop_map(|(user_id, email, name, roles, groups, tenant_id)| {
+                CallerIdentity::User(UserIdentity {
+                    user_id,
+                    email,
+                    name,
+                    roles,
+                    groups,
+                    tenant_id,
+                })
+            }),
+        ("[a-z]{1,10}\\.(local|com|io)", name, name, any::<bool>(),).prop_map(
+            |(trust_domain, namespace, service, full)| {
+                let spiffe_id = format!("spiffe://{trust_domain}/ns/{namespace}/sa/{service}");
+                CallerIdentity::Spiffe(SpiffeIdentity {
+                    spiffe_id,
+                    trust_domain: full.then_some(trust_domain),
+                    service_name: full.then_some(service),
+                })
+            }
+        ),
+        (
+            "(key|ak)-[a-f0-9]{8,16}",
+            "[A-Za-z ]{1,20}",
+            proptest::collection::vec("(read|write|admin):[a-z]{1,10}", 0..4),
+            proptest::option::of(name),
+        )
+            .prop_map(|(key_id, name, scopes, owner_id)| {
+                CallerIdentity::ApiKey(ApiKeyIdentity {
+                    key_id,
+                    name,
+                    scopes,
+                    owner_id,
+                })
+            }),
+        Just(CallerIdentity::anonymous()),
+    ]
+}
+
+/// Strategy generating policy inputs with callers from
+/// [`prop_caller_strategy`].
+pub fn prop_input_strategy() -> impl Strategy<Value = Value> {
+    prop_input_with_callers(prop_caller_strategy())
+}
+
+/// Strategy generating policy inputs, in the `PolicyInput` schema, with
+/// callers from `callers` and arbitrary operations, methods and paths.
+pub fn prop_input_with_callers(
+    callers: impl Strategy<Value = CallerIdentity>,
+) -> impl Strategy<Value = Value> {
+    (
+        callers,
+        "(get|list|create|update|delete)(User|Order|Payment)(ById|s|)",
+        "(GET|POST|PUT|PATCH|DELETE)",
+        "/(users|orders|payments)(/[a-z0-9-]{1,36})?",
+        "[a-z][a-z0-9-]{0,15}-service",
+    )
+        .prop_map(|(caller, operation, method, path, service)| {
+            InputBuilder::new()
+                .caller(caller)
+                .operation(operation)
+                .method(method)
+                .path(path)
+                .service(service)
+                .build()
+        })
+}
+
+/// Evaluates queries against generated inputs, checking that decisions
+/// are always booleans and that invariants over them hold.
+///
+/// Failing inputs are shrunk to a minimal one before being reported as a
+/// [`TestError::FuzzFailure`].
+#[derive(Debug, Clone)]
+pub struct PolicyFuzzer {
+    config: ProptestConfig,
+}
+
+impl Default for PolicyFuzzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PolicyFuzzer {
+    /// Creates a fuzzer evaluating [`DEFAULT_FUZZ_RUNS`] inputs per check.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_config(default_fuzz_config())
+    }
+
+    /// Creates a fuzzer with a custom proptest configuration.
+    #[must_use]
+    pub const fn with_config(config: ProptestConfig) -> Self {
+        Self { config }
+    }
+
+    /// Sets the number of inputs evaluated per check.
+    #[must_use]
+    pub const fn with_runs(mut self, runs: u32) -> Self {
+        self.config.cases = runs;
+        self
+    }
+
+    /// Returns the proptest configuration.
+    #[must_use]
+    pub const fn config(&self) -> &ProptestConfig {
+        &self.config
+    }
+
+    /// Checks that `query` evaluates to a boolean for every generated
+    /// input, without panicking or failing.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TestError::FuzzFailure`] with the minimal input for which
+    /// evaluation panicked, failed or was not a boolean.
+    pub fn assert_never_panics(
+        &self,
+        engine: &mut RegoEngine,
+        query: &str,
+        strategy: impl Strategy<Value = Value>,
+    ) -> Result<()> {
+        self.assert_invariant(engine, query, strategy, "always decides", |_, _| true)
+    }
+
+    /// Checks that `query` denies every caller generated without an
+    /// identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TestError::FuzzFailure`] with an input that was allowed,
+    /// or for which no boolean decision was made.
+    pub fn assert_denies_anonymous(&self, engine: &mut RegoEngine, query: &str) -> Result<()> {
+        self.assert_invariant(
+            engine,
+            query,
+            prop_input_with_callers(Just(CallerIdentity::anonymous())),
+            "anonymous callers are denied",
+            |_, allowed| !allowed,
+        )
+    }
+
+    /// Checks that `query` evaluates to a boolean for every generated
+    /// input, and that `invariant` holds for the input and decision.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TestError::FuzzFailure`] with the minimal input for which
+    /// evaluation panicked, failed, was not a boolean or violated the
+    /// invariant.
+    pub fn assert_invariant(
+        &self,
+        engine: &mut RegoEngine,
+        query: &str,
+        strategy: impl Strategy<Value = Value>,
+        name: &str,
+        invariant: impl Fn(&Value, bool) -> bool,
+    ) -> Result<()> {
+        let engine = RefCell::new(engine);
+        let mut runner = TestRunner::new(self.config.clone());
+
+        let outcome = runner.run(&strategy, |input| {
+            let result = engine
+                .borrow_mut()
+                .eval_with_input(query, input.clone())
+                .map_err(|e| TestCaseError::fail(format!("evaluation failed: {e}")))?;
+            match result {
+                EvalResult::Bool(allowed) if invariant(&input, allowed) => Ok(()),
+                EvalResult::Bool(allowed) => Err(TestCaseError::fail(format!(
+                    "invariant '{name}' violated by decision {allowed}"
+                ))),
+                EvalResult::Undefined => Err(TestCaseError::fail(
+                    "result is undefined; is a `default` rule missing?",
+                )),
+                other => Err(TestCaseError::fail(format!(
+                    "result {} is not a boolean",
+                    other.to_json()
+                ))),
+            }
+        });
+
+        match outcome {
+            Ok(()) => Ok(()),
+            Err(ProptestError::Fail(reason, input)) => Err(TestError::FuzzFailure {
+                query: query.to_string(),
+                input: input.to_string(),
+                reason: reason.to_string(),
+            }),
+            Err(ProptestError::Abort(reason)) => Err(TestError::ExecutionError {
+                message: format!("Fuzzing {query} aborted: {reason}"),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::strategy::ValueTree;
+    use std::collections::HashSet;
+
+    fn engine(policy: &str) -> RegoEngine {
+        let mut engine = RegoEngine::new();
+        engine.add_policy("authz.rego", policy).unwrap();
+        engine
+    }
+
+    #[test]
+    fn test_caller_strategy_covers_all_variants() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = prop_caller_strategy();
+
+        let mut kinds = HashSet::new();
+        for _ in 0..200 {
+            let caller = strategy.new_tree(&mut runner).unwrap().current();
+            let value = serde_json::to_value(caller).unwrap();
+            kinds.insert(value["type"].as_str().unwrap().to_string());
+        }
+        assert_eq!(kinds.len(), 4, "{kinds:?}");
+    }
+
+    #[test]
+    fn test_missing_default_is_caught() {
+        let mut engine = engine(
+            "package authz\n\nimport future.keywords.if\n\n\
+             allow if input.caller.type == \"user\"\n",
+        );
+
+        let err = PolicyFuzzer::new()
+            .with_runs(100)
+            .assert_never_panics(&mut engine, "data.authz.allow", prop_input_strategy())
+            .unwrap_err();
+        let TestError::FuzzFailure {
+            query,
+            input,
+            reason,
+        } = err
+        else {
+            panic!("expected a fuzz failure, got {err}");
+        };
+        assert_eq!(query, "data.authz.allow");
+        assert!(reason.contains("`default` rule missing"), "{reason}");
+        assert!(!input.contains(r#""type":"user""#), "{input}");
+    }
+
+    #[test]
+    fn test_invariants() {
+        let mut engine = engine(
+            "package authz\n\nimport future.keywords.if\n\n\
+             default allow := false\n\n\
+             allow if input.caller.type == \"user\"\n",
+        );
+        let fuzzer = PolicyFuzzer::new().with_runs(100);
+
+        fuzzer
+            .assert_never_panics(&mut engine, "data.authz.allow", prop_input_strategy())
+            .unwrap();
+        fuzzer
+            .assert_denies_anonymous(&mut engine, "data.authz.allow")
+            .unwrap();
+
+        let err = fuzzer
+            .assert_invariant(
+                &mut engine,
+                "data.authz.allow",
+                prop_input_strategy(),
+                "users are denied",
+                |_, allowed| !allowed,
+            )
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("invariant 'users are denied'"),
+            "{err}"
+        );
+    }
+}
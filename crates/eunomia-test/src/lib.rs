@@ -10,6 +10,8 @@
 //! - Reporting test results
 //! - Mock identity builders for testing
 //! - Test utilities and assertion helpers
+//! - Property-based fuzzing of policy inputs
+//! - Replaying decision logs against candidate bundles
 //!
 //! ## Example
 //!
@@ -60,18 +62,34 @@ pub mod coverage;
 pub mod discovery;
 pub mod error;
 pub mod fixtures;
+pub mod fuzzing;
 pub mod mock_identity;
+pub mod replay;
 pub mod reporter;
 pub mod runner;
 pub mod test_utils;
 
+pub use coverage::{CoverageReport, CoverageReporter, FileCoverage, RuleCoverage};
 pub use discovery::{DiscoveredTest, DiscoveryConfig, FixtureFormat, TestDiscovery, TestSuite};
 pub use error::{Result, TestError};
-pub use fixtures::TestFixture;
+pub use fixtures::{
+    FixtureCase, FixtureSchema, FixtureSet, FixtureTest, MatrixFixture, SnapshotStore, TestFixture,
+};
+pub use fuzzing::{
+    default_fuzz_config, prop_caller_strategy, prop_input_strategy, prop_input_with_callers,
+    PolicyFuzzer, ProptestConfig, DEFAULT_FUZZ_RUNS,
+};
 pub use mock_identity::{MockApiKey, MockSpiffe, MockUser};
-pub use reporter::{ConsoleReporter, Reporter};
-pub use runner::{TestConfig, TestResult, TestResults, TestRunner};
+pub use replay::{
+    ChangedDecision, DecisionReplayer, ReplayFailure, ReplayRecord, ReplayReport, Sampled,
+    DEFAULT_SAMPLE_SIZE,
+};
+pub use reporter::{ConsoleReporter, JsonReporter, JunitReporter, Reporter};
+pub use runner::{
+    SnapshotMismatch, SnapshotResult, TestConfig, TestInterruption, TestKind, TestResult,
+    TestResults, TestRunner,
+};
 pub use test_utils::{
     assert_all_passed, assert_allowed, assert_denied, role_based_policy, scope_based_policy,
-    simple_allow_policy, InputBuilder,
+    simple_allow_policy, InputBuilder, MatrixAxis, TestMatrix,
 };
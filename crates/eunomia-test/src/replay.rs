@@ -0,0 +1,488 @@
+//! Decision log replay.
+//!
+//! Re-evaluates recorded authorization decisions against a candidate bundle
+//! and reports the decisions that would change, so that a policy change can
+//! be checked against real traffic before it is rolled out.
+//!
+//! The decision log is read line by line. Each line is either an
+//! [`AuthorizationEvent`] as written by the audit logger, or a raw policy
+//! input. Events carry the decision that was made at the time; raw inputs
+//! are compared against a baseline bundle, if one is given. Events logged
+//! without their policy input cannot be replayed and are only counted.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use eunomia_core::Bundle;
+//! use eunomia_test::DecisionReplayer;
+//!
+//! let bundle = Bundle::from_file("users-service-v1.3.0.bundle.tar.gz")?;
+//! let log = std::io::BufReader::new(std::fs::File::open("decisions.jsonl")?);
+//!
+//! let report = DecisionReplayer::new(&bundle)?.replay(log)?;
+//! println!("{} decisions would change", report.changes());
+//! ```
+
+use std::io::BufRead;
+use std::path::Path;
+
+use eunomia_audit::{parse_event, AuthorizationEvent, ParsedEvent};
+use eunomia_compiler::RegoEngine;
+use eunomia_core::Bundle;
+use serde::Serialize;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::error::{Result, TestError};
+
+/// Number of samples kept per kind of change by default.
+pub const DEFAULT_SAMPLE_SIZE: usize = 5;
+
+/// A decision read from a decision log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayRecord {
+    /// The policy input of the decision.
+    pub input: Value,
+    /// The decision that was made, if the log recorded one.
+    pub previous: Option<bool>,
+}
+
+impl ReplayRecord {
+    /// Parses one line of a decision log.
+    ///
+    /// Lines with `allowed` and `caller_type` fields are read as
+    /// authorization events, any other JSON object as a raw policy input.
+    /// Returns `None` for an event logged without its input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the line is not a JSON object or is a malformed
+    /// audit event.
+    pub fn parse(line: &str) -> Result<Option<Self>> {
+        let value: Value = serde_json::from_str(line)?;
+        let Some(fields) = value.as_object() else {
+            return Err(TestError::Parse(
+                "decision log entry is not a JSON object".to_string(),
+            ));
+        };
+
+        if !(fields.contains_key("allowed") && fields.contains_key("caller_type")) {
+            return Ok(Some(Self {
+                input: value,
+                previous: None,
+            }));
+        }
+
+        match parse_event(value).map_err(|e| TestError::Parse(e.to_string()))? {
+            ParsedEvent::Authorization(event) => Ok(Self::from_event(event)),
+            other => Err(TestError::Parse(format!(
+                "expected an authorization event, found {}",
+                other.event_type()
+            ))),
+        }
+    }
+
+    /// Creates a record from a logged authorization event.
+    ///
+    /// Returns `None` if the event was logged without its input: the caller,
+    /// service and operation it records are only part of the input the
+    /// decision was made on, so replaying them would report spurious
+    /// changes.
+    #[must_use]
+    pub fn from_event(event: AuthorizationEvent) -> Option<Self> {
+        Some(Self {
+            input: event.input?,
+            previous: Some(event.allowed),
+        })
+    }
+}
+
+/// A count of replayed decisions, with the first few kept as samples.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Sampled<T> {
+    /// Number of decisions.
+    pub count: usize,
+    /// The first decisions, up to the replayer's sample size.
+    pub samples: Vec<T>,
+}
+
+impl<T> Default for Sampled<T> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl<T> Sampled<T> {
+    fn record(&mut self, sample: T, limit: usize) {
+        self.count += 1;
+        if self.samples.len() < limit {
+            self.samples.push(sample);
+        }
+    }
+}
+
+/// A decision that changed on replay.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangedDecision {
+    /// Line of the decision log the decision was read from.
+    pub line: usize,
+    /// The policy input of the decision.
+    pub input: Value,
+}
+
+/// A decision log entry that could not be replayed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReplayFailure {
+    /// Line of the decision log the entry was read from.
+    pub line: usize,
+    /// Why the entry could not be replayed.
+    pub message: String,
+}
+
+/// Outcome of replaying a decision log.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ReplayReport {
+    /// Number of decision log entries read.
+    pub total: usize,
+    /// Decisions the bundle made the same way.
+    pub unchanged: usize,
+    /// Decisions that were allowed and are now denied.
+    pub allow_to_deny: Sampled<ChangedDecision>,
+    /// Decisions that were denied and are now allowed.
+    pub deny_to_allow: Sampled<ChangedDecision>,
+    /// Inputs that were evaluated but had no previous decision to compare.
+    pub unrecorded: usize,
+    /// Authorization events logged without their input, which cannot be
+    /// replayed.
+    pub unreplayable: usize,
+    /// Entries that could not be parsed or evaluated.
+    pub errors: Sampled<ReplayFailure>,
+}
+
+impl ReplayReport {
+    /// Returns the number of decisions that changed in either direction.
+    #[must_use]
+    pub const fn changes(&self) -> usize {
+        self.allow_to_deny.count + self.deny_to_allow.count
+    }
+}
+
+/// Replays decision logs against a bundle.
+#[derive(Debug)]
+pub struct DecisionReplayer {
+    engine: RegoEngine,
+    baseline: Option<RegoEngine>,
+    query: String,
+    sample_size: usize,
+}
+
+impl DecisionReplayer {
+    /// Creates a replayer for the given bundle.
+    ///
+    /// Decisions are made by `data.<root>.authz.allow`, where `<root>` is
+    /// the first root of the bundle manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bundle's policies or data cannot be loaded.
+    pub fn new(bundle: &Bundle) -> Result<Self> {
+        Ok(Self {
+            engine: load_bundle(bundle)?,
+            baseline: None,
+            query: default_query(bundle),
+            sample_size: DEFAULT_SAMPLE_SIZE,
+        })
+    }
+
+    /// Sets the query that makes the decision.
+    #[must_use]
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = query.into();
+        self
+    }
+
+    /// Sets a baseline bundle that decides raw inputs with no recorded
+    /// decision.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the baseline's policies or data cannot be loaded.
+    pub fn with_baseline(mut self, bundle: &Bundle) -> Result<Self> {
+        self.baseline = Some(load_bundle(bundle)?);
+        Ok(self)
+    }
+
+    /// Sets the number of samples kept per kind of change.
+    #[must_use]
+    pub const fn with_sample_size(mut self, sample_size: usize) -> Self {
+        self.sample_size = sample_size;
+        self
+    }
+
+    /// Returns the query that makes the decision.
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Replays every decision of a decision log.
+    ///
+    /// Blank lines are skipped. Entries that cannot be parsed or evaluated
+    /// are counted in [`ReplayReport::errors`] and do not stop the replay.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the decision log cannot be read.
+    pub fn replay<R: BufRead>(&mut self, reader: R) -> Result<ReplayReport> {
+        let mut report = ReplayReport::default();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| TestError::ExecutionError {
+                message: format!("Failed to read decision log: {e}"),
+            })?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            report.total += 1;
+            let number = index + 1;
+            if let Err(e) = self.replay_line(&line, number, &mut report) {
+                debug!(line = number, error = %e, "Failed to replay decision");
+                report.errors.record(
+                    ReplayFailure {
+                        line: number,
+                        message: e.to_string(),
+                    },
+                    self.sample_size,
+                );
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn replay_line(&mut self, line: &str, number: usize, report: &mut ReplayReport) -> Result<()> {
+        let Some(record) = ReplayRecord::parse(line)? else {
+            report.unreplayable += 1;
+            return Ok(());
+        };
+
+        let previous = match (record.previous, self.baseline.as_mut()) {
+            (Some(previous), _) => Some(previous),
+            (None, Some(baseline)) => Some(decide(baseline, &self.query, &record.input)?),
+            (None, None) => None,
+        };
+        let current = decide(&mut self.engine, &self.query, &record.input)?;
+
+        let changes = match (previous, current) {
+            (None, _) => {
+                report.unrecorded += 1;
+                return Ok(());
+            }
+            (Some(previous), current) if previous == current => {
+                report.unchanged += 1;
+                return Ok(());
+            }
+            (Some(true), _) => &mut report.allow_to_deny,
+            (Some(false), _) => &mut report.deny_to_allow,
+        };
+        changes.record(
+            ChangedDecision {
+                line: number,
+                input: record.input,
+            },
+            self.sample_size,
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the query of the bundle's entry point.
+fn default_query(bundle: &Bundle) -> String {
+    let root = bundle
+        .manifest
+        .roots
+        .first()
+        .cloned()
+        .unwrap_or_else(|| bundle.name.replace('-', "_"));
+    format!("data.{root}.authz.allow")
+}
+
+/// Creates an engine with the policies and data files of a bundle loaded.
+fn load_bundle(bundle: &Bundle) -> Result<RegoEngine> {
+    let mut engine = RegoEngine::new();
+
+    let mut policies: Vec<_> = bundle.policies.iter().collect();
+    policies.sort_by_key(|(package, _)| *package);
+    for (package, source) in policies {
+        engine
+            .add_policy(package, source)
+            .map_err(|e| TestError::ExecutionError {
+                message: format!("Failed to load policy {package}: {e}"),
+            })?;
+    }
+
+    let mut data_files: Vec<_> = bundle.data_files.iter().collect();
+    data_files.sort_by_key(|(path, _)| *path);
+    for (path, content) in data_files {
+        let extension = Path::new(path).extension();
+        let data: Value = if extension.is_some_and(|ext| ext == "yaml" || ext == "yml") {
+            serde_yaml::from_str(content)?
+        } else {
+            serde_json::from_str(content)?
+        };
+        engine
+            .add_data(data)
+            .map_err(|e| TestError::ExecutionError {
+                message: format!("Failed to load data from {path}: {e}"),
+            })?;
+    }
+
+    Ok(engine)
+}
+
+/// Evaluates a decision, which must be a boolean or undefined.
+fn decide(engine: &mut RegoEngine, query: &str, input: &Value) -> Result<bool> {
+    let result =
+        engine
+            .eval_with_input(query, input.clone())
+            .map_err(|e| TestError::ExecutionError {
+                message: e.to_string(),
+            })?;
+    result.as_bool().ok_or_else(|| TestError::ExecutionError {
+        message: format!("{query} did not evaluate to a boolean"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const POLICY: &str = "package users_service.authz\n\n\
+                          import future.keywords.if\n\n\
+                          default allow := false\n\n\
+                          allow if input.caller.type == \"user\"\n";
+
+    fn bundle(policy: &str) -> Bundle {
+        Bundle::builder("users-service")
+            .version("1.0.0")
+            .add_policy("users_service.authz", policy)
+            .build()
+    }
+
+    #[test]
+    fn test_parse_raw_input() {
+        let record = ReplayRecord::parse(r#"{"caller": {"type": "anonymous"}}"#)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(record.input, json!({"caller": {"type": "anonymous"}}));
+        assert_eq!(record.previous, None);
+    }
+
+    #[test]
+    fn test_parse_event_with_input() {
+        let input = json!({"caller": {"type": "user", "user_id": "alice"}});
+        let event = AuthorizationEvent::allowed("users-service", "getUser", "user")
+            .with_input(input.clone());
+
+        let record = ReplayRecord::parse(&serde_json::to_string(&event).unwrap())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(record.input, input);
+        assert_eq!(record.previous, Some(true));
+    }
+
+    #[test]
+    fn test_parse_event_without_input() {
+        let event = AuthorizationEvent::denied("users-service", "deleteUser", "spiffe", "denied")
+            .with_caller_id("orders-service");
+
+        let record = ReplayRecord::parse(&serde_json::to_string(&event).unwrap()).unwrap();
+
+        assert_eq!(record, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_non_object() {
+        assert!(ReplayRecord::parse("[1, 2]").is_err());
+        assert!(ReplayRecord::parse("not json").is_err());
+    }
+
+    #[test]
+    fn test_default_query() {
+        let replayer = DecisionReplayer::new(&bundle(POLICY)).unwrap();
+        assert_eq!(replayer.query(), "data.users_service.authz.allow");
+
+        let replayer = replayer.with_query("data.authz.allow");
+        assert_eq!(replayer.query(), "data.authz.allow");
+    }
+
+    #[test]
+    fn test_replay_reports_changes() {
+        let log = [
+            r#"{"caller": {"type": "user"}}"#,
+            "",
+            r#"{"caller": {"type": "anonymous"}}"#,
+            "not json",
+        ]
+        .join("\n");
+
+        let mut replayer = DecisionReplayer::new(&bundle(POLICY))
+            .unwrap()
+            .with_baseline(&bundle("package users_service.authz\n\nallow := true\n"))
+            .unwrap()
+            .with_sample_size(1);
+        let report = replayer.replay(log.as_bytes()).unwrap();
+
+        assert_eq!(report.total, 3);
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.allow_to_deny.count, 1);
+        assert_eq!(report.allow_to_deny.samples[0].line, 3);
+        assert_eq!(report.deny_to_allow.count, 0);
+        assert_eq!(report.errors.count, 1);
+        assert_eq!(report.errors.samples[0].line, 4);
+        assert_eq!(report.changes(), 1);
+    }
+
+    #[test]
+    fn test_replay_without_baseline_counts_unrecorded() {
+        let denied = AuthorizationEvent::denied("users-service", "getUser", "user", "denied");
+        let log = format!(
+            "{}\n{}\n{}\n",
+            serde_json::to_string(
+                &denied
+                    .clone()
+                    .with_input(json!({"caller": {"type": "user"}}))
+            )
+            .unwrap(),
+            serde_json::to_string(&denied).unwrap(),
+            r#"{"caller": {"type": "user"}}"#
+        );
+
+        let mut replayer = DecisionReplayer::new(&bundle(POLICY)).unwrap();
+        let report = replayer.replay(log.as_bytes()).unwrap();
+
+        assert_eq!(report.total, 3);
+        assert_eq!(report.deny_to_allow.count, 1);
+        assert_eq!(report.unrecorded, 1);
+        assert_eq!(report.unreplayable, 1);
+    }
+
+    #[test]
+    fn test_sampled_keeps_first_samples() {
+        let mut sampled = Sampled::default();
+        for line in 1..=3 {
+            sampled.record(line, 2);
+        }
+
+        assert_eq!(sampled.count, 3);
+        assert_eq!(sampled.samples, vec![1, 2]);
+    }
+}
@@ -1,20 +1,32 @@
 //! Test result reporting.
 //!
-//! This module provides formatters for test results.
+//! This module provides formatters for test results: a human-readable
+//! console report, JSON, and `JUnit` XML for CI systems.
 
+use std::collections::BTreeMap;
 use std::fmt::Write as FmtWrite;
 use std::io::{self, Write};
 
-use crate::runner::{TestResult, TestResults};
+use crate::coverage::CoverageReport;
+use crate::runner::{TestInterruption, TestResult, TestResults};
 
 /// Trait for reporting test results.
 pub trait Reporter {
-    /// Reports the results of a test run.
+    /// Writes the results of a test run to `out`.
     ///
     /// # Errors
     ///
     /// Returns an IO error if writing to output fails.
-    fn report(&self, results: &TestResults) -> io::Result<()>;
+    fn write_report(&self, results: &TestResults, out: &mut dyn Write) -> io::Result<()>;
+
+    /// Reports the results of a test run to stdout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an IO error if writing to output fails.
+    fn report(&self, results: &TestResults) -> io::Result<()> {
+        self.write_report(results, &mut io::stdout().lock())
+    }
 }
 
 /// Console reporter that outputs results to stdout.
@@ -105,34 +117,120 @@ impl ConsoleReporter {
             "FAILED"
         };
 
-        format!(
-            "\n{}: {} passed, {} failed, {} total ({:.2}s)",
-            status,
+        let mut summary = format!(
+            "\n{status}: {} passed, {} failed",
             results.passed(),
-            results.failed(),
+            results.failed()
+        );
+        if results.timed_out() > 0 {
+            let _ = write!(summary, " ({} timed out)", results.timed_out());
+        }
+        if results.cancelled() > 0 {
+            let _ = write!(summary, " ({} cancelled)", results.cancelled());
+        }
+        let _ = write!(
+            summary,
+            ", {} total ({:.2}s)",
             results.total(),
             results.total_duration.as_secs_f64()
-        )
-    }
-}
-
-impl Reporter for ConsoleReporter {
-    fn report(&self, results: &TestResults) -> io::Result<()> {
-        let mut stdout = io::stdout().lock();
+        );
 
-        writeln!(stdout, "\nTest Results:")?;
-        writeln!(stdout, "{}", "─".repeat(50))?;
+        let fixtures = results.fixtures();
+        if fixtures > 0 {
+            let failed = results.failures().filter(|r| r.is_fixture()).count();
+            let _ = write!(
+                summary,
+                "\nFixtures: {} passed, {failed} failed",
+                fixtures - failed
+            );
+        }
+        summary
+    }
 
-        for result in &results.results {
+    /// Writes a section of test result lines.
+    fn write_section<'a>(
+        &self,
+        title: &str,
+        results: impl IntoIterator<Item = &'a TestResult>,
+        out: &mut dyn Write,
+    ) -> io::Result<()> {
+        writeln!(out, "\n{title}:")?;
+        writeln!(out, "{}", "─".repeat(50))?;
+
+        for result in results {
             if result.passed {
-                writeln!(stdout, "{}", self.format_pass(result))?;
+                writeln!(out, "{}", self.format_pass(result))?;
             } else {
-                writeln!(stdout, "{}", self.format_fail(result))?;
+                writeln!(out, "{}", self.format_fail(result))?;
+            }
+        }
+
+        writeln!(out, "{}", "─".repeat(50))
+    }
+}
+
+impl ConsoleReporter {
+    /// Formats a coverage summary table, listing uncovered rules in verbose
+    /// mode.
+    fn format_coverage(&self, coverage: &CoverageReport) -> String {
+        let mut table = format!(
+            "\nCoverage:\n{:<40} {:>7} {:>7} {:>8}\n{}",
+            "File",
+            "Lines",
+            "Covered",
+            "Percent",
+            "─".repeat(65)
+        );
+
+        for file in coverage.sorted_files() {
+            let _ = write!(
+                table,
+                "\n{:<40} {:>7} {:>7} {:>7.1}%",
+                file.path,
+                file.total_lines,
+                file.covered_lines.len(),
+                file.coverage_percent
+            );
+            if self.verbose {
+                for rule in file.uncovered_rules() {
+                    let _ = write!(
+                        table,
+                        "\n    uncovered rule: {} (line {})",
+                        rule.name, rule.line
+                    );
+                }
             }
         }
 
-        writeln!(stdout, "{}", "─".repeat(50))?;
-        writeln!(stdout, "{}", self.format_summary(results))?;
+        let _ = write!(
+            table,
+            "\n{}\n{:<40} {:>7} {:>7} {:>7.1}%",
+            "─".repeat(65),
+            "Total",
+            coverage.total_lines,
+            coverage.covered_lines,
+            coverage.total_coverage
+        );
+
+        table
+    }
+}
+
+impl Reporter for ConsoleReporter {
+    fn write_report(&self, results: &TestResults, out: &mut dyn Write) -> io::Result<()> {
+        // Fixture tests are listed after the Rego tests, in their own section
+        let (fixtures, rego): (Vec<_>, Vec<_>) =
+            results.results.iter().partition(|r| r.is_fixture());
+
+        self.write_section("Test Results", rego, out)?;
+        if !fixtures.is_empty() {
+            self.write_section("Fixture Results", fixtures, out)?;
+        }
+        writeln!(out, "{}", self.format_summary(results))?;
+
+        if let Some(coverage) = &results.coverage {
+            writeln!(out, "{}", self.format_coverage(coverage))?;
+        }
 
         Ok(())
     }
@@ -161,9 +259,7 @@ impl JsonReporter {
 }
 
 impl Reporter for JsonReporter {
-    fn report(&self, results: &TestResults) -> io::Result<()> {
-        let mut stdout = io::stdout().lock();
-
+    fn write_report(&self, results: &TestResults, out: &mut dyn Write) -> io::Result<()> {
         let json = if self.pretty {
             serde_json::to_string_pretty(results)
         } else {
@@ -171,15 +267,199 @@ impl Reporter for JsonReporter {
         }
         .map_err(io::Error::other)?;
 
-        writeln!(stdout, "{json}")?;
+        writeln!(out, "{json}")?;
 
         Ok(())
     }
 }
 
+/// Suite name used for results that do not belong to a Rego package, such
+/// as fixture tests.
+const DEFAULT_SUITE: &str = "fixtures";
+
+/// `JUnit` XML reporter for CI systems.
+///
+/// Each Rego package becomes a `<testsuite>` and each test a `<testcase>`;
+/// failed tests carry a `<failure>` element with the evaluation error and
+/// the query that failed. Tests that panicked or timed out are reported as
+/// `<error>`s instead, and tests cancelled by the global timeout as
+/// `<skipped>`.
+#[derive(Debug)]
+pub struct JunitReporter {
+    /// Name of the top-level `<testsuites>` element.
+    name: String,
+}
+
+impl Default for JunitReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JunitReporter {
+    /// Creates a new `JUnit` reporter.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            name: "eunomia".to_string(),
+        }
+    }
+
+    /// Sets the name of the top-level `<testsuites>` element.
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Renders the results as a `JUnit` XML document.
+    #[must_use]
+    pub fn to_xml(&self, results: &TestResults) -> String {
+        let mut suites: BTreeMap<&str, Vec<&TestResult>> = BTreeMap::new();
+        for result in &results.results {
+            suites
+                .entry(result.package.as_deref().unwrap_or(DEFAULT_SUITE))
+                .or_default()
+                .push(result);
+        }
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let all: Vec<&TestResult> = results.results.iter().collect();
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"{}\" tests=\"{}\" {} time=\"{:.3}\">",
+            escape_xml(&self.name),
+            results.total(),
+            Self::counts(&all),
+            results.total_duration.as_secs_f64()
+        );
+
+        for (package, cases) in suites {
+            let time: f64 = cases.iter().map(|r| r.duration.as_secs_f64()).sum();
+            let _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" {} time=\"{time:.3}\">",
+                escape_xml(package),
+                cases.len(),
+                Self::counts(&cases)
+            );
+
+            for case in cases {
+                Self::write_case(&mut xml, package, case);
+            }
+
+            xml.push_str("  </testsuite>\n");
+        }
+
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    /// Formats the `failures`, `errors` and `skipped` attributes for a set
+    /// of results.
+    fn counts(cases: &[&TestResult]) -> String {
+        let count = |kind: Outcome| cases.iter().filter(|r| Outcome::of(r) == kind).count();
+        format!(
+            "failures=\"{}\" errors=\"{}\" skipped=\"{}\"",
+            count(Outcome::Failure),
+            count(Outcome::Error),
+            count(Outcome::Skipped)
+        )
+    }
+
+    fn write_case(xml: &mut String, package: &str, case: &TestResult) {
+        let _ = write!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+            escape_xml(&case.name),
+            escape_xml(package),
+            case.duration.as_secs_f64()
+        );
+
+        let message = case.error.as_deref().unwrap_or("Test failed");
+        let (element, kind) = match Outcome::of(case) {
+            Outcome::Passed => {
+                xml.push_str("/>\n");
+                return;
+            }
+            Outcome::Skipped => {
+                let _ = write!(
+                    xml,
+                    ">\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                    escape_xml(message)
+                );
+                return;
+            }
+            Outcome::Error if case.is_timed_out() => ("error", "timeout"),
+            Outcome::Error => ("error", "panic"),
+            Outcome::Failure => ("failure", "failure"),
+        };
+
+        let mut details = Vec::new();
+        if let Some(query) = &case.query {
+            details.push(format!("Query: {query}"));
+        }
+        if let (Some(expected), Some(actual)) = (&case.expected, &case.actual) {
+            details.push(format!("Expected: {expected}"));
+            details.push(format!("Actual: {actual}"));
+        }
+
+        let _ = write!(
+            xml,
+            ">\n      <{element} message=\"{}\" type=\"{kind}\">{}</{element}>\n    </testcase>\n",
+            escape_xml(message),
+            escape_xml(&details.join("\n"))
+        );
+    }
+}
+
+/// How a test result is reported in `JUnit` XML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Passed,
+    Failure,
+    Error,
+    Skipped,
+}
+
+impl Outcome {
+    const fn of(result: &TestResult) -> Self {
+        match result.interrupted {
+            _ if result.passed => Self::Passed,
+            Some(TestInterruption::Cancelled) => Self::Skipped,
+            Some(TestInterruption::Panicked | TestInterruption::TimedOut) => Self::Error,
+            None => Self::Failure,
+        }
+    }
+}
+
+impl Reporter for JunitReporter {
+    fn write_report(&self, results: &TestResults, out: &mut dyn Write) -> io::Result<()> {
+        out.write_all(self.to_xml(results).as_bytes())
+    }
+}
+
+/// Escapes text for use in XML attributes and element content.
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::coverage::FileCoverage;
+    use crate::runner::TestKind;
     use std::time::Duration;
 
     fn sample_results() -> TestResults {
@@ -245,4 +525,162 @@ mod tests {
         assert!(summary.contains("2 passed"));
         assert!(summary.contains("1 failed"));
     }
+
+    #[test]
+    fn test_console_reporter_lists_fixtures_separately() {
+        let mut results = sample_results();
+        results.add(
+            TestResult::fail("authz.yaml::guest_denied", Duration::ZERO, "mismatch")
+                .with_kind(TestKind::Fixture),
+        );
+
+        let mut out = Vec::new();
+        ConsoleReporter::new()
+            .with_colors(false)
+            .write_report(&results, &mut out)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let (rego, fixtures) = out.split_once("Fixture Results:").unwrap();
+        assert!(rego.contains("test_anonymous_denied"), "{out}");
+        assert!(!rego.contains("guest_denied"), "{out}");
+        assert!(fixtures.contains("✗ authz.yaml::guest_denied"), "{out}");
+        assert!(out.contains("2 failed, 4 total"), "{out}");
+        assert!(out.contains("Fixtures: 0 passed, 1 failed"), "{out}");
+    }
+
+    #[test]
+    fn test_console_reporter_format_coverage() {
+        let mut file = FileCoverage::from_lines("authz.rego", [1, 2, 3], [4]);
+        file.collect_rules("default allow := false\n\n\ndeny := true\n");
+        let mut coverage = CoverageReport::new();
+        coverage.add_file(file);
+
+        let table = ConsoleReporter::new()
+            .with_colors(false)
+            .with_verbose(true)
+            .format_coverage(&coverage);
+
+        assert!(table.contains("authz.rego"));
+        assert!(table.contains("75.0%"));
+        assert!(table.contains("uncovered rule: deny (line 4)"));
+        assert!(table.contains("Total"));
+    }
+
+    #[test]
+    fn test_junit_reporter_groups_by_package() {
+        let mut results = TestResults::new();
+        results.add(
+            TestResult::pass("test_admin", Duration::from_millis(5)).with_package("authz_test"),
+        );
+        results.add(
+            TestResult::fail(
+                "test_guest",
+                Duration::from_millis(3),
+                "Test rule evaluated to false",
+            )
+            .with_comparison("true", "false")
+            .with_package("authz_test")
+            .with_query("data.authz_test.test_guest"),
+        );
+        results.add(TestResult::pass("fixture_case", Duration::from_millis(1)));
+        results.total_duration = Duration::from_millis(9);
+
+        let xml = JunitReporter::new().to_xml(&results);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+        assert!(xml.contains("<testsuites name=\"eunomia\" tests=\"3\" failures=\"1\""));
+        assert!(xml.contains("<testsuite name=\"authz_test\" tests=\"2\" failures=\"1\""));
+        assert!(xml.contains("<testsuite name=\"fixtures\" tests=\"1\" failures=\"0\""));
+        assert!(xml.contains("<testcase name=\"test_admin\" classname=\"authz_test\""));
+        assert!(xml.contains(
+            "<failure message=\"Test rule evaluated to false\" type=\"failure\">\
+             Query: data.authz_test.test_guest\nExpected: true\nActual: false</failure>"
+        ));
+    }
+
+    #[test]
+    fn test_junit_reporter_errors_and_skipped() {
+        let mut results = TestResults::new();
+        results.add(TestResult::pass("test_a", Duration::from_millis(2)).with_package("p"));
+        results
+            .add(TestResult::fail("test_b", Duration::from_millis(2), "false").with_package("p"));
+        results.add(
+            TestResult::panicked("test_c", Duration::from_millis(1), "boom").with_package("p"),
+        );
+        results.add(TestResult::timed_out("test_d", Duration::from_secs(1)).with_package("p"));
+        results.add(TestResult::cancelled("test_e", Duration::ZERO).with_package("p"));
+
+        let mut out = Vec::new();
+        JunitReporter::new()
+            .write_report(&results, &mut out)
+            .unwrap();
+        let xml = String::from_utf8(out).unwrap();
+
+        assert!(xml.contains(
+            "<testsuites name=\"eunomia\" tests=\"5\" failures=\"1\" errors=\"2\" skipped=\"1\""
+        ));
+        assert!(xml.contains(
+            "<testsuite name=\"p\" tests=\"5\" failures=\"1\" errors=\"2\" skipped=\"1\""
+        ));
+        assert!(xml.contains("<error message=\"Test panicked: boom\" type=\"panic\">"));
+        assert!(xml.contains("<error message=\"Test timed out\" type=\"timeout\">"));
+        assert!(xml.contains("<skipped message=\"Test cancelled: global timeout expired\"/>"));
+
+        // Every opened element is closed, in order
+        let mut open = Vec::new();
+        for tag in xml.split('<').skip(2) {
+            let tag = tag.split('>').next().unwrap();
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop(), Some(name));
+            } else if !tag.ends_with('/') {
+                open.push(tag.split(' ').next().unwrap());
+            }
+        }
+        assert!(open.is_empty());
+    }
+
+    #[test]
+    fn test_junit_reporter_escapes_names() {
+        let mut results = TestResults::new();
+        results.add(TestResult::fail(
+            "test_\"quoted\" <tag> & 'apos'",
+            Duration::from_millis(1),
+            "expected <true>",
+        ));
+
+        let xml = JunitReporter::new().to_xml(&results);
+
+        assert!(
+            xml.contains(r#"name="test_&quot;quoted&quot; &lt;tag&gt; &amp; &apos;apos&apos;""#)
+        );
+        assert!(xml.contains("message=\"expected &lt;true&gt;\""));
+        assert!(!xml.contains("<tag>"));
+    }
+
+    #[test]
+    fn test_json_reporter_includes_durations() {
+        let mut out = Vec::new();
+        JsonReporter::new()
+            .write_report(&sample_results(), &mut out)
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["results"].as_array().unwrap().len(), 3);
+        assert!(json["results"][0]["duration"].is_object());
+        assert!(json["total_duration"].is_object());
+    }
+
+    #[test]
+    fn test_console_reporter_writes_to_buffer() {
+        let mut out = Vec::new();
+        ConsoleReporter::new()
+            .with_colors(false)
+            .write_report(&sample_results(), &mut out)
+            .unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("test_admin_access"));
+        assert!(text.contains("FAILED"));
+    }
 }
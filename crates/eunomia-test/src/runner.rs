@@ -9,6 +9,27 @@
 //! 1. **Native Rego Tests**: Tests written as `test_*` rules in `*_test.rego` files
 //! 2. **Fixture-Based Tests**: Tests defined in JSON/YAML fixtures
 //!
+//! Tests can be selected by name and package (see [`TestConfig::filter`] and
+//! [`TestConfig::package`]). With [`TestConfig::parallel`] set, or through
+//! [`TestRunner::run_suite_parallel`], packages run on separate threads, each
+//! with its own engine. Results are always sorted by package and name so
+//! reports are stable across runs.
+//!
+//! [`TestConfig::test_timeout`] bounds the time a single test may take and
+//! [`TestConfig::global_timeout`] the time of a whole suite run. Tests that
+//! exceed their timeout are reported as timed out, and tests that had not
+//! finished when the global timeout expired as cancelled.
+//!
+//! Fixture files that declare a `query` and a list of `cases` are run
+//! table-driven: each case is evaluated against the suite's policies and
+//! reported as its own test named `<fixture file>::<case>`. Fixture files
+//! with a `matrix` run a Rego test once per row of a [`TestMatrix`], through
+//! [`TestRunner::run_matrix`].
+//!
+//! [`TestRunner::run_snapshot_tests`] re-evaluates the inputs of a
+//! [`SnapshotStore`] and reports the decisions that changed since they were
+//! recorded, or records the new ones with [`TestConfig::update_snapshots`].
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -25,36 +46,86 @@
 //! println!("Passed: {}, Failed: {}", results.passed(), results.failed());
 //! ```
 
+use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
-use eunomia_compiler::RegoEngine;
+use eunomia_compiler::{PolicyCoverage, RegoEngine};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc as async_mpsc, oneshot};
 use tracing::{debug, info, warn};
 
-use crate::discovery::{DiscoveredFixture, DiscoveredTest, FixtureFormat, TestSuite};
+use crate::coverage::CoverageReport;
+use crate::discovery::{
+    extract_package, DiscoveredFixture, DiscoveredTest, FixtureFormat, TestSuite,
+};
 use crate::error::{Result, TestError};
-use crate::fixtures::{FixtureSet, TestFixture};
+use crate::fixtures::{
+    FixtureCase, FixtureSchema, FixtureSet, FixtureTest, MatrixFixture, SnapshotStore, TestFixture,
+};
+use crate::fuzzing::{default_fuzz_config, prop_input_strategy, PolicyFuzzer, ProptestConfig};
+use crate::test_utils::TestMatrix;
+
+/// Maximum number of evaluator threads a worker leaves running after their
+/// tests timed out before it cancels its remaining tests.
+pub const MAX_ABANDONED_EVALUATORS: usize = 4;
 
 /// Configuration for the test runner.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct TestConfig {
     /// Whether to fail fast on first error.
     pub fail_fast: bool,
     /// Timeout for each test.
+    ///
+    /// Kept for compatibility; only [`test_timeout`](Self::test_timeout) is
+    /// enforced.
+    #[deprecated(note = "use `test_timeout`, which is the enforced per-test timeout")]
     pub timeout: Duration,
-    /// Whether to run tests in parallel.
+    /// Maximum duration of a single test run by [`TestRunner::run_suite`].
+    pub test_timeout: Option<Duration>,
+    /// Maximum duration of a whole [`TestRunner::run_suite`] call.
+    pub global_timeout: Option<Duration>,
+    /// Whether to run packages in parallel.
     pub parallel: bool,
     /// Number of parallel workers.
     pub workers: usize,
+    /// Whether to collect line and rule coverage of policy files.
+    pub coverage: bool,
+    /// Only run tests whose qualified name matches this regex, or contains
+    /// it if it is not a valid regex.
+    pub filter: Option<String>,
+    /// Only run tests in this package or its subpackages.
+    pub package: Option<String>,
+    /// Whether [`TestRunner::run_snapshot_tests`] overwrites the stored
+    /// decisions with the current ones instead of only comparing them.
+    pub update_snapshots: bool,
+    /// Proptest configuration used by [`TestRunner::run_fuzz`].
+    pub proptest: ProptestConfig,
 }
 
 impl Default for TestConfig {
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
             fail_fast: false,
             timeout: Duration::from_secs(30),
+            test_timeout: None,
+            global_timeout: None,
             parallel: false,
             workers: 4,
+            coverage: false,
+            filter: None,
+            package: None,
+            update_snapshots: false,
+            proptest: default_fuzz_config(),
         }
     }
 }
@@ -73,10 +144,27 @@ impl TestConfig {
         self
     }
 
-    /// Sets the test timeout.
+    /// Sets the test timeout, enforcing it as the
+    /// [per-test timeout](Self::with_test_timeout).
     #[must_use]
+    #[allow(deprecated)]
     pub const fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
+        self.test_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum duration of a single test.
+    #[must_use]
+    pub const fn with_test_timeout(mut self, timeout: Duration) -> Self {
+        self.test_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum duration of a whole suite run.
+    #[must_use]
+    pub const fn with_global_timeout(mut self, timeout: Duration) -> Self {
+        self.global_timeout = Some(timeout);
         self
     }
 
@@ -93,6 +181,67 @@ impl TestConfig {
         self.workers = workers;
         self
     }
+
+    /// Enables coverage collection for [`TestRunner::run_suite`].
+    #[must_use]
+    pub const fn with_coverage(mut self, coverage: bool) -> Self {
+        self.coverage = coverage;
+        self
+    }
+
+    /// Sets the test name filter.
+    #[must_use]
+    pub fn with_filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+
+    /// Sets the package filter.
+    #[must_use]
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    /// Sets whether snapshot tests record the current decisions.
+    #[must_use]
+    pub const fn with_update_snapshots(mut self, update: bool) -> Self {
+        self.update_snapshots = update;
+        self
+    }
+
+    /// Sets the proptest configuration used for fuzzing.
+    #[must_use]
+    pub fn with_proptest_config(mut self, config: ProptestConfig) -> Self {
+        self.proptest = config;
+        self
+    }
+
+    /// Sets the number of inputs fuzzed per query.
+    #[must_use]
+    pub const fn with_fuzz_runs(mut self, runs: u32) -> Self {
+        self.proptest.cases = runs;
+        self
+    }
+
+    /// Returns true if a test with this qualified name passes the name filter.
+    #[must_use]
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.filter.as_deref().is_none_or(|filter| {
+            Regex::new(filter).map_or_else(|_| name.contains(filter), |re| re.is_match(name))
+        })
+    }
+
+    /// Returns true if a package passes the package filter.
+    #[must_use]
+    pub fn matches_package(&self, package: &str) -> bool {
+        self.package.as_deref().is_none_or(|filter| {
+            package == filter
+                || package
+                    .strip_prefix(filter)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
 }
 
 /// Result of a single test execution.
@@ -113,6 +262,50 @@ pub struct TestResult {
     /// Actual value.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub actual: Option<String>,
+    /// Rego package the test belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// Query evaluated by the test.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Why the test did not run to completion, if it was interrupted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interrupted: Option<TestInterruption>,
+    /// Whether the test is a Rego test or was defined in a fixture file.
+    #[serde(default, skip_serializing_if = "TestKind::is_rego")]
+    pub kind: TestKind,
+}
+
+/// Where a test was defined.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestKind {
+    /// A `test_` rule in a Rego file.
+    #[default]
+    Rego,
+    /// A fixture or case in a fixture file.
+    Fixture,
+}
+
+impl TestKind {
+    /// Returns true for Rego tests.
+    #[must_use]
+    pub fn is_rego(&self) -> bool {
+        *self == Self::Rego
+    }
+}
+
+/// Reason a test was stopped before it completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestInterruption {
+    /// The test exceeded [`TestConfig::test_timeout`].
+    TimedOut,
+    /// The run exceeded [`TestConfig::global_timeout`] before the test
+    /// completed.
+    Cancelled,
+    /// The test panicked.
+    Panicked,
 }
 
 impl TestResult {
@@ -126,6 +319,10 @@ impl TestResult {
             error: None,
             expected: None,
             actual: None,
+            package: None,
+            query: None,
+            interrupted: None,
+            kind: TestKind::Rego,
         }
     }
 
@@ -139,9 +336,49 @@ impl TestResult {
             error: Some(error.into()),
             expected: None,
             actual: None,
+            package: None,
+            query: None,
+            interrupted: None,
+            kind: TestKind::Rego,
         }
     }
 
+    /// Creates a failing result for a test that exceeded its timeout.
+    #[must_use]
+    pub fn timed_out(name: impl Into<String>, duration: Duration) -> Self {
+        let mut result = Self::fail(name, duration, "Test timed out");
+        result.interrupted = Some(TestInterruption::TimedOut);
+        result
+    }
+
+    /// Creates a failing result for a test cancelled by the global timeout.
+    #[must_use]
+    pub fn cancelled(name: impl Into<String>, duration: Duration) -> Self {
+        let mut result = Self::fail(name, duration, "Test cancelled: global timeout expired");
+        result.interrupted = Some(TestInterruption::Cancelled);
+        result
+    }
+
+    /// Creates a failing result for a test that panicked.
+    #[must_use]
+    pub fn panicked(name: impl Into<String>, duration: Duration, message: &str) -> Self {
+        let mut result = Self::fail(name, duration, format!("Test panicked: {message}"));
+        result.interrupted = Some(TestInterruption::Panicked);
+        result
+    }
+
+    /// Returns true if the test exceeded its timeout.
+    #[must_use]
+    pub fn is_timed_out(&self) -> bool {
+        self.interrupted == Some(TestInterruption::TimedOut)
+    }
+
+    /// Returns true if the test was cancelled by the global timeout.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.interrupted == Some(TestInterruption::Cancelled)
+    }
+
     /// Adds expected/actual values for assertion failures.
     #[must_use]
     pub fn with_comparison(
@@ -153,6 +390,33 @@ impl TestResult {
         self.actual = Some(actual.into());
         self
     }
+
+    /// Sets the Rego package the test belongs to.
+    #[must_use]
+    pub fn with_package(mut self, package: impl Into<String>) -> Self {
+        self.package = Some(package.into());
+        self
+    }
+
+    /// Sets the query evaluated by the test.
+    #[must_use]
+    pub fn with_query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Sets where the test was defined.
+    #[must_use]
+    pub const fn with_kind(mut self, kind: TestKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Returns true if the test was defined in a fixture file.
+    #[must_use]
+    pub fn is_fixture(&self) -> bool {
+        self.kind == TestKind::Fixture
+    }
 }
 
 /// Aggregated results from running multiple tests.
@@ -162,6 +426,9 @@ pub struct TestResults {
     pub results: Vec<TestResult>,
     /// Total duration of test run.
     pub total_duration: Duration,
+    /// Coverage of non-test policy files (if coverage was enabled).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageReport>,
 }
 
 impl TestResults {
@@ -188,6 +455,24 @@ impl TestResults {
         self.results.iter().filter(|r| !r.passed).count()
     }
 
+    /// Returns the number of tests that exceeded their timeout.
+    #[must_use]
+    pub fn timed_out(&self) -> usize {
+        self.results.iter().filter(|r| r.is_timed_out()).count()
+    }
+
+    /// Returns the number of tests cancelled by the global timeout.
+    #[must_use]
+    pub fn cancelled(&self) -> usize {
+        self.results.iter().filter(|r| r.is_cancelled()).count()
+    }
+
+    /// Returns the number of tests defined in fixture files.
+    #[must_use]
+    pub fn fixtures(&self) -> usize {
+        self.results.iter().filter(|r| r.is_fixture()).count()
+    }
+
     /// Returns the total number of tests.
     #[must_use]
     pub const fn total(&self) -> usize {
@@ -210,6 +495,46 @@ impl TestResults {
     pub fn results(&self) -> &[TestResult] {
         &self.results
     }
+
+    /// Sorts results by package, then name.
+    pub fn sort(&mut self) {
+        self.results
+            .sort_by(|a, b| (a.package.as_deref(), &a.name).cmp(&(b.package.as_deref(), &b.name)));
+    }
+}
+
+/// Outcome of checking a [`SnapshotStore`] against the current policies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotResult {
+    /// Snapshot file that was checked.
+    pub file: PathBuf,
+    /// Number of inputs evaluated.
+    pub checked: usize,
+    /// Inputs whose decision differs from the stored one.
+    pub mismatches: Vec<SnapshotMismatch>,
+    /// Whether the stored decisions were overwritten with the current ones.
+    pub updated: bool,
+}
+
+impl SnapshotResult {
+    /// Returns true if no decision changed, or the changes were recorded.
+    #[must_use]
+    pub const fn passed(&self) -> bool {
+        self.updated || self.mismatches.is_empty()
+    }
+}
+
+/// A stored input whose decision changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotMismatch {
+    /// Position of the input in the snapshot file.
+    pub index: usize,
+    /// The input.
+    pub input: serde_json::Value,
+    /// Stored decision.
+    pub expected: bool,
+    /// Decision of the current policies.
+    pub actual: bool,
 }
 
 /// Test runner for executing policy tests.
@@ -246,25 +571,203 @@ impl TestRunner {
     /// Runs all tests in a discovered test suite.
     ///
     /// This method:
-    /// 1. Creates a Rego engine
-    /// 2. Loads all policy files
-    /// 3. Executes each discovered test
-    /// 4. Collects and returns results
+    /// 1. Selects the tests matching [`TestConfig::filter`] and
+    ///    [`TestConfig::package`]
+    /// 2. Creates a Rego engine and loads all policy and data files, once
+    ///    per worker thread if [`TestConfig::parallel`] is set
+    /// 3. Executes each selected test, spreading packages across workers
+    /// 4. Collects and returns results sorted by package and name, with
+    ///    coverage if [`TestConfig::coverage`] is set
+    ///
+    /// In fail-fast mode, workers stop starting new tests once any test
+    /// fails; tests already running on other workers still report.
+    ///
+    /// A test that panics is reported as failed with the panic message, and
+    /// its worker continues on a freshly loaded engine.
+    ///
+    /// If [`TestConfig::test_timeout`] or [`TestConfig::global_timeout`] is
+    /// set, tests are evaluated on a separate thread per worker. A test that
+    /// exceeds a timeout is abandoned on that thread and the worker continues
+    /// on a new one; the coverage collected by abandoned threads is lost.
+    /// Evaluations cannot be interrupted, so an abandoned thread exits once
+    /// its test finishes; a worker with [`MAX_ABANDONED_EVALUATORS`] of them
+    /// still running cancels its remaining tests rather than start more.
     ///
     /// # Errors
     ///
     /// Returns an error if policy loading fails.
     pub fn run_suite(&self, suite: &TestSuite) -> Result<TestResults> {
+        let workers = if self.config.parallel {
+            self.config.workers
+        } else {
+            1
+        };
+        self.run_suite_with_workers(suite, workers)
+    }
+
+    /// Runs all tests in a suite on up to `parallelism` worker threads,
+    /// regardless of [`TestConfig::parallel`] and [`TestConfig::workers`].
+    ///
+    /// Results are identical to [`run_suite`](Self::run_suite): sorted by
+    /// package and name, independent of the order tests finished in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if policy loading fails.
+    pub fn run_suite_parallel(&self, suite: &TestSuite, parallelism: usize) -> Result<TestResults> {
+        self.run_suite_with_workers(suite, parallelism)
+    }
+
+    /// Runs the selected tests of a suite on an engine that already has the
+    /// suite's policy and data files loaded, so nothing is parsed again.
+    ///
+    /// Tests run one at a time, in order, without timeouts or coverage. A
+    /// test that panics is reported as failed and `engine` is replaced with
+    /// one freshly loaded from the suite.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `engine` has to be reloaded and policy loading
+    /// fails.
+    pub fn run_suite_on(&self, engine: &mut RegoEngine, suite: &TestSuite) -> Result<TestResults> {
+        let start = Instant::now();
+        let mut results = TestResults::new();
+
+        for test in suite.tests().iter().filter(|test| {
+            self.config.matches_package(&test.package)
+                && self.config.matches_name(&test.qualified_name)
+        }) {
+            let result = Self::run_test_isolated(engine, test, || Self::load_engine(suite))?;
+            let failed = !result.passed;
+            results.add(result);
+            if self.config.fail_fast && failed {
+                warn!("Stopping early due to fail-fast mode");
+                break;
+            }
+        }
+
+        results.sort();
+        results.total_duration = start.elapsed();
+        Ok(results)
+    }
+
+    /// Runs the selected tests of a suite, spreading packages across at most
+    /// `workers` threads.
+    fn run_suite_with_workers(&self, suite: &TestSuite, workers: usize) -> Result<TestResults> {
         let start = Instant::now();
         let mut results = TestResults::new();
 
-        info!(tests = suite.test_count(), "Running test suite");
+        // Group the selected tests by package
+        let mut packages: BTreeMap<&str, Vec<&DiscoveredTest>> = BTreeMap::new();
+        for test in suite.tests().iter().filter(|test| {
+            self.config.matches_package(&test.package)
+                && self.config.matches_name(&test.qualified_name)
+        }) {
+            packages.entry(&test.package).or_default().push(test);
+        }
+        let packages: Vec<Vec<&DiscoveredTest>> = packages.into_values().collect();
+
+        let workers = workers.clamp(1, packages.len().max(1));
+        info!(
+            tests = packages.iter().map(Vec::len).sum::<usize>(),
+            packages = packages.len(),
+            workers,
+            "Running test suite"
+        );
+
+        // Deal packages round-robin to workers
+        let mut batches: Vec<Vec<&DiscoveredTest>> = vec![Vec::new(); workers];
+        for (i, package) in packages.into_iter().enumerate() {
+            batches[i % workers].extend(package);
+        }
+
+        let stop = AtomicBool::new(false);
+        let deadline = self
+            .config
+            .global_timeout
+            .map(|timeout| tokio::time::Instant::from_std(start + timeout));
+        let sources = (self.config.test_timeout.is_some() || deadline.is_some())
+            .then(|| Arc::new(EngineSources::new(suite, self.config.coverage)));
+        let run_batch = |batch: &[&DiscoveredTest]| {
+            sources.as_ref().map_or_else(
+                || self.run_batch(suite, batch, &stop),
+                |sources| {
+                    let spawn = || Evaluator::spawn(Arc::clone(sources));
+                    block_on(self.run_batch_with_timeouts(spawn, batch, &stop, deadline))
+                },
+            )
+        };
+
+        // Timed batches run their own runtime, so never on the caller's thread
+        let outcomes: Vec<Result<(Vec<TestResult>, Vec<PolicyCoverage>)>> =
+            if workers == 1 && sources.is_none() {
+                batches.iter().map(|batch| run_batch(batch)).collect()
+            } else {
+                std::thread::scope(|scope| {
+                    // Collected so that every batch is spawned before the
+                    // first one is joined
+                    #[allow(clippy::needless_collect)]
+                    let handles: Vec<_> = batches
+                        .iter()
+                        .map(|batch| scope.spawn(|| run_batch(batch)))
+                        .collect();
+                    handles
+                        .into_iter()
+                        .map(|handle| {
+                            handle.join().unwrap_or_else(|_| {
+                                Err(TestError::ExecutionError {
+                                    message: "Test worker panicked".to_string(),
+                                })
+                            })
+                        })
+                        .collect()
+                })
+            };
+
+        let mut coverage = Vec::new();
+        for outcome in outcomes {
+            let (batch_results, batch_coverage) = outcome?;
+            for result in batch_results {
+                results.add(result);
+            }
+            coverage.extend(batch_coverage);
+        }
+        results.sort();
+
+        if self.config.coverage {
+            let policies = merge_coverage(coverage);
+            let report = CoverageReport::from_policies(&policies);
+            debug!(coverage = report.total_coverage, "Collected coverage");
+            results.coverage = Some(report);
+        }
+
+        results.total_duration = start.elapsed();
+        info!(
+            passed = results.passed(),
+            failed = results.failed(),
+            timed_out = results.timed_out(),
+            cancelled = results.cancelled(),
+            duration = ?results.total_duration,
+            "Test suite complete"
+        );
+
+        Ok(results)
+    }
+
+    /// Creates an engine with all policy and data files of a suite loaded.
+    fn load_engine(suite: &TestSuite) -> Result<RegoEngine> {
+        Self::load_files(suite.policy_files(), suite.data_files())
+    }
 
-        // Create a Rego engine and load all policies
+    /// Creates an engine with the given policy and data files loaded.
+    fn load_files<'a>(
+        policy_files: impl IntoIterator<Item = (&'a PathBuf, &'a String)>,
+        data_files: impl IntoIterator<Item = (&'a PathBuf, &'a serde_json::Value)>,
+    ) -> Result<RegoEngine> {
         let mut engine = RegoEngine::new();
 
         // Load all policy files
-        for (path, source) in suite.policy_files() {
+        for (path, source) in policy_files {
             let name = path.to_string_lossy().to_string();
             debug!(file = %name, "Loading policy file");
 
@@ -276,7 +779,7 @@ impl TestRunner {
         }
 
         // Load all data files
-        for (path, data) in suite.data_files() {
+        for (path, data) in data_files {
             debug!(file = %path.display(), "Loading data file");
 
             engine
@@ -286,38 +789,207 @@ impl TestRunner {
                 })?;
         }
 
-        // Run each test
-        for test in suite.tests() {
-            let result = self.run_test(&mut engine, test);
-            let failed = !result.passed;
-            results.add(result);
+        Ok(engine)
+    }
 
-            if self.config.fail_fast && failed {
+    /// Runs a batch of tests on a fresh engine.
+    ///
+    /// Returns the results and, if coverage is enabled, the coverage of the
+    /// non-test policies.
+    fn run_batch(
+        &self,
+        suite: &TestSuite,
+        tests: &[&DiscoveredTest],
+        stop: &AtomicBool,
+    ) -> Result<(Vec<TestResult>, Vec<PolicyCoverage>)> {
+        let mut results = Vec::new();
+        if tests.is_empty() {
+            return Ok((results, Vec::new()));
+        }
+
+        let load = || -> Result<RegoEngine> {
+            let mut engine = Self::load_engine(suite)?;
+            if self.config.coverage {
+                engine.set_enable_coverage(true);
+            }
+            Ok(engine)
+        };
+        let mut engine = load()?;
+
+        for test in tests {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let result = Self::run_test_isolated(&mut engine, test, load)?;
+            if self.config.fail_fast && !result.passed {
                 warn!("Stopping early due to fail-fast mode");
+                stop.store(true, Ordering::Relaxed);
+            }
+            results.push(result);
+        }
+
+        let coverage = if self.config.coverage {
+            Self::collect_coverage(&engine)?
+        } else {
+            Vec::new()
+        };
+
+        Ok((results, coverage))
+    }
+
+    /// Runs a batch of tests on evaluator threads created by `spawn`,
+    /// enforcing the per-test timeout and the global `deadline`.
+    ///
+    /// Returns the results and, if coverage is enabled, the coverage
+    /// collected by the last evaluator.
+    async fn run_batch_with_timeouts(
+        &self,
+        spawn: impl Fn() -> Evaluator,
+        tests: &[&DiscoveredTest],
+        stop: &AtomicBool,
+        deadline: Option<tokio::time::Instant>,
+    ) -> Result<(Vec<TestResult>, Vec<PolicyCoverage>)> {
+        let mut results = Vec::new();
+        if tests.is_empty() {
+            return Ok((results, Vec::new()));
+        }
+
+        let mut evaluator = spawn();
+        let mut abandoned: Vec<JoinHandle<()>> = Vec::new();
+
+        for (i, test) in tests.iter().enumerate() {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+
+            abandoned.retain(|thread| !thread.is_finished());
+            let remaining = deadline
+                .map(|deadline| deadline.saturating_duration_since(tokio::time::Instant::now()));
+            if remaining == Some(Duration::ZERO) || abandoned.len() >= MAX_ABANDONED_EVALUATORS {
+                warn!(
+                    remaining = tests.len() - i,
+                    abandoned = abandoned.len(),
+                    "Cancelling tests"
+                );
+                results.extend(tests[i..].iter().map(|test| {
+                    TestResult::cancelled(&test.name, Duration::ZERO)
+                        .with_package(&test.package)
+                        .with_query(&test.qualified_name)
+                }));
                 break;
             }
+            let (limit, interruption) = match (self.config.test_timeout, remaining) {
+                (Some(timeout), Some(remaining)) if remaining < timeout => {
+                    (remaining, TestInterruption::Cancelled)
+                }
+                (Some(timeout), _) => (timeout, TestInterruption::TimedOut),
+                (None, remaining) => (
+                    remaining.unwrap_or(Duration::MAX),
+                    TestInterruption::Cancelled,
+                ),
+            };
+
+            let start = tokio::time::Instant::now();
+            let received = match evaluator.tests.send((*test).clone()) {
+                Ok(()) => tokio::time::timeout(limit, evaluator.results.recv()).await,
+                Err(_) => Ok(None),
+            };
+            let result = match received {
+                Ok(Some(result)) => result?,
+                Err(_) => {
+                    // The evaluation cannot be interrupted; leave it to finish
+                    // on its own and continue on a new evaluator.
+                    abandoned.push(std::mem::replace(&mut evaluator, spawn()).thread);
+                    let result = if interruption == TestInterruption::TimedOut {
+                        warn!(test = %test.name, timeout = ?limit, "Test timed out");
+                        TestResult::timed_out(&test.name, start.elapsed())
+                    } else {
+                        warn!(test = %test.name, "Global timeout expired, cancelling test");
+                        TestResult::cancelled(&test.name, start.elapsed())
+                    };
+                    result
+                        .with_package(&test.package)
+                        .with_query(&test.qualified_name)
+                }
+                Ok(None) => {
+                    return Err(TestError::ExecutionError {
+                        message: "Test evaluator exited unexpectedly".to_string(),
+                    });
+                }
+            };
+            if self.config.fail_fast && !result.passed {
+                warn!("Stopping early due to fail-fast mode");
+                stop.store(true, Ordering::Relaxed);
+            }
+            results.push(result);
         }
 
-        results.total_duration = start.elapsed();
-        info!(
-            passed = results.passed(),
-            failed = results.failed(),
-            duration = ?results.total_duration,
-            "Test suite complete"
-        );
+        abandoned.retain(|thread| !thread.is_finished());
+        if !abandoned.is_empty() {
+            warn!(
+                running = abandoned.len(),
+                "Timed out tests are still evaluating and will be discarded"
+            );
+        }
 
-        Ok(results)
+        // Closing the test channel makes the evaluator report its coverage
+        drop(evaluator.tests);
+        let coverage = match evaluator.coverage.await {
+            Ok(coverage) => coverage?,
+            Err(_) => Vec::new(),
+        };
+
+        Ok((results, coverage))
+    }
+
+    /// Runs a single discovered test, reporting a panic as a failure and
+    /// replacing `engine` with a freshly loaded one after it.
+    fn run_test_isolated(
+        engine: &mut RegoEngine,
+        test: &DiscoveredTest,
+        load: impl Fn() -> Result<RegoEngine>,
+    ) -> Result<TestResult> {
+        let start = Instant::now();
+        match panic::catch_unwind(AssertUnwindSafe(|| Self::run_test(engine, test))) {
+            Ok(result) => Ok(result),
+            Err(payload) => {
+                let message = panic_message(payload.as_ref());
+                warn!(test = %test.name, panic = %message, "Test panicked");
+                // The engine may be left in an inconsistent state; the
+                // coverage it collected so far is discarded with it.
+                *engine = load()?;
+                Ok(TestResult::panicked(&test.name, start.elapsed(), &message)
+                    .with_package(&test.package)
+                    .with_query(&test.qualified_name))
+            }
+        }
+    }
+
+    /// Returns the coverage of the non-test policies loaded in `engine`.
+    fn collect_coverage(engine: &RegoEngine) -> Result<Vec<PolicyCoverage>> {
+        let policies = engine.coverage().map_err(|e| TestError::ExecutionError {
+            message: format!("Failed to collect coverage: {e}"),
+        })?;
+
+        Ok(policies
+            .into_iter()
+            .filter(|policy| {
+                !engine
+                    .get_policy_info(&policy.file)
+                    .is_some_and(|info| info.is_test)
+            })
+            .collect())
     }
 
     /// Runs a single discovered test.
-    #[allow(clippy::unused_self)]
-    fn run_test(&self, engine: &mut RegoEngine, test: &DiscoveredTest) -> TestResult {
+    fn run_test(engine: &mut RegoEngine, test: &DiscoveredTest) -> TestResult {
         let start = Instant::now();
 
         debug!(test = %test.qualified_name, "Running test");
 
         // Evaluate the test rule
-        match engine.eval_bool(&test.qualified_name) {
+        let result = match engine.eval_bool(&test.qualified_name) {
             Ok(passed) => {
                 let duration = start.elapsed();
                 if passed {
@@ -334,7 +1006,11 @@ impl TestRunner {
                 warn!(test = %test.name, error = %e, "Test execution error");
                 TestResult::fail(&test.name, duration, format!("Evaluation error: {e}"))
             }
-        }
+        };
+
+        result
+            .with_package(&test.package)
+            .with_query(&test.qualified_name)
     }
 
     /// Runs a single test fixture against a policy.
@@ -397,8 +1073,13 @@ impl TestRunner {
         let start = Instant::now();
         let mut results = TestResults::new();
 
-        for fixture in fixtures {
-            let result = self.run_fixture(fixture, policy_source);
+        for fixture in fixtures
+            .iter()
+            .filter(|fixture| self.config.matches_name(&fixture.name))
+        {
+            let result = self
+                .run_fixture(fixture, policy_source)
+                .with_kind(TestKind::Fixture);
             let failed = !result.passed;
             results.add(result);
 
@@ -463,42 +1144,507 @@ impl TestRunner {
         // Load the fixture set
         let fixture_set = match discovered.format {
             FixtureFormat::Json => FixtureSet::from_json_file(&discovered.file)?,
-            FixtureFormat::Yaml => FixtureSet::from_yaml_file(&discovered.file)?,
+            FixtureFormat::Yaml => {
+                let content =
+                    fs::read_to_string(&discovered.file).map_err(|e| TestError::FileReadError {
+                        path: discovered.file.clone(),
+                        source: e,
+                    })?;
+                if FixtureSchema::matches(&content) {
+                    let schema = FixtureSchema::from_yaml(&content)?;
+                    return self.run_fixture_schema(&schema, &discovered.file, suite);
+                }
+                serde_yaml::from_str(&content)?
+            }
         };
 
-        // Find the policy source
-        let policy_source = self.find_policy_for_fixture(discovered, suite)?;
+        let mut results = TestResults::new();
+
+        let package_selected = fixture_set.package.as_deref().map_or_else(
+            || self.config.package.is_none(),
+            |package| self.config.matches_package(package),
+        );
+        if !package_selected {
+            return Ok(results);
+        }
 
-        // Run all fixtures in the set
-        self.run_fixture_set(&fixture_set.fixtures, &policy_source)
-    }
+        if !fixture_set.fixtures.is_empty() {
+            // Find the policy source
+            let policy_source = self.find_policy_for_fixture(discovered, suite)?;
 
-    /// Finds the policy source for a discovered fixture.
-    #[allow(clippy::unused_self)]
-    fn find_policy_for_fixture(
-        &self,
-        discovered: &DiscoveredFixture,
-        suite: &TestSuite,
-    ) -> Result<String> {
-        // First, check if there's an associated test file with a policy
-        if let Some(ref test_file) = discovered.test_file {
-            if let Some(source) = suite.policy_files().get(test_file) {
-                return Ok(source.clone());
+            // Run all fixtures in the set
+            results = self.run_fixture_set(&fixture_set.fixtures, &policy_source)?;
+        }
+
+        if !fixture_set.cases.is_empty() {
+            let file = discovered
+                .file
+                .strip_prefix(suite.root())
+                .unwrap_or(&discovered.file);
+            let prefix = file.to_string_lossy().replace('\\', "/");
+
+            for result in self
+                .run_fixture_cases(&fixture_set, &prefix, suite)?
+                .results()
+            {
+                results.add(result.clone());
             }
         }
 
-        // Try to find a policy file based on the fixture file name
-        // e.g., authz_fixtures.json -> authz.rego
-        let fixture_stem = discovered
-            .file
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("");
+        if let Some(ref matrix) = fixture_set.matrix {
+            let test = Self::find_matrix_test(matrix, suite)?;
+            if self.config.matches_name(&test.qualified_name) {
+                for result in self.run_matrix(suite, test, &matrix.matrix)? {
+                    results.add(result);
+                }
+            }
+        }
 
-        // Remove _fixtures suffix
-        let policy_name = fixture_stem
-            .strip_suffix("_fixtures")
-            .or_else(|| fixture_stem.strip_suffix("_fixture"))
+        Ok(results)
+    }
+
+    /// Runs the tests of a [`FixtureSchema`] file against the `allow` rule of
+    /// its policy, with the suite's policies and data loaded.
+    ///
+    /// `file` is the fixture file, which `policy_file` is relative to. Each
+    /// test is reported as a fixture test named `<file>::<test name>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the policy file cannot be read or has no package,
+    /// or if the suite's policies fail to load.
+    pub fn run_fixture_schema(
+        &self,
+        schema: &FixtureSchema,
+        file: &Path,
+        suite: &TestSuite,
+    ) -> Result<TestResults> {
+        let start = Instant::now();
+        let mut results = TestResults::new();
+
+        let policy_file = file
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(&schema.policy_file);
+        let source = fs::read_to_string(&policy_file).map_err(|e| TestError::FileReadError {
+            path: policy_file.clone(),
+            source: e,
+        })?;
+        let package = extract_package(&source).ok_or_else(|| TestError::FixtureParseError {
+            message: format!("Policy {} has no package", policy_file.display()),
+        })?;
+        if !self.config.matches_package(&package) {
+            return Ok(results);
+        }
+
+        // The policy may live outside the suite's root
+        let mut engine = Self::load_engine(suite)?;
+        if !engine
+            .all_policies()
+            .any(|(_, info)| info.package == package)
+        {
+            let name = policy_file.to_string_lossy();
+            engine
+                .add_policy(&name, &source)
+                .map_err(|e| TestError::ExecutionError {
+                    message: format!("Failed to load policy {name}: {e}"),
+                })?;
+        }
+
+        let prefix = file
+            .strip_prefix(suite.root())
+            .unwrap_or(file)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let query = format!("data.{package}.allow");
+
+        for test in schema.tests.iter().filter(|test| {
+            self.config
+                .matches_name(&format!("{prefix}::{}", test.name))
+        }) {
+            let result = Self::run_fixture_test(engine.clone(), &package, test, &prefix)
+                .with_package(&package)
+                .with_query(&query)
+                .with_kind(TestKind::Fixture);
+            let failed = !result.passed;
+            results.add(result);
+
+            if self.config.fail_fast && failed {
+                warn!("Stopping early due to fail-fast mode");
+                break;
+            }
+        }
+
+        results.total_duration = start.elapsed();
+        Ok(results)
+    }
+
+    /// Evaluates a single test of a [`FixtureSchema`].
+    fn run_fixture_test(
+        mut engine: RegoEngine,
+        package: &str,
+        test: &FixtureTest,
+        prefix: &str,
+    ) -> TestResult {
+        let start = Instant::now();
+        let name = format!("{prefix}::{}", test.name);
+
+        debug!(test = %name, "Running fixture test");
+
+        if let Err(e) = engine.set_input_json(&test.input) {
+            return TestResult::fail(&name, start.elapsed(), format!("Failed to set input: {e}"));
+        }
+
+        let allowed = match engine.eval_bool(&format!("data.{package}.allow")) {
+            Ok(allowed) => allowed,
+            Err(e) => {
+                warn!(test = %name, error = %e, "Test execution error");
+                return TestResult::fail(&name, start.elapsed(), format!("Evaluation error: {e}"));
+            }
+        };
+        if allowed != test.expected_allow {
+            return TestResult::fail(&name, start.elapsed(), "Allow decision mismatch")
+                .with_comparison(test.expected_allow.to_string(), allowed.to_string());
+        }
+
+        if let Some(ref expected) = test.expected_deny_reason {
+            let reason = match engine.eval(&format!("data.{package}.reason")) {
+                Ok(reason) => reason.to_json(),
+                Err(e) => {
+                    return TestResult::fail(
+                        &name,
+                        start.elapsed(),
+                        format!("Evaluation error: {e}"),
+                    )
+                }
+            };
+            if reason.as_str() != Some(expected.as_str()) {
+                return TestResult::fail(&name, start.elapsed(), "Deny reason mismatch")
+                    .with_comparison(
+                        serde_json::Value::from(expected.as_str()).to_string(),
+                        reason.to_string(),
+                    );
+            }
+        }
+
+        TestResult::pass(name, start.elapsed())
+    }
+
+    /// Finds the test a matrix fixture runs.
+    fn find_matrix_test<'a>(
+        matrix: &MatrixFixture,
+        suite: &'a TestSuite,
+    ) -> Result<&'a DiscoveredTest> {
+        let qualified_name = if matrix.test.starts_with("data.") {
+            matrix.test.clone()
+        } else {
+            format!("data.{}", matrix.test)
+        };
+
+        suite
+            .tests()
+            .iter()
+            .find(|test| test.qualified_name == qualified_name)
+            .ok_or_else(|| TestError::FixtureParseError {
+                message: format!("Matrix test {} not found", matrix.test),
+            })
+    }
+
+    /// Runs a test once per row of an input matrix, with the row's input.
+    ///
+    /// Each row is reported as a test named `<test name>[row N]`, counting
+    /// from 0; failures include the row's input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the suite's policies fail to load.
+    pub fn run_matrix(
+        &self,
+        suite: &TestSuite,
+        test: &DiscoveredTest,
+        matrix: &TestMatrix,
+    ) -> Result<Vec<TestResult>> {
+        let engine = Self::load_engine(suite)?;
+        let mut results = Vec::with_capacity(matrix.len());
+
+        for (row, input) in matrix.generate().iter().enumerate() {
+            let mut engine = engine.clone();
+            let mut result = match engine.set_input_json(input) {
+                Ok(()) => Self::run_test(&mut engine, test),
+                Err(e) => TestResult::fail(
+                    &test.name,
+                    Duration::ZERO,
+                    format!("Failed to set input: {e}"),
+                )
+                .with_package(&test.package)
+                .with_query(&test.qualified_name),
+            };
+            result.name = format!("{}[row {row}]", test.name);
+            result.error = result
+                .error
+                .map(|error| format!("{error} (input: {input})"));
+
+            let failed = !result.passed;
+            results.push(result);
+            if self.config.fail_fast && failed {
+                warn!("Stopping early due to fail-fast mode");
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Fuzzes the `allow` rule of every non-test package in a suite with
+    /// generated inputs, checking that it always decides.
+    ///
+    /// Each package is reported as a test named `fuzz`, with the minimal
+    /// failing input in its error. Inputs are generated as configured by
+    /// [`TestConfig::proptest`]; the package filter applies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the suite's policies fail to load.
+    pub fn run_fuzz(&self, suite: &TestSuite) -> Result<Vec<TestResult>> {
+        let mut engine = Self::load_engine(suite)?;
+        let fuzzer = PolicyFuzzer::with_config(self.config.proptest.clone());
+
+        let packages: BTreeSet<String> = engine
+            .all_policies()
+            .map(|(_, info)| info)
+            .filter(|info| !info.is_test && info.rules.iter().any(|rule| rule == "allow"))
+            .filter(|info| self.config.matches_package(&info.package))
+            .map(|info| info.package.clone())
+            .collect();
+
+        let mut results = Vec::with_capacity(packages.len());
+        for package in packages {
+            let query = format!("data.{package}.allow");
+            let start = Instant::now();
+            let result =
+                match fuzzer.assert_never_panics(&mut engine, &query, prop_input_strategy()) {
+                    Ok(()) => TestResult::pass("fuzz", start.elapsed()),
+                    Err(e) => TestResult::fail("fuzz", start.elapsed(), e.to_string()),
+                };
+
+            let failed = !result.passed;
+            results.push(result.with_package(&package).with_query(&query));
+            if self.config.fail_fast && failed {
+                warn!("Stopping early due to fail-fast mode");
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Runs the table-driven cases of a fixture set against a suite's
+    /// policies and data.
+    ///
+    /// Each case is reported as a test named `<prefix>::<case name>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the set has cases but no query, or if the suite's
+    /// policies fail to load.
+    pub fn run_fixture_cases(
+        &self,
+        set: &FixtureSet,
+        prefix: &str,
+        suite: &TestSuite,
+    ) -> Result<TestResults> {
+        let start = Instant::now();
+        let mut results = TestResults::new();
+
+        let query = set
+            .query
+            .as_deref()
+            .ok_or_else(|| TestError::FixtureParseError {
+                message: format!("Fixture {prefix} has cases but no query"),
+            })?;
+        let engine = Self::load_engine(suite)?;
+
+        for case in set.cases.iter().filter(|case| {
+            self.config
+                .matches_name(&format!("{prefix}::{}", case.name))
+        }) {
+            let mut result = Self::run_fixture_case(engine.clone(), query, case, prefix)
+                .with_kind(TestKind::Fixture);
+            if let Some(ref package) = set.package {
+                result = result.with_package(package);
+            }
+            let failed = !result.passed;
+            results.add(result);
+
+            if self.config.fail_fast && failed {
+                warn!("Stopping early due to fail-fast mode");
+                break;
+            }
+        }
+
+        results.total_duration = start.elapsed();
+        Ok(results)
+    }
+
+    /// Evaluates a single table-driven case.
+    fn run_fixture_case(
+        mut engine: RegoEngine,
+        query: &str,
+        case: &FixtureCase,
+        prefix: &str,
+    ) -> TestResult {
+        let start = Instant::now();
+        let name = format!("{prefix}::{}", case.name);
+
+        debug!(test = %name, "Running fixture case");
+
+        if let Some(ref data) = case.data {
+            if let Err(e) = engine.add_data(data.clone()) {
+                return TestResult::fail(
+                    &name,
+                    start.elapsed(),
+                    format!("Failed to load data: {e}"),
+                )
+                .with_query(query);
+            }
+        }
+
+        if let Err(e) = engine.set_input_json(&case.input) {
+            return TestResult::fail(&name, start.elapsed(), format!("Failed to set input: {e}"))
+                .with_query(query);
+        }
+
+        let result = match engine.eval(query) {
+            Ok(actual) => {
+                let duration = start.elapsed();
+                let (matched, actual) = if let serde_json::Value::Bool(expected) = case.expect {
+                    let actual = actual.is_truthy();
+                    (actual == expected, serde_json::Value::Bool(actual))
+                } else {
+                    let actual = actual.to_json();
+                    (json_matches(&case.expect, &actual), actual)
+                };
+
+                if matched {
+                    debug!(test = %name, duration = ?duration, "Test passed");
+                    TestResult::pass(&name, duration)
+                } else {
+                    let mut diff = Vec::new();
+                    json_diff("$", &case.expect, &actual, &mut diff);
+                    info!(test = %name, duration = ?duration, "Test failed - mismatch");
+                    TestResult::fail(
+                        &name,
+                        duration,
+                        format!("Result mismatch:\n  {}", diff.join("\n  ")),
+                    )
+                    .with_comparison(case.expect.to_string(), actual.to_string())
+                }
+            }
+            Err(e) => {
+                let duration = start.elapsed();
+                warn!(test = %name, error = %e, "Test execution error");
+                TestResult::fail(&name, duration, format!("Evaluation error: {e}"))
+            }
+        };
+
+        result.with_query(query)
+    }
+
+    /// Evaluates the inputs of a snapshot store against a suite's policies and
+    /// data, and compares the decisions to the stored ones.
+    ///
+    /// Decisions are the truthiness of the store's query, so an undefined
+    /// result is a deny. With [`TestConfig::update_snapshots`] set, the
+    /// store's decisions are replaced with the current ones and saved; the
+    /// mismatches are still reported, as the decisions that were updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the suite's policies fail to load, an input cannot
+    /// be evaluated, or the updated store cannot be saved.
+    pub fn run_snapshot_tests(
+        &self,
+        suite: &TestSuite,
+        store: &mut SnapshotStore,
+    ) -> Result<SnapshotResult> {
+        debug!(file = %store.path().display(), "Running snapshot tests");
+
+        let engine = Self::load_engine(suite)?;
+        let mut decisions = Vec::with_capacity(store.decisions().len());
+        let mut mismatches = Vec::new();
+
+        for (index, (input, expected)) in store.decisions().iter().enumerate() {
+            let mut engine = engine.clone();
+            let actual = engine
+                .set_input_json(input)
+                .and_then(|()| engine.eval(store.query()))
+                .map_err(|e| TestError::ExecutionError {
+                    message: format!(
+                        "Failed to evaluate snapshot input {index} of {}: {e}",
+                        store.path().display()
+                    ),
+                })?
+                .is_truthy();
+
+            if actual != *expected {
+                mismatches.push(SnapshotMismatch {
+                    index,
+                    input: input.clone(),
+                    expected: *expected,
+                    actual,
+                });
+            }
+            decisions.push((input.clone(), actual));
+        }
+
+        let updated = self.config.update_snapshots;
+        if updated {
+            store.set_decisions(decisions);
+            store.save()?;
+        }
+
+        info!(
+            file = %store.path().display(),
+            checked = store.decisions().len(),
+            mismatches = mismatches.len(),
+            updated,
+            "Snapshot tests complete"
+        );
+
+        Ok(SnapshotResult {
+            file: store.path().to_path_buf(),
+            checked: store.decisions().len(),
+            mismatches,
+            updated,
+        })
+    }
+
+    /// Finds the policy source for a discovered fixture.
+    #[allow(clippy::unused_self)]
+    fn find_policy_for_fixture(
+        &self,
+        discovered: &DiscoveredFixture,
+        suite: &TestSuite,
+    ) -> Result<String> {
+        // First, check if there's an associated test file with a policy
+        if let Some(ref test_file) = discovered.test_file {
+            if let Some(source) = suite.policy_files().get(test_file) {
+                return Ok(source.clone());
+            }
+        }
+
+        // Try to find a policy file based on the fixture file name
+        // e.g., authz_fixtures.json -> authz.rego
+        let fixture_stem = discovered
+            .file
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("");
+
+        // Remove _fixtures suffix
+        let policy_name = fixture_stem
+            .strip_suffix("_fixtures")
+            .or_else(|| fixture_stem.strip_suffix("_fixture"))
             .unwrap_or(fixture_stem);
 
         // Look for matching policy file
@@ -542,18 +1688,21 @@ impl TestRunner {
 
         // Run native Rego tests
         let rego_results = self.run_suite(suite)?;
+        let stopped = self.config.fail_fast && !rego_results.all_passed();
         for result in rego_results.results() {
             combined.add(result.clone());
         }
+        combined.coverage = rego_results.coverage;
 
         // Run fixture-based tests (if any)
-        if !suite.fixtures().is_empty() {
+        if !stopped && !suite.fixtures().is_empty() {
             let fixture_results = self.run_discovered_fixtures(suite)?;
             for result in fixture_results.results() {
                 combined.add(result.clone());
             }
         }
 
+        combined.sort();
         combined.total_duration = start.elapsed();
         Ok(combined)
     }
@@ -571,9 +1720,230 @@ impl Default for TestRunner {
     }
 }
 
+/// Policy and data files of a suite, owned so that evaluator threads can
+/// load their own engines.
+#[derive(Debug)]
+struct EngineSources {
+    policy_files: Vec<(PathBuf, String)>,
+    data_files: Vec<(PathBuf, serde_json::Value)>,
+    coverage: bool,
+}
+
+impl EngineSources {
+    fn new(suite: &TestSuite, coverage: bool) -> Self {
+        Self {
+            policy_files: suite
+                .policy_files()
+                .iter()
+                .map(|(path, source)| (path.clone(), source.clone()))
+                .collect(),
+            data_files: suite
+                .data_files()
+                .iter()
+                .map(|(path, data)| (path.clone(), data.clone()))
+                .collect(),
+            coverage,
+        }
+    }
+
+    /// Creates an engine with all files loaded.
+    fn load(&self) -> Result<RegoEngine> {
+        let mut engine = TestRunner::load_files(
+            self.policy_files
+                .iter()
+                .map(|(path, source)| (path, source)),
+            self.data_files.iter().map(|(path, data)| (path, data)),
+        )?;
+        if self.coverage {
+            engine.set_enable_coverage(true);
+        }
+        Ok(engine)
+    }
+}
+
+/// A thread evaluating tests on its own engine.
+///
+/// The engine cannot be shared across threads, and an evaluation cannot be
+/// interrupted, so a test that exceeds its timeout is abandoned together
+/// with its evaluator, which exits once the evaluation finishes.
+struct Evaluator {
+    /// Tests to evaluate.
+    tests: mpsc::Sender<DiscoveredTest>,
+    /// Results, one per test.
+    results: async_mpsc::UnboundedReceiver<Result<TestResult>>,
+    /// Coverage of the non-test policies, sent once `tests` is closed.
+    coverage: oneshot::Receiver<Result<Vec<PolicyCoverage>>>,
+    /// The evaluating thread.
+    thread: JoinHandle<()>,
+}
+
+impl Evaluator {
+    fn spawn(sources: Arc<EngineSources>) -> Self {
+        let (tests, pending) = mpsc::channel::<DiscoveredTest>();
+        let (results_tx, results) = async_mpsc::unbounded_channel();
+        let (coverage_tx, coverage) = oneshot::channel();
+
+        let thread = std::thread::spawn(move || {
+            let load = || sources.load();
+            let mut engine = match load() {
+                Ok(engine) => engine,
+                Err(e) => {
+                    let _ = results_tx.send(Err(e));
+                    return;
+                }
+            };
+            for test in pending {
+                let result = TestRunner::run_test_isolated(&mut engine, &test, load);
+                let failed = result.is_err();
+                if results_tx.send(result).is_err() || failed {
+                    return;
+                }
+            }
+            if sources.coverage {
+                let _ = coverage_tx.send(TestRunner::collect_coverage(&engine));
+            }
+        });
+
+        Self {
+            tests,
+            results,
+            coverage,
+            thread,
+        }
+    }
+
+    /// Creates an evaluator that reports `results` in order, then never
+    /// reports again, as if its next evaluation hung.
+    #[cfg(test)]
+    fn scripted(results: Vec<TestResult>) -> Self {
+        let (tests, pending) = mpsc::channel::<DiscoveredTest>();
+        let (results_tx, results_rx) = async_mpsc::unbounded_channel();
+        let (coverage_tx, coverage) = oneshot::channel();
+        for result in results {
+            let _ = results_tx.send(Ok(result));
+        }
+        let _ = coverage_tx.send(Ok(Vec::new()));
+
+        let thread = std::thread::spawn(move || {
+            let _results = results_tx;
+            while pending.recv().is_ok() {}
+        });
+
+        Self {
+            tests,
+            results: results_rx,
+            coverage,
+            thread,
+        }
+    }
+}
+
+/// Runs a future to completion on a new single-threaded runtime.
+fn block_on<T>(future: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .map_err(|e| TestError::ExecutionError {
+            message: format!("Failed to create test runtime: {e}"),
+        })?
+        .block_on(future)
+}
+
+/// Extracts the message of a caught panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Merges coverage of the same files collected by different engines: a line
+/// is covered if any engine covered it.
+fn merge_coverage(coverage: Vec<PolicyCoverage>) -> Vec<PolicyCoverage> {
+    let mut merged: BTreeMap<String, PolicyCoverage> = BTreeMap::new();
+    for policy in coverage {
+        match merged.get_mut(&policy.file) {
+            Some(existing) => {
+                existing.covered.extend(policy.covered);
+                existing.not_covered.extend(policy.not_covered);
+            }
+            None => {
+                merged.insert(policy.file.clone(), policy);
+            }
+        }
+    }
+
+    for PolicyCoverage {
+        covered,
+        not_covered,
+        ..
+    } in merged.values_mut()
+    {
+        not_covered.retain(|line| !covered.contains(line));
+    }
+
+    merged.into_values().collect()
+}
+
+/// Compares JSON values, treating numbers as equal when their floating-point
+/// values are (Rego evaluates all numbers as floats).
+fn json_matches(expected: &serde_json::Value, actual: &serde_json::Value) -> bool {
+    use serde_json::Value;
+
+    match (expected, actual) {
+        (Value::Number(e), Value::Number(a)) => e.as_f64() == a.as_f64(),
+        (Value::Array(e), Value::Array(a)) => {
+            e.len() == a.len() && e.iter().zip(a).all(|(e, a)| json_matches(e, a))
+        }
+        (Value::Object(e), Value::Object(a)) => {
+            e.len() == a.len()
+                && e.iter()
+                    .all(|(k, e)| a.get(k).is_some_and(|a| json_matches(e, a)))
+        }
+        _ => expected == actual,
+    }
+}
+
+/// Describes where `actual` differs from `expected`, one line per
+/// difference, using JSON paths rooted at `path`.
+fn json_diff(
+    path: &str,
+    expected: &serde_json::Value,
+    actual: &serde_json::Value,
+    out: &mut Vec<String>,
+) {
+    use serde_json::Value;
+
+    match (expected, actual) {
+        (Value::Object(e), Value::Object(a)) => {
+            for (key, e) in e {
+                match a.get(key) {
+                    Some(a) => json_diff(&format!("{path}.{key}"), e, a, out),
+                    None => out.push(format!("{path}.{key}: missing (expected {e})")),
+                }
+            }
+            for (key, a) in a.iter().filter(|(key, _)| !e.contains_key(*key)) {
+                out.push(format!("{path}.{key}: unexpected {a}"));
+            }
+        }
+        (Value::Array(e), Value::Array(a)) if e.len() == a.len() => {
+            for (i, (e, a)) in e.iter().zip(a).enumerate() {
+                json_diff(&format!("{path}[{i}]"), e, a, out);
+            }
+        }
+        _ if json_matches(expected, actual) => {}
+        _ => out.push(format!("{path}: expected {expected}, got {actual}")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
     use super::*;
+    use crate::discovery::TestDiscovery;
+    use crate::test_utils::MatrixAxis;
     use serde_json::json;
 
     #[test]
@@ -595,7 +1965,11 @@ mod tests {
         assert!(config.fail_fast);
         assert!(config.parallel);
         assert_eq!(config.workers, 8);
-        assert_eq!(config.timeout, Duration::from_secs(60));
+        #[allow(deprecated)]
+        let timeout = config.timeout;
+        assert_eq!(timeout, Duration::from_secs(60));
+        assert_eq!(config.test_timeout, Some(Duration::from_secs(60)));
+        assert_eq!(config.global_timeout, None);
     }
 
     #[test]
@@ -698,4 +2072,670 @@ default allow := false
         assert!(!result.passed, "Should fail due to mismatch");
         assert!(result.error.is_some());
     }
+
+    #[test]
+    fn test_run_suite_collects_coverage() {
+        let policy = r#"package authz
+
+import future.keywords.if
+
+default allow := false
+
+allow if {
+    input.caller.type == "admin"
+}
+
+deny if {
+    input.caller.type == "banned"
+}
+"#;
+        let tests = r#"package authz_test
+
+import data.authz
+import future.keywords.if
+
+test_admin_allowed if {
+    authz.allow with input as {"caller": {"type": "admin"}}
+}
+"#;
+
+        let mut suite = TestSuite::new("/policies");
+        suite.add_policy_file(PathBuf::from("authz.rego"), policy.to_string());
+        suite.add_policy_file(PathBuf::from("authz_test.rego"), tests.to_string());
+        suite.add_test(DiscoveredTest {
+            file: PathBuf::from("authz_test.rego"),
+            package: "authz_test".to_string(),
+            name: "test_admin_allowed".to_string(),
+            qualified_name: "data.authz_test.test_admin_allowed".to_string(),
+            policy_file: Some(PathBuf::from("authz.rego")),
+            description: None,
+        });
+
+        let without = TestRunner::default().run_suite(&suite).unwrap();
+        assert!(without.coverage.is_none());
+
+        let runner = TestRunner::new(TestConfig::new().with_coverage(true));
+        let results = runner.run_suite(&suite).unwrap();
+        assert!(results.all_passed());
+
+        let coverage = results.coverage.unwrap();
+        assert_eq!(coverage.files.len(), 1, "test files are excluded");
+        let file = &coverage.files["authz.rego"];
+        assert!(file.covered_lines.contains(&8));
+        assert!(file.uncovered_lines.contains(&12));
+        assert!(coverage.total_coverage > 0.0 && coverage.total_coverage < 100.0);
+    }
+
+    fn table_suite() -> TestSuite {
+        let policy = r#"package authz
+
+import future.keywords.if
+import future.keywords.in
+
+default allow := false
+
+allow if {
+    "admin" in input.caller.roles
+}
+
+decision := {"allowed": allow, "reason": data.reasons[input.method]}
+"#;
+        let mut suite = TestSuite::new("/policies");
+        suite.add_policy_file(PathBuf::from("authz.rego"), policy.to_string());
+        suite.add_data_file(
+            PathBuf::from("data.json"),
+            json!({"reasons": {"GET": "read", "DELETE": "write"}}),
+        );
+        suite
+    }
+
+    #[test]
+    fn test_run_fixture_cases() {
+        let suite = table_suite();
+        let set = FixtureSet::new()
+            .with_query("data.authz.allow")
+            .add_case(
+                FixtureCase::new("admin_allowed", json!(true))
+                    .with_input(json!({"caller": {"roles": ["admin"]}})),
+            )
+            .add_case(FixtureCase::new("no_input_denied", json!(false)));
+
+        let results = TestRunner::default()
+            .run_fixture_cases(&set, "fixtures/admin.yaml", &suite)
+            .unwrap();
+
+        assert!(results.all_passed(), "{:?}", results.results());
+        assert_eq!(
+            results.results()[0].name,
+            "fixtures/admin.yaml::admin_allowed"
+        );
+        assert_eq!(
+            results.results()[0].query.as_deref(),
+            Some("data.authz.allow")
+        );
+    }
+
+    #[test]
+    fn test_run_fixture_cases_value_mismatch_diff() {
+        let suite = table_suite();
+        let set = FixtureSet::new()
+            .with_query("data.authz.decision")
+            .add_case(
+                FixtureCase::new("read", json!({"allowed": false, "reason": "read"}))
+                    .with_input(json!({"caller": {"roles": []}, "method": "GET"})),
+            )
+            .add_case(
+                FixtureCase::new("write", json!({"allowed": true, "reason": "read"}))
+                    .with_input(json!({"caller": {"roles": []}, "method": "DELETE"})),
+            );
+
+        let results = TestRunner::default()
+            .run_fixture_cases(&set, "cases.json", &suite)
+            .unwrap();
+
+        assert!(results.results()[0].passed);
+        let failure = &results.results()[1];
+        assert!(!failure.passed);
+        let error = failure.error.as_deref().unwrap();
+        assert!(
+            error.contains("$.allowed: expected true, got false"),
+            "{error}"
+        );
+        assert!(
+            error.contains(r#"$.reason: expected "read", got "write""#),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_run_snapshot_tests_detects_changed_decisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("authz.toml");
+        let admin = json!({"caller": {"roles": ["admin"]}});
+        let guest = json!({"caller": {"roles": ["guest"]}});
+
+        // Record the decisions of the current policy
+        let mut store = SnapshotStore::new(&path, "data.authz.allow");
+        store.record(admin.clone(), false);
+        store.record(guest.clone(), false);
+        let updater = TestRunner::new(TestConfig::new().with_update_snapshots(true));
+        let result = updater
+            .run_snapshot_tests(&table_suite(), &mut store)
+            .unwrap();
+        assert!(result.updated && result.passed());
+        assert_eq!(result.mismatches.len(), 1);
+
+        let mut store = SnapshotStore::load(&path).unwrap();
+        assert_eq!(store.decisions(), &[(admin, true), (guest.clone(), false)]);
+        let result = TestRunner::default()
+            .run_snapshot_tests(&table_suite(), &mut store)
+            .unwrap();
+        assert!(result.passed());
+        assert_eq!(result.checked, 2);
+
+        // Let guests in too
+        let mut suite = TestSuite::new("/policies");
+        suite.add_policy_file(
+            PathBuf::from("authz.rego"),
+            "package authz\n\nallow := true\n".to_string(),
+        );
+        let result = TestRunner::default()
+            .run_snapshot_tests(&suite, &mut store)
+            .unwrap();
+
+        assert!(!result.passed());
+        assert_eq!(
+            result.mismatches,
+            vec![SnapshotMismatch {
+                index: 1,
+                input: guest,
+                expected: false,
+                actual: true,
+            }]
+        );
+        // Stored decisions are left alone without update mode
+        assert_eq!(SnapshotStore::load(&path).unwrap(), store);
+    }
+
+    fn matrix_suite() -> (TestSuite, DiscoveredTest) {
+        let mut suite = table_suite();
+        suite.add_policy_file(
+            PathBuf::from("authz_test.rego"),
+            "package authz_test\n\nimport data.authz\n\ntest_allowed := authz.allow\n".to_string(),
+        );
+        let test = DiscoveredTest {
+            file: PathBuf::from("authz_test.rego"),
+            package: "authz_test".to_string(),
+            name: "test_allowed".to_string(),
+            qualified_name: "data.authz_test.test_allowed".to_string(),
+            policy_file: Some(PathBuf::from("authz.rego")),
+            description: None,
+        };
+        suite.add_test(test.clone());
+        (suite, test)
+    }
+
+    #[test]
+    fn test_run_matrix() {
+        let (suite, test) = matrix_suite();
+        let matrix = TestMatrix::new(vec![
+            MatrixAxis::new("caller.roles", vec![json!(["admin"]), json!(["guest"])]),
+            MatrixAxis::new("method", vec![json!("GET"), json!("PUT"), json!("DELETE")]),
+        ]);
+
+        let results = TestRunner::default()
+            .run_matrix(&suite, &test, &matrix)
+            .unwrap();
+
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "test_allowed[row 0]",
+                "test_allowed[row 1]",
+                "test_allowed[row 2]",
+                "test_allowed[row 3]",
+                "test_allowed[row 4]",
+                "test_allowed[row 5]",
+            ]
+        );
+        // Admin rows pass, guest rows fail and show their input
+        assert!(results[..3].iter().all(|r| r.passed));
+        assert!(results[3..].iter().all(|r| !r.passed));
+        let error = results[3].error.as_deref().unwrap();
+        assert!(error.contains(r#""roles":["guest"]"#), "{error}");
+        assert_eq!(results[0].package.as_deref(), Some("authz_test"));
+    }
+
+    #[test]
+    fn test_matrix_fixture_requires_known_test() {
+        let (suite, _) = matrix_suite();
+        let fixture = |test: &str| MatrixFixture {
+            test: test.to_string(),
+            matrix: TestMatrix::new(vec![]),
+        };
+
+        let test = TestRunner::find_matrix_test(&fixture("authz_test.test_allowed"), &suite);
+        assert_eq!(test.unwrap().name, "test_allowed");
+        assert!(TestRunner::find_matrix_test(&fixture("authz_test.test_missing"), &suite).is_err());
+    }
+
+    #[test]
+    fn test_run_fuzz() {
+        let policy = |package: &str, default: &str| {
+            format!(
+                "package {package}\n\nimport future.keywords.if\n\n{default}\n\n\
+                 allow if input.method == \"GET\"\n"
+            )
+        };
+        let mut suite = TestSuite::new(".");
+        suite.add_policy_file(
+            PathBuf::from("complete.rego"),
+            policy("complete", "default allow := false"),
+        );
+        suite.add_policy_file(PathBuf::from("partial.rego"), policy("partial", ""));
+        let runner = TestRunner::new(TestConfig::new().with_fuzz_runs(50));
+
+        let results = runner.run_fuzz(&suite).unwrap();
+        let outcomes: Vec<(Option<&str>, bool)> = results
+            .iter()
+            .map(|r| (r.package.as_deref(), r.passed))
+            .collect();
+        assert_eq!(
+            outcomes,
+            [(Some("complete"), true), (Some("partial"), false)]
+        );
+        assert_eq!(results[1].name, "fuzz");
+        assert!(results[1].error.as_deref().unwrap().contains("undefined"));
+    }
+
+    #[test]
+    fn test_run_all_with_fixture_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = r#"package authz
+
+import future.keywords.if
+
+default allow := false
+
+allow if input.caller.roles[_] == "admin"
+
+reason := "admin" if allow
+
+reason := "no matching permission" if not allow
+"#;
+        let test = "package authz_test\n\nimport data.authz\nimport future.keywords.if\n\n\
+                    test_denied_by_default if not authz.allow\n";
+        let fixture = r"
+description: Authorization fixtures
+policy_file: ../authz.rego
+tests:
+  - name: admin_allowed
+    input: { caller: { roles: [admin] } }
+    expected_allow: true
+  - name: guest_denied
+    input: { caller: { roles: [guest] } }
+    expected_allow: false
+    expected_deny_reason: no matching permission
+  - name: wrong_reason
+    expected_allow: false
+    expected_deny_reason: blocked
+";
+        std::fs::write(dir.path().join("authz.rego"), policy).unwrap();
+        std::fs::write(dir.path().join("authz_test.rego"), test).unwrap();
+        std::fs::create_dir(dir.path().join("fixtures")).unwrap();
+        std::fs::write(dir.path().join("fixtures/authz.yaml"), fixture).unwrap();
+
+        let suite = TestDiscovery::new().discover(dir.path()).unwrap();
+        assert_eq!(suite.test_count(), 1);
+        assert_eq!(suite.fixtures().len(), 1);
+
+        let results = TestRunner::default().run_all(&suite).unwrap();
+        assert_eq!(results.total(), 4);
+        assert_eq!(results.fixtures(), 3);
+        assert_eq!(results.failed(), 1);
+
+        let failure = results.failures().next().unwrap();
+        assert_eq!(failure.name, "fixtures/authz.yaml::wrong_reason");
+        assert_eq!(failure.kind, TestKind::Fixture);
+        assert_eq!(failure.query.as_deref(), Some("data.authz.allow"));
+        assert_eq!(
+            failure.actual.as_deref(),
+            Some(r#""no matching permission""#)
+        );
+
+        let rego = results.results().iter().find(|r| !r.is_fixture()).unwrap();
+        assert_eq!(rego.name, "test_denied_by_default");
+    }
+
+    #[test]
+    fn test_run_fixture_cases_requires_query() {
+        let set = FixtureSet::new().add_case(FixtureCase::new("case", json!(true)));
+        let result = TestRunner::default().run_fixture_cases(&set, "f.json", &table_suite());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_json_diff() {
+        let mut diff = Vec::new();
+        json_diff(
+            "$",
+            &json!({"a": 1, "b": [1, 2], "c": true}),
+            &json!({"a": 1.0, "b": [1, 3], "d": null}),
+            &mut diff,
+        );
+
+        assert_eq!(
+            diff,
+            vec![
+                "$.b[1]: expected 2, got 3",
+                "$.c: missing (expected true)",
+                "$.d: unexpected null",
+            ]
+        );
+    }
+
+    /// Builds a suite with one package per entry, each with tests
+    /// `test_a`..`test_c`; `test_b` in package `failing` fails.
+    fn multi_package_suite(packages: &[&str]) -> TestSuite {
+        let mut suite = TestSuite::new("/policies");
+        for package in packages {
+            let outcome = if *package == "failing" {
+                "false"
+            } else {
+                "true"
+            };
+            let source = format!(
+                "package {package}\n\nimport future.keywords.if\n\n\
+                 test_c if {{ true }}\n\ntest_a if {{ true }}\n\ntest_b if {{ {outcome} }}\n"
+            );
+            let file = PathBuf::from(format!("{package}_test.rego"));
+            suite.add_policy_file(file.clone(), source);
+            for name in ["test_c", "test_a", "test_b"] {
+                suite.add_test(DiscoveredTest {
+                    file: file.clone(),
+                    package: (*package).to_string(),
+                    name: name.to_string(),
+                    qualified_name: format!("data.{package}.{name}"),
+                    policy_file: None,
+                    description: None,
+                });
+            }
+        }
+        suite
+    }
+
+    fn names(results: &TestResults) -> Vec<String> {
+        results
+            .results()
+            .iter()
+            .map(|r| format!("{}.{}", r.package.as_deref().unwrap_or(""), r.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_config_filters() {
+        let config = TestConfig::new().with_filter("admin").with_package("authz");
+
+        assert!(config.matches_name("data.authz_test.test_admin_allowed"));
+        assert!(!config.matches_name("data.authz_test.test_guest_denied"));
+        assert!(config.matches_package("authz"));
+        assert!(config.matches_package("authz.users"));
+        assert!(!config.matches_package("authz_test"));
+
+        let regex = TestConfig::new().with_filter("test_(admin|guest)_");
+        assert!(regex.matches_name("data.authz_test.test_guest_denied"));
+        assert!(!regex.matches_name("data.authz_test.test_other"));
+
+        // Invalid regexes fall back to substring matching
+        let substring = TestConfig::new().with_filter("test_[");
+        assert!(substring.matches_name("data.x.test_[weird]"));
+        assert!(TestConfig::new().matches_name("anything"));
+    }
+
+    #[test]
+    fn test_run_suite_filtering() {
+        let suite = multi_package_suite(&["alpha", "beta"]);
+
+        let config = TestConfig::new().with_filter("test_a$");
+        let results = TestRunner::new(config).run_suite(&suite).unwrap();
+        assert_eq!(names(&results), vec!["alpha.test_a", "beta.test_a"]);
+
+        let config = TestConfig::new().with_package("beta");
+        let results = TestRunner::new(config).run_suite(&suite).unwrap();
+        assert_eq!(
+            names(&results),
+            vec!["beta.test_a", "beta.test_b", "beta.test_c"]
+        );
+    }
+
+    #[test]
+    fn test_run_suite_on_loaded_engine() {
+        let suite = multi_package_suite(&["beta", "alpha"]);
+        let mut engine = TestRunner::load_engine(&suite).unwrap();
+
+        let results = TestRunner::default()
+            .run_suite_on(&mut engine, &suite)
+            .unwrap();
+        assert!(results.all_passed());
+        assert_eq!(
+            names(&results),
+            names(&TestRunner::default().run_suite(&suite).unwrap())
+        );
+
+        let config = TestConfig::new().with_package("alpha");
+        let results = TestRunner::new(config)
+            .run_suite_on(&mut engine, &suite)
+            .unwrap();
+        assert_eq!(results.total(), 3);
+    }
+
+    #[test]
+    fn test_run_suite_parallel_is_deterministic() {
+        let suite = multi_package_suite(&["delta", "alpha", "gamma", "beta", "failing"]);
+
+        let sequential = TestRunner::default().run_suite(&suite).unwrap();
+        let config = TestConfig::new().with_parallel(true).with_workers(3);
+        let parallel = TestRunner::new(config).run_suite(&suite).unwrap();
+
+        assert_eq!(sequential.total(), 15);
+        assert_eq!(names(&sequential), names(&parallel));
+        assert_eq!(names(&sequential)[0], "alpha.test_a");
+        assert_eq!(parallel.failed(), 1);
+    }
+
+    #[test]
+    fn test_run_suite_parallel_preserves_results() {
+        // 20 tests in 5 packages, discovered in reverse package order
+        let mut suite = TestSuite::new("/policies");
+        for package in (0..5).rev().map(|i| format!("pkg_{i}")) {
+            let file = PathBuf::from(format!("{package}_test.rego"));
+            let rules = (0..4)
+                .map(|i| format!("test_{i} if {{ {i} < 4 }}"))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            suite.add_policy_file(
+                file.clone(),
+                format!("package {package}\n\nimport future.keywords.if\n\n{rules}"),
+            );
+            for i in 0..4 {
+                suite.add_test(DiscoveredTest {
+                    file: file.clone(),
+                    package: package.clone(),
+                    name: format!("test_{i}"),
+                    qualified_name: format!("data.{package}.test_{i}"),
+                    policy_file: None,
+                    description: None,
+                });
+            }
+        }
+        let runner = TestRunner::default();
+
+        let sequential = runner.run_suite(&suite).unwrap();
+        assert_eq!(sequential.total(), 20);
+        assert!(sequential.all_passed());
+        assert_eq!(names(&sequential)[0], "pkg_0.test_0");
+
+        for parallelism in [0, 2, 4, 8, 32] {
+            let parallel = runner.run_suite_parallel(&suite, parallelism).unwrap();
+            assert!(parallel.all_passed(), "parallelism {parallelism}");
+            assert_eq!(
+                names(&parallel),
+                names(&sequential),
+                "parallelism {parallelism}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload = panic::catch_unwind(|| panic!("engine exploded")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "engine exploded");
+
+        let payload = panic::catch_unwind(|| panic!("{} failed", "test_a")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "test_a failed");
+    }
+
+    #[test]
+    fn test_results_interruptions() {
+        let mut results = TestResults::new();
+        results.add(TestResult::pass("test_a", Duration::from_millis(1)));
+        results.add(TestResult::timed_out("test_b", Duration::from_secs(1)));
+        results.add(TestResult::cancelled("test_c", Duration::ZERO));
+
+        assert_eq!(results.failed(), 2);
+        assert_eq!(results.timed_out(), 1);
+        assert_eq!(results.cancelled(), 1);
+        assert!(results.results()[1].is_timed_out());
+
+        let json = serde_json::to_value(&results.results()[2]).unwrap();
+        assert_eq!(json["interrupted"], "cancelled");
+        assert!(serde_json::to_value(&results.results()[0])
+            .unwrap()
+            .get("interrupted")
+            .is_none());
+    }
+
+    #[test]
+    fn test_run_suite_global_timeout_cancels_tests() {
+        let suite = multi_package_suite(&["alpha", "beta"]);
+
+        let config = TestConfig::new().with_global_timeout(Duration::ZERO);
+        let results = TestRunner::new(config).run_suite(&suite).unwrap();
+
+        assert_eq!(results.total(), 6);
+        assert_eq!(results.cancelled(), 6);
+        assert_eq!(names(&results)[0], "alpha.test_a");
+    }
+
+    fn discovered(name: &str) -> DiscoveredTest {
+        DiscoveredTest {
+            file: PathBuf::from("batch_test.rego"),
+            package: "batch".to_string(),
+            name: name.to_string(),
+            qualified_name: format!("data.batch.{name}"),
+            policy_file: None,
+            description: None,
+        }
+    }
+
+    /// Returns a spawner handing out scripted evaluators in order.
+    fn scripted(scripts: Vec<Vec<TestResult>>) -> impl Fn() -> Evaluator {
+        let scripts = std::sync::Mutex::new(scripts.into_iter());
+        move || Evaluator::scripted(scripts.lock().unwrap().next().unwrap_or_default())
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_test_timeout() {
+        let tests = [
+            discovered("test_a"),
+            discovered("test_slow"),
+            discovered("test_b"),
+        ];
+        let batch: Vec<_> = tests.iter().collect();
+        let spawn = scripted(vec![
+            vec![TestResult::pass("test_a", Duration::ZERO)],
+            vec![TestResult::pass("test_b", Duration::ZERO)],
+        ]);
+
+        let config = TestConfig::new().with_test_timeout(Duration::from_millis(100));
+        let (results, _) = TestRunner::new(config)
+            .run_batch_with_timeouts(spawn, &batch, &AtomicBool::new(false), None)
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[1].is_timed_out());
+        assert_eq!(results[1].duration, Duration::from_millis(100));
+        // Tests after the timed out one still run on a new evaluator
+        assert!(results[0].passed);
+        assert!(results[2].passed);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_global_deadline_before_test_timeout_cancels() {
+        let tests = [discovered("test_slow"), discovered("test_a")];
+        let batch: Vec<_> = tests.iter().collect();
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(50);
+
+        let config = TestConfig::new().with_test_timeout(Duration::from_millis(100));
+        let (results, _) = TestRunner::new(config)
+            .run_batch_with_timeouts(
+                scripted(Vec::new()),
+                &batch,
+                &AtomicBool::new(false),
+                Some(deadline),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(TestResult::is_cancelled));
+        assert_eq!(results[0].duration, Duration::from_millis(50));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_batch_cancels_after_too_many_abandoned_evaluators() {
+        let tests: Vec<_> = (0..=MAX_ABANDONED_EVALUATORS)
+            .map(|i| discovered(&format!("test_{i}")))
+            .collect();
+        let batch: Vec<_> = tests.iter().collect();
+        let (release, hold) = mpsc::channel::<()>();
+        let hold = Arc::new(std::sync::Mutex::new(hold));
+        let spawn = move || {
+            let mut evaluator = Evaluator::scripted(Vec::new());
+            let hold = Arc::clone(&hold);
+            // Keep the evaluations running until the test releases them
+            evaluator.thread = std::thread::spawn(move || {
+                let _ = hold.lock().unwrap().recv();
+            });
+            evaluator
+        };
+
+        let config = TestConfig::new().with_test_timeout(Duration::from_millis(100));
+        let (results, _) = TestRunner::new(config)
+            .run_batch_with_timeouts(spawn, &batch, &AtomicBool::new(false), None)
+            .await
+            .unwrap();
+        drop(release);
+
+        let timed_out = results.iter().filter(|r| r.is_timed_out()).count();
+        assert_eq!(timed_out, MAX_ABANDONED_EVALUATORS);
+        assert!(results.last().unwrap().is_cancelled());
+    }
+
+    #[test]
+    fn test_run_suite_fail_fast() {
+        let suite = multi_package_suite(&["failing", "zeta"]);
+
+        let config = TestConfig::new().with_fail_fast(true);
+        let results = TestRunner::new(config).run_suite(&suite).unwrap();
+
+        // `failing` runs first and its failure stops the run before `zeta`
+        assert_eq!(
+            names(&results),
+            vec!["failing.test_a", "failing.test_b", "failing.test_c"]
+        );
+        assert_eq!(results.failed(), 1);
+    }
 }
@@ -7,6 +7,7 @@
 //!
 //! The utilities are organized into:
 //! - [`InputBuilder`] - Fluent builder for policy input
+//! - [`TestMatrix`] - Input combinations for running a test once per row
 //! - Assertion helpers for common test patterns
 //! - Result matchers for test outcomes
 //!
@@ -23,11 +24,14 @@
 //!     .method("DELETE")
 //!     .path("/users/user-123")
 //!     .service("users-service")
+//!     .resource_owner("user-123")
 //!     .build();
 //! ```
 
+use chrono::{DateTime, SecondsFormat, Utc};
 use eunomia_core::CallerIdentity;
-use serde_json::{json, Value};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 
 /// Fluent builder for constructing policy input JSON.
@@ -54,12 +58,16 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, Default)]
 pub struct InputBuilder {
     caller: Option<Value>,
+    caller_claims: Map<String, Value>,
     operation_id: Option<String>,
     method: Option<String>,
     path: Option<String>,
     service: Option<String>,
     headers: HashMap<String, String>,
     context: HashMap<String, Value>,
+    resource: Map<String, Value>,
+    timestamp: Option<DateTime<Utc>>,
+    request_id: Option<String>,
     environment: Option<String>,
 }
 
@@ -86,6 +94,13 @@ impl InputBuilder {
         self
     }
 
+    /// Adds a custom claim to the caller, under `caller.claims`.
+    #[must_use]
+    pub fn caller_claim(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.caller_claims.insert(key.into(), value.into());
+        self
+    }
+
     /// Sets the operation ID (from Themis contract).
     #[must_use]
     pub fn operation(mut self, operation_id: impl Into<String>) -> Self {
@@ -121,6 +136,13 @@ impl InputBuilder {
         self
     }
 
+    /// Adds a context value (for extracted parameters such as `tenant_id`).
+    #[must_use]
+    pub fn context(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.context.insert(key.into(), value.into());
+        self
+    }
+
     /// Adds a context value (for resource attributes, extracted parameters).
     #[must_use]
     pub fn context_value(mut self, key: impl Into<String>, value: Value) -> Self {
@@ -135,6 +157,33 @@ impl InputBuilder {
         self
     }
 
+    /// Adds an attribute of the accessed resource, under `resource`.
+    #[must_use]
+    pub fn resource(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.resource.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets the owner of the accessed resource (`resource.owner_id`).
+    #[must_use]
+    pub fn resource_owner(self, owner_id: impl Into<String>) -> Self {
+        self.resource("owner_id", owner_id.into())
+    }
+
+    /// Sets the request time, serialized as an RFC 3339 `timestamp`.
+    #[must_use]
+    pub const fn time(mut self, time: DateTime<Utc>) -> Self {
+        self.timestamp = Some(time);
+        self
+    }
+
+    /// Sets the request ID.
+    #[must_use]
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
     /// Sets the environment (e.g., "production", "staging").
     #[must_use]
     pub fn environment(mut self, env: impl Into<String>) -> Self {
@@ -153,6 +202,9 @@ impl InputBuilder {
             // Default to anonymous
             input["caller"] = json!({"type": "anonymous"});
         }
+        if !self.caller_claims.is_empty() {
+            input["caller"]["claims"] = Value::Object(self.caller_claims);
+        }
 
         if let Some(op) = self.operation_id {
             input["operation_id"] = json!(op);
@@ -178,6 +230,18 @@ impl InputBuilder {
             input["context"] = json!(self.context);
         }
 
+        if !self.resource.is_empty() {
+            input["resource"] = Value::Object(self.resource);
+        }
+
+        if let Some(timestamp) = self.timestamp {
+            input["timestamp"] = json!(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+        }
+
+        if let Some(request_id) = self.request_id {
+            input["request_id"] = json!(request_id);
+        }
+
         if let Some(env) = self.environment {
             input["environment"] = json!(env);
         } else {
@@ -188,6 +252,125 @@ impl InputBuilder {
     }
 }
 
+/// A set of values for one input field of a [`TestMatrix`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatrixAxis {
+    /// Dotted path of the input field, e.g. `caller.type`.
+    pub name: String,
+    /// Values the field takes.
+    pub values: Vec<Value>,
+}
+
+impl MatrixAxis {
+    /// Creates an axis setting the field at `name` to each of `values`.
+    #[must_use]
+    pub fn new(name: impl Into<String>, values: Vec<Value>) -> Self {
+        Self {
+            name: name.into(),
+            values,
+        }
+    }
+}
+
+/// Combinations of input values to run a single test with.
+///
+/// Every combination of one value per axis is merged into the `base` input,
+/// giving one input per row. Rows are ordered with the last axis varying
+/// fastest.
+///
+/// # Examples
+///
+/// ```rust
+/// use eunomia_test::{MatrixAxis, TestMatrix};
+/// use serde_json::json;
+///
+/// let matrix = TestMatrix::new(vec![
+///     MatrixAxis::new("caller.type", vec![json!("user"), json!("service")]),
+///     MatrixAxis::new("operation_id", vec![json!("getUser"), json!("listUsers")]),
+/// ])
+/// .with_base(json!({"method": "GET"}));
+///
+/// let inputs = matrix.generate();
+/// assert_eq!(inputs.len(), 4);
+/// assert_eq!(
+///     inputs[1],
+///     json!({"method": "GET", "caller": {"type": "user"}, "operation_id": "listUsers"})
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestMatrix {
+    /// Input template the combinations are merged into.
+    #[serde(default = "empty_input")]
+    pub base: Value,
+    /// Axes of the matrix.
+    pub axes: Vec<MatrixAxis>,
+}
+
+fn empty_input() -> Value {
+    Value::Object(Map::new())
+}
+
+impl TestMatrix {
+    /// Creates a matrix over `axes` with an empty base input.
+    #[must_use]
+    pub fn new(axes: Vec<MatrixAxis>) -> Self {
+        Self {
+            base: empty_input(),
+            axes,
+        }
+    }
+
+    /// Sets the input template the combinations are merged into.
+    #[must_use]
+    pub fn with_base(mut self, base: Value) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Returns the number of rows, the product of the axis sizes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.axes.iter().map(|axis| axis.values.len()).product()
+    }
+
+    /// Returns true if some axis has no values, so there are no rows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Generates the input of every row.
+    #[must_use]
+    pub fn generate(&self) -> Vec<Value> {
+        let mut inputs = vec![self.base.clone()];
+        for axis in &self.axes {
+            inputs = inputs
+                .iter()
+                .flat_map(|input| {
+                    axis.values.iter().map(move |value| {
+                        let mut input = input.clone();
+                        set_path(&mut input, &axis.name, value.clone());
+                        input
+                    })
+                })
+                .collect();
+        }
+        inputs
+    }
+}
+
+/// Sets the field at a dotted path, creating objects along the way.
+fn set_path(target: &mut Value, path: &str, value: Value) {
+    let mut current = target;
+    for key in path.split('.') {
+        if !current.is_object() {
+            *current = empty_input();
+        }
+        current = &mut current[key];
+    }
+    *current = value;
+}
+
 /// Asserts that a test result indicates the request was allowed.
 ///
 /// # Panics
@@ -382,6 +565,70 @@ mod tests {
         assert_eq!(input["caller"]["type"], "api_key");
     }
 
+    #[test]
+    fn test_input_builder_resource_context_and_time() {
+        let time = DateTime::parse_from_rfc3339("2026-01-05T10:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let input = InputBuilder::new()
+            .caller(MockUser::new("user-1").with_tenant("acme").build())
+            .caller_claim("department", "finance")
+            .operation("getDocument")
+            .resource_owner("user-1")
+            .resource("status", "draft")
+            .context("tenant_id", "acme")
+            .header("x-request-source", "mobile")
+            .time(time)
+            .build();
+
+        assert_eq!(
+            input["resource"],
+            json!({"owner_id": "user-1", "status": "draft"})
+        );
+        assert_eq!(input["context"]["tenant_id"], "acme");
+        assert_eq!(input["headers"]["x-request-source"], "mobile");
+        assert_eq!(input["timestamp"], "2026-01-05T10:30:00Z");
+        assert_eq!(input["caller"]["claims"]["department"], "finance");
+        assert_eq!(input["caller"]["tenant_id"], "acme");
+    }
+
+    #[test]
+    fn test_input_builder_round_trips_through_policy_input() {
+        let callers = [
+            MockUser::new("auditor-1").with_roles(["auditor"]).build(),
+            MockSpiffe::new("billing")
+                .with_trust_domain("prod.example.com")
+                .build(),
+            MockApiKey::new("ci-key")
+                .with_scopes(["read:orders", "write:orders"])
+                .build(),
+        ];
+
+        for caller in callers {
+            let input = InputBuilder::new()
+                .caller(caller.clone())
+                .service("orders-service")
+                .operation("updateOrder")
+                .method("PUT")
+                .path("/orders/42")
+                .header("content-type", "application/json")
+                .resource_owner("user-7")
+                .context("tenant_id", "acme")
+                .time(Utc::now())
+                .request_id("01234567-89ab-cdef-0123-456789abcdef")
+                .build();
+
+            let parsed: eunomia_core::PolicyInput = serde_json::from_value(input).unwrap();
+            assert_eq!(
+                serde_json::to_value(&parsed.caller).unwrap(),
+                serde_json::to_value(&caller).unwrap()
+            );
+            assert_eq!(parsed.service, "orders-service");
+            assert_eq!(parsed.operation_id, "updateOrder");
+            assert_eq!(parsed.headers["content-type"], "application/json");
+        }
+    }
+
     #[test]
     fn test_simple_allow_policy() {
         let policy = simple_allow_policy("admin");
@@ -400,4 +647,47 @@ mod tests {
         let policy = scope_based_policy("read:users");
         assert!(policy.contains(r#""read:users" in input.caller.scopes"#));
     }
+
+    #[test]
+    fn test_matrix_cartesian_product() {
+        let matrix = TestMatrix::new(vec![
+            MatrixAxis::new("caller.type", vec![json!("user"), json!("service")]),
+            MatrixAxis::new(
+                "operation_id",
+                vec![json!("getUser"), json!("listUsers"), json!("deleteUser")],
+            ),
+        ])
+        .with_base(json!({"caller": {"id": "u-1"}, "method": "GET"}));
+
+        let inputs = matrix.generate();
+        assert_eq!(matrix.len(), 6);
+        assert_eq!(inputs.len(), 6);
+        for (i, input) in inputs.iter().enumerate() {
+            assert!(!inputs[..i].contains(input), "duplicate row {i}");
+            assert_eq!(input["caller"]["id"], "u-1");
+            assert_eq!(input["method"], "GET");
+        }
+        assert_eq!(inputs[0]["caller"]["type"], "user");
+        assert_eq!(inputs[0]["operation_id"], "getUser");
+        assert_eq!(inputs[5]["caller"]["type"], "service");
+        assert_eq!(inputs[5]["operation_id"], "deleteUser");
+    }
+
+    #[test]
+    fn test_matrix_edge_cases() {
+        // No axes: the base input is the only row
+        assert_eq!(TestMatrix::new(vec![]).generate(), vec![json!({})]);
+
+        let matrix = TestMatrix::new(vec![
+            MatrixAxis::new("a", vec![json!(1)]),
+            MatrixAxis::new("b", vec![]),
+        ]);
+        assert!(matrix.is_empty());
+        assert!(matrix.generate().is_empty());
+
+        // Non-object fields on the path are replaced
+        let matrix = TestMatrix::new(vec![MatrixAxis::new("caller.type", vec![json!("user")])])
+            .with_base(json!({"caller": "anonymous"}));
+        assert_eq!(matrix.generate(), vec![json!({"caller": {"type": "user"}})]);
+    }
 }